@@ -0,0 +1,68 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use miden_lib::transaction::{KernelVersion, ToTransactionKernelInputs};
+use mock::{
+    mock::{account::MockAccountType, notes::AssetPreservationStatus, transaction::mock_inputs},
+    prepare_transaction,
+};
+
+// ALLOCATION COUNTER
+// ================================================================================================
+
+/// A global allocator which counts the number of allocations and the number of allocated bytes.
+struct CountingAllocator;
+
+static NUM_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static NUM_ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        NUM_ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        NUM_ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// BENCHMARKS
+// ================================================================================================
+
+fn build_kernel_inputs(c: &mut Criterion) {
+    let tx_inputs =
+        mock_inputs(MockAccountType::StandardExisting, AssetPreservationStatus::Preserved);
+    let transaction = prepare_transaction(tx_inputs, None, "begin push.0 drop end", None);
+    let kernel = KernelVersion::current();
+
+    // report the allocation volume of building kernel inputs for a single transaction
+    let allocations_before = NUM_ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes_before = NUM_ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let _ = transaction.get_kernel_inputs(&kernel);
+    println!(
+        "Building kernel inputs: {} allocations, {} bytes allocated",
+        NUM_ALLOCATIONS.load(Ordering::Relaxed) - allocations_before,
+        NUM_ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes_before,
+    );
+
+    c.bench_function("Build transaction kernel inputs", |bench| {
+        bench.iter(|| transaction.get_kernel_inputs(&kernel))
+    });
+}
+
+criterion_group!(kernel_inputs, build_kernel_inputs);
+criterion_main!(kernel_inputs);
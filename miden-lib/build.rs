@@ -6,9 +6,12 @@ use std::{
 };
 
 use assembly::{
-    ast::{AstSerdeOptions, ProgramAst},
-    LibraryNamespace, MaslLibrary, Version,
+    ast::{AstSerdeOptions, CodeBody, ModuleAst, ProgramAst},
+    crypto::hash::RpoDigest,
+    utils::{ByteWriter, Serializable},
+    Assembler, AssemblyContext, LibraryNamespace, MaslLibrary, Version,
 };
+use miden_stdlib::StdLibrary;
 
 // CONSTANTS
 // ================================================================================================
@@ -18,6 +21,7 @@ const ASM_DIR: &str = "asm";
 const ASM_MIDEN_DIR: &str = "miden";
 const ASM_NOTE_SCRIPTS_DIR: &str = "note_scripts";
 const ASM_KERNELS_DIR: &str = "kernels/transaction";
+const KERNEL_SOURCE_MAP_FILE: &str = "kernels/transaction.srcmap";
 
 // PRE-PROCESSING
 // ================================================================================================
@@ -54,6 +58,11 @@ fn main() -> io::Result<()> {
         &target_dir.join(ASM_NOTE_SCRIPTS_DIR),
     )?;
 
+    // build the kernel source map if requested
+    if env::var("CARGO_FEATURE_SOURCE_MAP").is_ok() {
+        build_kernel_source_map(&source_dir, &target_dir.join(KERNEL_SOURCE_MAP_FILE))?;
+    }
+
     Ok(())
 }
 
@@ -157,6 +166,162 @@ fn compile_kernels(source_dir: &Path, target_dir: &Path) -> io::Result<()> {
     Ok(())
 }
 
+// BUILD KERNEL SOURCE MAP
+// ================================================================================================
+
+/// Builds a source map for the transaction kernel and writes it into the specified file.
+///
+/// The source map contains an entry for every procedure of the kernel executable program, of the
+/// kernel API, and of the miden library. Each entry consists of the MAST root of the procedure,
+/// the fully-qualified name of the procedure, the path of the MASM file defining it (relative to
+/// the `asm` directory), the line at which the procedure starts, and the lines of all top-level
+/// operations of the procedure body.
+fn build_kernel_source_map(source_dir: &Path, target_file: &Path) -> io::Result<()> {
+    let mut entries = Vec::new();
+
+    // the procedures are compiled with the same libraries and kernel as the transaction kernel
+    let api_file = Path::new(ASM_KERNELS_DIR).join("api.masm");
+    let api_source = fs::read_to_string(source_dir.join(&api_file))?;
+    let ns = LibraryNamespace::try_from("miden".to_string()).expect("invalid base namespace");
+    let version = Version::try_from(env!("CARGO_PKG_VERSION")).expect("invalid cargo version");
+    let miden_lib = MaslLibrary::read_from_dir(source_dir.join(ASM_MIDEN_DIR), ns, true, version)?;
+    let assembler = Assembler::default()
+        .with_library(&miden_lib)
+        .and_then(|assembler| assembler.with_library(&StdLibrary::default()))
+        .and_then(|assembler| assembler.with_kernel(&api_source))
+        .map_err(assembly_error)?;
+
+    // kernel executable program; its procedures are compiled without the program body
+    let main_file = Path::new(ASM_KERNELS_DIR).join("main.masm");
+    let main_source = fs::read_to_string(source_dir.join(&main_file))?;
+    let main_ast = ProgramAst::parse(&main_source)?;
+    let main_root = assembler.compile(&main_source).map_err(assembly_error)?.hash();
+    let main_lines = body_lines(main_ast.body());
+    let main_line = main_lines.first().copied().unwrap_or(1);
+    entries.push((main_root, "#exec::main".to_string(), main_file.clone(), main_line, main_lines));
+    let procs_source = main_source
+        .lines()
+        .take_while(|line| !line.starts_with("begin"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    for proc in main_ast.procedures() {
+        let root = procedure_root(&assembler, &procs_source, proc.start.line(), false)?;
+        let name = format!("#exec::{}", proc.name.as_ref());
+        entries.push((root, name, main_file.clone(), proc.start.line(), body_lines(&proc.body)));
+    }
+
+    // kernel API
+    let api_ast = ModuleAst::parse(&api_source)?;
+    for proc in api_ast.procs() {
+        let root = procedure_root(&assembler, &api_source, proc.start.line(), true)?;
+        let name = format!("#sys::{}", proc.name.as_ref());
+        entries.push((root, name, api_file.clone(), proc.start.line(), body_lines(&proc.body)));
+    }
+
+    // miden library
+    let mut todo = vec![source_dir.join(ASM_MIDEN_DIR)];
+    while let Some(dir) = todo.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                todo.push(path);
+                continue;
+            } else if !is_masm_file(&path)? {
+                continue;
+            }
+
+            let file = path.strip_prefix(source_dir).expect("file is in the source directory");
+            let module_path = file
+                .with_extension("")
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("::");
+            let module_source = fs::read_to_string(&path)?;
+            let module_ast = ModuleAst::parse(&module_source)?;
+            for proc in module_ast.procs() {
+                let root = procedure_root(&assembler, &module_source, proc.start.line(), false)?;
+                let name = format!("{module_path}::{}", proc.name.as_ref());
+                let (file, line) = (file.to_path_buf(), proc.start.line());
+                entries.push((root, name, file, line, body_lines(&proc.body)));
+            }
+        }
+    }
+    entries.sort_by(|a, b| a.1.cmp(&b.1));
+
+    // serialize the entries
+    let mut bytes = Vec::new();
+    bytes.write_u32(entries.len() as u32);
+    for (root, name, file, line, op_lines) in entries {
+        let file = file.to_string_lossy().replace('\\', "/");
+        root.write_into(&mut bytes);
+        bytes.write_u32(name.len() as u32);
+        bytes.write_bytes(name.as_bytes());
+        bytes.write_u32(file.len() as u32);
+        bytes.write_bytes(file.as_bytes());
+        bytes.write_u32(line);
+        bytes.write_u32(op_lines.len() as u32);
+        for op_line in op_lines {
+            bytes.write_u32(op_line);
+        }
+    }
+
+    if let Some(parent) = target_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(target_file, bytes)
+}
+
+/// Returns the source lines of the top-level operations of the provided code body.
+fn body_lines(body: &CodeBody) -> Vec<u32> {
+    body.source_locations().iter().map(|location| location.line()).collect()
+}
+
+/// Returns the MAST root of the procedure declared at the specified line of the module source.
+///
+/// The module is compiled with the procedure as its only export: local procedures do not have
+/// their roots reported by the assembler, and thus every other procedure is made local, and
+/// re-exports of procedures defined in other modules are dropped.
+fn procedure_root(
+    assembler: &Assembler,
+    module_source: &str,
+    line: u32,
+    is_kernel: bool,
+) -> io::Result<RpoDigest> {
+    let mut source = Vec::new();
+    for (idx, source_line) in module_source.lines().enumerate() {
+        let declaration = source_line
+            .strip_prefix("export.")
+            .map(|rest| (true, rest))
+            .or_else(|| source_line.strip_prefix("proc.").map(|rest| (false, rest)));
+        match declaration {
+            Some((_, rest)) if idx + 1 == line as usize => source.push(format!("export.{rest}")),
+            Some((true, rest)) if rest.contains("::") => (),
+            Some((_, rest)) => source.push(format!("proc.{rest}")),
+            None if idx + 1 == line as usize => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("no procedure is declared at line {line}"),
+                ))
+            },
+            None => source.push(source_line.to_string()),
+        }
+    }
+
+    let module = ModuleAst::parse(&source.join("\n"))?;
+    let roots = assembler
+        .compile_module(&module, None, &mut AssemblyContext::for_module(is_kernel))
+        .map_err(assembly_error)?;
+    roots.first().copied().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("no procedure root for line {line}"))
+    })
+}
+
+/// Converts an assembly error into an IO error.
+fn assembly_error(err: assembly::AssemblyError) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err.to_string())
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
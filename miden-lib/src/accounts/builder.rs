@@ -0,0 +1,291 @@
+use miden_objects::{
+    accounts::{
+        Account, AccountCode, AccountId, AccountStorage, AccountStorageMode, AccountType, SlotItem,
+        StorageSlotType,
+    },
+    assembly::ModuleAst,
+    assets::AssetVault,
+    utils::{
+        collections::{BTreeSet, Vec},
+        format,
+        string::{String, ToString},
+        vec,
+    },
+    AccountError, Word, ZERO,
+};
+
+use super::{
+    faucets::{TokenMetadata, FUNGIBLE_FAUCET_METADATA_SLOT},
+    AuthScheme, TransactionKernel, AUTH_DATA_SLOT,
+};
+
+// ACCOUNT COMPONENT
+// ================================================================================================
+
+/// A reusable part of the code and the storage of an account.
+///
+/// Every component contributes a set of exported procedures to the code of the account, and
+/// initializes the storage slots it relies on. Components are combined into an account via the
+/// [AccountBuilder].
+pub enum AccountComponent {
+    /// The basic wallet interface, which exposes the `receive_asset` and `send_asset` procedures.
+    ///
+    /// Can be used only with regular accounts.
+    BasicWallet,
+
+    /// The basic fungible faucet interface, which exposes the `distribute`, `burn` and
+    /// `get_total_issuance` procedures.
+    ///
+    /// The token metadata is stored at [FUNGIBLE_FAUCET_METADATA_SLOT]. Can be used only with
+    /// fungible faucet accounts.
+    BasicFungibleFaucet { metadata: TokenMetadata },
+
+    /// The authentication procedure of the specified authentication scheme.
+    ///
+    /// Public key information for the scheme (see [AuthScheme::auth_data()]) is stored at
+    /// [AUTH_DATA_SLOT].
+    Auth(AuthScheme),
+
+    /// A custom component defined by its source code.
+    ///
+    /// `imports` are `use` statements required by the code (e.g., `use.miden::account`), `code`
+    /// contains the procedures exported by the component (e.g., `export.foo ... end`), and
+    /// `storage` contains the items initializing the storage slots used by the component.
+    Custom {
+        imports: Vec<String>,
+        code: String,
+        storage: Vec<SlotItem>,
+    },
+}
+
+impl AccountComponent {
+    /// Returns the name of this component used in error messages.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::BasicWallet => "basic wallet",
+            Self::BasicFungibleFaucet { .. } => "basic fungible faucet",
+            Self::Auth(_) => "auth",
+            Self::Custom { .. } => "custom",
+        }
+    }
+
+    /// Returns true if this component can be used with accounts of the specified type.
+    fn supports(&self, account_type: AccountType) -> bool {
+        match self {
+            Self::BasicWallet => matches!(
+                account_type,
+                AccountType::RegularAccountImmutableCode | AccountType::RegularAccountUpdatableCode
+            ),
+            Self::BasicFungibleFaucet { .. } => account_type == AccountType::FungibleFaucet,
+            Self::Auth(_) | Self::Custom { .. } => true,
+        }
+    }
+
+    /// Returns the `use` statements required by this component.
+    fn imports(&self) -> Vec<&str> {
+        match self {
+            Self::BasicWallet => vec!["use.miden::contracts::wallets::basic->basic_wallet"],
+            Self::BasicFungibleFaucet { .. } => {
+                vec!["use.miden::contracts::faucets::basic_fungible"]
+            },
+            Self::Auth(AuthScheme::RpoFalcon512 { .. }) => {
+                vec!["use.miden::contracts::auth::basic"]
+            },
+            Self::Auth(AuthScheme::RpoFalcon512Multisig { .. }) => {
+                vec!["use.miden::contracts::auth::multisig"]
+            },
+            #[cfg(feature = "ecdsa")]
+            Self::Auth(AuthScheme::EcdsaK256 { .. }) => vec!["use.miden::contracts::auth::ecdsa"],
+            Self::Custom { imports, .. } => imports.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Returns the code exporting the procedures of this component.
+    fn code(&self) -> String {
+        match self {
+            Self::BasicWallet => {
+                "export.basic_wallet::receive_asset\nexport.basic_wallet::send_asset\n".to_string()
+            },
+            Self::BasicFungibleFaucet { .. } => "export.basic_fungible::distribute\n\
+                export.basic_fungible::burn\n\
+                export.basic_fungible::get_total_issuance\n"
+                .to_string(),
+            Self::Auth(scheme) => {
+                let module = scheme.auth_module().rsplit("::").next().expect("path is not empty");
+                format!("export.{module}::{}\n", scheme.auth_procedure())
+            },
+            Self::Custom { code, .. } => code.clone(),
+        }
+    }
+
+    /// Returns the items initializing the storage slots used by this component.
+    fn storage(&self) -> Vec<SlotItem> {
+        match self {
+            Self::BasicWallet => vec![],
+            Self::BasicFungibleFaucet { metadata } => {
+                vec![FUNGIBLE_FAUCET_METADATA_SLOT.item(metadata)]
+            },
+            Self::Auth(scheme) => vec![AUTH_DATA_SLOT.item(&scheme.auth_data())],
+            Self::Custom { storage, .. } => storage.clone(),
+        }
+    }
+
+    /// Returns the types of the storage slots used by this component.
+    fn layout(&self) -> Vec<(u8, StorageSlotType)> {
+        match self {
+            Self::BasicWallet => vec![],
+            Self::BasicFungibleFaucet { .. } => vec![(
+                FUNGIBLE_FAUCET_METADATA_SLOT.index(),
+                FUNGIBLE_FAUCET_METADATA_SLOT.slot_type(),
+            )],
+            Self::Auth(_) => vec![(AUTH_DATA_SLOT.index(), AUTH_DATA_SLOT.slot_type())],
+            Self::Custom { storage, .. } => {
+                storage.iter().map(|(index, (slot_type, _))| (*index, *slot_type)).collect()
+            },
+        }
+    }
+}
+
+// ACCOUNT BUILDER
+// ================================================================================================
+
+/// Builds a new account out of a sequence of [AccountComponent]s.
+///
+/// The code of the account exports the procedures of all components, and the storage of the
+/// account is the union of the storage slots of all components. For example, a basic wallet can
+/// be built as follows:
+///
+/// ```ignore
+/// let (account, seed) = AccountBuilder::new(init_seed)
+///     .account_type(AccountType::RegularAccountUpdatableCode)
+///     .with_component(AccountComponent::BasicWallet)
+///     .with_component(AccountComponent::Auth(auth_scheme))
+///     .build()?;
+/// ```
+///
+/// By default, the builder creates private accounts with updatable code.
+pub struct AccountBuilder {
+    init_seed: [u8; 32],
+    account_type: AccountType,
+    storage_mode: AccountStorageMode,
+    components: Vec<AccountComponent>,
+}
+
+impl AccountBuilder {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new builder without any components; the ID of the account will be derived from
+    /// the specified initial seed.
+    pub fn new(init_seed: [u8; 32]) -> Self {
+        Self {
+            init_seed,
+            account_type: AccountType::RegularAccountUpdatableCode,
+            storage_mode: AccountStorageMode::Private,
+            components: Vec::new(),
+        }
+    }
+
+    /// Sets the type of the account.
+    pub fn account_type(mut self, account_type: AccountType) -> Self {
+        self.account_type = account_type;
+        self
+    }
+
+    /// Sets the storage mode of the account.
+    pub fn storage_mode(mut self, storage_mode: AccountStorageMode) -> Self {
+        self.storage_mode = storage_mode;
+        self
+    }
+
+    /// Appends the specified component to the components of the account.
+    pub fn with_component(mut self, component: AccountComponent) -> Self {
+        self.components.push(component);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the source code of the account code module composed of all components.
+    pub fn source(&self) -> String {
+        let imports = self
+            .components
+            .iter()
+            .flat_map(AccountComponent::imports)
+            .collect::<BTreeSet<_>>();
+
+        let mut source = String::new();
+        for import in imports {
+            source.push_str(import);
+            source.push('\n');
+        }
+        for component in self.components.iter() {
+            source.push('\n');
+            source.push_str(&component.code());
+        }
+        source
+    }
+
+    // BUILDERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Compiles the code of the account and initializes its storage.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Any of the components cannot be used with the type of the account.
+    /// - More than one component initializes the same storage slot.
+    /// - The storage slots initialized by any of the components do not have the types declared by
+    ///   the component.
+    /// - The composed account code cannot be compiled.
+    pub fn build_code_and_storage(&self) -> Result<(AccountCode, AccountStorage), AccountError> {
+        let mut slots = BTreeSet::new();
+        let mut items = Vec::new();
+        let mut layout = Vec::new();
+        for component in self.components.iter() {
+            if !component.supports(self.account_type) {
+                return Err(AccountError::AccountComponentNotSupported {
+                    component: component.name().to_string(),
+                    account_type: self.account_type,
+                });
+            }
+            for item in component.storage() {
+                if !slots.insert(item.0) {
+                    return Err(AccountError::StorageSlotConflict(item.0));
+                }
+                items.push(item);
+            }
+            layout.extend(component.layout());
+        }
+
+        let module = ModuleAst::parse(&self.source())
+            .map_err(|err| AccountError::AccountCodeAssemblerError(err.into()))?;
+        let code = AccountCode::new(module, &TransactionKernel::assembler())?;
+        let storage = AccountStorage::new(items)?;
+        storage.validate_layout(&layout)?;
+
+        Ok((code, storage))
+    }
+
+    /// Builds a new account with an empty vault, and returns it together with the seed from
+    /// which its ID was derived.
+    ///
+    /// # Errors
+    /// Returns an error if the code or the storage of the account cannot be built (see
+    /// [AccountBuilder::build_code_and_storage()]), or if the account seed cannot be computed.
+    pub fn build(self) -> Result<(Account, Word), AccountError> {
+        let (code, storage) = self.build_code_and_storage()?;
+        let vault = AssetVault::new(&[]).map_err(AccountError::AssetVaultError)?;
+
+        let seed = AccountId::get_account_seed(
+            self.init_seed,
+            self.account_type,
+            self.storage_mode == AccountStorageMode::Public,
+            code.root(),
+            storage.root(),
+        )?;
+        let id = AccountId::new(seed, code.root(), storage.root())?;
+
+        Ok((Account::new(id, vault, storage, code, ZERO), seed))
+    }
+}
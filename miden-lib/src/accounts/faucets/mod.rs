@@ -1,12 +1,19 @@
-use assembly::LibraryPath;
 use miden_objects::{
-    accounts::{Account, AccountCode, AccountId, AccountStorage, AccountType, StorageSlotType},
-    assets::{AssetVault, TokenSymbol},
-    utils::{string::ToString, vec},
-    AccountError, Felt, StarkField, Word, ZERO,
+    accounts::{Account, AccountId, AccountType, StorageSlot, StorageSlotType, StorageValue},
+    assembly::ProgramAst,
+    assets::{Asset, FungibleAsset, TokenSymbol},
+    notes::Note,
+    utils::{
+        collections::Vec,
+        format,
+        string::{String, ToString},
+        vec,
+    },
+    AccountError, Felt, NoteError, StarkField, Word, ZERO,
 };
 
-use super::{AuthScheme, Library, MidenLib, TransactionKernel};
+use super::{AccountBuilder, AccountComponent, AuthScheme};
+use crate::{notes::create_p2id_note, transaction::memory::FAUCET_STORAGE_DATA_SLOT};
 
 // FUNGIBLE FAUCET
 // ================================================================================================
@@ -14,18 +21,31 @@ use super::{AuthScheme, Library, MidenLib, TransactionKernel};
 const MAX_MAX_SUPPLY: u64 = (1 << 63) - 1;
 const MAX_DECIMALS: u8 = 12;
 
+/// The storage slot holding the token metadata of fungible faucets created via
+/// [create_basic_fungible_faucet()].
+pub const FUNGIBLE_FAUCET_METADATA_SLOT: StorageSlot<TokenMetadata> = StorageSlot::new(1);
+
 /// Creates a new faucet account with basic fungible faucet interface,
 /// specified authentication scheme, and provided meta data (token symbol, decimals, max supply).
 ///
-/// The basic faucet interface exposes two procedures:
+/// The basic faucet interface exposes three procedures:
 /// - `distribute`, which mints an assets and create a note for the provided recipient.
 /// - `burn`, which burns the provided asset.
+/// - `get_total_issuance`, which returns the amount of tokens minted and not yet burned.
 ///
 /// `distribute` requires authentication. The authentication procedure is defined by the specified
-/// authentication scheme. `burn` does not require authentication and can be called by anyone.
+/// authentication scheme, which must be [AuthScheme::RpoFalcon512]. `burn` and
+/// `get_total_issuance` do not require authentication and can be called by anyone.
+///
+/// Notes minting tokens of the faucet and transaction scripts distributing them can be created
+/// via [create_mint_note()] and [create_mint_script()] respectively.
+///
+/// Public key information for the scheme is stored in the account storage at
+/// [AUTH_DATA_SLOT](super::AUTH_DATA_SLOT).
+/// The token metadata is stored in the account storage at [FUNGIBLE_FAUCET_METADATA_SLOT].
 ///
-/// Public key information for the scheme is stored in the account storage at slot 0. The token
-/// metadata is stored in the account storage at slot 1.
+/// The faucet is built via the [AccountBuilder] out of the [AccountComponent::Auth] and
+/// [AccountComponent::BasicFungibleFaucet] components.
 pub fn create_basic_fungible_faucet(
     init_seed: [u8; 32],
     symbol: TokenSymbol,
@@ -33,55 +53,164 @@ pub fn create_basic_fungible_faucet(
     max_supply: Felt,
     auth_scheme: AuthScheme,
 ) -> Result<(Account, Word), AccountError> {
-    // Atm we only have RpoFalcon512 as authentication scheme and this is also the default in the
-    // faucet contract, so we can just use the public key as storage slot 0.
-    // TODO: consider using a trait when we have more auth schemes.
-    let auth_data: Word = match auth_scheme {
-        AuthScheme::RpoFalcon512 { pub_key } => pub_key.into(),
-    };
-
-    let miden = MidenLib::default();
-    let path = "miden::contracts::faucets::basic_fungible";
-    let faucet_code_ast = miden
-        .get_module_ast(&LibraryPath::new(path).unwrap())
-        .expect("Getting module AST failed");
-
-    let account_assembler = TransactionKernel::assembler();
-    let account_code = AccountCode::new(faucet_code_ast.clone(), &account_assembler)?;
+    // The faucet contract authenticates the faucet owner via RpoFalcon512
+    if !matches!(auth_scheme, AuthScheme::RpoFalcon512 { .. }) {
+        return Err(AccountError::AuthSchemeNotSupported(
+            "basic fungible faucets support only RpoFalcon512 authentication".to_string(),
+        ));
+    }
 
     // First check that the metadata is valid.
-    if decimals > MAX_DECIMALS {
-        return Err(AccountError::FungibleFaucetInvalidMetadata(
-            "Decimals must be less than 13".to_string(),
-        ));
-    } else if max_supply.as_int() > MAX_MAX_SUPPLY {
-        return Err(AccountError::FungibleFaucetInvalidMetadata(
-            "Max supply must be < 2^63".to_string(),
+    let metadata = TokenMetadata::new(symbol, decimals, max_supply)?;
+
+    // The authentication component is added first such that the procedures of the account are
+    // exported in the same order as by the `basic_fungible` module
+    AccountBuilder::new(init_seed)
+        .account_type(AccountType::FungibleFaucet)
+        .with_component(AccountComponent::Auth(auth_scheme))
+        .with_component(AccountComponent::BasicFungibleFaucet { metadata })
+        .build()
+}
+
+/// Returns the total issuance of the specified fungible faucet, i.e., the amount of tokens minted
+/// and not yet burned, or None if the account is not a fungible faucet.
+///
+/// The total issuance is tracked by the transaction kernel in the reserved faucet storage slot.
+pub fn get_total_issuance(faucet: &Account) -> Option<u64> {
+    if !faucet.id().is_fungible_faucet() {
+        return None;
+    }
+    let faucet_data: Word = faucet.storage().get_item(FAUCET_STORAGE_DATA_SLOT).into();
+    Some(faucet_data[3].as_int())
+}
+
+// MINT TRANSACTIONS
+// ================================================================================================
+
+/// Creates a P2ID note carrying `amount` tokens minted by the specified faucet to the `target`
+/// account.
+///
+/// The note is created by the faucet when a transaction executing the script returned by
+/// [create_mint_script()] is executed against the faucet.
+///
+/// # Errors
+/// Returns an error if the faucet is not a fungible faucet or the amount is invalid.
+pub fn create_mint_note(
+    faucet_id: AccountId,
+    amount: u64,
+    target: AccountId,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    let asset = FungibleAsset::new(faucet_id, amount).map_err(NoteError::InvalidAssetData)?;
+    create_p2id_note(faucet_id, target, vec![asset.into()], serial_num)
+}
+
+/// Returns a transaction script which mints the tokens carried by the provided notes and creates
+/// the notes via the `distribute` procedure of the basic fungible faucet.
+///
+/// The transaction is authenticated via `auth_tx_rpo_falcon512`, thus the secret key of the
+/// faucet owner must be provided via the transaction script inputs (or by an authenticator).
+///
+/// # Errors
+/// Returns an error if any of the notes does not carry exactly one fungible asset issued by the
+/// sender of the note, or if the notes were created by different faucets.
+pub fn create_mint_script(notes: &[Note]) -> Result<ProgramAst, NoteError> {
+    let mut body = String::new();
+    for note in notes {
+        let amount = match note.assets().iter().collect::<Vec<_>>().as_slice() {
+            [Asset::Fungible(asset)] if asset.faucet_id() == note.metadata().sender() => {
+                asset.amount()
+            },
+            _ => return Err(NoteError::InvalidMintNote(note.id())),
+        };
+        if note.metadata().sender() != notes[0].metadata().sender() {
+            return Err(NoteError::InvalidMintNote(note.id()));
+        }
+
+        let recipient: Word = note.recipient().into();
+        body.push_str(&format!(
+            "    push.{} push.{} push.{amount}\n    call.faucet::distribute drop dropw dropw\n",
+            recipient.iter().map(|x| x.as_int().to_string()).collect::<Vec<_>>().join("."),
+            note.metadata().tag(),
         ));
     }
 
-    // Note: data is stored as [a0, a1, a2, a3] but loaded onto the stack as [a3, a2, a1, a0, ...]
-    let metadata = [max_supply, Felt::from(decimals), symbol.into(), ZERO];
-
-    // We store the authentication data and the token metadata in the account storage:
-    // - slot 0: authentication data
-    // - slot 1: token metadata as [max_supply, decimals, token_symbol, 0]
-    let account_storage = AccountStorage::new(vec![
-        (0, (StorageSlotType::Value { value_arity: 0 }, auth_data)),
-        (1, (StorageSlotType::Value { value_arity: 0 }, metadata)),
-    ])?;
-    let account_vault = AssetVault::new(&[]).map_err(AccountError::AssetVaultError)?;
-
-    let account_seed = AccountId::get_account_seed(
-        init_seed,
-        AccountType::FungibleFaucet,
-        false,
-        account_code.root(),
-        account_storage.root(),
-    )?;
-    let account_id = AccountId::new(account_seed, account_code.root(), account_storage.root())?;
-    Ok((
-        Account::new(account_id, account_vault, account_storage, account_code, ZERO),
-        account_seed,
-    ))
+    let source = format!(
+        "use.miden::contracts::auth::basic->auth_tx
+use.miden::contracts::faucets::basic_fungible->faucet
+
+begin
+{body}    call.auth_tx::auth_tx_rpo_falcon512
+end
+"
+    );
+    ProgramAst::parse(&source).map_err(|err| NoteError::ScriptCompilationError(err.into()))
+}
+
+// TOKEN METADATA
+// ================================================================================================
+
+/// Metadata of the token issued by a basic fungible faucet.
+///
+/// The metadata is stored in [FUNGIBLE_FAUCET_METADATA_SLOT] as
+/// `[max_supply, decimals, token_symbol, 0]`. Note that the data is loaded onto the stack as
+/// `[0, token_symbol, decimals, max_supply, ...]`.
+#[derive(Clone, Copy, Debug)]
+pub struct TokenMetadata {
+    symbol: TokenSymbol,
+    decimals: u8,
+    max_supply: Felt,
+}
+
+impl TokenMetadata {
+    /// Returns new token metadata with the specified symbol, number of decimals and max supply.
+    ///
+    /// # Errors
+    /// Returns an error if the number of decimals is greater than 12, or if the max supply is
+    /// greater than or equal to 2^63.
+    pub fn new(symbol: TokenSymbol, decimals: u8, max_supply: Felt) -> Result<Self, AccountError> {
+        if decimals > MAX_DECIMALS {
+            return Err(AccountError::FungibleFaucetInvalidMetadata(
+                "Decimals must be less than 13".to_string(),
+            ));
+        } else if max_supply.as_int() > MAX_MAX_SUPPLY {
+            return Err(AccountError::FungibleFaucetInvalidMetadata(
+                "Max supply must be < 2^63".to_string(),
+            ));
+        }
+
+        Ok(Self { symbol, decimals, max_supply })
+    }
+
+    /// Returns the symbol of the token.
+    pub fn symbol(&self) -> TokenSymbol {
+        self.symbol
+    }
+
+    /// Returns the number of decimals of the token.
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// Returns the max supply of the token.
+    pub fn max_supply(&self) -> Felt {
+        self.max_supply
+    }
+}
+
+impl StorageValue for TokenMetadata {
+    const SLOT_TYPE: StorageSlotType = StorageSlotType::Value { value_arity: 0 };
+
+    fn to_slot_word(&self) -> Word {
+        [self.max_supply, Felt::from(self.decimals), self.symbol.into(), ZERO]
+    }
+
+    fn from_slot_word(word: Word) -> Result<Self, &'static str> {
+        let decimals = u8::try_from(word[1].as_int()).map_err(|_| "invalid token decimals")?;
+        let symbol = TokenSymbol::try_from(word[2]).map_err(|_| "invalid token symbol")?;
+        if word[3] != ZERO {
+            return Err("last element of token metadata must be zero");
+        }
+        Self::new(symbol, decimals, word[0]).map_err(|_| "invalid token metadata")
+    }
 }
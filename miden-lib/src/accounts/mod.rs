@@ -1,4 +1,16 @@
-use super::{auth::AuthScheme, transaction::TransactionKernel, Library, MidenLib};
+use miden_objects::{accounts::StorageSlot, Word};
+
+use super::{auth::AuthScheme, transaction::TransactionKernel};
+
+mod builder;
+pub use builder::{AccountBuilder, AccountComponent};
 
 pub mod faucets;
 pub mod wallets;
+
+// STORAGE LAYOUT
+// ================================================================================================
+
+/// The storage slot holding the authentication data (e.g., the public key) of the accounts
+/// created via the constructors of this module.
+pub const AUTH_DATA_SLOT: StorageSlot<Word> = StorageSlot::new(0);
@@ -1,16 +1,10 @@
 use miden_objects::{
-    accounts::{Account, AccountCode, AccountId, AccountStorage, AccountType, StorageSlotType},
-    assembly::ModuleAst,
-    assets::AssetVault,
-    utils::{
-        format,
-        string::{String, ToString},
-        vec,
-    },
-    AccountError, Word, ZERO,
+    accounts::{Account, AccountType},
+    utils::string::ToString,
+    AccountError, Word,
 };
 
-use super::{AuthScheme, TransactionKernel};
+use super::{AccountBuilder, AccountComponent, AuthScheme};
 
 // BASIC WALLET
 // ================================================================================================
@@ -25,7 +19,10 @@ use super::{AuthScheme, TransactionKernel};
 ///
 /// Both methods require authentication. The authentication procedure is defined by the specified
 /// authentication scheme. Public key information for the scheme is stored in the account storage
-/// at slot 0.
+/// at [AUTH_DATA_SLOT](super::AUTH_DATA_SLOT).
+///
+/// The wallet is built via the [AccountBuilder] out of the [AccountComponent::BasicWallet] and
+/// [AccountComponent::Auth] components.
 pub fn create_basic_wallet(
     init_seed: [u8; 32],
     auth_scheme: AuthScheme,
@@ -37,44 +34,9 @@ pub fn create_basic_wallet(
         ));
     }
 
-    let (auth_scheme_procedure, storage_slot_0_data): (&str, Word) = match auth_scheme {
-        AuthScheme::RpoFalcon512 { pub_key } => ("basic::auth_tx_rpo_falcon512", pub_key.into()),
-    };
-
-    let account_code_string: String = format!(
-        "
-    use.miden::contracts::wallets::basic->basic_wallet
-    use.miden::contracts::auth::basic
-
-    export.basic_wallet::receive_asset
-    export.basic_wallet::send_asset
-    export.{auth_scheme_procedure}
-
-    "
-    );
-    let account_code_src: &str = &account_code_string;
-
-    let account_code_ast = ModuleAst::parse(account_code_src)
-        .map_err(|e| AccountError::AccountCodeAssemblerError(e.into()))?;
-    let account_assembler = TransactionKernel::assembler();
-    let account_code = AccountCode::new(account_code_ast.clone(), &account_assembler)?;
-
-    let account_storage = AccountStorage::new(vec![(
-        0,
-        (StorageSlotType::Value { value_arity: 0 }, storage_slot_0_data),
-    )])?;
-    let account_vault = AssetVault::new(&[]).map_err(AccountError::AssetVaultError)?;
-
-    let account_seed = AccountId::get_account_seed(
-        init_seed,
-        account_type,
-        false,
-        account_code.root(),
-        account_storage.root(),
-    )?;
-    let account_id = AccountId::new(account_seed, account_code.root(), account_storage.root())?;
-    Ok((
-        Account::new(account_id, account_vault, account_storage, account_code, ZERO),
-        account_seed,
-    ))
+    AccountBuilder::new(init_seed)
+        .account_type(account_type)
+        .with_component(AccountComponent::BasicWallet)
+        .with_component(AccountComponent::Auth(auth_scheme))
+        .build()
 }
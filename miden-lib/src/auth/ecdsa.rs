@@ -0,0 +1,119 @@
+//! Helpers for accounts authenticated via ECDSA signatures over the secp256k1 curve.
+//!
+//! The `auth_tx_ecdsa_k256` procedure expects 256-bit values (i.e., the coordinates of the public
+//! key, the message hash and the components of the signature) to be encoded as eight 32-bit limbs,
+//! least significant limb first. The account stores a commitment to the public key, and the public
+//! key together with the signature is provided to the transaction via the advice map entry
+//! returned by [sign()].
+
+use k256::ecdsa::{
+    signature::hazmat::{PrehashSigner, PrehashVerifier},
+    Signature,
+};
+pub use k256::ecdsa::{SigningKey, VerifyingKey};
+use miden_objects::{
+    utils::{collections::Vec, string::ToString},
+    Felt, Hasher, StarkField, Word, ZERO,
+};
+
+use super::AuthSchemeError;
+
+// PUBLIC KEY
+// ================================================================================================
+
+/// Returns the commitment to the specified public key stored in the account storage, i.e., a
+/// sequential hash of the limbs of the affine coordinates `[X, Y]` of the public key.
+pub fn pub_key_commitment(pub_key: &VerifyingKey) -> Word {
+    Hasher::hash_elements(&encode_pub_key(pub_key)).into()
+}
+
+// SIGNATURES
+// ================================================================================================
+
+/// Returns the 256-bit message hash signed for the specified message, encoded as 32 big-endian
+/// bytes.
+///
+/// For a message `[m0, m1, m2, m3]`, the message hash is `m0 + m1 * 2^64 + m2 * 2^128 + m3 *
+/// 2^192`.
+pub fn message_hash(message: Word) -> [u8; 32] {
+    let mut bytes = [0_u8; 32];
+    for (chunk, element) in bytes.chunks_exact_mut(8).zip(message.iter().rev()) {
+        chunk.copy_from_slice(&element.as_int().to_be_bytes());
+    }
+    bytes
+}
+
+/// Signs the specified message with the specified key and returns the advice map entry providing
+/// the public key and the signature to the `auth_tx_ecdsa_k256` procedure. The entry is expected
+/// to be passed to the transaction via its transaction script inputs.
+///
+/// The entry is stored under `hash(PUB_KEY_COMMITMENT, message)` and contains `[X, Y, R, S]`.
+///
+/// # Errors
+/// Returns an error if the message cannot be signed with the specified key.
+pub fn sign(signing_key: &SigningKey, message: Word) -> Result<(Word, Vec<Felt>), AuthSchemeError> {
+    let signature: Signature = signing_key
+        .sign_prehash(&message_hash(message))
+        .map_err(|err| AuthSchemeError::SigningFailed(err.to_string()))?;
+    let pub_key = signing_key.verifying_key();
+
+    let key = Hasher::merge(&[pub_key_commitment(pub_key).into(), message.into()]);
+    let mut values = encode_pub_key(pub_key);
+    let (r, s) = signature.split_bytes();
+    values.extend(to_limbs(&r));
+    values.extend(to_limbs(&s));
+
+    Ok((key.into(), values))
+}
+
+/// Returns true if `values` contain a valid signature of the specified message, in the format
+/// returned by [sign()], made with the secret key associated with the specified public key.
+pub fn verify(pub_key: &VerifyingKey, message: Word, values: &[Felt]) -> bool {
+    if values.len() != 32 || values[..16] != encode_pub_key(pub_key)[..] {
+        return false;
+    }
+
+    let mut bytes = [0_u8; 64];
+    for (chunk, limbs) in bytes.chunks_exact_mut(32).zip(values[16..].chunks_exact(8)) {
+        match from_limbs(limbs) {
+            Some(value) => chunk.copy_from_slice(&value),
+            None => return false,
+        }
+    }
+
+    Signature::from_slice(&bytes)
+        .and_then(|signature| pub_key.verify_prehash(&message_hash(message), &signature))
+        .is_ok()
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the limbs of the affine coordinates `[X, Y]` of the specified public key.
+fn encode_pub_key(pub_key: &VerifyingKey) -> Vec<Felt> {
+    let point = pub_key.to_encoded_point(false);
+    let x = point.x().expect("uncompressed point has an x coordinate");
+    let y = point.y().expect("uncompressed point has a y coordinate");
+    to_limbs(x).into_iter().chain(to_limbs(y)).collect()
+}
+
+/// Returns the 32-bit limbs of the specified 256-bit big-endian value, least significant limb
+/// first.
+fn to_limbs(bytes: &[u8]) -> [Felt; 8] {
+    let mut limbs = [ZERO; 8];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(4)) {
+        *limb = Felt::from(u32::from_be_bytes(chunk.try_into().expect("chunk has four bytes")));
+    }
+    limbs
+}
+
+/// Returns the 256-bit big-endian value encoded by the specified limbs, or None if any of the
+/// limbs is not a u32 value.
+fn from_limbs(limbs: &[Felt]) -> Option<[u8; 32]> {
+    let mut bytes = [0_u8; 32];
+    for (chunk, limb) in bytes.rchunks_exact_mut(4).zip(limbs) {
+        let limb = u32::try_from(limb.as_int()).ok()?;
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    Some(bytes)
+}
@@ -0,0 +1,120 @@
+use core::fmt;
+
+use miden_objects::{
+    accounts::AccountId, crypto::dsa::rpo_falcon512, utils::string::String, Digest, Felt, Hasher,
+    Word, ZERO,
+};
+
+#[cfg(feature = "ecdsa")]
+pub mod ecdsa;
+
+mod multisig;
+pub use multisig::MultisigConfig;
+
+// AUTH SCHEME
+// ================================================================================================
+
+/// Defines authentication schemes available to standard and faucet accounts.
+pub enum AuthScheme {
+    /// A single-key authentication scheme which relies RPO Falcon512 signatures. RPO Falcon512 is a
+    /// variant of the [Falcon](https://falcon-sign.info/) signature scheme. This variant differs from
+    /// the standard in that instead of using SHAKE256 hash function in the hash-to-point algorithm we
+    /// use RPO256. This makes the signature more efficient to verify in Miden VM.
+    RpoFalcon512 { pub_key: rpo_falcon512::PublicKey },
+
+    /// An m-of-n authentication scheme which relies on RPO Falcon512 signatures. A transaction is
+    /// authenticated if it is signed with at least `threshold` of the public keys of the
+    /// configuration (see [MultisigConfig]).
+    RpoFalcon512Multisig { config: MultisigConfig },
+
+    /// A single-key authentication scheme which relies on ECDSA signatures over the secp256k1
+    /// curve, i.e., the signature scheme used by Ethereum and Bitcoin accounts (see [ecdsa]).
+    #[cfg(feature = "ecdsa")]
+    EcdsaK256 { pub_key: ecdsa::VerifyingKey },
+}
+
+impl AuthScheme {
+    /// Returns the name of the authentication procedure of this scheme.
+    pub fn auth_procedure(&self) -> &'static str {
+        match self {
+            Self::RpoFalcon512 { .. } => "auth_tx_rpo_falcon512",
+            Self::RpoFalcon512Multisig { .. } => "auth_tx_rpo_falcon512_multisig",
+            #[cfg(feature = "ecdsa")]
+            Self::EcdsaK256 { .. } => "auth_tx_ecdsa_k256",
+        }
+    }
+
+    /// Returns the path of the miden-lib module defining the authentication procedure of this
+    /// scheme.
+    pub fn auth_module(&self) -> &'static str {
+        match self {
+            Self::RpoFalcon512 { .. } => "miden::contracts::auth::basic",
+            Self::RpoFalcon512Multisig { .. } => "miden::contracts::auth::multisig",
+            #[cfg(feature = "ecdsa")]
+            Self::EcdsaK256 { .. } => "miden::contracts::auth::ecdsa",
+        }
+    }
+
+    /// Returns the authentication data stored in the account storage, i.e., the public key for
+    /// RPO Falcon512, and the commitment to the public key(s) for the remaining schemes.
+    pub fn auth_data(&self) -> Word {
+        match self {
+            Self::RpoFalcon512 { pub_key } => (*pub_key).into(),
+            Self::RpoFalcon512Multisig { config } => config.commitment(),
+            #[cfg(feature = "ecdsa")]
+            Self::EcdsaK256 { pub_key } => ecdsa::pub_key_commitment(pub_key),
+        }
+    }
+}
+
+// TRANSACTION MESSAGE
+// ================================================================================================
+
+/// Returns the message signed by the owner of the specified account to authenticate a transaction,
+/// as computed by the `compute_tx_message` procedure of `miden::contracts::auth::basic`.
+///
+/// `nonce` is the nonce of the account at the time the authentication procedure is invoked, and
+/// the notes hashes are the commitments to the notes consumed and created by the transaction.
+pub fn tx_message(
+    account_id: AccountId,
+    nonce: Felt,
+    input_notes_hash: Digest,
+    output_notes_hash: Digest,
+) -> Word {
+    let nonce_word: Word = [nonce, ZERO, ZERO, ZERO];
+    let account_id_word: Word = [account_id.into(), ZERO, ZERO, ZERO];
+    let account_hash = Hasher::merge(&[nonce_word.into(), account_id_word.into()]);
+    let input_hash = Hasher::merge(&[input_notes_hash, account_hash]);
+    Hasher::merge(&[output_notes_hash, input_hash]).into()
+}
+
+// AUTH SCHEME ERROR
+// ================================================================================================
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AuthSchemeError {
+    DuplicatePublicKey(Word),
+    InvalidThreshold { threshold: u32, num_keys: usize },
+    SigningFailed(String),
+    UnknownSigner(Word),
+}
+
+impl fmt::Display for AuthSchemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicatePublicKey(pub_key) => {
+                write!(f, "public key {pub_key:?} is included more than once")
+            },
+            Self::InvalidThreshold { threshold, num_keys } => {
+                write!(f, "threshold {threshold} is invalid for {num_keys} public keys")
+            },
+            Self::SigningFailed(err) => write!(f, "failed to sign the message: {err}"),
+            Self::UnknownSigner(pub_key) => {
+                write!(f, "public key {pub_key:?} is not a part of the configuration")
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AuthSchemeError {}
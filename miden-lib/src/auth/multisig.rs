@@ -0,0 +1,117 @@
+use miden_objects::{
+    crypto::dsa::rpo_falcon512::PublicKey, utils::collections::Vec, Felt, Hasher, Word, ZERO,
+};
+
+use super::AuthSchemeError;
+
+// MULTISIG CONFIG
+// ================================================================================================
+
+/// The configuration of an m-of-n RPO Falcon512 multisig account.
+///
+/// The account stores a commitment to the configuration computed as
+/// `hash([threshold, num_keys, 0, 0], hash(PUB_KEY_0, ..., PUB_KEY_{n-1}))`, where the public keys
+/// are padded with an empty word to an even number of words. When a transaction is authenticated,
+/// the configuration together with the set of keys signing the transaction is provided to the
+/// `auth_tx_rpo_falcon512_multisig` procedure via the advice map entry returned by
+/// [MultisigConfig::advice_inputs()].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultisigConfig {
+    threshold: u32,
+    pub_keys: Vec<PublicKey>,
+}
+
+impl MultisigConfig {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new configuration requiring `threshold` signatures of the specified public keys.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The threshold is zero or greater than the number of public keys.
+    /// - Any of the public keys is included more than once.
+    pub fn new(threshold: u32, pub_keys: Vec<PublicKey>) -> Result<Self, AuthSchemeError> {
+        if threshold == 0 || threshold as usize > pub_keys.len() {
+            return Err(AuthSchemeError::InvalidThreshold { threshold, num_keys: pub_keys.len() });
+        }
+
+        for (i, pub_key) in pub_keys.iter().enumerate() {
+            if pub_keys[..i].contains(pub_key) {
+                return Err(AuthSchemeError::DuplicatePublicKey((*pub_key).into()));
+            }
+        }
+
+        Ok(Self { threshold, pub_keys })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the minimum number of signatures required to authenticate a transaction.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns the public keys of the signers.
+    pub fn pub_keys(&self) -> &[PublicKey] {
+        &self.pub_keys
+    }
+
+    /// Returns the commitment to this configuration stored in the account storage.
+    pub fn commitment(&self) -> Word {
+        Hasher::merge(&[self.config_word().into(), Hasher::hash_elements(&self.padded_keys())])
+            .into()
+    }
+
+    // ADVICE INPUTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the advice map entry providing this configuration and the specified signers to the
+    /// `auth_tx_rpo_falcon512_multisig` procedure. The entry is expected to be passed to the
+    /// transaction via its transaction script inputs.
+    ///
+    /// The signatures of the signers are requested from the advice provider in the same way as
+    /// for single-key RPO Falcon512 accounts (e.g., by providing the secret keys of the signers via
+    /// the transaction script inputs, or via an authenticator).
+    ///
+    /// # Errors
+    /// Returns an error if any of the signers is not a part of this configuration.
+    pub fn advice_inputs(
+        &self,
+        signers: &[PublicKey],
+    ) -> Result<(Word, Vec<Felt>), AuthSchemeError> {
+        if let Some(signer) = signers.iter().find(|signer| !self.pub_keys.contains(signer)) {
+            return Err(AuthSchemeError::UnknownSigner((*signer).into()));
+        }
+
+        let mut values = self.config_word().to_vec();
+        values.extend(self.padded_keys());
+        values.extend(
+            self.pub_keys.iter().map(|pub_key| Felt::from(signers.contains(pub_key) as u32)),
+        );
+
+        Ok((self.commitment(), values))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns [threshold, num_keys, 0, 0].
+    fn config_word(&self) -> Word {
+        [Felt::from(self.threshold), Felt::new(self.pub_keys.len() as u64), ZERO, ZERO]
+    }
+
+    /// Returns the elements of the public keys padded with an empty word to an even number of
+    /// words.
+    fn padded_keys(&self) -> Vec<Felt> {
+        let mut elements = self
+            .pub_keys
+            .iter()
+            .flat_map(|pub_key| Word::from(*pub_key))
+            .collect::<Vec<_>>();
+        if self.pub_keys.len() % 2 == 1 {
+            elements.extend([ZERO; 4]);
+        }
+        elements
+    }
+}
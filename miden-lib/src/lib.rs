@@ -5,10 +5,11 @@ extern crate alloc;
 
 use assembly::{utils::Deserializable, Library, LibraryNamespace, MaslLibrary, Version};
 
-mod auth;
+pub mod auth;
 pub use auth::AuthScheme;
 
 pub mod accounts;
+pub mod math;
 pub mod notes;
 pub mod transaction;
 
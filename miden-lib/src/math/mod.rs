@@ -0,0 +1,7 @@
+//! Rust-side counterparts of the math modules of the miden library.
+//!
+//! The types in this module encode values into (and decode values from) the stack representation
+//! expected by the procedures of the `miden::math` MASM modules.
+
+mod u256;
+pub use u256::{U256Error, U256};
@@ -0,0 +1,249 @@
+use core::{cmp::Ordering, fmt};
+
+use miden_objects::{Felt, StarkField};
+
+// U256
+// ================================================================================================
+
+/// An unsigned 256-bit integer in the representation used by the `miden::math::u256` module.
+///
+/// The value is stored as eight u32 limbs in little-endian order, i.e., limb 0 is the least
+/// significant one. When a value is put onto the stack, limb 0 ends up on top of the stack; thus,
+/// the elements returned by [U256::to_elements()] are ordered from the top of the stack down.
+///
+/// Values of external chains (e.g., Ethereum amounts) are usually encoded as 32 big-endian bytes,
+/// which can be converted via [U256::from_be_bytes()] and [U256::to_be_bytes()].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct U256([u32; 8]);
+
+impl U256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Number of field elements (u32 limbs) a u256 value occupies on the stack.
+    pub const NUM_ELEMENTS: usize = 8;
+
+    /// The value 0.
+    pub const ZERO: Self = Self([0; 8]);
+
+    /// The largest u256 value (2^256 - 1).
+    pub const MAX: Self = Self([u32::MAX; 8]);
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [U256] built from the provided limbs, least significant limb first.
+    pub const fn from_limbs(limbs: [u32; 8]) -> Self {
+        Self(limbs)
+    }
+
+    /// Returns a new [U256] built from the provided big-endian bytes.
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        let mut limbs = [0_u32; 8];
+        for (i, chunk) in bytes.chunks_exact(4).rev().enumerate() {
+            limbs[i] = u32::from_be_bytes(chunk.try_into().expect("chunk is 4 bytes long"));
+        }
+        Self(limbs)
+    }
+
+    /// Returns a new [U256] decoded from the provided stack elements, top of the stack first.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The number of elements is not [U256::NUM_ELEMENTS].
+    /// - Any of the elements is not a valid u32 value.
+    pub fn try_from_elements(elements: &[Felt]) -> Result<Self, U256Error> {
+        if elements.len() != Self::NUM_ELEMENTS {
+            return Err(U256Error::InvalidLength(elements.len()));
+        }
+
+        let mut limbs = [0_u32; 8];
+        for (limb, element) in limbs.iter_mut().zip(elements) {
+            *limb =
+                u32::try_from(element.as_int()).map_err(|_| U256Error::InvalidLimb(*element))?;
+        }
+        Ok(Self(limbs))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the limbs of this value, least significant limb first.
+    pub const fn limbs(&self) -> [u32; 8] {
+        self.0
+    }
+
+    /// Returns the big-endian byte representation of this value.
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0_u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(4).rev().zip(self.0) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the stack representation of this value, top of the stack (i.e., the least
+    /// significant limb) first.
+    pub fn to_elements(&self) -> [Felt; 8] {
+        self.0.map(Felt::from)
+    }
+
+    /// Returns this value as a field element, or None if the value is not smaller than the
+    /// modulus of the field.
+    ///
+    /// This mirrors the `to_felt` procedure of the `miden::math::u256` module.
+    pub fn to_felt(&self) -> Option<Felt> {
+        if self.0[2..].iter().any(|&limb| limb != 0) {
+            return None;
+        }
+        let value = ((self.0[1] as u64) << 32) | self.0[0] as u64;
+        (value < Felt::MODULUS).then(|| Felt::new(value))
+    }
+
+    /// Returns true if this value is zero.
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    // ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns `self + other`, or None if the result does not fit into 256 bits.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let (result, overflow) = self.overflowing_add(other);
+        (!overflow).then_some(result)
+    }
+
+    /// Returns `self + other` modulo 2^256.
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        self.overflowing_add(other).0
+    }
+
+    /// Returns `self - other`, or None if `other` is greater than `self`.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let (result, underflow) = self.overflowing_sub(other);
+        (!underflow).then_some(result)
+    }
+
+    /// Returns `self - other` modulo 2^256.
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        self.overflowing_sub(other).0
+    }
+
+    /// Returns `self * other`, or None if the result does not fit into 256 bits.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let mut result = [0_u64; 16];
+        for (i, &a) in self.0.iter().enumerate() {
+            let mut carry = 0_u64;
+            for (j, &b) in other.0.iter().enumerate() {
+                let product = a as u64 * b as u64 + result[i + j] + carry;
+                result[i + j] = product & u32::MAX as u64;
+                carry = product >> 32;
+            }
+            result[i + 8] = carry;
+        }
+
+        if result[8..].iter().any(|&limb| limb != 0) {
+            return None;
+        }
+        Some(Self(core::array::from_fn(|i| result[i] as u32)))
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    fn overflowing_add(&self, other: &Self) -> (Self, bool) {
+        let mut limbs = [0_u32; 8];
+        let mut carry = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (sum, overflow1) = self.0[i].overflowing_add(other.0[i]);
+            let (sum, overflow2) = sum.overflowing_add(carry as u32);
+            *limb = sum;
+            carry = overflow1 || overflow2;
+        }
+        (Self(limbs), carry)
+    }
+
+    fn overflowing_sub(&self, other: &Self) -> (Self, bool) {
+        let mut limbs = [0_u32; 8];
+        let mut borrow = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (diff, underflow1) = self.0[i].overflowing_sub(other.0[i]);
+            let (diff, underflow2) = diff.overflowing_sub(borrow as u32);
+            *limb = diff;
+            borrow = underflow1 || underflow2;
+        }
+        (Self(limbs), borrow)
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        let mut limbs = [0_u32; 8];
+        limbs[0] = value as u32;
+        limbs[1] = (value >> 32) as u32;
+        Self(limbs)
+    }
+}
+
+impl From<Felt> for U256 {
+    fn from(value: Felt) -> Self {
+        value.as_int().into()
+    }
+}
+
+impl From<U256> for [Felt; 8] {
+    fn from(value: U256) -> Self {
+        value.to_elements()
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // limbs are stored least significant first, thus they are compared in reverse order
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x")?;
+        for limb in self.0.iter().rev() {
+            write!(f, "{limb:08x}")?;
+        }
+        Ok(())
+    }
+}
+
+// U256 ERROR
+// ================================================================================================
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum U256Error {
+    InvalidLength(usize),
+    InvalidLimb(Felt),
+}
+
+impl fmt::Display for U256Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(
+                    f,
+                    "expected {} elements for a u256 value, but got {len}",
+                    U256::NUM_ELEMENTS
+                )
+            },
+            Self::InvalidLimb(limb) => write!(f, "element {limb} is not a valid u32 limb"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for U256Error {}
@@ -0,0 +1,260 @@
+use miden_objects::{
+    accounts::AccountId,
+    assets::FungibleAsset,
+    notes::{Note, NoteTag},
+    utils::{collections::Vec, format, vec},
+    Felt, NoteError, StarkField, Word, ZERO,
+};
+
+use super::{create_note, Script};
+use crate::transaction::{EventId, EventNamespace};
+
+// BRIDGE MESSAGE
+// ================================================================================================
+
+/// A message describing where an asset burned at a bridge faucet should be released.
+///
+/// A bridge message is carried by the inputs of a BRIDGE note, which is created by the owner of
+/// the asset via [create_bridge_note()] and consumed by the bridge faucet. Consuming the note
+/// burns its asset and emits a [BridgeEvent] carrying the message; the relayer of the bridge
+/// picks up the event (or reads the message from the input notes of the burning transaction via
+/// [BridgeMessage::from_note()]), and releases the asset on the destination chain.
+///
+/// The message is encoded into the note inputs as follows:
+/// - `[bridge_faucet_id, destination_chain, 0, 0]`
+/// - the destination address as 8 big-endian u32 limbs spanning two words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BridgeMessage {
+    bridge_faucet_id: AccountId,
+    destination_chain: u32,
+    destination_address: [u8; 32],
+}
+
+impl BridgeMessage {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Number of note inputs used to encode a bridge message.
+    pub const NUM_INPUTS: usize = 12;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [BridgeMessage] for an asset burned at the specified bridge faucet.
+    ///
+    /// Addresses shorter than 32 bytes (e.g., 20-byte EVM addresses) are expected to be
+    /// left-padded with zeros.
+    pub fn new(
+        bridge_faucet_id: AccountId,
+        destination_chain: u32,
+        destination_address: [u8; 32],
+    ) -> Self {
+        Self {
+            bridge_faucet_id,
+            destination_chain,
+            destination_address,
+        }
+    }
+
+    /// Returns the bridge message carried by the specified note.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The note script is not the BRIDGE script.
+    /// - The note inputs are not a valid encoding of a bridge message.
+    pub fn from_note(note: &Note) -> Result<Self, NoteError> {
+        if super::standard_script_kind(&note.script().hash()) != Some("BRIDGE") {
+            return Err(NoteError::InvalidBridgeMessage("not a bridge note".into()));
+        }
+        Self::from_inputs(note.inputs().inputs())
+    }
+
+    /// Returns the bridge message encoded into the specified note inputs.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Fewer than 12 inputs are provided, or the padding elements of the message are not zero.
+    /// - The bridge faucet ID is not a valid ID of a faucet.
+    /// - Any of the limbs of the destination address is not a u32 value.
+    pub fn from_inputs(inputs: &[Felt]) -> Result<Self, NoteError> {
+        if inputs.len() < Self::NUM_INPUTS {
+            return Err(NoteError::InvalidBridgeMessage(format!(
+                "expected {} inputs, but got {}",
+                Self::NUM_INPUTS,
+                inputs.len()
+            )));
+        }
+        if inputs[2] != ZERO || inputs[3] != ZERO {
+            return Err(NoteError::InvalidBridgeMessage("non-zero padding".into()));
+        }
+
+        let bridge_faucet_id = AccountId::try_from(inputs[0].as_int())
+            .map_err(|err| NoteError::InvalidBridgeMessage(format!("{err}")))?;
+        if !bridge_faucet_id.is_faucet() {
+            return Err(NoteError::InvalidBridgeMessage(format!(
+                "account {bridge_faucet_id} is not a faucet"
+            )));
+        }
+
+        let destination_chain = to_u32(inputs[1])?;
+
+        let mut destination_address = [0_u8; 32];
+        for (bytes, limb) in destination_address.chunks_mut(4).zip(&inputs[4..Self::NUM_INPUTS]) {
+            bytes.copy_from_slice(&to_u32(*limb)?.to_be_bytes());
+        }
+
+        Ok(Self::new(bridge_faucet_id, destination_chain, destination_address))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the faucet at which the bridged asset is burned.
+    pub fn bridge_faucet_id(&self) -> AccountId {
+        self.bridge_faucet_id
+    }
+
+    /// Returns the identifier of the chain to which the asset is bridged.
+    pub fn destination_chain(&self) -> u32 {
+        self.destination_chain
+    }
+
+    /// Returns the address on the destination chain at which the asset is released.
+    pub fn destination_address(&self) -> &[u8; 32] {
+        &self.destination_address
+    }
+
+    /// Returns the note inputs encoding this message.
+    pub fn to_inputs(&self) -> Vec<Felt> {
+        let mut inputs = Vec::with_capacity(Self::NUM_INPUTS);
+        inputs.extend_from_slice(&[
+            self.bridge_faucet_id.into(),
+            self.destination_chain.into(),
+            ZERO,
+            ZERO,
+        ]);
+        for bytes in self.destination_address.chunks(4) {
+            let limb = u32::from_be_bytes(bytes.try_into().expect("chunk is 4 bytes long"));
+            inputs.push(limb.into());
+        }
+        inputs
+    }
+}
+
+// BRIDGE EVENT
+// ================================================================================================
+
+/// An event emitted by the BRIDGE note script once the asset of the note was burned at the bridge
+/// faucet.
+///
+/// The event is emitted in the miden-lib namespace (see [EventNamespace::MidenLib]) with the stack
+/// `[ASSET, MESSAGE_0, MESSAGE_1, MESSAGE_2, ...]`, where `MESSAGE_i` are the words of the note
+/// inputs encoding the [BridgeMessage]. Thus, the relayer of the bridge can pick up the burned
+/// asset and its destination via an event handler attached to the transaction host of the faucet,
+/// without inspecting the input notes of the transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BridgeEvent {
+    asset: FungibleAsset,
+    message: BridgeMessage,
+}
+
+impl BridgeEvent {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Index of the bridge event within the miden-lib event namespace.
+    pub const EVENT_INDEX: u16 = 0;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns the bridge event decoded from the state of the operand stack at the time the event
+    /// was emitted (ordered from the top of the stack).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The stack does not hold a fungible asset followed by a valid bridge message.
+    /// - The asset was not issued by the bridge faucet of the message.
+    pub fn from_stack(stack: &[Felt]) -> Result<Self, NoteError> {
+        if stack.len() < 4 + BridgeMessage::NUM_INPUTS {
+            return Err(NoteError::InvalidBridgeMessage(format!(
+                "expected at least {} stack elements, but got {}",
+                4 + BridgeMessage::NUM_INPUTS,
+                stack.len()
+            )));
+        }
+
+        // words are laid out on the stack with their first element deepest
+        let mut words = stack[..4 + BridgeMessage::NUM_INPUTS]
+            .chunks_exact(4)
+            .map(|word| [word[3], word[2], word[1], word[0]]);
+
+        let asset = words.next().expect("stack holds the asset word");
+        let asset = FungibleAsset::try_from(asset)
+            .map_err(|err| NoteError::InvalidBridgeMessage(format!("{err}")))?;
+        let message = BridgeMessage::from_inputs(&words.flatten().collect::<Vec<_>>())?;
+        if asset.faucet_id() != message.bridge_faucet_id() {
+            return Err(NoteError::BridgeFaucetMismatch(
+                message.bridge_faucet_id(),
+                asset.faucet_id(),
+            ));
+        }
+
+        Ok(Self { asset, message })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the bridge event.
+    pub fn event_id() -> EventId {
+        EventId::new(EventNamespace::MidenLib, Self::EVENT_INDEX)
+    }
+
+    /// Returns the asset burned at the bridge faucet.
+    pub fn asset(&self) -> FungibleAsset {
+        self.asset
+    }
+
+    /// Returns the message describing where the burned asset should be released.
+    pub fn message(&self) -> &BridgeMessage {
+        &self.message
+    }
+}
+
+// BRIDGE NOTE
+// ================================================================================================
+
+/// Creates a BRIDGE note which burns the specified asset at the bridge faucet of the message.
+///
+/// The note must be created by the owner of the asset in a transaction (e.g., via the
+/// `send_asset` procedure of the basic wallet with the recipient computed via
+/// [Note::recipient()]). The note is tagged for the bridge faucet, such that the operator of the
+/// faucet can discover and consume it.
+///
+/// # Errors
+/// Returns an error if the asset was not issued by the bridge faucet of the message.
+pub fn create_bridge_note(
+    asset: FungibleAsset,
+    message: BridgeMessage,
+    sender: AccountId,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    if asset.faucet_id() != message.bridge_faucet_id() {
+        return Err(NoteError::BridgeFaucetMismatch(message.bridge_faucet_id(), asset.faucet_id()));
+    }
+
+    create_note(
+        Script::BRIDGE { message },
+        vec![asset.into()],
+        sender,
+        Some(NoteTag::for_account(message.bridge_faucet_id())),
+        serial_num,
+    )
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_u32(value: Felt) -> Result<u32, NoteError> {
+    u32::try_from(value.as_int())
+        .map_err(|_| NoteError::InvalidBridgeMessage(format!("{value} is not a u32 value")))
+}
@@ -0,0 +1,265 @@
+use miden_objects::{
+    assembly::ProgramAst,
+    notes::NoteScript,
+    utils::{
+        collections::{BTreeSet, Vec},
+        format,
+        string::String,
+        vec,
+    },
+    NoteError,
+};
+
+use super::TransactionKernel;
+
+// NOTE SCRIPT COMPONENT
+// ================================================================================================
+
+/// A reusable part of a note script.
+///
+/// Every component is compiled into a separate procedure of the composed note script. A component
+/// consumes a fixed number of note inputs: when the procedure of a component is executed, the
+/// inputs of the component are on the top of the stack, with the first input on top of the stack.
+/// The procedure must consume these inputs and must leave the rest of the stack unchanged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NoteScriptComponent {
+    /// Fails unless the block number of the transaction reference block is greater than or equal
+    /// to the block number provided via the note inputs.
+    ///
+    /// Inputs: `[unlock_block_num]`
+    Timelock,
+
+    /// Fails unless the ID of the executing account is equal to the account ID provided via the
+    /// note inputs.
+    ///
+    /// Inputs: `[target_account_id]`
+    TargetAccount,
+
+    /// Fails unless the executing account is the sender of the note.
+    ///
+    /// Inputs: `[]`
+    SenderOnly,
+
+    /// Adds all assets of the note to the executing account via the `receive_asset` procedure of
+    /// the basic wallet.
+    ///
+    /// Inputs: `[]`
+    ReceiveAssets,
+
+    /// A custom component defined by the body of its procedure.
+    ///
+    /// `imports` are `use` statements required by the body (e.g., `use.miden::account`), and
+    /// `num_inputs` is the number of note inputs consumed by the body.
+    Custom {
+        imports: Vec<String>,
+        body: String,
+        num_inputs: usize,
+    },
+}
+
+impl NoteScriptComponent {
+    /// Returns the number of note inputs consumed by this component.
+    pub fn num_inputs(&self) -> usize {
+        match self {
+            Self::Timelock | Self::TargetAccount => 1,
+            Self::SenderOnly | Self::ReceiveAssets => 0,
+            Self::Custom { num_inputs, .. } => *num_inputs,
+        }
+    }
+
+    /// Returns the `use` statements required by this component.
+    fn imports(&self) -> Vec<&str> {
+        match self {
+            Self::Timelock => vec!["use.miden::tx"],
+            Self::TargetAccount => vec!["use.miden::account"],
+            Self::SenderOnly => vec!["use.miden::account", "use.miden::note"],
+            Self::ReceiveAssets => {
+                vec!["use.miden::note", "use.miden::contracts::wallets::basic->wallet"]
+            },
+            Self::Custom { imports, .. } => imports.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Returns the body of the procedure of this component.
+    fn body(&self) -> &str {
+        match self {
+            Self::Timelock => TIMELOCK_BODY,
+            Self::TargetAccount => TARGET_ACCOUNT_BODY,
+            Self::SenderOnly => SENDER_ONLY_BODY,
+            Self::ReceiveAssets => RECEIVE_ASSETS_BODY,
+            Self::Custom { body, .. } => body,
+        }
+    }
+}
+
+const TIMELOCK_BODY: &str = "
+    # => [unlock_block_num]
+    exec.tx::get_block_number
+    # => [block_num, unlock_block_num]
+    u32assert2 u32lte assert
+";
+
+const TARGET_ACCOUNT_BODY: &str = "
+    # => [target_account_id]
+    exec.account::get_id assert_eq
+";
+
+const SENDER_ONLY_BODY: &str = "
+    exec.note::get_sender exec.account::get_id assert_eq
+";
+
+const RECEIVE_ASSETS_BODY: &str = "
+    push.0 exec.note::get_assets
+    # => [num_of_assets, 0 = ptr]
+
+    # compute the pointer at which we should stop iterating
+    dup.1 add
+    # => [end_ptr, ptr]
+
+    # pad the stack and move the pointer to the top
+    padw movup.5
+    # => [ptr, 0, 0, 0, 0, end_ptr]
+
+    # compute the loop latch
+    dup dup.6 neq
+    # => [latch, ptr, 0, 0, 0, 0, end_ptr]
+
+    while.true
+        # save the pointer so that we can use it later
+        dup movdn.5
+        # => [ptr, 0, 0, 0, 0, ptr, end_ptr]
+
+        # load the asset and add it to the account
+        mem_loadw call.wallet::receive_asset
+        # => [ASSET, ptr, end_ptr]
+
+        # increment the pointer and compare it to the end_ptr
+        movup.4 add.1 dup dup.6 neq
+        # => [latch, ptr+1, ASSET, end_ptr]
+    end
+
+    # clear the stack
+    drop dropw drop
+";
+
+// NOTE SCRIPT COMPOSER
+// ================================================================================================
+
+/// Builds a note script out of a sequence of [NoteScriptComponent]s.
+///
+/// The components are executed in the order in which they were added, and the note can be
+/// consumed only if all of the components succeed. For example, a time-locked pay-to-id note can
+/// be composed as follows:
+///
+/// ```ignore
+/// let script = NoteScriptComposer::new()
+///     .then(NoteScriptComponent::Timelock)
+///     .then(NoteScriptComponent::TargetAccount)
+///     .then(NoteScriptComponent::ReceiveAssets)
+///     .compile()?;
+/// ```
+///
+/// The inputs of the composed script are the concatenation of the inputs of its components, in
+/// the order of the components (i.e., `[unlock_block_num, target_account_id]` in the example
+/// above).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NoteScriptComposer {
+    components: Vec<NoteScriptComponent>,
+}
+
+impl NoteScriptComposer {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new composer without any components.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the specified component to the sequence of components of the script.
+    pub fn then(mut self, component: NoteScriptComponent) -> Self {
+        self.components.push(component);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the components of the script in the order of execution.
+    pub fn components(&self) -> &[NoteScriptComponent] {
+        &self.components
+    }
+
+    /// Returns the total number of note inputs consumed by the components of the script.
+    pub fn num_inputs(&self) -> usize {
+        self.components.iter().map(NoteScriptComponent::num_inputs).sum()
+    }
+
+    /// Returns the source code of the composed note script.
+    pub fn source(&self) -> String {
+        let imports = self
+            .components
+            .iter()
+            .flat_map(NoteScriptComponent::imports)
+            .chain(core::iter::once("use.miden::note"))
+            .collect::<BTreeSet<_>>();
+
+        let mut source = String::new();
+        for import in imports {
+            source.push_str(import);
+            source.push('\n');
+        }
+
+        for (idx, component) in self.components.iter().enumerate() {
+            source.push_str(&format!("\nproc.component_{idx}\n{}end\n", component.body()));
+        }
+
+        // drop the script root, and then execute the components in order; before executing a
+        // component, its inputs are loaded from memory and pushed onto the stack such that the
+        // first input is on top
+        source.push_str("\nbegin\n    dropw\n");
+        let mut offset = 0;
+        for (idx, component) in self.components.iter().enumerate() {
+            let num_inputs = component.num_inputs();
+            if num_inputs > 0 {
+                source.push_str("    push.0 exec.note::get_inputs drop\n");
+                for input_idx in (offset..offset + num_inputs).rev() {
+                    source.push_str(&load_input(input_idx));
+                }
+            }
+            source.push_str(&format!("    exec.component_{idx}\n"));
+            offset += num_inputs;
+        }
+        source.push_str("end\n");
+
+        source
+    }
+
+    /// Compiles the composed note script.
+    ///
+    /// # Errors
+    /// Returns an error if the source of the composed script could not be parsed or compiled,
+    /// e.g., because of an invalid custom component.
+    pub fn compile(&self) -> Result<NoteScript, NoteError> {
+        let ast = ProgramAst::parse(&self.source())
+            .map_err(|err| NoteError::ScriptCompilationError(err.into()))?;
+        let (script, _) = NoteScript::new(ast, &TransactionKernel::assembler())?;
+        Ok(script)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the instructions which push the note input with the specified index onto the stack,
+/// assuming the note inputs were loaded into memory starting at address 0.
+fn load_input(input_idx: usize) -> String {
+    let addr = input_idx / 4;
+    // mem_loadw puts the last element of the word on top of the stack
+    let depth = 3 - input_idx % 4;
+    let movup = if depth > 0 {
+        format!(" movup.{depth}")
+    } else {
+        String::new()
+    };
+    format!("    padw push.{addr} mem_loadw{movup} movdn.3 drop drop drop\n")
+}
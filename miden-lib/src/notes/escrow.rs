@@ -0,0 +1,148 @@
+use miden_objects::{
+    accounts::AccountId,
+    assets::Asset,
+    notes::Note,
+    utils::{collections::Vec, format},
+    Felt, Hasher, NoteError, StarkField, Word, ZERO,
+};
+
+use super::{create_note, Script};
+
+// ESCROW
+// ================================================================================================
+
+/// The terms of an escrow note.
+///
+/// The assets of an escrow note can be claimed either by the beneficiary, if the arbiter signed
+/// the release of the note, or by the sender of the note once the reference block of the
+/// consuming transaction is at or after the deadline. The roles are supported as follows:
+/// - The sender creates the note via [Escrow::create_note()], and can check when the note can be
+///   reclaimed via [Escrow::is_reclaimable()].
+/// - The arbiter recovers the terms of a note via [Escrow::from_note()], and signs the message
+///   returned by [Escrow::release_message()] with its Falcon secret key.
+/// - The beneficiary passes the signature of the arbiter to the consuming transaction via its
+///   transaction script inputs, using the entry returned by [Escrow::release_inputs()].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Escrow {
+    beneficiary: AccountId,
+    arbiter_pub_key: Word,
+    deadline: u32,
+}
+
+impl Escrow {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns new escrow terms releasing the assets to `beneficiary` upon a signature of the
+    /// arbiter, or returning them to the sender at or after the `deadline` block.
+    ///
+    /// `arbiter_pub_key` is the hash of the Falcon public key of the arbiter.
+    pub fn new(beneficiary: AccountId, arbiter_pub_key: Word, deadline: u32) -> Self {
+        Self { beneficiary, arbiter_pub_key, deadline }
+    }
+
+    /// Returns the escrow terms of the specified note.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The note script is not the ESCROW script.
+    /// - The note inputs are not a valid encoding of escrow terms.
+    pub fn from_note(note: &Note) -> Result<Self, NoteError> {
+        if super::standard_script_kind(&note.script().hash()) != Some("ESCROW") {
+            return Err(NoteError::InvalidEscrowTerms("not an escrow note".into()));
+        }
+
+        let inputs = note.inputs().inputs();
+        let beneficiary = AccountId::try_from(inputs[0].as_int())
+            .map_err(|err| NoteError::InvalidEscrowTerms(format!("{err}")))?;
+        let deadline = u32::try_from(inputs[1].as_int())
+            .map_err(|_| NoteError::InvalidEscrowTerms("deadline is not a u32 value".into()))?;
+        let arbiter_pub_key = [inputs[4], inputs[5], inputs[6], inputs[7]];
+
+        Ok(Self::new(beneficiary, arbiter_pub_key, deadline))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the account which can claim the assets upon release by the arbiter.
+    pub fn beneficiary(&self) -> AccountId {
+        self.beneficiary
+    }
+
+    /// Returns the hash of the Falcon public key of the arbiter.
+    pub fn arbiter_pub_key(&self) -> Word {
+        self.arbiter_pub_key
+    }
+
+    /// Returns the block number at which the sender can reclaim the assets.
+    pub fn deadline(&self) -> u32 {
+        self.deadline
+    }
+
+    /// Returns the note inputs encoding these terms.
+    pub fn to_inputs(&self) -> Vec<Felt> {
+        let mut inputs = Vec::with_capacity(8);
+        inputs.extend_from_slice(&self.terms_word());
+        inputs.extend_from_slice(&self.arbiter_pub_key);
+        inputs
+    }
+
+    // SENDER
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an escrow note with these terms carrying the specified assets.
+    ///
+    /// The note is created without a tag; the sender is expected to share the details of the note
+    /// with the beneficiary and the arbiter off-chain.
+    ///
+    /// # Errors
+    /// Returns an error if the note could not be created.
+    pub fn create_note(
+        &self,
+        assets: Vec<Asset>,
+        sender: AccountId,
+        serial_num: Word,
+    ) -> Result<Note, NoteError> {
+        create_note(Script::ESCROW { escrow: *self }, assets, sender, None, serial_num)
+    }
+
+    /// Returns true if the sender can reclaim the note in a transaction against the specified
+    /// reference block.
+    pub fn is_reclaimable(&self, block_num: u32) -> bool {
+        block_num >= self.deadline
+    }
+
+    // ARBITER
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the message which the arbiter must sign to release the escrow note with the
+    /// specified serial number to the beneficiary, i.e., hash(serial_num, [beneficiary, deadline,
+    /// 0, 0]).
+    pub fn release_message(&self, serial_num: Word) -> Word {
+        Hasher::merge(&[serial_num.into(), self.terms_word().into()]).into()
+    }
+
+    // BENEFICIARY
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the advice map entry providing the release signature of the arbiter to the escrow
+    /// note with the specified serial number. The entry is expected to be passed to the
+    /// transaction consuming the note via its transaction script inputs.
+    ///
+    /// `signature` must be in the format expected by the `rpo_falcon512::verify` procedure of the
+    /// Miden standard library (e.g., as produced by `AdviceProvider::get_signature()` from the
+    /// secret key of the arbiter).
+    pub fn release_inputs(&self, serial_num: Word, signature: Vec<Felt>) -> (Word, Vec<Felt>) {
+        let message = self.release_message(serial_num);
+        let key = Hasher::merge(&[self.arbiter_pub_key.into(), message.into()]);
+        (key.into(), signature)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the first word of the note inputs, i.e., [beneficiary, deadline, 0, 0].
+    fn terms_word(&self) -> Word {
+        [self.beneficiary.into(), self.deadline.into(), ZERO, ZERO]
+    }
+}
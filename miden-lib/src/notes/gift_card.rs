@@ -0,0 +1,164 @@
+use miden_objects::{
+    accounts::AccountId,
+    assets::FungibleAsset,
+    notes::Note,
+    utils::{collections::Vec, vec},
+    Felt, Hasher, NoteError, Word, ZERO,
+};
+
+use super::{create_note, Script};
+
+// GIFT CARD
+// ================================================================================================
+
+/// The state of a gift card, i.e., a note holding a fungible asset from which the holder can
+/// withdraw at most `max_withdrawal` units per consumption.
+///
+/// Every time a gift card note is consumed with a partial withdrawal, the note script re-issues
+/// the note holding the remaining balance, with the usage counter incremented by one. The serial
+/// number of the re-issued note is derived from the serial number of the consumed note, such that
+/// the chain of notes can be followed only by the holder of the gift card:
+///
+/// ```ignore
+/// let card = GiftCard::new(asset, 10, sender, serial_num)?;
+/// let note = card.note()?;
+/// // ... create the note, and send the gift card to the holder ...
+///
+/// // the holder withdraws 7 units by consuming the note with the following script inputs
+/// let script_inputs = vec![card.withdrawal_inputs(7)];
+/// // the consuming transaction re-issues the note, which can be consumed again
+/// let card = card.withdraw(7, holder_account_id)?.expect("balance is not exhausted");
+/// let note = card.note()?;
+/// ```
+///
+/// Gift card notes are created without a tag and without a memo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GiftCard {
+    asset: FungibleAsset,
+    max_withdrawal: u64,
+    usage_count: u32,
+    sender: AccountId,
+    serial_num: Word,
+}
+
+impl GiftCard {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [GiftCard] holding the specified asset and allowing at most `max_withdrawal`
+    /// units of the asset to be withdrawn per consumption.
+    ///
+    /// `sender` is the account creating the gift card note, and `serial_num` must be generated
+    /// from a secure source of randomness.
+    ///
+    /// # Errors
+    /// Returns an error if `max_withdrawal` is zero or greater than the maximum amount of a
+    /// fungible asset.
+    pub fn new(
+        asset: FungibleAsset,
+        max_withdrawal: u64,
+        sender: AccountId,
+        serial_num: Word,
+    ) -> Result<Self, NoteError> {
+        if max_withdrawal == 0 || max_withdrawal > FungibleAsset::MAX_AMOUNT {
+            return Err(NoteError::InvalidGiftCardLimit(max_withdrawal));
+        }
+
+        Ok(Self {
+            asset,
+            max_withdrawal,
+            usage_count: 0,
+            sender,
+            serial_num,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the asset remaining on this gift card.
+    pub fn asset(&self) -> FungibleAsset {
+        self.asset
+    }
+
+    /// Returns the maximum amount which can be withdrawn per consumption.
+    pub fn max_withdrawal(&self) -> u64 {
+        self.max_withdrawal
+    }
+
+    /// Returns the number of times the gift card was consumed.
+    pub fn usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    /// Returns the ID of the account which created the current gift card note.
+    pub fn sender(&self) -> AccountId {
+        self.sender
+    }
+
+    /// Returns the serial number of the current gift card note.
+    pub fn serial_num(&self) -> Word {
+        self.serial_num
+    }
+
+    /// Returns the current gift card note.
+    ///
+    /// # Errors
+    /// Returns an error if the note could not be created.
+    pub fn note(&self) -> Result<Note, NoteError> {
+        create_note(
+            Script::GIFT_CARD {
+                max_withdrawal: self.max_withdrawal,
+                usage_count: self.usage_count,
+            },
+            vec![self.asset.into()],
+            self.sender,
+            None,
+            self.serial_num,
+        )
+    }
+
+    /// Returns the advice map entry instructing the gift card note to withdraw the specified
+    /// amount. The entry is expected to be passed to the consuming transaction via its
+    /// transaction script inputs.
+    pub fn withdrawal_inputs(&self, amount: u64) -> (Word, Vec<Felt>) {
+        (self.serial_num, vec![Felt::new(amount)])
+    }
+
+    // STATE TRANSITIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the state of the gift card after the specified amount is withdrawn by the
+    /// `consumer` account, or None if the whole balance is withdrawn.
+    ///
+    /// The returned state describes the note re-issued by the consuming transaction (i.e., the
+    /// note created by the `consumer` account).
+    ///
+    /// # Errors
+    /// Returns an error if the amount is greater than the maximum withdrawal or the balance of
+    /// the gift card.
+    pub fn withdraw(&self, amount: u64, consumer: AccountId) -> Result<Option<Self>, NoteError> {
+        if amount > self.max_withdrawal || amount > self.asset.amount() {
+            return Err(NoteError::InvalidGiftCardWithdrawal(amount));
+        }
+
+        let remaining = self.asset.amount() - amount;
+        if remaining == 0 {
+            return Ok(None);
+        }
+
+        let usage_count = self.usage_count + 1;
+        let serial_num = Hasher::merge(&[
+            self.serial_num.into(),
+            [Felt::from(usage_count), ZERO, ZERO, ZERO].into(),
+        ]);
+
+        Ok(Some(Self {
+            asset: FungibleAsset::new(self.asset.faucet_id(), remaining)
+                .expect("remaining amount is smaller than the current amount"),
+            max_withdrawal: self.max_withdrawal,
+            usage_count,
+            sender: consumer,
+            serial_num: serial_num.into(),
+        }))
+    }
+}
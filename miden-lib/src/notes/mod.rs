@@ -2,91 +2,259 @@ use miden_objects::{
     accounts::AccountId,
     assembly::ProgramAst,
     assets::Asset,
-    notes::{Note, NoteScript},
+    notes::{Note, NoteFile, NoteScript, NoteTag},
     utils::{collections::Vec, vec},
-    Digest, Felt, Hasher, NoteError, Word, ZERO,
+    Digest, Felt, Hasher, NoteError, Word, WORD_SIZE, ZERO,
 };
 
 use super::transaction::TransactionKernel;
 
+mod bridge;
+pub use bridge::{create_bridge_note, BridgeEvent, BridgeMessage};
+
+mod composition;
+pub use composition::{NoteScriptComponent, NoteScriptComposer};
+
+mod escrow;
+pub use escrow::Escrow;
+
+mod gift_card;
+pub use gift_card::GiftCard;
+
+mod oracle;
+pub use oracle::OracleConstraint;
+
+mod template;
+pub use template::{NoteInputDescriptor, NoteInputType, NoteInputsBuilder, NoteScriptTemplate};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The use case ID of the tags of the SWAP notes created via [create_swap_note()].
+pub const SWAP_USE_CASE_ID: u16 = 0;
+
 // STANDARDIZED SCRIPTS
 // ================================================================================================
 
 pub enum Script {
-    P2ID { target: AccountId },
-    P2IDR { target: AccountId, recall_height: u32 },
-    SWAP { asset: Asset, serial_num: Word },
+    P2ID {
+        target: AccountId,
+    },
+    P2IDR {
+        target: AccountId,
+        recall_height: u32,
+    },
+    SWAP {
+        asset: Asset,
+        serial_num: Word,
+        oracle: Option<OracleConstraint>,
+    },
+    BEARER,
+    BRIDGE {
+        message: BridgeMessage,
+    },
+    ESCROW {
+        escrow: Escrow,
+    },
+    #[allow(non_camel_case_types)]
+    GIFT_CARD {
+        max_withdrawal: u64,
+        usage_count: u32,
+    },
 }
 
-/// Users can create notes with a standard script. Atm we provide three standard scripts:
+/// Users can create notes with a standard script. Atm we provide seven standard scripts:
 /// 1. P2ID - pay to id.
 /// 2. P2IDR - pay to id with recall after a certain block height.
-/// 3. SWAP - swap of assets between two accounts.
+/// 3. SWAP - swap of assets between two accounts. If an [OracleConstraint] is provided, the swap
+///    can be executed only if the price published by the oracle account satisfies the constraint.
+/// 4. BEARER - pay to whoever knows the details of the note (see [create_bearer_note()]).
+/// 5. BRIDGE - burn an asset at a bridge faucet to release it on another chain (see
+///    [create_bridge_note()]).
+/// 6. ESCROW - release to a beneficiary upon a signature of an arbiter, or reclaim by the sender
+///    after a deadline (see [Escrow]).
+/// 7. GIFT_CARD - withdraw a limited amount per consumption, re-issuing the note with the
+///    remaining balance (see [GiftCard]).
+///
+/// If no tag is provided, the note is created with a zero tag. Tags should be built via the
+/// [NoteTag] constructors (e.g., [NoteTag::for_account()]).
+///
+/// P2ID, P2IDR and SWAP notes with standard tags can be created via [create_p2id_note()],
+/// [create_p2idr_note()] and [create_swap_note()] respectively.
 pub fn create_note(
     script: Script,
     assets: Vec<Asset>,
     sender: AccountId,
-    tag: Option<Felt>,
+    tag: Option<NoteTag>,
     serial_num: Word,
 ) -> Result<Note, NoteError> {
-    let note_assembler = TransactionKernel::assembler();
-
     // Include the binary version of the scripts into the source file at compile time
     let p2id_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/P2ID.masb"));
     let p2idr_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/P2IDR.masb"));
     let swap_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/SWAP.masb"));
+    let swap_oracle_bytes =
+        include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/SWAP_ORACLE.masb"));
+    let bearer_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/BEARER.masb"));
+    let bridge_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/BRIDGE.masb"));
+    let escrow_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/ESCROW.masb"));
+    let gift_card_bytes =
+        include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/GIFT_CARD.masb"));
 
-    let (note_script_ast, inputs): (ProgramAst, Vec<Felt>) = match script {
-        Script::P2ID { target } => (
-            ProgramAst::from_bytes(p2id_bytes).map_err(NoteError::NoteDeserializationError)?,
-            vec![target.into(), ZERO, ZERO, ZERO],
-        ),
-        Script::P2IDR { target, recall_height } => (
-            ProgramAst::from_bytes(p2idr_bytes).map_err(NoteError::NoteDeserializationError)?,
-            vec![target.into(), recall_height.into(), ZERO, ZERO],
-        ),
-        Script::SWAP { asset, serial_num } => {
-            let recipient = build_p2id_recipient(sender, serial_num)?;
+    let (script_bytes, inputs): (&[u8], Vec<Felt>) = match script {
+        Script::P2ID { target } => (p2id_bytes, vec![target.into(), ZERO, ZERO, ZERO]),
+        Script::P2IDR { target, recall_height } => {
+            (p2idr_bytes, vec![target.into(), recall_height.into(), ZERO, ZERO])
+        },
+        Script::SWAP { asset, serial_num, oracle } => {
             let asset_word: Word = asset.into();
-            (
-                ProgramAst::from_bytes(swap_bytes).map_err(NoteError::NoteDeserializationError)?,
-                vec![
-                    recipient[0],
-                    recipient[1],
-                    recipient[2],
-                    recipient[3],
-                    asset_word[0],
-                    asset_word[1],
-                    asset_word[2],
-                    asset_word[3],
-                    sender.into(),
-                    ZERO,
-                    ZERO,
-                    ZERO,
-                ],
-            )
+            let tag = NoteTag::for_account(sender);
+            let mut inputs = Vec::with_capacity(4 * WORD_SIZE);
+            let script_bytes = match oracle {
+                // the SWAP_ORACLE script expects the recipient of the payback note
+                Some(oracle) => {
+                    inputs.extend(build_p2id_recipient(sender, serial_num)?.as_elements());
+                    inputs.extend(asset_word);
+                    inputs.extend(oracle.to_input_word(tag));
+                    &swap_oracle_bytes[..]
+                },
+                // the SWAP script builds the recipient of the payback note via the transaction
+                // kernel, such that the payback note can be recovered by the transaction host
+                None => {
+                    inputs.extend(serial_num);
+                    inputs.extend(asset_word);
+                    inputs.extend([tag.into(), ZERO, ZERO, ZERO]);
+                    inputs.extend(p2id_script_hash()?.as_elements());
+                    &swap_bytes[..]
+                },
+            };
+            (script_bytes, inputs)
+        },
+        Script::BEARER => (bearer_bytes, vec![]),
+        Script::BRIDGE { message } => (bridge_bytes, message.to_inputs()),
+        Script::ESCROW { escrow } => (escrow_bytes, escrow.to_inputs()),
+        Script::GIFT_CARD { max_withdrawal, usage_count } => {
+            (gift_card_bytes, vec![Felt::new(max_withdrawal), usage_count.into(), ZERO, ZERO])
         },
     };
 
-    let (note_script, _) = NoteScript::new(note_script_ast, &note_assembler)?;
+    let note_script = compile_note_script(script_bytes)?;
 
-    Note::new(note_script.clone(), &inputs, &assets, serial_num, sender, tag.unwrap_or(ZERO))
+    Note::new(note_script, &inputs, &assets, serial_num, sender, tag.unwrap_or_default())
 }
 
-/// Utility function generating RECIPIENT for the P2ID note script created by the SWAP script
-fn build_p2id_recipient(target: AccountId, serial_num: Word) -> Result<Digest, NoteError> {
-    // TODO: add lazy_static initialization or compile-time optimization instead of re-generating
-    // the script hash every time we call the SWAP script
-    let assembler = TransactionKernel::assembler();
+// STANDARD NOTE CONSTRUCTORS
+// ================================================================================================
 
-    let p2id_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/P2ID.masb"));
+/// Creates a P2ID note which transfers the specified assets from the sender to the `target`
+/// account.
+///
+/// The note is tagged for the target account (see [NoteTag::for_account()]), such that the
+/// target can discover it. The note must be created by the sender in a transaction (e.g., via
+/// the `send_asset` procedure of the basic wallet with the recipient computed via
+/// [Note::recipient()]).
+///
+/// # Errors
+/// Returns an error if the note could not be created (e.g., if the assets are invalid).
+pub fn create_p2id_note(
+    sender: AccountId,
+    target: AccountId,
+    assets: Vec<Asset>,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    create_note(
+        Script::P2ID { target },
+        assets,
+        sender,
+        Some(NoteTag::for_account(target)),
+        serial_num,
+    )
+}
 
-    let note_script_ast =
-        ProgramAst::from_bytes(p2id_bytes).map_err(NoteError::NoteDeserializationError)?;
+/// Creates a P2IDR note which transfers the specified assets from the sender to the `target`
+/// account, and which can be reclaimed by the sender once the block height reaches
+/// `recall_height`.
+///
+/// The note is tagged for the target account (see [NoteTag::for_account()]).
+///
+/// # Errors
+/// Returns an error if the note could not be created (e.g., if the assets are invalid).
+pub fn create_p2idr_note(
+    sender: AccountId,
+    target: AccountId,
+    assets: Vec<Asset>,
+    recall_height: u32,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    create_note(
+        Script::P2IDR { target, recall_height },
+        assets,
+        sender,
+        Some(NoteTag::for_account(target)),
+        serial_num,
+    )
+}
+
+/// Creates a SWAP note which offers `offered_asset` in exchange for `requested_asset`.
+///
+/// The account consuming the note receives the offered asset, and must pay the requested asset
+/// back to the sender. The payment is made via a P2ID note created by the SWAP script, with
+/// `payback_serial_num` as its serial number; thus, the sender can compute the details of the
+/// payback note in advance and consume it once it is created. The payback note is tagged for the
+/// sender (see [NoteTag::for_account()]).
+///
+/// The note is tagged with the [SWAP_USE_CASE_ID] use case, and the payload of the tag contains
+/// the 8 most significant bits of the IDs of the faucets of the offered and the requested assets
+/// (in this order), such that the note can be discovered by the accounts interested in the
+/// traded pair.
+///
+/// # Errors
+/// Returns an error if the note could not be created (e.g., if the assets are invalid).
+pub fn create_swap_note(
+    sender: AccountId,
+    offered_asset: Asset,
+    requested_asset: Asset,
+    serial_num: Word,
+    payback_serial_num: Word,
+) -> Result<Note, NoteError> {
+    let faucet_bits = |asset: Asset| (u64::from(asset.faucet_id()) >> 56) as u16;
+    let payload = (faucet_bits(offered_asset) << 8) | faucet_bits(requested_asset);
+    let tag = NoteTag::for_local_execution(SWAP_USE_CASE_ID, payload)?;
+
+    create_note(
+        Script::SWAP {
+            asset: requested_asset,
+            serial_num: payback_serial_num,
+            oracle: None,
+        },
+        vec![offered_asset],
+        sender,
+        Some(tag),
+        serial_num,
+    )
+}
 
-    let (note_script, _) = NoteScript::new(note_script_ast, &assembler)?;
+/// Creates a bearer note carrying the specified assets and returns it packaged into a [NoteFile].
+///
+/// A bearer note can be consumed by any account which knows the details of the note. Thus, the
+/// returned note file is the secret granting the right to claim the assets (e.g., it can be
+/// encoded into a gift link via [NoteFile::to_hex()]), and `serial_num` must be generated from a
+/// secure source of randomness. The note is created without a tag, so that it cannot be linked to
+/// its consumer.
+///
+/// The note must be created by the sender in a transaction (with the recipient computed via
+/// [Note::recipient()]), and the note file must be shared with the consumer only off-chain.
+pub fn create_bearer_note(
+    assets: Vec<Asset>,
+    sender: AccountId,
+    serial_num: Word,
+) -> Result<NoteFile, NoteError> {
+    let note = create_note(Script::BEARER, assets, sender, None, serial_num)?;
+    Ok(NoteFile::from(note))
+}
 
-    let script_hash = note_script.hash();
+/// Utility function generating RECIPIENT for the P2ID note script created by the SWAP script
+fn build_p2id_recipient(target: AccountId, serial_num: Word) -> Result<Digest, NoteError> {
+    let script_hash = p2id_script_hash()?;
 
     let serial_num_hash = Hasher::merge(&[serial_num.into(), Digest::default()]);
 
@@ -97,3 +265,91 @@ fn build_p2id_recipient(target: AccountId, serial_num: Word) -> Result<Digest, N
         Hasher::hash_elements(&[target.into(), ZERO, ZERO, ZERO]),
     ]))
 }
+
+fn p2id_script_hash() -> Result<Digest, NoteError> {
+    // TODO: add lazy_static initialization or compile-time optimization instead of re-generating
+    // the script hash every time we call the SWAP script
+    let p2id_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/P2ID.masb"));
+
+    Ok(compile_note_script(p2id_bytes)?.hash())
+}
+
+/// Deserializes the note script from the provided binary and compiles it against the transaction
+/// kernel.
+fn compile_note_script(script_bytes: &[u8]) -> Result<NoteScript, NoteError> {
+    let note_script_ast =
+        ProgramAst::from_bytes(script_bytes).map_err(NoteError::NoteDeserializationError)?;
+    let (note_script, _) = NoteScript::new(note_script_ast, &TransactionKernel::assembler())?;
+
+    Ok(note_script)
+}
+
+// STANDARD SCRIPT RECOGNITION
+// ================================================================================================
+
+/// Returns the name of the standard note script (i.e., "P2ID", "P2IDR", "SWAP", "SWAP_ORACLE",
+/// "BEARER", "BRIDGE", "ESCROW", or "GIFT_CARD") with the specified MAST root, or None if the root
+/// does not belong to any of the standard scripts.
+///
+/// This can be used to label notes in transaction descriptions, e.g.:
+/// `executed_tx.description().with_script_kinds(standard_script_kind)`.
+pub fn standard_script_kind(script_root: &Digest) -> Option<&'static str> {
+    #[cfg(feature = "std")]
+    let roots = {
+        static STANDARD_SCRIPT_ROOTS: std::sync::OnceLock<[(&str, Digest); 8]> =
+            std::sync::OnceLock::new();
+        STANDARD_SCRIPT_ROOTS.get_or_init(compile_standard_script_roots)
+    };
+    #[cfg(not(feature = "std"))]
+    let roots = &compile_standard_script_roots();
+
+    roots.iter().find(|(_, root)| root == script_root).map(|(kind, _)| *kind)
+}
+
+/// Compiles the standard note scripts and returns their names together with their MAST roots.
+fn compile_standard_script_roots() -> [(&'static str, Digest); 8] {
+    let compile = |bytes: &[u8]| {
+        compile_note_script(bytes).expect("standard note script is well formed").hash()
+    };
+
+    [
+        (
+            "P2ID",
+            compile(include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/P2ID.masb"))),
+        ),
+        (
+            "P2IDR",
+            compile(include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/P2IDR.masb"))),
+        ),
+        (
+            "SWAP",
+            compile(include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/SWAP.masb"))),
+        ),
+        (
+            "SWAP_ORACLE",
+            compile(include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/assets/note_scripts/SWAP_ORACLE.masb"
+            ))),
+        ),
+        (
+            "BEARER",
+            compile(include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/BEARER.masb"))),
+        ),
+        (
+            "BRIDGE",
+            compile(include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/BRIDGE.masb"))),
+        ),
+        (
+            "ESCROW",
+            compile(include_bytes!(concat!(env!("OUT_DIR"), "/assets/note_scripts/ESCROW.masb"))),
+        ),
+        (
+            "GIFT_CARD",
+            compile(include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/assets/note_scripts/GIFT_CARD.masb"
+            ))),
+        ),
+    ]
+}
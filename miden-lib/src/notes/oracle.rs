@@ -0,0 +1,119 @@
+use miden_objects::{
+    accounts::{Account, AccountHeader, AccountId, AccountStorage},
+    crypto::merkle::{MerklePath, NodeIndex},
+    notes::NoteTag,
+    utils::collections::Vec,
+    Felt, NoteError, Word,
+};
+
+// ORACLE CONSTRAINT
+// ================================================================================================
+
+/// A constraint on the price published by an oracle account, which can be attached to a SWAP note.
+///
+/// A SWAP note with an oracle constraint can be consumed only if the price stored in the first
+/// element of the `price_slot` storage slot of the oracle account is greater than or equal to
+/// `min_price`. The price is read from the state of the oracle account as of the reference block
+/// of the consuming transaction.
+///
+/// Since the transaction kernel does not have access to the state of other accounts, the consumer
+/// of the note must provide a proof of the price via the transaction script inputs. The proof is
+/// built via [OracleConstraint::build_proof()].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleConstraint {
+    oracle_id: AccountId,
+    price_slot: u8,
+    min_price: Felt,
+}
+
+impl OracleConstraint {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Depth of the account database tree.
+    pub const ACCOUNT_DB_DEPTH: u8 = 64;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [OracleConstraint] requiring the price stored in the specified slot of the
+    /// oracle account to be greater than or equal to `min_price`.
+    pub fn new(oracle_id: AccountId, price_slot: u8, min_price: Felt) -> Self {
+        Self { oracle_id, price_slot, min_price }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the oracle account.
+    pub fn oracle_id(&self) -> AccountId {
+        self.oracle_id
+    }
+
+    /// Returns the index of the storage slot of the oracle account holding the price.
+    pub fn price_slot(&self) -> u8 {
+        self.price_slot
+    }
+
+    /// Returns the minimum price required for the note to be consumed.
+    pub fn min_price(&self) -> Felt {
+        self.min_price
+    }
+
+    /// Returns the word encoding this constraint in the inputs of a SWAP note with the specified
+    /// tag, i.e., `[tag, oracle_id, price_slot, min_price]`.
+    pub fn to_input_word(&self, tag: NoteTag) -> Word {
+        [tag.into(), self.oracle_id.into(), Felt::from(self.price_slot), self.min_price]
+    }
+
+    // PROOF BUILDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the advice map entry proving the price of the oracle account to a SWAP note with
+    /// this constraint created by `sender`.
+    ///
+    /// `oracle_account` must be the state of the oracle account as of the reference block of the
+    /// consuming transaction, and `account_path` must be the Merkle path of the oracle account in
+    /// the account database of that block. The returned entry is expected to be passed to the
+    /// consuming transaction via its transaction script inputs.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The ID of the provided account is not the ID of the oracle account.
+    /// - The depth of the provided account path is not 64.
+    pub fn build_proof(
+        &self,
+        sender: AccountId,
+        oracle_account: &Account,
+        account_path: &MerklePath,
+    ) -> Result<(Word, Vec<Felt>), NoteError> {
+        if oracle_account.id() != self.oracle_id {
+            return Err(NoteError::OracleAccountMismatch(self.oracle_id, oracle_account.id()));
+        }
+        if account_path.depth() != Self::ACCOUNT_DB_DEPTH {
+            return Err(NoteError::InvalidOracleAccountPathDepth(account_path.depth()));
+        }
+
+        let storage = oracle_account.storage();
+        let storage_index =
+            NodeIndex::new(AccountStorage::STORAGE_TREE_DEPTH, self.price_slot as u64)
+                .expect("slot index is within the storage tree");
+        let storage_path = storage
+            .slots()
+            .get_path(storage_index)
+            .expect("slot index is within the storage tree");
+
+        let mut proof = Vec::with_capacity(16 + 4 * (1 + storage_path.len() + account_path.len()));
+
+        // account header
+        proof.extend_from_slice(&AccountHeader::from(oracle_account).to_elements());
+
+        // path from the account hash to the account database root
+        account_path.iter().for_each(|node| proof.extend_from_slice(node.as_elements()));
+
+        // price slot value and path from the value to the storage root
+        proof.extend_from_slice(storage.get_item(self.price_slot).as_elements());
+        storage_path.iter().for_each(|node| proof.extend_from_slice(node.as_elements()));
+
+        Ok((self.to_input_word(NoteTag::for_account(sender)), proof))
+    }
+}
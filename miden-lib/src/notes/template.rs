@@ -0,0 +1,322 @@
+use miden_objects::{
+    notes::{NoteInputs, NoteScript},
+    transaction::ToInputElements,
+    utils::{
+        collections::Vec,
+        format,
+        string::{String, ToString},
+        vec,
+    },
+    Felt, NoteError, ZERO,
+};
+
+use super::compile_note_script;
+
+// NOTE INPUT TYPE
+// ================================================================================================
+
+/// The type of a single input of a note script, defining the number of elements the value of the
+/// input is encoded into.
+///
+/// Values are encoded via [ToInputElements]; thus, the encoding of every type matches the encoding
+/// of the corresponding Rust type (e.g., [NoteInputType::U64] inputs are encoded as `[lo, hi]`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoteInputType {
+    /// A single field element.
+    Felt,
+    /// A u32 value encoded as a single element.
+    U32,
+    /// A u64 value encoded as two u32 limbs `[lo, hi]`, as expected by `std::math::u64`.
+    U64,
+    /// An account ID encoded as a single element.
+    AccountId,
+    /// A word (e.g., a digest or an asset) encoded as four elements.
+    Word,
+}
+
+impl NoteInputType {
+    /// Returns the number of elements occupied by an input of this type.
+    pub const fn num_elements(&self) -> usize {
+        match self {
+            Self::Word => 4,
+            Self::U64 => 2,
+            _ => 1,
+        }
+    }
+}
+
+// NOTE INPUT DESCRIPTOR
+// ================================================================================================
+
+/// Describes a single input of a note script: its name, its type, and the position of its first
+/// element in the note inputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoteInputDescriptor {
+    name: String,
+    input_type: NoteInputType,
+    offset: usize,
+}
+
+impl NoteInputDescriptor {
+    /// Returns a new [NoteInputDescriptor] for an input with the specified name and type, placed
+    /// at the specified position of the note inputs.
+    pub fn new(name: impl Into<String>, input_type: NoteInputType, offset: usize) -> Self {
+        Self { name: name.into(), input_type, offset }
+    }
+
+    /// Returns the name of this input.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of this input.
+    pub fn input_type(&self) -> NoteInputType {
+        self.input_type
+    }
+
+    /// Returns the position of the first element of this input in the note inputs.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the position right after the last element of this input in the note inputs.
+    fn end(&self) -> usize {
+        self.offset + self.input_type.num_elements()
+    }
+}
+
+// NOTE SCRIPT TEMPLATE
+// ================================================================================================
+
+/// A compiled note script together with the layout of the inputs expected by the script.
+///
+/// Note inputs are built from named values via [NoteScriptTemplate::inputs_builder()], such that
+/// the values are placed at the positions expected by the script regardless of the order in
+/// which they are provided, e.g.:
+///
+/// ```ignore
+/// let template = NoteScriptTemplate::p2idr()?;
+/// let inputs = template
+///     .inputs_builder()
+///     .set("recall_height", 100_u32)?
+///     .set("target", target_account_id)?
+///     .build()?;
+/// let note = Note::new(template.script().clone(), &inputs, &assets, serial_num, sender, tag)?;
+/// ```
+///
+/// Templates of the standard note scripts with fixed inputs are provided via
+/// [NoteScriptTemplate::p2id()], [NoteScriptTemplate::p2idr()], [NoteScriptTemplate::swap()] and
+/// [NoteScriptTemplate::gift_card()].
+#[derive(Clone, Debug)]
+pub struct NoteScriptTemplate {
+    script: NoteScript,
+    inputs: Vec<NoteInputDescriptor>,
+}
+
+impl NoteScriptTemplate {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [NoteScriptTemplate] for the provided script expecting the specified inputs.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Two inputs have the same name.
+    /// - Two inputs occupy the same element of the note inputs.
+    /// - Any of the inputs does not fit into the note inputs.
+    pub fn new(script: NoteScript, inputs: Vec<NoteInputDescriptor>) -> Result<Self, NoteError> {
+        for (idx, input) in inputs.iter().enumerate() {
+            if input.end() > NoteInputs::NOTE_NUM_INPUTS {
+                return Err(NoteError::InvalidNoteInputLayout(format!(
+                    "input {} does not fit into {} note inputs",
+                    input.name,
+                    NoteInputs::NOTE_NUM_INPUTS
+                )));
+            }
+
+            for other in inputs[..idx].iter() {
+                if other.name == input.name {
+                    return Err(NoteError::InvalidNoteInputLayout(format!(
+                        "input {} is defined more than once",
+                        input.name
+                    )));
+                }
+                if other.offset < input.end() && input.offset < other.end() {
+                    return Err(NoteError::InvalidNoteInputLayout(format!(
+                        "inputs {} and {} overlap",
+                        other.name, input.name
+                    )));
+                }
+            }
+        }
+
+        Ok(Self { script, inputs })
+    }
+
+    /// Returns the template of the P2ID note script.
+    ///
+    /// The script expects the ID of the target account as its only input (`target`).
+    pub fn p2id() -> Result<Self, NoteError> {
+        let script = compile_note_script(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/assets/note_scripts/P2ID.masb"
+        )))?;
+        Self::new(script, vec![NoteInputDescriptor::new("target", NoteInputType::AccountId, 0)])
+    }
+
+    /// Returns the template of the P2IDR note script.
+    ///
+    /// The script expects the ID of the target account (`target`) followed by the block height
+    /// after which the note can be reclaimed by the sender (`recall_height`).
+    pub fn p2idr() -> Result<Self, NoteError> {
+        let script = compile_note_script(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/assets/note_scripts/P2IDR.masb"
+        )))?;
+        Self::new(
+            script,
+            vec![
+                NoteInputDescriptor::new("target", NoteInputType::AccountId, 0),
+                NoteInputDescriptor::new("recall_height", NoteInputType::U32, 1),
+            ],
+        )
+    }
+
+    /// Returns the template of the SWAP note script.
+    ///
+    /// The script expects the serial number of the payback note (`payback_serial_num`), the asset
+    /// requested in exchange (`requested_asset`), the tag of the payback note (`payback_tag`), and
+    /// the hash of the P2ID script (`p2id_script_hash`), each starting at a word boundary.
+    pub fn swap() -> Result<Self, NoteError> {
+        let script = compile_note_script(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/assets/note_scripts/SWAP.masb"
+        )))?;
+        Self::new(
+            script,
+            vec![
+                NoteInputDescriptor::new("payback_serial_num", NoteInputType::Word, 0),
+                NoteInputDescriptor::new("requested_asset", NoteInputType::Word, 4),
+                NoteInputDescriptor::new("payback_tag", NoteInputType::Felt, 8),
+                NoteInputDescriptor::new("p2id_script_hash", NoteInputType::Word, 12),
+            ],
+        )
+    }
+
+    /// Returns the template of the GIFT_CARD note script.
+    ///
+    /// The script expects the maximum amount which can be withdrawn per consumption
+    /// (`max_withdrawal`) followed by the number of times the gift card was consumed
+    /// (`usage_count`). As for asset amounts, the maximum withdrawal is a single element rather
+    /// than a u64 value. See also [GiftCard](super::GiftCard).
+    pub fn gift_card() -> Result<Self, NoteError> {
+        let script = compile_note_script(include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/assets/note_scripts/GIFT_CARD.masb"
+        )))?;
+        Self::new(
+            script,
+            vec![
+                NoteInputDescriptor::new("max_withdrawal", NoteInputType::Felt, 0),
+                NoteInputDescriptor::new("usage_count", NoteInputType::U32, 1),
+            ],
+        )
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the note script of this template.
+    pub fn script(&self) -> &NoteScript {
+        &self.script
+    }
+
+    /// Returns the descriptors of the inputs expected by the script, in the order in which they
+    /// were defined.
+    pub fn inputs(&self) -> &[NoteInputDescriptor] {
+        &self.inputs
+    }
+
+    /// Returns the descriptor of the input with the specified name, or None if the script does
+    /// not expect such an input.
+    pub fn input(&self, name: &str) -> Option<&NoteInputDescriptor> {
+        self.inputs.iter().find(|input| input.name == name)
+    }
+
+    /// Returns the number of elements spanned by the inputs of the script.
+    pub fn num_input_elements(&self) -> usize {
+        self.inputs.iter().map(NoteInputDescriptor::end).max().unwrap_or(0)
+    }
+
+    /// Returns a builder of the note inputs expected by the script.
+    pub fn inputs_builder(&self) -> NoteInputsBuilder<'_> {
+        NoteInputsBuilder {
+            template: self,
+            values: vec![None; self.inputs.len()],
+        }
+    }
+}
+
+// NOTE INPUTS BUILDER
+// ================================================================================================
+
+/// Builds the inputs of a note from named values, according to the layout of a
+/// [NoteScriptTemplate].
+#[derive(Clone, Debug)]
+pub struct NoteInputsBuilder<'a> {
+    template: &'a NoteScriptTemplate,
+    values: Vec<Option<Vec<Felt>>>,
+}
+
+impl NoteInputsBuilder<'_> {
+    /// Sets the value of the input with the specified name.
+    ///
+    /// The value is encoded via [ToInputElements], in the same way as by
+    /// [NoteInputs::from_values()].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The script does not expect an input with the specified name.
+    /// - The value of the input was already set.
+    /// - The number of elements encoding the value does not match the type of the input.
+    pub fn set(mut self, name: &str, value: impl ToInputElements) -> Result<Self, NoteError> {
+        let idx = self
+            .template
+            .inputs
+            .iter()
+            .position(|input| input.name == name)
+            .ok_or_else(|| NoteError::UnknownNoteInput(name.to_string()))?;
+        if self.values[idx].is_some() {
+            return Err(NoteError::DuplicateNoteInput(name.to_string()));
+        }
+
+        let elements = value.to_input_elements();
+        let input_type = self.template.inputs[idx].input_type;
+        if elements.len() != input_type.num_elements() {
+            return Err(NoteError::InvalidNoteInputValue(format!(
+                "input {name} of type {input_type:?} expects {} elements, but the value is encoded \
+                into {} elements",
+                input_type.num_elements(),
+                elements.len()
+            )));
+        }
+
+        self.values[idx] = Some(elements);
+        Ok(self)
+    }
+
+    /// Returns the note inputs encoding the provided values.
+    ///
+    /// The returned inputs span all inputs of the script, with the elements not covered by any
+    /// input set to ZERO.
+    ///
+    /// # Errors
+    /// Returns an error if the value of any of the inputs was not set.
+    pub fn build(self) -> Result<Vec<Felt>, NoteError> {
+        let mut elements = vec![ZERO; self.template.num_input_elements()];
+        for (input, value) in self.template.inputs.iter().zip(self.values) {
+            let value = value.ok_or_else(|| NoteError::MissingNoteInput(input.name.clone()))?;
+            elements[input.offset..input.end()].copy_from_slice(&value);
+        }
+        Ok(elements)
+    }
+}
@@ -4,6 +4,9 @@ use miden_objects::{vm::StackInputs, Felt, Hasher, Word, ONE, ZERO};
 use vm_processor::{ContextId, MemAdviceProvider, Process, ProcessState};
 
 use super::Library;
+use crate::transaction::{
+    kernel_procedure_name, kernel_procedure_trace_id, TransactionKernel, KERNEL_PROCEDURES,
+};
 
 mod test_account;
 mod test_asset;
@@ -12,7 +15,10 @@ mod test_epilogue;
 mod test_faucet;
 mod test_note;
 mod test_prologue;
+#[cfg(feature = "source-map")]
+mod test_source_map;
 mod test_tx;
+mod test_u256;
 
 // CONSTANTS
 // ================================================================================================
@@ -37,6 +43,27 @@ fn test_compile() {
     assert!(exists);
 }
 
+#[test]
+fn test_kernel_procedures_are_traced() {
+    let source = TransactionKernel::kernel();
+    let mut lines = source.lines();
+
+    let mut names = Vec::new();
+    while let Some(line) = lines.next() {
+        if let Some(name) = line.strip_prefix("export.") {
+            // every kernel procedure starts with the trace decorator of its index
+            let trace_id = kernel_procedure_trace_id(names.len());
+            assert_eq!(lines.next().unwrap().trim(), format!("trace.{trace_id}"));
+            assert_eq!(kernel_procedure_name(trace_id), Some(name));
+            names.push(name);
+        }
+    }
+
+    assert_eq!(names, KERNEL_PROCEDURES);
+    assert_eq!(kernel_procedure_name(kernel_procedure_trace_id(names.len())), None);
+    assert_eq!(kernel_procedure_name(1), None);
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -5,8 +5,8 @@ use miden_objects::accounts::{
 };
 use mock::{
     constants::{
-        storage_item_0, storage_item_1, CHILD_ROOT_PARENT_LEAF_INDEX, CHILD_SMT_DEPTH,
-        CHILD_STORAGE_INDEX_0, CHILD_STORAGE_VALUE_0,
+        storage_item_0, storage_item_1, STORAGE_INDEX_0, STORAGE_INDEX_2, STORAGE_MAP_KEY_0,
+        STORAGE_MAP_VALUE_0, STORAGE_VALUE_0,
     },
     mock::{
         account::MockAccountType,
@@ -20,8 +20,8 @@ use mock::{
 };
 
 use super::{
-    super::transaction::ToTransactionKernelInputs, ContextId, Felt, MemAdviceProvider,
-    ProcessState, StackInputs, Word, ONE, ZERO,
+    super::transaction::{KernelVersion, ToTransactionKernelInputs},
+    ContextId, Felt, MemAdviceProvider, ProcessState, StackInputs, Word, ONE, ZERO,
 };
 use crate::transaction::memory::{ACCT_CODE_ROOT_PTR, ACCT_NEW_CODE_ROOT_PTR};
 
@@ -89,7 +89,8 @@ pub fn test_set_code_succeeds() {
         "
     );
 
-    let (stack_inputs, advice_inputs) = executed_transaction.get_kernel_inputs();
+    let (stack_inputs, advice_inputs) =
+        executed_transaction.get_kernel_inputs(&KernelVersion::current());
     let host = MockHost::new(executed_transaction.initial_account().into(), advice_inputs);
     let process = run_within_host("", &code, stack_inputs, host, None).unwrap();
 
@@ -100,6 +101,33 @@ pub fn test_set_code_succeeds() {
     );
 }
 
+#[test]
+pub fn test_set_code_fails_on_immutable_account() {
+    let tx_inputs = mock_inputs(
+        MockAccountType::FungibleFaucet {
+            acct_id: ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+            nonce: ONE,
+            empty_reserved_slot: false,
+        },
+        AssetPreservationStatus::Preserved,
+    );
+
+    let code = "
+        use.miden::kernels::tx::prologue
+        use.miden::account
+        begin
+            exec.prologue::prepare_transaction
+            push.1.2.3.4
+            exec.account::set_code
+        end
+        ";
+
+    let transaction = prepare_transaction(tx_inputs, None, code, None);
+    let result = run_tx(&transaction);
+
+    assert!(result.is_err());
+}
+
 // ACCOUNT ID TESTS
 // ================================================================================================
 
@@ -306,8 +334,6 @@ fn test_set_item() {
     let _process = run_tx(&transaction).unwrap();
 }
 
-// TODO: reenable once storage map support is implemented
-#[ignore]
 #[test]
 fn test_get_map_item() {
     let tx_inputs =
@@ -322,29 +348,112 @@ fn test_get_map_item() {
             # prepare the transaction
             exec.prologue::prepare_transaction
 
-            # push the account storage index the child root is stored at
-            push.{CHILD_ROOT_PARENT_LEAF_INDEX}
+            # push the key of the map entry and the index of the slot storing the map
+            push.{map_key} push.{STORAGE_INDEX_2}
 
-            # get the child root
-            exec.account::get_item
+            # get the value from the map
+            exec.account::get_map_item
 
-            # get a value from the child tree
-            push.{CHILD_STORAGE_INDEX_0}
+            # assert the value is correct
+            push.{map_value} assert_eqw
 
-            # get the item
-            push.{CHILD_SMT_DEPTH} mtree_get
+            # absent keys are mapped to empty values
+            push.{absent_key} push.{STORAGE_INDEX_2}
+            exec.account::get_map_item
+            padw assert_eqw
+        end
+        ",
+        map_key = prepare_word(&STORAGE_MAP_KEY_0),
+        map_value = prepare_word(&STORAGE_MAP_VALUE_0),
+        absent_key = prepare_word(&STORAGE_VALUE_0),
+    );
 
-            # assert the child value is correct
-            push.{child_value} assert_eqw
+    let transaction = prepare_transaction(tx_inputs, None, code.as_str(), None);
+    let _process = run_tx(&transaction).unwrap();
+}
+
+#[test]
+fn test_set_map_item() {
+    let tx_inputs =
+        mock_inputs(MockAccountType::StandardExisting, AssetPreservationStatus::Preserved);
+
+    // compute the expected root of the map after the update
+    const NEW_MAP_VALUE: Word = [Felt::new(21), Felt::new(22), Felt::new(23), Felt::new(24)];
+    let mut storage = tx_inputs.account().storage().clone();
+    let old_value = storage
+        .set_map_item(STORAGE_INDEX_2, STORAGE_MAP_KEY_0.into(), NEW_MAP_VALUE)
+        .unwrap();
+    assert_eq!(old_value, STORAGE_MAP_VALUE_0);
+
+    let code = format!(
+        "
+        use.miden::account
+        use.miden::kernels::tx::memory
+        use.miden::kernels::tx::prologue
+
+        begin
+            # prepare the transaction
+            exec.prologue::prepare_transaction
+
+            # push the new value, the key of the map entry and the index of the slot storing the map
+            push.{new_value} push.{map_key} push.{STORAGE_INDEX_2}
+
+            # set the value in the map
+            exec.account::set_map_item
+
+            # assert the new map root and the old value are correct
+            push.{new_map_root} assert_eqw
+            push.{old_value} assert_eqw
+
+            # assert the new storage root is correct
+            exec.memory::get_acct_storage_root
+            push.{new_storage_root} assert_eqw
+
+            # assert the new value can be read from the map
+            push.{map_key} push.{STORAGE_INDEX_2}
+            exec.account::get_map_item
+            push.{new_value} assert_eqw
         end
         ",
-        child_value = prepare_word(&CHILD_STORAGE_VALUE_0)
+        new_value = prepare_word(&NEW_MAP_VALUE),
+        map_key = prepare_word(&STORAGE_MAP_KEY_0),
+        old_value = prepare_word(&STORAGE_MAP_VALUE_0),
+        new_map_root = prepare_word(&storage.get_item(STORAGE_INDEX_2).into()),
+        new_storage_root = prepare_word(&storage.root().into()),
     );
 
     let transaction = prepare_transaction(tx_inputs, None, code.as_str(), None);
     let _process = run_tx(&transaction).unwrap();
 }
 
+#[test]
+fn test_set_map_item_fails_on_value_slot() {
+    let tx_inputs =
+        mock_inputs(MockAccountType::StandardExisting, AssetPreservationStatus::Preserved);
+
+    let code = format!(
+        "
+        use.miden::account
+        use.miden::kernels::tx::prologue
+
+        begin
+            # prepare the transaction
+            exec.prologue::prepare_transaction
+
+            # try to set a map entry in a slot which stores a single value
+            push.{map_value} push.{map_key} push.{STORAGE_INDEX_0}
+            exec.account::set_map_item
+        end
+        ",
+        map_value = prepare_word(&STORAGE_MAP_VALUE_0),
+        map_key = prepare_word(&STORAGE_MAP_KEY_0),
+    );
+
+    let transaction = prepare_transaction(tx_inputs, None, code.as_str(), None);
+    let result = run_tx(&transaction);
+    assert!(result.is_err());
+}
+
 // ACCOUNT VAULT TESTS
 // ================================================================================================
 
@@ -386,8 +495,8 @@ fn test_authenticate_procedure() {
     let account = tx_inputs.account();
 
     let test_cases = vec![
-        (account.code().procedure_tree().get_leaf(0).unwrap(), true),
-        (account.code().procedure_tree().get_leaf(1).unwrap(), true),
+        (*account.code().procedures()[0], true),
+        (*account.code().procedures()[1], true),
         ([ONE, ZERO, ONE, ZERO], false),
     ];
 
@@ -1,16 +1,29 @@
+use miden_objects::{
+    accounts::{
+        ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
+    },
+    transaction::{InputNotes, TransactionInputs},
+};
 use mock::{
-    mock::{notes::AssetPreservationStatus, transaction::mock_executed_tx},
+    mock::{
+        account::{mock_account, MockAccountType},
+        notes::AssetPreservationStatus,
+        transaction::{mock_executed_tx, mock_inputs_with_existing},
+    },
+    prepare_transaction,
     procedures::output_notes_data_procedure,
-    run_within_tx_kernel,
+    run_tx, run_within_tx_kernel,
 };
 
 use super::{
-    build_module_path, ContextId, MemAdviceProvider, ProcessState, Word, TX_KERNEL_DIR, ZERO,
+    build_module_path, ContextId, MemAdviceProvider, ProcessState, Word, ONE, TX_KERNEL_DIR, ZERO,
 };
 use crate::transaction::{
     memory::{CREATED_NOTE_ASSET_HASH_OFFSET, CREATED_NOTE_SECTION_OFFSET, NOTE_MEM_SIZE},
-    ToTransactionKernelInputs, FINAL_ACCOUNT_HASH_WORD_IDX, OUTPUT_NOTES_COMMITMENT_WORD_IDX,
-    TX_SCRIPT_ROOT_WORD_IDX,
+    KernelVersion, ToTransactionKernelInputs, TransactionKernel, EXPIRATION_BLOCK_NUM_IDX,
+    FEE_AMOUNT_IDX, FEE_FAUCET_ID_IDX, FEE_NUM_CYCLES_IDX, FINAL_ACCOUNT_HASH_WORD_IDX,
+    OUTPUT_NOTES_COMMITMENT_WORD_IDX, TX_SCRIPT_ROOT_WORD_IDX,
 };
 
 const EPILOGUE_FILE: &str = "epilogue.masm";
@@ -35,7 +48,8 @@ fn test_epilogue() {
         "
     );
 
-    let (stack_inputs, advice_inputs) = executed_transaction.get_kernel_inputs();
+    let (stack_inputs, advice_inputs) =
+        executed_transaction.get_kernel_inputs(&KernelVersion::current());
     let assembly_file = build_module_path(TX_KERNEL_DIR, EPILOGUE_FILE);
     let process = run_within_tx_kernel(
         imports,
@@ -67,13 +81,16 @@ fn test_epilogue() {
         executed_transaction.final_account().hash().as_elements(),
     );
 
+    // assert the transaction does not expire
+    assert_eq!(process.stack.get(EXPIRATION_BLOCK_NUM_IDX), ZERO);
+
+    // assert no fee was charged, as the transaction was executed without fee parameters
+    assert_eq!(process.stack.get(FEE_FAUCET_ID_IDX), ZERO);
+    assert_eq!(process.stack.get(FEE_AMOUNT_IDX), ZERO);
+    assert_eq!(process.stack.get(FEE_NUM_CYCLES_IDX), ZERO);
+
     // assert stack has been truncated correctly
     assert_eq!(process.stack.depth(), 16);
-
-    // assert the bottom of the stack is filled with zeros
-    for i in 12..16 {
-        assert_eq!(process.stack.get(i), ZERO);
-    }
 }
 
 #[test]
@@ -96,7 +113,8 @@ fn test_compute_created_note_id() {
         "
         );
 
-        let (stack_inputs, advice_inputs) = executed_transaction.get_kernel_inputs();
+        let (stack_inputs, advice_inputs) =
+            executed_transaction.get_kernel_inputs(&KernelVersion::current());
         let assembly_file = build_module_path(TX_KERNEL_DIR, EPILOGUE_FILE);
         let process = run_within_tx_kernel(
             imports,
@@ -148,7 +166,8 @@ fn test_epilogue_asset_preservation_violation() {
         "
         );
 
-        let (stack_inputs, advice_inputs) = executed_transaction.get_kernel_inputs();
+        let (stack_inputs, advice_inputs) =
+            executed_transaction.get_kernel_inputs(&KernelVersion::current());
         let assembly_file = build_module_path(TX_KERNEL_DIR, EPILOGUE_FILE);
         let process = run_within_tx_kernel(
             imports,
@@ -184,7 +203,8 @@ fn test_epilogue_increment_nonce_success() {
         "
     );
 
-    let (stack_inputs, advice_inputs) = executed_transaction.get_kernel_inputs();
+    let (stack_inputs, advice_inputs) =
+        executed_transaction.get_kernel_inputs(&KernelVersion::current());
     let assembly_file = build_module_path(TX_KERNEL_DIR, EPILOGUE_FILE);
     let _process = run_within_tx_kernel(
         imports,
@@ -216,7 +236,8 @@ fn test_epilogue_increment_nonce_violation() {
         "
     );
 
-    let (stack_inputs, advice_inputs) = executed_transaction.get_kernel_inputs();
+    let (stack_inputs, advice_inputs) =
+        executed_transaction.get_kernel_inputs(&KernelVersion::current());
     let assembly_file = build_module_path(TX_KERNEL_DIR, EPILOGUE_FILE);
     let process = run_within_tx_kernel(
         imports,
@@ -228,3 +249,49 @@ fn test_epilogue_increment_nonce_violation() {
 
     assert!(process.is_err());
 }
+
+#[test]
+fn test_epilogue_code_update() {
+    // the code root is updated directly in memory, i.e., bypassing `account::set_code`, to make
+    // sure the epilogue checks the code update on its own
+    let code = "
+        use.miden::kernels::tx::account
+        use.miden::kernels::tx::epilogue
+        use.miden::kernels::tx::memory
+        use.miden::kernels::tx::prologue
+
+        begin
+            exec.prologue::prepare_transaction
+            push.1.2.3.4 exec.memory::set_new_acct_code_root
+            push.1 exec.account::incr_nonce
+            exec.epilogue::finalize_transaction
+        end
+        ";
+
+    let assembler = TransactionKernel::assembler();
+    for (account_id, is_updatable) in [
+        (ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN, true),
+        (ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN, false),
+    ] {
+        let account = mock_account(Some(account_id), ONE, None, &assembler);
+        let (account, block_header, chain_mmr, input_notes, _) = mock_inputs_with_existing(
+            MockAccountType::StandardExisting,
+            AssetPreservationStatus::Preserved,
+            Some(account),
+            Some(vec![]),
+        );
+        let tx_inputs = TransactionInputs::new(
+            account,
+            None,
+            block_header,
+            chain_mmr,
+            InputNotes::new(input_notes).unwrap(),
+        )
+        .unwrap();
+
+        let transaction = prepare_transaction(tx_inputs, None, code, None);
+        let result = run_tx(&transaction);
+
+        assert_eq!(result.is_ok(), is_updatable);
+    }
+}
@@ -23,9 +23,10 @@ use crate::transaction::{
         ACCT_STORAGE_ROOT_PTR, ACCT_STORAGE_SLOT_TYPE_DATA_OFFSET, ACCT_VAULT_ROOT_PTR,
         BATCH_ROOT_PTR, BLK_HASH_PTR, BLOCK_METADATA_PTR, BLOCK_NUMBER_IDX,
         CHAIN_MMR_NUM_LEAVES_PTR, CHAIN_MMR_PEAKS_PTR, CHAIN_ROOT_PTR,
-        CONSUMED_NOTE_SECTION_OFFSET, INIT_ACCT_HASH_PTR, INIT_NONCE_PTR, NOTE_ROOT_PTR,
-        NULLIFIER_COM_PTR, NULLIFIER_DB_ROOT_PTR, PREV_BLOCK_HASH_PTR, PROOF_HASH_PTR,
-        PROTOCOL_VERSION_IDX, TIMESTAMP_IDX, TX_SCRIPT_ROOT_PTR,
+        CONSUMED_NOTE_SECTION_OFFSET, FEE_PARAMS_PTR, FEE_RECIPIENT_PTR, INIT_ACCT_HASH_PTR,
+        INIT_NONCE_PTR, NOTE_ROOT_PTR, NULLIFIER_COM_PTR, NULLIFIER_DB_ROOT_PTR,
+        PREV_BLOCK_HASH_PTR, PROOF_HASH_PTR, PROTOCOL_VERSION_IDX, TIMESTAMP_IDX,
+        TX_SCRIPT_ARGS_PTR, TX_SCRIPT_ROOT_PTR,
     },
     TransactionKernel,
 };
@@ -56,7 +57,8 @@ fn test_transaction_prologue() {
             .unwrap();
 
     let assembly_file = build_module_path(TX_KERNEL_DIR, PROLOGUE_FILE);
-    let transaction = prepare_transaction(tx_inputs, Some(tx_script), code, Some(assembly_file));
+    let transaction = prepare_transaction(tx_inputs, Some(tx_script), code, Some(assembly_file))
+        .with_tx_script_args([Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)]);
     let process = run_tx(&transaction).unwrap();
 
     global_input_memory_assertions(&process, &transaction);
@@ -102,6 +104,23 @@ fn global_input_memory_assertions(process: &Process<MockHost>, inputs: &Prepared
         process.get_mem_value(ContextId::root(), TX_SCRIPT_ROOT_PTR).unwrap(),
         **inputs.tx_script().as_ref().unwrap().hash()
     );
+
+    // The transaction script arguments should be stored at the TX_SCRIPT_ARGS_PTR
+    assert_eq!(
+        process.get_mem_value(ContextId::root(), TX_SCRIPT_ARGS_PTR).unwrap(),
+        inputs.tx_script_args()
+    );
+
+    // No fee parameters were provided, thus empty words should be stored at the FEE_PARAMS_PTR
+    // and at the FEE_RECIPIENT_PTR
+    assert_eq!(
+        process.get_mem_value(ContextId::root(), FEE_PARAMS_PTR).unwrap(),
+        Word::default()
+    );
+    assert_eq!(
+        process.get_mem_value(ContextId::root(), FEE_RECIPIENT_PTR).unwrap(),
+        Word::default()
+    );
 }
 
 fn block_data_memory_assertions(process: &Process<MockHost>, inputs: &PreparedTransaction) {
@@ -296,14 +315,22 @@ fn consumed_notes_memory_assertions(process: &Process<MockHost>, inputs: &Prepar
             Word::from(note.note().metadata())
         );
 
-        // The assets should be stored at (CONSUMED_NOTES_OFFSET + (note_index + 1) * 1024 + 6..)
+        // The note memo should be stored at (CONSUMED_NOTES_OFFSET + (note_index + 1) * 1024 + 6)
+        assert_eq!(
+            process
+                .get_mem_value(ContextId::root(), consumed_note_data_ptr(note_idx) + 6)
+                .unwrap(),
+            note.note().memo().as_elements()
+        );
+
+        // The assets should be stored at (CONSUMED_NOTES_OFFSET + (note_index + 1) * 1024 + 7..)
         for (asset, asset_idx) in note.note().assets().iter().cloned().zip(0u32..) {
             let word: Word = asset.into();
             assert_eq!(
                 process
                     .get_mem_value(
                         ContextId::root(),
-                        consumed_note_data_ptr(note_idx) + 6 + asset_idx
+                        consumed_note_data_ptr(note_idx) + 7 + asset_idx
                     )
                     .unwrap(),
                 word
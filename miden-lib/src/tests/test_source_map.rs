@@ -0,0 +1,49 @@
+use miden_objects::assembly::{AssemblyContext, ModuleAst};
+
+use crate::transaction::TransactionKernel;
+
+// SOURCE MAP TESTS
+// ================================================================================================
+
+#[test]
+fn test_kernel_source_map_resolves_procedures() {
+    let source_map = TransactionKernel::source_map();
+
+    let get_id = source_map.get_by_name("miden::account::get_id").unwrap();
+    assert_eq!(get_id.file(), "miden/account.masm");
+    assert_eq!(get_id.line(), 7);
+    assert!(get_id.op_line(0).unwrap() > get_id.line());
+
+    let main = source_map.get_by_name("#exec::main").unwrap();
+    assert_eq!(main.file(), "kernels/transaction/main.masm");
+
+    assert!(source_map.iter().any(|proc| proc.name().starts_with("#sys::")));
+}
+
+#[test]
+fn test_kernel_source_map_resolves_mast_roots() {
+    let source_map = TransactionKernel::source_map();
+
+    // the kernel executable program is resolved by the root of its main code block
+    let main_root = TransactionKernel::main_code_block().hash();
+    assert_eq!(source_map.get(&main_root).unwrap().name(), "#exec::main");
+
+    // a procedure which only executes another procedure has the same MAST root as the procedure
+    let module = ModuleAst::parse(
+        "
+        use.miden::account
+
+        export.get_id_wrapper
+            exec.account::get_id
+        end
+        ",
+    )
+    .unwrap();
+    let roots = TransactionKernel::assembler()
+        .compile_module(&module, None, &mut AssemblyContext::for_module(false))
+        .unwrap();
+    let get_id = source_map.get(&roots[0]).unwrap();
+    assert_eq!(get_id.mast_root(), roots[0]);
+    assert_eq!(source_map.get_by_name(get_id.name()), Some(get_id));
+    assert_eq!(source_map.get_by_name("miden::account::get_id").unwrap().mast_root(), roots[0]);
+}
@@ -1,5 +1,5 @@
 use miden_objects::{
-    notes::Note,
+    notes::{Note, NoteMetadata, NoteTag},
     transaction::{OutputNote, OutputNotes},
 };
 use mock::{
@@ -127,6 +127,174 @@ fn test_create_note_too_many_notes() {
     assert!(process.is_err());
 }
 
+#[test]
+fn test_create_note_with_invalid_tag() {
+    let tx_inputs =
+        mock_inputs(MockAccountType::StandardExisting, AssetPreservationStatus::Preserved);
+
+    let recipient = [ZERO, ONE, Felt::new(2), Felt::new(3)];
+    let tag = Felt::new(1 << 32);
+    let asset = [Felt::new(10), ZERO, ZERO, Felt::new(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN)];
+
+    let code = format!(
+        "
+    use.miden::kernels::tx::prologue
+    use.miden::tx
+
+    begin
+        exec.prologue::prepare_transaction
+
+        push.{recipient}
+        push.{tag}
+        push.{asset}
+
+        exec.tx::create_note
+    end
+    ",
+        recipient = prepare_word(&recipient),
+        tag = tag,
+        asset = prepare_word(&asset)
+    );
+
+    let transaction = prepare_transaction(tx_inputs, None, &code, None);
+    let process = run_tx(&transaction);
+
+    // assert the process failed
+    assert!(process.is_err());
+}
+
+#[test]
+fn test_set_note_expiration_and_aux() {
+    let tx_inputs =
+        mock_inputs(MockAccountType::StandardExisting, AssetPreservationStatus::Preserved);
+    let account_id = tx_inputs.account().id();
+
+    let recipient = [ZERO, ONE, Felt::new(2), Felt::new(3)];
+    let tag = NoteTag::from(4);
+    let asset = [Felt::new(10), ZERO, ZERO, Felt::new(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN)];
+    let expiration_block_num = 1234;
+    let aux = Felt::new(42);
+
+    let code = format!(
+        "
+    use.miden::kernels::tx::prologue
+    use.miden::tx
+
+    begin
+        exec.prologue::prepare_transaction
+
+        push.{recipient}
+        push.{tag}
+        push.{asset}
+
+        exec.tx::create_note drop
+
+        push.{expiration_block_num}
+        exec.tx::set_note_expiration
+
+        push.{aux}
+        exec.tx::set_note_aux
+    end
+    ",
+        recipient = prepare_word(&recipient),
+        asset = prepare_word(&asset)
+    );
+
+    let transaction = prepare_transaction(tx_inputs, None, &code, None);
+    let process = run_tx(&transaction).unwrap();
+
+    // assert the metadata stored by the kernel matches the encoding of the metadata
+    let expected_metadata = NoteMetadata::new(account_id, tag, ONE)
+        .with_expiration(expiration_block_num)
+        .unwrap()
+        .with_aux(aux);
+    assert_eq!(
+        process
+            .get_mem_value(
+                ContextId::root(),
+                CREATED_NOTE_SECTION_OFFSET + CREATED_NOTE_METADATA_OFFSET
+            )
+            .unwrap(),
+        Word::from(expected_metadata)
+    );
+}
+
+#[test]
+fn test_set_note_expiration_with_invalid_input() {
+    let recipient = [ZERO, ONE, Felt::new(2), Felt::new(3)];
+    let asset = [Felt::new(10), ZERO, ZERO, Felt::new(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN)];
+    let create_note = format!(
+        "push.{recipient} push.4 push.{asset} exec.tx::create_note drop",
+        recipient = prepare_word(&recipient),
+        asset = prepare_word(&asset)
+    );
+
+    // 0 and u32::MAX cannot be encoded as expiration block numbers, and the expiration can only be
+    // set once a note was created
+    let cases = [
+        (create_note.clone(), 0_u64),
+        (create_note.clone(), u32::MAX as u64),
+        (create_note, 1 << 32),
+        (String::new(), 1234),
+    ];
+    for (create_note, expiration_block_num) in cases {
+        let tx_inputs =
+            mock_inputs(MockAccountType::StandardExisting, AssetPreservationStatus::Preserved);
+        let code = format!(
+            "
+        use.miden::kernels::tx::prologue
+        use.miden::tx
+
+        begin
+            exec.prologue::prepare_transaction
+            {create_note}
+            push.{expiration_block_num}
+            exec.tx::set_note_expiration
+        end
+        "
+        );
+
+        let transaction = prepare_transaction(tx_inputs, None, &code, None);
+        let process = run_tx(&transaction);
+
+        // assert the process failed
+        assert!(process.is_err());
+    }
+}
+
+#[test]
+fn test_get_block_header_fields() {
+    let tx_inputs =
+        mock_inputs(MockAccountType::StandardExisting, AssetPreservationStatus::Preserved);
+    let block_header = tx_inputs.block_header();
+
+    let code = format!(
+        "
+    use.miden::kernels::tx::prologue
+    use.miden::tx
+
+    begin
+        exec.prologue::prepare_transaction
+
+        exec.tx::get_block_number
+        push.{block_num} assert_eq
+
+        exec.tx::get_block_timestamp
+        push.{timestamp} assert_eq
+
+        exec.tx::get_chain_root
+        push.{chain_root} assert_eqw
+    end
+    ",
+        block_num = block_header.block_num(),
+        timestamp = block_header.timestamp(),
+        chain_root = prepare_word(&block_header.chain_root())
+    );
+
+    let transaction = prepare_transaction(tx_inputs, None, &code, None);
+    let _process = run_tx(&transaction).unwrap();
+}
+
 #[test]
 fn test_get_output_notes_hash() {
     let tx_inputs =
@@ -140,7 +308,7 @@ fn test_get_output_notes_hash() {
 
     // create output note 1
     let output_serial_no_1 = [Felt::new(8); 4];
-    let output_tag_1 = Felt::new(8888);
+    let output_tag_1 = NoteTag::from(8888);
     let output_note_1 = Note::new(
         input_note_1.script().clone(),
         &[],
@@ -153,7 +321,7 @@ fn test_get_output_notes_hash() {
 
     // create output note 2
     let output_serial_no_2 = [Felt::new(11); 4];
-    let output_tag_2 = Felt::new(1111);
+    let output_tag_2 = NoteTag::from(1111);
     let output_note_2 = Note::new(
         input_note_2.script().clone(),
         &[],
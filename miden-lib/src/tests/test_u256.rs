@@ -0,0 +1,167 @@
+use miden_objects::StarkField;
+use mock::run_within_tx_kernel;
+
+use super::{Felt, MemAdviceProvider, StackInputs};
+use crate::math::U256;
+
+// HELPERS
+// ================================================================================================
+
+const IMPORTS: &str = "use.miden::math::u256\n";
+
+/// Returns the MASM instruction pushing the provided value onto the stack.
+fn push_u256(value: U256) -> String {
+    let limbs = value.limbs().iter().rev().map(|limb| limb.to_string()).collect::<Vec<_>>();
+    format!("push.{}", limbs.join("."))
+}
+
+/// Executes the specified binary procedure of the u256 module against `a` and `b`, and returns
+/// the resulting stack, or None if the execution failed.
+fn exec_binary(proc: &str, a: U256, b: U256) -> Option<Vec<Felt>> {
+    let code = format!(
+        "
+        begin
+            {a}
+            {b}
+            exec.u256::{proc}
+        end
+        ",
+        a = push_u256(a),
+        b = push_u256(b),
+    );
+
+    run_within_tx_kernel(IMPORTS, &code, StackInputs::default(), MemAdviceProvider::default(), None)
+        .ok()
+        .map(|process| (0..16).map(|i| process.stack.get(i)).collect())
+}
+
+fn test_values() -> Vec<U256> {
+    vec![
+        U256::ZERO,
+        U256::from(1),
+        U256::from(u64::MAX),
+        U256::from_limbs([u32::MAX, 0, 7, 0, u32::MAX, 0, 0, 1]),
+        U256::from_limbs([3, 1, 4, 1, 5, 9, 2, 6]),
+        U256::MAX,
+    ]
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn test_u256_encoding_round_trip() {
+    for value in test_values() {
+        assert_eq!(U256::from_be_bytes(value.to_be_bytes()), value);
+        assert_eq!(U256::try_from_elements(&value.to_elements()).unwrap(), value);
+    }
+
+    let mut bytes = [0_u8; 32];
+    bytes[31] = 1;
+    bytes[0] = 0x80;
+    let value = U256::from_be_bytes(bytes);
+    assert_eq!(value.limbs(), [1, 0, 0, 0, 0, 0, 0, 0x8000_0000]);
+
+    assert!(U256::try_from_elements(&[Felt::new(1); 7]).is_err());
+    assert!(U256::try_from_elements(&[Felt::new(1 << 32); 8]).is_err());
+}
+
+#[test]
+fn test_u256_felt_conversions() {
+    for felt in [Felt::new(0), Felt::new(u32::MAX as u64 + 5), Felt::new(Felt::MODULUS - 1)] {
+        let code = format!(
+            "
+            begin
+                push.{felt}
+                exec.u256::from_felt
+                dupw.1 dupw.1
+                exec.u256::to_felt
+            end
+            "
+        );
+        let process = run_within_tx_kernel(
+            IMPORTS,
+            &code,
+            StackInputs::default(),
+            MemAdviceProvider::default(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(process.stack.get(0), felt);
+        let limbs = (1..9).map(|i| process.stack.get(i)).collect::<Vec<_>>();
+        assert_eq!(U256::try_from_elements(&limbs).unwrap(), U256::from(felt));
+        assert_eq!(U256::from(felt).to_felt(), Some(felt));
+    }
+
+    // values which do not fit into a field element cannot be converted
+    let too_large = U256::from(Felt::MODULUS);
+    assert_eq!(too_large.to_felt(), None);
+    let code = format!("begin {} exec.u256::to_felt end", push_u256(too_large));
+    assert!(run_within_tx_kernel(
+        IMPORTS,
+        &code,
+        StackInputs::default(),
+        MemAdviceProvider::default(),
+        None
+    )
+    .is_err());
+}
+
+#[test]
+fn test_u256_arithmetic() {
+    for a in test_values() {
+        for b in test_values() {
+            let wrapping_add = exec_binary("wrapping_add", a, b).unwrap();
+            assert_eq!(wrapping_add[..8], a.wrapping_add(&b).to_elements());
+            let checked_add = exec_binary("checked_add", a, b);
+            assert_eq!(
+                checked_add.map(|stack| stack[..8].to_vec()),
+                a.checked_add(&b).map(|c| c.to_elements().to_vec())
+            );
+
+            let wrapping_sub = exec_binary("wrapping_sub", a, b).unwrap();
+            assert_eq!(wrapping_sub[..8], a.wrapping_sub(&b).to_elements());
+            let checked_sub = exec_binary("checked_sub", a, b);
+            assert_eq!(
+                checked_sub.map(|stack| stack[..8].to_vec()),
+                a.checked_sub(&b).map(|c| c.to_elements().to_vec())
+            );
+
+            let checked_mul = exec_binary("checked_mul", a, b);
+            assert_eq!(
+                checked_mul.map(|stack| stack[..8].to_vec()),
+                a.checked_mul(&b).map(|c| c.to_elements().to_vec())
+            );
+        }
+    }
+}
+
+#[test]
+fn test_u256_comparisons() {
+    for a in test_values() {
+        for b in test_values() {
+            for (proc, expected) in
+                [("eq", a == b), ("lt", a < b), ("gt", a > b), ("lte", a <= b), ("gte", a >= b)]
+            {
+                let stack = exec_binary(proc, a, b).unwrap();
+                assert_eq!(
+                    stack[0],
+                    Felt::new(expected as u64),
+                    "u256::{proc} failed for {a} and {b}"
+                );
+            }
+        }
+
+        let code = format!("begin {} exec.u256::is_zero end", push_u256(a));
+        let process = run_within_tx_kernel(
+            IMPORTS,
+            &code,
+            StackInputs::default(),
+            MemAdviceProvider::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(process.stack.get(0), Felt::new(a.is_zero() as u64));
+    }
+}
@@ -1,6 +1,47 @@
 use core::fmt;
 
-use super::Digest;
+use super::{Digest, TransactionEvent};
+
+// TRANSACTION KERNEL ERROR CODES
+// ================================================================================================
+
+/// Error code raised by the transaction kernel when the code of an account whose type does not
+/// allow code updates (i.e., any account other than a regular account with updatable code) is
+/// changed.
+pub const ERR_ACCOUNT_CODE_IS_NOT_UPDATABLE: u32 = 0x3_0000; // 196608
+
+/// Error code raised by the transaction kernel when a map procedure is invoked on an account
+/// storage slot which is not of a map type.
+pub const ERR_ACCOUNT_STORAGE_SLOT_IS_NOT_MAP: u32 = 0x3_0001; // 196609
+
+/// Error code raised by the transaction kernel when a note is created with a tag which is not a
+/// u32 value.
+pub const ERR_INVALID_NOTE_TAG: u32 = 0x3_0002; // 196610
+
+/// Error code raised by the transaction kernel when the expiration block number of a transaction
+/// is set to a block which is not after the reference block of the transaction.
+pub const ERR_INVALID_TX_EXPIRATION_BLOCK_NUM: u32 = 0x3_0003; // 196611
+
+/// Error code raised by the transaction kernel when the expiration block number of a note is set
+/// to a value which is not a u32 value, is 0, or is [u32::MAX].
+pub const ERR_INVALID_NOTE_EXPIRATION_BLOCK_NUM: u32 = 0x3_0004; // 196612
+
+/// Error code raised by the transaction kernel when the metadata of the most recently created note
+/// is updated before any notes were created by the transaction.
+pub const ERR_NO_CREATED_NOTES: u32 = 0x3_0005; // 196613
+
+/// Error code raised by the transaction kernel when an account procedure which can be invoked by
+/// transaction scripts only is invoked during the execution of an input note.
+pub const ERR_ACCOUNT_PROCEDURE_IS_TX_SCRIPT_ONLY: u32 = 0x3_0006; // 196614
+
+/// Error code raised by the transaction kernel when an internal account procedure is invoked by a
+/// note script or a transaction script.
+pub const ERR_ACCOUNT_PROCEDURE_IS_INTERNAL: u32 = 0x3_0007; // 196615
+
+/// Error code raised by the transaction kernel when a consumed note expires at or before the
+/// reference block of the transaction, i.e., the note would expire before the transaction could be
+/// included into a block.
+pub const ERR_CONSUMED_NOTE_EXPIRED: u32 = 0x3_0008; // 196616
 
 // TRANSACTION KERNEL ERROR
 // ================================================================================================
@@ -23,12 +64,38 @@ impl fmt::Display for TransactionKernelError {
 #[cfg(feature = "std")]
 impl std::error::Error for TransactionKernelError {}
 
+// EVENT ID ERROR
+// ================================================================================================
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EventIdError {
+    NotUserNamespace(u16),
+    ReservedNamespace(u16),
+}
+
+impl fmt::Display for EventIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotUserNamespace(prefix) => {
+                write!(f, "event ID prefix {prefix:#06x} is not in the user range")
+            },
+            Self::ReservedNamespace(prefix) => {
+                write!(f, "event ID prefix {prefix:#06x} is reserved")
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EventIdError {}
+
 // TRANSACTION EVENT PARSING ERROR
 // ================================================================================================
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum TransactionEventParsingError {
     InvalidTransactionEvent(u32),
+    MalformedPayload(TransactionEvent),
     NotTransactionEvent(u32),
 }
 
@@ -38,6 +105,9 @@ impl fmt::Display for TransactionEventParsingError {
             Self::InvalidTransactionEvent(event_id) => {
                 write!(f, "event {event_id} is not a valid transaction kernel event")
             },
+            Self::MalformedPayload(event) => {
+                write!(f, "operand stack does not contain a valid payload for the {event} event")
+            },
             Self::NotTransactionEvent(event_id) => {
                 write!(f, "event {event_id} is not a transaction kernel event")
             },
@@ -1,6 +1,146 @@
 use core::fmt;
 
-use super::TransactionEventParsingError;
+use miden_objects::assets::Asset;
+
+use super::{AccountId, Digest, EventIdError, Felt, TransactionEventParsingError, Word};
+
+// EVENT NAMESPACE
+// ================================================================================================
+
+/// A namespace of event IDs.
+///
+/// The upper 16 bits of an event ID (the event ID prefix) identify the namespace of the event,
+/// and the lower 16 bits identify the event within its namespace. Event ID prefixes are allocated
+/// as follows:
+/// - `0x0000..=0x0001`: reserved for events emitted by the VM and the standard library.
+/// - `0x0002`: events emitted by the transaction kernel (see [TransactionEvent]).
+/// - `0x0003`: events emitted by the account components and note scripts of miden-lib.
+/// - `0x0004..=0x00ff`: reserved for future use.
+/// - `0x0100..=0xffff`: events emitted by third-party account components and note scripts.
+///
+/// Third-party components should pick a prefix from the user range and document it, so that
+/// collisions between components can be detected when the components are composed (e.g., when
+/// handlers for their events are registered with a transaction host).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum EventNamespace {
+    Kernel,
+    MidenLib,
+    User(u16),
+}
+
+impl EventNamespace {
+    /// Event ID prefix of the transaction kernel namespace.
+    pub const KERNEL_PREFIX: u16 = TransactionEvent::EVENT_ID_PREFIX;
+
+    /// Event ID prefix of the miden-lib namespace.
+    pub const MIDEN_LIB_PREFIX: u16 = 3;
+
+    /// Smallest event ID prefix of the user range.
+    pub const MIN_USER_PREFIX: u16 = 0x0100;
+
+    /// Returns a new user namespace with the specified prefix.
+    ///
+    /// # Errors
+    /// Returns an error if the prefix is not in the user range.
+    pub fn user(prefix: u16) -> Result<Self, EventIdError> {
+        if prefix < Self::MIN_USER_PREFIX {
+            return Err(EventIdError::NotUserNamespace(prefix));
+        }
+        Ok(Self::User(prefix))
+    }
+
+    /// Returns the event ID prefix of this namespace.
+    pub fn prefix(&self) -> u16 {
+        match self {
+            Self::Kernel => Self::KERNEL_PREFIX,
+            Self::MidenLib => Self::MIDEN_LIB_PREFIX,
+            Self::User(prefix) => *prefix,
+        }
+    }
+}
+
+impl TryFrom<u16> for EventNamespace {
+    type Error = EventIdError;
+
+    fn try_from(prefix: u16) -> Result<Self, Self::Error> {
+        match prefix {
+            Self::KERNEL_PREFIX => Ok(Self::Kernel),
+            Self::MIDEN_LIB_PREFIX => Ok(Self::MidenLib),
+            prefix if prefix >= Self::MIN_USER_PREFIX => Ok(Self::User(prefix)),
+            prefix => Err(EventIdError::ReservedNamespace(prefix)),
+        }
+    }
+}
+
+impl fmt::Display for EventNamespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Kernel => write!(f, "kernel"),
+            Self::MidenLib => write!(f, "miden-lib"),
+            Self::User(prefix) => write!(f, "user({prefix:#06x})"),
+        }
+    }
+}
+
+// EVENT ID
+// ================================================================================================
+
+/// ID of an event emitted via the `emit.<event_id>` instruction, split into an [EventNamespace]
+/// (the upper 16 bits) and an index within the namespace (the lower 16 bits).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct EventId {
+    namespace: EventNamespace,
+    index: u16,
+}
+
+impl EventId {
+    /// Returns a new [EventId] for the event with the specified index in the specified namespace.
+    pub fn new(namespace: EventNamespace, index: u16) -> Self {
+        Self { namespace, index }
+    }
+
+    /// Returns the namespace of this event.
+    pub fn namespace(&self) -> EventNamespace {
+        self.namespace
+    }
+
+    /// Returns the index of this event within its namespace.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the value of this event ID as used by the `emit` instruction.
+    pub fn as_u32(&self) -> u32 {
+        ((self.namespace.prefix() as u32) << 16) | self.index as u32
+    }
+}
+
+impl TryFrom<u32> for EventId {
+    type Error = EventIdError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        let namespace = EventNamespace::try_from((value >> 16) as u16)?;
+        Ok(Self::new(namespace, value as u16))
+    }
+}
+
+impl From<TransactionEvent> for EventId {
+    fn from(event: TransactionEvent) -> Self {
+        Self::new(EventNamespace::Kernel, event as u32 as u16)
+    }
+}
+
+impl From<EventId> for u32 {
+    fn from(event_id: EventId) -> Self {
+        event_id.as_u32()
+    }
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{:#06x}", self.namespace, self.index)
+    }
+}
 
 // TRANSACTION EVENT
 // ================================================================================================
@@ -10,7 +150,7 @@ use super::TransactionEventParsingError;
 /// The events are emitted via the `emit.<event_id>` instruction. The event ID is a 32-bit
 /// unsigned integer which is used to identify the event type. For events emitted by the
 /// transaction kernel, the event_id is structured as follows:
-/// - The upper 16 bits of the event ID are set to 2.
+/// - The upper 16 bits of the event ID are set to 2 (see [EventNamespace::Kernel]).
 /// - The lower 16 bits represent a unique event ID within the transaction kernel.
 #[repr(u32)]
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -18,11 +158,101 @@ pub enum TransactionEvent {
     AddAssetToAccountVault = 0x2_0000,      // 131072
     RemoveAssetFromAccountVault = 0x2_0001, // 131073
     PushAccountProcedureIndex = 0x2_0002,   // 131074
+    SetAccountStorageItem = 0x2_0003,       // 131075
+    NotesProcessingStart = 0x2_0004,        // 131076
+    NoteExecutionStart = 0x2_0005,          // 131077
+    NoteExecutionEnd = 0x2_0006,            // 131078
+    TxScriptProcessingStart = 0x2_0007,     // 131079
+    EpilogueStart = 0x2_0008,               // 131080
+    GetForeignAccountItem = 0x2_0009,       // 131081
+    SetAccountStorageMapItem = 0x2_000a,    // 131082
+    CreateNote = 0x2_000b,                  // 131083
+    BuildRecipient = 0x2_000c,              // 131084
 }
 
 impl TransactionEvent {
     /// Value of the top 16 bits of a transaction kernel event ID.
     pub const EVENT_ID_PREFIX: u16 = 2;
+
+    /// Returns true if this event signals the progress of the transaction through the phases of
+    /// the kernel rather than requesting the host to act on the state of the transaction.
+    pub fn is_trace_event(&self) -> bool {
+        matches!(
+            self,
+            Self::NotesProcessingStart
+                | Self::NoteExecutionStart
+                | Self::NoteExecutionEnd
+                | Self::TxScriptProcessingStart
+                | Self::EpilogueStart
+        )
+    }
+
+    /// Decodes the data carried by this event from the state of the operand stack at the time
+    /// the event was emitted.
+    ///
+    /// The stack is expected to be ordered from the top, i.e., `stack[0]` is the top of the stack
+    /// (as returned by `ProcessState::get_stack_state()`).
+    ///
+    /// # Errors
+    /// Returns an error if the stack does not contain a valid payload for this event.
+    pub fn decode_payload(
+        &self,
+        stack: &[Felt],
+    ) -> Result<TransactionEventPayload, TransactionEventParsingError> {
+        let malformed = || TransactionEventParsingError::MalformedPayload(self.clone());
+        let item = |pos: usize| stack.get(pos).copied().ok_or_else(malformed);
+        let word = |pos: usize| -> Result<Word, TransactionEventParsingError> {
+            Ok([item(pos + 3)?, item(pos + 2)?, item(pos + 1)?, item(pos)?])
+        };
+        let slot_index = |pos: usize| -> Result<u8, TransactionEventParsingError> {
+            u8::try_from(item(pos)?.as_int()).map_err(|_| malformed())
+        };
+
+        use TransactionEvent::*;
+        match self {
+            // stack: [ASSET, ...]
+            AddAssetToAccountVault | RemoveAssetFromAccountVault => {
+                let asset = Asset::try_from(word(0)?).map_err(|_| malformed())?;
+                Ok(TransactionEventPayload::Asset(asset))
+            },
+            // stack: [PROC_ROOT, CODE_ROOT, ...]
+            PushAccountProcedureIndex => {
+                Ok(TransactionEventPayload::ProcedureRoot(word(0)?.into()))
+            },
+            // stack: [index, V', ...]
+            SetAccountStorageItem => {
+                Ok(TransactionEventPayload::StorageItem { index: slot_index(0)?, value: word(1)? })
+            },
+            // stack: [index, KEY, VALUE, ...]
+            SetAccountStorageMapItem => Ok(TransactionEventPayload::StorageMapItem {
+                index: slot_index(0)?,
+                key: word(1)?.into(),
+                value: word(5)?,
+            }),
+            // stack: [ASSET, tag, RECIPIENT, ...]
+            CreateNote => Ok(TransactionEventPayload::CreatedNote {
+                asset: Asset::try_from(word(0)?).map_err(|_| malformed())?,
+                recipient: word(5)?.into(),
+            }),
+            // stack: [SERIAL_NUM, MEMO, SCRIPT_HASH, INPUTS_HASH, ...]
+            BuildRecipient => Ok(TransactionEventPayload::RecipientPreimage {
+                serial_num: word(0)?,
+                memo: word(4)?.into(),
+                script_hash: word(8)?.into(),
+                inputs_hash: word(12)?.into(),
+            }),
+            // stack: [ACCT_DB_ROOT, foreign_acct_id, index, ...]
+            GetForeignAccountItem => Ok(TransactionEventPayload::ForeignAccountItem {
+                account_id: AccountId::try_from(item(4)?).map_err(|_| malformed())?,
+                index: slot_index(5)?,
+            }),
+            NotesProcessingStart
+            | NoteExecutionStart
+            | NoteExecutionEnd
+            | TxScriptProcessingStart
+            | EpilogueStart => Ok(TransactionEventPayload::None),
+        }
+    }
 }
 
 impl fmt::Display for TransactionEvent {
@@ -43,7 +273,81 @@ impl TryFrom<u32> for TransactionEvent {
             0x2_0000 => Ok(TransactionEvent::AddAssetToAccountVault),
             0x2_0001 => Ok(TransactionEvent::RemoveAssetFromAccountVault),
             0x2_0002 => Ok(TransactionEvent::PushAccountProcedureIndex),
+            0x2_0003 => Ok(TransactionEvent::SetAccountStorageItem),
+            0x2_0004 => Ok(TransactionEvent::NotesProcessingStart),
+            0x2_0005 => Ok(TransactionEvent::NoteExecutionStart),
+            0x2_0006 => Ok(TransactionEvent::NoteExecutionEnd),
+            0x2_0007 => Ok(TransactionEvent::TxScriptProcessingStart),
+            0x2_0008 => Ok(TransactionEvent::EpilogueStart),
+            0x2_0009 => Ok(TransactionEvent::GetForeignAccountItem),
+            0x2_000a => Ok(TransactionEvent::SetAccountStorageMapItem),
+            0x2_000b => Ok(TransactionEvent::CreateNote),
+            0x2_000c => Ok(TransactionEvent::BuildRecipient),
             _ => Err(TransactionEventParsingError::InvalidTransactionEvent(value)),
         }
     }
 }
+
+// TRANSACTION EVENT PAYLOAD
+// ================================================================================================
+
+/// Data carried by a [TransactionEvent], decoded from the operand stack at the time the event
+/// was emitted via [TransactionEvent::decode_payload()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionEventPayload {
+    /// The asset added to or removed from the account vault.
+    Asset(Asset),
+    /// The recipient and the asset of the note being created.
+    CreatedNote { asset: Asset, recipient: Digest },
+    /// The ID of the foreign account and the index of the storage slot read from it.
+    ForeignAccountItem { account_id: AccountId, index: u8 },
+    /// The root of the account procedure whose index is requested.
+    ProcedureRoot(Digest),
+    /// The preimage of the note recipient being built.
+    RecipientPreimage {
+        serial_num: Word,
+        memo: Digest,
+        script_hash: Digest,
+        inputs_hash: Digest,
+    },
+    /// The index and the new value of the account storage slot being updated.
+    StorageItem { index: u8, value: Word },
+    /// The index of the account storage slot holding the map being updated, and the key and the
+    /// new value of the map entry.
+    StorageMapItem { index: u8, key: Digest, value: Word },
+    /// The event carries no data (e.g., events signaling the transitions between kernel phases).
+    None,
+}
+
+impl fmt::Display for TransactionEventPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Asset(asset) => write!(f, "asset {asset:?}"),
+            Self::CreatedNote { asset, recipient } => {
+                write!(f, "note for recipient {recipient} with asset {asset:?}")
+            },
+            Self::ForeignAccountItem { account_id, index } => {
+                write!(f, "storage slot {index} of foreign account {account_id}")
+            },
+            Self::ProcedureRoot(root) => write!(f, "procedure {root}"),
+            Self::RecipientPreimage {
+                serial_num,
+                memo,
+                script_hash,
+                inputs_hash,
+            } => write!(
+                f,
+                "recipient with serial number {}, memo {memo}, script {script_hash} and inputs \
+                 {inputs_hash}",
+                Digest::from(*serial_num)
+            ),
+            Self::StorageItem { index, value } => {
+                write!(f, "storage slot {index} set to {}", Digest::from(*value))
+            },
+            Self::StorageMapItem { index, key, value } => {
+                write!(f, "key {key} of storage map {index} set to {}", Digest::from(*value))
+            },
+            Self::None => Ok(()),
+        }
+    }
+}
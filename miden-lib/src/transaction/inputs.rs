@@ -1,74 +1,157 @@
 use miden_objects::{
-    accounts::Account,
+    accounts::{Account, AccountHeader},
+    assets::AssetVault,
     transaction::{
-        ChainMmr, ExecutedTransaction, InputNotes, PreparedTransaction, TransactionInputs,
-        TransactionScript, TransactionWitness,
+        ChainMmr, ExecutedTransaction, ForeignAccountInputs, InputNotes, PreparedTransaction,
+        TransactionInputs, TransactionScript, TransactionWitness,
     },
     utils::{collections::Vec, vec, IntoBytes},
     vm::{AdviceInputs, StackInputs},
-    Felt, Word, ZERO,
+    Felt, Word, WORD_SIZE, ZERO,
 };
 
-use super::TransactionKernel;
+use super::KernelVersion;
 
 // TRANSACTION KERNEL INPUTS
 // ================================================================================================
 
 /// Defines how inputs required to execute a transaction kernel can be extracted from self.
+///
+/// The inputs are built for the specified [KernelVersion], which must be the version the
+/// transaction program was compiled against.
 pub trait ToTransactionKernelInputs {
-    /// Returns stack and advice inputs required to execute the transaction kernel.
-    fn get_kernel_inputs(&self) -> (StackInputs, AdviceInputs);
+    /// Returns stack and advice inputs required to execute the specified transaction kernel.
+    fn get_kernel_inputs(&self, kernel: &KernelVersion) -> (StackInputs, AdviceInputs);
+
+    /// Returns stack and advice inputs required to execute the specified transaction kernel, with
+    /// the Merkle data of the account vault returned separately from the rest of the advice
+    /// inputs.
+    ///
+    /// The returned tuple is arranged as follows:
+    /// - Stack inputs of the transaction kernel.
+    /// - Advice inputs of the transaction kernel excluding the account vault data.
+    /// - Account vault data, i.e., inner nodes of the vault tree and its leaf entries.
+    ///
+    /// The kernel cannot be executed with the second component alone: the vault data must be
+    /// supplied on demand by the host (e.g., via a lazily loading advice provider). This avoids
+    /// materializing the entire vault in the advice provider when a transaction touches only a
+    /// few of the assets in it.
+    fn get_kernel_inputs_with_lazy_vault(
+        &self,
+        kernel: &KernelVersion,
+    ) -> (StackInputs, AdviceInputs, AdviceInputs);
 }
 
 impl ToTransactionKernelInputs for PreparedTransaction {
-    fn get_kernel_inputs(&self) -> (StackInputs, AdviceInputs) {
-        let account = self.account();
-        let stack_inputs = TransactionKernel::build_input_stack(
-            account.id(),
-            if account.is_new() { None } else { Some(account.hash()) },
-            self.input_notes().commitment(),
-            self.block_header().hash(),
+    fn get_kernel_inputs(&self, kernel: &KernelVersion) -> (StackInputs, AdviceInputs) {
+        let (stack_inputs, mut advice_inputs, vault_inputs) = build_kernel_inputs(
+            kernel,
+            self.tx_inputs(),
+            self.tx_script(),
+            self.tx_script_args(),
+            AdviceInputs::default(),
         );
-
-        let mut advice_inputs = AdviceInputs::default();
-        extend_advice_inputs(self.tx_inputs(), self.tx_script(), &mut advice_inputs);
-
+        advice_inputs.extend(vault_inputs);
         (stack_inputs, advice_inputs)
     }
+
+    fn get_kernel_inputs_with_lazy_vault(
+        &self,
+        kernel: &KernelVersion,
+    ) -> (StackInputs, AdviceInputs, AdviceInputs) {
+        build_kernel_inputs(
+            kernel,
+            self.tx_inputs(),
+            self.tx_script(),
+            self.tx_script_args(),
+            AdviceInputs::default(),
+        )
+    }
 }
 
 impl ToTransactionKernelInputs for ExecutedTransaction {
-    fn get_kernel_inputs(&self) -> (StackInputs, AdviceInputs) {
-        let account = self.initial_account();
-        let stack_inputs = TransactionKernel::build_input_stack(
-            account.id(),
-            if account.is_new() { None } else { Some(account.hash()) },
-            self.input_notes().commitment(),
-            self.block_header().hash(),
+    fn get_kernel_inputs(&self, kernel: &KernelVersion) -> (StackInputs, AdviceInputs) {
+        let (stack_inputs, mut advice_inputs, vault_inputs) = build_kernel_inputs(
+            kernel,
+            self.tx_inputs(),
+            self.tx_script(),
+            self.tx_script_args(),
+            self.advice_witness().clone(),
         );
-
-        let mut advice_inputs = self.advice_witness().clone();
-        extend_advice_inputs(self.tx_inputs(), self.tx_script(), &mut advice_inputs);
-
+        advice_inputs.extend(vault_inputs);
         (stack_inputs, advice_inputs)
     }
+
+    fn get_kernel_inputs_with_lazy_vault(
+        &self,
+        kernel: &KernelVersion,
+    ) -> (StackInputs, AdviceInputs, AdviceInputs) {
+        build_kernel_inputs(
+            kernel,
+            self.tx_inputs(),
+            self.tx_script(),
+            self.tx_script_args(),
+            self.advice_witness().clone(),
+        )
+    }
 }
 
 impl ToTransactionKernelInputs for TransactionWitness {
-    fn get_kernel_inputs(&self) -> (StackInputs, AdviceInputs) {
-        let account = self.account();
-        let stack_inputs = TransactionKernel::build_input_stack(
-            account.id(),
-            if account.is_new() { None } else { Some(account.hash()) },
-            self.input_notes().commitment(),
-            self.block_header().hash(),
+    fn get_kernel_inputs(&self, kernel: &KernelVersion) -> (StackInputs, AdviceInputs) {
+        let (stack_inputs, mut advice_inputs, vault_inputs) = build_kernel_inputs(
+            kernel,
+            self.tx_inputs(),
+            self.tx_script(),
+            self.tx_script_args(),
+            self.advice_witness().clone(),
         );
-
-        let mut advice_inputs = self.advice_witness().clone();
-        extend_advice_inputs(self.tx_inputs(), self.tx_script(), &mut advice_inputs);
-
+        advice_inputs.extend(vault_inputs);
         (stack_inputs, advice_inputs)
     }
+
+    fn get_kernel_inputs_with_lazy_vault(
+        &self,
+        kernel: &KernelVersion,
+    ) -> (StackInputs, AdviceInputs, AdviceInputs) {
+        build_kernel_inputs(
+            kernel,
+            self.tx_inputs(),
+            self.tx_script(),
+            self.tx_script_args(),
+            self.advice_witness().clone(),
+        )
+    }
+}
+
+// KERNEL INPUTS BUILDER
+// ================================================================================================
+
+/// Builds stack inputs, advice inputs, and account vault advice data for executing a transaction
+/// with the specified inputs against the specified kernel version.
+///
+/// The provided advice inputs are extended with all data required by the transaction kernel except
+/// for the account vault data, which is returned as a separate set of advice inputs.
+fn build_kernel_inputs(
+    kernel: &KernelVersion,
+    tx_inputs: &TransactionInputs,
+    tx_script: Option<&TransactionScript>,
+    tx_script_args: Word,
+    mut advice_inputs: AdviceInputs,
+) -> (StackInputs, AdviceInputs, AdviceInputs) {
+    let account = tx_inputs.account();
+    let stack_inputs = kernel.build_input_stack(
+        account.id(),
+        if account.is_new() { None } else { Some(account.hash()) },
+        tx_inputs.input_notes().commitment(),
+        tx_inputs.block_header().hash(),
+    );
+
+    extend_advice_inputs(tx_inputs, tx_script, tx_script_args, &mut advice_inputs);
+
+    let mut vault_inputs = AdviceInputs::default();
+    add_account_vault_to_advice_inputs(account.vault(), &mut vault_inputs);
+
+    (stack_inputs, advice_inputs, vault_inputs)
 }
 
 // ADVICE INPUTS
@@ -77,16 +160,17 @@ impl ToTransactionKernelInputs for TransactionWitness {
 /// Extends the provided advice inputs with the data required for executing a transaction with the
 /// specified inputs.
 ///
-/// This includes the initial account, an optional account seed (required for new accounts), and
-/// the input note data, including core note data + authentication paths all the way to the root
-/// of one of chain MMR peaks.
+/// This includes the initial account (excluding the account vault data), an optional account seed
+/// (required for new accounts), and the input note data, including core note data + authentication
+/// paths all the way to the root of one of chain MMR peaks.
 fn extend_advice_inputs(
     tx_inputs: &TransactionInputs,
     tx_script: Option<&TransactionScript>,
+    tx_script_args: Word,
     advice_inputs: &mut AdviceInputs,
 ) {
     // build the advice stack
-    build_advice_stack(tx_inputs, tx_script, advice_inputs);
+    build_advice_stack(tx_inputs, tx_script, tx_script_args, advice_inputs);
 
     // build the advice map and Merkle store for relevant components
     add_chain_mmr_to_advice_inputs(tx_inputs.block_chain(), advice_inputs);
@@ -98,6 +182,9 @@ fn extend_advice_inputs(
 // ADVICE STACK BUILDER
 // ------------------------------------------------------------------------------------------------
 
+/// Number of elements placed onto the advice stack by [build_advice_stack()].
+const ADVICE_STACK_LEN: usize = 69;
+
 /// Builds the advice stack for the provided transaction inputs.
 ///
 /// The advice stack is arranged as follows:
@@ -115,43 +202,67 @@ fn extend_advice_inputs(
 ///  elements[44..47]  = account storage root
 ///  elements[48..51]  = account code root
 ///  elements[52]      = number of input notes
-///  elements[53..57]  = account seed, if one was provided; otherwise [ZERO; 4]
+///  elements[53..57]  = transaction script root, if one was provided; otherwise [ZERO; 4]
+///  elements[57..61]  = transaction script arguments, if a script was provided; otherwise [ZERO; 4]
+///  elements[61..65]  = [verification cost, base fee per note, base fee per cycle, fee faucet ID],
+///                      if fee parameters were provided; otherwise [ZERO; 4]
+///  elements[65..69]  = fee note recipient, if fee parameters were provided; otherwise [ZERO; 4]
 fn build_advice_stack(
     tx_inputs: &TransactionInputs,
     tx_script: Option<&TransactionScript>,
+    tx_script_args: Word,
     inputs: &mut AdviceInputs,
 ) {
+    // build the entire advice stack in a single pre-sized buffer to avoid growing the advice stack
+    // of the advice inputs multiple times
+    let mut stack = Vec::with_capacity(ADVICE_STACK_LEN);
+
     // push block header info into the stack
     let header = tx_inputs.block_header();
-    inputs.extend_stack(header.prev_hash());
-    inputs.extend_stack(header.chain_root());
-    inputs.extend_stack(header.account_root());
-    inputs.extend_stack(header.nullifier_root());
-    inputs.extend_stack(header.batch_root());
-    inputs.extend_stack(header.proof_hash());
-    inputs.extend_stack([header.block_num().into(), header.version(), header.timestamp(), ZERO]);
-    inputs.extend_stack([ZERO; 4]);
-    inputs.extend_stack(header.note_root());
+    stack.extend(header.prev_hash());
+    stack.extend(header.chain_root());
+    stack.extend(header.account_root());
+    stack.extend(header.nullifier_root());
+    stack.extend(header.batch_root());
+    stack.extend(header.proof_hash());
+    stack.extend([header.block_num().into(), header.version(), header.timestamp(), ZERO]);
+    stack.extend([ZERO; 4]);
+    stack.extend(header.note_root());
 
     // push core account items onto the stack
-    let account = tx_inputs.account();
-    inputs.extend_stack([account.id().into(), ZERO, ZERO, account.nonce()]);
-    inputs.extend_stack(account.vault().commitment());
-    inputs.extend_stack(account.storage().root());
-    inputs.extend_stack(account.code().root());
+    stack.extend(AccountHeader::from(tx_inputs.account()).to_elements());
 
     // push the number of input notes onto the stack
-    inputs.extend_stack([Felt::from(tx_inputs.input_notes().num_notes() as u32)]);
-
-    // push tx_script root onto the stack
-    if let Some(tx_script) = tx_script {
-        // insert the transaction script hash into the advice stack
-        inputs.extend_stack(*tx_script.hash());
-    } else {
-        // if no transaction script is provided, extend the advice stack with an empty transaction
-        // script root
-        inputs.extend_stack(Word::default());
+    stack.push(Felt::from(tx_inputs.input_notes().num_notes() as u32));
+
+    // push tx_script root onto the stack; if no transaction script is provided, an empty
+    // transaction script root is used
+    stack.extend(tx_script.map(|tx_script| *tx_script.hash()).unwrap_or_default());
+
+    // push tx_script arguments onto the stack; arguments are ignored if no transaction script is
+    // provided
+    stack.extend(tx_script.map(|_| tx_script_args).unwrap_or_default());
+
+    // push fee parameters and the fee note recipient onto the stack; if no fee parameters are
+    // provided, empty words are used which signal to the kernel that no fee is charged
+    let fee_parameters = tx_inputs
+        .fee_parameters()
+        .and_then(|params| params.fee_faucet_id.map(|faucet_id| (params, faucet_id)));
+    match fee_parameters {
+        Some((params, faucet_id)) => {
+            stack.extend([
+                Felt::new(params.verification_cost),
+                Felt::new(params.base_fee_per_note),
+                Felt::new(params.base_fee_per_cycle),
+                faucet_id.into(),
+            ]);
+            stack.extend(params.fee_recipient);
+        },
+        None => stack.extend([ZERO; 8]),
     }
+
+    debug_assert_eq!(stack.len(), ADVICE_STACK_LEN);
+    inputs.extend_stack(stack);
 }
 
 // CHAIN MMR INJECTOR
@@ -175,8 +286,10 @@ fn add_chain_mmr_to_advice_inputs(mmr: &ChainMmr, inputs: &mut AdviceInputs) {
 
     // insert MMR peaks info into the advice map
     let peaks = mmr.peaks();
-    let mut elements = vec![Felt::new(peaks.num_leaves() as u64), ZERO, ZERO, ZERO];
-    elements.extend(peaks.flatten_and_pad_peaks());
+    let peak_elements = peaks.flatten_and_pad_peaks();
+    let mut elements = Vec::with_capacity(WORD_SIZE + peak_elements.len());
+    elements.extend([Felt::new(peaks.num_leaves() as u64), ZERO, ZERO, ZERO]);
+    elements.extend(peak_elements);
     inputs.extend_map([(peaks.hash_peaks().into(), elements)]);
 }
 
@@ -185,15 +298,17 @@ fn add_chain_mmr_to_advice_inputs(mmr: &ChainMmr, inputs: &mut AdviceInputs) {
 
 /// Inserts core account data into the provided advice inputs.
 ///
+/// Account vault data is not inserted by this function; see [add_account_vault_to_advice_inputs()].
+///
 /// Inserts the following items into the Merkle store:
 /// - The Merkle nodes associated with the storage slots tree.
-/// - The Merkle nodes associated with the account vault tree.
+/// - The Merkle nodes associated with the storage maps trees, for each map tracked by the storage.
 /// - The Merkle nodes associated with the account code procedures tree.
 ///
 /// Inserts the following entries into the advice map:
 /// - The storage types commitment |-> storage slot types vector.
+/// - The node |-> (key, value), for all leaf nodes of the storage maps TSMTs.
 /// - The account procedure root |-> procedure index, for each account procedure.
-/// - The node |-> (key, value), for all leaf nodes of the asset vault TSMT.
 /// - [account_id, 0, 0, 0] |-> account_seed, when account seed is provided.
 fn add_account_to_advice_inputs(
     account: &Account,
@@ -206,27 +321,22 @@ fn add_account_to_advice_inputs(
     // extend the merkle store with the storage items
     inputs.extend_merkle_store(account.storage().slots().inner_nodes());
 
+    // extend the merkle store and advice map with the storage maps
+    // TODO: this currently handles only the upper leaves of the trees
+    for map in storage.maps().values() {
+        inputs.extend_merkle_store(map.inner_nodes());
+        inputs
+            .extend_map(map.upper_leaves().map(|(node, key, value)| {
+                (node.into(), (*key).into_iter().chain(value).collect())
+            }));
+    }
+
     // extend advice map with storage types commitment |-> storage types
     inputs.extend_map([(
         storage.slot_types_commitment().into(),
         storage.slot_types().iter().map(Felt::from).collect(),
     )]);
 
-    // --- account vault ------------------------------------------------------
-    let vault = account.vault();
-
-    // extend the merkle store with account vault data
-    inputs.extend_merkle_store(vault.asset_tree().inner_nodes());
-
-    // populate advice map with tiered merkle tree leaf nodes
-    // TODO: this currently handles only the upper leaves of the tree
-    inputs.extend_map(
-        vault
-            .asset_tree()
-            .upper_leaves()
-            .map(|(node, key, value)| (node.into(), (*key).into_iter().chain(value).collect())),
-    );
-
     // --- account code -------------------------------------------------------
     let code = account.code();
 
@@ -242,6 +352,30 @@ fn add_account_to_advice_inputs(
     }
 }
 
+// ACCOUNT VAULT INJECTOR
+// ------------------------------------------------------------------------------------------------
+
+/// Inserts account vault data into the provided advice inputs.
+///
+/// Inserts the following items into the Merkle store:
+/// - The Merkle nodes associated with the account vault tree.
+///
+/// Inserts the following entries into the advice map:
+/// - The node |-> (key, value), for all leaf nodes of the asset vault TSMT.
+fn add_account_vault_to_advice_inputs(vault: &AssetVault, inputs: &mut AdviceInputs) {
+    // extend the merkle store with account vault data
+    inputs.extend_merkle_store(vault.asset_tree().inner_nodes());
+
+    // populate advice map with tiered merkle tree leaf nodes
+    // TODO: this currently handles only the upper leaves of the tree
+    inputs.extend_map(
+        vault
+            .asset_tree()
+            .upper_leaves()
+            .map(|(node, key, value)| (node.into(), (*key).into_iter().chain(value).collect())),
+    );
+}
+
 // INPUT NOTE INJECTOR
 // ------------------------------------------------------------------------------------------------
 
@@ -258,10 +392,11 @@ fn add_account_to_advice_inputs(
 ///   out[8..12]   = input root
 ///   out[12..16]  = asset_hash
 ///   out[16..20]  = metadata
-///   out[20..24]  = asset_1
-///   out[24..28]  = asset_2
+///   out[20..24]  = memo
+///   out[24..28]  = asset_1
+///   out[28..32]  = asset_2
 ///   ...
-///   out[20 + num_assets * 4..] = Word::default() (this is conditional padding only applied
+///   out[24 + num_assets * 4..] = Word::default() (this is conditional padding only applied
 ///                                                 if the number of assets is odd)
 ///   out[-10]      = origin.block_number
 ///   out[-9..-5]   = origin.SUB_HASH
@@ -281,22 +416,21 @@ fn add_input_notes_to_advice_inputs(notes: &InputNotes, inputs: &mut AdviceInput
         return;
     }
 
-    let mut note_data = Vec::new();
+    // pre-size the combined note data vector; for each note it holds 6 words of core note data,
+    // the padded assets, and 10 elements of note origin data
+    let note_data_len = notes
+        .iter()
+        .map(|input_note| {
+            let num_assets = input_note.note().assets().num_assets();
+            6 * WORD_SIZE + (num_assets + num_assets % 2) * WORD_SIZE + 10
+        })
+        .sum();
+    let mut note_data = Vec::with_capacity(note_data_len);
+
     for input_note in notes.iter() {
         let note = input_note.note();
         let proof = input_note.proof();
-
-        // insert note inputs and assets into the advice map
-        inputs.extend_map([(note.inputs().hash().into(), note.inputs().inputs().to_vec())]);
-        inputs.extend_map([(note.assets().commitment().into(), note.assets().to_padded_assets())]);
-
-        // insert note authentication path nodes into the Merkle store
-        inputs.extend_merkle_store(
-            proof
-                .note_path()
-                .inner_nodes(proof.origin().node_index.value(), note.authentication_hash())
-                .unwrap(),
-        );
+        let padded_assets = note.assets().to_padded_assets();
 
         // add the note elements to the combined vector of note data
         note_data.extend(note.serial_num());
@@ -304,19 +438,75 @@ fn add_input_notes_to_advice_inputs(notes: &InputNotes, inputs: &mut AdviceInput
         note_data.extend(*note.inputs().hash());
         note_data.extend(*note.assets().commitment());
         note_data.extend(Word::from(note.metadata()));
+        note_data.extend(*note.memo());
 
-        note_data.extend(note.assets().to_padded_assets());
+        note_data.extend_from_slice(&padded_assets);
 
         note_data.push(proof.origin().block_num.into());
         note_data.extend(*proof.sub_hash());
         note_data.extend(*proof.note_root());
         note_data.push(proof.origin().node_index.value().into());
+
+        // insert note inputs and assets into the advice map; the padded assets vector is moved
+        // into the map to avoid building it twice
+        inputs.extend_map([
+            (note.inputs().hash().into(), note.inputs().inputs().to_vec()),
+            (note.assets().commitment().into(), padded_assets),
+        ]);
+
+        // insert note authentication path nodes into the Merkle store
+        inputs.extend_merkle_store(
+            proof
+                .note_path()
+                .inner_nodes(proof.origin().node_index.value(), note.authentication_hash())
+                .unwrap(),
+        );
     }
 
     // insert the combined note data into the advice map
     inputs.extend_map([(notes.commitment().into(), note_data)]);
 }
 
+// FOREIGN ACCOUNT INJECTOR
+// ------------------------------------------------------------------------------------------------
+
+/// Inserts the data required to read the storage of the provided foreign accounts via
+/// `account::get_foreign_item` into the provided advice inputs.
+///
+/// Inserts the following items into the Merkle store:
+/// - The Merkle nodes associated with the path of each foreign account in the account database.
+/// - The Merkle nodes associated with the storage slots tree of each foreign account.
+///
+/// Inserts the following entries into the advice map:
+/// - The foreign account hash |-> [account_id, 0, 0, nonce, VAULT_ROOT, STORAGE_ROOT, CODE_ROOT],
+///   for each foreign account.
+///
+/// # Panics
+/// Panics if the witness of a foreign account is not a valid path in the account database.
+pub fn add_foreign_accounts_to_advice_inputs(
+    foreign_accounts: &[ForeignAccountInputs],
+    inputs: &mut AdviceInputs,
+) {
+    for foreign_account in foreign_accounts {
+        let header = foreign_account.header();
+
+        // insert the account database path of the foreign account into the Merkle store
+        inputs.extend_merkle_store(
+            foreign_account
+                .witness()
+                .proof()
+                .inner_nodes(u64::from(header.id()), header.hash())
+                .unwrap(),
+        );
+
+        // insert the storage slots of the foreign account into the Merkle store
+        inputs.extend_merkle_store(foreign_account.storage().slots().inner_nodes());
+
+        // insert the header of the foreign account into the advice map
+        inputs.extend_map([(header.hash().into(), header.to_elements().to_vec())]);
+    }
+}
+
 // TRANSACTION SCRIPT INJECTOR
 // ------------------------------------------------------------------------------------------------
 
@@ -0,0 +1,131 @@
+use miden_objects::{
+    accounts::AccountId,
+    transaction::{TransactionFee, TransactionOutputs},
+    vm::{CodeBlock, Kernel, ProgramInfo, StackInputs, StackOutputs},
+    Digest, TransactionOutputError,
+};
+
+use super::{AdviceMap, TransactionKernel};
+
+// KERNEL VERSION
+// ================================================================================================
+
+/// A version of the transaction kernel, described by the executable program of the kernel and the
+/// set of procedures exposed by the kernel.
+///
+/// Transaction programs are compiled against a specific kernel version, and their proofs can be
+/// verified only against the [ProgramInfo] of the same version. Kernel versions other than
+/// [KernelVersion::current()] can be used to keep building and verifying transactions against
+/// older kernels during a rolling kernel upgrade.
+///
+/// The inputs and outputs of the kernel are built and parsed via the kernel version the
+/// transaction is executed with (e.g., [KernelVersion::build_input_stack()]). All kernel versions
+/// currently share the stack layout of the current kernel.
+#[derive(Clone, Debug)]
+pub struct KernelVersion {
+    main: CodeBlock,
+    program_info: ProgramInfo,
+}
+
+impl KernelVersion {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns the version of the transaction kernel defined by this library.
+    ///
+    /// # Panics
+    /// Panics if the transaction kernel source is not well-formed.
+    pub fn current() -> Self {
+        Self {
+            main: TransactionKernel::main_code_block(),
+            program_info: TransactionKernel::program_info(),
+        }
+    }
+
+    /// Returns a new [KernelVersion] with the provided executable program exposing the provided
+    /// kernel procedures.
+    pub fn new(main: CodeBlock, kernel: Kernel) -> Self {
+        let program_info = ProgramInfo::new(main.hash(), kernel);
+        Self { main, program_info }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the hash of the executable program of this kernel version.
+    pub fn hash(&self) -> Digest {
+        self.main.hash()
+    }
+
+    /// Returns the compiled executable program of this kernel version.
+    pub fn main_code_block(&self) -> &CodeBlock {
+        &self.main
+    }
+
+    /// Returns the [ProgramInfo] against which proofs of transactions compiled against this kernel
+    /// version are verified.
+    pub fn program_info(&self) -> &ProgramInfo {
+        &self.program_info
+    }
+
+    /// Returns the procedures exposed by this kernel version.
+    pub fn kernel(&self) -> &Kernel {
+        self.program_info.kernel()
+    }
+
+    // STACK INPUTS / OUTPUTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the input stack required to execute this kernel version.
+    ///
+    /// See [TransactionKernel::build_input_stack()] for the layout of the stack.
+    pub fn build_input_stack(
+        &self,
+        acct_id: AccountId,
+        init_acct_hash: Option<Digest>,
+        input_notes_hash: Digest,
+        block_hash: Digest,
+    ) -> StackInputs {
+        TransactionKernel::build_input_stack(acct_id, init_acct_hash, input_notes_hash, block_hash)
+    }
+
+    /// Returns the output stack produced by this kernel version.
+    ///
+    /// See [TransactionKernel::build_output_stack()] for the layout of the stack.
+    pub fn build_output_stack(
+        &self,
+        final_acct_hash: Digest,
+        output_notes_hash: Digest,
+        tx_script_root: Option<Digest>,
+        expiration_block_num: Option<u32>,
+        fee: Option<TransactionFee>,
+    ) -> StackOutputs {
+        TransactionKernel::build_output_stack(
+            final_acct_hash,
+            output_notes_hash,
+            tx_script_root,
+            expiration_block_num,
+            fee,
+        )
+    }
+
+    /// Returns [TransactionOutputs] constructed from the stack outputs and the advice map produced
+    /// by this kernel version.
+    ///
+    /// See [TransactionKernel::parse_transaction_outputs()] for the expected layout of the outputs.
+    ///
+    /// # Errors
+    /// Returns an error if the outputs are not valid outputs of this kernel version.
+    pub fn parse_transaction_outputs(
+        &self,
+        stack: &StackOutputs,
+        adv_map: &AdviceMap,
+    ) -> Result<TransactionOutputs, TransactionOutputError> {
+        TransactionKernel::parse_transaction_outputs(stack, adv_map)
+    }
+}
+
+impl Default for KernelVersion {
+    fn default() -> Self {
+        Self::current()
+    }
+}
@@ -39,6 +39,15 @@ pub const INPUT_VAULT_ROOT_PTR: MemoryAddress = 3;
 /// The memory address at which the output vault root is stored
 pub const OUTPUT_VAULT_ROOT_PTR: MemoryAddress = 4;
 
+/// The memory address at which the expiration block number of the transaction is stored.
+pub const TX_EXPIRATION_BLOCK_NUM_PTR: MemoryAddress = 5;
+
+/// The memory address at which the fee asset charged by the transaction is stored.
+pub const TX_FEE_ASSET_PTR: MemoryAddress = 6;
+
+/// The memory address at which the number of cycles for which the fee was charged is stored.
+pub const TX_FEE_NUM_CYCLES_PTR: MemoryAddress = 7;
+
 // GLOBAL INPUTS
 // ------------------------------------------------------------------------------------------------
 
@@ -63,6 +72,15 @@ pub const INIT_NONCE_PTR: MemoryAddress = 104;
 /// The memory address at which the transaction script mast root is store
 pub const TX_SCRIPT_ROOT_PTR: MemoryAddress = 105;
 
+/// The memory address at which the transaction script arguments are stored.
+pub const TX_SCRIPT_ARGS_PTR: MemoryAddress = 106;
+
+/// The memory address at which the fee parameters are stored.
+pub const FEE_PARAMS_PTR: MemoryAddress = 107;
+
+/// The memory address at which the recipient of the fee note is stored.
+pub const FEE_RECIPIENT_PTR: MemoryAddress = 108;
+
 // BLOCK DATA
 // ------------------------------------------------------------------------------------------------
 
@@ -197,7 +215,8 @@ pub const CONSUMED_NOTE_SCRIPT_ROOT_OFFSET: MemoryOffset = 2;
 pub const CONSUMED_NOTE_INPUTS_HASH_OFFSET: MemoryOffset = 3;
 pub const CONSUMED_NOTE_ASSET_HASH_OFFSET: MemoryOffset = 4;
 pub const CONSUMED_NOTE_METADATA_OFFSET: MemoryOffset = 5;
-pub const CONSUMED_NOTE_ASSETS_OFFSET: MemoryOffset = 6;
+pub const CONSUMED_NOTE_MEMO_OFFSET: MemoryOffset = 6;
+pub const CONSUMED_NOTE_ASSETS_OFFSET: MemoryOffset = 7;
 
 /// The maximum number of consumed notes that can be processed in a single transaction.
 pub const MAX_NUM_CONSUMED_NOTES: u32 = 1023;
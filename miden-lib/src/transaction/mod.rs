@@ -1,13 +1,14 @@
 use assembly::{ast::ProgramAst, utils::DeserializationError, Assembler, AssemblyContext};
 use miden_objects::{
     accounts::AccountId,
-    transaction::{OutputNotes, TransactionOutputs},
+    assets::FungibleAsset,
+    transaction::{OutputNotes, TransactionFee, TransactionOutputs},
     utils::{
         collections::{BTreeMap, Vec},
         group_slice_elements,
     },
-    vm::{ProgramInfo, StackInputs, StackOutputs},
-    Digest, Felt, TransactionOutputError, Word,
+    vm::{CodeBlock, ProgramInfo, StackInputs, StackOutputs},
+    Digest, Felt, StarkField, TransactionOutputError, Word, ZERO,
 };
 use miden_stdlib::StdLibrary;
 
@@ -16,19 +17,37 @@ use super::MidenLib;
 pub mod memory;
 
 mod events;
-pub use events::TransactionEvent;
+pub use events::{EventId, EventNamespace, TransactionEvent, TransactionEventPayload};
+
+mod kernel_version;
+pub use kernel_version::KernelVersion;
 
 mod inputs;
-pub use inputs::ToTransactionKernelInputs;
+pub use inputs::{add_foreign_accounts_to_advice_inputs, ToTransactionKernelInputs};
+
+mod procedures;
+pub use procedures::{kernel_procedure_name, kernel_procedure_trace_id, KERNEL_PROCEDURES};
 
 mod outputs;
 pub use outputs::{
-    notes_try_from_elements, parse_final_account_stub, FINAL_ACCOUNT_HASH_WORD_IDX,
+    notes_try_from_elements, parse_final_account_header, EXPIRATION_BLOCK_NUM_IDX, FEE_AMOUNT_IDX,
+    FEE_FAUCET_ID_IDX, FEE_NUM_CYCLES_IDX, FINAL_ACCOUNT_HASH_WORD_IDX,
     OUTPUT_NOTES_COMMITMENT_WORD_IDX, TX_SCRIPT_ROOT_WORD_IDX,
 };
 
+#[cfg(feature = "source-map")]
+mod source_map;
+#[cfg(feature = "source-map")]
+pub use source_map::{KernelSourceMap, ProcedureSource};
+
 mod errors;
-pub use errors::{TransactionEventParsingError, TransactionKernelError};
+pub use errors::{
+    EventIdError, TransactionEventParsingError, TransactionKernelError,
+    ERR_ACCOUNT_CODE_IS_NOT_UPDATABLE, ERR_ACCOUNT_PROCEDURE_IS_INTERNAL,
+    ERR_ACCOUNT_PROCEDURE_IS_TX_SCRIPT_ONLY, ERR_ACCOUNT_STORAGE_SLOT_IS_NOT_MAP,
+    ERR_CONSUMED_NOTE_EXPIRED, ERR_INVALID_NOTE_EXPIRATION_BLOCK_NUM, ERR_INVALID_NOTE_TAG,
+    ERR_INVALID_TX_EXPIRATION_BLOCK_NUM, ERR_NO_CREATED_NOTES,
+};
 
 // TRANSACTION KERNEL
 // ================================================================================================
@@ -54,19 +73,62 @@ impl TransactionKernel {
         ProgramAst::from_bytes(kernel_bytes)
     }
 
+    /// Returns the source map of the transaction kernel, which maps kernel procedures to their
+    /// locations in the MASM source code.
+    #[cfg(feature = "source-map")]
+    pub fn source_map() -> KernelSourceMap {
+        KernelSourceMap::load()
+    }
+
     /// Returns [ProgramInfo] for the transaction kernel executable program.
     ///
+    /// When the `std` feature is enabled, the transaction kernel is compiled at most once per
+    /// process, and the result is shared by all subsequent calls.
+    ///
     /// # Panics
     /// Panics if the transaction kernel source is not well-formed.
     pub fn program_info() -> ProgramInfo {
-        // TODO: construct kernel_main and kernel using lazy static or at build time
+        #[cfg(feature = "std")]
+        return Self::compiled_main().1.clone();
+
+        #[cfg(not(feature = "std"))]
+        return Self::compile_main().1;
+    }
+
+    /// Returns the compiled code block of the transaction kernel executable program.
+    ///
+    /// When the `std` feature is enabled, the transaction kernel is compiled at most once per
+    /// process, and the result is shared by all subsequent calls.
+    ///
+    /// # Panics
+    /// Panics if the transaction kernel source is not well-formed.
+    pub fn main_code_block() -> CodeBlock {
+        #[cfg(feature = "std")]
+        return Self::compiled_main().0.clone();
+
+        #[cfg(not(feature = "std"))]
+        return Self::compile_main().0;
+    }
+
+    /// Returns a reference to the process-wide compiled transaction kernel executable program.
+    #[cfg(feature = "std")]
+    fn compiled_main() -> &'static (CodeBlock, ProgramInfo) {
+        static COMPILED_MAIN: std::sync::OnceLock<(CodeBlock, ProgramInfo)> =
+            std::sync::OnceLock::new();
+        COMPILED_MAIN.get_or_init(Self::compile_main)
+    }
+
+    /// Compiles the transaction kernel executable program and returns its code block together
+    /// with the corresponding [ProgramInfo].
+    fn compile_main() -> (CodeBlock, ProgramInfo) {
         let assembler = Self::assembler();
         let main_ast = TransactionKernel::main().expect("main is well formed");
         let kernel_main = assembler
             .compile_in_context(&main_ast, &mut AssemblyContext::for_program(Some(&main_ast)))
             .expect("main is well formed");
 
-        ProgramInfo::new(kernel_main.hash(), assembler.kernel().clone())
+        let program_info = ProgramInfo::new(kernel_main.hash(), assembler.kernel().clone());
+        (kernel_main, program_info)
     }
 
     // ASSEMBLER CONSTRUCTOR
@@ -115,16 +177,41 @@ impl TransactionKernel {
         StackInputs::new(inputs)
     }
 
+    /// Returns the output stack produced by the transaction kernel.
+    ///
+    /// Stack: [TXSR, CNC, FAH, exp, fee_faucet_id, fee_amount, fee_num_cycles]
+    ///
+    /// Where:
+    /// - TXSR is the transaction script root, or [ZERO; 4] if no script was executed.
+    /// - CNC is the commitment to the notes created by the transaction.
+    /// - FAH is the final account hash of the account that the transaction is being
+    ///   executed against.
+    /// - exp is the number of the last block into which the transaction can be included, or 0 if
+    ///   the transaction does not expire.
+    /// - fee_faucet_id, fee_amount and fee_num_cycles are the faucet and the amount of the fee
+    ///   charged by the transaction, and the number of cycles for which the fee was charged, or 0
+    ///   if no fee was charged.
     pub fn build_output_stack(
         final_acct_hash: Digest,
         output_notes_hash: Digest,
         tx_script_root: Option<Digest>,
+        expiration_block_num: Option<u32>,
+        fee: Option<TransactionFee>,
     ) -> StackOutputs {
-        let mut outputs: Vec<Felt> = Vec::with_capacity(9);
+        let mut outputs: Vec<Felt> = Vec::with_capacity(16);
         outputs.extend(final_acct_hash);
         outputs.extend(output_notes_hash);
         outputs.extend(tx_script_root.unwrap_or_default());
         outputs.reverse();
+        outputs.push(Felt::from(expiration_block_num.unwrap_or(0)));
+        match fee {
+            Some(fee) => outputs.extend([
+                fee.asset().faucet_id().into(),
+                Felt::new(fee.asset().amount()),
+                Felt::from(fee.num_cycles()),
+            ]),
+            None => outputs.extend([ZERO; 3]),
+        }
         StackOutputs::from_elements(outputs, Vec::new()).unwrap()
     }
 
@@ -132,20 +219,67 @@ impl TransactionKernel {
     ///
     /// The data on the stack is expected to be arranged as follows:
     ///
-    /// Stack: [TXSR, CNC, FAH]
+    /// Stack: [TXSR, CNC, FAH, exp, fee_faucet_id, fee_amount, fee_num_cycles]
     ///
     /// Where:
     /// - TXSR is the transaction script root.
     /// - CNC is the commitment to the notes created by the transaction.
     /// - FAH is the final account hash of the account that the transaction is being
     ///   executed against.
-    pub fn parse_output_stack(stack: &StackOutputs) -> (Digest, Digest, Digest) {
+    /// - exp is the number of the last block into which the transaction can be included, or 0 if
+    ///   the transaction does not expire.
+    /// - fee_faucet_id, fee_amount and fee_num_cycles are the faucet and the amount of the fee
+    ///   charged by the transaction, and the number of cycles for which the fee was charged, or 0
+    ///   if no fee was charged.
+    ///
+    /// The fee is returned as the paid asset and the number of cycles for which it was charged.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The expiration block number is not a u32 value.
+    /// - A fee faucet is specified, but the fee is not a valid fungible asset or the number of
+    ///   cycles for which the fee was charged is not a u32 value.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_output_stack(
+        stack: &StackOutputs,
+    ) -> Result<
+        (Digest, Digest, Digest, Option<u32>, Option<(FungibleAsset, u32)>),
+        TransactionOutputError,
+    > {
         // TODO: use constants
         let tx_script_root = stack.get_stack_word(0).expect("first word missing").into();
         let output_notes_hash = stack.get_stack_word(4).expect("second word missing").into();
         let final_account_hash = stack.get_stack_word(8).expect("third word missing").into();
 
-        (final_account_hash, output_notes_hash, tx_script_root)
+        let expiration_block_num = stack
+            .get_stack_item(EXPIRATION_BLOCK_NUM_IDX)
+            .expect("expiration block number missing")
+            .as_int();
+        let expiration_block_num = match u32::try_from(expiration_block_num) {
+            Ok(0) => None,
+            Ok(value) => Some(value),
+            Err(_) => {
+                return Err(TransactionOutputError::InvalidExpirationBlockNum(expiration_block_num))
+            },
+        };
+
+        let fee_faucet_id = stack.get_stack_item(FEE_FAUCET_ID_IDX).expect("fee faucet missing");
+        let fee = if fee_faucet_id == ZERO {
+            None
+        } else {
+            let fee_amount = stack.get_stack_item(FEE_AMOUNT_IDX).expect("fee amount missing");
+            let asset = FungibleAsset::try_from([fee_amount, ZERO, ZERO, fee_faucet_id])
+                .map_err(TransactionOutputError::InvalidFee)?;
+
+            let num_cycles =
+                stack.get_stack_item(FEE_NUM_CYCLES_IDX).expect("fee cycles missing").as_int();
+            let num_cycles = u32::try_from(num_cycles)
+                .map_err(|_| TransactionOutputError::InvalidFeeNumCycles(num_cycles))?;
+
+            Some((asset, num_cycles))
+        };
+
+        Ok((final_account_hash, output_notes_hash, tx_script_root, expiration_block_num, fee))
     }
 
     // TRANSACTION OUTPUT PARSER
@@ -155,13 +289,18 @@ impl TransactionKernel {
     ///
     /// The output stack is expected to be arrange as follows:
     ///
-    /// Stack: [TXSR, CNC, FAH]
+    /// Stack: [TXSR, CNC, FAH, exp, fee_faucet_id, fee_amount, fee_num_cycles]
     ///
     /// Where:
     /// - TXSR is the transaction script root.
     /// - CNC is the commitment to the notes created by the transaction.
     /// - FAH is the final account hash of the account that the transaction is being
     ///   executed against.
+    /// - exp is the number of the last block into which the transaction can be included, or 0 if
+    ///   the transaction does not expire.
+    /// - fee_faucet_id, fee_amount and fee_num_cycles are the faucet and the amount of the fee
+    ///   charged by the transaction, and the number of cycles for which the fee was charged, or 0
+    ///   if no fee was charged.
     ///
     /// The actual data describing the new account state and output notes is expected to be located
     /// in the provided advice map under keys CNC and FAH.
@@ -169,7 +308,8 @@ impl TransactionKernel {
         stack: &StackOutputs,
         adv_map: &AdviceMap,
     ) -> Result<TransactionOutputs, TransactionOutputError> {
-        let (final_acct_hash, output_notes_hash, _tx_script_root) = Self::parse_output_stack(stack);
+        let (final_acct_hash, output_notes_hash, _tx_script_root, expiration_block_num, fee) =
+            Self::parse_output_stack(stack)?;
 
         // --- parse final account state --------------------------------------
         let final_account_data: &[Word] = group_slice_elements(
@@ -177,8 +317,8 @@ impl TransactionKernel {
                 .get(final_acct_hash)
                 .ok_or(TransactionOutputError::FinalAccountDataNotFound)?,
         );
-        let account = parse_final_account_stub(final_account_data)
-            .map_err(TransactionOutputError::FinalAccountStubDataInvalid)?;
+        let account = parse_final_account_header(final_account_data)
+            .map_err(TransactionOutputError::FinalAccountHeaderDataInvalid)?;
 
         // --- parse output notes ---------------------------------------------
 
@@ -211,7 +351,24 @@ impl TransactionKernel {
             output_notes
         };
 
-        Ok(TransactionOutputs { account, output_notes })
+        // --- parse fee ------------------------------------------------------
+
+        // the fee is paid into the last note created by the transaction
+        let fee = match fee {
+            Some((asset, num_cycles)) => {
+                let fee_note =
+                    output_notes.iter().last().ok_or(TransactionOutputError::FeeNoteNotFound)?;
+                Some(TransactionFee::new(asset, num_cycles, fee_note.id()))
+            },
+            None => None,
+        };
+
+        Ok(TransactionOutputs {
+            account,
+            output_notes,
+            expiration_block_num,
+            fee,
+        })
     }
 }
 
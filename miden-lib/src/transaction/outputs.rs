@@ -1,5 +1,5 @@
 use miden_objects::{
-    accounts::{AccountId, AccountStub},
+    accounts::{AccountHeader, AccountId},
     assets::Asset,
     notes::{NoteAssets, NoteId, NoteMetadata},
     transaction::OutputNote,
@@ -26,23 +26,39 @@ pub const OUTPUT_NOTES_COMMITMENT_WORD_IDX: usize = 1;
 /// The index of the word at which the final account hash is stored on the output stack.
 pub const FINAL_ACCOUNT_HASH_WORD_IDX: usize = 2;
 
-// ACCOUNT STUB EXTRACTOR
+/// The index of the element at which the expiration block number of the transaction is stored on
+/// the output stack.
+pub const EXPIRATION_BLOCK_NUM_IDX: usize = 12;
+
+/// The index of the element at which the ID of the faucet of the fee charged by the transaction is
+/// stored on the output stack.
+pub const FEE_FAUCET_ID_IDX: usize = 13;
+
+/// The index of the element at which the amount of the fee charged by the transaction is stored on
+/// the output stack.
+pub const FEE_AMOUNT_IDX: usize = 14;
+
+/// The index of the element at which the number of cycles for which the fee was charged is stored
+/// on the output stack.
+pub const FEE_NUM_CYCLES_IDX: usize = 15;
+
+// ACCOUNT HEADER EXTRACTOR
 // ================================================================================================
 
-/// Parses the stub account data returned by the VM into individual account component commitments.
-/// Returns a tuple of account ID, vault root, storage root, code root, and nonce.
-pub fn parse_final_account_stub(elements: &[Word]) -> Result<AccountStub, AccountError> {
+/// Parses the account data returned by the VM into the header of the final account, i.e., the
+/// account ID, nonce, and the commitments to the vault, storage, and code of the account.
+pub fn parse_final_account_header(elements: &[Word]) -> Result<AccountHeader, AccountError> {
     if elements.len() != ACCT_DATA_MEM_SIZE {
-        return Err(AccountError::StubDataIncorrectLength(elements.len(), ACCT_DATA_MEM_SIZE));
+        return Err(AccountError::HeaderDataIncorrectLength(elements.len(), ACCT_DATA_MEM_SIZE));
     }
 
     let id = AccountId::try_from(elements[ACCT_ID_AND_NONCE_OFFSET as usize][ACCT_ID_IDX])?;
     let nonce = elements[ACCT_ID_AND_NONCE_OFFSET as usize][ACCT_NONCE_IDX];
     let vault_root = elements[ACCT_VAULT_ROOT_OFFSET as usize].into();
-    let storage_root = elements[ACCT_STORAGE_ROOT_OFFSET as usize].into();
-    let code_root = elements[ACCT_CODE_ROOT_OFFSET as usize].into();
+    let storage_commitment = elements[ACCT_STORAGE_ROOT_OFFSET as usize].into();
+    let code_commitment = elements[ACCT_CODE_ROOT_OFFSET as usize].into();
 
-    Ok(AccountStub::new(id, nonce, vault_root, storage_root, code_root))
+    Ok(AccountHeader::new(id, nonce, vault_root, storage_commitment, code_commitment))
 }
 
 // NOTES EXTRACTOR
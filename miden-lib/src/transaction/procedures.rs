@@ -0,0 +1,70 @@
+use super::TransactionEvent;
+
+// KERNEL PROCEDURES
+// ================================================================================================
+
+/// Names of the procedures exposed by the transaction kernel, in the order in which they are
+/// defined in the kernel API.
+///
+/// Every kernel procedure starts with a `trace.<trace_id>` decorator, whose trace ID is derived
+/// from the index of the procedure in this list (see [kernel_procedure_trace_id()]). When tracing
+/// is enabled in the execution options, the decorators allow the host to report the kernel
+/// procedures entered by a transaction. Decorators do not affect the MAST roots of the procedures.
+pub const KERNEL_PROCEDURES: [&str; 38] = [
+    "get_account_id",
+    "get_account_nonce",
+    "get_initial_account_hash",
+    "get_current_account_hash",
+    "incr_account_nonce",
+    "get_account_item",
+    "get_foreign_account_item",
+    "set_account_item",
+    "get_account_map_item",
+    "set_account_map_item",
+    "set_account_code",
+    "account_vault_get_balance",
+    "account_vault_has_non_fungible_asset",
+    "account_vault_add_asset",
+    "account_vault_remove_asset",
+    "get_note_vault_info",
+    "get_note_inputs_hash",
+    "get_note_memo",
+    "get_note_serial_number",
+    "get_note_sender",
+    "get_block_number",
+    "get_block_timestamp",
+    "get_chain_root",
+    "get_block_hash",
+    "get_account_db_root",
+    "get_tx_script_args",
+    "get_input_notes_hash",
+    "get_output_notes_hash",
+    "create_note",
+    "set_note_expiration",
+    "set_note_aux",
+    "get_expiration_block_num",
+    "update_expiration_block_num",
+    "get_account_vault_commitment",
+    "mint_asset",
+    "burn_asset",
+    "get_fungible_faucet_total_issuance",
+    "build_recipient_hash",
+];
+
+/// Returns the ID of the trace emitted when the kernel procedure with the specified index in
+/// [KERNEL_PROCEDURES] is entered.
+///
+/// Like the IDs of the events emitted by the kernel, trace IDs of kernel procedures carry the
+/// kernel namespace prefix in their upper 16 bits.
+pub const fn kernel_procedure_trace_id(index: usize) -> u32 {
+    ((TransactionEvent::EVENT_ID_PREFIX as u32) << 16) | index as u32
+}
+
+/// Returns the name of the kernel procedure which emitted the trace with the specified ID, or None
+/// if the trace was not emitted by a kernel procedure.
+pub fn kernel_procedure_name(trace_id: u32) -> Option<&'static str> {
+    if trace_id >> 16 != TransactionEvent::EVENT_ID_PREFIX as u32 {
+        return None;
+    }
+    KERNEL_PROCEDURES.get((trace_id & 0xffff) as usize).copied()
+}
@@ -0,0 +1,155 @@
+use assembly::utils::{ByteReader, DeserializationError, SliceReader};
+use miden_objects::{
+    utils::{
+        collections::{BTreeMap, Vec},
+        serde::Deserializable,
+    },
+    Digest,
+};
+
+// PROCEDURE SOURCE
+// ================================================================================================
+
+/// Location of a single procedure in the MASM source code of the transaction kernel or of the
+/// miden library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcedureSource {
+    mast_root: Digest,
+    name: String,
+    file: String,
+    line: u32,
+    op_lines: Vec<u32>,
+}
+
+impl ProcedureSource {
+    /// Returns the fully-qualified name of the procedure (e.g., `miden::account::get_id`).
+    ///
+    /// Procedures of the kernel executable program are prefixed with `#exec`, and procedures of
+    /// the kernel API with `#sys`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the MAST root of the procedure, i.e., the digest by which the procedure is
+    /// identified in compiled programs.
+    pub fn mast_root(&self) -> Digest {
+        self.mast_root
+    }
+
+    /// Returns the path of the MASM file defining the procedure, relative to the `asm` directory
+    /// of miden-lib.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Returns the line at which the procedure is defined.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the line of the top-level operation of the procedure body at the specified index,
+    /// or None if the index is out of bounds.
+    pub fn op_line(&self, op_index: usize) -> Option<u32> {
+        self.op_lines.get(op_index).copied()
+    }
+}
+
+// KERNEL SOURCE MAP
+// ================================================================================================
+
+/// A map from the procedures of the transaction kernel (and of the miden library it is built
+/// from) to their locations in the MASM source code.
+///
+/// The source map is built together with the transaction kernel when the `source-map` feature is
+/// enabled. It allows debuggers and debug-mode executors to resolve procedures and operations
+/// encountered during execution back to the files and lines defining them.
+///
+/// Procedures are keyed by their MAST roots, as these are the only identifiers of procedures
+/// available in compiled programs. Procedures with identical bodies share the same MAST root; such
+/// roots are resolved to the first of these procedures in the order of their names.
+#[derive(Debug, Clone, Default)]
+pub struct KernelSourceMap {
+    procedures: Vec<ProcedureSource>,
+    roots: BTreeMap<Digest, usize>,
+}
+
+impl KernelSourceMap {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns the source map built together with the transaction kernel.
+    ///
+    /// # Panics
+    /// Panics if the source map packaged with the kernel is malformed.
+    pub fn load() -> Self {
+        let bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/kernels/transaction.srcmap"));
+        Self::read_from_bytes(bytes).expect("kernel source map is well formed")
+    }
+
+    /// Returns a source map read from the provided bytes in the format produced by the build
+    /// script of miden-lib.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a valid source map.
+    pub fn read_from_bytes(bytes: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(bytes);
+        let num_procedures = source.read_u32()?;
+        let mut procedures = Vec::new();
+        for _ in 0..num_procedures {
+            let mast_root = Digest::read_from(&mut source)?;
+            let name = read_string(&mut source)?;
+            let file = read_string(&mut source)?;
+            let line = source.read_u32()?;
+            let num_ops = source.read_u32()?;
+            let op_lines =
+                (0..num_ops).map(|_| source.read_u32()).collect::<Result<Vec<_>, _>>()?;
+
+            procedures.push(ProcedureSource { mast_root, name, file, line, op_lines });
+        }
+
+        procedures.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut roots = BTreeMap::new();
+        for (idx, procedure) in procedures.iter().enumerate() {
+            roots.entry(procedure.mast_root).or_insert(idx);
+        }
+
+        Ok(Self { procedures, roots })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the source of the procedure with the specified MAST root, if it is in this map.
+    pub fn get(&self, mast_root: &Digest) -> Option<&ProcedureSource> {
+        self.roots.get(mast_root).map(|&idx| &self.procedures[idx])
+    }
+
+    /// Returns the source of the procedure with the specified fully-qualified name, if it is in
+    /// this map.
+    pub fn get_by_name(&self, name: &str) -> Option<&ProcedureSource> {
+        self.procedures
+            .binary_search_by(|procedure| procedure.name.as_str().cmp(name))
+            .ok()
+            .map(|idx| &self.procedures[idx])
+    }
+
+    /// Returns an iterator over the sources of all procedures in this map, ordered by their
+    /// names.
+    pub fn iter(&self) -> impl Iterator<Item = &ProcedureSource> {
+        self.procedures.iter()
+    }
+
+    /// Returns the number of procedures in this map.
+    pub fn num_procedures(&self) -> usize {
+        self.procedures.len()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads a length-prefixed UTF-8 string from the provided source.
+fn read_string(source: &mut SliceReader) -> Result<String, DeserializationError> {
+    let len = source.read_u32()? as usize;
+    let bytes = source.read_vec(len)?;
+    String::from_utf8(bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+}
@@ -0,0 +1,92 @@
+use miden_objects::{
+    crypto::dsa::rpo_falcon512::KeyPair,
+    utils::{
+        collections::{BTreeMap, Vec},
+        string::ToString,
+    },
+    vm::AdviceInputs,
+    Digest, Felt, Word,
+};
+use vm_processor::{AdviceProvider, MemAdviceProvider, SignatureKind};
+
+use super::AuthenticationError;
+
+// TRANSACTION AUTHENTICATOR
+// ================================================================================================
+
+/// The [TransactionAuthenticator] trait defines the interface used by the transaction host to
+/// answer signature requests made by account authentication procedures.
+///
+/// When an authenticator is provided to a [TransactionExecutor](crate::TransactionExecutor), the
+/// secret keys of the account do not need to be placed into the advice inputs of the transaction.
+/// Instead, whenever the kernel requests a signature over a message, the authenticator is asked to
+/// produce it. The produced signature is recorded in the advice map of the transaction witness so
+/// that the transaction can later be proven without access to the authenticator.
+///
+/// Authenticators must be [Send] and [Sync] so that executors holding them can be shared across
+/// threads.
+pub trait TransactionAuthenticator: Send + Sync {
+    /// Returns a signature of the specified kind over `message` made with the secret key
+    /// associated with `pub_key`.
+    ///
+    /// The signature must be returned in the format expected by the signature verification
+    /// procedure used by the account (i.e., the same format as the one produced by
+    /// [AdviceProvider::get_signature()](vm_processor::AdviceProvider::get_signature)).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The authenticator does not hold a secret key for the specified public key.
+    /// - The authenticator does not support the requested signature kind.
+    /// - The authenticator refused to sign the message.
+    fn get_signature(
+        &self,
+        kind: SignatureKind,
+        pub_key: Word,
+        message: Word,
+    ) -> Result<Vec<Felt>, AuthenticationError>;
+}
+
+// BASIC AUTHENTICATOR
+// ================================================================================================
+
+/// A [TransactionAuthenticator] which signs messages with RpoFalcon512 key pairs held in memory.
+///
+/// Signatures are produced in the same way as by the advice provider of the VM, thus a
+/// transaction executed with this authenticator behaves exactly as if the secret keys were placed
+/// into the advice inputs of the transaction, except that the secret keys do not become a part of
+/// the transaction witness.
+pub struct BasicAuthenticator {
+    keys: BTreeMap<Digest, KeyPair>,
+}
+
+impl BasicAuthenticator {
+    /// Returns a new [BasicAuthenticator] holding the specified key pairs.
+    pub fn new(key_pairs: &[KeyPair]) -> Self {
+        let keys = key_pairs
+            .iter()
+            .cloned()
+            .map(|key_pair| (Digest::from(Word::from(key_pair.public_key())), key_pair))
+            .collect();
+
+        Self { keys }
+    }
+}
+
+impl TransactionAuthenticator for BasicAuthenticator {
+    fn get_signature(
+        &self,
+        kind: SignatureKind,
+        pub_key: Word,
+        message: Word,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        let key = Digest::from(pub_key);
+        let key_pair = self.keys.get(&key).ok_or(AuthenticationError::UnknownKey(key))?;
+
+        // the signature is produced by the advice provider of the VM holding only the secret key
+        // of the requested key pair
+        let secret_key = key_pair.to_bytes().iter().map(|byte| Felt::from(*byte)).collect();
+        MemAdviceProvider::from(AdviceInputs::default().with_map([(key.as_bytes(), secret_key)]))
+            .get_signature(kind, pub_key, message)
+            .map_err(|err| AuthenticationError::InternalError(err.to_string()))
+    }
+}
@@ -1,6 +1,9 @@
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError, RwLock};
+
+use miden_lib::transaction::KernelVersion;
 use miden_objects::{
     assembly::{Assembler, AssemblyContext, ModuleAst, ProgramAst},
-    transaction::{InputNotes, TransactionScript},
+    transaction::{InputNotes, TransactionInputs, TransactionScript},
     Felt, NoteError, TransactionScriptError, Word,
 };
 
@@ -23,30 +26,76 @@ mod tests;
 ///
 /// In addition to transaction compilation, transaction compiler provides methods which can be
 /// used to compile Miden account code and note scripts.
+///
+/// All methods of the compiler take `&self`: the cache of loaded account interfaces is kept behind
+/// a read-write lock, and the assembler behind a mutex. Thus, a single compiler (and the account
+/// interfaces loaded into it) can be shared by multiple threads.
+///
+/// Compilers targeting the current kernel version share a single process-wide assembler, so the
+/// transaction kernel and its libraries are loaded at most once per process. Cloned compilers
+/// share the assembler of the original compiler (e.g., a compiler built via
+/// [TransactionCompiler::with_kernel()] can be shared by an executor and a prover), while the
+/// account interfaces are copied.
 pub struct TransactionCompiler {
-    assembler: Assembler,
-    account_procedures: BTreeMap<AccountId, Vec<Digest>>,
-    kernel_main: CodeBlock,
+    assembler: Arc<Mutex<Assembler>>,
+    account_procedures: RwLock<BTreeMap<AccountId, Vec<Digest>>>,
+    kernel: KernelVersion,
 }
 
 impl TransactionCompiler {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new [TransactionCompiler].
+    /// Returns a new [TransactionCompiler] which compiles transactions against the current version
+    /// of the transaction kernel.
+    ///
+    /// The compiled transaction kernel executable program (see
+    /// [TransactionKernel::main_code_block()]) and the assembler are shared across all compiler
+    /// instances, thus instantiating a compiler does not require re-assembling the kernel or
+    /// miden-lib. Account interfaces are not shared: they are loaded separately into each compiler.
     pub fn new() -> TransactionCompiler {
-        let assembler = TransactionKernel::assembler();
-
-        // compile transaction kernel main
-        let main_ast = TransactionKernel::main().expect("main is well formed");
-        let kernel_main = assembler
-            .compile_in_context(&main_ast, &mut AssemblyContext::for_program(Some(&main_ast)))
-            .expect("main is well formed");
+        static SHARED_ASSEMBLER: OnceLock<Arc<Mutex<Assembler>>> = OnceLock::new();
 
         TransactionCompiler {
-            assembler,
-            account_procedures: BTreeMap::default(),
-            kernel_main,
+            assembler: SHARED_ASSEMBLER
+                .get_or_init(|| Arc::new(Mutex::new(TransactionKernel::assembler())))
+                .clone(),
+            account_procedures: RwLock::new(BTreeMap::default()),
+            kernel: KernelVersion::current(),
+        }
+    }
+
+    /// Returns a new [TransactionCompiler] which compiles transactions against the specified
+    /// kernel version, using the provided assembler to compile account code and scripts.
+    ///
+    /// Executors and provers built with the returned compiler (see
+    /// `TransactionExecutorBuilder::with_compiler()` and `TransactionProver::with_compiler()`)
+    /// execute and prove transactions against the specified kernel version.
+    ///
+    /// # Errors
+    /// Returns an error if the assembler was not instantiated with the procedures of the
+    /// specified kernel version.
+    pub fn with_kernel(
+        assembler: Assembler,
+        kernel: KernelVersion,
+    ) -> Result<TransactionCompiler, TransactionCompilerError> {
+        if assembler.kernel() != kernel.kernel() {
+            return Err(TransactionCompilerError::KernelMismatch(kernel.hash()));
         }
+
+        Ok(TransactionCompiler {
+            assembler: Arc::new(Mutex::new(assembler)),
+            account_procedures: RwLock::new(BTreeMap::default()),
+            kernel,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the version of the transaction kernel against which this compiler compiles
+    /// transactions.
+    pub fn kernel(&self) -> &KernelVersion {
+        &self.kernel
     }
 
     // ACCOUNT CODE AND NOTE SCRIPT COMPILERS
@@ -55,35 +104,59 @@ impl TransactionCompiler {
     /// Compiles the provided module into [AccountCode] and associates the resulting procedures
     /// with the specified account ID.
     pub fn load_account(
-        &mut self,
+        &self,
         account_id: AccountId,
         account_code: ModuleAst,
     ) -> Result<AccountCode, TransactionCompilerError> {
-        let account_code = AccountCode::new(account_code, &self.assembler)
+        let account_code = AccountCode::new(account_code, &self.assembler())
             .map_err(TransactionCompilerError::LoadAccountFailed)?;
-        self.account_procedures.insert(account_id, account_code.procedures().to_vec());
+        self.account_procedures
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(account_id, account_code.procedures().to_vec());
         Ok(account_code)
     }
 
     /// Loads the provided account interface (vector of procedure digests) into this compiler.
     /// Returns the old account interface if it previously existed.
     pub fn load_account_interface(
-        &mut self,
+        &self,
         account_id: AccountId,
         procedures: Vec<Digest>,
     ) -> Option<Vec<Digest>> {
-        self.account_procedures.insert(account_id, procedures)
+        self.account_procedures
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(account_id, procedures)
+    }
+
+    /// Removes the interface of the specified account from this compiler. Returns the removed
+    /// account interface if it previously existed.
+    ///
+    /// Account interfaces are kept until they are removed; thus, long-lived compilers which load
+    /// many accounts should remove the interfaces of the accounts they no longer compile
+    /// transactions for.
+    pub fn unload_account(&self, account_id: AccountId) -> Option<Vec<Digest>> {
+        self.account_procedures
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&account_id)
+    }
+
+    /// Returns true if the interface of the specified account has been loaded into this compiler.
+    pub fn has_account(&self, account_id: AccountId) -> bool {
+        self.get_account_interface(account_id).is_some()
     }
 
     /// Compiles the provided program into the [NoteScript] and checks (to the extent possible)
     /// if a note could be executed against all accounts with the specified interfaces.
     pub fn compile_note_script(
-        &mut self,
+        &self,
         note_script_ast: ProgramAst,
         target_account_proc: Vec<ScriptTarget>,
     ) -> Result<NoteScript, TransactionCompilerError> {
-        let (note_script, code_block) =
-            NoteScript::new(note_script_ast, &self.assembler).map_err(|err| match err {
+        let (note_script, code_block) = NoteScript::new(note_script_ast, &self.assembler())
+            .map_err(|err| match err {
                 NoteError::ScriptCompilationError(err) => {
                     TransactionCompilerError::CompileNoteScriptFailed(err)
                 },
@@ -103,7 +176,7 @@ impl TransactionCompiler {
     /// Constructs a [TransactionScript] by compiling the provided source code and checking the
     /// compatibility of the resulting program with the target account interfaces.
     pub fn compile_tx_script<T>(
-        &mut self,
+        &self,
         tx_script_ast: ProgramAst,
         tx_script_inputs: T,
         target_account_proc: Vec<ScriptTarget>,
@@ -112,13 +185,12 @@ impl TransactionCompiler {
         T: IntoIterator<Item = (Word, Vec<Felt>)>,
     {
         let (tx_script, code_block) =
-            TransactionScript::new(tx_script_ast, tx_script_inputs, &mut self.assembler).map_err(
-                |e| match e {
+            TransactionScript::new(tx_script_ast, tx_script_inputs, &mut self.assembler())
+                .map_err(|e| match e {
                     TransactionScriptError::ScriptCompilationError(asm_error) => {
                         TransactionCompilerError::CompileTxScriptFailed(asm_error)
                     },
-                },
-            )?;
+                })?;
         for target in target_account_proc.into_iter() {
             verify_program_account_compatibility(
                 &code_block,
@@ -136,7 +208,7 @@ impl TransactionCompiler {
     ///
     /// The account is assumed to have been previously loaded into this compiler.
     pub fn compile_transaction(
-        &mut self,
+        &self,
         account_id: AccountId,
         notes: &InputNotes,
         tx_script: Option<&ProgramAst>,
@@ -144,11 +216,47 @@ impl TransactionCompiler {
         // Fetch the account interface from the `account_procedures` map. Return an error if the
         // interface is not found.
         let target_account_interface = self
-            .account_procedures
-            .get(&account_id)
-            .cloned()
+            .get_account_interface(account_id)
             .ok_or(TransactionCompilerError::AccountInterfaceNotFound(account_id))?;
 
+        self.compile_transaction_with_interface(target_account_interface, notes, tx_script)
+    }
+
+    /// Compiles the program of the transaction described by the specified inputs and optional
+    /// transaction script.
+    ///
+    /// Unlike [TransactionCompiler::compile_transaction()], the account interface is compiled from
+    /// the account code contained in the inputs, thus the account does not need to be loaded into
+    /// this compiler beforehand. The interface is not retained by this compiler, so compiling the
+    /// transactions of many accounts (e.g., by a prover) does not grow the cache of account
+    /// interfaces. This is used to compile the programs of transactions described by a
+    /// [TransactionWitness](miden_objects::transaction::TransactionWitness).
+    pub fn compile_transaction_from_inputs(
+        &self,
+        tx_inputs: &TransactionInputs,
+        tx_script: Option<&TransactionScript>,
+    ) -> Result<Program, TransactionCompilerError> {
+        let account_code =
+            AccountCode::new(tx_inputs.account().code().module().clone(), &self.assembler())
+                .map_err(TransactionCompilerError::LoadAccountFailed)?;
+        self.compile_transaction_with_interface(
+            account_code.procedures().to_vec(),
+            tx_inputs.input_notes(),
+            tx_script.map(|script| script.code()),
+        )
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Compiles a transaction which executes the provided notes and an optional tx script against
+    /// an account with the specified interface.
+    fn compile_transaction_with_interface(
+        &self,
+        target_account_interface: Vec<Digest>,
+        notes: &InputNotes,
+        tx_script: Option<&ProgramAst>,
+    ) -> Result<Program, TransactionCompilerError> {
         // Transaction must contain at least one input note or a transaction script
         if notes.is_empty() && tx_script.is_none() {
             return Err(TransactionCompilerError::NoTransactionDriver);
@@ -157,13 +265,18 @@ impl TransactionCompiler {
         // Create the [AssemblyContext] for compilation of notes scripts and the transaction script
         let mut assembly_context = AssemblyContext::for_program(None);
 
+        // The assembler is locked for the whole compilation, so that the code block table is
+        // built from the same procedure cache the programs were compiled with
+        let assembler = self.assembler();
+
         // Compile note scripts
         let note_script_programs =
-            self.compile_notes(&target_account_interface, notes, &mut assembly_context)?;
+            compile_notes(&assembler, &target_account_interface, notes, &mut assembly_context)?;
 
         // Compile the transaction script
         let tx_script_program = match tx_script {
-            Some(tx_script) => Some(self.compile_tx_script_program(
+            Some(tx_script) => Some(compile_tx_script_program(
+                &assembler,
                 tx_script,
                 &mut assembly_context,
                 target_account_interface,
@@ -172,8 +285,7 @@ impl TransactionCompiler {
         };
 
         // Create [CodeBlockTable] from [AssemblyContext]
-        let mut cb_table = self
-            .assembler
+        let mut cb_table = assembler
             .build_cb_table(assembly_context)
             .map_err(TransactionCompilerError::BuildCodeBlockTableFailed)?;
 
@@ -189,8 +301,8 @@ impl TransactionCompiler {
 
         // Create transaction program with kernel
         let program = Program::with_kernel(
-            self.kernel_main.clone(),
-            self.assembler.kernel().clone(),
+            self.kernel.main_code_block().clone(),
+            assembler.kernel().clone(),
             cb_table,
         );
 
@@ -198,56 +310,18 @@ impl TransactionCompiler {
         Ok(program)
     }
 
-    // HELPER METHODS
-    // --------------------------------------------------------------------------------------------
-
-    /// Compiles the provided notes into [CodeBlock]s (programs) and verifies that each note is
-    /// compatible with the target account interfaces. Returns a vector of the compiled note
-    /// programs.
-    fn compile_notes(
-        &mut self,
-        target_account_interface: &[Digest],
-        notes: &InputNotes,
-        assembly_context: &mut AssemblyContext,
-    ) -> Result<Vec<CodeBlock>, TransactionCompilerError> {
-        let mut note_programs = Vec::new();
-
-        // Create and verify note programs. Note programs are verified against the target account.
-        for recorded_note in notes.iter() {
-            let note_program = self
-                .assembler
-                .compile_in_context(recorded_note.note().script().code(), assembly_context)
-                .map_err(TransactionCompilerError::CompileNoteScriptFailed)?;
-            verify_program_account_compatibility(
-                &note_program,
-                target_account_interface,
-                ScriptType::NoteScript,
-            )?;
-            note_programs.push(note_program);
-        }
-
-        Ok(note_programs)
+    /// Returns the assembler of this compiler.
+    fn assembler(&self) -> MutexGuard<'_, Assembler> {
+        self.assembler.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
-    /// Returns a [CodeBlock] of the compiled transaction script program.
-    ///
-    /// The transaction script compatibility is verified against the target account interface.
-    fn compile_tx_script_program(
-        &mut self,
-        tx_script: &ProgramAst,
-        assembly_context: &mut AssemblyContext,
-        target_account_interface: Vec<Digest>,
-    ) -> Result<CodeBlock, TransactionCompilerError> {
-        let tx_script_code_block = self
-            .assembler
-            .compile_in_context(tx_script, assembly_context)
-            .map_err(TransactionCompilerError::CompileTxScriptFailed)?;
-        verify_program_account_compatibility(
-            &tx_script_code_block,
-            &target_account_interface,
-            ScriptType::TransactionScript,
-        )?;
-        Ok(tx_script_code_block)
+    /// Returns the interface of the specified account if it has been loaded into this compiler.
+    fn get_account_interface(&self, account_id: AccountId) -> Option<Vec<Digest>> {
+        self.account_procedures
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&account_id)
+            .cloned()
     }
 
     /// Returns the account interface associated with the provided [ScriptTarget].
@@ -261,15 +335,25 @@ impl TransactionCompiler {
     ) -> Result<Vec<Digest>, TransactionCompilerError> {
         match target {
             ScriptTarget::AccountId(id) => self
-                .account_procedures
-                .get(&id)
-                .cloned()
+                .get_account_interface(id)
                 .ok_or(TransactionCompilerError::AccountInterfaceNotFound(id)),
             ScriptTarget::Procedures(procs) => Ok(procs),
         }
     }
 }
 
+impl Clone for TransactionCompiler {
+    fn clone(&self) -> Self {
+        TransactionCompiler {
+            assembler: self.assembler.clone(),
+            account_procedures: RwLock::new(
+                self.account_procedures.read().unwrap_or_else(PoisonError::into_inner).clone(),
+            ),
+            kernel: self.kernel.clone(),
+        }
+    }
+}
+
 impl Default for TransactionCompiler {
     fn default() -> Self {
         Self::new()
@@ -279,6 +363,53 @@ impl Default for TransactionCompiler {
 // TRANSACTION COMPILER HELPERS
 // ------------------------------------------------------------------------------------------------
 
+/// Compiles the provided notes into [CodeBlock]s (programs) and verifies that each note is
+/// compatible with the target account interfaces. Returns a vector of the compiled note
+/// programs.
+fn compile_notes(
+    assembler: &Assembler,
+    target_account_interface: &[Digest],
+    notes: &InputNotes,
+    assembly_context: &mut AssemblyContext,
+) -> Result<Vec<CodeBlock>, TransactionCompilerError> {
+    let mut note_programs = Vec::new();
+
+    // Create and verify note programs. Note programs are verified against the target account.
+    for recorded_note in notes.iter() {
+        let note_program = assembler
+            .compile_in_context(recorded_note.note().script().code(), assembly_context)
+            .map_err(TransactionCompilerError::CompileNoteScriptFailed)?;
+        verify_program_account_compatibility(
+            &note_program,
+            target_account_interface,
+            ScriptType::NoteScript,
+        )?;
+        note_programs.push(note_program);
+    }
+
+    Ok(note_programs)
+}
+
+/// Returns a [CodeBlock] of the compiled transaction script program.
+///
+/// The transaction script compatibility is verified against the target account interface.
+fn compile_tx_script_program(
+    assembler: &Assembler,
+    tx_script: &ProgramAst,
+    assembly_context: &mut AssemblyContext,
+    target_account_interface: Vec<Digest>,
+) -> Result<CodeBlock, TransactionCompilerError> {
+    let tx_script_code_block = assembler
+        .compile_in_context(tx_script, assembly_context)
+        .map_err(TransactionCompilerError::CompileTxScriptFailed)?;
+    verify_program_account_compatibility(
+        &tx_script_code_block,
+        &target_account_interface,
+        ScriptType::TransactionScript,
+    )?;
+    Ok(tx_script_code_block)
+}
+
 /// Verifies that the provided program is compatible with the target account interface.
 ///
 /// This is achieved by checking that at least one execution branch in the program is compatible
@@ -1,9 +1,11 @@
+use std::sync::Arc;
+
 use miden_objects::{
     accounts::ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
     assets::{Asset, FungibleAsset},
-    notes::{Note, NoteInclusionProof},
+    notes::{Note, NoteInclusionProof, NoteTag},
     transaction::{InputNote, InputNotes},
-    Felt, FieldElement, Word,
+    Felt, Word,
 };
 
 use super::{AccountId, ModuleAst, ProgramAst, ScriptTarget, TransactionCompiler};
@@ -46,7 +48,7 @@ end
 
 #[test]
 fn test_load_account() {
-    let mut tx_compiler = TransactionCompiler::new();
+    let tx_compiler = TransactionCompiler::new();
     let account_id =
         AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
     let account_code_ast = ModuleAst::parse(ACCOUNT_CODE_MASM).unwrap();
@@ -102,7 +104,7 @@ fn test_compile_valid_note_script() {
         ),
     ];
 
-    let mut tx_compiler = TransactionCompiler::new();
+    let tx_compiler = TransactionCompiler::new();
     let account_id =
         AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
     let account_code_ast = ModuleAst::parse(ACCOUNT_CODE_MASM).unwrap();
@@ -127,10 +129,7 @@ fn test_compile_valid_note_script() {
     }
 }
 
-fn mock_consumed_notes(
-    tx_compiler: &mut TransactionCompiler,
-    target_account: AccountId,
-) -> Vec<Note> {
+fn mock_consumed_notes(tx_compiler: &TransactionCompiler, target_account: AccountId) -> Vec<Note> {
     pub const ACCOUNT_ID_SENDER: u64 = 0b0110111011u64 << 54;
 
     pub const ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN: u64 = 0b1010011100 << 54;
@@ -160,7 +159,7 @@ fn mock_consumed_notes(
         &[fungible_asset_1, fungible_asset_2, fungible_asset_3],
         SERIAL_NUM_1,
         sender,
-        Felt::ZERO,
+        NoteTag::default(),
     )
     .unwrap();
 
@@ -171,7 +170,7 @@ fn mock_consumed_notes(
         &[fungible_asset_1, fungible_asset_2, fungible_asset_3],
         SERIAL_NUM_2,
         sender,
-        Felt::ZERO,
+        NoteTag::default(),
     )
     .unwrap();
 
@@ -180,13 +179,13 @@ fn mock_consumed_notes(
 
 #[test]
 fn test_transaction_compilation_succeeds() {
-    let mut tx_compiler = TransactionCompiler::new();
+    let tx_compiler = TransactionCompiler::new();
     let account_id =
         AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
     let account_code_ast = ModuleAst::parse(ACCOUNT_CODE_MASM).unwrap();
     let _account_code = tx_compiler.load_account(account_id, account_code_ast).unwrap();
 
-    let notes = mock_consumed_notes(&mut tx_compiler, account_id);
+    let notes = mock_consumed_notes(&tx_compiler, account_id);
     let mock_inclusion_proof = NoteInclusionProof::new(
         Default::default(),
         Default::default(),
@@ -209,6 +208,32 @@ fn test_transaction_compilation_succeeds() {
     assert!(res.is_ok());
 }
 
+#[test]
+fn test_compilers_share_assembler() {
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+    let tx_compiler_1 = TransactionCompiler::new();
+    let tx_compiler_2 = TransactionCompiler::new();
+    assert!(Arc::ptr_eq(&tx_compiler_1.assembler, &tx_compiler_2.assembler));
+
+    // account interfaces are not shared between compilers
+    let account_code_ast = ModuleAst::parse(ACCOUNT_CODE_MASM).unwrap();
+    tx_compiler_1.load_account(account_id, account_code_ast).unwrap();
+    assert!(tx_compiler_1.get_account_interface(account_id).is_some());
+    assert!(tx_compiler_2.get_account_interface(account_id).is_none());
+
+    // cloned compilers share the assembler, and copy the account interfaces
+    let tx_compiler_3 = tx_compiler_1.clone();
+    assert!(Arc::ptr_eq(&tx_compiler_1.assembler, &tx_compiler_3.assembler));
+    assert!(tx_compiler_3.has_account(account_id));
+
+    // unloading an account from one compiler does not affect the others
+    assert!(tx_compiler_3.unload_account(account_id).is_some());
+    assert!(!tx_compiler_3.has_account(account_id));
+    assert!(tx_compiler_3.unload_account(account_id).is_none());
+    assert!(tx_compiler_1.has_account(account_id));
+}
+
 // HELPERS
 // ================================================================================================
 
@@ -0,0 +1,278 @@
+//! Configuration of the transaction executor, prover, verifier, and validator.
+//!
+//! A [MidenTxConfig] can be loaded from a TOML file, e.g.:
+//!
+//! ```toml
+//! [executor]
+//! max_cycles = 1048576
+//! debug = false
+//!
+//! [prover]
+//! security_level = "128-bit"
+//!
+//! [verifier]
+//! min_security_level = 96
+//!
+//! [validation]
+//! max_input_notes = 512
+//! ```
+//!
+//! All sections and fields are optional; missing values fall back to the defaults used by the
+//! corresponding components.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use vm_processor::ExecutionOptions;
+
+use crate::{
+    ConfigError, DataStore, ProvingOptions, TransactionExecutor, TransactionExecutorBuilder,
+    TransactionProver, TransactionValidator, TransactionVerifier, ValidationLimits,
+};
+
+// MIDEN TX CONFIG
+// ================================================================================================
+
+/// Configuration shared by the components of the transaction stack.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MidenTxConfig {
+    /// Configuration of the [TransactionExecutor].
+    pub executor: ExecutorConfig,
+    /// Configuration of the [TransactionProver].
+    pub prover: ProverConfig,
+    /// Configuration of the [TransactionVerifier].
+    pub verifier: VerifierConfig,
+    /// Configuration of the [TransactionValidator].
+    pub validation: ValidationConfig,
+}
+
+impl MidenTxConfig {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Parses a [MidenTxConfig] from the provided TOML string.
+    ///
+    /// # Errors
+    /// Returns an error if the string is not a valid TOML configuration.
+    pub fn from_toml(toml: &str) -> Result<Self, ConfigError> {
+        toml::from_str(toml).map_err(ConfigError::ParseFailed)
+    }
+
+    /// Loads a [MidenTxConfig] from the TOML file at the specified path.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or is not a valid TOML configuration.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let toml = fs::read_to_string(path).map_err(ConfigError::IoError)?;
+        Self::from_toml(&toml)
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the [ExecutionOptions] described by this configuration.
+    ///
+    /// # Errors
+    /// Returns an error if the configured cycle limits are invalid.
+    pub fn execution_options(&self) -> Result<ExecutionOptions, ConfigError> {
+        ExecutionOptions::new(self.executor.max_cycles, self.executor.expected_cycles)
+            .map_err(ConfigError::InvalidExecutionOptions)
+    }
+
+    /// Returns the [ProvingOptions] described by this configuration.
+    pub fn proving_options(&self) -> ProvingOptions {
+        match self.prover.security_level {
+            SecurityLevel::Bits96 => ProvingOptions::with_96_bit_security(self.prover.recursive),
+            SecurityLevel::Bits128 => ProvingOptions::with_128_bit_security(self.prover.recursive),
+        }
+    }
+
+    /// Returns the [ValidationLimits] described by this configuration.
+    pub fn validation_limits(&self) -> ValidationLimits {
+        ValidationLimits {
+            max_input_notes: self.validation.max_input_notes,
+            max_output_notes: self.validation.max_output_notes,
+            max_tx_size: self.validation.max_tx_size,
+        }
+    }
+
+    /// Returns a [TransactionExecutorBuilder] configured according to this configuration.
+    ///
+    /// The [DataStore] (and, optionally, the authenticator and compiler) must still be provided
+    /// to the returned builder.
+    ///
+    /// # Errors
+    /// Returns an error if the configured cycle limits are invalid.
+    pub fn executor_builder<D: DataStore>(
+        &self,
+    ) -> Result<TransactionExecutorBuilder<D>, ConfigError> {
+        let mut builder =
+            TransactionExecutor::builder().with_exec_options(self.execution_options()?);
+        if self.executor.debug {
+            builder = builder.in_debug_mode();
+        }
+        if let Some(dir) = &self.executor.replay_dir {
+            builder = builder.with_replay_recording(dir);
+        }
+        Ok(builder)
+    }
+
+    /// Returns a [TransactionProver] configured according to this configuration.
+    pub fn prover(&self) -> TransactionProver {
+        TransactionProver::new(self.proving_options())
+    }
+
+    /// Returns a [TransactionVerifier] configured according to this configuration.
+    pub fn verifier(&self) -> TransactionVerifier {
+        TransactionVerifier::new(self.verifier.min_security_level)
+    }
+
+    /// Returns a [TransactionValidator] configured according to this configuration.
+    pub fn validator(&self) -> TransactionValidator {
+        TransactionValidator::new(self.validation_limits())
+    }
+}
+
+// EXECUTOR CONFIG
+// ================================================================================================
+
+/// Configuration of the [TransactionExecutor].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExecutorConfig {
+    /// Maximum number of VM cycles a transaction may consume.
+    ///
+    /// When omitted from the configuration file, the limit of [ExecutionOptions::default()] is
+    /// used. When set to `None`, the largest cycle limit supported by the VM is used; transactions
+    /// are never executed without a cycle limit.
+    pub max_cycles: Option<u32>,
+    /// Number of VM cycles a transaction is expected to consume.
+    pub expected_cycles: u32,
+    /// Whether the executor runs in debug mode.
+    pub debug: bool,
+    /// Directory into which replays of executed transactions are recorded; replays are not
+    /// recorded if not set.
+    pub replay_dir: Option<PathBuf>,
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        let exec_options = ExecutionOptions::default();
+        Self {
+            max_cycles: Some(exec_options.max_cycles()),
+            expected_cycles: exec_options.expected_cycles(),
+            debug: false,
+            replay_dir: None,
+        }
+    }
+}
+
+// PROVER CONFIG
+// ================================================================================================
+
+/// Security level of transaction proofs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecurityLevel {
+    #[serde(rename = "96-bit")]
+    #[default]
+    Bits96,
+    #[serde(rename = "128-bit")]
+    Bits128,
+}
+
+/// Configuration of the [TransactionProver].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProverConfig {
+    /// Security level of the generated proofs.
+    pub security_level: SecurityLevel,
+    /// Whether the generated proofs are optimized for recursive verification.
+    pub recursive: bool,
+}
+
+// VERIFIER CONFIG
+// ================================================================================================
+
+/// Configuration of the [TransactionVerifier].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VerifierConfig {
+    /// Minimum security level (in bits) of accepted transaction proofs.
+    pub min_security_level: u32,
+}
+
+impl Default for VerifierConfig {
+    fn default() -> Self {
+        Self { min_security_level: 96 }
+    }
+}
+
+// VALIDATION CONFIG
+// ================================================================================================
+
+/// Configuration of the [TransactionValidator].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationConfig {
+    /// Maximum number of notes consumed by a transaction.
+    pub max_input_notes: usize,
+    /// Maximum number of notes created by a transaction.
+    pub max_output_notes: usize,
+    /// Maximum size of a serialized transaction (including the proof) in bytes.
+    pub max_tx_size: usize,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        let limits = ValidationLimits::default();
+        Self {
+            max_input_notes: limits.max_input_notes,
+            max_output_notes: limits.max_output_notes,
+            max_tx_size: limits.max_tx_size,
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{MidenTxConfig, SecurityLevel, ValidationConfig};
+
+    #[test]
+    fn config_is_loaded_from_toml() {
+        let config = MidenTxConfig::from_toml(
+            r#"
+            [executor]
+            max_cycles = 65536
+            debug = true
+
+            [prover]
+            security_level = "128-bit"
+
+            [validation]
+            max_input_notes = 16
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.executor.max_cycles, Some(65536));
+        assert!(config.executor.debug);
+        assert_eq!(config.prover.security_level, SecurityLevel::Bits128);
+        assert_eq!(config.verifier.min_security_level, 96);
+        assert_eq!(config.validation.max_input_notes, 16);
+        assert_eq!(
+            config.validation.max_output_notes,
+            ValidationConfig::default().max_output_notes
+        );
+        assert_eq!(config.execution_options().unwrap().max_cycles(), 65536);
+        assert_eq!(config.validation_limits().max_input_notes, 16);
+
+        assert!(MidenTxConfig::from_toml("[executor]\nunknown = 1").is_err());
+    }
+}
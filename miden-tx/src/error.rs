@@ -1,12 +1,92 @@
 use core::fmt;
 
+use miden_lib::transaction::EventNamespace;
 use miden_objects::{
-    assembly::AssemblyError, crypto::merkle::NodeIndex, NoteError, TransactionInputError,
+    assembly::AssemblyError,
+    crypto::merkle::NodeIndex,
+    notes::{NoteId, Nullifier},
+    transaction::{TransactionFee, TransactionId},
+    utils::serde::DeserializationError,
+    AccountDeltaError, AssetError, AssetVaultError, NoteError, TransactionInputError,
     TransactionOutputError,
 };
 use miden_verifier::VerificationError;
 
-use super::{AccountError, AccountId, Digest, ExecutionError};
+use super::{host::TransactionPhase, AccountError, AccountId, Digest, ExecutionError};
+
+// AUTHENTICATION ERROR
+// ================================================================================================
+
+#[derive(Debug)]
+pub enum AuthenticationError {
+    InternalError(String),
+    RejectedSignature(String),
+    UnknownKey(Digest),
+    UnsupportedSignatureKind,
+}
+
+impl fmt::Display for AuthenticationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AuthenticationError {}
+
+// CONFIG ERROR
+// ================================================================================================
+
+/// Errors which can occur while loading a [MidenTxConfig](crate::config::MidenTxConfig).
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub enum ConfigError {
+    InvalidExecutionOptions(vm_processor::ExecutionOptionsError),
+    IoError(std::io::Error),
+    ParseFailed(toml::de::Error),
+}
+
+#[cfg(feature = "config")]
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ConfigError::*;
+        match self {
+            InvalidExecutionOptions(err) => write!(f, "invalid execution options: {err}"),
+            IoError(err) => write!(f, "failed to read configuration file: {err}"),
+            ParseFailed(err) => write!(f, "failed to parse configuration: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidExecutionOptions(_) => None,
+            Self::IoError(err) => Some(err),
+            Self::ParseFailed(err) => Some(err),
+        }
+    }
+}
+
+// EVENT REGISTRATION ERROR
+// ================================================================================================
+
+/// Errors which can occur while registering [EventHandler](crate::host::EventHandler)s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventRegistrationError {
+    NamespaceCollision(EventNamespace),
+    ReservedNamespace(EventNamespace),
+}
+
+impl fmt::Display for EventRegistrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EventRegistrationError {}
 
 // TRANSACTION COMPILER ERROR
 // ================================================================================================
@@ -17,6 +97,7 @@ pub enum TransactionCompilerError {
     BuildCodeBlockTableFailed(AssemblyError),
     CompileNoteScriptFailed(AssemblyError),
     CompileTxScriptFailed(AssemblyError),
+    KernelMismatch(Digest),
     LoadAccountFailed(AccountError),
     NoteIncompatibleWithAccountInterface(Digest),
     NoteScriptError(NoteError),
@@ -36,66 +117,634 @@ impl std::error::Error for TransactionCompilerError {}
 // TRANSACTION EXECUTOR ERROR
 // ================================================================================================
 
+/// Errors which can occur during transaction execution.
+///
+/// Each variant identifies the stage of the execution at which the error occurred and carries the
+/// context required to identify the failed transaction. Where applicable, the underlying error is
+/// available via [std::error::Error::source()].
 #[derive(Debug)]
 pub enum TransactionExecutorError {
     CompileNoteScriptFailed(TransactionCompilerError),
     CompileTransactionScriptFailed(TransactionCompilerError),
-    CompileTransactionFiled(TransactionCompilerError),
-    ExecuteTransactionProgramFailed(ExecutionError),
-    FetchAccountCodeFailed(DataStoreError),
-    FetchTransactionInputsFailed(DataStoreError),
+    CompileTransactionFailed {
+        account_id: AccountId,
+        source: TransactionCompilerError,
+    },
+    ExecuteTransactionProgramFailed {
+        account_id: AccountId,
+        block_ref: u32,
+        input_notes: Vec<NoteId>,
+        phase: TransactionPhase,
+        failed_note: Option<NoteId>,
+        source: ExecutionError,
+    },
+    ExtractAccountStorageDeltaFailed {
+        account_id: AccountId,
+        source: TransactionOutputError,
+    },
+    FetchAccountCodeFailed {
+        account_id: AccountId,
+        source: DataStoreError,
+    },
+    FetchForeignAccountInputsFailed {
+        account_id: AccountId,
+        foreign_account_id: AccountId,
+        source: DataStoreError,
+    },
+    FetchTransactionInputsFailed {
+        account_id: AccountId,
+        block_ref: u32,
+        source: DataStoreError,
+    },
+    ImmutableAccountCodeUpdated(AccountId),
     InconsistentAccountId {
         input_id: AccountId,
         output_id: AccountId,
     },
-    LoadAccountFailed(TransactionCompilerError),
-    InvalidTransactionOutput(TransactionOutputError),
+    InconsistentForeignAccountInputs {
+        account_id: AccountId,
+        foreign_account_id: AccountId,
+        block_ref: u32,
+    },
+    InvalidAccountDelta {
+        account_id: AccountId,
+        source: AccountDeltaError,
+    },
+    InvalidTransactionOutput {
+        account_id: AccountId,
+        source: TransactionOutputError,
+    },
+    LoadAccountFailed {
+        account_id: AccountId,
+        source: TransactionCompilerError,
+    },
+    NetworkNoteNotConsumable {
+        account_id: AccountId,
+        note_id: NoteId,
+    },
+    RecordReplayFailed {
+        account_id: AccountId,
+        source: Box<TransactionReplayError>,
+    },
+    UnexpectedTransactionEffects {
+        account_id: AccountId,
+        mismatches: Vec<TransactionEffectMismatch>,
+    },
 }
 
 impl fmt::Display for TransactionExecutorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TransactionExecutorError::*;
+        match self {
+            CompileNoteScriptFailed(err) => write!(f, "failed to compile note script: {err}"),
+            CompileTransactionScriptFailed(err) => {
+                write!(f, "failed to compile transaction script: {err}")
+            },
+            CompileTransactionFailed { account_id, source } => {
+                write!(f, "failed to compile transaction against account {account_id}: {source}")
+            },
+            ExecuteTransactionProgramFailed {
+                account_id,
+                block_ref,
+                input_notes,
+                phase,
+                failed_note,
+                source,
+            } => {
+                write!(
+                    f,
+                    "failed to execute transaction against account {account_id} at block \
+                    {block_ref} consuming {} note(s) {input_notes:?} during {phase}",
+                    input_notes.len()
+                )?;
+                if let Some(note_id) = failed_note {
+                    write!(f, " (note {note_id:?})")?;
+                }
+                write!(f, ": {source}")
+            },
+            ExtractAccountStorageDeltaFailed { account_id, source } => {
+                write!(f, "failed to extract storage delta of account {account_id}: {source}")
+            },
+            FetchAccountCodeFailed { account_id, source } => {
+                write!(f, "failed to fetch code of account {account_id}: {source}")
+            },
+            FetchForeignAccountInputsFailed { account_id, foreign_account_id, source } => {
+                write!(
+                    f,
+                    "failed to fetch foreign account {foreign_account_id} for transaction \
+                    against account {account_id}: {source}"
+                )
+            },
+            FetchTransactionInputsFailed { account_id, block_ref, source } => {
+                write!(
+                    f,
+                    "failed to fetch inputs for transaction against account {account_id} at \
+                    block {block_ref}: {source}"
+                )
+            },
+            ImmutableAccountCodeUpdated(account_id) => {
+                write!(f, "transaction updated the immutable code of account {account_id}")
+            },
+            InconsistentAccountId { input_id, output_id } => {
+                write!(
+                    f,
+                    "transaction output account {output_id} does not match input account {input_id}"
+                )
+            },
+            InconsistentForeignAccountInputs {
+                account_id,
+                foreign_account_id,
+                block_ref,
+            } => {
+                write!(
+                    f,
+                    "state of foreign account {foreign_account_id} read by transaction against \
+                    account {account_id} is not recorded in block {block_ref}"
+                )
+            },
+            InvalidAccountDelta { account_id, source } => {
+                write!(f, "invalid delta of transaction against account {account_id}: {source}")
+            },
+            InvalidTransactionOutput { account_id, source } => {
+                write!(f, "invalid output of transaction against account {account_id}: {source}")
+            },
+            LoadAccountFailed { account_id, source } => {
+                write!(f, "failed to load code of account {account_id}: {source}")
+            },
+            NetworkNoteNotConsumable { account_id, note_id } => {
+                write!(
+                    f,
+                    "network note {note_id:?} cannot be consumed against account {account_id}"
+                )
+            },
+            RecordReplayFailed { account_id, source } => {
+                write!(
+                    f,
+                    "failed to record replay of transaction against account {account_id}: {source}"
+                )
+            },
+            UnexpectedTransactionEffects { account_id, mismatches } => {
+                write!(
+                    f,
+                    "effects of transaction against account {account_id} differ from the \
+                    expected effects: {mismatches:?}"
+                )
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransactionExecutorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use TransactionExecutorError::*;
+        match self {
+            CompileNoteScriptFailed(err) | CompileTransactionScriptFailed(err) => Some(err),
+            CompileTransactionFailed { source, .. } | LoadAccountFailed { source, .. } => {
+                Some(source)
+            },
+            ExecuteTransactionProgramFailed { source, .. } => Some(source),
+            ExtractAccountStorageDeltaFailed { source, .. }
+            | InvalidTransactionOutput { source, .. } => Some(source),
+            InvalidAccountDelta { source, .. } => Some(source),
+            RecordReplayFailed { source, .. } => Some(source.as_ref()),
+            FetchAccountCodeFailed { source, .. }
+            | FetchForeignAccountInputsFailed { source, .. }
+            | FetchTransactionInputsFailed { source, .. } => Some(source),
+            ImmutableAccountCodeUpdated(_)
+            | InconsistentAccountId { .. }
+            | InconsistentForeignAccountInputs { .. }
+            | NetworkNoteNotConsumable { .. }
+            | UnexpectedTransactionEffects { .. } => None,
+        }
+    }
+}
+
+// TRANSACTION REQUEST ERROR
+// ================================================================================================
+
+#[derive(Debug)]
+pub enum TransactionRequestError {
+    AmountOverflow(AccountId),
+    InvalidAsset(AssetError),
+    InvalidOutputNote(NoteError),
+    NotAFungibleFaucet(AccountId),
+}
+
+impl fmt::Display for TransactionRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransactionRequestError {}
+
+// TRANSACTION EFFECT MISMATCH
+// ================================================================================================
+
+/// A difference between the effects a [TransactionRequest](crate::TransactionRequest) expected a
+/// transaction to have and the actual effects of the executed transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionEffectMismatch {
+    BalanceMismatch {
+        faucet_id: AccountId,
+        expected: u64,
+        actual: u64,
+    },
+    InvalidVaultDelta(AssetVaultError),
+    MissingOutputNote(NoteId),
+    UnexpectedOutputNote(NoteId),
+}
+
+impl fmt::Display for TransactionEffectMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransactionEffectMismatch {}
+
+// PROVEN TRANSACTION MISMATCH
+// ================================================================================================
+
+/// A difference between the public inputs and outputs of a [ProvenTransaction] and the inputs and
+/// outputs of the executed transaction it was proven from.
+///
+/// [ProvenTransaction]: miden_objects::transaction::ProvenTransaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProvenTransactionMismatch {
+    ExpirationBlockNum {
+        expected: Option<u32>,
+        actual: Option<u32>,
+    },
+    Fee {
+        expected: Option<TransactionFee>,
+        actual: Option<TransactionFee>,
+    },
+    FinalAccountHash {
+        expected: Digest,
+        actual: Digest,
+    },
+    OutputNotesCommitment {
+        expected: Digest,
+        actual: Digest,
+    },
+}
+
+impl fmt::Display for ProvenTransactionMismatch {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for TransactionExecutorError {}
+impl std::error::Error for ProvenTransactionMismatch {}
+
+// PROVER OPTIONS ERROR
+// ================================================================================================
+
+/// Errors which can occur while configuring [ProverOptions](crate::ProverOptions).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProverOptionsError {
+    InvalidBlowupFactor(usize),
+}
+
+impl fmt::Display for ProverOptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBlowupFactor(blowup_factor) => write!(
+                f,
+                "blowup factor {blowup_factor} is not a power of two between {} and {}",
+                crate::ProverOptions::MIN_BLOWUP_FACTOR,
+                crate::ProverOptions::MAX_BLOWUP_FACTOR
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProverOptionsError {}
 
 // TRANSACTION PROVER ERROR
 // ================================================================================================
 
+/// Errors which can occur while proving a transaction.
 #[derive(Debug)]
 pub enum TransactionProverError {
-    ProveTransactionProgramFailed(ExecutionError),
-    InvalidTransactionOutput(TransactionOutputError),
+    CompileTransactionFailed {
+        account_id: AccountId,
+        source: TransactionCompilerError,
+    },
+    InvalidTransactionOutput {
+        account_id: AccountId,
+        source: TransactionOutputError,
+    },
+    KernelMismatch(AccountId),
+    ProofVerificationFailed {
+        account_id: AccountId,
+        source: TransactionVerifierError,
+    },
+    ProveTransactionProgramFailed {
+        account_id: AccountId,
+        source: ExecutionError,
+    },
+    ProvenTransactionMismatch {
+        account_id: AccountId,
+        mismatches: Vec<ProvenTransactionMismatch>,
+    },
+    RemoteProvingFailed {
+        account_id: AccountId,
+        source: RemoteProverError,
+    },
 }
 
 impl fmt::Display for TransactionProverError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        use TransactionProverError::*;
+        match self {
+            CompileTransactionFailed { account_id, source } => {
+                write!(f, "failed to compile transaction against account {account_id}: {source}")
+            },
+            InvalidTransactionOutput { account_id, source } => {
+                write!(f, "invalid output of transaction against account {account_id}: {source}")
+            },
+            KernelMismatch(account_id) => write!(
+                f,
+                "transaction against account {account_id} was not executed with the kernel of \
+                the prover"
+            ),
+            ProofVerificationFailed { account_id, source } => {
+                write!(
+                    f,
+                    "proof of transaction against account {account_id} failed to verify: {source}"
+                )
+            },
+            ProveTransactionProgramFailed { account_id, source } => {
+                write!(f, "failed to prove transaction against account {account_id}: {source}")
+            },
+            ProvenTransactionMismatch { account_id, mismatches } => {
+                write!(
+                    f,
+                    "proven transaction against account {account_id} does not match the executed \
+                    transaction: {mismatches:?}"
+                )
+            },
+            RemoteProvingFailed { account_id, source } => {
+                write!(
+                    f,
+                    "failed to prove transaction against account {account_id} remotely: {source}"
+                )
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransactionProverError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CompileTransactionFailed { source, .. } => Some(source),
+            Self::InvalidTransactionOutput { source, .. } => Some(source),
+            Self::KernelMismatch(_) => None,
+            Self::ProofVerificationFailed { source, .. } => Some(source),
+            Self::ProveTransactionProgramFailed { source, .. } => Some(source),
+            Self::ProvenTransactionMismatch { .. } => None,
+            Self::RemoteProvingFailed { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Errors which can occur while proving a transaction via a remote proving service.
+#[derive(Debug)]
+pub enum RemoteProverError {
+    ConnectionFailed(String),
+    InvalidResponse(DeserializationError),
+    ProvenTransactionMismatch(TransactionId),
+    ServiceError(String),
+}
+
+impl fmt::Display for RemoteProverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use RemoteProverError::*;
+        match self {
+            ConnectionFailed(err) => write!(f, "failed to reach proving service: {err}"),
+            InvalidResponse(err) => write!(f, "invalid response of proving service: {err}"),
+            ProvenTransactionMismatch(tx_id) => write!(
+                f,
+                "proven transaction {tx_id:?} returned by proving service does not match the \
+                transaction witness"
+            ),
+            ServiceError(err) => write!(f, "proving service failed to prove transaction: {err}"),
+        }
     }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for TransactionProverError {}
+impl std::error::Error for RemoteProverError {}
+
+// TRANSACTION REPLAY ERROR
+// ================================================================================================
+
+/// Errors which can occur while recording or replaying a transaction.
+#[derive(Debug)]
+pub enum TransactionReplayError {
+    DeserializationFailed(DeserializationError),
+    ExecutionFailed(TransactionExecutorError),
+    IoError(std::io::Error),
+    KernelMismatch { expected: Digest, actual: Digest },
+}
+
+impl fmt::Display for TransactionReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TransactionReplayError::*;
+        match self {
+            DeserializationFailed(err) => write!(f, "failed to deserialize replay: {err}"),
+            ExecutionFailed(err) => write!(f, "failed to replay transaction: {err}"),
+            IoError(err) => write!(f, "failed to access replay file: {err}"),
+            KernelMismatch { expected, actual } => write!(
+                f,
+                "transaction was recorded with kernel {actual:?} but the current kernel is \
+                {expected:?}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransactionReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use TransactionReplayError::*;
+        match self {
+            ExecutionFailed(err) => Some(err),
+            IoError(err) => Some(err),
+            DeserializationFailed(_) | KernelMismatch { .. } => None,
+        }
+    }
+}
 
 // TRANSACTION VERIFIER ERROR
 // ================================================================================================
 
+/// Errors which can occur while verifying a transaction.
 #[derive(Debug)]
 pub enum TransactionVerifierError {
-    TransactionVerificationFailed(VerificationError),
-    InsufficientProofSecurityLevel(u32, u32),
+    InsufficientProofSecurityLevel {
+        tx_id: TransactionId,
+        actual: u32,
+        required: u32,
+    },
+    TransactionVerificationFailed {
+        tx_id: TransactionId,
+        source: VerificationError,
+    },
+    UnknownKernel {
+        tx_id: TransactionId,
+        kernel_hash: Digest,
+    },
 }
 
 impl fmt::Display for TransactionVerifierError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        use TransactionVerifierError::*;
+        match self {
+            InsufficientProofSecurityLevel { tx_id, actual, required } => write!(
+                f,
+                "proof of transaction {tx_id:?} has security level {actual} but at least \
+                {required} is required"
+            ),
+            TransactionVerificationFailed { tx_id, source } => {
+                write!(f, "failed to verify transaction {tx_id:?}: {source}")
+            },
+            UnknownKernel { tx_id, kernel_hash } => write!(
+                f,
+                "transaction {tx_id:?} was executed with transaction kernel {kernel_hash:?} \
+                which is not accepted by the verifier"
+            ),
+        }
     }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for TransactionVerifierError {}
+impl std::error::Error for TransactionVerifierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InsufficientProofSecurityLevel { .. } => None,
+            Self::TransactionVerificationFailed { source, .. } => Some(source),
+            Self::UnknownKernel { .. } => None,
+        }
+    }
+}
+
+// TRANSACTION VALIDATION ERROR
+// ================================================================================================
+
+/// Reasons for rejecting a transaction by a [TransactionValidator](crate::TransactionValidator).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionValidationError {
+    AccountDetailsMismatch {
+        expected: AccountId,
+        actual: AccountId,
+    },
+    EmptyTransaction(TransactionId),
+    FeeNoteMismatch {
+        tx_id: TransactionId,
+        expected: NoteId,
+        actual: NoteId,
+    },
+    FeeNoteNotFound {
+        tx_id: TransactionId,
+        note_id: NoteId,
+    },
+    InsufficientFee {
+        tx_id: TransactionId,
+        fee: Option<TransactionFee>,
+    },
+    MalformedTransaction(DeserializationError),
+    NullifierAlreadyPending(Nullifier),
+    OutputNoteExpired {
+        note_id: NoteId,
+        expiration_block_num: u32,
+    },
+    PrivateAccountDetails(AccountId),
+    TooManyInputNotes {
+        actual: usize,
+        max: usize,
+    },
+    TooManyOutputNotes {
+        actual: usize,
+        max: usize,
+    },
+    TransactionExpired {
+        tx_id: TransactionId,
+        expiration_block_num: u32,
+    },
+    TransactionTooLarge {
+        actual: usize,
+        max: usize,
+    },
+}
+
+impl fmt::Display for TransactionValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TransactionValidationError::*;
+        match self {
+            AccountDetailsMismatch { expected, actual } => write!(
+                f,
+                "transaction contains details of account {actual} but was executed against \
+                account {expected}"
+            ),
+            EmptyTransaction(tx_id) => write!(f, "transaction {tx_id:?} has no effect"),
+            FeeNoteMismatch { tx_id, expected, actual } => write!(
+                f,
+                "transaction {tx_id:?} paid the fee into note {actual:?} instead of the expected \
+                fee note {expected:?}"
+            ),
+            FeeNoteNotFound { tx_id, note_id } => write!(
+                f,
+                "fee note {note_id:?} of transaction {tx_id:?} is not among its output notes"
+            ),
+            InsufficientFee { tx_id, fee: Some(fee) } => write!(
+                f,
+                "transaction {tx_id:?} paid {} of asset {} which does not cover the required fee",
+                fee.asset().amount(),
+                fee.asset().faucet_id()
+            ),
+            InsufficientFee { tx_id, fee: None } => {
+                write!(f, "transaction {tx_id:?} did not pay a fee")
+            },
+            MalformedTransaction(err) => write!(f, "transaction is malformed: {err}"),
+            NullifierAlreadyPending(nullifier) => write!(
+                f,
+                "note with nullifier {:?} is already consumed by a pending transaction",
+                nullifier.inner()
+            ),
+            OutputNoteExpired { note_id, expiration_block_num } => {
+                write!(f, "output note {note_id:?} expired at block {expiration_block_num}")
+            },
+            PrivateAccountDetails(account_id) => write!(
+                f,
+                "transaction contains details of account {account_id} which is not public"
+            ),
+            TooManyInputNotes { actual, max } => {
+                write!(f, "transaction consumes {actual} notes but at most {max} are allowed")
+            },
+            TooManyOutputNotes { actual, max } => {
+                write!(f, "transaction creates {actual} notes but at most {max} are allowed")
+            },
+            TransactionExpired { tx_id, expiration_block_num } => {
+                write!(f, "transaction {tx_id:?} expired at block {expiration_block_num}")
+            },
+            TransactionTooLarge { actual, max } => {
+                write!(f, "transaction has {actual} bytes but at most {max} bytes are allowed")
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransactionValidationError {}
 
 // DATA STORE ERROR
 // ================================================================================================
@@ -104,9 +753,25 @@ impl std::error::Error for TransactionVerifierError {}
 pub enum DataStoreError {
     AccountNotFound(AccountId),
     BlockNotFound(u32),
+    ChainRootMismatch {
+        block_num: u32,
+        expected: Digest,
+        actual: Digest,
+    },
+    InputNoteNotFound(NoteId),
     InvalidTransactionInput(TransactionInputError),
     InternalError(String),
     NoteNotFound(u32, NodeIndex),
+    NoteNotInChain {
+        note_id: NoteId,
+        block_num: u32,
+        block_ref: u32,
+    },
+    NoteProofMismatch(NoteId),
+    UnexpectedBlockNum {
+        expected: u32,
+        actual: u32,
+    },
 }
 
 impl fmt::Display for DataStoreError {
@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use miden_objects::{
+    notes::NoteScript,
+    utils::{collections::BTreeMap, sync::Arc},
+    Digest,
+};
+use vm_processor::ExecutionOptions;
+
+use super::{TransactionCompiler, TransactionExecutor};
+#[cfg(doc)]
+use crate::DataStore;
+use crate::{
+    host::{EventHandlers, TransactionTraceHandler},
+    TransactionAuthenticator,
+};
+
+// TRANSACTION EXECUTOR BUILDER
+// ================================================================================================
+
+/// A builder for [TransactionExecutor] instances.
+///
+/// The builder is obtained via [TransactionExecutor::builder()]. Only the data store (either a
+/// [DataStore] or, with the `async` feature, an `AsyncDataStore`) must be
+/// provided; all other parameters fall back to their defaults:
+/// - A new [TransactionCompiler] is built when no compiler is provided.
+/// - [ExecutionOptions::default()] are used when no execution options are provided.
+/// - No [TransactionAuthenticator] is used when none is provided; in this case, signatures must be
+///   provided via the advice inputs of the transaction.
+/// - Events outside of the transaction kernel namespace are ignored unless [EventHandlers] are
+///   provided.
+/// - Replays of executed transactions are not recorded unless a replay directory is provided.
+/// - The progress of transaction executions is not streamed unless a [TransactionTraceHandler] is
+///   provided.
+/// - Events outside of the transaction kernel namespace are not decoded for the trace handler
+///   unless the [EventHandlers] provided for their namespace decode them.
+/// - The full details of output notes are recovered only for notes whose scripts are the scripts
+///   of the input notes of the transaction, unless additional note scripts are provided.
+pub struct TransactionExecutorBuilder<D> {
+    data_store: Option<D>,
+    compiler: Option<TransactionCompiler>,
+    exec_options: ExecutionOptions,
+    authenticator: Option<Arc<dyn TransactionAuthenticator>>,
+    event_handlers: EventHandlers,
+    replay_dir: Option<PathBuf>,
+    trace_handler: Option<Arc<dyn TransactionTraceHandler>>,
+    note_scripts: BTreeMap<Digest, NoteScript>,
+    in_debug_mode: bool,
+}
+
+impl<D> TransactionExecutorBuilder<D> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [TransactionExecutorBuilder] with all parameters set to their defaults.
+    pub(super) fn new() -> Self {
+        Self {
+            data_store: None,
+            compiler: None,
+            exec_options: ExecutionOptions::default(),
+            authenticator: None,
+            event_handlers: EventHandlers::default(),
+            replay_dir: None,
+            trace_handler: None,
+            note_scripts: BTreeMap::new(),
+            in_debug_mode: false,
+        }
+    }
+
+    // BUILDER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the [DataStore] from which the executor fetches the data required for transaction
+    /// execution.
+    pub fn with_data_store(mut self, data_store: D) -> Self {
+        self.data_store = Some(data_store);
+        self
+    }
+
+    /// Sets a pre-built [TransactionCompiler] to be used by the executor.
+    ///
+    /// Account interfaces already loaded into the provided compiler are retained.
+    pub fn with_compiler(mut self, compiler: TransactionCompiler) -> Self {
+        self.compiler = Some(compiler);
+        self
+    }
+
+    /// Sets the [ExecutionOptions] used when executing transaction programs.
+    pub fn with_exec_options(mut self, exec_options: ExecutionOptions) -> Self {
+        self.exec_options = exec_options;
+        self
+    }
+
+    /// Sets the [TransactionAuthenticator] used to answer signature requests made during
+    /// transaction execution.
+    pub fn with_authenticator(mut self, authenticator: Arc<dyn TransactionAuthenticator>) -> Self {
+        self.authenticator = Some(authenticator);
+        self
+    }
+
+    /// Sets the [EventHandlers] used to handle events emitted by account components and note
+    /// scripts outside of the transaction kernel namespace.
+    pub fn with_event_handlers(mut self, event_handlers: EventHandlers) -> Self {
+        self.event_handlers = event_handlers;
+        self
+    }
+
+    /// Makes the executor record a [TransactionReplay](crate::replay::TransactionReplay) of every
+    /// executed transaction into the specified directory.
+    ///
+    /// Replays are recorded for failed executions as well, which allows reproducing execution
+    /// failures via [replay()](crate::replay::replay) without access to the original data store
+    /// and authenticator.
+    pub fn with_replay_recording(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.replay_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the [TransactionTraceHandler] to which the executor streams the progress of every
+    /// executed transaction (i.e., the phases of the transaction kernel, the input note being
+    /// executed, the kernel procedures entered, and the events emitted by the kernel).
+    ///
+    /// Kernel procedures are reported via tracing decorators, thus setting a trace handler enables
+    /// tracing in the same way as [TransactionExecutorBuilder::in_debug_mode()].
+    pub fn with_trace_handler(mut self, trace_handler: Arc<dyn TransactionTraceHandler>) -> Self {
+        self.trace_handler = Some(trace_handler);
+        self
+    }
+
+    /// Adds the provided note scripts to the scripts the executor uses to recover the full details
+    /// of the notes created by executed transactions.
+    ///
+    /// The scripts of the input notes of a transaction are always used, thus only the scripts of
+    /// notes which are not consumed by the transaction need to be provided (e.g., the P2ID script
+    /// for transactions sending assets).
+    pub fn with_note_scripts<I: IntoIterator<Item = NoteScript>>(mut self, scripts: I) -> Self {
+        self.note_scripts
+            .extend(scripts.into_iter().map(|script| (script.hash(), script)));
+        self
+    }
+
+    /// Puts the executor into debug mode.
+    ///
+    /// In debug mode, tracing decorators in the executed programs are enabled regardless of the
+    /// execution options provided via [TransactionExecutorBuilder::with_exec_options()].
+    pub fn in_debug_mode(mut self) -> Self {
+        self.in_debug_mode = true;
+        self
+    }
+
+    /// Returns a new [TransactionExecutor] configured with the parameters of this builder.
+    ///
+    /// # Panics
+    /// Panics if the [DataStore] was not provided.
+    pub fn build(self) -> TransactionExecutor<D> {
+        let data_store = self.data_store.expect("data store must be provided");
+        let compiler = self.compiler.unwrap_or_default();
+        let exec_options = if self.in_debug_mode || self.trace_handler.is_some() {
+            self.exec_options.with_tracing()
+        } else {
+            self.exec_options
+        };
+
+        TransactionExecutor {
+            data_store,
+            compiler,
+            exec_options,
+            authenticator: self.authenticator,
+            event_handlers: self.event_handlers,
+            replay_dir: self.replay_dir,
+            trace_handler: self.trace_handler,
+            note_scripts: self.note_scripts,
+        }
+    }
+}
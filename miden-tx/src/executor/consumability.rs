@@ -0,0 +1,29 @@
+#[cfg(doc)]
+use super::TransactionExecutor;
+use crate::TransactionExecutorError;
+
+// NOTE CONSUMABILITY
+// ================================================================================================
+
+/// Outcome of checking whether a note can be consumed by an account via
+/// [TransactionExecutor::check_note_consumability()].
+#[derive(Debug)]
+pub enum NoteConsumability {
+    /// The note script executes successfully against the account.
+    Consumable,
+    /// The note cannot currently be consumed by the account (e.g., because the note targets
+    /// another account, its timelock has not expired yet, the account does not expose the
+    /// procedures called by the note script, or the epilogue rejects the transaction consuming
+    /// the note).
+    ///
+    /// The contained error describes the reason of the failure; for failures during the execution
+    /// of the note, the error identifies the failed note and the source of the failure.
+    NotConsumable(TransactionExecutorError),
+}
+
+impl NoteConsumability {
+    /// Returns true if the note can be consumed by the account.
+    pub fn is_consumable(&self) -> bool {
+        matches!(self, Self::Consumable)
+    }
+}
@@ -1,5 +1,8 @@
 use miden_objects::{
-    accounts::AccountId, assembly::ModuleAst, notes::NoteId, transaction::TransactionInputs,
+    accounts::AccountId,
+    assembly::ModuleAst,
+    notes::NoteId,
+    transaction::{ForeignAccountInputs, TransactionInputs},
 };
 
 use crate::DataStoreError;
@@ -9,7 +12,10 @@ use crate::DataStoreError;
 
 /// The [DataStore] trait defines the interface that transaction objects use to fetch data
 /// required for transaction execution.
-pub trait DataStore {
+///
+/// Data stores are required to be [Send] and [Sync], so that executors (and the data stores they
+/// own) can be shared between threads.
+pub trait DataStore: Send + Sync {
     /// Returns account, chain, and input note data required to execute a transaction against
     /// the account with the specified ID and consuming the set of specified input notes.
     ///
@@ -33,4 +39,59 @@ pub trait DataStore {
 
     /// Returns the account code [ModuleAst] associated with the the specified [AccountId].
     fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError>;
+
+    /// Returns the data required to read the state of the foreign account with the specified ID
+    /// from within a transaction executed against the block with the number `block_ref`.
+    ///
+    /// The returned state must be the state of the foreign account recorded in the account
+    /// database of the specified block. Data stores which do not track the state of other
+    /// accounts can rely on the default implementation, which reports every foreign account as
+    /// not found.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The account with the specified ID could not be found in the data store.
+    /// - The block with the specified number could not be found in the data store.
+    /// - The data store encountered some internal error
+    fn get_foreign_account_inputs(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+    ) -> Result<ForeignAccountInputs, DataStoreError> {
+        let _ = block_ref;
+        Err(DataStoreError::AccountNotFound(account_id))
+    }
+}
+
+// ASYNC DATA STORE TRAIT
+// ================================================================================================
+
+/// The [AsyncDataStore] trait is the asynchronous counterpart of [DataStore].
+///
+/// It allows building transaction executors on top of network-backed stores (e.g., a gRPC client
+/// of a node, or an SQL database) without blocking the current thread while the data required
+/// for transaction execution is fetched. Executors backed by an [AsyncDataStore] expose the
+/// [load_account_async()](crate::TransactionExecutor::load_account_async) and
+/// [execute_transaction_async()](crate::TransactionExecutor::execute_transaction_async) methods
+/// of the [TransactionExecutor](crate::TransactionExecutor).
+///
+/// The futures returned by the store are required to be [Send], and thus so are the futures
+/// returned by the executor methods above, which allows executing transactions on tasks spawned
+/// onto multi-threaded runtimes.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncDataStore: Send + Sync {
+    /// Returns account, chain, and input note data required to execute a transaction against
+    /// the account with the specified ID and consuming the set of specified input notes.
+    ///
+    /// See [DataStore::get_transaction_inputs()] for the requirements on the returned data.
+    async fn get_transaction_inputs(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        notes: &[NoteId],
+    ) -> Result<TransactionInputs, DataStoreError>;
+
+    /// Returns the account code [ModuleAst] associated with the the specified [AccountId].
+    async fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError>;
 }
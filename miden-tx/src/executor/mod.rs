@@ -1,23 +1,56 @@
-use miden_lib::transaction::{ToTransactionKernelInputs, TransactionKernel};
+use std::path::PathBuf;
+
+use miden_lib::transaction::{
+    add_foreign_accounts_to_advice_inputs, KernelVersion, ToTransactionKernelInputs,
+};
 use miden_objects::{
-    accounts::{Account, AccountDelta, AccountStorage, AccountStorageDelta, AccountStub},
-    assembly::ProgramAst,
-    crypto::merkle::{merkle_tree_delta, MerkleStore},
-    transaction::{TransactionInputs, TransactionScript},
+    accounts::{
+        Account, AccountDelta, AccountHeader, AccountStorage, AccountStorageDelta,
+        AccountVaultDelta, StorageMapDelta,
+    },
+    assembly::{ModuleAst, ProgramAst},
+    assets::Asset,
+    crypto::merkle::{MerkleStore, NodeIndex},
+    notes::NetworkNote,
+    transaction::{
+        ForeignAccountInputs, InputNotes, TransactionInputs, TransactionMeasurements,
+        TransactionScript, TransactionWitness,
+    },
+    utils::{collections::BTreeMap, sync::Arc},
     vm::{Program, StackOutputs},
-    Felt, TransactionOutputError, Word,
+    AccountDeltaError, Felt, TransactionOutputError, Word, EMPTY_WORD,
+};
+use vm_processor::{
+    AdviceInputs, ExecutionError, ExecutionOptions, ExecutionOptionsError, ExecutionTrace,
+    MemAdviceProvider, StackInputs,
 };
-use vm_processor::ExecutionOptions;
 
 use super::{
+    host::{
+        AccountStorageUpdates, CompactAdviceRecorder, EventHandlers, LazyAdviceProvider,
+        TransactionPhase, TransactionTraceHandler,
+    },
+    metrics,
+    replay::{SignatureRecorder, TransactionReplay},
     AccountCode, AccountId, Digest, ExecutedTransaction, NoteId, NoteScript, PreparedTransaction,
-    RecAdviceProvider, ScriptTarget, TransactionCompiler, TransactionExecutorError,
-    TransactionHost,
+    ScriptTarget, TransactionAuthenticator, TransactionCompiler, TransactionCompilerError,
+    TransactionExecutorError, TransactionHost, TransactionRequest,
 };
 
+mod builder;
+pub use builder::TransactionExecutorBuilder;
+
+mod consumability;
+pub use consumability::NoteConsumability;
+
 mod data;
+#[cfg(feature = "async")]
+pub use data::AsyncDataStore;
 pub use data::DataStore;
 
+mod simulation;
+pub use simulation::TransactionSimulation;
+
 // TRANSACTION EXECUTOR
 // ================================================================================================
 
@@ -34,54 +67,78 @@ pub use data::DataStore;
 /// The [TransactionExecutor::execute_transaction()] method is the main entry point for the
 /// executor and produces an [ExecutedTransaction] for the transaction. The executed transaction
 /// can then be used to by the prover to generate a proof transaction execution.
-pub struct TransactionExecutor<D: DataStore> {
+///
+/// Executors with non-default configuration can be constructed via
+/// [TransactionExecutor::builder()].
+///
+/// All methods of the executor take `&self`, and the account interfaces loaded into its compiler
+/// are kept behind a lock. Thus, provided that the [DataStore] is [Sync], a single executor can be
+/// shared across threads (e.g., via an `Arc`), and account code loaded while serving one request
+/// is reused by all subsequent requests.
+pub struct TransactionExecutor<D> {
     data_store: D,
     compiler: TransactionCompiler,
     exec_options: ExecutionOptions,
+    authenticator: Option<Arc<dyn TransactionAuthenticator>>,
+    event_handlers: EventHandlers,
+    replay_dir: Option<PathBuf>,
+    trace_handler: Option<Arc<dyn TransactionTraceHandler>>,
+    note_scripts: BTreeMap<Digest, NoteScript>,
 }
 
-impl<D: DataStore> TransactionExecutor<D> {
+impl<D> TransactionExecutor<D> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new [TransactionExecutor] instance with the specified [DataStore].
-    pub fn new(data_store: D) -> Self {
-        Self {
-            data_store,
-            compiler: TransactionCompiler::new(),
-            exec_options: ExecutionOptions::default(),
-        }
+    /// Returns a [TransactionExecutorBuilder] which can be used to construct a
+    /// [TransactionExecutor] with custom configuration.
+    pub fn builder() -> TransactionExecutorBuilder<D> {
+        TransactionExecutorBuilder::new()
     }
 
-    // STATE MUTATORS
+    /// Consumes this executor and returns its [TransactionCompiler] so that it can be reused by
+    /// another executor.
+    pub fn into_compiler(self) -> TransactionCompiler {
+        self.compiler
+    }
+
+    // EXECUTION OPTIONS
     // --------------------------------------------------------------------------------------------
 
-    /// Fetches the account code from the [DataStore], compiles it, and loads the compiled code
-    /// into the internal cache.
+    /// Limits the number of VM cycles which can be consumed by a transaction program; executions
+    /// exceeding the limit fail with [ExecutionError::CycleLimitExceeded].
     ///
-    /// This also returns the [AccountCode] object built from the loaded account code.
+    /// The expected number of cycles is lowered to `max_cycles` if it exceeds the new limit, and
+    /// all other execution options are retained. The number of cycles actually consumed by a
+    /// transaction is reported via [ExecutedTransaction::measurements()].
     ///
-    /// # Errors:
-    /// Returns an error if:
-    /// - If the account code cannot be fetched from the [DataStore].
-    /// - If the account code fails to be loaded into the compiler.
-    pub fn load_account(
-        &mut self,
-        account_id: AccountId,
-    ) -> Result<AccountCode, TransactionExecutorError> {
-        let account_code = self
-            .data_store
-            .get_account_code(account_id)
-            .map_err(TransactionExecutorError::FetchAccountCodeFailed)?;
-        self.compiler
-            .load_account(account_id, account_code)
-            .map_err(TransactionExecutorError::LoadAccountFailed)
+    /// # Errors
+    /// Returns an error if `max_cycles` is outside of the range of cycle limits supported by the
+    /// VM.
+    ///
+    /// [ExecutionError::CycleLimitExceeded]: vm_processor::ExecutionError::CycleLimitExceeded
+    pub fn with_max_cycles(mut self, max_cycles: u32) -> Result<Self, ExecutionOptionsError> {
+        let expected_cycles = self.exec_options.expected_cycles().min(max_cycles);
+        let mut exec_options = ExecutionOptions::new(Some(max_cycles), expected_cycles)?;
+        if self.exec_options.enable_tracing() {
+            exec_options = exec_options.with_tracing();
+        }
+        self.exec_options = exec_options;
+        Ok(self)
     }
 
+    /// Returns the [ExecutionOptions] used when executing transaction programs.
+    pub fn execution_options(&self) -> &ExecutionOptions {
+        &self.exec_options
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
     /// Loads the provided account interface (vector of procedure digests) into the compiler.
     ///
     /// Returns the old interface for the specified account ID if it previously existed.
     pub fn load_account_interface(
-        &mut self,
+        &self,
         account_id: AccountId,
         procedures: Vec<Digest>,
     ) -> Option<Vec<Digest>> {
@@ -92,7 +149,7 @@ impl<D: DataStore> TransactionExecutor<D> {
     /// the specified note program could be executed against all accounts with the specified
     /// interfaces.
     pub fn compile_note_script(
-        &mut self,
+        &self,
         note_script_ast: ProgramAst,
         target_account_procs: Vec<ScriptTarget>,
     ) -> Result<NoteScript, TransactionExecutorError> {
@@ -105,7 +162,7 @@ impl<D: DataStore> TransactionExecutor<D> {
     /// checks (to the extent possible) that the transaction script can be executed against all
     /// accounts with the specified interfaces.
     pub fn compile_tx_script<T>(
-        &mut self,
+        &self,
         tx_script_ast: ProgramAst,
         inputs: T,
         target_account_procs: Vec<ScriptTarget>,
@@ -118,6 +175,487 @@ impl<D: DataStore> TransactionExecutor<D> {
             .map_err(TransactionExecutorError::CompileTransactionScriptFailed)
     }
 
+    // WITNESS RE-EXECUTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Re-executes the transaction described by the provided [TransactionWitness] and returns the
+    /// resulting [ExecutedTransaction].
+    ///
+    /// The transaction is executed purely from the data contained in the witness: neither the
+    /// [DataStore] nor the [TransactionAuthenticator] of this executor are accessed, and the
+    /// transaction program is compiled from the inputs and the script of the witness. Signatures
+    /// requested by the transaction are read from the advice witness, where they were recorded
+    /// during the original execution. Thus, re-executing the witness of a transaction produces the
+    /// same [ExecutedTransaction] as the original execution.
+    ///
+    /// Event handlers, event decoders and the trace handler of this executor are attached to the
+    /// re-execution, while replays are not recorded.
+    ///
+    /// # Errors:
+    /// Returns an error if:
+    /// - The transaction was executed with a different transaction kernel, or its program cannot
+    ///   be compiled.
+    /// - The transaction program can not be executed (e.g., because the advice witness is
+    ///   incomplete).
+    /// - The outputs of the transaction are invalid.
+    pub fn execute_from_witness(
+        &self,
+        tx_witness: TransactionWitness,
+    ) -> Result<ExecutedTransaction, TransactionExecutorError> {
+        let account_id = tx_witness.account().id();
+        let block_ref = tx_witness.block_header().block_num();
+        let notes = tx_witness.input_notes().iter().map(|note| note.id()).collect::<Vec<_>>();
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "execute_from_witness",
+            account_id = %account_id,
+            block_ref
+        )
+        .entered();
+
+        // the kernel inputs are rebuilt from the transaction inputs, and the advice witness only
+        // contributes its advice map and Merkle store (e.g., recorded signatures and foreign
+        // account data); this way, the advice recorder records the same advice witness as during
+        // the original execution
+        if tx_witness.kernel_hash() != self.compiler.kernel().hash() {
+            return Err(TransactionExecutorError::CompileTransactionFailed {
+                account_id,
+                source: TransactionCompilerError::KernelMismatch(tx_witness.kernel_hash()),
+            });
+        }
+        let tx_program = self
+            .compiler
+            .compile_transaction_from_inputs(tx_witness.tx_inputs(), tx_witness.tx_script())
+            .map_err(|source| TransactionExecutorError::CompileTransactionFailed {
+                account_id,
+                source,
+            })?;
+        let transaction = PreparedTransaction::new(
+            Arc::new(tx_program),
+            tx_witness.tx_script().cloned(),
+            tx_witness.tx_inputs().clone(),
+        )
+        .with_tx_script_args(tx_witness.tx_script_args());
+        let (stack_inputs, mut advice_inputs, vault_inputs) =
+            transaction.get_kernel_inputs_with_lazy_vault(self.compiler.kernel());
+        let (_, witness_map, witness_store) = tx_witness.advice_witness().clone().into_parts();
+        advice_inputs.extend_map(witness_map);
+        advice_inputs.extend_merkle_store(witness_store.inner_nodes());
+
+        let advice_recorder: CompactAdviceRecorder = advice_inputs.into();
+        let advice_provider = LazyAdviceProvider::new(
+            advice_recorder,
+            transaction.account().vault().commitment(),
+            vault_inputs,
+        );
+        let mut host = TransactionHost::new(transaction.account().into(), advice_provider)
+            .with_note_scripts(self.note_scripts(transaction.input_notes()))
+            .with_event_handlers(self.event_handlers.clone());
+        if let Some(trace_handler) = self.trace_handler.clone() {
+            host = host.with_trace_handler(trace_handler);
+        }
+
+        let result = vm_processor::execute(
+            transaction.program(),
+            stack_inputs,
+            &mut host,
+            self.exec_options,
+        )
+        .map_err(|source| {
+            program_execution_failed(account_id, block_ref, &notes, host.phase(), source)
+        })?;
+
+        let measurements = TransactionMeasurements {
+            num_cycles: result.trace_len_summary().main_trace_len(),
+        };
+
+        let (_, tx_script, tx_script_args, tx_inputs) = transaction.into_parts();
+
+        let executed_tx = build_executed_transaction(
+            self.compiler.kernel(),
+            tx_script,
+            tx_script_args,
+            tx_inputs,
+            result.stack_outputs().clone(),
+            host,
+            measurements,
+        )?;
+        metrics::record_execution(measurements.num_cycles);
+
+        Ok(executed_tx)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the note scripts known to this executor together with the scripts of the provided
+    /// input notes, keyed by their hashes.
+    fn note_scripts(&self, input_notes: &InputNotes) -> BTreeMap<Digest, NoteScript> {
+        let mut note_scripts = self.note_scripts.clone();
+        for input_note in input_notes.iter() {
+            let script = input_note.note().script();
+            note_scripts.insert(script.hash(), script.clone());
+        }
+        note_scripts
+    }
+
+    /// Loads the provided account code into the compiler.
+    fn load_account_code(
+        &self,
+        account_id: AccountId,
+        account_code: ModuleAst,
+    ) -> Result<AccountCode, TransactionExecutorError> {
+        self.compiler
+            .load_account(account_id, account_code)
+            .map_err(|source| TransactionExecutorError::LoadAccountFailed { account_id, source })
+    }
+
+    /// Compiles the transaction defined by the provided inputs and transaction script into an
+    /// executable program using the [TransactionCompiler], and returns a [PreparedTransaction].
+    ///
+    /// # Errors:
+    /// Returns an error if the transaction can not be compiled.
+    fn compile_transaction(
+        &self,
+        tx_inputs: TransactionInputs,
+        tx_script: Option<TransactionScript>,
+    ) -> Result<PreparedTransaction, TransactionExecutorError> {
+        let account_id = tx_inputs.account().id();
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "compile_transaction",
+            num_input_notes = tx_inputs.input_notes().num_notes()
+        )
+        .entered();
+
+        let tx_program = self
+            .compiler
+            .compile_transaction(
+                account_id,
+                tx_inputs.input_notes(),
+                tx_script.as_ref().map(|x| x.code()),
+            )
+            .map_err(|source| TransactionExecutorError::CompileTransactionFailed {
+                account_id,
+                source,
+            })?;
+
+        Ok(PreparedTransaction::new(Arc::new(tx_program), tx_script, tx_inputs))
+    }
+
+    /// Executes the provided [PreparedTransaction] and returns an [ExecutedTransaction].
+    ///
+    /// The data of the provided foreign accounts is made available to the transaction via the
+    /// advice provider; only the parts of it which are read by the transaction become a part of
+    /// the advice witness.
+    ///
+    /// # Errors:
+    /// Returns an error if the transaction program can not be executed.
+    fn execute_prepared_transaction(
+        &self,
+        transaction: PreparedTransaction,
+        block_ref: u32,
+        notes: &[NoteId],
+        foreign_accounts: &[ForeignAccountInputs],
+    ) -> Result<ExecutedTransaction, TransactionExecutorError> {
+        let (stack_inputs, mut advice_inputs, vault_inputs) =
+            transaction.get_kernel_inputs_with_lazy_vault(self.compiler.kernel());
+        add_foreign_accounts_to_advice_inputs(foreign_accounts, &mut advice_inputs);
+        self.run_prepared_transaction(
+            transaction,
+            stack_inputs,
+            advice_inputs,
+            vault_inputs,
+            block_ref,
+            notes,
+        )
+    }
+
+    /// Re-executes a transaction recorded via [TransactionReplay] against the recorded inputs,
+    /// transaction script and its arguments, and initial advice inputs, and returns an
+    /// [ExecutedTransaction].
+    ///
+    /// The account of the transaction must be loaded into this executor.
+    ///
+    /// # Errors:
+    /// Returns an error if the transaction can not be compiled or executed.
+    pub(crate) fn replay_transaction(
+        &self,
+        tx_inputs: TransactionInputs,
+        tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
+        advice_inputs: AdviceInputs,
+    ) -> Result<ExecutedTransaction, TransactionExecutorError> {
+        let block_ref = tx_inputs.block_header().block_num();
+        let notes = tx_inputs.input_notes().iter().map(|note| note.id()).collect::<Vec<_>>();
+        let transaction = self
+            .compile_transaction(tx_inputs, tx_script)?
+            .with_tx_script_args(tx_script_args);
+
+        // the vault data is derived from the account, and thus does not need to be recorded
+        let (stack_inputs, _, vault_inputs) =
+            transaction.get_kernel_inputs_with_lazy_vault(self.compiler.kernel());
+        self.run_prepared_transaction(
+            transaction,
+            stack_inputs,
+            advice_inputs,
+            vault_inputs,
+            block_ref,
+            &notes,
+        )
+    }
+
+    /// Executes the provided [PreparedTransaction] with the specified initial advice inputs and
+    /// returns an [ExecutedTransaction].
+    ///
+    /// If this executor records replays, the advice inputs are recorded together with the
+    /// transaction, such that the replay is executed against exactly the same advice data (e.g.,
+    /// including the data of foreign accounts).
+    fn run_prepared_transaction(
+        &self,
+        transaction: PreparedTransaction,
+        stack_inputs: StackInputs,
+        advice_inputs: AdviceInputs,
+        vault_inputs: AdviceInputs,
+        block_ref: u32,
+        notes: &[NoteId],
+    ) -> Result<ExecutedTransaction, TransactionExecutorError> {
+        let account_id = transaction.account().id();
+
+        let recorded_advice_inputs = self.replay_dir.as_ref().map(|_| advice_inputs.clone());
+        let advice_recorder: CompactAdviceRecorder = advice_inputs.into();
+        let advice_provider = LazyAdviceProvider::new(
+            advice_recorder,
+            transaction.account().vault().commitment(),
+            vault_inputs,
+        );
+        let mut host = TransactionHost::new(transaction.account().into(), advice_provider)
+            .with_note_scripts(self.note_scripts(transaction.input_notes()))
+            .with_event_handlers(self.event_handlers.clone());
+
+        // when recording replays, signatures produced by the authenticator are captured so that
+        // the transaction can later be re-executed without access to the authenticator
+        let signature_recorder = self
+            .replay_dir
+            .as_ref()
+            .map(|_| Arc::new(SignatureRecorder::new(self.authenticator.clone())));
+        let authenticator = match &signature_recorder {
+            Some(recorder) => Some(recorder.clone() as Arc<dyn TransactionAuthenticator>),
+            None => self.authenticator.clone(),
+        };
+        if let Some(authenticator) = authenticator {
+            host = host.with_authenticator(authenticator);
+        }
+        if let Some(trace_handler) = self.trace_handler.clone() {
+            host = host.with_trace_handler(trace_handler);
+        }
+
+        #[cfg(feature = "tracing")]
+        let execution_span =
+            tracing::info_span!("execute_program", num_cycles = tracing::field::Empty).entered();
+
+        let result = vm_processor::execute(
+            transaction.program(),
+            stack_inputs,
+            &mut host,
+            self.exec_options,
+        );
+
+        if let (Some(dir), Some(recorder), Some(advice_inputs)) =
+            (&self.replay_dir, signature_recorder, recorded_advice_inputs)
+        {
+            TransactionReplay::new(
+                transaction.tx_inputs().clone(),
+                transaction.tx_script().cloned(),
+                transaction.tx_script_args(),
+                advice_inputs,
+                recorder.signatures(),
+            )
+            .write_to_dir(dir)
+            .map_err(|source| TransactionExecutorError::RecordReplayFailed {
+                account_id,
+                source: Box::new(source),
+            })?;
+        }
+
+        let result = result.map_err(|source| {
+            program_execution_failed(account_id, block_ref, notes, host.phase(), source)
+        })?;
+
+        #[cfg(feature = "tracing")]
+        {
+            execution_span.record("num_cycles", result.trace_len_summary().main_trace_len());
+            execution_span.exit();
+        }
+
+        let measurements = TransactionMeasurements {
+            num_cycles: result.trace_len_summary().main_trace_len(),
+        };
+        let (_, tx_script, tx_script_args, tx_inputs) = transaction.into_parts();
+
+        let executed_tx = build_executed_transaction(
+            self.compiler.kernel(),
+            tx_script,
+            tx_script_args,
+            tx_inputs,
+            result.stack_outputs().clone(),
+            host,
+            measurements,
+        )?;
+        metrics::record_execution(measurements.num_cycles);
+
+        Ok(executed_tx)
+    }
+
+    /// Executes the provided [PreparedTransaction] without any side effects, and returns the
+    /// execution trace together with the host the transaction was executed with.
+    ///
+    /// The transaction is executed without the authenticator and the trace handler of this
+    /// executor, and without recording the advice witness; thus, signature requests can only be
+    /// answered from the advice inputs of the transaction. Replays are not recorded, and neither
+    /// successful nor failed executions are reported to the executor metrics.
+    ///
+    /// # Errors:
+    /// Returns an error if the transaction program can not be executed.
+    fn run_prepared_transaction_without_side_effects(
+        &self,
+        transaction: &PreparedTransaction,
+        notes: &[NoteId],
+    ) -> Result<
+        (ExecutionTrace, TransactionHost<LazyAdviceProvider<MemAdviceProvider>>),
+        TransactionExecutorError,
+    > {
+        let account_id = transaction.account().id();
+        let block_ref = transaction.block_header().block_num();
+
+        let (stack_inputs, advice_inputs, vault_inputs) =
+            transaction.get_kernel_inputs_with_lazy_vault(self.compiler.kernel());
+        let advice_provider = LazyAdviceProvider::new(
+            MemAdviceProvider::from(advice_inputs),
+            transaction.account().vault().commitment(),
+            vault_inputs,
+        );
+        let mut host = TransactionHost::new(transaction.account().into(), advice_provider)
+            .with_note_scripts(self.note_scripts(transaction.input_notes()))
+            .with_event_handlers(self.event_handlers.clone());
+
+        let trace = vm_processor::execute(
+            transaction.program(),
+            stack_inputs,
+            &mut host,
+            self.exec_options,
+        )
+        .map_err(|source| execution_error(account_id, block_ref, notes, host.phase(), source))?;
+
+        Ok((trace, host))
+    }
+
+    /// Executes the provided [PreparedTransaction] without any side effects, and discards the
+    /// result of the execution.
+    ///
+    /// # Errors:
+    /// Returns an error if the transaction program can not be executed.
+    fn dry_run_prepared_transaction(
+        &self,
+        transaction: PreparedTransaction,
+        notes: &[NoteId],
+    ) -> Result<(), TransactionExecutorError> {
+        self.run_prepared_transaction_without_side_effects(&transaction, notes)?;
+        Ok(())
+    }
+
+    /// Executes the provided [PreparedTransaction] without any side effects, and returns the
+    /// projected effects of the transaction.
+    ///
+    /// # Errors:
+    /// Returns an error if the transaction program can not be executed, or if the outputs of the
+    /// transaction are invalid.
+    fn simulate_prepared_transaction(
+        &self,
+        transaction: PreparedTransaction,
+        block_ref: u32,
+        notes: &[NoteId],
+    ) -> Result<TransactionSimulation, TransactionExecutorError> {
+        let account_id = transaction.account().id();
+        let (result, host) =
+            self.run_prepared_transaction_without_side_effects(&transaction, notes)?;
+
+        let measurements = TransactionMeasurements {
+            num_cycles: result.trace_len_summary().main_trace_len(),
+        };
+
+        let (advice_provider, vault_delta, storage_updates, _) = host.into_parts();
+        let (advice_provider, _) = advice_provider.into_parts();
+        let (_, map, store) = advice_provider.into_parts();
+
+        let tx_outputs = self
+            .compiler
+            .kernel()
+            .parse_transaction_outputs(result.stack_outputs(), &map.into())
+            .map_err(|source| TransactionExecutorError::InvalidTransactionOutput {
+                account_id,
+                source,
+            })?;
+        let (account_delta, _) = build_account_delta(
+            transaction.account(),
+            &tx_outputs.account,
+            &store,
+            &storage_updates,
+            vault_delta,
+        )?;
+
+        Ok(TransactionSimulation::new(
+            block_ref,
+            tx_outputs.account,
+            account_delta,
+            tx_outputs.output_notes,
+            tx_outputs.fee,
+            measurements,
+        ))
+    }
+}
+
+impl<D: DataStore> TransactionExecutor<D> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new [TransactionExecutor] instance with the specified [DataStore].
+    pub fn new(data_store: D) -> Self {
+        Self::with_compiler(data_store, TransactionCompiler::new())
+    }
+
+    /// Creates a new [TransactionExecutor] instance with the specified [DataStore] and a
+    /// pre-built [TransactionCompiler].
+    ///
+    /// Account interfaces already loaded into the provided compiler are retained, thus they do not
+    /// need to be loaded again for each new executor.
+    pub fn with_compiler(data_store: D, compiler: TransactionCompiler) -> Self {
+        Self::builder().with_data_store(data_store).with_compiler(compiler).build()
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Fetches the account code from the [DataStore], compiles it, and loads the compiled code
+    /// into the internal cache.
+    ///
+    /// This also returns the [AccountCode] object built from the loaded account code.
+    ///
+    /// # Errors:
+    /// Returns an error if:
+    /// - If the account code cannot be fetched from the [DataStore].
+    /// - If the account code fails to be loaded into the compiler.
+    pub fn load_account(
+        &self,
+        account_id: AccountId,
+    ) -> Result<AccountCode, TransactionExecutorError> {
+        let account_code = self.data_store.get_account_code(account_id).map_err(|source| {
+            TransactionExecutorError::FetchAccountCodeFailed { account_id, source }
+        })?;
+        self.load_account_code(account_id, account_code)
+    }
+
     // TRANSACTION EXECUTION
     // --------------------------------------------------------------------------------------------
 
@@ -128,41 +666,345 @@ impl<D: DataStore> TransactionExecutor<D> {
     /// and compile the transaction into an executable program. Then, it executes the transaction
     /// program and creates an [ExecutedTransaction] object.
     ///
+    /// The transaction script reads `tx_script_args` via `miden::tx::get_tx_script_args`. The
+    /// arguments are passed to the transaction kernel together with the other transaction inputs
+    /// rather than compiled into the script, thus a compiled script can be executed with different
+    /// arguments (e.g., different amounts to transfer) without being recompiled. The arguments are
+    /// ignored if no transaction script is provided.
+    ///
     /// # Errors:
     /// Returns an error if:
     /// - If required data can not be fetched from the [DataStore].
     /// - If the transaction program can not be compiled.
     /// - If the transaction program can not be executed.
     pub fn execute_transaction(
-        &mut self,
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        notes: &[NoteId],
+        tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
+    ) -> Result<ExecutedTransaction, TransactionExecutorError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "execute_transaction",
+            account_id = %account_id,
+            block_ref,
+            num_input_notes = notes.len()
+        )
+        .entered();
+
+        let transaction =
+            self.prepare_transaction(account_id, block_ref, notes, tx_script, tx_script_args)?;
+        self.execute_prepared_transaction(transaction, block_ref, notes, &[])
+    }
+
+    /// Prepares and executes a transaction which can read the storage of the specified foreign
+    /// accounts, and returns an [ExecutedTransaction].
+    ///
+    /// The state of each foreign account is fetched from the [DataStore] and authenticated
+    /// against the account database of the reference block. Note scripts and the transaction
+    /// script can then read items from the storage of these accounts via
+    /// `miden::account::get_foreign_item` (e.g., to read prices published by an oracle account).
+    ///
+    /// # Errors:
+    /// Returns an error if:
+    /// - If the state of any of the foreign accounts can not be fetched from the [DataStore].
+    /// - If the state of any of the foreign accounts is not recorded in the reference block.
+    /// - If the transaction can not be executed (see
+    ///   [TransactionExecutor::execute_transaction()]).
+    pub fn execute_transaction_with_foreign_accounts(
+        &self,
         account_id: AccountId,
         block_ref: u32,
         notes: &[NoteId],
+        foreign_account_ids: &[AccountId],
         tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
     ) -> Result<ExecutedTransaction, TransactionExecutorError> {
-        let transaction = self.prepare_transaction(account_id, block_ref, notes, tx_script)?;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "execute_transaction_with_foreign_accounts",
+            account_id = %account_id,
+            block_ref,
+            num_input_notes = notes.len(),
+            num_foreign_accounts = foreign_account_ids.len()
+        )
+        .entered();
 
-        let (stack_inputs, advice_inputs) = transaction.get_kernel_inputs();
-        let advice_recorder: RecAdviceProvider = advice_inputs.into();
-        let mut host = TransactionHost::new(transaction.account().into(), advice_recorder);
+        let transaction =
+            self.prepare_transaction(account_id, block_ref, notes, tx_script, tx_script_args)?;
 
-        let result = vm_processor::execute(
-            transaction.program(),
-            stack_inputs,
-            &mut host,
-            self.exec_options,
+        let mut foreign_accounts = Vec::with_capacity(foreign_account_ids.len());
+        for &foreign_account_id in foreign_account_ids {
+            let foreign_account = self
+                .data_store
+                .get_foreign_account_inputs(foreign_account_id, block_ref)
+                .map_err(|source| TransactionExecutorError::FetchForeignAccountInputsFailed {
+                    account_id,
+                    foreign_account_id,
+                    source,
+                })?;
+
+            if foreign_account.account_id() != foreign_account_id
+                || !foreign_account.verify(transaction.block_header())
+            {
+                return Err(TransactionExecutorError::InconsistentForeignAccountInputs {
+                    account_id,
+                    foreign_account_id,
+                    block_ref,
+                });
+            }
+            foreign_accounts.push(foreign_account);
+        }
+
+        self.execute_prepared_transaction(transaction, block_ref, notes, &foreign_accounts)
+    }
+
+    /// Executes the transaction described by the provided [TransactionRequest] against the
+    /// reference block `block_ref` and returns an [ExecutedTransaction].
+    ///
+    /// The transaction script generated by the request is compiled against the interface of the
+    /// account of the request, thus the account must be loaded into this executor (e.g., via
+    /// [TransactionExecutor::load_account()]) prior to calling this method.
+    ///
+    /// # Errors:
+    /// Returns an error if:
+    /// - If the transaction script of the request can not be compiled.
+    /// - If the transaction can not be executed (see
+    ///   [TransactionExecutor::execute_transaction()]).
+    /// - If the effects of the executed transaction differ from the effects expected by the
+    ///   request.
+    pub fn execute_request(
+        &self,
+        block_ref: u32,
+        request: TransactionRequest,
+    ) -> Result<ExecutedTransaction, TransactionExecutorError> {
+        let account_id = request.account_id();
+        let tx_script = self.compile_tx_script(
+            request.script_ast(),
+            request.script_inputs().clone(),
+            vec![ScriptTarget::AccountId(account_id)],
+        )?;
+
+        let transaction = self.execute_transaction(
+            account_id,
+            block_ref,
+            request.input_note_ids(),
+            Some(tx_script),
+            EMPTY_WORD,
+        )?;
+
+        request.check_effects(&transaction).map_err(|mismatches| {
+            TransactionExecutorError::UnexpectedTransactionEffects { account_id, mismatches }
+        })?;
+
+        Ok(transaction)
+    }
+
+    /// Executes a network transaction consuming the provided network notes against the specified
+    /// on-chain account, and returns an [ExecutedTransaction].
+    ///
+    /// Network transactions are executed by the network operator rather than by the owner of the
+    /// account. Thus, no transaction script is executed, and the state of the account as well as
+    /// the notes are fetched from the [DataStore] of the operator (which tracks the state of all
+    /// on-chain accounts and public notes).
+    ///
+    /// # Errors:
+    /// Returns an error if:
+    /// - If any of the notes cannot be consumed against the specified account, e.g., because the
+    ///   account is not an on-chain account or the note targets another account.
+    /// - If the transaction can not be executed (see
+    ///   [TransactionExecutor::execute_transaction()]).
+    pub fn execute_network_transaction(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        notes: &[NetworkNote],
+    ) -> Result<ExecutedTransaction, TransactionExecutorError> {
+        if let Some(note) = notes.iter().find(|note| !note.can_be_consumed_by(account_id)) {
+            return Err(TransactionExecutorError::NetworkNoteNotConsumable {
+                account_id,
+                note_id: note.id(),
+            });
+        }
+
+        let note_ids = notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+        self.execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+    }
+
+    /// Executes a batch of transactions, each specified by a tuple of account ID, reference block
+    /// number, input note IDs, an optional transaction script, and the arguments of the script,
+    /// and returns the result of each transaction in the order in which the transactions were
+    /// provided.
+    ///
+    /// Transactions are executed independently of each other, thus a failure of one transaction
+    /// does not prevent the remaining transactions from being executed. Work is shared across the
+    /// transactions of the batch as follows:
+    /// - Account code is fetched from the [DataStore] and compiled only once per account (and not
+    ///   at all for accounts which have already been loaded into this executor).
+    /// - Transaction inputs are fetched from the [DataStore] only once per distinct account,
+    ///   reference block, and set of input notes.
+    /// - Transaction programs are compiled only once per distinct account, set of input notes,
+    ///   and transaction script (the arguments of the script are not a part of the program).
+    ///
+    /// Advice inputs commit to the state of a specific account and its input notes, thus a new
+    /// advice provider is built for every transaction; likewise, transactions of the batch are not
+    /// chained, i.e., every transaction is executed against the account state provided by the
+    /// [DataStore].
+    pub fn execute_batch<I>(
+        &self,
+        transactions: I,
+    ) -> Vec<Result<ExecutedTransaction, TransactionExecutorError>>
+    where
+        I: IntoIterator<Item = (AccountId, u32, Vec<NoteId>, Option<TransactionScript>, Word)>,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("execute_batch").entered();
+
+        let mut tx_inputs_cache =
+            BTreeMap::<(AccountId, u32, Vec<NoteId>), TransactionInputs>::new();
+        let mut program_cache =
+            BTreeMap::<(AccountId, Vec<NoteId>, Option<Digest>), Arc<Program>>::new();
+
+        transactions
+            .into_iter()
+            .map(|(account_id, block_ref, notes, tx_script, tx_script_args)| {
+                if !self.compiler.has_account(account_id) {
+                    self.load_account(account_id)?;
+                }
+
+                let inputs_key = (account_id, block_ref, notes.clone());
+                let tx_inputs = match tx_inputs_cache.get(&inputs_key) {
+                    Some(tx_inputs) => tx_inputs.clone(),
+                    None => {
+                        let tx_inputs = self
+                            .data_store
+                            .get_transaction_inputs(account_id, block_ref, &notes)
+                            .map_err(|source| {
+                                TransactionExecutorError::FetchTransactionInputsFailed {
+                                    account_id,
+                                    block_ref,
+                                    source,
+                                }
+                            })?;
+                        tx_inputs_cache.insert(inputs_key, tx_inputs.clone());
+                        tx_inputs
+                    },
+                };
+
+                let program_key =
+                    (account_id, notes.clone(), tx_script.as_ref().map(|script| *script.hash()));
+                let transaction = match program_cache.get(&program_key) {
+                    Some(program) => {
+                        PreparedTransaction::new(program.clone(), tx_script, tx_inputs)
+                    },
+                    None => {
+                        let transaction = self.compile_transaction(tx_inputs, tx_script)?;
+                        program_cache.insert(program_key, transaction.shared_program());
+                        transaction
+                    },
+                }
+                .with_tx_script_args(tx_script_args);
+
+                self.execute_prepared_transaction(transaction, block_ref, &notes, &[])
+            })
+            .collect()
+    }
+
+    // TRANSACTION SIMULATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Simulates the transaction specified by the provided arguments, and returns its projected
+    /// effects: the account delta, the created notes, and the number of consumed VM cycles.
+    ///
+    /// The transaction program is executed in the same way as by
+    /// [TransactionExecutor::execute_transaction()], but the advice witness required to prove the
+    /// transaction is not recorded, and no [ExecutedTransaction] is built. Thus, simulating a
+    /// transaction is cheaper than executing it, and can be used to preview the effects of a
+    /// transaction before committing to prove it.
+    ///
+    /// Like [TransactionExecutor::check_note_consumability()], the simulation has no side
+    /// effects: the authenticator and the trace handler of this executor are not invoked, no
+    /// replay is recorded, and the execution is not reported to the executor metrics. Thus, if
+    /// the transaction requires signatures, they must be provided via the advice inputs of the
+    /// transaction (e.g., by the [DataStore]); otherwise, the simulation fails.
+    ///
+    /// # Errors:
+    /// Returns an error if:
+    /// - If required data can not be fetched from the [DataStore].
+    /// - If the transaction program can not be compiled.
+    /// - If the transaction program can not be executed.
+    pub fn simulate_transaction(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        notes: &[NoteId],
+        tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
+    ) -> Result<TransactionSimulation, TransactionExecutorError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "simulate_transaction",
+            account_id = %account_id,
+            block_ref,
+            num_input_notes = notes.len()
         )
-        .map_err(TransactionExecutorError::ExecuteTransactionProgramFailed)?;
+        .entered();
 
-        let (tx_program, tx_script, tx_inputs) = transaction.into_parts();
+        let transaction =
+            self.prepare_transaction(account_id, block_ref, notes, tx_script, tx_script_args)?;
+        self.simulate_prepared_transaction(transaction, block_ref, notes)
+    }
 
-        build_executed_transaction(
-            tx_program,
-            tx_script,
-            tx_inputs,
-            result.stack_outputs().clone(),
-            host,
+    // NOTE CONSUMABILITY
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the specified note can currently be consumed by the specified account by
+    /// executing a transaction which consumes only this note against the state of the account as
+    /// of the reference block `block_ref`.
+    ///
+    /// The transaction is executed in a dry-run mode which has no side effects: no transaction
+    /// script is executed, the authenticator and the trace handler of this executor are not
+    /// invoked, no replay is recorded, and the execution is not reported to the executor metrics.
+    /// The executed transaction is discarded.
+    ///
+    /// A note is reported as [NoteConsumability::NotConsumable] together with the reason if it
+    /// cannot be compiled against the interface of the account, or if any phase of the
+    /// transaction fails. This includes failures after the note was executed, e.g., the epilogue
+    /// rejecting a note which changes the state of the account without the nonce of the account
+    /// being incremented.
+    ///
+    /// The account is assumed to have been loaded into this executor (e.g., via
+    /// [TransactionExecutor::load_account()]).
+    ///
+    /// # Errors:
+    /// Returns an error if the data required to execute the transaction can not be fetched from
+    /// the [DataStore].
+    pub fn check_note_consumability(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        note_id: NoteId,
+    ) -> Result<NoteConsumability, TransactionExecutorError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "check_note_consumability",
+            account_id = %account_id,
+            block_ref
         )
+        .entered();
+
+        let notes = [note_id];
+        let result = self
+            .prepare_transaction(account_id, block_ref, &notes, None, EMPTY_WORD)
+            .and_then(|transaction| self.dry_run_prepared_transaction(transaction, &notes));
+
+        match result {
+            Ok(()) => Ok(NoteConsumability::Consumable),
+            Err(err @ TransactionExecutorError::FetchTransactionInputsFailed { .. }) => Err(err),
+            Err(err) => Ok(NoteConsumability::NotConsumable(err)),
+        }
     }
 
     // HELPER METHODS
@@ -170,122 +1012,483 @@ impl<D: DataStore> TransactionExecutor<D> {
 
     /// Fetches the data required to execute the transaction from the [DataStore], compiles the
     /// transaction into an executable program using the [TransactionCompiler], and returns a
-    /// [PreparedTransaction].
+    /// [PreparedTransaction] which executes the transaction script with the provided arguments.
     ///
     /// # Errors:
     /// Returns an error if:
     /// - If required data can not be fetched from the [DataStore].
     /// - If the transaction can not be compiled.
     fn prepare_transaction(
-        &mut self,
+        &self,
         account_id: AccountId,
         block_ref: u32,
         notes: &[NoteId],
         tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
     ) -> Result<PreparedTransaction, TransactionExecutorError> {
+        #[cfg(feature = "tracing")]
+        let fetch_span = tracing::info_span!("fetch_transaction_inputs").entered();
+
         let tx_inputs = self
             .data_store
             .get_transaction_inputs(account_id, block_ref, notes)
-            .map_err(TransactionExecutorError::FetchTransactionInputsFailed)?;
+            .map_err(|source| TransactionExecutorError::FetchTransactionInputsFailed {
+                account_id,
+                block_ref,
+                source,
+            })?;
 
-        let tx_program = self
-            .compiler
-            .compile_transaction(
+        #[cfg(feature = "tracing")]
+        fetch_span.exit();
+
+        Ok(self
+            .compile_transaction(tx_inputs, tx_script)?
+            .with_tx_script_args(tx_script_args))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D: AsyncDataStore> TransactionExecutor<D> {
+    // ASYNC STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Fetches the account code from the [AsyncDataStore], compiles it, and loads the compiled
+    /// code into the internal cache.
+    ///
+    /// This is the asynchronous counterpart of [TransactionExecutor::load_account()].
+    ///
+    /// # Errors:
+    /// Returns an error if:
+    /// - If the account code cannot be fetched from the [AsyncDataStore].
+    /// - If the account code fails to be loaded into the compiler.
+    pub async fn load_account_async(
+        &self,
+        account_id: AccountId,
+    ) -> Result<AccountCode, TransactionExecutorError> {
+        let account_code =
+            self.data_store.get_account_code(account_id).await.map_err(|source| {
+                TransactionExecutorError::FetchAccountCodeFailed { account_id, source }
+            })?;
+        self.load_account_code(account_id, account_code)
+    }
+
+    // ASYNC TRANSACTION EXECUTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Prepares and executes a transaction specified by the provided arguments and returns an
+    /// [ExecutedTransaction].
+    ///
+    /// This is the asynchronous counterpart of [TransactionExecutor::execute_transaction()]: the
+    /// data required to execute the transaction is awaited from the [AsyncDataStore]. Compiling
+    /// and executing the transaction program is CPU-bound and is performed synchronously, thus
+    /// callers running on a single-threaded runtime may want to offload the returned future.
+    ///
+    /// # Errors:
+    /// Returns an error if:
+    /// - If required data can not be fetched from the [AsyncDataStore].
+    /// - If the transaction program can not be compiled.
+    /// - If the transaction program can not be executed.
+    pub async fn execute_transaction_async(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        notes: &[NoteId],
+        tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
+    ) -> Result<ExecutedTransaction, TransactionExecutorError> {
+        let tx_inputs = self
+            .data_store
+            .get_transaction_inputs(account_id, block_ref, notes)
+            .await
+            .map_err(|source| TransactionExecutorError::FetchTransactionInputsFailed {
                 account_id,
-                tx_inputs.input_notes(),
-                tx_script.as_ref().map(|x| x.code()),
-            )
-            .map_err(TransactionExecutorError::CompileTransactionFiled)?;
+                block_ref,
+                source,
+            })?;
 
-        Ok(PreparedTransaction::new(tx_program, tx_script, tx_inputs))
+        let transaction = self
+            .compile_transaction(tx_inputs, tx_script)?
+            .with_tx_script_args(tx_script_args);
+        self.execute_prepared_transaction(transaction, block_ref, notes, &[])
     }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
-/// Creates a new [ExecutedTransaction] from the provided data, advice provider and stack outputs.
+/// Returns the error reported when the program of a transaction consuming the specified notes
+/// fails in the specified phase, and records the failure in the executor metrics.
+fn program_execution_failed(
+    account_id: AccountId,
+    block_ref: u32,
+    notes: &[NoteId],
+    phase: TransactionPhase,
+    source: ExecutionError,
+) -> TransactionExecutorError {
+    metrics::record_execution_failure();
+    execution_error(account_id, block_ref, notes, phase, source)
+}
+
+/// Returns the error reported when the program of a transaction consuming the specified notes
+/// fails in the specified phase, attributing the failure to the note executed in that phase.
+fn execution_error(
+    account_id: AccountId,
+    block_ref: u32,
+    notes: &[NoteId],
+    phase: TransactionPhase,
+    source: ExecutionError,
+) -> TransactionExecutorError {
+    let failed_note = match phase {
+        TransactionPhase::NoteExecution { note_index } => notes.get(note_index).copied(),
+        _ => None,
+    };
+    TransactionExecutorError::ExecuteTransactionProgramFailed {
+        account_id,
+        block_ref,
+        input_notes: notes.to_vec(),
+        phase,
+        failed_note,
+        source,
+    }
+}
+
+/// Creates a new [ExecutedTransaction] from the provided data, advice provider, stack outputs and
+/// execution measurements.
 fn build_executed_transaction(
-    program: Program,
+    kernel: &KernelVersion,
     tx_script: Option<TransactionScript>,
+    tx_script_args: Word,
     tx_inputs: TransactionInputs,
     stack_outputs: StackOutputs,
-    host: TransactionHost<RecAdviceProvider>,
+    host: TransactionHost<LazyAdviceProvider<CompactAdviceRecorder>>,
+    measurements: TransactionMeasurements,
 ) -> Result<ExecutedTransaction, TransactionExecutorError> {
-    let (advice_recorder, vault_delta) = host.into_parts();
+    #[cfg(feature = "tracing")]
+    let _span =
+        tracing::info_span!("build_executed_transaction", num_output_notes = tracing::field::Empty)
+            .entered();
+
+    let (advice_provider, vault_delta, storage_updates, output_note_tracker) = host.into_parts();
+    let (advice_recorder, loaded_vault_inputs) = advice_provider.into_parts();
 
-    // finalize the advice recorder
-    let (advice_witness, _, map, store) = advice_recorder.finalize();
+    // finalize the advice recorder; the advice witness is extended only with the parts of the
+    // account vault which were accessed during transaction execution
+    let (mut advice_witness, advice_provider) = advice_recorder.finalize();
+    advice_witness.extend(loaded_vault_inputs);
+    let (_, map, store) = advice_provider.into_parts();
 
     // parse transaction results
-    let tx_outputs = TransactionKernel::parse_transaction_outputs(&stack_outputs, &map.into())
-        .map_err(TransactionExecutorError::InvalidTransactionOutput)?;
+    let initial_account = tx_inputs.account();
+    let account_id = initial_account.id();
+
+    let tx_outputs =
+        kernel
+            .parse_transaction_outputs(&stack_outputs, &map.into())
+            .map_err(|source| TransactionExecutorError::InvalidTransactionOutput {
+                account_id,
+                source,
+            })?;
     let final_account = &tx_outputs.account;
 
-    let initial_account = tx_inputs.account();
+    #[cfg(feature = "tracing")]
+    _span.record("num_output_notes", tx_outputs.output_notes.num_notes());
+
+    // recover the full details of the output notes whose recipients were known to the host
+    let output_note_details = tx_outputs
+        .output_notes
+        .iter()
+        .filter_map(|note| output_note_tracker.build_note(note))
+        .collect::<Vec<_>>();
+
+    let (account_delta, account_details) =
+        build_account_delta(initial_account, final_account, &store, &storage_updates, vault_delta)?;
 
-    if initial_account.id() != final_account.id() {
+    Ok(ExecutedTransaction::new(
+        kernel.hash(),
+        tx_inputs,
+        tx_outputs,
+        account_delta,
+        account_details,
+        tx_script,
+        tx_script_args,
+        advice_witness,
+        measurements,
+    )
+    .with_output_note_details(output_note_details))
+}
+
+/// Returns the delta of the account against which a transaction was executed, together with the
+/// full final state of the account if the transaction created a new on-chain account.
+///
+/// The storage delta is extracted from the provided Merkle store, which is expected to contain the
+/// storage of the final account.
+///
+/// # Errors
+/// Returns an error if:
+/// - The ID of the final account differs from the ID of the initial account.
+/// - The code of an account with immutable code was updated.
+/// - The storage delta cannot be extracted, or the resulting account delta is invalid.
+fn build_account_delta(
+    initial_account: &Account,
+    final_account: &AccountHeader,
+    store: &MerkleStore,
+    storage_updates: &AccountStorageUpdates,
+    vault_delta: AccountVaultDelta,
+) -> Result<(AccountDelta, Option<Account>), TransactionExecutorError> {
+    let account_id = initial_account.id();
+
+    if account_id != final_account.id() {
         return Err(TransactionExecutorError::InconsistentAccountId {
-            input_id: initial_account.id(),
+            input_id: account_id,
             output_id: final_account.id(),
         });
     }
 
+    // make sure the account code was updated only if the account type allows code updates; the
+    // kernel enforces this as well, so this guards against hosts running a non-standard kernel
+    if final_account.code_commitment() != initial_account.code().root()
+        && !account_id.is_updatable_code()
+    {
+        return Err(TransactionExecutorError::ImmutableAccountCodeUpdated(account_id));
+    }
+
     // build account delta
 
-    // TODO: Fix delta extraction for new account creation
     // extract the account storage delta
-    let storage_delta = extract_account_storage_delta(&store, initial_account, final_account)
-        .map_err(TransactionExecutorError::InvalidTransactionOutput)?;
+    #[cfg(feature = "tracing")]
+    let _delta_span = tracing::info_span!(
+        "extract_account_delta",
+        num_updated_slots = storage_updates.slots.len()
+    )
+    .entered();
+
+    let storage_delta =
+        extract_account_storage_delta(store, initial_account, final_account, storage_updates)
+            .map_err(|source| TransactionExecutorError::ExtractAccountStorageDeltaFailed {
+                account_id,
+                source,
+            })?;
 
-    // extract the nonce delta
-    let nonce_delta = if initial_account.nonce() != final_account.nonce() {
-        Some(final_account.nonce())
+    // a new account is not recorded in the account database prior to the transaction, thus its
+    // delta must describe the full final state of the account rather than the changes made by
+    // the transaction to the initial state
+    let (account_delta, account_details) = if initial_account.is_new() {
+        let account =
+            build_final_account(initial_account, final_account, &storage_delta, &vault_delta)
+                .map_err(|source| TransactionExecutorError::InvalidTransactionOutput {
+                    account_id,
+                    source,
+                })?;
+        let account_delta = build_new_account_delta(&account).map_err(|source| {
+            TransactionExecutorError::InvalidAccountDelta { account_id, source }
+        })?;
+        let account_details = if account.is_on_chain() { Some(account) } else { None };
+
+        (account_delta, account_details)
     } else {
-        None
-    };
+        // extract the nonce delta
+        let nonce_delta = if initial_account.nonce() != final_account.nonce() {
+            Some(final_account.nonce())
+        } else {
+            None
+        };
 
-    // construct the account delta
-    let account_delta =
-        AccountDelta::new(storage_delta, vault_delta, nonce_delta).expect("invalid account delta");
+        // construct the account delta; this also makes sure that the delta is not larger than
+        // what batch and block producers accept
+        let account_delta =
+            AccountDelta::new(storage_delta, vault_delta, nonce_delta).map_err(|source| {
+                TransactionExecutorError::InvalidAccountDelta { account_id, source }
+            })?;
 
-    Ok(ExecutedTransaction::new(
-        program,
-        tx_inputs,
-        tx_outputs,
-        account_delta,
-        tx_script,
-        advice_witness,
-    ))
+        (account_delta, None)
+    };
+
+    Ok((account_delta, account_details))
 }
 
-/// Extracts account storage delta between the `initial_account` and `final_account_stub` from the
-/// provided `MerkleStore`
-fn extract_account_storage_delta(
+/// Extracts account storage delta between the `initial_account` and `final_account_header` from the
+/// provided `MerkleStore`.
+///
+/// Only the slots listed in `storage_updates` are inspected, as no other slots could have been
+/// changed during transaction execution. A slot which was written to is included into the delta
+/// only if its final value differs from its initial value.
+///
+/// Map entries are included with the last values written to them; if the contents of a map are
+/// tracked by the initial account storage, entries whose final value matches their initial value
+/// are omitted.
+pub(crate) fn extract_account_storage_delta(
     store: &MerkleStore,
     initial_account: &Account,
-    final_account_stub: &AccountStub,
+    final_account_header: &AccountHeader,
+    storage_updates: &AccountStorageUpdates,
 ) -> Result<AccountStorageDelta, TransactionOutputError> {
-    // extract storage slots delta
-    let tree_delta = merkle_tree_delta(
-        initial_account.storage().root(),
-        final_account_stub.storage_root(),
-        AccountStorage::STORAGE_TREE_DEPTH,
-        store,
-    )
-    .map_err(TransactionOutputError::ExtractAccountStorageSlotsDeltaFailed)?;
+    let final_root = final_account_header.storage_commitment();
+    let initial_storage = initial_account.storage();
 
-    // map tree delta to cleared/updated slots; we can cast indexes to u8 because the
-    // the number of storage slots cannot be greater than 256
-    let cleared_items = tree_delta.cleared_slots().iter().map(|idx| *idx as u8).collect();
-    let updated_items = tree_delta
-        .updated_slots()
-        .iter()
-        .map(|(idx, value)| (*idx as u8, *value))
-        .collect();
+    let mut cleared_items = Vec::new();
+    let mut updated_items = Vec::new();
+    for &slot_idx in storage_updates.slots.iter() {
+        let index = NodeIndex::new(AccountStorage::STORAGE_TREE_DEPTH, slot_idx as u64)
+            .expect("storage slot index is valid");
+        let final_value = store
+            .get_node(final_root, index)
+            .map_err(TransactionOutputError::ExtractAccountStorageSlotsDeltaFailed)?;
+
+        if final_value == initial_storage.get_item(slot_idx) {
+            continue;
+        }
+
+        if final_value == Digest::default() {
+            cleared_items.push(slot_idx);
+        } else {
+            updated_items.push((slot_idx, final_value.into()));
+        }
+    }
+
+    let mut updated_maps = Vec::new();
+    for (&slot_idx, items) in storage_updates.map_items.iter() {
+        let mut map_delta = StorageMapDelta::default();
+        for (&key, &value) in items.iter() {
+            if initial_storage.get_map_item(slot_idx, key).ok() == Some(value) {
+                continue;
+            }
+
+            if value == Word::default() {
+                map_delta.cleared_leaves.push(key);
+            } else {
+                map_delta.updated_leaves.push((key, value));
+            }
+        }
+
+        if !map_delta.is_empty() {
+            updated_maps.push((slot_idx, map_delta));
+        }
+    }
 
     // construct storage delta
-    let storage_delta = AccountStorageDelta { cleared_items, updated_items };
+    let storage_delta = AccountStorageDelta {
+        cleared_items,
+        updated_items,
+        updated_maps,
+    };
 
     Ok(storage_delta)
 }
+
+/// Returns the full state of the account after the transaction was executed if the transaction
+/// created a new on-chain account, or None otherwise.
+///
+/// The state is built by applying the provided storage and vault deltas to the initial account.
+///
+/// # Errors
+/// Returns an error if the vault delta cannot be applied to the initial account vault, or if the
+/// hash of the resulting account is not the final account hash.
+pub(crate) fn build_account_details(
+    initial_account: &Account,
+    final_account_header: &AccountHeader,
+    storage_delta: &AccountStorageDelta,
+    vault_delta: &AccountVaultDelta,
+) -> Result<Option<Account>, TransactionOutputError> {
+    if !initial_account.is_new() || !initial_account.is_on_chain() {
+        return Ok(None);
+    }
+
+    build_final_account(initial_account, final_account_header, storage_delta, vault_delta).map(Some)
+}
+
+/// Returns the state of the account after the transaction was executed, built by applying the
+/// provided storage and vault deltas to the initial account.
+///
+/// Map entry updates are applied to the maps whose contents are tracked by the initial account
+/// storage, so that the final storage keeps tracking them.
+///
+/// # Errors
+/// Returns an error if the storage or vault deltas cannot be applied to the initial account, or
+/// if the hash of the resulting account is not the final account hash.
+fn build_final_account(
+    initial_account: &Account,
+    final_account_header: &AccountHeader,
+    storage_delta: &AccountStorageDelta,
+    vault_delta: &AccountVaultDelta,
+) -> Result<Account, TransactionOutputError> {
+    let mut storage = initial_account.storage().clone();
+    storage
+        .apply_delta(storage_delta)
+        .map_err(TransactionOutputError::FinalAccountStorageDeltaInvalid)?;
+
+    let mut vault = initial_account.vault().clone();
+    vault
+        .apply_delta(vault_delta)
+        .map_err(TransactionOutputError::FinalAccountVaultDeltaInvalid)?;
+
+    let account = Account::new(
+        initial_account.id(),
+        vault,
+        storage,
+        initial_account.code().clone(),
+        final_account_header.nonce(),
+    );
+
+    if account.hash() != final_account_header.hash() {
+        return Err(TransactionOutputError::FinalAccountStateInconsistent(
+            final_account_header.hash(),
+            account.hash(),
+        ));
+    }
+
+    Ok(account)
+}
+
+/// Returns the delta of a transaction which created the provided account.
+///
+/// The delta is computed relative to an empty account: all non-empty storage slots are included
+/// as updated items, all assets of the account vault are included as added assets, and the nonce
+/// is set to the nonce of the account. The entries of the maps tracked by the account storage are
+/// included as updated map entries. This allows applying the delta of account-creating
+/// transactions in the same way as the delta of any other transaction. The nonce is included even
+/// if the account has neither storage items nor assets, as the nonce of a new account is always
+/// set by the transaction which creates it.
+///
+/// # Errors
+/// Returns an error if the resulting delta is invalid (e.g., because it contains more entries
+/// than batch and block producers accept).
+pub(crate) fn build_new_account_delta(
+    account: &Account,
+) -> Result<AccountDelta, AccountDeltaError> {
+    let updated_items = account
+        .storage()
+        .iter()
+        .filter(|(_, value, _)| *value != Word::default())
+        .map(|(slot_idx, value, _)| (slot_idx, value))
+        .collect::<Vec<_>>();
+    let updated_maps = account
+        .storage()
+        .maps()
+        .iter()
+        .filter(|(_, map)| map.iter().next().is_some())
+        .map(|(&slot_idx, map)| {
+            let updated_leaves = map.iter().map(|(key, value)| (*key, *value)).collect();
+            (
+                slot_idx,
+                StorageMapDelta {
+                    cleared_leaves: Vec::new(),
+                    updated_leaves,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    let storage_delta = AccountStorageDelta {
+        cleared_items: Vec::new(),
+        updated_items,
+        updated_maps,
+    };
+    let mut vault_delta = AccountVaultDelta::default();
+    for asset in account.vault().assets() {
+        match asset {
+            Asset::Fungible(asset) => vault_delta.added_assets.push(asset),
+            Asset::NonFungible(asset) => vault_delta.added_nfts.push(asset),
+        }
+    }
+
+    AccountDelta::new(storage_delta, vault_delta, Some(account.nonce()))
+}
@@ -0,0 +1,94 @@
+use miden_objects::{
+    accounts::{AccountDelta, AccountHeader, AccountId},
+    transaction::{OutputNotes, TransactionFee, TransactionMeasurements},
+};
+
+#[cfg(doc)]
+use super::TransactionExecutor;
+#[cfg(doc)]
+use crate::ExecutedTransaction;
+
+// TRANSACTION SIMULATION
+// ================================================================================================
+
+/// The projected effects of a transaction, produced by
+/// [TransactionExecutor::simulate_transaction()].
+///
+/// A simulation describes the changes the transaction would make to the account and the notes it
+/// would create, but unlike an [ExecutedTransaction] it carries neither the transaction program
+/// nor the advice witness, and thus cannot be proven. To prove the transaction, it must be
+/// executed via [TransactionExecutor::execute_transaction()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionSimulation {
+    account_id: AccountId,
+    block_ref: u32,
+    final_account: AccountHeader,
+    account_delta: AccountDelta,
+    output_notes: OutputNotes,
+    fee: Option<TransactionFee>,
+    measurements: TransactionMeasurements,
+}
+
+impl TransactionSimulation {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [TransactionSimulation] instantiated from the provided data.
+    pub(crate) fn new(
+        block_ref: u32,
+        final_account: AccountHeader,
+        account_delta: AccountDelta,
+        output_notes: OutputNotes,
+        fee: Option<TransactionFee>,
+        measurements: TransactionMeasurements,
+    ) -> Self {
+        Self {
+            account_id: final_account.id(),
+            block_ref,
+            final_account,
+            account_delta,
+            output_notes,
+            fee,
+            measurements,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the account against which the transaction was simulated.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the number of the reference block of the simulated transaction.
+    pub fn block_ref(&self) -> u32 {
+        self.block_ref
+    }
+
+    /// Returns the header of the account after the transaction.
+    pub fn final_account(&self) -> &AccountHeader {
+        &self.final_account
+    }
+
+    /// Returns the changes the transaction would make to the account.
+    pub fn account_delta(&self) -> &AccountDelta {
+        &self.account_delta
+    }
+
+    /// Returns the notes the transaction would create.
+    pub fn output_notes(&self) -> &OutputNotes {
+        &self.output_notes
+    }
+
+    /// Returns the fee the transaction would be charged by the transaction kernel, or None if the
+    /// transaction was simulated without fee parameters.
+    pub fn fee(&self) -> Option<TransactionFee> {
+        self.fee
+    }
+
+    /// Returns the resources consumed by executing the transaction program (e.g., the number of
+    /// VM cycles).
+    pub fn measurements(&self) -> TransactionMeasurements {
+        self.measurements
+    }
+}
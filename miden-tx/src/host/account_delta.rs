@@ -1,8 +1,8 @@
 use miden_objects::{
     accounts::{AccountId, AccountVaultDelta},
     assets::{Asset, FungibleAsset, NonFungibleAsset},
-    utils::collections::{btree_map::Entry, BTreeMap},
-    Digest,
+    utils::collections::{btree_map::Entry, BTreeMap, BTreeSet, Vec},
+    Digest, Word,
 };
 use vm_processor::{ExecutionError, ProcessState};
 
@@ -17,8 +17,9 @@ use vm_processor::{ExecutionError, ProcessState};
 ///   faucet ID of the asset, and the value is the amount of the asset being added or removed from
 ///   the vault (positive value for added assets, negative value for removed assets).
 /// - Non-fungible asset map: tracks changes to the vault's non-fungible assets, where the key is
-///   the non-fungible asset, and the value is either 1 or -1 depending on whether the asset is
-///   being added or removed from the vault.
+///   the full word of the non-fungible asset, and the value is either 1 or -1 depending on whether
+///   the asset is being added or removed from the vault. An asset which is added and later
+///   removed (or vice versa) within the same transaction does not appear in the delta.
 #[derive(Default, Debug)]
 pub struct AccountVaultDeltaTracker {
     fungible_assets: BTreeMap<AccountId, i128>,
@@ -55,8 +56,7 @@ impl AccountVaultDeltaTracker {
     }
 
     /// Extracts the asset that is being removed from the account's vault from the process state
-    /// and updates the appropriate [AccountVaultDeltaHandler::fungible_assets] or
-    /// [AccountVaultDeltaHandler::non_fungible_assets] map.
+    /// and updates the appropriate fungible or non-fungible asset map.
     pub fn remove_asset<S: ProcessState>(&mut self, process: &S) -> Result<(), ExecutionError> {
         let asset: Asset = process.get_stack_word(0).try_into().map_err(|err| {
             ExecutionError::EventError(format!(
@@ -85,28 +85,33 @@ impl AccountVaultDeltaTracker {
 
     /// Consumes this delta tracker and returns the [AccountVaultDelta] that represents the changes
     /// to the account's vault.
+    ///
+    /// Non-fungible assets are reported via the dedicated [AccountVaultDelta::added_nfts] and
+    /// [AccountVaultDelta::removed_nfts] vectors, ordered by their vault keys.
     pub fn into_vault_delta(self) -> AccountVaultDelta {
         let mut added_assets = Vec::new();
         let mut removed_assets = Vec::new();
+        let mut added_nfts = Vec::new();
+        let mut removed_nfts = Vec::new();
 
         // process fungible assets
         for (faucet_id, amount) in self.fungible_assets {
             if amount > 0 {
-                added_assets.push(Asset::Fungible(
+                added_assets.push(
                     FungibleAsset::new(
                         AccountId::new_unchecked(faucet_id.into()),
                         amount.unsigned_abs() as u64,
                     )
                     .expect("fungible asset is well formed"),
-                ));
+                );
             } else {
-                removed_assets.push(Asset::Fungible(
+                removed_assets.push(
                     FungibleAsset::new(
                         AccountId::new_unchecked(faucet_id.into()),
                         amount.unsigned_abs() as u64,
                     )
                     .expect("fungible asset is well formed"),
-                ));
+                );
             }
         }
 
@@ -114,21 +119,111 @@ impl AccountVaultDeltaTracker {
         for (non_fungible_asset, amount) in self.non_fungible_assets {
             match amount {
                 1 => {
-                    added_assets.push(Asset::NonFungible(unsafe {
-                        NonFungibleAsset::new_unchecked(*non_fungible_asset)
-                    }));
+                    added_nfts
+                        .push(unsafe { NonFungibleAsset::new_unchecked(*non_fungible_asset) });
                 },
                 -1 => {
-                    removed_assets.push(Asset::NonFungible(unsafe {
-                        NonFungibleAsset::new_unchecked(*non_fungible_asset)
-                    }));
+                    removed_nfts
+                        .push(unsafe { NonFungibleAsset::new_unchecked(*non_fungible_asset) });
                 },
                 _ => unreachable!("non-fungible asset amount must be 1 or -1"),
             }
         }
 
-        AccountVaultDelta { added_assets, removed_assets }
+        AccountVaultDelta {
+            added_assets,
+            removed_assets,
+            added_nfts,
+            removed_nfts,
+        }
+    }
+}
+
+// ACCOUNT STORAGE DELTA TRACKER
+// ================================================================================================
+
+/// The account storage delta tracker is responsible for tracking which storage slots of the
+/// account the transaction is being executed against were written to.
+///
+/// The tracker records only the indexes of the written slots; it does not record the written
+/// values, as a slot may be written multiple times and only the final value is relevant. Thus,
+/// the storage delta is computed after the transaction has been executed by comparing the
+/// initial and final values of the written slots only, instead of comparing the entire storage
+/// trees.
+///
+/// Updates of map entries are recorded together with the written values, keyed by the index of
+/// the slot storing the map and the key of the entry; if an entry is written multiple times, only
+/// the last value is retained.
+#[derive(Default, Debug)]
+pub struct AccountStorageDeltaTracker {
+    updated_slots: BTreeSet<u8>,
+    updated_map_items: BTreeMap<u8, BTreeMap<Digest, Word>>,
+}
+
+impl AccountStorageDeltaTracker {
+    // MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Extracts the index of the storage slot which is being set from the process state and adds
+    /// it to the set of updated slots.
+    pub fn set_item<S: ProcessState>(&mut self, process: &S) -> Result<(), ExecutionError> {
+        // the storage tree has depth 8, so any valid slot index fits into a u8
+        let index = process.get_stack_item(0);
+        let index = u8::try_from(index.as_int()).map_err(|_| {
+            ExecutionError::EventError(format!(
+                "Failed to track account storage update - slot index {index} is invalid"
+            ))
+        })?;
+        self.updated_slots.insert(index);
+        Ok(())
     }
+
+    /// Extracts the index of the storage slot storing the map, and the key and the new value of
+    /// the map entry which is being set from the process state, and records the new value of the
+    /// entry.
+    pub fn set_map_item<S: ProcessState>(&mut self, process: &S) -> Result<(), ExecutionError> {
+        let index = process.get_stack_item(0);
+        let index = u8::try_from(index.as_int()).map_err(|_| {
+            ExecutionError::EventError(format!(
+                "Failed to track account storage map update - slot index {index} is invalid"
+            ))
+        })?;
+        let word = |pos: usize| -> Word {
+            [
+                process.get_stack_item(pos + 3),
+                process.get_stack_item(pos + 2),
+                process.get_stack_item(pos + 1),
+                process.get_stack_item(pos),
+            ]
+        };
+
+        self.updated_map_items.entry(index).or_default().insert(word(1).into(), word(5));
+        Ok(())
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Consumes this tracker and returns the recorded storage updates.
+    pub fn into_updates(self) -> AccountStorageUpdates {
+        AccountStorageUpdates {
+            slots: self.updated_slots.into_iter().collect(),
+            map_items: self.updated_map_items,
+        }
+    }
+}
+
+// ACCOUNT STORAGE UPDATES
+// ================================================================================================
+
+/// Updates of the account storage recorded during transaction execution.
+#[derive(Default, Debug)]
+pub struct AccountStorageUpdates {
+    /// Indexes of the storage slots written to, in ascending order.
+    pub slots: Vec<u8>,
+    /// Last values written to map entries, keyed by the index of the slot storing the map and the
+    /// key of the entry.
+    pub map_items: BTreeMap<u8, BTreeMap<Digest, Word>>,
 }
 
 // HELPER FUNCTIONS
@@ -1,13 +1,15 @@
 use miden_lib::transaction::TransactionKernelError;
 use miden_objects::accounts::AccountCode;
 
-use super::{AdviceProvider, BTreeMap, Digest, NodeIndex, ProcessState};
+use super::{AdviceProvider, BTreeMap, Digest, Felt, NodeIndex, ProcessState};
 
 // ACCOUNT PROCEDURE INDEX MAP
 // ================================================================================================
 
-/// A map of proc_root |-> proc_index for all known procedures of an account interface.
-pub struct AccountProcedureIndexMap(BTreeMap<Digest, u8>);
+/// A map of proc_root |-> (proc_index, proc_access) for all known procedures of an account
+/// interface, where proc_access is the access level of the procedure committed to by the account
+/// code root.
+pub struct AccountProcedureIndexMap(BTreeMap<Digest, (u8, Felt)>);
 
 impl AccountProcedureIndexMap {
     /// Returns a new [AccountProcedureIndexMap] instantiated with account procedures present in
@@ -19,26 +21,31 @@ impl AccountProcedureIndexMap {
         // get the Merkle store with the procedure tree from the advice provider
         let proc_store = adv_provider.get_store_subset([account_code_root].iter());
 
-        // iterate over all possible procedure indexes
+        // iterate over all possible procedure indexes; the MAST root and the access level of the
+        // procedure at index i are the leaves 2i and 2i + 1 of the procedure tree
         let mut result = BTreeMap::new();
         for i in 0..AccountCode::MAX_NUM_PROCEDURES {
-            let index = NodeIndex::new(AccountCode::PROCEDURE_TREE_DEPTH, i as u64)
+            let root_index = NodeIndex::new(AccountCode::PROCEDURE_LEAF_DEPTH, 2 * i as u64)
+                .expect("procedure tree index is valid");
+            let access_index = NodeIndex::new(AccountCode::PROCEDURE_LEAF_DEPTH, 2 * i as u64 + 1)
                 .expect("procedure tree index is valid");
             // if the node at the current index does not exist, skip it and try the next node;this
             // situation is valid if not all account procedures are loaded into the advice provider
-            if let Ok(proc_root) = proc_store.get_node(account_code_root, index) {
+            if let Ok(proc_root) = proc_store.get_node(account_code_root, root_index) {
                 // if we got an empty digest, this means we got to the end of the procedure list
                 if proc_root == Digest::default() {
                     break;
                 }
-                result.insert(proc_root, i as u8);
+                if let Ok(access) = proc_store.get_node(account_code_root, access_index) {
+                    result.insert(proc_root, (i as u8, access[0]));
+                }
             }
         }
         Self(result)
     }
 
-    /// Returns index of the procedure whose root is currently at the top of the operand stack in
-    /// the provided process.
+    /// Returns index and access level of the procedure whose root is currently at the top of the
+    /// operand stack in the provided process.
     ///
     /// # Errors
     /// Returns an error if the procedure at the top of the operand stack is not present in this
@@ -46,7 +53,7 @@ impl AccountProcedureIndexMap {
     pub fn get_proc_index<S: ProcessState>(
         &self,
         process: &S,
-    ) -> Result<u8, TransactionKernelError> {
+    ) -> Result<(u8, Felt), TransactionKernelError> {
         let proc_root = process.get_stack_word(0).into();
         self.0
             .get(&proc_root)
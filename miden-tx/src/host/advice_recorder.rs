@@ -0,0 +1,300 @@
+use core::{borrow::Borrow, cell::RefCell};
+
+use miden_objects::{
+    crypto::merkle::{InnerNodeInfo, MerklePath, MerkleStore},
+    transaction::read_advice_inputs,
+    utils::{
+        collections::{BTreeMap, BTreeSet, Vec},
+        serde::{ByteReader, ByteWriter, DeserializationError, Serializable},
+    },
+    vm::AdviceInputs,
+    Digest, Felt, Word,
+};
+use vm_processor::{
+    crypto::NodeIndex, AdviceProvider, AdviceSource, ExecutionError, MemAdviceProvider,
+    ProcessState, SignatureKind,
+};
+
+// COMPACT ADVICE RECORDER
+// ================================================================================================
+
+/// An advice provider which records only the advice data accessed during program execution.
+///
+/// Unlike [RecAdviceProvider](vm_processor::RecAdviceProvider), this recorder does not keep a
+/// copy of every value it has served:
+/// - Advice map entries are recorded once per key, with the values read when the key was first
+///   accessed. Entries inserted by the program itself are not recorded, as they are re-inserted
+///   when the program is executed from the witness. Thus, overwriting a key after it was read
+///   does not change the recorded values.
+/// - Merkle store nodes are recorded as (root, index) pairs, and the witness contains the union of
+///   the corresponding authentication paths, so nodes shared between paths are stored only once.
+///   Since the Merkle store is content-addressed, the paths are reconstructed from the wrapped
+///   provider on demand.
+///
+/// The recorded witness can either be materialized via [CompactAdviceRecorder::finalize()], or
+/// streamed into a writer via [CompactAdviceRecorder::write_witness_into()] without building an
+/// intermediate [AdviceInputs] instance.
+pub struct CompactAdviceRecorder {
+    provider: MemAdviceProvider,
+    init_stack: Vec<Felt>,
+    map_entries: RefCell<BTreeMap<[u8; 32], Vec<Felt>>>,
+    inserted_keys: BTreeSet<[u8; 32]>,
+    nodes: RefCell<BTreeSet<(Digest, NodeIndex)>>,
+}
+
+impl CompactAdviceRecorder {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of distinct advice map keys accessed so far.
+    pub fn num_recorded_keys(&self) -> usize {
+        self.map_entries.borrow().len()
+    }
+
+    /// Returns the number of distinct Merkle store nodes accessed so far.
+    pub fn num_recorded_nodes(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    /// Writes the advice witness recorded so far into the specified target.
+    ///
+    /// The witness is written in the format expected by [read_advice_witness()]:
+    /// - The initial advice stack.
+    /// - The accessed advice map entries.
+    /// - The deduplicated inner nodes of the accessed Merkle paths.
+    pub fn write_witness_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.init_stack.len() as u64);
+        self.init_stack.iter().for_each(|value| value.write_into(target));
+
+        let map_entries = self.map_entries.borrow();
+        target.write_u64(map_entries.len() as u64);
+        for (key, values) in map_entries.iter() {
+            target.write_bytes(key);
+            target.write_u64(values.len() as u64);
+            values.iter().for_each(|value| value.write_into(target));
+        }
+
+        let nodes = self.recorded_inner_nodes();
+        target.write_u64(nodes.len() as u64);
+        for node in nodes.values() {
+            node.value.write_into(target);
+            node.left.write_into(target);
+            node.right.write_into(target);
+        }
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Consumes this recorder and returns the recorded advice witness together with the wrapped
+    /// advice provider.
+    ///
+    /// The returned provider contains the full state of the advice provider at the end of the
+    /// execution, and can be used to read outputs produced by the program.
+    pub fn finalize(self) -> (AdviceInputs, MemAdviceProvider) {
+        let nodes = self.recorded_inner_nodes();
+        let map_entries = self.map_entries.into_inner();
+
+        let mut witness = AdviceInputs::default().with_stack(self.init_stack).with_map(map_entries);
+        witness.extend_merkle_store(nodes.into_values());
+
+        (witness, self.provider)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the values currently stored under the specified key in the advice map of the
+    /// wrapped provider, unless the key has already been recorded or was inserted by the program.
+    fn record_map_entry(&self, key: [u8; 32]) {
+        if self.inserted_keys.contains(&key) || self.map_entries.borrow().contains_key(&key) {
+            return;
+        }
+        if let Some(values) = self.provider.get_mapped_values(&key) {
+            self.map_entries.borrow_mut().insert(key, values.to_vec());
+        }
+    }
+
+    /// Returns the inner nodes of all recorded Merkle paths keyed by node value.
+    ///
+    /// Since the Merkle store of the wrapped provider is never pruned, the paths for all recorded
+    /// nodes are still available after execution, including the paths in the trees which were
+    /// created during execution.
+    fn recorded_inner_nodes(&self) -> BTreeMap<Digest, InnerNodeInfo> {
+        let mut result = BTreeMap::new();
+        for (root, index) in self.nodes.borrow().iter() {
+            let depth = Felt::from(index.depth());
+            let position = Felt::new(index.value());
+            let value = self.provider.get_tree_node((*root).into(), &depth, &position);
+            let path = self.provider.get_merkle_path((*root).into(), &depth, &position);
+            if let (Ok(value), Ok(path)) = (value, path) {
+                let nodes = path
+                    .inner_nodes(index.value(), value.into())
+                    .expect("path from the advice provider must be valid");
+                for node in nodes {
+                    result.insert(node.value, node);
+                }
+            }
+        }
+        result
+    }
+
+    /// Records that the node at the specified index of the tree with the specified root was
+    /// accessed.
+    fn record_node(&self, root: Word, depth: &Felt, index: &Felt) {
+        if let Ok(index) = NodeIndex::from_elements(depth, index) {
+            self.nodes.borrow_mut().insert((root.into(), index));
+        }
+    }
+}
+
+impl From<AdviceInputs> for CompactAdviceRecorder {
+    fn from(inputs: AdviceInputs) -> Self {
+        let init_stack = inputs.stack().to_vec();
+        Self {
+            provider: MemAdviceProvider::from(inputs),
+            init_stack,
+            map_entries: RefCell::new(BTreeMap::new()),
+            inserted_keys: BTreeSet::new(),
+            nodes: RefCell::new(BTreeSet::new()),
+        }
+    }
+}
+
+impl AdviceProvider for CompactAdviceRecorder {
+    // ADVICE STACK
+    // --------------------------------------------------------------------------------------------
+
+    fn pop_stack<S: ProcessState>(&mut self, process: &S) -> Result<Felt, ExecutionError> {
+        self.provider.pop_stack(process)
+    }
+
+    fn pop_stack_word<S: ProcessState>(&mut self, process: &S) -> Result<Word, ExecutionError> {
+        self.provider.pop_stack_word(process)
+    }
+
+    fn pop_stack_dword<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<[Word; 2], ExecutionError> {
+        self.provider.pop_stack_dword(process)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        if let AdviceSource::Map { key, .. } = source {
+            self.record_map_entry(Digest::from(key).as_bytes());
+        }
+        self.provider.push_stack(source)
+    }
+
+    // ADVICE MAP
+    // --------------------------------------------------------------------------------------------
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) -> Result<(), ExecutionError> {
+        self.inserted_keys.insert(Digest::from(key).as_bytes());
+        self.provider.insert_into_map(key, values)
+    }
+
+    fn get_signature(
+        &self,
+        kind: SignatureKind,
+        pub_key: Word,
+        msg: Word,
+    ) -> Result<Vec<Felt>, ExecutionError> {
+        // signatures are generated from the secret key stored in the advice map under the public
+        // key; thus, the secret key must be a part of the witness
+        self.record_map_entry(Digest::from(pub_key).as_bytes());
+        self.provider.get_signature(kind, pub_key, msg)
+    }
+
+    fn get_mapped_values(&self, key: &[u8; 32]) -> Option<&[Felt]> {
+        self.record_map_entry(*key);
+        self.provider.get_mapped_values(key)
+    }
+
+    // MERKLE STORE
+    // --------------------------------------------------------------------------------------------
+
+    fn get_tree_node(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<Word, ExecutionError> {
+        let node = self.provider.get_tree_node(root, depth, index)?;
+        self.record_node(root, depth, index);
+        Ok(node)
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<MerklePath, ExecutionError> {
+        let path = self.provider.get_merkle_path(root, depth, index)?;
+        self.record_node(root, depth, index);
+        Ok(path)
+    }
+
+    fn get_leaf_depth(
+        &self,
+        root: Word,
+        tree_depth: &Felt,
+        index: &Felt,
+    ) -> Result<u8, ExecutionError> {
+        let leaf_depth = self.provider.get_leaf_depth(root, tree_depth, index)?;
+        let leaf_index = index.as_int() >> (tree_depth.as_int() - leaf_depth as u64);
+        self.record_node(root, &Felt::from(leaf_depth), &Felt::new(leaf_index));
+        Ok(leaf_depth)
+    }
+
+    fn find_lone_leaf(
+        &self,
+        root: Word,
+        root_index: NodeIndex,
+        tree_depth: u8,
+    ) -> Result<Option<(NodeIndex, Word)>, ExecutionError> {
+        let leaf = self.provider.find_lone_leaf(root, root_index, tree_depth)?;
+        let index = leaf.map(|(index, _)| index).unwrap_or(root_index);
+        self.nodes.borrow_mut().insert((root.into(), index));
+        Ok(leaf)
+    }
+
+    fn update_merkle_node(
+        &mut self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+        value: Word,
+    ) -> Result<(MerklePath, Word), ExecutionError> {
+        self.record_node(root, depth, index);
+        self.provider.update_merkle_node(root, depth, index, value)
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_roots(lhs, rhs)
+    }
+
+    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+    where
+        I: Iterator<Item = R>,
+        R: Borrow<Digest>,
+    {
+        self.provider.get_store_subset(roots)
+    }
+}
+
+// WITNESS DESERIALIZATION
+// ================================================================================================
+
+/// Reads an advice witness written via [CompactAdviceRecorder::write_witness_into()] from the
+/// specified source.
+///
+/// # Errors
+/// Returns an error if the source does not contain a valid advice witness.
+pub fn read_advice_witness<R: ByteReader>(
+    source: &mut R,
+) -> Result<AdviceInputs, DeserializationError> {
+    read_advice_inputs(source)
+}
@@ -0,0 +1,177 @@
+use core::fmt;
+
+use miden_lib::transaction::{EventId, EventNamespace, TransactionEvent, TransactionEventPayload};
+use miden_objects::{
+    utils::{collections::BTreeMap, string::String, sync::Arc},
+    Felt,
+};
+use vm_processor::{ExecutionError, ProcessState};
+
+use crate::EventRegistrationError;
+
+// EVENT HANDLER
+// ================================================================================================
+
+/// The [EventHandler] trait defines the interface used by the transaction host to dispatch events
+/// emitted by account components and note scripts outside of the transaction kernel namespace.
+///
+/// Handlers observe the state of the VM at the time the event was emitted; they cannot inject
+/// data into the execution. This guarantees that a transaction executed with handlers can be
+/// proven by a prover which does not have them.
+///
+/// Handlers must be [Send] and [Sync] so that executors holding them can be shared across
+/// threads.
+pub trait EventHandler: Send + Sync {
+    /// Handles the specified event emitted by a process in the specified state.
+    ///
+    /// # Errors
+    /// Returning an error aborts the execution of the transaction.
+    fn on_event(&self, event_id: EventId, process: &dyn ProcessState)
+        -> Result<(), ExecutionError>;
+
+    /// Returns a human-readable description of the specified event (e.g., for execution logs of
+    /// dApps) decoded from the state of the operand stack at the time the event was emitted
+    /// (ordered from the top of the stack), or None if the event is not recognized.
+    ///
+    /// Descriptions are only requested when a
+    /// [TransactionTraceHandler](super::TransactionTraceHandler) is attached to the host. By
+    /// default, events are not decoded.
+    fn decode(&self, event_id: EventId, stack: &[Felt]) -> Option<String> {
+        let _ = (event_id, stack);
+        None
+    }
+}
+
+// EVENT HANDLERS
+// ================================================================================================
+
+/// A set of [EventHandler]s keyed by the [EventNamespace] of the events they handle.
+///
+/// Every namespace can have at most one handler; this allows detecting event ID collisions
+/// between account components when the handlers of several components are composed via
+/// [EventHandlers::merge()].
+#[derive(Clone, Default)]
+pub struct EventHandlers {
+    handlers: BTreeMap<EventNamespace, Arc<dyn EventHandler>>,
+}
+
+impl EventHandlers {
+    /// Returns a new empty [EventHandlers] set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the handler for the events in the specified namespace.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The namespace is the transaction kernel namespace, which is handled by the host itself.
+    /// - A handler for the namespace has already been registered.
+    pub fn register(
+        &mut self,
+        namespace: EventNamespace,
+        handler: Arc<dyn EventHandler>,
+    ) -> Result<(), EventRegistrationError> {
+        if namespace == EventNamespace::Kernel {
+            return Err(EventRegistrationError::ReservedNamespace(namespace));
+        }
+        if self.handlers.contains_key(&namespace) {
+            return Err(EventRegistrationError::NamespaceCollision(namespace));
+        }
+        self.handlers.insert(namespace, handler);
+        Ok(())
+    }
+
+    /// Moves all handlers of `other` into this set.
+    ///
+    /// # Errors
+    /// Returns an error if both sets contain a handler for the same namespace; in this case, this
+    /// set is left unchanged.
+    pub fn merge(&mut self, other: EventHandlers) -> Result<(), EventRegistrationError> {
+        if let Some(namespace) = other.handlers.keys().find(|ns| self.handlers.contains_key(ns)) {
+            return Err(EventRegistrationError::NamespaceCollision(*namespace));
+        }
+        self.handlers.extend(other.handlers);
+        Ok(())
+    }
+
+    /// Returns the handler registered for the specified namespace, if any.
+    pub fn get(&self, namespace: EventNamespace) -> Option<&Arc<dyn EventHandler>> {
+        self.handlers.get(&namespace)
+    }
+
+    /// Returns an iterator over the namespaces which have a registered handler.
+    pub fn namespaces(&self) -> impl Iterator<Item = EventNamespace> + '_ {
+        self.handlers.keys().copied()
+    }
+
+    /// Returns true if no handlers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Decodes the specified event from the state of the operand stack at the time the event was
+    /// emitted (ordered from the top of the stack).
+    ///
+    /// Events in the transaction kernel namespace are decoded via
+    /// [TransactionEvent::decode_payload()], and all other events via
+    /// [EventHandler::decode()] of the handler registered for their namespace. Kernel events with
+    /// a malformed payload, as well as events which no handler recognizes, are returned as
+    /// [DecodedEvent::Undecoded].
+    pub fn decode(&self, event_id: EventId, stack: &[Felt]) -> DecodedEvent {
+        if event_id.namespace() == EventNamespace::Kernel {
+            return TransactionEvent::try_from(event_id.as_u32())
+                .and_then(|event| {
+                    let payload = event.decode_payload(stack)?;
+                    Ok(DecodedEvent::Kernel { event, payload })
+                })
+                .unwrap_or(DecodedEvent::Undecoded(event_id));
+        }
+
+        self.get(event_id.namespace())
+            .and_then(|handler| handler.decode(event_id, stack))
+            .map(|description| DecodedEvent::User { event_id, description })
+            .unwrap_or(DecodedEvent::Undecoded(event_id))
+    }
+}
+
+// DECODED EVENT
+// ================================================================================================
+
+/// An event emitted during the execution of a transaction together with the data it carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedEvent {
+    /// An event emitted by the transaction kernel and its payload.
+    Kernel {
+        event: TransactionEvent,
+        payload: TransactionEventPayload,
+    },
+    /// An event outside of the transaction kernel namespace described by its [EventHandler].
+    User { event_id: EventId, description: String },
+    /// An event which could not be decoded.
+    Undecoded(EventId),
+}
+
+impl DecodedEvent {
+    /// Returns the ID of the decoded event.
+    pub fn event_id(&self) -> EventId {
+        match self {
+            Self::Kernel { event, .. } => EventId::from(event.clone()),
+            Self::User { event_id, .. } | Self::Undecoded(event_id) => *event_id,
+        }
+    }
+}
+
+impl fmt::Display for DecodedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Kernel {
+                event,
+                payload: TransactionEventPayload::None,
+            } => write!(f, "{event}"),
+            Self::Kernel { event, payload } => write!(f, "{event}: {payload}"),
+            Self::User { event_id, description } => write!(f, "{event_id}: {description}"),
+            Self::Undecoded(event_id) => write!(f, "{event_id}"),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use core::{borrow::Borrow, cell::RefCell};
+
+use miden_objects::{
+    crypto::merkle::{MerklePath, MerkleStore},
+    utils::{
+        collections::{BTreeMap, BTreeSet, Vec},
+        string::ToString,
+    },
+    vm::AdviceInputs,
+    Digest, Felt, Word,
+};
+use vm_processor::{
+    crypto::NodeIndex, AdviceProvider, AdviceSource, ExecutionError, ProcessState, SignatureKind,
+};
+
+// LAZY ADVICE PROVIDER
+// ================================================================================================
+
+/// An advice provider which keeps the Merkle data of the account vault outside of the wrapped
+/// advice provider and loads it only when the data is requested by the transaction kernel.
+///
+/// All requests are forwarded to the wrapped advice provider, except for the Merkle store and
+/// advice map requests which reference the account vault. These are served from the lazy source
+/// data, and every Merkle path and advice map entry served in this way is recorded. Thus, once the
+/// transaction has been executed, [LazyAdviceProvider::into_parts()] returns only the vault data
+/// which was actually touched by the transaction, rather than the data for the entire vault.
+///
+/// Updates of the account vault tree performed by the kernel are applied to the lazy source data,
+/// and the roots of the updated trees are tracked so that subsequent requests against them are
+/// routed to the lazy source as well.
+pub struct LazyAdviceProvider<A> {
+    inner: A,
+    source_store: MerkleStore,
+    source_map: BTreeMap<[u8; 32], Vec<Felt>>,
+    source_roots: BTreeSet<Digest>,
+    loaded_nodes: RefCell<BTreeSet<(Digest, NodeIndex)>>,
+    loaded_keys: RefCell<BTreeSet<[u8; 32]>>,
+}
+
+impl<A: AdviceProvider> LazyAdviceProvider<A> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [LazyAdviceProvider] which wraps the provided advice provider and loads data
+    /// of the account vault with the specified root from `vault_inputs` on demand.
+    ///
+    /// `vault_inputs` is expected to contain the inner nodes of the vault tree in its Merkle
+    /// store and the leaf entries of the vault tree in its advice map.
+    pub fn new(inner: A, vault_root: Digest, vault_inputs: AdviceInputs) -> Self {
+        let (_, source_map, source_store) = vault_inputs.into_parts();
+        let mut source_roots = BTreeSet::new();
+        source_roots.insert(vault_root);
+
+        Self {
+            inner,
+            source_store,
+            source_map: source_map.into_iter().collect(),
+            source_roots,
+            loaded_nodes: RefCell::new(BTreeSet::new()),
+            loaded_keys: RefCell::new(BTreeSet::new()),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of distinct vault tree nodes which were requested from the lazy source.
+    pub fn num_loaded_nodes(&self) -> usize {
+        self.loaded_nodes.borrow().len()
+    }
+
+    /// Consumes this provider and returns the wrapped advice provider together with the advice
+    /// inputs containing the account vault data which was loaded during execution.
+    ///
+    /// The returned advice inputs contain the authentication paths of all requested vault tree
+    /// nodes and all requested vault leaf entries; no advice stack values are returned.
+    pub fn into_parts(self) -> (A, AdviceInputs) {
+        let mut loaded = AdviceInputs::default();
+
+        for (root, index) in self.loaded_nodes.into_inner() {
+            let path = self
+                .source_store
+                .get_path(root, index)
+                .expect("loaded node must be present in the source store");
+            let nodes = path
+                .path
+                .inner_nodes(index.value(), path.value)
+                .expect("path from the source store must be valid");
+            loaded.extend_merkle_store(nodes);
+        }
+
+        let keys = self.loaded_keys.into_inner();
+        loaded.extend_map(self.source_map.into_iter().filter(|(key, _)| keys.contains(key)));
+
+        (self.inner, loaded)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the tree with the specified root is served from the lazy source.
+    fn is_lazy_root(&self, root: Word) -> bool {
+        self.source_roots.contains(&root.into())
+    }
+
+    /// Records that the node at the specified index of the tree with the specified root was
+    /// loaded from the lazy source.
+    fn record_node(&self, root: Word, index: NodeIndex) {
+        self.loaded_nodes.borrow_mut().insert((root.into(), index));
+    }
+}
+
+impl<A: AdviceProvider> AdviceProvider for LazyAdviceProvider<A> {
+    // ADVICE STACK
+    // --------------------------------------------------------------------------------------------
+
+    fn pop_stack<S: ProcessState>(&mut self, process: &S) -> Result<Felt, ExecutionError> {
+        self.inner.pop_stack(process)
+    }
+
+    fn pop_stack_word<S: ProcessState>(&mut self, process: &S) -> Result<Word, ExecutionError> {
+        self.inner.pop_stack_word(process)
+    }
+
+    fn pop_stack_dword<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<[Word; 2], ExecutionError> {
+        self.inner.pop_stack_dword(process)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        // values requested from the map may reside in the lazy source; load them into the inner
+        // provider's advice stack directly
+        if let AdviceSource::Map { key, include_len } = source {
+            let key = Digest::from(key).as_bytes();
+            if let Some(values) = self.source_map.get(&key) {
+                self.loaded_keys.borrow_mut().insert(key);
+                // push the values such that the first value ends up at the top of the stack, and
+                // the number of values (if requested) is pushed on top of them
+                let values = values.clone();
+                let num_values = values.len() as u64;
+                for value in values.into_iter().rev() {
+                    self.inner.push_stack(AdviceSource::Value(value))?;
+                }
+                if include_len {
+                    self.inner.push_stack(AdviceSource::Value(Felt::new(num_values)))?;
+                }
+                return Ok(());
+            }
+        }
+        self.inner.push_stack(source)
+    }
+
+    // ADVICE MAP
+    // --------------------------------------------------------------------------------------------
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) -> Result<(), ExecutionError> {
+        self.inner.insert_into_map(key, values)
+    }
+
+    fn get_signature(
+        &self,
+        kind: SignatureKind,
+        pub_key: Word,
+        msg: Word,
+    ) -> Result<Vec<Felt>, ExecutionError> {
+        self.inner.get_signature(kind, pub_key, msg)
+    }
+
+    fn get_mapped_values(&self, key: &[u8; 32]) -> Option<&[Felt]> {
+        match self.inner.get_mapped_values(key) {
+            Some(values) => Some(values),
+            None => {
+                let values = self.source_map.get(key)?;
+                self.loaded_keys.borrow_mut().insert(*key);
+                Some(values)
+            },
+        }
+    }
+
+    // MERKLE STORE
+    // --------------------------------------------------------------------------------------------
+
+    fn get_tree_node(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<Word, ExecutionError> {
+        if !self.is_lazy_root(root) {
+            return self.inner.get_tree_node(root, depth, index);
+        }
+
+        let node_index = NodeIndex::from_elements(depth, index)
+            .map_err(|_| ExecutionError::InvalidTreeNodeIndex { depth: *depth, value: *index })?;
+        let node = self
+            .source_store
+            .get_node(root.into(), node_index)
+            .map_err(ExecutionError::MerkleStoreLookupFailed)?;
+        self.record_node(root, node_index);
+
+        Ok(node.into())
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<MerklePath, ExecutionError> {
+        if !self.is_lazy_root(root) {
+            return self.inner.get_merkle_path(root, depth, index);
+        }
+
+        let node_index = NodeIndex::from_elements(depth, index)
+            .map_err(|_| ExecutionError::InvalidTreeNodeIndex { depth: *depth, value: *index })?;
+        let path = self
+            .source_store
+            .get_path(root.into(), node_index)
+            .map_err(ExecutionError::MerkleStoreLookupFailed)?;
+        self.record_node(root, node_index);
+
+        Ok(path.path)
+    }
+
+    fn get_leaf_depth(
+        &self,
+        root: Word,
+        tree_depth: &Felt,
+        index: &Felt,
+    ) -> Result<u8, ExecutionError> {
+        if !self.is_lazy_root(root) {
+            return self.inner.get_leaf_depth(root, tree_depth, index);
+        }
+
+        let tree_depth_u8 = u8::try_from(tree_depth.as_int())
+            .map_err(|_| ExecutionError::InvalidTreeDepth { depth: *tree_depth })?;
+        let leaf_depth = self
+            .source_store
+            .get_leaf_depth(root.into(), tree_depth_u8, index.as_int())
+            .map_err(ExecutionError::MerkleStoreLookupFailed)?;
+
+        // record the path to the leaf; the index of the leaf is derived from the index at the
+        // bottom of the tree by discarding the bits below the leaf depth
+        let leaf_index = index.as_int() >> (tree_depth_u8 - leaf_depth);
+        let node_index = NodeIndex::new(leaf_depth, leaf_index).map_err(|_| {
+            ExecutionError::InvalidTreeNodeIndex { depth: *tree_depth, value: *index }
+        })?;
+        self.record_node(root, node_index);
+
+        Ok(leaf_depth)
+    }
+
+    fn find_lone_leaf(
+        &self,
+        root: Word,
+        root_index: NodeIndex,
+        tree_depth: u8,
+    ) -> Result<Option<(NodeIndex, Word)>, ExecutionError> {
+        if !self.is_lazy_root(root) {
+            return self.inner.find_lone_leaf(root, root_index, tree_depth);
+        }
+
+        let leaf = self
+            .source_store
+            .find_lone_leaf(root.into(), root_index, tree_depth)
+            .map_err(ExecutionError::MerkleStoreLookupFailed)?;
+        if let Some((leaf_index, _)) = leaf {
+            self.record_node(root, leaf_index);
+        } else {
+            self.record_node(root, root_index);
+        }
+
+        Ok(leaf.map(|(index, leaf)| (index, leaf.into())))
+    }
+
+    fn update_merkle_node(
+        &mut self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+        value: Word,
+    ) -> Result<(MerklePath, Word), ExecutionError> {
+        if !self.is_lazy_root(root) {
+            return self.inner.update_merkle_node(root, depth, index, value);
+        }
+
+        let node_index = NodeIndex::from_elements(depth, index)
+            .map_err(|_| ExecutionError::InvalidTreeNodeIndex { depth: *depth, value: *index })?;
+        self.record_node(root, node_index);
+
+        let update = self
+            .source_store
+            .set_node(root.into(), node_index, value.into())
+            .map_err(ExecutionError::MerkleStoreUpdateFailed)?;
+        self.source_roots.insert(update.root);
+
+        Ok((update.path, update.root.into()))
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        if !self.is_lazy_root(lhs) && !self.is_lazy_root(rhs) {
+            return self.inner.merge_roots(lhs, rhs);
+        }
+
+        // the kernel never merges vault trees; supporting this would require moving the trees
+        // between the lazy source and the wrapped provider
+        Err(ExecutionError::EventError(
+            "cannot merge trees served from the lazy account vault source".to_string(),
+        ))
+    }
+
+    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+    where
+        I: Iterator<Item = R>,
+        R: Borrow<Digest>,
+    {
+        let (lazy_roots, inner_roots): (Vec<Digest>, Vec<Digest>) = roots
+            .map(|root| *root.borrow())
+            .partition(|root| self.source_roots.contains(root));
+
+        let mut store = self.inner.get_store_subset(inner_roots.iter());
+        store.extend(self.source_store.subset(lazy_roots.iter()).inner_nodes());
+        store
+    }
+}
@@ -1,50 +1,153 @@
-use miden_lib::transaction::TransactionEvent;
+use miden_lib::transaction::{kernel_procedure_name, EventId, EventNamespace, TransactionEvent};
 use miden_objects::{
-    accounts::{delta::AccountVaultDelta, AccountStub},
-    utils::{collections::BTreeMap, string::ToString},
-    Digest,
+    accounts::{delta::AccountVaultDelta, AccountHeader},
+    block::ACCOUNT_TREE_DEPTH,
+    notes::NoteScript,
+    utils::{
+        collections::{BTreeMap, Vec},
+        string::ToString,
+        sync::Arc,
+    },
+    Digest, Felt, Hasher,
 };
 use vm_processor::{
     crypto::NodeIndex, AdviceExtractor, AdviceInjector, AdviceProvider, AdviceSource, ContextId,
-    ExecutionError, Host, HostResponse, ProcessState,
+    ExecutionError, Host, HostResponse, ProcessState, SignatureKind,
 };
 
+use crate::TransactionAuthenticator;
+
 mod account_delta;
-use account_delta::AccountVaultDeltaTracker;
+pub use account_delta::AccountStorageUpdates;
+use account_delta::{AccountStorageDeltaTracker, AccountVaultDeltaTracker};
 
 mod account_procs;
 use account_procs::AccountProcedureIndexMap;
 
+mod advice_recorder;
+pub use advice_recorder::{read_advice_witness, CompactAdviceRecorder};
+
+mod event_handlers;
+pub use event_handlers::{DecodedEvent, EventHandler, EventHandlers};
+
+mod lazy_provider;
+pub use lazy_provider::LazyAdviceProvider;
+
+mod output_notes;
+pub use output_notes::OutputNoteTracker;
+
+mod trace;
+pub use trace::{TransactionPhase, TransactionTraceHandler};
+
 // TRANSACTION HOST
 // ================================================================================================
 
 /// Transaction host is responsible for handling [Host] requests made by a transaction kernel.
 ///
-/// Transaction host is composed of two components:
+/// Transaction host is composed of the following components:
 /// - An advice provider which is used to provide non-deterministic inputs to the transaction
 ///   runtime.
 /// - An account vault delta tracker which is used to keep track of changes made to the asset
 ///   of the account the transaction is being executed against.
+/// - An account storage delta tracker which is used to keep track of the storage slots of the
+///   account the transaction is being executed against which were written to.
+/// - An output note tracker which is used to recover the full details of the notes created by
+///   the transaction (see [OutputNoteTracker]).
+/// - An optional [TransactionAuthenticator] which is used to answer signature requests.
+///
+/// Signatures are looked up in the advice map first (under `hash(pub_key, message)`), and only
+/// if no signature is found there, the host asks the authenticator (if present) to produce it.
+/// Signatures produced by the authenticator are inserted into the advice map under the same key,
+/// so that they become a part of the transaction witness. If neither source provides a
+/// signature, the request is forwarded to the advice provider.
+///
+/// Events in the transaction kernel namespace are handled by the host itself. Events in other
+/// namespaces are dispatched to the [EventHandler] registered for their namespace, and are ignored
+/// if no handler is registered (see [EventNamespace] for the allocation of event IDs).
+///
+/// The host also keeps track of the [TransactionPhase] the transaction is in, which allows
+/// attributing execution failures to a specific phase and input note. The progress of the
+/// execution can be streamed to a [TransactionTraceHandler], together with the emitted events
+/// decoded via [TransactionEvent::decode_payload()] and the registered [EventHandlers], and (when
+/// tracing is enabled in the execution options) the kernel procedures entered by the transaction.
 pub struct TransactionHost<A> {
     adv_provider: A,
     acct_vault_delta_tracker: AccountVaultDeltaTracker,
+    acct_storage_delta_tracker: AccountStorageDeltaTracker,
     acct_procedure_index_map: AccountProcedureIndexMap,
+    output_note_tracker: OutputNoteTracker,
+    authenticator: Option<Arc<dyn TransactionAuthenticator>>,
+    event_handlers: EventHandlers,
+    trace_handler: Option<Arc<dyn TransactionTraceHandler>>,
+    phase: TransactionPhase,
+    num_started_notes: usize,
 }
 
 impl<A: AdviceProvider> TransactionHost<A> {
     /// Returns a new [TransactionHost] instance with the provided [AdviceProvider].
-    pub fn new(account: AccountStub, adv_provider: A) -> Self {
-        let proc_index_map = AccountProcedureIndexMap::new(account.code_root(), &adv_provider);
+    pub fn new(account: AccountHeader, adv_provider: A) -> Self {
+        let proc_index_map =
+            AccountProcedureIndexMap::new(account.code_commitment(), &adv_provider);
         Self {
             adv_provider,
             acct_vault_delta_tracker: AccountVaultDeltaTracker::default(),
+            acct_storage_delta_tracker: AccountStorageDeltaTracker::default(),
             acct_procedure_index_map: proc_index_map,
+            output_note_tracker: OutputNoteTracker::default(),
+            authenticator: None,
+            event_handlers: EventHandlers::default(),
+            trace_handler: None,
+            phase: TransactionPhase::default(),
+            num_started_notes: 0,
         }
     }
 
-    /// Consumes this transaction host and returns the advice provider and account vault delta.
-    pub fn into_parts(self) -> (A, AccountVaultDelta) {
-        (self.adv_provider, self.acct_vault_delta_tracker.into_vault_delta())
+    /// Sets the [TransactionAuthenticator] used by this host to answer signature requests.
+    pub fn with_authenticator(mut self, authenticator: Arc<dyn TransactionAuthenticator>) -> Self {
+        self.authenticator = Some(authenticator);
+        self
+    }
+
+    /// Sets the note scripts used by this host to recover the full details of the notes created
+    /// by the transaction.
+    ///
+    /// Only notes whose scripts are among the provided scripts can be recovered.
+    pub fn with_note_scripts(mut self, note_scripts: BTreeMap<Digest, NoteScript>) -> Self {
+        self.output_note_tracker = OutputNoteTracker::new(note_scripts);
+        self
+    }
+
+    /// Sets the [EventHandlers] used by this host to handle events outside of the transaction
+    /// kernel namespace.
+    pub fn with_event_handlers(mut self, event_handlers: EventHandlers) -> Self {
+        self.event_handlers = event_handlers;
+        self
+    }
+
+    /// Sets the [TransactionTraceHandler] to which this host streams the progress of the
+    /// transaction execution.
+    pub fn with_trace_handler(mut self, trace_handler: Arc<dyn TransactionTraceHandler>) -> Self {
+        self.trace_handler = Some(trace_handler);
+        self
+    }
+
+    /// Returns the phase the executed transaction is currently in.
+    ///
+    /// If the execution failed, this is the phase in which the failure occurred.
+    pub fn phase(&self) -> TransactionPhase {
+        self.phase
+    }
+
+    /// Consumes this transaction host and returns the advice provider, account vault delta,
+    /// account storage updates (i.e., updated slots and map entries), and the output note tracker
+    /// recorded during transaction execution.
+    pub fn into_parts(self) -> (A, AccountVaultDelta, AccountStorageUpdates, OutputNoteTracker) {
+        (
+            self.adv_provider,
+            self.acct_vault_delta_tracker.into_vault_delta(),
+            self.acct_storage_delta_tracker.into_updates(),
+            self.output_note_tracker,
+        )
     }
 
     // EVENT HANDLERS
@@ -54,13 +157,116 @@ impl<A: AdviceProvider> TransactionHost<A> {
         &mut self,
         process: &S,
     ) -> Result<(), ExecutionError> {
-        let proc_idx = self
+        let (proc_idx, proc_access) = self
             .acct_procedure_index_map
             .get_proc_index(process)
             .map_err(|err| ExecutionError::EventError(err.to_string()))?;
+        // the kernel reads the index of the procedure first, and then its access level
+        self.adv_provider.push_stack(AdviceSource::Value(proc_access))?;
         self.adv_provider.push_stack(AdviceSource::Value(proc_idx.into()))?;
         Ok(())
     }
+
+    /// Checks that the data required to read the storage of the foreign account at stack position
+    /// 4 is available to the advice provider, so that a missing foreign account is reported as
+    /// such rather than as a generic Merkle store lookup failure.
+    ///
+    /// Expected stack state: [ACCT_DB_ROOT, foreign_acct_id, index, ...]
+    fn on_get_foreign_account_item<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<(), ExecutionError> {
+        let account_db_root = process.get_stack_word(0);
+        let foreign_account_id = process.get_stack_item(4);
+
+        self.adv_provider
+            .get_tree_node(account_db_root, &Felt::from(ACCOUNT_TREE_DEPTH), &foreign_account_id)
+            .map_err(|_| {
+                ExecutionError::EventError(format!(
+                    "state of foreign account {foreign_account_id} was not provided to the \
+                    transaction"
+                ))
+            })?;
+
+        Ok(())
+    }
+
+    /// Updates the phase of the executed transaction in response to the specified trace event
+    /// and notifies the trace handler (if any) when a new phase starts.
+    fn on_trace_event<S: ProcessState>(&mut self, process: &S, event: &TransactionEvent) {
+        use TransactionEvent::*;
+        self.phase = match event {
+            NotesProcessingStart | NoteExecutionEnd => TransactionPhase::NotesProcessing,
+            NoteExecutionStart => {
+                self.num_started_notes += 1;
+                TransactionPhase::NoteExecution { note_index: self.num_started_notes - 1 }
+            },
+            TxScriptProcessingStart => TransactionPhase::TransactionScript,
+            EpilogueStart => TransactionPhase::Epilogue,
+            AddAssetToAccountVault
+            | RemoveAssetFromAccountVault
+            | PushAccountProcedureIndex
+            | SetAccountStorageItem
+            | SetAccountStorageMapItem
+            | GetForeignAccountItem
+            | CreateNote
+            | BuildRecipient => return,
+        };
+
+        if let Some(trace_handler) = &self.trace_handler {
+            trace_handler.on_phase_started(self.phase, process);
+        }
+    }
+
+    /// Decodes the specified event and passes it to the trace handler (if any).
+    fn trace_decoded_event<S: ProcessState>(&self, process: &S, event_id: EventId) {
+        if let Some(trace_handler) = &self.trace_handler {
+            let event = self.event_handlers.decode(event_id, &process.get_stack_state());
+            trace_handler.on_event_decoded(&event, self.phase, process);
+        }
+    }
+
+    // ADVICE HANDLERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Pushes a signature over the message at stack positions [4..8] made with the secret key
+    /// associated with the public key at stack positions [0..4] onto the advice stack.
+    fn on_signature_requested<S: ProcessState>(
+        &mut self,
+        process: &S,
+        kind: SignatureKind,
+    ) -> Result<HostResponse, ExecutionError> {
+        let pub_key = process.get_stack_word(0);
+        let message = process.get_stack_word(1);
+        let signature_key = Hasher::merge(&[pub_key.into(), message.into()]);
+
+        let signature = match self.adv_provider.get_mapped_values(&signature_key.as_bytes()) {
+            Some(signature) => signature.to_vec(),
+            None => match &self.authenticator {
+                Some(authenticator) => {
+                    let signature = authenticator
+                        .get_signature(kind, pub_key, message)
+                        .map_err(|err| ExecutionError::EventError(err.to_string()))?;
+                    self.adv_provider.insert_into_map(signature_key.into(), signature.clone())?;
+                    // read the signature back so that advice providers which record accessed
+                    // map entries include it into the transaction witness
+                    self.adv_provider.get_mapped_values(&signature_key.as_bytes());
+                    signature
+                },
+                None => {
+                    return self
+                        .adv_provider
+                        .set_advice(process, &AdviceInjector::SigToStack { kind })
+                },
+            },
+        };
+
+        for value in signature {
+            self.adv_provider.push_stack(AdviceSource::Value(value))?;
+        }
+
+        Ok(HostResponse::None)
+    }
 }
 
 impl<A: AdviceProvider> Host for TransactionHost<A> {
@@ -77,7 +283,10 @@ impl<A: AdviceProvider> Host for TransactionHost<A> {
         process: &S,
         injector: AdviceInjector,
     ) -> Result<HostResponse, ExecutionError> {
-        self.adv_provider.set_advice(process, &injector)
+        match injector {
+            AdviceInjector::SigToStack { kind } => self.on_signature_requested(process, kind),
+            injector => self.adv_provider.set_advice(process, &injector),
+        }
     }
 
     fn on_event<S: ProcessState>(
@@ -85,7 +294,17 @@ impl<A: AdviceProvider> Host for TransactionHost<A> {
         process: &S,
         event_id: u32,
     ) -> Result<HostResponse, ExecutionError> {
-        let event = TransactionEvent::try_from(event_id)
+        let event_id = EventId::try_from(event_id)
+            .map_err(|err| ExecutionError::EventError(err.to_string()))?;
+        if event_id.namespace() != EventNamespace::Kernel {
+            self.trace_decoded_event(process, event_id);
+            if let Some(handler) = self.event_handlers.get(event_id.namespace()) {
+                handler.on_event(event_id, process)?;
+            }
+            return Ok(HostResponse::None);
+        }
+
+        let event = TransactionEvent::try_from(event_id.as_u32())
             .map_err(|err| ExecutionError::EventError(err.to_string()))?;
 
         if process.ctx() != ContextId::root() {
@@ -94,13 +313,45 @@ impl<A: AdviceProvider> Host for TransactionHost<A> {
             )));
         }
 
+        if let (Some(trace_handler), false) = (&self.trace_handler, event.is_trace_event()) {
+            trace_handler.on_kernel_event(&event, self.phase, process);
+            self.trace_decoded_event(process, event_id);
+        }
+
         use TransactionEvent::*;
         match event {
             AddAssetToAccountVault => self.acct_vault_delta_tracker.add_asset(process),
             RemoveAssetFromAccountVault => self.acct_vault_delta_tracker.remove_asset(process),
             PushAccountProcedureIndex => self.on_push_account_procedure_index(process),
+            SetAccountStorageItem => self.acct_storage_delta_tracker.set_item(process),
+            SetAccountStorageMapItem => self.acct_storage_delta_tracker.set_map_item(process),
+            GetForeignAccountItem => self.on_get_foreign_account_item(process),
+            CreateNote => Ok(()),
+            BuildRecipient => self.output_note_tracker.build_recipient(process, &self.adv_provider),
+            NotesProcessingStart
+            | NoteExecutionStart
+            | NoteExecutionEnd
+            | TxScriptProcessingStart
+            | EpilogueStart => {
+                self.on_trace_event(process, &event);
+                Ok(())
+            },
         }?;
 
         Ok(HostResponse::None)
     }
+
+    fn on_trace<S: ProcessState>(
+        &mut self,
+        process: &S,
+        trace_id: u32,
+    ) -> Result<HostResponse, ExecutionError> {
+        if let (Some(trace_handler), Some(procedure)) =
+            (&self.trace_handler, kernel_procedure_name(trace_id))
+        {
+            trace_handler.on_kernel_procedure_entered(procedure, self.phase, process);
+        }
+
+        Ok(HostResponse::None)
+    }
 }
@@ -0,0 +1,114 @@
+use miden_objects::{
+    notes::{Note, NoteInputs, NoteScript},
+    transaction::OutputNote,
+    utils::collections::BTreeMap,
+    Digest, Word,
+};
+use vm_processor::{AdviceProvider, ExecutionError, ProcessState};
+
+// OUTPUT NOTE TRACKER
+// ================================================================================================
+
+/// The output note tracker is responsible for recovering the full details of the notes created
+/// during transaction execution.
+///
+/// Whenever a recipient is built via the `build_recipient_hash` kernel procedure, the kernel
+/// signals the preimage of the recipient, i.e., `[SERIAL_NUM, MEMO, SCRIPT_HASH, INPUTS_HASH]`, to
+/// the host. As the recipient is computed by the kernel from the same preimage, the preimage is
+/// guaranteed to match the recipient of the notes created for it. If the script with the specified
+/// hash is known to the tracker and the note inputs are stored in the advice map under
+/// `INPUTS_HASH` (e.g., via `miden::note::compute_inputs_hash`), the details of the recipient are
+/// recorded, so that the full note can be built once the transaction outputs are available.
+///
+/// Notes whose recipient is not built by the kernel (e.g., recipients computed outside of the
+/// transaction), as well as notes whose script or inputs are not available, are not tracked; this
+/// does not affect the execution of the transaction.
+#[derive(Default, Debug)]
+pub struct OutputNoteTracker {
+    note_scripts: BTreeMap<Digest, NoteScript>,
+    recipients: BTreeMap<Digest, RecipientDetails>,
+}
+
+impl OutputNoteTracker {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [OutputNoteTracker] which recognizes the provided note scripts.
+    pub fn new(note_scripts: BTreeMap<Digest, NoteScript>) -> Self {
+        Self {
+            note_scripts,
+            recipients: BTreeMap::new(),
+        }
+    }
+
+    // MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Extracts the preimage of the recipient being built from the process state, and records the
+    /// details of the recipient if its script is known and its inputs can be found in the advice
+    /// map of the provided advice provider.
+    ///
+    /// Expected stack state: [SERIAL_NUM, MEMO, SCRIPT_HASH, INPUTS_HASH, ...]
+    pub fn build_recipient<S: ProcessState, A: AdviceProvider>(
+        &mut self,
+        process: &S,
+        adv_provider: &A,
+    ) -> Result<(), ExecutionError> {
+        let word = |pos: usize| -> Word {
+            [
+                process.get_stack_item(pos + 3),
+                process.get_stack_item(pos + 2),
+                process.get_stack_item(pos + 1),
+                process.get_stack_item(pos),
+            ]
+        };
+        let serial_num = word(0);
+        let memo: Digest = word(4).into();
+        let script_hash: Digest = word(8).into();
+        let inputs_hash: Digest = word(12).into();
+
+        let script = self.note_scripts.get(&script_hash).cloned();
+        let inputs = adv_provider
+            .get_mapped_values(&inputs_hash.as_bytes())
+            .and_then(|inputs| NoteInputs::new(inputs).ok())
+            .filter(|inputs| inputs.hash() == inputs_hash);
+
+        if let (Some(script), Some(inputs)) = (script, inputs) {
+            let recipient = Note::compute_recipient(serial_num, memo, script_hash, inputs_hash);
+            self.recipients
+                .insert(recipient, RecipientDetails { serial_num, memo, script, inputs });
+        }
+
+        Ok(())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the full note described by the provided output note, or None if the details of
+    /// its recipient were not recorded during transaction execution.
+    pub fn build_note(&self, output_note: &OutputNote) -> Option<Note> {
+        let details = self.recipients.get(output_note.recipient())?;
+        let note = Note::from_parts(
+            details.script.clone(),
+            details.inputs.clone(),
+            output_note.assets().clone(),
+            details.serial_num,
+            *output_note.metadata(),
+        )
+        .with_memo(details.memo);
+
+        Some(note)
+    }
+}
+
+// RECIPIENT DETAILS
+// ================================================================================================
+
+/// The preimage of a note recipient.
+#[derive(Debug)]
+struct RecipientDetails {
+    serial_num: Word,
+    memo: Digest,
+    script: NoteScript,
+    inputs: NoteInputs,
+}
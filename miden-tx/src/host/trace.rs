@@ -0,0 +1,102 @@
+use core::fmt;
+
+use miden_lib::transaction::TransactionEvent;
+use vm_processor::ProcessState;
+
+use super::DecodedEvent;
+
+// TRANSACTION PHASE
+// ================================================================================================
+
+/// A phase of the execution of a transaction by the transaction kernel.
+///
+/// The transaction kernel signals transitions between phases via events, which allows the
+/// [TransactionHost](super::TransactionHost) to keep track of the phase the transaction is in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionPhase {
+    /// The prologue, which validates the transaction inputs and sets up the root context.
+    #[default]
+    Prologue,
+    /// The note processing phase, outside of the execution of a specific note (e.g., the set up
+    /// and the teardown of the note processing loop).
+    NotesProcessing,
+    /// The execution of the input note with the specified index.
+    NoteExecution { note_index: usize },
+    /// The processing of the transaction script (if any).
+    TransactionScript,
+    /// The epilogue, which computes the transaction outputs and enforces the nonce and asset
+    /// preservation rules.
+    Epilogue,
+}
+
+impl fmt::Display for TransactionPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Prologue => write!(f, "prologue"),
+            Self::NotesProcessing => write!(f, "note processing"),
+            Self::NoteExecution { note_index } => write!(f, "execution of input note {note_index}"),
+            Self::TransactionScript => write!(f, "transaction script processing"),
+            Self::Epilogue => write!(f, "epilogue"),
+        }
+    }
+}
+
+// TRANSACTION TRACE HANDLER
+// ================================================================================================
+
+/// The [TransactionTraceHandler] trait defines the interface used by the transaction host to
+/// stream the progress of a transaction execution, e.g., to debuggers or execution visualizers.
+///
+/// Handlers are given access to the state of the VM at every callback, thus a snapshot of the
+/// operand stack can be obtained via [ProcessState::get_stack_state()]. Like [EventHandler]s,
+/// trace handlers cannot affect the execution of the transaction. Also like [EventHandler]s, trace
+/// handlers must be [Send] and [Sync], as they may be invoked by executions running in parallel.
+///
+/// [EventHandler]: super::EventHandler
+pub trait TransactionTraceHandler: Send + Sync {
+    /// Invoked when the transaction enters the specified phase.
+    fn on_phase_started(&self, phase: TransactionPhase, process: &dyn ProcessState);
+
+    /// Invoked when the transaction enters the kernel procedure with the specified name (see
+    /// [KERNEL_PROCEDURES](miden_lib::transaction::KERNEL_PROCEDURES)), before the body of the
+    /// procedure is executed. `phase` is the phase from which the procedure was invoked.
+    ///
+    /// Kernel procedures are reported only when tracing is enabled in the execution options of the
+    /// process, which executors do whenever a trace handler is set.
+    fn on_kernel_procedure_entered(
+        &self,
+        procedure: &str,
+        phase: TransactionPhase,
+        process: &dyn ProcessState,
+    ) {
+        let _ = (procedure, phase, process);
+    }
+
+    /// Invoked when the transaction kernel emits an event requesting the host to act on the state
+    /// of the transaction (e.g., when an asset is added to the account vault), before the event
+    /// is handled. `phase` is the phase in which the event was emitted.
+    fn on_kernel_event(
+        &self,
+        event: &TransactionEvent,
+        phase: TransactionPhase,
+        process: &dyn ProcessState,
+    ) {
+        let _ = (event, phase, process);
+    }
+
+    /// Invoked for every event emitted during the execution of the transaction, except for the
+    /// events signaling transitions between kernel phases, with the data carried by the event
+    /// decoded from the operand stack. `phase` is the phase in which the event was emitted.
+    ///
+    /// Events outside of the transaction kernel namespace are decoded via the
+    /// [EventHandlers](super::EventHandlers) of the host, and are reported as
+    /// [DecodedEvent::Undecoded] if no handler recognizes them.
+    fn on_event_decoded(
+        &self,
+        event: &DecodedEvent,
+        phase: TransactionPhase,
+        process: &dyn ProcessState,
+    ) {
+        let _ = (event, phase, process);
+    }
+}
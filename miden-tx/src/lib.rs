@@ -9,28 +9,90 @@ use miden_objects::{
     AccountError, Digest,
 };
 use vm_core::Program;
-use vm_processor::{ExecutionError, RecAdviceProvider};
+use vm_processor::ExecutionError;
+
+mod auth;
+pub use auth::{BasicAuthenticator, TransactionAuthenticator};
 
 mod compiler;
+
+#[cfg(feature = "config")]
+pub mod config;
 pub use compiler::{ScriptTarget, TransactionCompiler};
 
 mod executor;
-pub use executor::{DataStore, TransactionExecutor};
+#[cfg(feature = "async")]
+pub use executor::AsyncDataStore;
+pub use executor::{
+    DataStore, NoteConsumability, TransactionExecutor, TransactionExecutorBuilder,
+    TransactionSimulation,
+};
 
 pub mod host;
 pub use host::TransactionHost;
 
+pub mod metrics;
+
 mod prover;
-pub use prover::{ProvingOptions, TransactionProver};
+#[cfg(feature = "remote-prover")]
+pub use prover::HttpProvingService;
+#[cfg(feature = "testing")]
+pub use prover::TestProver;
+pub use prover::{
+    ProofHashFunction, ProverOptions, ProvingOptions, ProvingRequest, TransactionProver,
+};
+#[cfg(feature = "async")]
+pub use prover::{ProvingService, RemoteTransactionProver};
+
+pub mod replay;
+
+// the reference data stores hold objects which are shared between threads only with `std`
+#[cfg(feature = "std")]
+mod store;
+#[cfg(feature = "std")]
+pub use store::MemDataStore;
+#[cfg(feature = "sqlite")]
+pub use store::SqliteDataStore;
+
+mod request;
+pub use request::{Airdrop, TransactionRequest};
+
+mod validation;
+pub use validation::{TransactionValidator, ValidationLimits};
 
 mod verifier;
 pub use verifier::TransactionVerifier;
 
 mod error;
+#[cfg(feature = "config")]
+pub use error::ConfigError;
 pub use error::{
-    DataStoreError, TransactionCompilerError, TransactionExecutorError, TransactionProverError,
-    TransactionVerifierError,
+    AuthenticationError, DataStoreError, EventRegistrationError, ProvenTransactionMismatch,
+    ProverOptionsError, RemoteProverError, TransactionCompilerError, TransactionEffectMismatch,
+    TransactionExecutorError, TransactionProverError, TransactionReplayError,
+    TransactionRequestError, TransactionValidationError, TransactionVerifierError,
 };
 
+// PRELUDE
+// ================================================================================================
+
+/// Re-exports of the types most commonly used to create accounts and notes, and to execute, prove,
+/// and verify transactions.
+pub mod prelude {
+    pub use miden_lib::{
+        accounts::{faucets::create_basic_fungible_faucet, wallets::create_basic_wallet},
+        notes::{create_note, Script},
+        AuthScheme,
+    };
+    pub use miden_objects::prelude::*;
+
+    pub use crate::{
+        BasicAuthenticator, DataStore, DataStoreError, ProvingOptions, ScriptTarget,
+        TransactionAuthenticator, TransactionCompiler, TransactionExecutor,
+        TransactionExecutorError, TransactionProver, TransactionProverError, TransactionRequest,
+        TransactionVerifier, TransactionVerifierError,
+    };
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,239 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{Parser, Subcommand};
+use miden_lib::notes::standard_script_kind;
+use miden_objects::{
+    accounts::{Account, AccountFile, AccountId},
+    assembly::{ModuleAst, ProgramAst},
+    block::BlockHeader,
+    notes::{NoteFile, NoteId},
+    transaction::{ChainMmr, ExecutedTransaction, InputNote, InputNotes, ProvenTransaction},
+    utils::serde::{Deserializable, Serializable, SliceReader},
+    Felt, Word, EMPTY_WORD,
+};
+use miden_tx::{
+    DataStore, DataStoreError, ProvingOptions, ScriptTarget, TransactionExecutor,
+    TransactionInputs, TransactionProver, TransactionVerifier,
+};
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+// ARGUMENTS
+// ================================================================================================
+
+/// Executes, proves, and verifies Miden transactions.
+#[derive(Parser, Debug)]
+#[command(name = "miden-tx", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Executes a transaction and prints its description.
+    Execute(TransactionArgs),
+
+    /// Executes and proves a transaction, writes the proven transaction into a file, and prints
+    /// its description.
+    Prove {
+        #[command(flatten)]
+        tx: TransactionArgs,
+
+        /// File into which the proven transaction is written.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Verifies the proof of a proven transaction read from a file.
+    Verify {
+        /// File containing the proven transaction.
+        proof: PathBuf,
+
+        /// Minimum security level (in bits) of the proof.
+        #[arg(long, default_value_t = 96)]
+        security_level: u32,
+    },
+
+    /// Prints the description of a proven transaction read from a file.
+    Summary {
+        /// File containing the proven transaction.
+        proof: PathBuf,
+    },
+}
+
+/// Inputs of a transaction to execute.
+#[derive(clap::Args, Debug)]
+struct TransactionArgs {
+    /// Account file of the account against which the transaction is executed.
+    #[arg(short, long)]
+    account: PathBuf,
+
+    /// Note files of the notes consumed by the transaction; the files must contain the inclusion
+    /// proofs of the notes.
+    #[arg(short, long)]
+    notes: Vec<PathBuf>,
+
+    /// MASM source file of the transaction script.
+    ///
+    /// If the account file contains auth data, the auth data is provided to the script via the
+    /// advice map.
+    #[arg(short, long)]
+    script: Option<PathBuf>,
+
+    /// File containing the reference block header followed by the chain MMR authenticating the
+    /// blocks of the consumed notes.
+    #[arg(short, long)]
+    chain: PathBuf,
+}
+
+// MAIN
+// ================================================================================================
+
+fn main() -> CliResult<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Execute(args) => {
+            let executed_tx = execute(args)?;
+            println!("{}", executed_tx.description().with_script_kinds(standard_script_kind));
+        },
+        Command::Prove { tx, output } => {
+            let executed_tx = execute(tx)?;
+            let proven_tx =
+                TransactionProver::new(ProvingOptions::default()).prove_transaction(executed_tx)?;
+            fs::write(&output, proven_tx.to_bytes())?;
+            println!("{}", proven_tx.description().with_script_kinds(standard_script_kind));
+            println!("proven transaction written to {}", output.display());
+        },
+        Command::Verify { proof, security_level } => {
+            let proven_tx = read_proven_tx(&proof)?;
+            let tx_id = proven_tx.id();
+            TransactionVerifier::new(security_level).verify(proven_tx)?;
+            println!("transaction {tx_id:?} verified");
+        },
+        Command::Summary { proof } => {
+            let proven_tx = read_proven_tx(&proof)?;
+            println!("{}", proven_tx.description().with_script_kinds(standard_script_kind));
+        },
+    }
+
+    Ok(())
+}
+
+// COMMANDS
+// ================================================================================================
+
+/// Executes the transaction described by the provided arguments.
+fn execute(args: TransactionArgs) -> CliResult<ExecutedTransaction> {
+    let (account, account_seed, auth) = AccountFile::read(&args.account)?.into_parts();
+
+    let mut notes = Vec::new();
+    for path in args.notes.iter() {
+        let note_file = NoteFile::read_from_bytes(&fs::read(path)?)?;
+        let proof = note_file
+            .inclusion_proof()
+            .ok_or_else(|| format!("note file {} has no inclusion proof", path.display()))?;
+        notes.push(InputNote::new(note_file.note().clone(), proof.clone()));
+    }
+
+    let chain_bytes = fs::read(&args.chain)?;
+    let mut chain_reader = SliceReader::new(&chain_bytes);
+    let block_header = BlockHeader::read_from(&mut chain_reader)?;
+    let block_chain = ChainMmr::read_from(&mut chain_reader)?;
+
+    let account_id = account.id();
+    let block_ref = block_header.block_num();
+    let note_ids = notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let data_store = FileDataStore {
+        account,
+        account_seed,
+        block_header,
+        block_chain,
+        notes,
+    };
+
+    let executor = TransactionExecutor::new(data_store);
+    executor.load_account(account_id)?;
+
+    let tx_script = match args.script {
+        Some(path) => {
+            let script_ast = ProgramAst::parse(&fs::read_to_string(path)?)?;
+            let inputs: Vec<(Word, Vec<Felt>)> = auth
+                .map(|auth| {
+                    let (_, key_pair) = auth.to_advice_map_entry();
+                    (auth.pub_key(), key_pair)
+                })
+                .into_iter()
+                .collect();
+            Some(executor.compile_tx_script(
+                script_ast,
+                inputs,
+                vec![ScriptTarget::AccountId(account_id)],
+            )?)
+        },
+        None => None,
+    };
+
+    Ok(executor.execute_transaction(account_id, block_ref, &note_ids, tx_script, EMPTY_WORD)?)
+}
+
+/// Reads a proven transaction from the specified file.
+fn read_proven_tx(path: &Path) -> CliResult<ProvenTransaction> {
+    Ok(ProvenTransaction::read_from_bytes(&fs::read(path)?)?)
+}
+
+// FILE DATA STORE
+// ================================================================================================
+
+/// A [DataStore] serving the data read from the files provided on the command line.
+struct FileDataStore {
+    account: Account,
+    account_seed: Option<Word>,
+    block_header: BlockHeader,
+    block_chain: ChainMmr,
+    notes: Vec<InputNote>,
+}
+
+impl DataStore for FileDataStore {
+    fn get_transaction_inputs(
+        &self,
+        account_id: AccountId,
+        block_num: u32,
+        notes: &[NoteId],
+    ) -> Result<TransactionInputs, DataStoreError> {
+        if account_id != self.account.id() {
+            return Err(DataStoreError::AccountNotFound(account_id));
+        }
+        if block_num != self.block_header.block_num() {
+            return Err(DataStoreError::BlockNotFound(block_num));
+        }
+
+        let notes = self
+            .notes
+            .iter()
+            .filter(|note| notes.contains(&note.id()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let notes = InputNotes::new(notes).map_err(DataStoreError::InvalidTransactionInput)?;
+
+        TransactionInputs::new(
+            self.account.clone(),
+            self.account_seed,
+            self.block_header,
+            self.block_chain.clone(),
+            notes,
+        )
+        .map_err(DataStoreError::InvalidTransactionInput)
+    }
+
+    fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError> {
+        if account_id != self.account.id() {
+            return Err(DataStoreError::AccountNotFound(account_id));
+        }
+        Ok(self.account.code().module().clone())
+    }
+}
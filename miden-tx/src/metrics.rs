@@ -0,0 +1,82 @@
+//! Metrics describing the work performed by transaction executors and provers.
+//!
+//! When the `metrics` feature is enabled, the metrics are recorded via the
+//! [metrics](https://docs.rs/metrics) facade, and can thus be exported by any recorder installed
+//! by the application (e.g., a Prometheus exporter). Without the feature, recording the metrics is
+//! a no-op.
+
+#[cfg(feature = "metrics")]
+use std::time::Duration;
+
+// METRIC NAMES
+// ================================================================================================
+
+/// Counter of transactions successfully executed by a
+/// [TransactionExecutor](crate::TransactionExecutor).
+pub const TRANSACTIONS_EXECUTED: &str = "miden_tx_transactions_executed_total";
+
+/// Counter of transactions whose execution failed.
+pub const TRANSACTIONS_FAILED: &str = "miden_tx_transactions_failed_total";
+
+/// Histogram of the number of VM cycles consumed by executed transactions.
+pub const TRANSACTION_CYCLES: &str = "miden_tx_transaction_cycles";
+
+/// Counter of transactions proven by a [TransactionProver](crate::TransactionProver).
+pub const TRANSACTIONS_PROVEN: &str = "miden_tx_transactions_proven_total";
+
+/// Histogram of the time taken to prove transactions, in seconds.
+pub const PROVING_TIME: &str = "miden_tx_proving_time_seconds";
+
+/// Histogram of the size of transaction proofs, in bytes.
+pub const PROOF_SIZE: &str = "miden_tx_proof_size_bytes";
+
+// DESCRIPTIONS
+// ================================================================================================
+
+/// Registers the descriptions of all metrics recorded by this crate with the installed recorder.
+///
+/// This is optional, but allows exporters to attach help texts and units to the metrics. It
+/// should be called after the recorder was installed.
+#[cfg(feature = "metrics")]
+pub fn describe_metrics() {
+    use ::metrics::{describe_counter, describe_histogram, Unit};
+
+    describe_counter!(TRANSACTIONS_EXECUTED, "Number of successfully executed transactions");
+    describe_counter!(TRANSACTIONS_FAILED, "Number of transactions which failed to execute");
+    describe_histogram!(
+        TRANSACTION_CYCLES,
+        Unit::Count,
+        "Number of VM cycles consumed by executed transactions"
+    );
+    describe_counter!(TRANSACTIONS_PROVEN, "Number of proven transactions");
+    describe_histogram!(PROVING_TIME, Unit::Seconds, "Time taken to prove transactions");
+    describe_histogram!(PROOF_SIZE, Unit::Bytes, "Size of transaction proofs");
+}
+
+// RECORDING
+// ================================================================================================
+
+/// Records the successful execution of a transaction which consumed the specified number of
+/// cycles.
+pub(crate) fn record_execution(_num_cycles: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::counter!(TRANSACTIONS_EXECUTED).increment(1);
+        ::metrics::histogram!(TRANSACTION_CYCLES).record(_num_cycles as f64);
+    }
+}
+
+/// Records a failed transaction execution.
+pub(crate) fn record_execution_failure() {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(TRANSACTIONS_FAILED).increment(1);
+}
+
+/// Records the proving of a transaction which took the specified time and produced a proof of the
+/// specified size.
+#[cfg(feature = "metrics")]
+pub(crate) fn record_proof(proving_time: Duration, proof_size: usize) {
+    ::metrics::counter!(TRANSACTIONS_PROVEN).increment(1);
+    ::metrics::histogram!(PROVING_TIME).record(proving_time.as_secs_f64());
+    ::metrics::histogram!(PROOF_SIZE).record(proof_size as f64);
+}
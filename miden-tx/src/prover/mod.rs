@@ -1,28 +1,100 @@
-use miden_lib::transaction::{ToTransactionKernelInputs, TransactionKernel};
+use miden_lib::transaction::ToTransactionKernelInputs;
 use miden_objects::{
     notes::Nullifier,
-    transaction::{InputNotes, ProvenTransaction, TransactionWitness},
+    transaction::{ExecutedTransaction, InputNotes, ProvenTransaction, TransactionWitness},
+    utils::collections::Vec,
+    vm::ProgramInfo,
 };
 use miden_prover::prove;
 pub use miden_prover::ProvingOptions;
 use vm_processor::MemAdviceProvider;
 
-use super::{TransactionHost, TransactionProverError};
+#[cfg(feature = "metrics")]
+use super::metrics;
+use super::{
+    executor::{build_account_details, extract_account_storage_delta},
+    host::LazyAdviceProvider,
+    ProvenTransactionMismatch, TransactionCompiler, TransactionHost, TransactionProverError,
+    TransactionVerifier,
+};
+
+mod options;
+pub use options::{ProofHashFunction, ProverOptions};
+
+mod remote;
+#[cfg(feature = "remote-prover")]
+pub use remote::HttpProvingService;
+pub use remote::ProvingRequest;
+#[cfg(feature = "async")]
+pub use remote::{ProvingService, RemoteTransactionProver};
 
-/// Transaction prover is a stateless component which is responsible for proving transactions.
+#[cfg(feature = "testing")]
+mod testing;
+#[cfg(feature = "testing")]
+pub use testing::TestProver;
+
+// TRANSACTION PROVER
+// ================================================================================================
+
+/// Transaction prover is responsible for proving transactions.
 ///
 /// Transaction prover exposes the `prove_transaction` method which takes a [TransactionWitness],
 /// or anything that can be converted into a [TransactionWitness], and returns a [ProvenTransaction].
+///
+/// The kernel-related preprocessing is performed once when the prover is created and is reused
+/// across proofs. This includes the [ProgramInfo] of the transaction kernel, against which the
+/// witnesses of proven transactions are checked before proving, and the [TransactionCompiler]
+/// used to compile the programs of proven transactions from their witnesses. Thus, a single
+/// prover should be used to generate many proofs (e.g., by a proving service); provers can be
+/// shared across threads.
 pub struct TransactionProver {
     proof_options: ProvingOptions,
+    kernel_info: ProgramInfo,
+    compiler: TransactionCompiler,
 }
 
 impl TransactionProver {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new [TransactionProver] instance.
-    pub fn new(proof_options: ProvingOptions) -> Self {
-        Self { proof_options }
+    /// Creates a new [TransactionProver] instance which generates proofs with the specified
+    /// options, i.e., either [ProverOptions] or the full [ProvingOptions] of the VM prover.
+    ///
+    /// The prover proves transactions compiled against the current version of the transaction
+    /// kernel.
+    pub fn new<O: Into<ProvingOptions>>(options: O) -> Self {
+        Self::with_compiler(options, TransactionCompiler::new())
+    }
+
+    /// Creates a new [TransactionProver] instance which generates proofs with the specified
+    /// options, and proves transactions compiled against the kernel version of the provided
+    /// compiler (see [TransactionCompiler::with_kernel()]).
+    ///
+    /// The compiler is also used to compile the programs of proven transactions. A clone of the
+    /// compiler used by an executor can be provided, in which case the executor and the prover
+    /// share the same assembler.
+    pub fn with_compiler<O: Into<ProvingOptions>>(
+        options: O,
+        compiler: TransactionCompiler,
+    ) -> Self {
+        Self {
+            proof_options: options.into(),
+            kernel_info: compiler.kernel().program_info().clone(),
+            compiler,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the [ProvingOptions] used by this prover.
+    pub fn proving_options(&self) -> &ProvingOptions {
+        &self.proof_options
+    }
+
+    /// Returns the [ProgramInfo] of the transaction kernel against which this prover proves
+    /// transactions.
+    pub fn kernel_info(&self) -> &ProgramInfo {
+        &self.kernel_info
     }
 
     // TRANSACTION PROVER
@@ -31,6 +103,8 @@ impl TransactionProver {
     /// Proves the provided transaction and returns a [ProvenTransaction].
     ///
     /// # Errors
+    /// - If the transaction was not executed with the transaction kernel of this prover.
+    /// - If the transaction program cannot be compiled from the transaction witness.
     /// - If the consumed note data in the transaction witness is corrupt.
     /// - If the transaction program cannot be proven.
     /// - If the transaction result is corrupt.
@@ -40,8 +114,22 @@ impl TransactionProver {
     ) -> Result<ProvenTransaction, TransactionProverError> {
         let tx_witness: TransactionWitness = transaction.into();
 
+        // make sure no proving work is wasted on programs which the verifier would reject
+        if tx_witness.kernel_hash() != self.compiler.kernel().hash() {
+            return Err(TransactionProverError::KernelMismatch(tx_witness.account().id()));
+        }
+
+        let program = self
+            .compiler
+            .compile_transaction_from_inputs(tx_witness.tx_inputs(), tx_witness.tx_script())
+            .map_err(|source| TransactionProverError::CompileTransactionFailed {
+                account_id: tx_witness.account().id(),
+                source,
+            })?;
+
         // extract required data from the transaction witness
-        let (stack_inputs, advice_inputs) = tx_witness.get_kernel_inputs();
+        let (stack_inputs, advice_inputs, vault_inputs) =
+            tx_witness.get_kernel_inputs_with_lazy_vault(self.compiler.kernel());
 
         let input_notes: InputNotes<Nullifier> = (tx_witness.tx_inputs().input_notes()).into();
 
@@ -50,17 +138,78 @@ impl TransactionProver {
         let block_hash = tx_witness.block_header().hash();
         let tx_script_root = tx_witness.tx_script().map(|script| *script.hash());
 
-        let advice_provider: MemAdviceProvider = advice_inputs.into();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "prove_transaction",
+            account_id = %account_id,
+            num_input_notes = input_notes.num_notes(),
+            num_output_notes = tracing::field::Empty
+        )
+        .entered();
+
+        let advice_provider = LazyAdviceProvider::new(
+            MemAdviceProvider::from(advice_inputs),
+            tx_witness.account().vault().commitment(),
+            vault_inputs,
+        );
         let mut host = TransactionHost::new(tx_witness.account().into(), advice_provider);
+
+        #[cfg(feature = "metrics")]
+        let proving_start = std::time::Instant::now();
+
         let (stack_outputs, proof) =
-            prove(tx_witness.program(), stack_inputs, &mut host, self.proof_options.clone())
-                .map_err(TransactionProverError::ProveTransactionProgramFailed)?;
+            prove(&program, stack_inputs, &mut host, self.proof_options.clone()).map_err(
+                |source| TransactionProverError::ProveTransactionProgramFailed {
+                    account_id,
+                    source,
+                },
+            )?;
+
+        #[cfg(feature = "metrics")]
+        {
+            use miden_objects::utils::serde::Serializable;
+            metrics::record_proof(proving_start.elapsed(), proof.to_bytes().len());
+        }
 
         // extract transaction outputs and process transaction data
-        let (advice_provider, _event_handler) = host.into_parts();
-        let (_, map, _) = advice_provider.into_parts();
-        let tx_outputs = TransactionKernel::parse_transaction_outputs(&stack_outputs, &map.into())
-            .map_err(TransactionProverError::InvalidTransactionOutput)?;
+        let (advice_provider, vault_delta, storage_updates, _) = host.into_parts();
+        let (advice_provider, _) = advice_provider.into_parts();
+        let (_, map, store) = advice_provider.into_parts();
+        let tx_outputs = self
+            .compiler
+            .kernel()
+            .parse_transaction_outputs(&stack_outputs, &map.into())
+            .map_err(|source| TransactionProverError::InvalidTransactionOutput {
+                account_id,
+                source,
+            })?;
+
+        // build the full state of the account if the transaction created a new on-chain account
+        let account_details = if tx_witness.account().is_new() {
+            extract_account_storage_delta(
+                &store,
+                tx_witness.account(),
+                &tx_outputs.account,
+                &storage_updates,
+            )
+            .and_then(|storage_delta| {
+                build_account_details(
+                    tx_witness.account(),
+                    &tx_outputs.account,
+                    &storage_delta,
+                    &vault_delta,
+                )
+            })
+            .map_err(|source| TransactionProverError::InvalidTransactionOutput {
+                account_id,
+                source,
+            })?
+        } else {
+            None
+        };
+
+        #[cfg(feature = "tracing")]
+        _span.record("num_output_notes", tx_outputs.output_notes.num_notes());
 
         Ok(ProvenTransaction::new(
             account_id,
@@ -70,7 +219,105 @@ impl TransactionProver {
             tx_outputs.output_notes.into(),
             tx_script_root,
             block_hash,
+            tx_witness.kernel_hash(),
+            account_details,
+            tx_outputs.expiration_block_num,
+            tx_outputs.fee,
             proof,
         ))
     }
+
+    /// Proves the provided executed transaction, checks that the outputs of the resulting
+    /// [ProvenTransaction] match the outputs of the executed transaction, and verifies the proof.
+    ///
+    /// This catches proofs which would be rejected downstream, or which prove a different state
+    /// transition than the one which was executed (e.g., because the advice provided to the prover
+    /// differs from the advice the transaction was executed with), at proving time, at the cost of
+    /// verifying every generated proof. The proof is verified regardless of its security level,
+    /// which is determined by the options of this prover.
+    ///
+    /// # Errors
+    /// - If the transaction cannot be proven (see [TransactionProver::prove_transaction()]).
+    /// - If the final account hash, output notes commitment, expiration block number, or fee of
+    ///   the proven transaction differ from those of the executed transaction.
+    /// - If the proof of the transaction fails to verify.
+    pub fn prove_and_validate(
+        &self,
+        transaction: ExecutedTransaction,
+    ) -> Result<ProvenTransaction, TransactionProverError> {
+        let proven_tx = self.prove_transaction(transaction.clone())?;
+        self.validate_proven_transaction(proven_tx, &transaction)
+    }
+
+    /// Checks that the outputs of the provided [ProvenTransaction] match the outputs of the
+    /// executed transaction it was proven from, and verifies its proof.
+    ///
+    /// The remaining public inputs of the proven transaction (i.e., the initial account hash, the
+    /// input notes, the reference block, and the transaction script root) are not compared, as
+    /// the prover reads them from the witness of the executed transaction.
+    pub(crate) fn validate_proven_transaction(
+        &self,
+        proven_tx: ProvenTransaction,
+        transaction: &ExecutedTransaction,
+    ) -> Result<ProvenTransaction, TransactionProverError> {
+        let account_id = transaction.account_id();
+        let final_account_hash = transaction.final_account().hash();
+        let output_notes_commitment = transaction.output_notes().commitment();
+        let expiration_block_num = transaction.expiration_block_num();
+        let fee = transaction.fee();
+
+        let mut mismatches = Vec::new();
+        if proven_tx.final_account_hash() != final_account_hash {
+            mismatches.push(ProvenTransactionMismatch::FinalAccountHash {
+                expected: final_account_hash,
+                actual: proven_tx.final_account_hash(),
+            });
+        }
+        if proven_tx.output_notes().commitment() != output_notes_commitment {
+            mismatches.push(ProvenTransactionMismatch::OutputNotesCommitment {
+                expected: output_notes_commitment,
+                actual: proven_tx.output_notes().commitment(),
+            });
+        }
+        if proven_tx.expiration_block_num() != expiration_block_num {
+            mismatches.push(ProvenTransactionMismatch::ExpirationBlockNum {
+                expected: expiration_block_num,
+                actual: proven_tx.expiration_block_num(),
+            });
+        }
+        if proven_tx.fee() != fee {
+            mismatches
+                .push(ProvenTransactionMismatch::Fee { expected: fee, actual: proven_tx.fee() });
+        }
+        if !mismatches.is_empty() {
+            return Err(TransactionProverError::ProvenTransactionMismatch {
+                account_id,
+                mismatches,
+            });
+        }
+
+        TransactionVerifier::with_kernels(0, vec![self.compiler.kernel().clone()])
+            .verify(proven_tx.clone())
+            .map_err(|source| TransactionProverError::ProofVerificationFailed {
+                account_id,
+                source,
+            })?;
+
+        Ok(proven_tx)
+    }
+
+    /// Proves the transaction described by the provided [ProvingRequest].
+    ///
+    /// The transaction program is compiled by the [TransactionCompiler] of this prover, thus
+    /// account interfaces loaded while serving previous requests are reused.
+    ///
+    /// # Errors
+    /// Returns an error if the transaction cannot be proven (see
+    /// [TransactionProver::prove_transaction()]).
+    pub fn prove_request(
+        &self,
+        request: ProvingRequest,
+    ) -> Result<ProvenTransaction, TransactionProverError> {
+        self.prove_transaction(request.into_witness())
+    }
 }
@@ -0,0 +1,115 @@
+/// The hash function used by the VM prover to commit to the execution trace of a transaction.
+///
+/// This is distinct from the hash function used by the transaction kernel (i.e., RPO256), which
+/// is fixed.
+pub use miden_prover::HashFunction as ProofHashFunction;
+
+use super::ProvingOptions;
+use crate::ProverOptionsError;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of FRI queries used by [ProverOptions]; together with the default blowup and grinding
+/// factors, this provides 96 bits of conjectured security.
+const NUM_QUERIES: usize = 27;
+
+/// Blowup factor used by [ProverOptions] unless another one is selected.
+const DEFAULT_BLOWUP_FACTOR: usize = 8;
+
+/// Grinding factor used by [ProverOptions].
+const GRINDING_FACTOR: u32 = 16;
+
+/// FRI folding factor used by [ProverOptions].
+const FRI_FOLDING_FACTOR: usize = 8;
+
+/// Maximum degree of the FRI remainder polynomial used by [ProverOptions].
+const FRI_REMAINDER_MAX_DEGREE: usize = 255;
+
+// PROVER OPTIONS
+// ================================================================================================
+
+/// Options of the [TransactionProver](super::TransactionProver) which are commonly tuned by
+/// operators: the hash function used to commit to the execution trace and the blowup factor of
+/// the low-degree extension of the trace.
+///
+/// By default, proofs are generated with [ProofHashFunction::Blake3_192] and the smallest blowup
+/// factor supported by the VM AIR, which matches [ProvingOptions::default()]. Proofs which are
+/// verified recursively should use [ProofHashFunction::Rpo256]. Increasing the blowup factor makes
+/// proofs smaller and more secure at the cost of slower proof generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProverOptions {
+    hash_fn: ProofHashFunction,
+    blowup_factor: usize,
+}
+
+impl ProverOptions {
+    /// The smallest blowup factor supported by the constraint degree of the VM AIR.
+    pub const MIN_BLOWUP_FACTOR: usize = 8;
+
+    /// The largest supported blowup factor.
+    pub const MAX_BLOWUP_FACTOR: usize = 128;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns new [ProverOptions] with the default hash function and blowup factor.
+    pub fn new() -> Self {
+        Self {
+            hash_fn: ProofHashFunction::Blake3_192,
+            blowup_factor: DEFAULT_BLOWUP_FACTOR,
+        }
+    }
+
+    /// Sets the hash function used to generate proofs.
+    pub fn with_hash_fn(mut self, hash_fn: ProofHashFunction) -> Self {
+        self.hash_fn = hash_fn;
+        self
+    }
+
+    /// Sets the blowup factor used to generate proofs.
+    ///
+    /// # Errors
+    /// Returns an error if the blowup factor is not a power of two between
+    /// [ProverOptions::MIN_BLOWUP_FACTOR] and [ProverOptions::MAX_BLOWUP_FACTOR].
+    pub fn with_blowup_factor(mut self, blowup_factor: usize) -> Result<Self, ProverOptionsError> {
+        if !blowup_factor.is_power_of_two()
+            || !(Self::MIN_BLOWUP_FACTOR..=Self::MAX_BLOWUP_FACTOR).contains(&blowup_factor)
+        {
+            return Err(ProverOptionsError::InvalidBlowupFactor(blowup_factor));
+        }
+        self.blowup_factor = blowup_factor;
+        Ok(self)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the hash function used to generate proofs.
+    pub fn hash_fn(&self) -> ProofHashFunction {
+        self.hash_fn
+    }
+
+    /// Returns the blowup factor used to generate proofs.
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+}
+
+impl Default for ProverOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<ProverOptions> for ProvingOptions {
+    fn from(options: ProverOptions) -> Self {
+        ProvingOptions::new(
+            NUM_QUERIES,
+            options.blowup_factor,
+            GRINDING_FACTOR,
+            FRI_FOLDING_FACTOR,
+            FRI_REMAINDER_MAX_DEGREE,
+            options.hash_fn,
+        )
+    }
+}
@@ -0,0 +1,319 @@
+#[cfg(feature = "async")]
+use miden_objects::transaction::ProvenTransaction;
+use miden_objects::{
+    transaction::{
+        read_advice_inputs, write_advice_inputs, TransactionInputs, TransactionScript,
+        TransactionWitness,
+    },
+    utils::serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Digest, Word,
+};
+use vm_processor::AdviceInputs;
+
+#[cfg(feature = "async")]
+use crate::RemoteProverError;
+#[cfg(feature = "async")]
+use crate::TransactionProverError;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the proving request format.
+const PROVING_REQUEST_FORMAT_VERSION: u8 = 1;
+
+// PROVING SERVICE
+// ================================================================================================
+
+/// The [ProvingService] trait defines the transport used by a [RemoteTransactionProver] to reach
+/// a remote proving service.
+///
+/// Implementations send the serialized [ProvingRequest] to the service and return the serialized
+/// [ProvenTransaction] produced by the service. `HttpProvingService` (available with the
+/// `remote-prover` feature) implements the trait for services exposed over HTTP; other network
+/// stacks can be supported by implementing the trait directly.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait ProvingService: Send + Sync {
+    /// Sends the provided serialized [ProvingRequest] to the remote service and returns the
+    /// serialized [ProvenTransaction] received in response.
+    ///
+    /// # Errors
+    /// Returns an error if the service cannot be reached or if it fails to prove the transaction.
+    async fn prove(&self, request: Vec<u8>) -> Result<Vec<u8>, RemoteProverError>;
+}
+
+// HTTP PROVING SERVICE
+// ================================================================================================
+
+/// A [ProvingService] which reaches the remote proving service over HTTP.
+///
+/// Proving requests are sent as the body of a `POST` request to the endpoint of the service with
+/// the `application/octet-stream` content type, and the service is expected to respond with the
+/// serialized [ProvenTransaction] as the body of a successful response. The body of an
+/// unsuccessful response is reported as the error of the service.
+#[cfg(feature = "remote-prover")]
+#[derive(Clone, Debug)]
+pub struct HttpProvingService {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+#[cfg(feature = "remote-prover")]
+impl HttpProvingService {
+    /// Returns a new [HttpProvingService] which sends proving requests to the specified URL.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self::with_client(reqwest::Client::new(), endpoint)
+    }
+
+    /// Returns a new [HttpProvingService] which sends proving requests to the specified URL using
+    /// the provided client (e.g., a client configured with timeouts or TLS settings).
+    pub fn with_client(client: reqwest::Client, endpoint: impl Into<String>) -> Self {
+        Self { client, endpoint: endpoint.into() }
+    }
+
+    /// Returns the URL to which proving requests are sent.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+#[cfg(feature = "remote-prover")]
+#[async_trait::async_trait]
+impl ProvingService for HttpProvingService {
+    async fn prove(&self, request: Vec<u8>) -> Result<Vec<u8>, RemoteProverError> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(request)
+            .send()
+            .await
+            .map_err(|err| RemoteProverError::ConnectionFailed(err.to_string()))?;
+
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|err| RemoteProverError::ConnectionFailed(err.to_string()))?;
+        if !status.is_success() {
+            let message = String::from_utf8_lossy(&body);
+            return Err(RemoteProverError::ServiceError(format!("{status}: {message}")));
+        }
+
+        Ok(body.to_vec())
+    }
+}
+
+// REMOTE TRANSACTION PROVER
+// ================================================================================================
+
+/// Transaction prover which delegates proof generation to a remote proving service.
+///
+/// The remote prover exposes an asynchronous counterpart of the `prove_transaction` method of the
+/// [TransactionProver](super::TransactionProver): it takes a [TransactionWitness], or anything that
+/// can be converted into a [TransactionWitness], and returns a [ProvenTransaction]. This makes it
+/// possible for devices which cannot generate STARK proofs in reasonable time (e.g., browser and
+/// mobile wallets) to execute transactions locally and offload only the proving.
+///
+/// The proven transaction returned by the service is checked to describe the transaction defined
+/// by the witness; however, the proof itself is not verified, thus callers who do not trust the
+/// service should verify it via [TransactionVerifier](crate::TransactionVerifier).
+#[cfg(feature = "async")]
+pub struct RemoteTransactionProver<S> {
+    service: S,
+}
+
+#[cfg(feature = "async")]
+impl<S: ProvingService> RemoteTransactionProver<S> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new [RemoteTransactionProver] which sends proving requests via the specified
+    /// [ProvingService].
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+
+    // TRANSACTION PROVER
+    // --------------------------------------------------------------------------------------------
+
+    /// Proves the provided transaction using the remote proving service and returns a
+    /// [ProvenTransaction].
+    ///
+    /// # Errors
+    /// - If the remote service cannot be reached or fails to prove the transaction.
+    /// - If the response of the service is not a valid [ProvenTransaction].
+    /// - If the proven transaction returned by the service does not match the provided
+    ///   transaction.
+    pub async fn prove_transaction<T: Into<TransactionWitness>>(
+        &self,
+        transaction: T,
+    ) -> Result<ProvenTransaction, TransactionProverError> {
+        let tx_witness: TransactionWitness = transaction.into();
+        let account_id = tx_witness.account().id();
+
+        let request = ProvingRequest::from(&tx_witness);
+        let response = self.service.prove(request.to_bytes());
+        #[cfg(feature = "tracing")]
+        let response = tracing::Instrument::instrument(
+            response,
+            tracing::info_span!("prove_transaction_remotely", account_id = %account_id),
+        );
+        let response = response
+            .await
+            .map_err(|source| TransactionProverError::RemoteProvingFailed { account_id, source })?;
+
+        let proven_tx = ProvenTransaction::read_from_bytes(&response).map_err(|err| {
+            TransactionProverError::RemoteProvingFailed {
+                account_id,
+                source: RemoteProverError::InvalidResponse(err),
+            }
+        })?;
+
+        if !describes_transaction(&proven_tx, &tx_witness) {
+            return Err(TransactionProverError::RemoteProvingFailed {
+                account_id,
+                source: RemoteProverError::ProvenTransactionMismatch(proven_tx.id()),
+            });
+        }
+
+        Ok(proven_tx)
+    }
+}
+
+// PROVING REQUEST
+// ================================================================================================
+
+/// A request to prove a transaction sent to a remote proving service.
+///
+/// The request contains everything needed to rebuild the [TransactionWitness] of the transaction:
+/// - The commitment to the transaction kernel the transaction was executed with.
+/// - The transaction inputs (account, block header, chain MMR, and input notes).
+/// - The transaction script (if any), including its inputs, and the arguments it was executed
+///   with.
+/// - The advice witness recorded during the execution of the transaction.
+///
+/// The transaction program is not part of the request; instead, it is compiled by the service
+/// when proving the [TransactionWitness] returned by [ProvingRequest::into_witness()].
+#[derive(Clone, Debug)]
+pub struct ProvingRequest {
+    kernel_hash: Digest,
+    tx_inputs: TransactionInputs,
+    tx_script: Option<TransactionScript>,
+    tx_script_args: Word,
+    advice_witness: AdviceInputs,
+}
+
+impl ProvingRequest {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the hash of the main code block of the kernel the transaction was executed with.
+    pub fn kernel_hash(&self) -> Digest {
+        self.kernel_hash
+    }
+
+    /// Returns the inputs of the transaction to be proven.
+    pub fn tx_inputs(&self) -> &TransactionInputs {
+        &self.tx_inputs
+    }
+
+    /// Returns the transaction script of the transaction to be proven, if any.
+    pub fn tx_script(&self) -> Option<&TransactionScript> {
+        self.tx_script.as_ref()
+    }
+
+    /// Returns the arguments the transaction script of the transaction to be proven was executed
+    /// with.
+    pub fn tx_script_args(&self) -> Word {
+        self.tx_script_args
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the [TransactionWitness] described by this request.
+    ///
+    /// This is intended to be used by proving services to handle incoming requests, e.g., by
+    /// passing the returned witness to [TransactionProver](super::TransactionProver), which
+    /// rejects witnesses of transactions executed with a different transaction kernel.
+    pub fn into_witness(self) -> TransactionWitness {
+        TransactionWitness::new(
+            self.kernel_hash,
+            self.tx_inputs,
+            self.tx_script,
+            self.tx_script_args,
+            self.advice_witness,
+        )
+    }
+}
+
+impl From<&TransactionWitness> for ProvingRequest {
+    fn from(tx_witness: &TransactionWitness) -> Self {
+        Self {
+            kernel_hash: tx_witness.kernel_hash(),
+            tx_inputs: tx_witness.tx_inputs().clone(),
+            tx_script: tx_witness.tx_script().cloned(),
+            tx_script_args: tx_witness.tx_script_args(),
+            advice_witness: tx_witness.advice_witness().clone(),
+        }
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for ProvingRequest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(PROVING_REQUEST_FORMAT_VERSION);
+        self.kernel_hash.write_into(target);
+        self.tx_inputs.write_into(target);
+        self.tx_script.write_into(target);
+        self.tx_script_args.write_into(target);
+
+        write_advice_inputs(&self.advice_witness, target);
+    }
+}
+
+impl Deserializable for ProvingRequest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        if version != PROVING_REQUEST_FORMAT_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported proving request format version {version}"
+            )));
+        }
+
+        let kernel_hash = Digest::read_from(source)?;
+        let tx_inputs = TransactionInputs::read_from(source)?;
+        let tx_script = Option::<TransactionScript>::read_from(source)?;
+        let tx_script_args = Word::read_from(source)?;
+        let advice_witness = read_advice_inputs(source)?;
+
+        Ok(Self {
+            kernel_hash,
+            tx_inputs,
+            tx_script,
+            tx_script_args,
+            advice_witness,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the provided proven transaction was produced for the transaction defined by
+/// the provided witness.
+#[cfg(feature = "async")]
+///
+/// The final state of the account and the output notes are not part of the witness, thus they
+/// are bound to the transaction only by the proof.
+fn describes_transaction(proven_tx: &ProvenTransaction, tx_witness: &TransactionWitness) -> bool {
+    let nullifiers = tx_witness.input_notes().iter().map(|note| note.nullifier());
+
+    proven_tx.account_id() == tx_witness.account().id()
+        && proven_tx.initial_account_hash() == tx_witness.account().hash()
+        && proven_tx.block_ref() == tx_witness.block_header().hash()
+        && proven_tx.tx_script_root() == tx_witness.tx_script().map(|script| *script.hash())
+        && proven_tx.input_notes().iter().copied().eq(nullifiers)
+}
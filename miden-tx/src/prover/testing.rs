@@ -0,0 +1,83 @@
+use super::{ProofHashFunction, ProvingOptions, TransactionProver, TransactionProverError};
+use miden_objects::transaction::{ProvenTransaction, TransactionWitness};
+
+// TEST PROVER
+// ================================================================================================
+
+/// Number of FRI queries used by the [TestProver].
+const TEST_NUM_QUERIES: usize = 2;
+
+/// Blowup factor used by the [TestProver]. This is the smallest blowup factor supported by the
+/// constraint degree of the VM AIR.
+const TEST_BLOWUP_FACTOR: usize = 8;
+
+/// Grinding factor used by the [TestProver].
+const TEST_GRINDING_FACTOR: u32 = 0;
+
+/// FRI folding factor used by the [TestProver].
+const TEST_FRI_FOLDING_FACTOR: usize = 8;
+
+/// Maximum degree of the FRI remainder polynomial used by the [TestProver].
+const TEST_FRI_REMAINDER_MAX_DEGREE: usize = 255;
+
+/// Transaction prover which generates proofs with minimal security parameters.
+///
+/// Proofs produced by this prover offer essentially no soundness guarantees, but they are
+/// generated much faster than proofs produced with the default [ProvingOptions]. This makes it
+/// possible to exercise the full execute -> prove -> verify pipeline in tests.
+///
+/// Proofs generated by this prover can be verified by a verifier instantiated via
+/// [TransactionVerifier::new_insecure()](crate::TransactionVerifier::new_insecure).
+///
+/// This prover must never be used outside of tests.
+pub struct TestProver {
+    prover: TransactionProver,
+}
+
+impl TestProver {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new [TestProver] instance.
+    pub fn new() -> Self {
+        Self {
+            prover: TransactionProver::new(Self::proving_options()),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the [ProvingOptions] used by this prover.
+    pub fn proving_options() -> ProvingOptions {
+        ProvingOptions::new(
+            TEST_NUM_QUERIES,
+            TEST_BLOWUP_FACTOR,
+            TEST_GRINDING_FACTOR,
+            TEST_FRI_FOLDING_FACTOR,
+            TEST_FRI_REMAINDER_MAX_DEGREE,
+            ProofHashFunction::Blake3_192,
+        )
+    }
+
+    // TRANSACTION PROVER
+    // --------------------------------------------------------------------------------------------
+
+    /// Proves the provided transaction with minimal security parameters and returns a
+    /// [ProvenTransaction].
+    ///
+    /// # Errors
+    /// Returns an error under the same conditions as
+    /// [TransactionProver::prove_transaction()].
+    pub fn prove_transaction<T: Into<TransactionWitness>>(
+        &self,
+        transaction: T,
+    ) -> Result<ProvenTransaction, TransactionProverError> {
+        self.prover.prove_transaction(transaction)
+    }
+}
+
+impl Default for TestProver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
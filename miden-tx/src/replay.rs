@@ -0,0 +1,358 @@
+//! Deterministic replay of transaction executions.
+//!
+//! An executor built with [TransactionExecutorBuilder::with_replay_recording()] writes a
+//! [TransactionReplay] artifact for every transaction it executes (regardless of whether the
+//! execution succeeded). The artifact contains everything needed to re-run the transaction
+//! exactly as it was executed, without access to the original [DataStore] or
+//! [TransactionAuthenticator]:
+//! - The commitment to the transaction kernel the transaction was executed with.
+//! - The transaction inputs (account, block header, chain MMR, and input notes).
+//! - The transaction script (if any), including its inputs.
+//! - The initial advice inputs the transaction was executed with (i.e., the data derived from the
+//!   transaction inputs, the transaction script inputs, and the data of foreign accounts).
+//! - The signatures produced by the authenticator during execution.
+//!
+//! Transaction execution is otherwise fully deterministic: the only source of non-determinism is
+//! the randomness used by the authenticator when signing, and since signatures are recorded
+//! verbatim, no RNG seed needs to be stored.
+//!
+//! A recorded transaction can be re-executed via [replay()].
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::AccountId,
+    assembly::ModuleAst,
+    notes::NoteId,
+    transaction::{
+        read_advice_inputs, write_advice_inputs, ExecutedTransaction, TransactionInputs,
+        TransactionScript,
+    },
+    utils::{
+        collections::{BTreeMap, Vec},
+        serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+        sync::Arc,
+    },
+    Digest, Felt, Hasher, Word,
+};
+use vm_core::utils::to_hex;
+use vm_processor::{AdviceInputs, SignatureKind};
+
+use crate::{
+    AuthenticationError, DataStore, DataStoreError, TransactionAuthenticator, TransactionExecutor,
+    TransactionReplayError,
+};
+
+#[cfg(doc)]
+use crate::TransactionExecutorBuilder;
+
+// CONSTANTS
+// ================================================================================================
+
+/// File extension of transaction replay artifacts.
+pub const REPLAY_FILE_EXTENSION: &str = "mtxr";
+
+/// Version of the replay artifact format.
+const REPLAY_FORMAT_VERSION: u8 = 3;
+
+// TRANSACTION REPLAY
+// ================================================================================================
+
+/// A self-contained record of a transaction execution which can be used to re-run the
+/// transaction deterministically.
+#[derive(Clone, Debug)]
+pub struct TransactionReplay {
+    kernel_hash: Digest,
+    tx_inputs: TransactionInputs,
+    tx_script: Option<TransactionScript>,
+    tx_script_args: Word,
+    advice_inputs: AdviceInputs,
+    signatures: BTreeMap<(Digest, Digest), Vec<Felt>>,
+}
+
+impl TransactionReplay {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [TransactionReplay] of a transaction executed with the current transaction
+    /// kernel against the provided inputs, transaction script and its arguments, and initial
+    /// advice inputs.
+    ///
+    /// `signatures` maps `(pub_key, message)` pairs to the signatures produced by the
+    /// authenticator during execution.
+    pub fn new(
+        tx_inputs: TransactionInputs,
+        tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
+        advice_inputs: AdviceInputs,
+        signatures: BTreeMap<(Digest, Digest), Vec<Felt>>,
+    ) -> Self {
+        Self {
+            kernel_hash: TransactionKernel::main_code_block().hash(),
+            tx_inputs,
+            tx_script,
+            tx_script_args,
+            advice_inputs,
+            signatures,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the hash of the main code block of the kernel the transaction was executed with.
+    pub fn kernel_hash(&self) -> Digest {
+        self.kernel_hash
+    }
+
+    /// Returns the inputs of the recorded transaction.
+    pub fn tx_inputs(&self) -> &TransactionInputs {
+        &self.tx_inputs
+    }
+
+    /// Returns the transaction script of the recorded transaction, if any.
+    pub fn tx_script(&self) -> Option<&TransactionScript> {
+        self.tx_script.as_ref()
+    }
+
+    /// Returns the arguments the transaction script of the recorded transaction was executed with.
+    pub fn tx_script_args(&self) -> Word {
+        self.tx_script_args
+    }
+
+    /// Returns the initial advice inputs of the recorded transaction.
+    pub fn advice_inputs(&self) -> &AdviceInputs {
+        &self.advice_inputs
+    }
+
+    /// Returns the signatures produced during the recorded execution, keyed by
+    /// `(pub_key, message)`.
+    pub fn signatures(&self) -> &BTreeMap<(Digest, Digest), Vec<Felt>> {
+        &self.signatures
+    }
+
+    // REPLAY
+    // --------------------------------------------------------------------------------------------
+
+    /// Re-executes the recorded transaction against the recorded advice inputs and returns the
+    /// resulting [ExecutedTransaction].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The transaction was recorded with a different transaction kernel.
+    /// - The transaction could not be executed.
+    pub fn execute(&self) -> Result<ExecutedTransaction, TransactionReplayError> {
+        let expected = TransactionKernel::main_code_block().hash();
+        if self.kernel_hash != expected {
+            return Err(TransactionReplayError::KernelMismatch {
+                expected,
+                actual: self.kernel_hash,
+            });
+        }
+
+        let account_id = self.tx_inputs.account().id();
+        let executor = TransactionExecutor::builder()
+            .with_data_store(ReplayDataStore { tx_inputs: self.tx_inputs.clone() })
+            .with_authenticator(Arc::new(ReplayAuthenticator {
+                signatures: self.signatures.clone(),
+            }))
+            .build();
+        executor
+            .load_account(account_id)
+            .map_err(TransactionReplayError::ExecutionFailed)?;
+        executor
+            .replay_transaction(
+                self.tx_inputs.clone(),
+                self.tx_script.clone(),
+                self.tx_script_args,
+                self.advice_inputs.clone(),
+            )
+            .map_err(TransactionReplayError::ExecutionFailed)
+    }
+
+    // FILE IO
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes this replay into a new file in the specified directory and returns the path of the
+    /// file.
+    ///
+    /// The name of the file is derived from the ID of the account and the hash of the serialized
+    /// replay, thus recording the same execution twice results in the same file.
+    pub fn write_to_dir(&self, dir: impl AsRef<Path>) -> Result<PathBuf, TransactionReplayError> {
+        let bytes = self.to_bytes();
+        let hash = Hasher::hash(&bytes);
+        let file_name = format!(
+            "{}-{}.{REPLAY_FILE_EXTENSION}",
+            self.tx_inputs.account().id(),
+            to_hex(&hash.as_bytes()).expect("hex formatting failed")
+        );
+
+        let path = dir.as_ref().join(file_name);
+        fs::create_dir_all(dir.as_ref()).map_err(TransactionReplayError::IoError)?;
+        fs::write(&path, bytes).map_err(TransactionReplayError::IoError)?;
+        Ok(path)
+    }
+
+    /// Reads a replay from the file at the specified path.
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<Self, TransactionReplayError> {
+        let bytes = fs::read(path).map_err(TransactionReplayError::IoError)?;
+        Self::read_from_bytes(&bytes).map_err(TransactionReplayError::DeserializationFailed)
+    }
+}
+
+/// Reads the replay artifact at the specified path and re-executes the recorded transaction.
+///
+/// # Errors
+/// Returns an error if the artifact cannot be read, or if replaying it fails (see
+/// [TransactionReplay::execute()]).
+pub fn replay(path: impl AsRef<Path>) -> Result<ExecutedTransaction, TransactionReplayError> {
+    TransactionReplay::read_from_file(path)?.execute()
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for TransactionReplay {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(REPLAY_FORMAT_VERSION);
+        self.kernel_hash.write_into(target);
+        self.tx_inputs.write_into(target);
+        self.tx_script.write_into(target);
+        self.tx_script_args.write_into(target);
+        write_advice_inputs(&self.advice_inputs, target);
+        target.write_u64(self.signatures.len() as u64);
+        for ((pub_key, message), signature) in self.signatures.iter() {
+            pub_key.write_into(target);
+            message.write_into(target);
+            target.write_u64(signature.len() as u64);
+            signature.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for TransactionReplay {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        if version != REPLAY_FORMAT_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported replay format version {version}"
+            )));
+        }
+
+        let kernel_hash = Digest::read_from(source)?;
+        let tx_inputs = TransactionInputs::read_from(source)?;
+        let tx_script = Option::<TransactionScript>::read_from(source)?;
+        let tx_script_args = Word::read_from(source)?;
+        let advice_inputs = read_advice_inputs(source)?;
+        let num_signatures = source.read_u64()?;
+        let mut signatures = BTreeMap::new();
+        for _ in 0..num_signatures {
+            let pub_key = Digest::read_from(source)?;
+            let message = Digest::read_from(source)?;
+            let len = source.read_u64()? as usize;
+            signatures.insert((pub_key, message), Felt::read_batch_from(source, len)?);
+        }
+
+        Ok(Self {
+            kernel_hash,
+            tx_inputs,
+            tx_script,
+            tx_script_args,
+            advice_inputs,
+            signatures,
+        })
+    }
+}
+
+// SIGNATURE RECORDER
+// ================================================================================================
+
+/// A [TransactionAuthenticator] which forwards signature requests to an inner authenticator and
+/// records the produced signatures.
+pub(crate) struct SignatureRecorder {
+    inner: Option<Arc<dyn TransactionAuthenticator>>,
+    signatures: Mutex<BTreeMap<(Digest, Digest), Vec<Felt>>>,
+}
+
+impl SignatureRecorder {
+    pub(crate) fn new(inner: Option<Arc<dyn TransactionAuthenticator>>) -> Self {
+        Self {
+            inner,
+            signatures: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the signatures recorded so far.
+    pub(crate) fn signatures(&self) -> BTreeMap<(Digest, Digest), Vec<Felt>> {
+        self.signatures.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+}
+
+impl TransactionAuthenticator for SignatureRecorder {
+    fn get_signature(
+        &self,
+        kind: SignatureKind,
+        pub_key: Word,
+        message: Word,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        let inner = self.inner.as_ref().ok_or(AuthenticationError::UnknownKey(pub_key.into()))?;
+        let signature = inner.get_signature(kind, pub_key, message)?;
+        self.signatures
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert((pub_key.into(), message.into()), signature.clone());
+        Ok(signature)
+    }
+}
+
+// REPLAY DATA SOURCES
+// ================================================================================================
+
+/// A [DataStore] which serves the recorded transaction inputs.
+struct ReplayDataStore {
+    tx_inputs: TransactionInputs,
+}
+
+impl DataStore for ReplayDataStore {
+    fn get_transaction_inputs(
+        &self,
+        account_id: AccountId,
+        _block_ref: u32,
+        _notes: &[NoteId],
+    ) -> Result<TransactionInputs, DataStoreError> {
+        if account_id != self.tx_inputs.account().id() {
+            return Err(DataStoreError::AccountNotFound(account_id));
+        }
+        Ok(self.tx_inputs.clone())
+    }
+
+    fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError> {
+        if account_id != self.tx_inputs.account().id() {
+            return Err(DataStoreError::AccountNotFound(account_id));
+        }
+        Ok(self.tx_inputs.account().code().module().clone())
+    }
+}
+
+/// A [TransactionAuthenticator] which answers signature requests with the recorded signatures.
+struct ReplayAuthenticator {
+    signatures: BTreeMap<(Digest, Digest), Vec<Felt>>,
+}
+
+impl TransactionAuthenticator for ReplayAuthenticator {
+    fn get_signature(
+        &self,
+        _kind: SignatureKind,
+        pub_key: Word,
+        message: Word,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        self.signatures
+            .get(&(pub_key.into(), message.into()))
+            .cloned()
+            .ok_or(AuthenticationError::UnknownKey(pub_key.into()))
+    }
+}
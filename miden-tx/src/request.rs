@@ -0,0 +1,440 @@
+use miden_lib::notes::{create_bridge_note, create_note, BridgeMessage, Script};
+use miden_objects::{
+    accounts::AccountId,
+    assembly::ProgramAst,
+    assets::{Asset, FungibleAsset},
+    notes::{Note, NoteId},
+    transaction::{ExecutedTransaction, TransactionScriptInputs, MAX_OUTPUT_NOTES_PER_TRANSACTION},
+    utils::{
+        collections::{BTreeMap, BTreeSet, Vec},
+        string::{String, ToString},
+    },
+    Felt, Hasher, StarkField, Word, ZERO,
+};
+
+use super::{TransactionEffectMismatch, TransactionRequestError};
+
+// TRANSACTION REQUEST
+// ================================================================================================
+
+/// A high-level description of a transaction in terms of the intents of the account owner.
+///
+/// A transaction request is built against a single account and can express the following intents:
+/// - Pay an asset from the vault of the account to another account via a P2ID note.
+/// - Bridge a fungible asset from the vault of the account to another chain via a BRIDGE note.
+/// - Mint an amount of the fungible asset issued by the account (which must be a fungible faucet)
+///   and send it to another account via a P2ID note.
+/// - Consume a set of notes.
+///
+/// From these intents, the request derives the transaction script to be executed, the list of
+/// notes to be consumed, and the notes expected to be created by the transaction. The generated
+/// script calls the interfaces of the basic wallet and the basic fungible faucet provided by
+/// miden-lib, and authenticates the transaction via `auth_tx_rpo_falcon512`.
+///
+/// In addition to the intents, callers can attach the effects they expect the transaction to have
+/// (i.e., additional notes to be created and final balances of fungible assets in the account
+/// vault). When the request is executed, the resulting transaction is checked against the
+/// expected effects via [TransactionRequest::check_effects()]. Notes created by the intents of a
+/// request are always expected, and the transaction must not create any notes which were not
+/// expected.
+///
+/// A request can be executed via
+/// [TransactionExecutor::execute_request()](crate::TransactionExecutor::execute_request).
+#[derive(Clone, Debug)]
+pub struct TransactionRequest {
+    account_id: AccountId,
+    input_notes: Vec<NoteId>,
+    intents: Vec<OutputIntent>,
+    script_inputs: TransactionScriptInputs,
+    expected_notes: Vec<NoteId>,
+    expected_balances: BTreeMap<AccountId, u64>,
+}
+
+/// An intent which results in an output note being created by the transaction.
+#[derive(Clone, Debug)]
+enum OutputIntent {
+    Pay { asset: Asset, note: Note },
+    Mint { amount: u64, note: Note },
+}
+
+impl TransactionRequest {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty [TransactionRequest] against the account with the specified ID.
+    pub fn new(account_id: AccountId) -> Self {
+        Self {
+            account_id,
+            input_notes: Vec::new(),
+            intents: Vec::new(),
+            script_inputs: TransactionScriptInputs::new(),
+            expected_notes: Vec::new(),
+            expected_balances: BTreeMap::new(),
+        }
+    }
+
+    // INTENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds an intent to pay the specified asset to the `target` account.
+    ///
+    /// The asset is sent via a P2ID note with the specified serial number.
+    ///
+    /// # Errors
+    /// Returns an error if the P2ID note could not be created.
+    pub fn pay_to_id(
+        mut self,
+        asset: Asset,
+        target: AccountId,
+        serial_num: Word,
+    ) -> Result<Self, TransactionRequestError> {
+        let note =
+            create_note(Script::P2ID { target }, vec![asset], self.account_id, None, serial_num)
+                .map_err(TransactionRequestError::InvalidOutputNote)?;
+        self.intents.push(OutputIntent::Pay { asset, note });
+        Ok(self)
+    }
+
+    /// Adds an intent to bridge the specified asset to the destination of the `message`.
+    ///
+    /// The asset is sent via a BRIDGE note with the specified serial number. Once the note is
+    /// consumed by the bridge faucet, the asset is burned, and the bridge releases it on the
+    /// destination chain.
+    ///
+    /// # Errors
+    /// Returns an error if the asset was not issued by the bridge faucet of the message.
+    pub fn bridge(
+        mut self,
+        asset: FungibleAsset,
+        message: BridgeMessage,
+        serial_num: Word,
+    ) -> Result<Self, TransactionRequestError> {
+        let note = create_bridge_note(asset, message, self.account_id, serial_num)
+            .map_err(TransactionRequestError::InvalidOutputNote)?;
+        self.intents.push(OutputIntent::Pay { asset: asset.into(), note });
+        Ok(self)
+    }
+
+    /// Adds an intent to mint the specified amount of the fungible asset issued by the account of
+    /// this request and to send it to the `target` account.
+    ///
+    /// The minted asset is sent via a P2ID note with the specified serial number.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The account of this request is not a fungible faucet, or the amount is invalid.
+    /// - The P2ID note could not be created.
+    pub fn mint(
+        mut self,
+        amount: u64,
+        target: AccountId,
+        serial_num: Word,
+    ) -> Result<Self, TransactionRequestError> {
+        let asset = FungibleAsset::new(self.account_id, amount)
+            .map_err(TransactionRequestError::InvalidAsset)?;
+        let note = create_note(
+            Script::P2ID { target },
+            vec![asset.into()],
+            self.account_id,
+            None,
+            serial_num,
+        )
+        .map_err(TransactionRequestError::InvalidOutputNote)?;
+        self.intents.push(OutputIntent::Mint { amount, note });
+        Ok(self)
+    }
+
+    /// Adds an intent to consume the notes with the specified IDs.
+    ///
+    /// Notes which were already added to this request are ignored.
+    pub fn consume_notes<I: IntoIterator<Item = NoteId>>(mut self, note_ids: I) -> Self {
+        for note_id in note_ids {
+            if !self.input_notes.contains(&note_id) {
+                self.input_notes.push(note_id);
+            }
+        }
+        self
+    }
+
+    /// Sets the inputs passed to the transaction script (e.g., the secret key used by the
+    /// authentication procedure when no authenticator is used by the executor).
+    pub fn with_script_inputs(mut self, script_inputs: TransactionScriptInputs) -> Self {
+        self.script_inputs = script_inputs;
+        self
+    }
+
+    // EXPECTED EFFECTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the notes with the specified IDs to the set of notes the transaction is expected to
+    /// create in addition to the notes created by the intents of this request.
+    pub fn expect_output_notes<I: IntoIterator<Item = NoteId>>(mut self, note_ids: I) -> Self {
+        for note_id in note_ids {
+            if !self.expected_notes.contains(&note_id) {
+                self.expected_notes.push(note_id);
+            }
+        }
+        self
+    }
+
+    /// Sets the balance of the fungible asset issued by the specified faucet which the account
+    /// vault is expected to hold after the transaction is executed.
+    ///
+    /// # Errors
+    /// Returns an error if the specified account is not a fungible faucet.
+    pub fn expect_balance(
+        mut self,
+        faucet_id: AccountId,
+        balance: u64,
+    ) -> Result<Self, TransactionRequestError> {
+        if !faucet_id.is_fungible_faucet() {
+            return Err(TransactionRequestError::NotAFungibleFaucet(faucet_id));
+        }
+        self.expected_balances.insert(faucet_id, balance);
+        Ok(self)
+    }
+
+    /// Checks the effects of the provided executed transaction against the effects expected by
+    /// this request.
+    ///
+    /// # Errors
+    /// Returns all of the detected mismatches if:
+    /// - An expected note was not created by the transaction.
+    /// - The transaction created a note which was not expected.
+    /// - The final balance of a fungible asset differs from the expected balance.
+    pub fn check_effects(
+        &self,
+        transaction: &ExecutedTransaction,
+    ) -> Result<(), Vec<TransactionEffectMismatch>> {
+        let mut mismatches = Vec::new();
+
+        // check created notes
+        let expected_notes: BTreeSet<NoteId> = self
+            .expected_output_notes()
+            .map(|note| note.id())
+            .chain(self.expected_notes.iter().copied())
+            .collect();
+        let created_notes: BTreeSet<NoteId> =
+            transaction.output_notes().iter().map(|note| note.id()).collect();
+
+        for note_id in expected_notes.difference(&created_notes) {
+            mismatches.push(TransactionEffectMismatch::MissingOutputNote(*note_id));
+        }
+        for note_id in created_notes.difference(&expected_notes) {
+            mismatches.push(TransactionEffectMismatch::UnexpectedOutputNote(*note_id));
+        }
+
+        // check final balances
+        if !self.expected_balances.is_empty() {
+            let mut final_vault = transaction.initial_account().vault().clone();
+            match final_vault.apply_delta(transaction.account_delta().vault()) {
+                Ok(()) => {
+                    for (&faucet_id, &expected) in self.expected_balances.iter() {
+                        let actual = final_vault
+                            .get_balance(faucet_id)
+                            .expect("expected balances are set only for fungible faucets");
+                        if actual != expected {
+                            mismatches.push(TransactionEffectMismatch::BalanceMismatch {
+                                faucet_id,
+                                expected,
+                                actual,
+                            });
+                        }
+                    }
+                },
+                Err(err) => mismatches.push(TransactionEffectMismatch::InvalidVaultDelta(err)),
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the account this request is made against.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the IDs of the notes to be consumed by the transaction.
+    pub fn input_note_ids(&self) -> &[NoteId] {
+        &self.input_notes
+    }
+
+    /// Returns the notes expected to be created by the transaction, in the order of creation.
+    pub fn expected_output_notes(&self) -> impl Iterator<Item = &Note> {
+        self.intents.iter().map(|intent| match intent {
+            OutputIntent::Pay { note, .. } | OutputIntent::Mint { note, .. } => note,
+        })
+    }
+
+    /// Returns the inputs to be passed to the transaction script.
+    pub fn script_inputs(&self) -> &TransactionScriptInputs {
+        &self.script_inputs
+    }
+
+    /// Returns the source code of the transaction script fulfilling the intents of this request.
+    pub fn script_source(&self) -> String {
+        let mut imports = BTreeSet::new();
+        imports.insert("use.miden::contracts::auth::basic->auth_tx");
+
+        let mut body = String::new();
+        for intent in self.intents.iter() {
+            match intent {
+                OutputIntent::Pay { asset, note } => {
+                    imports.insert("use.miden::contracts::wallets::basic->wallet");
+                    body.push_str(&format!(
+                        "    push.{} push.{} push.{}\n    call.wallet::send_asset drop dropw dropw\n",
+                        format_word(&note.recipient().into()),
+                        note.metadata().tag(),
+                        format_word(&Word::from(asset)),
+                    ));
+                },
+                OutputIntent::Mint { amount, note } => {
+                    imports.insert("use.miden::contracts::faucets::basic_fungible->faucet");
+                    body.push_str(&format!(
+                        "    push.{} push.{} push.{}\n    call.faucet::distribute drop dropw dropw\n",
+                        format_word(&note.recipient().into()),
+                        note.metadata().tag(),
+                        amount,
+                    ));
+                },
+            }
+        }
+        body.push_str("    call.auth_tx::auth_tx_rpo_falcon512\n");
+
+        let mut source = String::new();
+        for import in imports {
+            source.push_str(import);
+            source.push('\n');
+        }
+        source.push_str("\nbegin\n");
+        source.push_str(&body);
+        source.push_str("end\n");
+        source
+    }
+
+    /// Returns the AST of the transaction script fulfilling the intents of this request.
+    pub fn script_ast(&self) -> ProgramAst {
+        ProgramAst::parse(&self.script_source()).expect("generated transaction script is valid")
+    }
+}
+
+// AIRDROP
+// ================================================================================================
+
+/// A set of transaction requests which mint a fungible asset to multiple recipients.
+///
+/// Every recipient receives the minted asset via a separate P2ID note. The notes are distributed
+/// over the minimal number of mint transactions such that no transaction creates more than
+/// [MAX_OUTPUT_NOTES_PER_TRANSACTION] notes.
+///
+/// Once the transactions are executed, the created notes (returned by [Airdrop::notes()]) must be
+/// delivered to the recipients, e.g., by publishing them or by sending them off-chain.
+#[derive(Clone, Debug)]
+pub struct Airdrop {
+    requests: Vec<TransactionRequest>,
+}
+
+impl Airdrop {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [Airdrop] of the fungible asset issued by the specified faucet to the
+    /// specified `(recipient, amount)` pairs.
+    ///
+    /// Amounts of recipients listed more than once are summed up, such that each recipient
+    /// receives a single note, and recipients with a zero amount are skipped. The serial numbers of
+    /// the notes are derived from `serial_seed` and the position of the note in the airdrop; thus,
+    /// the seed should not be reused across airdrops.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The faucet is not a fungible faucet, or the total amount for a recipient is invalid.
+    /// - The total amount for a recipient listed more than once overflows a u64 value.
+    /// - Any of the P2ID notes could not be created.
+    pub fn new(
+        faucet_id: AccountId,
+        recipients: &[(AccountId, u64)],
+        serial_seed: Word,
+    ) -> Result<Self, TransactionRequestError> {
+        Self::with_max_notes_per_tx(
+            faucet_id,
+            recipients,
+            serial_seed,
+            MAX_OUTPUT_NOTES_PER_TRANSACTION,
+        )
+    }
+
+    /// Returns a new [Airdrop] with at most `max_notes_per_tx` notes created by each transaction.
+    pub(crate) fn with_max_notes_per_tx(
+        faucet_id: AccountId,
+        recipients: &[(AccountId, u64)],
+        serial_seed: Word,
+        max_notes_per_tx: usize,
+    ) -> Result<Self, TransactionRequestError> {
+        // merge the amounts of repeated recipients, preserving the order of first appearance
+        let mut amounts: Vec<(AccountId, u64)> = Vec::new();
+        let mut positions: BTreeMap<u64, usize> = BTreeMap::new();
+        for &(recipient, amount) in recipients.iter().filter(|(_, amount)| *amount > 0) {
+            match positions.get(&u64::from(recipient)) {
+                Some(&idx) => {
+                    let total = &mut amounts[idx].1;
+                    *total = total
+                        .checked_add(amount)
+                        .ok_or(TransactionRequestError::AmountOverflow(recipient))?;
+                },
+                None => {
+                    positions.insert(recipient.into(), amounts.len());
+                    amounts.push((recipient, amount));
+                },
+            }
+        }
+
+        let mut requests = Vec::new();
+        for (chunk_idx, chunk) in amounts.chunks(max_notes_per_tx).enumerate() {
+            let mut request = TransactionRequest::new(faucet_id);
+            for (note_idx, &(recipient, amount)) in chunk.iter().enumerate() {
+                let note_idx = chunk_idx * max_notes_per_tx + note_idx;
+                let serial_num = Hasher::merge(&[
+                    serial_seed.into(),
+                    [Felt::new(note_idx as u64), ZERO, ZERO, ZERO].into(),
+                ]);
+                request = request.mint(amount, recipient, serial_num.into())?;
+            }
+            requests.push(request);
+        }
+
+        Ok(Self { requests })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the mint transaction requests of this airdrop.
+    pub fn requests(&self) -> &[TransactionRequest] {
+        &self.requests
+    }
+
+    /// Returns the notes created by the transactions of this airdrop, in the order of the
+    /// recipients.
+    pub fn notes(&self) -> impl Iterator<Item = &Note> {
+        self.requests.iter().flat_map(TransactionRequest::expected_output_notes)
+    }
+
+    /// Consumes this airdrop and returns its mint transaction requests.
+    pub fn into_requests(self) -> Vec<TransactionRequest> {
+        self.requests
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Formats the provided word as the immediate value of a MASM `push` instruction.
+fn format_word(word: &Word) -> String {
+    word.iter().map(|x| x.as_int().to_string()).collect::<Vec<_>>().join(".")
+}
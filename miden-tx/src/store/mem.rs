@@ -0,0 +1,169 @@
+use miden_objects::{
+    accounts::{Account, AccountId},
+    assembly::ModuleAst,
+    notes::NoteId,
+    transaction::{FeeParameters, InputNote, InputNotes, TransactionInputs},
+    utils::collections::{BTreeMap, Vec},
+    BlockHeader, Word,
+};
+
+use super::{validate_fee_parameters, with_fee_parameters, BlockChain};
+use crate::{DataStore, DataStoreError};
+
+// IN-MEMORY DATA STORE
+// ================================================================================================
+
+/// A [DataStore] which keeps accounts, block headers, and note records in memory.
+///
+/// Blocks must be added in order starting with the genesis block; each added block is checked to
+/// commit to the chain of all previously added blocks. The chain MMR returned for a transaction
+/// is built for the referenced block (rather than for the latest block known to the store), and
+/// contains authentication paths only for the blocks in which the input notes of the transaction
+/// were created.
+///
+/// The store keeps only the latest known state of every account. The seed of an account is
+/// provided to the executor only while the account is new; thus, the seed can be retained after
+/// the first transaction against the account has been executed.
+///
+/// If fee parameters are set, they are included into the inputs of every transaction, and thus
+/// the fee is charged by the transaction kernel.
+#[derive(Debug, Clone, Default)]
+pub struct MemDataStore {
+    accounts: BTreeMap<AccountId, (Account, Option<Word>)>,
+    chain: BlockChain,
+    notes: BTreeMap<NoteId, InputNote>,
+    fee_parameters: Option<FeeParameters>,
+}
+
+impl MemDataStore {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty [MemDataStore].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts the provided account into the store, replacing the previous state of the account
+    /// with the same ID (if any).
+    ///
+    /// `seed` must be provided for accounts which have not been recorded in the chain yet.
+    pub fn add_account(&mut self, account: Account, seed: Option<Word>) {
+        let seed = seed.or_else(|| self.accounts.get(&account.id()).and_then(|(_, seed)| *seed));
+        self.accounts.insert(account.id(), (account, seed));
+    }
+
+    /// Appends the provided block header to the chain of blocks known to the store.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The block does not directly follow the latest block known to the store.
+    /// - The chain root of the block is not the root of the MMR of all previous blocks.
+    pub fn add_block(&mut self, header: BlockHeader) -> Result<(), DataStoreError> {
+        self.chain.add_block(header)
+    }
+
+    /// Inserts the provided note record into the store.
+    ///
+    /// # Errors
+    /// Returns an error if the inclusion proof of the note does not reference a block known to
+    /// the store.
+    pub fn add_note(&mut self, note: InputNote) -> Result<(), DataStoreError> {
+        self.chain.validate_note(&note)?;
+        self.notes.insert(note.id(), note);
+        Ok(())
+    }
+
+    /// Removes the note with the specified ID from the store (e.g., after the note was consumed),
+    /// and returns it.
+    pub fn remove_note(&mut self, note_id: NoteId) -> Option<InputNote> {
+        self.notes.remove(&note_id)
+    }
+
+    /// Sets the fee parameters included into the inputs of transactions, or removes them if
+    /// `fee_parameters` is None.
+    ///
+    /// # Errors
+    /// Returns an error if the fee defined by the parameters cannot be charged by the transaction
+    /// kernel (see [FeeParameters::is_kernel_chargeable()]).
+    pub fn set_fee_parameters(
+        &mut self,
+        fee_parameters: Option<FeeParameters>,
+    ) -> Result<(), DataStoreError> {
+        if let Some(fee_parameters) = &fee_parameters {
+            validate_fee_parameters(fee_parameters)?;
+        }
+        self.fee_parameters = fee_parameters;
+        Ok(())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the latest known state of the account with the specified ID.
+    pub fn account(&self, account_id: AccountId) -> Option<&Account> {
+        self.accounts.get(&account_id).map(|(account, _)| account)
+    }
+
+    /// Returns the header of the block with the specified number.
+    pub fn block_header(&self, block_num: u32) -> Option<&BlockHeader> {
+        self.chain.get(block_num)
+    }
+
+    /// Returns the header of the latest block known to the store.
+    pub fn latest_block_header(&self) -> Option<&BlockHeader> {
+        self.chain.headers.last()
+    }
+
+    /// Returns the record of the note with the specified ID.
+    pub fn note(&self, note_id: NoteId) -> Option<&InputNote> {
+        self.notes.get(&note_id)
+    }
+
+    /// Returns the fee parameters included into the inputs of transactions, if any.
+    pub fn fee_parameters(&self) -> Option<&FeeParameters> {
+        self.fee_parameters.as_ref()
+    }
+}
+
+impl DataStore for MemDataStore {
+    fn get_transaction_inputs(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        notes: &[NoteId],
+    ) -> Result<TransactionInputs, DataStoreError> {
+        let (account, seed) = self
+            .accounts
+            .get(&account_id)
+            .ok_or(DataStoreError::AccountNotFound(account_id))?;
+        let block_header =
+            *self.chain.get(block_ref).ok_or(DataStoreError::BlockNotFound(block_ref))?;
+
+        let notes = notes
+            .iter()
+            .map(|note_id| {
+                self.notes
+                    .get(note_id)
+                    .cloned()
+                    .ok_or(DataStoreError::InputNoteNotFound(*note_id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let block_chain = self.chain.chain_mmr(block_ref, &notes)?;
+        let notes = InputNotes::new(notes).map_err(DataStoreError::InvalidTransactionInput)?;
+
+        let seed = if account.is_new() { *seed } else { None };
+        let tx_inputs =
+            TransactionInputs::new(account.clone(), seed, block_header, block_chain, notes)
+                .map_err(DataStoreError::InvalidTransactionInput)?;
+        with_fee_parameters(tx_inputs, self.fee_parameters)
+    }
+
+    fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError> {
+        self.account(account_id)
+            .map(|account| account.code().module().clone())
+            .ok_or(DataStoreError::AccountNotFound(account_id))
+    }
+}
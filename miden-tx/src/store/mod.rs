@@ -0,0 +1,167 @@
+use miden_objects::{
+    crypto::merkle::{Mmr, PartialMmr},
+    transaction::{ChainMmr, FeeParameters, InputNote, TransactionInputs},
+    utils::{
+        collections::{BTreeMap, Vec},
+        format,
+    },
+    BlockHeader, Digest, TransactionInputError,
+};
+
+use crate::DataStoreError;
+
+mod mem;
+pub use mem::MemDataStore;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteDataStore;
+
+// BLOCK CHAIN
+// ================================================================================================
+
+/// The headers of a contiguous sequence of blocks starting at the genesis block, together with
+/// the MMR of their hashes.
+///
+/// The chain root committed to by the header of block `N` is the root of the MMR built from the
+/// hashes of blocks `0..N`, i.e., it does not include the hash of block `N` itself. Thus, the
+/// [ChainMmr] of a transaction executed against block `N` must be built from the peaks of the MMR
+/// at forest `N`, rather than from the peaks of the latest MMR known to the store.
+#[derive(Debug, Clone, Default)]
+struct BlockChain {
+    headers: Vec<BlockHeader>,
+    mmr: Mmr,
+}
+
+impl BlockChain {
+    // MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the provided block header to the chain.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The block does not directly follow the last block of the chain.
+    /// - The chain root of the block is not the root of the MMR of all previous blocks.
+    fn add_block(&mut self, header: BlockHeader) -> Result<(), DataStoreError> {
+        let expected = self.headers.len() as u32;
+        if header.block_num() != expected {
+            return Err(DataStoreError::UnexpectedBlockNum {
+                expected,
+                actual: header.block_num(),
+            });
+        }
+
+        let chain_root = self
+            .mmr
+            .peaks(self.mmr.forest())
+            .map_err(|err| DataStoreError::InternalError(format!("{err:?}")))?
+            .hash_peaks();
+        if header.chain_root() != chain_root {
+            return Err(DataStoreError::ChainRootMismatch {
+                block_num: header.block_num(),
+                expected: chain_root,
+                actual: header.chain_root(),
+            });
+        }
+
+        self.mmr.add(header.hash());
+        self.headers.push(header);
+        Ok(())
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the header of the block with the specified number, if it is a part of the chain.
+    fn get(&self, block_num: u32) -> Option<&BlockHeader> {
+        self.headers.get(block_num as usize)
+    }
+
+    /// Returns an error if the inclusion proof of the provided note does not reference a block of
+    /// this chain.
+    fn validate_note(&self, note: &InputNote) -> Result<(), DataStoreError> {
+        let block_num = note.origin().block_num;
+        let header = self.get(block_num).ok_or(DataStoreError::BlockNotFound(block_num))?;
+        if note.proof().sub_hash() != header.sub_hash()
+            || note.proof().note_root() != header.note_root()
+        {
+            return Err(DataStoreError::NoteProofMismatch(note.id()));
+        }
+        Ok(())
+    }
+
+    /// Returns the [ChainMmr] of a transaction executed against the block with number `block_ref`
+    /// and consuming the provided notes.
+    ///
+    /// The returned chain MMR describes the chain preceding `block_ref`, and contains
+    /// authentication paths for the blocks in which the provided notes were created.
+    ///
+    /// # Errors
+    /// Returns an error if any of the notes was not created before block `block_ref`.
+    fn chain_mmr(&self, block_ref: u32, notes: &[InputNote]) -> Result<ChainMmr, DataStoreError> {
+        let forest = block_ref as usize;
+        let peaks = self
+            .mmr
+            .peaks(forest)
+            .map_err(|err| DataStoreError::InternalError(format!("{err:?}")))?;
+
+        let mut partial_mmr = PartialMmr::from_peaks(peaks);
+        let mut blocks = BTreeMap::<u32, Digest>::new();
+        for note in notes {
+            let block_num = note.origin().block_num;
+            if block_num >= block_ref {
+                return Err(DataStoreError::NoteNotInChain {
+                    note_id: note.id(),
+                    block_num,
+                    block_ref,
+                });
+            }
+            if blocks.contains_key(&block_num) {
+                continue;
+            }
+
+            let block_hash = self.headers[block_num as usize].hash();
+            let path = self
+                .mmr
+                .open(block_num as usize, forest)
+                .map_err(|err| DataStoreError::InternalError(format!("{err:?}")))?
+                .merkle_path;
+            partial_mmr
+                .add(block_num as usize, block_hash, &path)
+                .map_err(|err| DataStoreError::InternalError(format!("{err:?}")))?;
+            blocks.insert(block_num, block_hash);
+        }
+
+        ChainMmr::new(partial_mmr, blocks)
+            .map_err(|err| DataStoreError::InternalError(format!("{err:?}")))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an error if the fee defined by the provided parameters cannot be charged by the
+/// transaction kernel.
+fn validate_fee_parameters(fee_parameters: &FeeParameters) -> Result<(), DataStoreError> {
+    if !fee_parameters.is_kernel_chargeable() {
+        return Err(DataStoreError::InvalidTransactionInput(
+            TransactionInputError::InvalidFeeParameters(*fee_parameters),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the provided transaction inputs with the specified fee parameters (if any).
+fn with_fee_parameters(
+    tx_inputs: TransactionInputs,
+    fee_parameters: Option<FeeParameters>,
+) -> Result<TransactionInputs, DataStoreError> {
+    match fee_parameters {
+        Some(fee_parameters) => tx_inputs
+            .with_fee_parameters(fee_parameters)
+            .map_err(DataStoreError::InvalidTransactionInput),
+        None => Ok(tx_inputs),
+    }
+}
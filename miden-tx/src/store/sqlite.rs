@@ -0,0 +1,313 @@
+use std::{
+    path::Path,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use miden_objects::{
+    accounts::{Account, AccountId},
+    assembly::ModuleAst,
+    notes::NoteId,
+    transaction::{FeeParameters, InputNote, InputNotes, TransactionInputs},
+    utils::{
+        collections::Vec,
+        serde::{Deserializable, Serializable},
+        string::ToString,
+    },
+    BlockHeader, Word,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::{validate_fee_parameters, with_fee_parameters, BlockChain};
+use crate::{DataStore, DataStoreError};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Schema of the database backing a [SqliteDataStore].
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS accounts (
+        account_id BLOB PRIMARY KEY,
+        account BLOB NOT NULL,
+        seed BLOB
+    );
+    CREATE TABLE IF NOT EXISTS block_headers (
+        block_num INTEGER PRIMARY KEY,
+        header BLOB NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS notes (
+        note_id BLOB PRIMARY KEY,
+        note BLOB NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS fee_parameters (
+        id INTEGER PRIMARY KEY CHECK (id = 0),
+        parameters BLOB NOT NULL
+    );
+";
+
+// SQLITE DATA STORE
+// ================================================================================================
+
+/// A [DataStore] which persists accounts, block headers, and note records in an SQLite database.
+///
+/// The store enforces the same invariants as [MemDataStore](super::MemDataStore): blocks must be
+/// added in order starting with the genesis block, and notes can be added only for blocks known
+/// to the store. Block headers are additionally kept in memory, so that the chain MMR of a
+/// transaction can be built without reading the entire chain from the database.
+///
+/// Objects are stored in their binary serialization format. The database connection is kept
+/// behind a mutex, so that the store can be shared by multiple threads. Like block headers, the
+/// fee parameters included into the inputs of transactions (if any) are persisted in the database
+/// and kept in memory.
+pub struct SqliteDataStore {
+    conn: Mutex<Connection>,
+    chain: BlockChain,
+    fee_parameters: Option<FeeParameters>,
+}
+
+impl SqliteDataStore {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Opens the database at the specified path (creating it if it does not exist) and returns a
+    /// [SqliteDataStore] backed by it.
+    ///
+    /// # Errors
+    /// Returns an error if the database cannot be opened, or if the blocks stored in it do not
+    /// form a valid chain.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, DataStoreError> {
+        let conn = Connection::open(path).map_err(db_error)?;
+        Self::from_connection(conn)
+    }
+
+    /// Returns a [SqliteDataStore] backed by a new in-memory database.
+    pub fn in_memory() -> Result<Self, DataStoreError> {
+        let conn = Connection::open_in_memory().map_err(db_error)?;
+        Self::from_connection(conn)
+    }
+
+    /// Initializes the schema of the database behind the provided connection, and loads the
+    /// stored block headers and fee parameters.
+    fn from_connection(conn: Connection) -> Result<Self, DataStoreError> {
+        conn.execute_batch(SCHEMA).map_err(db_error)?;
+
+        let mut chain = BlockChain::default();
+        {
+            let mut stmt = conn
+                .prepare("SELECT header FROM block_headers ORDER BY block_num")
+                .map_err(db_error)?;
+            let headers = stmt.query_map([], |row| row.get::<_, Vec<u8>>(0)).map_err(db_error)?;
+            for header in headers {
+                chain.add_block(read_object(&header.map_err(db_error)?)?)?;
+            }
+        }
+
+        let fee_parameters = conn
+            .query_row("SELECT parameters FROM fee_parameters WHERE id = 0", [], |row| {
+                row.get::<_, Vec<u8>>(0)
+            })
+            .optional()
+            .map_err(db_error)?
+            .map(|parameters| read_object(&parameters))
+            .transpose()?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            chain,
+            fee_parameters,
+        })
+    }
+
+    // MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts the provided account into the store, replacing the previous state of the account
+    /// with the same ID (if any).
+    ///
+    /// `seed` must be provided for accounts which have not been recorded in the chain yet. If
+    /// `seed` is None, the previously stored seed of the account is retained.
+    pub fn add_account(&self, account: &Account, seed: Option<Word>) -> Result<(), DataStoreError> {
+        self.conn()
+            .execute(
+                "INSERT INTO accounts (account_id, account, seed) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(account_id) DO UPDATE
+                 SET account = excluded.account, seed = COALESCE(excluded.seed, accounts.seed)",
+                params![account.id().to_bytes(), account.to_bytes(), seed.map(|s| s.to_bytes())],
+            )
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Appends the provided block header to the chain of blocks known to the store.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The block does not directly follow the latest block known to the store.
+    /// - The chain root of the block is not the root of the MMR of all previous blocks.
+    /// - The block header cannot be written into the database.
+    pub fn add_block(&mut self, header: BlockHeader) -> Result<(), DataStoreError> {
+        let mut chain = self.chain.clone();
+        chain.add_block(header)?;
+
+        self.conn()
+            .execute(
+                "INSERT INTO block_headers (block_num, header) VALUES (?1, ?2)",
+                params![header.block_num(), header.to_bytes()],
+            )
+            .map_err(db_error)?;
+        self.chain = chain;
+        Ok(())
+    }
+
+    /// Inserts the provided note record into the store.
+    ///
+    /// # Errors
+    /// Returns an error if the inclusion proof of the note does not reference a block known to
+    /// the store, or if the note cannot be written into the database.
+    pub fn add_note(&self, note: &InputNote) -> Result<(), DataStoreError> {
+        self.chain.validate_note(note)?;
+        self.conn()
+            .execute(
+                "INSERT OR REPLACE INTO notes (note_id, note) VALUES (?1, ?2)",
+                params![note.id().to_bytes(), note.to_bytes()],
+            )
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Removes the note with the specified ID from the store (e.g., after the note was consumed).
+    pub fn remove_note(&self, note_id: NoteId) -> Result<(), DataStoreError> {
+        self.conn()
+            .execute("DELETE FROM notes WHERE note_id = ?1", params![note_id.to_bytes()])
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Sets the fee parameters included into the inputs of transactions, or removes them if
+    /// `fee_parameters` is None.
+    ///
+    /// # Errors
+    /// Returns an error if the fee defined by the parameters cannot be charged by the transaction
+    /// kernel (see [FeeParameters::is_kernel_chargeable()]), or if the parameters cannot be
+    /// written into the database.
+    pub fn set_fee_parameters(
+        &mut self,
+        fee_parameters: Option<FeeParameters>,
+    ) -> Result<(), DataStoreError> {
+        match &fee_parameters {
+            Some(parameters) => {
+                validate_fee_parameters(parameters)?;
+                self.conn().execute(
+                    "INSERT OR REPLACE INTO fee_parameters (id, parameters) VALUES (0, ?1)",
+                    params![parameters.to_bytes()],
+                )
+            },
+            None => self.conn().execute("DELETE FROM fee_parameters", []),
+        }
+        .map_err(db_error)?;
+        self.fee_parameters = fee_parameters;
+        Ok(())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the latest known state of the account with the specified ID, together with its
+    /// seed (if any).
+    pub fn account(
+        &self,
+        account_id: AccountId,
+    ) -> Result<(Account, Option<Word>), DataStoreError> {
+        let (account, seed) = self
+            .conn()
+            .query_row(
+                "SELECT account, seed FROM accounts WHERE account_id = ?1",
+                params![account_id.to_bytes()],
+                |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Option<Vec<u8>>>(1)?)),
+            )
+            .optional()
+            .map_err(db_error)?
+            .ok_or(DataStoreError::AccountNotFound(account_id))?;
+
+        let seed = seed.map(|seed| read_object(&seed)).transpose()?;
+        Ok((read_object(&account)?, seed))
+    }
+
+    /// Returns the header of the block with the specified number.
+    pub fn block_header(&self, block_num: u32) -> Option<&BlockHeader> {
+        self.chain.get(block_num)
+    }
+
+    /// Returns the header of the latest block known to the store.
+    pub fn latest_block_header(&self) -> Option<&BlockHeader> {
+        self.chain.headers.last()
+    }
+
+    /// Returns the record of the note with the specified ID.
+    pub fn note(&self, note_id: NoteId) -> Result<InputNote, DataStoreError> {
+        let note = self
+            .conn()
+            .query_row(
+                "SELECT note FROM notes WHERE note_id = ?1",
+                params![note_id.to_bytes()],
+                |row| row.get::<_, Vec<u8>>(0),
+            )
+            .optional()
+            .map_err(db_error)?
+            .ok_or(DataStoreError::InputNoteNotFound(note_id))?;
+
+        read_object(&note)
+    }
+
+    /// Returns the fee parameters included into the inputs of transactions, if any.
+    pub fn fee_parameters(&self) -> Option<&FeeParameters> {
+        self.fee_parameters.as_ref()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the connection to the database backing this store.
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl DataStore for SqliteDataStore {
+    fn get_transaction_inputs(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        notes: &[NoteId],
+    ) -> Result<TransactionInputs, DataStoreError> {
+        let (account, seed) = self.account(account_id)?;
+        let block_header =
+            *self.chain.get(block_ref).ok_or(DataStoreError::BlockNotFound(block_ref))?;
+
+        let notes =
+            notes.iter().map(|note_id| self.note(*note_id)).collect::<Result<Vec<_>, _>>()?;
+        let block_chain = self.chain.chain_mmr(block_ref, &notes)?;
+        let notes = InputNotes::new(notes).map_err(DataStoreError::InvalidTransactionInput)?;
+
+        let seed = if account.is_new() { seed } else { None };
+        let tx_inputs = TransactionInputs::new(account, seed, block_header, block_chain, notes)
+            .map_err(DataStoreError::InvalidTransactionInput)?;
+        with_fee_parameters(tx_inputs, self.fee_parameters)
+    }
+
+    fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError> {
+        let (account, _) = self.account(account_id)?;
+        Ok(account.code().module().clone())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts a database error into a [DataStoreError].
+fn db_error(err: rusqlite::Error) -> DataStoreError {
+    DataStoreError::InternalError(err.to_string())
+}
+
+/// Deserializes an object read from the database.
+fn read_object<T: Deserializable>(bytes: &[u8]) -> Result<T, DataStoreError> {
+    T::read_from_bytes(bytes).map_err(|err| DataStoreError::InternalError(err.to_string()))
+}
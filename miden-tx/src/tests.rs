@@ -1,30 +1,71 @@
-use miden_lib::transaction::{ToTransactionKernelInputs, TransactionKernel};
+use std::{sync::Mutex, thread};
+
+use miden_lib::transaction::{
+    EventId, EventNamespace, KernelVersion, ToTransactionKernelInputs, TransactionEvent,
+    TransactionEventParsingError, TransactionEventPayload, TransactionKernel,
+};
 use miden_objects::{
-    accounts::{Account, AccountCode},
+    accounts::{Account, AccountCode, AccountStorage, StorageMapDelta, StorageSlotType},
     assembly::{Assembler, ModuleAst, ProgramAst},
-    assets::{Asset, FungibleAsset},
-    block::BlockHeader,
-    notes::NoteId,
-    transaction::{ChainMmr, InputNote, InputNotes, TransactionWitness},
-    Felt, Word,
+    assets::{Asset, AssetVault, FungibleAsset},
+    block::{AccountWitness, BlockBuilder, BlockHeader, ACCOUNT_TREE_DEPTH},
+    crypto::merkle::{Mmr, NodeIndex, SimpleSmt},
+    notes::{
+        NetworkNote, Note, NoteEnvelope, NoteId, NoteInclusionProof, NoteScript, NoteTag, NoteType,
+        NOTE_LEAF_DEPTH, NOTE_TREE_DEPTH,
+    },
+    transaction::{
+        ChainMmr, ExecutedTransaction, FeeParameters, ForeignAccountInputs, InputNote, InputNotes,
+        OutputNotes, ProvenTransaction, TransactionBatch, TransactionFee, TransactionWitness,
+    },
+    utils::{
+        serde::{Deserializable, Serializable, SliceReader},
+        sync::Arc,
+    },
+    vm::{CodeBlock, Kernel, Program},
+    BlockError, Digest, Felt, Word, EMPTY_WORD, ONE, ZERO,
 };
 use miden_prover::ProvingOptions;
 use mock::{
     constants::{
         non_fungible_asset, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
         ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN_2, ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN,
-        ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX, ACCOUNT_PROCEDURE_SET_CODE_PROC_IDX,
-        ACCOUNT_PROCEDURE_SET_ITEM_PROC_IDX, FUNGIBLE_ASSET_AMOUNT, STORAGE_INDEX_0,
+        ACCOUNT_ID_SENDER, ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX,
+        ACCOUNT_PROCEDURE_SET_CODE_PROC_IDX, ACCOUNT_PROCEDURE_SET_ITEM_PROC_IDX,
+        ACCOUNT_PROCEDURE_SET_MAP_ITEM_PROC_IDX, FUNGIBLE_ASSET_AMOUNT, STORAGE_INDEX_0,
+        STORAGE_INDEX_2, STORAGE_MAP_KEY_0,
+    },
+    mock::{
+        account::{mock_account, mock_account_code, MockAccountType},
+        block::mock_block_header,
+        notes::{mock_notes, AssetPreservationStatus},
+        transaction::{mock_inputs, mock_inputs_with_existing},
     },
-    mock::{account::MockAccountType, notes::AssetPreservationStatus, transaction::mock_inputs},
     utils::prepare_word,
 };
-use vm_core::utils::to_hex;
-use vm_processor::MemAdviceProvider;
+use vm_core::{utils::to_hex, Operation};
+use vm_processor::{AdviceInputs, ExecutionError, MemAdviceProvider, ProcessState, StackInputs};
 
+#[cfg(feature = "remote-prover")]
+use super::HttpProvingService;
+#[cfg(feature = "sqlite")]
+use super::SqliteDataStore;
+use super::{
+    executor::build_new_account_delta,
+    host::{
+        read_advice_witness, CompactAdviceRecorder, DecodedEvent, EventHandler, EventHandlers,
+        TransactionPhase, TransactionTraceHandler,
+    },
+    replay::{replay, TransactionReplay},
+    AccountId, Airdrop, DataStore, DataStoreError, EventRegistrationError, MemDataStore,
+    NoteConsumability, ProofHashFunction, ProvenTransactionMismatch, ProverOptions,
+    ProverOptionsError, TransactionCompiler, TransactionCompilerError, TransactionExecutor,
+    TransactionExecutorError, TransactionHost, TransactionInputs, TransactionProver,
+    TransactionProverError, TransactionRequestError, TransactionVerifier, TransactionVerifierError,
+};
+#[cfg(feature = "async")]
 use super::{
-    AccountId, DataStore, DataStoreError, TransactionExecutor, TransactionHost, TransactionInputs,
-    TransactionProver, TransactionVerifier,
+    AsyncDataStore, ProvingRequest, ProvingService, RemoteProverError, RemoteTransactionProver,
 };
 
 // TESTS
@@ -33,7 +74,7 @@ use super::{
 #[test]
 fn test_transaction_executor_witness() {
     let data_store = MockDataStore::default();
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
 
     let account_id = data_store.account.id();
     executor.load_account(account_id).unwrap();
@@ -42,31 +83,266 @@ fn test_transaction_executor_witness() {
     let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
 
     // execute the transaction and get the witness
-    let executed_transaction =
-        executor.execute_transaction(account_id, block_ref, &note_ids, None).unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
     let tx_witness: TransactionWitness = executed_transaction.clone().into();
 
     // use the witness to execute the transaction again
-    let (stack_inputs, advice_inputs) = tx_witness.get_kernel_inputs();
+    let (stack_inputs, advice_inputs) = tx_witness.get_kernel_inputs(&KernelVersion::current());
     let mem_advice_provider: MemAdviceProvider = advice_inputs.into();
     let mut host = TransactionHost::new(tx_witness.account().into(), mem_advice_provider);
-    let result =
-        vm_processor::execute(tx_witness.program(), stack_inputs, &mut host, Default::default())
-            .unwrap();
+    let result = vm_processor::execute(
+        &witness_program(&tx_witness),
+        stack_inputs,
+        &mut host,
+        Default::default(),
+    )
+    .unwrap();
 
-    let (advice_provider, _event_handler) = host.into_parts();
+    let (advice_provider, ..) = host.into_parts();
     let (_, map, _) = advice_provider.into_parts();
-    let tx_outputs =
-        TransactionKernel::parse_transaction_outputs(result.stack_outputs(), &map.into()).unwrap();
+    let tx_outputs = KernelVersion::current()
+        .parse_transaction_outputs(result.stack_outputs(), &map.into())
+        .unwrap();
 
     assert_eq!(executed_transaction.final_account().hash(), tx_outputs.account.hash());
     assert_eq!(executed_transaction.output_notes(), &tx_outputs.output_notes);
 }
 
+#[test]
+fn test_execute_from_witness() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    let tx_witness: TransactionWitness = executed_transaction.clone().into();
+
+    // an executor without a data store and without loaded accounts re-executes the witness
+    let witness_executor = TransactionExecutor::builder().with_data_store(()).build();
+    let reexecuted_transaction = witness_executor.execute_from_witness(tx_witness).unwrap();
+
+    assert_eq!(reexecuted_transaction.id(), executed_transaction.id());
+    assert_eq!(reexecuted_transaction.account_delta(), executed_transaction.account_delta());
+    assert_eq!(reexecuted_transaction.measurements(), executed_transaction.measurements());
+    assert_eq!(reexecuted_transaction.to_bytes(), executed_transaction.to_bytes());
+}
+
+#[test]
+fn test_simulate_transaction() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let simulation = executor
+        .simulate_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+
+    // the simulation projects the same effects as the execution of the transaction
+    assert_eq!(simulation.account_id(), account_id);
+    assert_eq!(simulation.block_ref(), block_ref);
+    assert_eq!(simulation.final_account(), executed_transaction.final_account());
+    assert_eq!(simulation.account_delta(), executed_transaction.account_delta());
+    assert_eq!(simulation.output_notes(), executed_transaction.output_notes());
+    assert_eq!(simulation.fee(), None);
+    assert_eq!(simulation.measurements(), executed_transaction.measurements());
+}
+
+#[test]
+fn test_max_cycles() {
+    let data_store = MockDataStore::default();
+    let account_id = data_store.account.id();
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    // the number of consumed cycles is reported by the executed transaction
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account_id).unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    let num_cycles = executed_transaction.measurements().num_cycles as u32;
+    assert!(num_cycles > 0);
+
+    // a limit above the consumed cycles does not affect the execution
+    let executor = TransactionExecutor::new(data_store.clone())
+        .with_max_cycles(num_cycles)
+        .unwrap();
+    assert_eq!(executor.execution_options().max_cycles(), num_cycles);
+    executor.load_account(account_id).unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    assert_eq!(executed_transaction.measurements().num_cycles as u32, num_cycles);
+
+    // a limit below the consumed cycles makes the execution fail
+    let executor = TransactionExecutor::new(data_store.clone())
+        .with_max_cycles(num_cycles / 2)
+        .unwrap();
+    executor.load_account(account_id).unwrap();
+    let err = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        TransactionExecutorError::ExecuteTransactionProgramFailed {
+            source: ExecutionError::CycleLimitExceeded(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_transaction_witness_contains_only_accessed_vault_data() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    let tx_witness: TransactionWitness = executed_transaction.clone().into();
+
+    // the advice witness must be sufficient to execute the transaction again without the full
+    // account vault data
+    let (stack_inputs, advice_inputs, _) =
+        tx_witness.get_kernel_inputs_with_lazy_vault(&KernelVersion::current());
+    let mem_advice_provider: MemAdviceProvider = advice_inputs.into();
+    let mut host = TransactionHost::new(tx_witness.account().into(), mem_advice_provider);
+    let result = vm_processor::execute(
+        &witness_program(&tx_witness),
+        stack_inputs,
+        &mut host,
+        Default::default(),
+    )
+    .unwrap();
+
+    let (advice_provider, ..) = host.into_parts();
+    let (_, map, _) = advice_provider.into_parts();
+    let tx_outputs = KernelVersion::current()
+        .parse_transaction_outputs(result.stack_outputs(), &map.into())
+        .unwrap();
+
+    assert_eq!(executed_transaction.final_account().hash(), tx_outputs.account.hash());
+}
+
+#[test]
+fn test_compact_advice_recorder_streamed_witness() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    let tx_witness: TransactionWitness = executed_transaction.into();
+
+    // execute the transaction with the compact recorder
+    let (stack_inputs, advice_inputs) = tx_witness.get_kernel_inputs(&KernelVersion::current());
+    let advice_recorder: CompactAdviceRecorder = advice_inputs.into();
+    let mut host = TransactionHost::new(tx_witness.account().into(), advice_recorder);
+    vm_processor::execute(
+        &witness_program(&tx_witness),
+        stack_inputs,
+        &mut host,
+        Default::default(),
+    )
+    .unwrap();
+
+    let (advice_recorder, ..) = host.into_parts();
+    assert!(advice_recorder.num_recorded_nodes() > 0);
+
+    // stream the witness into a buffer and make sure it matches the finalized witness
+    let mut bytes = Vec::new();
+    advice_recorder.write_witness_into(&mut bytes);
+    let streamed_witness = read_advice_witness(&mut SliceReader::new(&bytes)).unwrap();
+
+    let (advice_witness, _) = advice_recorder.finalize();
+    assert_eq!(streamed_witness.stack(), advice_witness.stack());
+}
+
+#[test]
+fn test_compact_advice_recorder_overwritten_map_entry() {
+    let data_store = MockDataStore::default();
+
+    let key = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let original_value = vec![Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+
+    // read the entry stored under the key, overwrite it with a new value, and read it again
+    let program_source = format!(
+        "
+    begin
+        push.{key} adv.push_mapval adv_loadw
+        # => [ORIGINAL_VALUE]
+
+        push.9.9.9.9 push.1000 mem_storew dropw
+        push.1001.1000 push.{key} adv.insert_mem dropw drop drop
+        # => [ORIGINAL_VALUE]
+
+        push.{key} adv.push_mapval adv_loadw
+        # => [NEW_VALUE, ORIGINAL_VALUE]
+    end
+    ",
+        key = prepare_word(&key)
+    );
+    let program = Assembler::default().compile(program_source).unwrap();
+
+    let advice_inputs =
+        AdviceInputs::default().with_map([(Digest::from(key).as_bytes(), original_value.clone())]);
+    let advice_recorder: CompactAdviceRecorder = advice_inputs.into();
+    let mut host = TransactionHost::new(data_store.account.clone().into(), advice_recorder);
+    let result =
+        vm_processor::execute(&program, StackInputs::default(), &mut host, Default::default())
+            .unwrap();
+
+    // the witness must contain the value which was read, rather than the value the key was
+    // overwritten with
+    let (advice_recorder, ..) = host.into_parts();
+    let (advice_witness, _) = advice_recorder.finalize();
+    assert_eq!(
+        advice_witness.mapped_values(&Digest::from(key).as_bytes()),
+        Some(original_value.as_slice())
+    );
+
+    // replaying the program from the witness must produce the same outputs
+    let mut host = TransactionHost::new(
+        data_store.account.clone().into(),
+        MemAdviceProvider::from(advice_witness),
+    );
+    let replayed =
+        vm_processor::execute(&program, StackInputs::default(), &mut host, Default::default())
+            .unwrap();
+    assert_eq!(replayed.stack_outputs(), result.stack_outputs());
+}
+
 #[test]
 fn test_transaction_result_account_delta() {
     let data_store = MockDataStore::new(AssetPreservationStatus::PreservedWithAccountVaultDelta);
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     let account_id = data_store.account.id();
     executor.load_account(account_id).unwrap();
 
@@ -208,7 +484,7 @@ fn test_transaction_result_account_delta() {
     // --------------------------------------------------------------------------------------------
     // execute the transaction and get the witness
     let transaction_result = executor
-        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script))
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
         .unwrap();
 
     // nonce delta
@@ -236,34 +512,157 @@ fn test_transaction_result_account_delta() {
         .iter()
         .cloned()
         .collect::<Vec<_>>();
-    assert!(transaction_result
-        .account_delta()
-        .vault()
-        .added_assets
+    let vault_delta = transaction_result.account_delta().vault();
+    assert!(vault_delta.added().all(|x| added_assets.contains(&x)));
+    assert_eq!(added_assets.len(), vault_delta.added().count());
+
+    // assert that added non-fungible assets are tracked with their full word in the dedicated set
+    let added_nfts = added_assets.iter().filter(|asset| !asset.is_fungible()).collect::<Vec<_>>();
+    assert!(!added_nfts.is_empty());
+    assert_eq!(added_nfts.len(), vault_delta.added_nfts.len());
+    assert!(vault_delta
+        .added_nfts
         .iter()
-        .all(|x| added_assets.contains(x)));
+        .all(|x| added_nfts.contains(&&Asset::NonFungible(*x))));
+
+    // assert that removed assets are tracked
+    assert!(vault_delta.removed().all(|x| removed_assets.contains(&x)));
+    assert_eq!(removed_assets.len(), vault_delta.removed().count());
+}
+
+#[test]
+fn test_transaction_result_storage_map_delta() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let updated_map_value = [Felt::new(7), Felt::new(9), Felt::new(11), Felt::new(13)];
+    let new_map_key = [Felt::new(1), Felt::new(1), Felt::new(2), Felt::new(3)];
+    let new_map_value = [Felt::new(5), Felt::new(8), Felt::new(13), Felt::new(21)];
+
+    let account_procedure_incr_nonce_mast_root = to_hex(
+        &data_store.account.code().procedures()[ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX].as_bytes(),
+    )
+    .unwrap();
+    let account_procedure_set_map_item_mast_root = to_hex(
+        &data_store.account.code().procedures()[ACCOUNT_PROCEDURE_SET_MAP_ITEM_PROC_IDX].as_bytes(),
+    )
+    .unwrap();
+
+    let tx_script = format!(
+        "\
+        proc.set_map_item
+            call.0x{account_procedure_set_map_item_mast_root}
+            # => [MAP_ROOT', V]
+
+            dropw dropw
+            # => []
+        end
+
+        begin
+            # update an existing map entry, and later overwrite the update
+            push.{NEW_MAP_VALUE} push.{MAP_KEY} push.{STORAGE_INDEX_2} exec.set_map_item
+            push.{UPDATED_MAP_VALUE} push.{MAP_KEY} push.{STORAGE_INDEX_2} exec.set_map_item
+
+            # insert a new map entry, and later remove it
+            push.{NEW_MAP_VALUE} push.{NEW_MAP_KEY} push.{STORAGE_INDEX_2} exec.set_map_item
+            padw push.{NEW_MAP_KEY} push.{STORAGE_INDEX_2} exec.set_map_item
+
+            # update the account nonce
+            push.1 call.0x{account_procedure_incr_nonce_mast_root} drop
+        end
+    ",
+        MAP_KEY = prepare_word(&STORAGE_MAP_KEY_0),
+        NEW_MAP_KEY = prepare_word(&new_map_key),
+        NEW_MAP_VALUE = prepare_word(&new_map_value),
+        UPDATED_MAP_VALUE = prepare_word(&updated_map_value),
+    );
+    let tx_script_code = ProgramAst::parse(&tx_script).unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let executed_tx = executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    // only the last value written to each entry is recorded, and entries restored to their
+    // initial values are omitted
+    let mut expected_storage = data_store.account.storage().clone();
+    expected_storage
+        .set_map_item(STORAGE_INDEX_2, STORAGE_MAP_KEY_0.into(), updated_map_value)
+        .unwrap();
+
+    let storage_delta = executed_tx.account_delta().storage();
     assert_eq!(
-        added_assets.len(),
-        transaction_result.account_delta().vault().added_assets.len()
+        storage_delta.updated_maps,
+        vec![(
+            STORAGE_INDEX_2,
+            StorageMapDelta {
+                cleared_leaves: vec![],
+                updated_leaves: vec![(STORAGE_MAP_KEY_0.into(), updated_map_value)],
+            }
+        )]
     );
 
-    // assert that removed assets are tracked
-    assert!(transaction_result
-        .account_delta()
-        .vault()
-        .removed_assets
-        .iter()
-        .all(|x| removed_assets.contains(x)));
+    // the new root of the map is recorded as an item update
     assert_eq!(
-        removed_assets.len(),
-        transaction_result.account_delta().vault().removed_assets.len()
+        storage_delta.updated_items,
+        vec![(STORAGE_INDEX_2, expected_storage.get_item(STORAGE_INDEX_2).into())]
+    );
+
+    // applying the delta to the initial account results in the final account
+    let mut final_account = executed_tx.initial_account().clone();
+    final_account.apply_delta(executed_tx.account_delta()).unwrap();
+    assert_eq!(final_account.hash(), executed_tx.final_account().hash());
+    assert_eq!(
+        final_account.storage().get_map_item(STORAGE_INDEX_2, STORAGE_MAP_KEY_0.into()),
+        Ok(updated_map_value)
+    );
+}
+
+#[test]
+fn test_new_account_delta() {
+    let assembler = TransactionKernel::assembler();
+
+    // the delta of a new account describes the full state of the account
+    let account = mock_account(None, ONE, None, &assembler);
+    let delta = build_new_account_delta(&account).unwrap();
+
+    let expected_items = account
+        .storage()
+        .iter()
+        .filter(|(_, value, _)| *value != Word::default())
+        .map(|(slot_idx, value, _)| (slot_idx, value))
+        .collect::<Vec<_>>();
+    assert!(!expected_items.is_empty());
+    assert_eq!(delta.storage().updated_items, expected_items);
+    assert!(delta.storage().cleared_items.is_empty());
+    assert_eq!(delta.storage().updated_maps.len(), account.storage().maps().len());
+    let expected_assets = account.vault().assets().collect::<Vec<_>>();
+    assert_eq!(delta.vault().added().count(), expected_assets.len());
+    assert!(delta.vault().added().all(|asset| expected_assets.contains(&asset)));
+    assert!(delta.vault().removed().next().is_none());
+    assert_eq!(delta.nonce(), Some(ONE));
+
+    // the delta of a new account without storage items and assets still sets the nonce
+    let empty_account = Account::new(
+        account.id(),
+        AssetVault::default(),
+        AccountStorage::new(vec![]).unwrap(),
+        mock_account_code(&assembler),
+        ONE,
     );
+    let delta = build_new_account_delta(&empty_account).unwrap();
+    assert!(delta.is_empty());
+    assert_eq!(delta.nonce(), Some(ONE));
 }
 
 #[test]
 fn test_prove_witness_and_verify() {
     let data_store = MockDataStore::default();
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
 
     let account_id = data_store.account.id();
     executor.load_account(account_id).unwrap();
@@ -272,101 +671,1910 @@ fn test_prove_witness_and_verify() {
     let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
 
     // execute the transaction and get the witness
-    let executed_transaction =
-        executor.execute_transaction(account_id, block_ref, &note_ids, None).unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
 
     // prove the transaction with the witness
     let proof_options = ProvingOptions::default();
     let prover = TransactionProver::new(proof_options);
+    let tx_summary = executed_transaction.summary();
     let proven_transaction = prover.prove_transaction(executed_transaction).unwrap();
+    assert_eq!(proven_transaction.summary(), tx_summary);
 
     let verifier = TransactionVerifier::new(96);
     assert!(verifier.verify(proven_transaction).is_ok());
 }
 
-// TEST TRANSACTION SCRIPT
-// ================================================================================================
-
 #[test]
-fn test_tx_script() {
+fn test_prove_and_validate() {
     let data_store = MockDataStore::default();
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
 
     let account_id = data_store.account.id();
     executor.load_account(account_id).unwrap();
 
     let block_ref = data_store.block_header.block_num();
     let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
 
-    let tx_script_input_key = [Felt::new(9999), Felt::new(8888), Felt::new(9999), Felt::new(8888)];
-    let tx_script_input_value = [Felt::new(9), Felt::new(8), Felt::new(7), Felt::new(6)];
-    let tx_script_source = format!(
-        "
-    begin
-        # push the tx script input key onto the stack
-        push.{key}
+    // the proof is verified and the proven outputs match the executed outputs
+    let prover = TransactionProver::new(ProvingOptions::default());
+    let tx_id = executed_transaction.id();
+    let proven_transaction = prover.prove_and_validate(executed_transaction.clone()).unwrap();
+    assert_eq!(proven_transaction.id(), tx_id);
 
-        # load the tx script input value from the map and read it onto the stack
-        adv.push_mapval adv_loadw
+    // a proven transaction whose outputs differ from the executed outputs is rejected
+    let tamper = |final_account_hash: Digest,
+                  output_notes: OutputNotes<NoteEnvelope>,
+                  expiration_block_num: Option<u32>,
+                  fee: Option<TransactionFee>| {
+        ProvenTransaction::new(
+            proven_transaction.account_id(),
+            proven_transaction.initial_account_hash(),
+            final_account_hash,
+            proven_transaction.input_notes().clone(),
+            output_notes,
+            proven_transaction.tx_script_root(),
+            proven_transaction.block_ref(),
+            proven_transaction.kernel_hash(),
+            None,
+            expiration_block_num,
+            fee,
+            proven_transaction.proof().clone(),
+        )
+    };
+    let assert_mismatch =
+        |tampered: ProvenTransaction, expected: ProvenTransactionMismatch| match prover
+            .validate_proven_transaction(tampered, &executed_transaction)
+        {
+            Err(TransactionProverError::ProvenTransactionMismatch { mismatches, .. }) => {
+                assert_eq!(mismatches, [expected])
+            },
+            result => panic!("unexpected result: {:?}", result.map(|tx| tx.id())),
+        };
 
-        # assert that the value is correct
-        push.{value} assert_eqw
-    end
-",
-        key = prepare_word(&tx_script_input_key),
-        value = prepare_word(&tx_script_input_value)
+    let final_account_hash = proven_transaction.final_account_hash();
+    let output_notes = proven_transaction.output_notes().clone();
+    let expiration_block_num = proven_transaction.expiration_block_num();
+    let fee = proven_transaction.fee();
+
+    let tampered_hash = Digest::new([ONE, ONE, ONE, ONE]);
+    assert_mismatch(
+        tamper(tampered_hash, output_notes.clone(), expiration_block_num, fee),
+        ProvenTransactionMismatch::FinalAccountHash {
+            expected: final_account_hash,
+            actual: tampered_hash,
+        },
     );
-    let tx_script_code = ProgramAst::parse(&tx_script_source).unwrap();
-    let tx_script = executor
-        .compile_tx_script(
-            tx_script_code,
-            vec![(tx_script_input_key, tx_script_input_value.into())],
-            vec![],
+
+    let tampered_notes = OutputNotes::new(output_notes.iter().skip(1).cloned().collect()).unwrap();
+    assert_mismatch(
+        tamper(final_account_hash, tampered_notes.clone(), expiration_block_num, fee),
+        ProvenTransactionMismatch::OutputNotesCommitment {
+            expected: output_notes.commitment(),
+            actual: tampered_notes.commitment(),
+        },
+    );
+
+    let tampered_expiration = Some(block_ref + 100);
+    assert_mismatch(
+        tamper(final_account_hash, output_notes.clone(), tampered_expiration, fee),
+        ProvenTransactionMismatch::ExpirationBlockNum {
+            expected: expiration_block_num,
+            actual: tampered_expiration,
+        },
+    );
+
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let tampered_fee = TransactionFee::new(
+        FungibleAsset::new(faucet_id, 1).unwrap(),
+        1,
+        output_notes.iter().last().unwrap().note_id(),
+    );
+    assert_mismatch(
+        tamper(
+            final_account_hash,
+            output_notes.clone(),
+            expiration_block_num,
+            Some(tampered_fee),
+        ),
+        ProvenTransactionMismatch::Fee {
+            expected: fee,
+            actual: Some(tampered_fee),
+        },
+    );
+
+    // outputs which match the executed outputs are checked against the proof
+    assert!(prover
+        .validate_proven_transaction(
+            tamper(final_account_hash, output_notes, expiration_block_num, fee),
+            &executed_transaction
         )
+        .is_ok());
+}
+
+#[test]
+fn test_transaction_serialization() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
         .unwrap();
 
-    // execute the transaction
-    let transaction_result =
-        executor.execute_transaction(account_id, block_ref, &note_ids, Some(tx_script));
+    // executed transaction
+    let bytes = executed_transaction.to_bytes();
+    let decoded = ExecutedTransaction::read_from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.id(), executed_transaction.id());
+    assert_eq!(decoded.kernel_hash(), executed_transaction.kernel_hash());
+    assert_eq!(decoded.account_delta(), executed_transaction.account_delta());
+    assert_eq!(decoded.measurements(), executed_transaction.measurements());
+    assert_eq!(decoded.to_bytes(), bytes);
 
-    // assert the transaction executed successfully
-    assert!(transaction_result.is_ok());
-}
+    // transaction witness
+    let tx_witness: TransactionWitness = executed_transaction.into();
+    let bytes = tx_witness.to_bytes();
+    let decoded = TransactionWitness::read_from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.kernel_hash(), tx_witness.kernel_hash());
+    assert_eq!(decoded.to_bytes(), bytes);
 
-// MOCK DATA STORE
-// ================================================================================================
+    // the decoded witness is sufficient to prove the transaction
+    let tx_witness = decoded;
 
-#[derive(Clone)]
-struct MockDataStore {
-    pub account: Account,
-    pub block_header: BlockHeader,
-    pub block_chain: ChainMmr,
-    pub notes: Vec<InputNote>,
+    // proven transaction
+    let proven_transaction = TransactionProver::new(ProvingOptions::default())
+        .prove_transaction(tx_witness)
+        .unwrap();
+    let bytes = proven_transaction.to_bytes();
+    let decoded = ProvenTransaction::read_from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.id(), proven_transaction.id());
+    assert_eq!(decoded.to_bytes(), bytes);
 }
 
-impl MockDataStore {
-    pub fn new(asset_preservation: AssetPreservationStatus) -> Self {
-        let (account, _, block_header, block_chain, notes) =
-            mock_inputs(MockAccountType::StandardExisting, asset_preservation).into_parts();
+#[test]
+fn test_prover_options() {
+    // the default options match the default options of the VM prover
+    let options = ProverOptions::default();
+    assert_eq!(options.hash_fn(), ProofHashFunction::Blake3_192);
+    assert_eq!(options.blowup_factor(), ProverOptions::MIN_BLOWUP_FACTOR);
+    assert_eq!(ProvingOptions::from(options), ProvingOptions::default());
 
-        Self {
-            account,
-            block_header,
-            block_chain,
-            notes: notes.into_vec(),
-        }
+    let options = options.with_hash_fn(ProofHashFunction::Rpo256).with_blowup_factor(16).unwrap();
+    let prover = TransactionProver::new(options);
+    assert_eq!(prover.proving_options().hash_fn(), ProofHashFunction::Rpo256);
+
+    // the blowup factor must be a supported power of two
+    for blowup_factor in [0, 4, 12, 256] {
+        assert_eq!(
+            ProverOptions::new().with_blowup_factor(blowup_factor),
+            Err(ProverOptionsError::InvalidBlowupFactor(blowup_factor))
+        );
     }
 }
 
-impl Default for MockDataStore {
-    fn default() -> Self {
-        Self::new(AssetPreservationStatus::Preserved)
+#[cfg(feature = "async")]
+#[test]
+fn test_remote_prover() {
+    /// A proving service which proves requests in-process.
+    struct LocalProvingService {
+        prover: TransactionProver,
     }
-}
 
-impl DataStore for MockDataStore {
-    fn get_transaction_inputs(
-        &self,
+    #[async_trait::async_trait]
+    impl ProvingService for LocalProvingService {
+        async fn prove(&self, request: Vec<u8>) -> Result<Vec<u8>, RemoteProverError> {
+            prove_serialized_request(&self.prover, &request)
+                .map_err(RemoteProverError::ServiceError)
+        }
+    }
+
+    /// A proving service which responds with malformed data.
+    struct BrokenProvingService;
+
+    #[async_trait::async_trait]
+    impl ProvingService for BrokenProvingService {
+        async fn prove(&self, _request: Vec<u8>) -> Result<Vec<u8>, RemoteProverError> {
+            Ok(vec![1, 2, 3])
+        }
+    }
+
+    let executed_transaction = execute_mock_transaction();
+    let tx_summary = executed_transaction.summary();
+
+    let prover = RemoteTransactionProver::new(LocalProvingService {
+        prover: TransactionProver::new(ProvingOptions::default()),
+    });
+    let proven_transaction =
+        futures::executor::block_on(prover.prove_transaction(executed_transaction.clone()))
+            .unwrap();
+    assert_eq!(proven_transaction.summary(), tx_summary);
+
+    let verifier = TransactionVerifier::new(96);
+    assert!(verifier.verify(proven_transaction).is_ok());
+
+    let prover = RemoteTransactionProver::new(BrokenProvingService);
+    assert!(matches!(
+        futures::executor::block_on(prover.prove_transaction(executed_transaction)),
+        Err(TransactionProverError::RemoteProvingFailed {
+            source: RemoteProverError::InvalidResponse(_),
+            ..
+        })
+    ));
+}
+
+#[cfg(feature = "remote-prover")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_http_proving_service() {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+
+    /// Serves a single HTTP request on a local port, responding with the status and body returned
+    /// by the handler, and returns the URL of the server.
+    fn serve_once<F>(handler: F) -> String
+    where
+        F: FnOnce(Vec<u8>) -> (u16, Vec<u8>) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/prove", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_lowercase();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let (status, response) = handler(body);
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status} STATUS\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                response.len()
+            )
+            .unwrap();
+            stream.write_all(&response).unwrap();
+        });
+        url
+    }
+
+    let executed_transaction = execute_mock_transaction();
+    let tx_summary = executed_transaction.summary();
+
+    // the request is proven by the service behind the endpoint
+    let endpoint = serve_once(|request| {
+        let prover = TransactionProver::new(ProvingOptions::default());
+        (200, prove_serialized_request(&prover, &request).unwrap())
+    });
+    let prover = RemoteTransactionProver::new(HttpProvingService::new(endpoint));
+    let proven_transaction = prover.prove_transaction(executed_transaction.clone()).await.unwrap();
+    assert_eq!(proven_transaction.summary(), tx_summary);
+
+    // failures of the service are reported together with the body of the response
+    let endpoint = serve_once(|_| (500, b"out of memory".to_vec()));
+    let prover = RemoteTransactionProver::new(HttpProvingService::new(endpoint));
+    match prover.prove_transaction(executed_transaction.clone()).await {
+        Err(TransactionProverError::RemoteProvingFailed {
+            source: RemoteProverError::ServiceError(message),
+            ..
+        }) => assert!(message.contains("out of memory")),
+        result => panic!("unexpected result: {result:?}"),
+    }
+
+    // unreachable services are reported as connection failures
+    let endpoint = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}/prove", listener.local_addr().unwrap())
+    };
+    let prover = RemoteTransactionProver::new(HttpProvingService::new(endpoint));
+    assert!(matches!(
+        prover.prove_transaction(executed_transaction).await,
+        Err(TransactionProverError::RemoteProvingFailed {
+            source: RemoteProverError::ConnectionFailed(_),
+            ..
+        })
+    ));
+}
+
+/// Executes a transaction against the default mock data store.
+#[cfg(feature = "async")]
+fn execute_mock_transaction() -> ExecutedTransaction {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap()
+}
+
+/// Proves the serialized proving request in the same way as a proving service would, and returns
+/// the serialized proven transaction.
+#[cfg(feature = "async")]
+fn prove_serialized_request(prover: &TransactionProver, request: &[u8]) -> Result<Vec<u8>, String> {
+    let request = ProvingRequest::read_from_bytes(request).map_err(|err| err.to_string())?;
+    prover
+        .prove_request(request)
+        .map(|proven_tx| proven_tx.to_bytes())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_test_prover_and_insecure_verifier() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+
+    // prove the transaction with minimal security parameters
+    let prover = super::TestProver::new();
+    let proven_transaction = prover.prove_transaction(executed_transaction).unwrap();
+
+    // the proof is rejected by a verifier requiring a realistic security level
+    let verifier = TransactionVerifier::new(96);
+    assert!(verifier.verify(proven_transaction.clone()).is_err());
+
+    // but accepted by the insecure verifier
+    let verifier = TransactionVerifier::new_insecure();
+    assert!(verifier.verify(proven_transaction).is_ok());
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_verify_with_multiple_kernels() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    let proven_transaction =
+        super::TestProver::new().prove_transaction(executed_transaction).unwrap();
+
+    // the proof is verified against the kernel the transaction was executed with, as long as the
+    // kernel is accepted by the verifier
+    let kernel = KernelVersion::current();
+    assert_eq!(proven_transaction.kernel_hash(), kernel.hash());
+    let other_kernel =
+        KernelVersion::new(CodeBlock::new_span(vec![Operation::Noop]), Kernel::default());
+    let verifier = TransactionVerifier::with_kernels(0, [other_kernel.clone(), kernel.clone()]);
+    assert!(verifier.verify(proven_transaction.clone()).is_ok());
+
+    let restricted_verifier = TransactionVerifier::with_kernels(0, [other_kernel.clone()]);
+    assert!(matches!(
+        restricted_verifier.verify(proven_transaction.clone()),
+        Err(TransactionVerifierError::UnknownKernel { kernel_hash, .. })
+            if kernel_hash == kernel.hash()
+    ));
+
+    // the proof does not verify against a different kernel claimed by the transaction
+    let tampered_transaction = ProvenTransaction::new(
+        proven_transaction.account_id(),
+        proven_transaction.initial_account_hash(),
+        proven_transaction.final_account_hash(),
+        proven_transaction.input_notes().clone(),
+        proven_transaction.output_notes().clone(),
+        proven_transaction.tx_script_root(),
+        proven_transaction.block_ref(),
+        other_kernel.hash(),
+        proven_transaction.account_details().cloned(),
+        proven_transaction.expiration_block_num(),
+        proven_transaction.fee(),
+        proven_transaction.proof().clone(),
+    );
+    assert!(matches!(
+        verifier.verify(tampered_transaction),
+        Err(TransactionVerifierError::TransactionVerificationFailed { .. })
+    ));
+
+    // a compiler cannot be instantiated with an assembler targeting a different kernel
+    let kernel = KernelVersion::current();
+    assert!(matches!(
+        TransactionCompiler::with_kernel(Assembler::default(), kernel.clone()),
+        Err(TransactionCompilerError::KernelMismatch(hash)) if hash == kernel.hash()
+    ));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_verify_batch() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let prover = super::TestProver::new();
+    let transactions = (0..3)
+        .map(|_| {
+            let executed_transaction = executor
+                .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+                .unwrap();
+            prover.prove_transaction(executed_transaction).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    // the result of every transaction is reported in the order of the transactions
+    let verifier = TransactionVerifier::new_insecure();
+    let results = verifier.verify_batch(&transactions);
+    assert_eq!(results.len(), transactions.len());
+    assert!(results.iter().all(|result| result.is_ok()));
+
+    let verifier = TransactionVerifier::new(96);
+    let results = verifier.verify_batch(&transactions);
+    assert_eq!(results.len(), transactions.len());
+    for (result, transaction) in results.into_iter().zip(transactions.iter()) {
+        assert!(matches!(
+            result,
+            Err(TransactionVerifierError::InsufficientProofSecurityLevel { tx_id, .. })
+                if tx_id == transaction.id()
+        ));
+    }
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_transaction_expiration() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    // the expiration can only be lowered
+    let tx_script_source = format!(
+        "
+    use.miden::tx
+
+    begin
+        push.{} exec.tx::update_expiration_block_num
+        push.{} exec.tx::update_expiration_block_num
+        push.{} exec.tx::update_expiration_block_num
+    end
+    ",
+        block_ref + 20,
+        block_ref + 10,
+        block_ref + 15
+    );
+    let tx_script_code = ProgramAst::parse(&tx_script_source).unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+    assert_eq!(executed_transaction.expiration_block_num(), Some(block_ref + 10));
+
+    let prover = super::TestProver::new();
+    let proven_transaction = prover.prove_transaction(executed_transaction).unwrap();
+    assert_eq!(proven_transaction.expiration_block_num(), Some(block_ref + 10));
+
+    let verifier = TransactionVerifier::new_insecure();
+    assert!(verifier.verify(proven_transaction.clone()).is_ok());
+
+    // the expiration block number is committed to by the proof
+    let tampered_transaction = ProvenTransaction::new(
+        proven_transaction.account_id(),
+        proven_transaction.initial_account_hash(),
+        proven_transaction.final_account_hash(),
+        proven_transaction.input_notes().clone(),
+        proven_transaction.output_notes().clone(),
+        proven_transaction.tx_script_root(),
+        proven_transaction.block_ref(),
+        proven_transaction.kernel_hash(),
+        proven_transaction.account_details().cloned(),
+        Some(block_ref + 100),
+        proven_transaction.fee(),
+        proven_transaction.proof().clone(),
+    );
+    assert!(verifier.verify(tampered_transaction).is_err());
+
+    // a transaction cannot expire at its reference block
+    let tx_script_source =
+        format!("use.miden::tx begin push.{block_ref} exec.tx::update_expiration_block_num end");
+    let tx_script_code = ProgramAst::parse(&tx_script_source).unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+    let result =
+        executor.execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_transaction_fee() {
+    let fee_faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fee_recipient = Digest::new([ONE, ZERO, ONE, ZERO]);
+    let fee_parameters =
+        FeeParameters::new(1, 10, 100).with_fee_faucet(fee_faucet_id, fee_recipient);
+    let data_store = MockDataStore::default().with_fee_parameters(fee_parameters);
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    // paying the fee changes the account vault, and thus the nonce must be incremented
+    let account_procedure_incr_nonce_mast_root = to_hex(
+        &data_store.account.code().procedures()[ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX].as_bytes(),
+    )
+    .unwrap();
+    let tx_script_source =
+        format!("begin push.1 call.0x{account_procedure_incr_nonce_mast_root} drop end");
+    let tx_script_code = ProgramAst::parse(&tx_script_source).unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+
+    let simulation = executor
+        .simulate_transaction(account_id, block_ref, &note_ids, Some(tx_script.clone()), EMPTY_WORD)
+        .unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    // the fee is charged in the fee asset and covers the executed cycles and notes
+    let fee = executed_transaction.fee().expect("fee was not charged");
+    assert_eq!(simulation.fee(), Some(fee));
+    assert_eq!(fee.asset().faucet_id(), fee_faucet_id);
+    let num_notes = executed_transaction.input_notes().num_notes()
+        + executed_transaction.output_notes().num_notes();
+    assert!(fee_parameters.is_fee_sufficient(&fee, num_notes));
+
+    // the fee is removed from the account vault and paid into the fee note
+    let removed_assets = &executed_transaction.account_delta().vault().removed_assets;
+    assert_eq!(removed_assets, &vec![Asset::Fungible(fee.asset())]);
+
+    let fee_note = executed_transaction.output_notes().iter().last().unwrap();
+    assert_eq!(fee_note.id(), fee.note_id());
+    assert_eq!(fee_parameters.fee_note_id(fee.asset()), fee.note_id());
+    assert_eq!(fee_note.recipient(), &fee_recipient);
+    assert_eq!(fee_note.metadata().sender(), account_id);
+    assert_eq!(fee_note.metadata().tag(), NoteTag::default());
+    assert_eq!(
+        fee_note.assets().iter().collect::<Vec<_>>(),
+        vec![&Asset::Fungible(fee.asset())]
+    );
+
+    // the fee is committed to by the proof
+    let prover = super::TestProver::new();
+    let proven_transaction = prover.prove_transaction(executed_transaction).unwrap();
+    assert_eq!(proven_transaction.fee(), Some(fee));
+
+    let verifier = TransactionVerifier::new_insecure();
+    assert!(verifier.verify(proven_transaction.clone()).is_ok());
+
+    let tampered_fee = TransactionFee::new(
+        FungibleAsset::new(fee_faucet_id, 1).unwrap(),
+        fee.num_cycles(),
+        fee.note_id(),
+    );
+    let tampered_transaction = ProvenTransaction::new(
+        proven_transaction.account_id(),
+        proven_transaction.initial_account_hash(),
+        proven_transaction.final_account_hash(),
+        proven_transaction.input_notes().clone(),
+        proven_transaction.output_notes().clone(),
+        proven_transaction.tx_script_root(),
+        proven_transaction.block_ref(),
+        proven_transaction.kernel_hash(),
+        proven_transaction.account_details().cloned(),
+        proven_transaction.expiration_block_num(),
+        Some(tampered_fee),
+        proven_transaction.proof().clone(),
+    );
+    assert!(verifier.verify(tampered_transaction).is_err());
+}
+
+// TEST TRANSACTION SCRIPT
+// ================================================================================================
+
+#[test]
+fn test_tx_script() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let tx_script_input_key = [Felt::new(9999), Felt::new(8888), Felt::new(9999), Felt::new(8888)];
+    let tx_script_input_value = [Felt::new(9), Felt::new(8), Felt::new(7), Felt::new(6)];
+    let tx_script_source = format!(
+        "
+    begin
+        # push the tx script input key onto the stack
+        push.{key}
+
+        # load the tx script input value from the map and read it onto the stack
+        adv.push_mapval adv_loadw
+
+        # assert that the value is correct
+        push.{value} assert_eqw
+    end
+",
+        key = prepare_word(&tx_script_input_key),
+        value = prepare_word(&tx_script_input_value)
+    );
+    let tx_script_code = ProgramAst::parse(&tx_script_source).unwrap();
+    let tx_script = executor
+        .compile_tx_script(
+            tx_script_code,
+            vec![(tx_script_input_key, tx_script_input_value.into())],
+            vec![],
+        )
+        .unwrap();
+
+    // execute the transaction
+    let transaction_result =
+        executor.execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD);
+
+    // assert the transaction executed successfully
+    assert!(transaction_result.is_ok());
+}
+
+#[test]
+fn test_tx_script_args() {
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::new(data_store.clone());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let tx_script_code = ProgramAst::parse(
+        "
+    use.miden::tx
+
+    begin
+        exec.tx::get_tx_script_args
+        push.1.2.3.4 assert_eqw
+    end
+",
+    )
+    .unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+
+    // the same compiled script is executed with different arguments
+    let args = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script.clone()), args)
+        .unwrap();
+    assert_eq!(executed_transaction.tx_script_args(), args);
+
+    let result =
+        executor.execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD);
+    assert!(result.is_err());
+
+    // the arguments are a part of the transaction witness, thus the transaction can be
+    // re-executed from the witness
+    let tx_witness: TransactionWitness = executed_transaction.clone().into();
+    assert_eq!(tx_witness.tx_script_args(), args);
+    let witness_executor = TransactionExecutor::builder().with_data_store(()).build();
+    let reexecuted_transaction = witness_executor.execute_from_witness(tx_witness).unwrap();
+    assert_eq!(reexecuted_transaction.id(), executed_transaction.id());
+    assert_eq!(reexecuted_transaction.tx_script_args(), args);
+}
+
+// AIRDROP
+// ================================================================================================
+
+#[test]
+fn test_airdrop_splits_recipients_across_transactions() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let recipient = |idx: u64| AccountId::new_unchecked(Felt::new((0b0110111011u64 << 54) | idx));
+    let recipients = [
+        (recipient(1), 10),
+        (recipient(2), 20),
+        (recipient(1), 5),
+        (recipient(3), 0),
+        (recipient(4), 7),
+    ];
+    let serial_seed = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    let airdrop = Airdrop::with_max_notes_per_tx(faucet_id, &recipients, serial_seed, 2).unwrap();
+
+    // repeated recipients receive a single note, and recipients with zero amount are skipped
+    let num_notes_per_tx = airdrop
+        .requests()
+        .iter()
+        .map(|r| r.expected_output_notes().count())
+        .collect::<Vec<_>>();
+    assert_eq!(num_notes_per_tx, vec![2, 1]);
+    assert!(airdrop.requests().iter().all(|request| request.account_id() == faucet_id));
+
+    let notes = airdrop.notes().collect::<Vec<_>>();
+    let expected = [(recipient(1), 15), (recipient(2), 20), (recipient(4), 7)];
+    for (note, (recipient, amount)) in notes.iter().zip(expected) {
+        assert_eq!(note.inputs().inputs()[0], Felt::from(recipient));
+        assert_eq!(
+            note.assets().iter().collect::<Vec<_>>(),
+            vec![&Asset::from(FungibleAsset::new(faucet_id, amount).unwrap())]
+        );
+    }
+
+    // every note has a distinct serial number
+    let serial_nums = notes.iter().map(|note| note.serial_num()).collect::<Vec<_>>();
+    assert!(serial_nums.iter().enumerate().all(|(i, s)| !serial_nums[..i].contains(s)));
+
+    // the faucet must issue a fungible asset
+    let non_fungible_faucet_id =
+        AccountId::try_from(ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    assert!(Airdrop::new(non_fungible_faucet_id, &recipients, serial_seed).is_err());
+
+    // the total amount of a repeated recipient must not overflow
+    let recipients = [(recipient(1), u64::MAX), (recipient(2), 1), (recipient(1), 1)];
+    assert!(matches!(
+        Airdrop::new(faucet_id, &recipients, serial_seed),
+        Err(TransactionRequestError::AmountOverflow(id)) if id == recipient(1)
+    ));
+}
+
+// TRANSACTION REPLAY
+// ================================================================================================
+
+#[test]
+fn test_recorded_transaction_replay() {
+    let replay_dir = unique_temp_path("miden-tx-replay-test");
+    let data_store = MockDataStore::default();
+    let account_id = data_store.account.id();
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    // execute the transaction without recording it
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account_id).unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+
+    // execute the same transaction with an executor which records it
+    let recording_executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_replay_recording(&replay_dir)
+        .build();
+    recording_executor.load_account(account_id).unwrap();
+    recording_executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+
+    let paths = std::fs::read_dir(&replay_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(paths.len(), 1);
+    let recorded = TransactionReplay::read_from_file(&paths[0]).unwrap();
+    assert_eq!(recorded.tx_inputs(), executed_transaction.tx_inputs());
+    assert!(recorded.tx_script().is_none());
+    assert!(recorded.signatures().is_empty());
+
+    // replaying the recorded transaction reproduces the unrecorded execution
+    let replayed_transaction = replay(&paths[0]).unwrap();
+    assert_eq!(replayed_transaction.to_bytes(), executed_transaction.to_bytes());
+
+    std::fs::remove_dir_all(&replay_dir).unwrap();
+}
+
+// BATCH EXECUTION
+// ================================================================================================
+
+#[test]
+fn test_execute_batch() {
+    /// A data store which counts the number of times transaction inputs are fetched.
+    struct CountingDataStore {
+        inner: MockDataStore,
+        num_fetches: Arc<Mutex<usize>>,
+    }
+
+    impl DataStore for CountingDataStore {
+        fn get_transaction_inputs(
+            &self,
+            account_id: AccountId,
+            block_ref: u32,
+            notes: &[NoteId],
+        ) -> Result<TransactionInputs, DataStoreError> {
+            *self.num_fetches.lock().unwrap() += 1;
+            self.inner.get_transaction_inputs(account_id, block_ref, notes)
+        }
+
+        fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError> {
+            self.inner.get_account_code(account_id)
+        }
+    }
+
+    let data_store = MockDataStore::default();
+    let account_id = data_store.account.id();
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account_id).unwrap();
+    let tx_script_code = ProgramAst::parse("begin push.1 drop end").unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+    let expected = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    let expected_with_script = executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script.clone()), EMPTY_WORD)
+        .unwrap();
+
+    // the account code is loaded by the batch itself, and the inputs shared by all transactions
+    // are fetched only once
+    let num_fetches = Arc::new(Mutex::new(0));
+    let executor = TransactionExecutor::new(CountingDataStore {
+        inner: data_store,
+        num_fetches: num_fetches.clone(),
+    });
+    let results = executor.execute_batch(vec![
+        (account_id, block_ref, note_ids.clone(), None, EMPTY_WORD),
+        (account_id, block_ref, note_ids.clone(), Some(tx_script), EMPTY_WORD),
+        (account_id, block_ref, note_ids.clone(), None, EMPTY_WORD),
+    ]);
+    assert_eq!(*num_fetches.lock().unwrap(), 1);
+
+    let ids = results.into_iter().map(|result| result.unwrap().id()).collect::<Vec<_>>();
+    assert_eq!(ids, vec![expected.id(), expected_with_script.id(), expected.id()]);
+}
+
+#[test]
+fn test_execute_network_transaction() {
+    let data_store = MockDataStore::default();
+    let account_id = data_store.account.id();
+    let assets = data_store.notes[0].note().assets().iter().copied().collect::<Vec<_>>();
+
+    // a network note which deposits its assets into the account and increments its nonce
+    let incr_nonce_root =
+        data_store.account.code().procedures()[ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX];
+    let note_script = ProgramAst::parse(&format!(
+        "
+        use.miden::note
+
+        begin
+            dropw
+            exec.note::add_assets_to_account
+            push.1 call.0x{} drop
+        end
+        ",
+        to_hex(&incr_nonce_root.as_bytes()).unwrap()
+    ))
+    .unwrap();
+    let (note_script, _) = NoteScript::new(note_script, &TransactionKernel::assembler()).unwrap();
+    let network_note = |tag: NoteTag| {
+        let note = Note::new(
+            note_script.clone(),
+            &[],
+            &assets,
+            [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+            AccountId::try_from(ACCOUNT_ID_SENDER).unwrap(),
+            tag,
+        )
+        .unwrap();
+        NetworkNote::new(note, NoteType::Public).unwrap()
+    };
+
+    // a note targeting the account is consumed by the network against the account
+    let note = network_note(NoteTag::for_network_account(account_id).unwrap());
+    let data_store = MockDataStore::with_notes(vec![note.note().clone()]);
+    let block_ref = data_store.block_header.block_num();
+    let executor = TransactionExecutor::new(data_store);
+    executor.load_account(account_id).unwrap();
+
+    let executed_transaction = executor
+        .execute_network_transaction(account_id, block_ref, &[note.clone()])
+        .unwrap();
+    assert_eq!(executed_transaction.input_notes().num_notes(), 1);
+    assert_eq!(executed_transaction.input_notes().get_note(0).id(), note.id());
+    assert!(executed_transaction.tx_script().is_none());
+
+    // a note targeting another account is rejected before execution
+    let other_account_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let note = network_note(NoteTag::for_network_account(other_account_id).unwrap());
+    let result = executor.execute_network_transaction(account_id, block_ref, &[note.clone()]);
+    assert!(matches!(
+        result,
+        Err(TransactionExecutorError::NetworkNoteNotConsumable { account_id: id, note_id })
+            if id == account_id && note_id == note.id()
+    ));
+}
+
+#[test]
+fn test_executor_shared_across_threads() {
+    let data_store = MockDataStore::default();
+    let account_id = data_store.account.id();
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    // the account code is loaded once and reused by all threads
+    let executor = Arc::new(TransactionExecutor::new(data_store));
+    executor.load_account(account_id).unwrap();
+
+    let handles = (0..4)
+        .map(|_| {
+            let executor = executor.clone();
+            let note_ids = note_ids.clone();
+            thread::spawn(move || {
+                executor
+                    .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+                    .unwrap()
+                    .id()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let expected = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected.id());
+    }
+}
+
+// NOTE CONSUMABILITY
+// ================================================================================================
+
+#[test]
+fn test_check_note_consumability() {
+    let mut data_store = MockDataStore::default();
+    let account_id = data_store.account.id();
+    let block_ref = data_store.block_header.block_num();
+    let notes = data_store.notes.clone();
+
+    // a note consumed on its own fails the asset preservation check in the epilogue; the check
+    // is a dry run, thus no replay is recorded for the failed execution
+    let replay_dir = unique_temp_path("miden-tx-consumability-replays");
+    data_store.notes = vec![notes[0].clone()];
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_replay_recording(&replay_dir)
+        .build();
+    executor.load_account(account_id).unwrap();
+    let consumability =
+        executor.check_note_consumability(account_id, block_ref, notes[0].id()).unwrap();
+    assert!(matches!(
+        consumability,
+        NoteConsumability::NotConsumable(
+            TransactionExecutorError::ExecuteTransactionProgramFailed {
+                phase: TransactionPhase::Epilogue,
+                failed_note: None,
+                ..
+            }
+        )
+    ));
+    assert!(!replay_dir.exists());
+
+    // a note which deposits its assets into the account and increments the nonce of the account
+    // can be consumed on its own
+    let assembler = TransactionKernel::assembler();
+    let incr_nonce_root =
+        data_store.account.code().procedures()[ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX];
+    let note_script = ProgramAst::parse(&format!(
+        "
+        use.miden::note
+
+        begin
+            dropw
+            exec.note::add_assets_to_account
+            push.1 call.0x{} drop
+        end
+        ",
+        to_hex(&incr_nonce_root.as_bytes()).unwrap()
+    ))
+    .unwrap();
+    let (note_script, _) = NoteScript::new(note_script, &assembler).unwrap();
+    let note = Note::new(
+        note_script,
+        &[],
+        &notes[0].note().assets().iter().copied().collect::<Vec<_>>(),
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+        AccountId::try_from(ACCOUNT_ID_SENDER).unwrap(),
+        NoteTag::default(),
+    )
+    .unwrap();
+    let executor = TransactionExecutor::new(MockDataStore::with_notes(vec![note.clone()]));
+    executor.load_account(account_id).unwrap();
+    let consumability =
+        executor.check_note_consumability(account_id, block_ref, note.id()).unwrap();
+    assert!(consumability.is_consumable());
+
+    // a note with an invalid inclusion proof is rejected by the prologue
+    data_store.notes = vec![InputNote::new(notes[0].note().clone(), notes[1].proof().clone())];
+    let executor = TransactionExecutor::new(data_store);
+    executor.load_account(account_id).unwrap();
+    let consumability =
+        executor.check_note_consumability(account_id, block_ref, notes[0].id()).unwrap();
+    assert!(matches!(
+        consumability,
+        NoteConsumability::NotConsumable(
+            TransactionExecutorError::ExecuteTransactionProgramFailed {
+                phase: TransactionPhase::Prologue,
+                ..
+            }
+        )
+    ));
+}
+
+// ASYNC DATA STORE
+// ================================================================================================
+
+#[cfg(feature = "async")]
+#[test]
+fn test_execute_transaction_with_async_data_store() {
+    #[async_trait::async_trait]
+    impl AsyncDataStore for MockDataStore {
+        async fn get_transaction_inputs(
+            &self,
+            account_id: AccountId,
+            block_ref: u32,
+            notes: &[NoteId],
+        ) -> Result<TransactionInputs, DataStoreError> {
+            DataStore::get_transaction_inputs(self, account_id, block_ref, notes)
+        }
+
+        async fn get_account_code(
+            &self,
+            account_id: AccountId,
+        ) -> Result<ModuleAst, DataStoreError> {
+            DataStore::get_account_code(self, account_id)
+        }
+    }
+
+    let data_store = MockDataStore::default();
+    let account_id = data_store.account.id();
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    fn assert_send<F: core::future::Future + Send>(future: F) -> F {
+        future
+    }
+
+    // the futures returned by the executor can be spawned onto multi-threaded runtimes
+    let executed_transaction = futures::executor::block_on(assert_send(async {
+        let executor = TransactionExecutor::builder().with_data_store(data_store.clone()).build();
+        executor.load_account_async(account_id).await.unwrap();
+        executor
+            .execute_transaction_async(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+            .await
+            .unwrap()
+    }));
+
+    // the transaction is the same as the one executed against the synchronous data store
+    let executor = TransactionExecutor::new(data_store);
+    executor.load_account(account_id).unwrap();
+    let expected = executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    assert_eq!(executed_transaction.id(), expected.id());
+}
+
+// EVENT HANDLERS
+// ================================================================================================
+
+#[test]
+fn test_user_events_are_dispatched_to_registered_handlers() {
+    struct RecordingHandler(Mutex<Vec<(EventId, Felt)>>);
+
+    impl EventHandler for RecordingHandler {
+        fn on_event(
+            &self,
+            event_id: EventId,
+            process: &dyn ProcessState,
+        ) -> Result<(), ExecutionError> {
+            self.0.lock().unwrap().push((event_id, process.get_stack_item(0)));
+            Ok(())
+        }
+    }
+
+    let namespace = EventNamespace::user(0x1234).unwrap();
+    let handler = Arc::new(RecordingHandler(Mutex::new(Vec::new())));
+    let mut event_handlers = EventHandlers::new();
+    event_handlers.register(namespace, handler.clone()).unwrap();
+
+    // handlers of composed components must not collide, and the kernel namespace is reserved
+    let mut other_handlers = EventHandlers::new();
+    other_handlers.register(namespace, handler.clone()).unwrap();
+    assert_eq!(
+        event_handlers.clone().merge(other_handlers),
+        Err(EventRegistrationError::NamespaceCollision(namespace))
+    );
+    assert_eq!(
+        EventHandlers::new().register(EventNamespace::Kernel, handler.clone()),
+        Err(EventRegistrationError::ReservedNamespace(EventNamespace::Kernel))
+    );
+    assert!(EventNamespace::user(EventNamespace::MIDEN_LIB_PREFIX).is_err());
+
+    let data_store = MockDataStore::default();
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_event_handlers(event_handlers)
+        .build();
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    // events in namespaces without a registered handler are ignored
+    let handled_event = EventId::new(namespace, 7);
+    let ignored_event = EventId::new(EventNamespace::user(0x4321).unwrap(), 7);
+    let tx_script_source = format!(
+        "
+    begin
+        push.42 emit.{handled} drop
+        push.43 emit.{ignored} drop
+    end
+",
+        handled = handled_event.as_u32(),
+        ignored = ignored_event.as_u32(),
+    );
+    let tx_script = executor
+        .compile_tx_script(ProgramAst::parse(&tx_script_source).unwrap(), vec![], vec![])
+        .unwrap();
+    executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    assert_eq!(handler.0.lock().unwrap().as_slice(), &[(handled_event, Felt::new(42))]);
+    assert_eq!(EventId::try_from(handled_event.as_u32()), Ok(handled_event));
+}
+
+// TRANSACTION TRACING
+// ================================================================================================
+
+#[test]
+fn test_trace_handler_and_failure_context() {
+    #[derive(Default)]
+    struct RecordingTraceHandler {
+        phases: Mutex<Vec<TransactionPhase>>,
+        procedures: Mutex<Vec<(String, TransactionPhase)>>,
+    }
+
+    impl TransactionTraceHandler for RecordingTraceHandler {
+        fn on_phase_started(&self, phase: TransactionPhase, _process: &dyn ProcessState) {
+            self.phases.lock().unwrap().push(phase);
+        }
+
+        fn on_kernel_procedure_entered(
+            &self,
+            procedure: &str,
+            phase: TransactionPhase,
+            _process: &dyn ProcessState,
+        ) {
+            self.procedures.lock().unwrap().push((procedure.to_string(), phase));
+        }
+    }
+
+    let data_store = MockDataStore::default();
+    let trace_handler = Arc::new(RecordingTraceHandler::default());
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_trace_handler(trace_handler.clone())
+        .build();
+    assert!(executor.execution_options().enable_tracing());
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    executor
+        .execute_transaction(account_id, block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+
+    // every note is executed within the note processing phase
+    let mut expected = vec![TransactionPhase::NotesProcessing];
+    for note_index in 0..note_ids.len() {
+        expected.push(TransactionPhase::NoteExecution { note_index });
+        expected.push(TransactionPhase::NotesProcessing);
+    }
+    expected.extend([TransactionPhase::TransactionScript, TransactionPhase::Epilogue]);
+    assert_eq!(trace_handler.phases.lock().unwrap().as_slice(), expected.as_slice());
+
+    // kernel procedures are reported together with the phase from which they were invoked
+    let procedures = trace_handler.procedures.lock().unwrap().clone();
+    assert!(procedures
+        .iter()
+        .all(|(_, phase)| matches!(phase, TransactionPhase::NoteExecution { .. })));
+    let tx_script = executor
+        .compile_tx_script(
+            ProgramAst::parse("use.miden::account begin exec.account::get_id drop end").unwrap(),
+            vec![],
+            vec![],
+        )
+        .unwrap();
+    trace_handler.procedures.lock().unwrap().clear();
+    executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+    assert!(trace_handler
+        .procedures
+        .lock()
+        .unwrap()
+        .contains(&("get_account_id".to_string(), TransactionPhase::TransactionScript)));
+
+    // a failure of the transaction script is attributed to the transaction script phase
+    let tx_script = executor
+        .compile_tx_script(ProgramAst::parse("begin push.0 assert end").unwrap(), vec![], vec![])
+        .unwrap();
+    let result =
+        executor.execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD);
+    assert!(matches!(
+        result,
+        Err(TransactionExecutorError::ExecuteTransactionProgramFailed {
+            phase: TransactionPhase::TransactionScript,
+            failed_note: None,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_decoded_events_are_streamed_to_trace_handler() {
+    struct PriceHandler;
+
+    impl EventHandler for PriceHandler {
+        fn on_event(
+            &self,
+            _event_id: EventId,
+            _process: &dyn ProcessState,
+        ) -> Result<(), ExecutionError> {
+            Ok(())
+        }
+
+        fn decode(&self, event_id: EventId, stack: &[Felt]) -> Option<String> {
+            (event_id.index() == 1).then(|| format!("price set to {}", stack[0]))
+        }
+    }
+
+    struct RecordingTraceHandler(Mutex<Vec<DecodedEvent>>);
+
+    impl TransactionTraceHandler for RecordingTraceHandler {
+        fn on_phase_started(&self, _phase: TransactionPhase, _process: &dyn ProcessState) {}
+
+        fn on_event_decoded(
+            &self,
+            event: &DecodedEvent,
+            _phase: TransactionPhase,
+            _process: &dyn ProcessState,
+        ) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    let namespace = EventNamespace::user(0x1234).unwrap();
+    let mut event_handlers = EventHandlers::new();
+    event_handlers.register(namespace, Arc::new(PriceHandler)).unwrap();
+
+    let data_store = MockDataStore::default();
+    let trace_handler = Arc::new(RecordingTraceHandler(Mutex::new(Vec::new())));
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_event_handlers(event_handlers)
+        .with_trace_handler(trace_handler.clone())
+        .build();
+
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let price_event = EventId::new(namespace, 1);
+    let unknown_event = EventId::new(namespace, 2);
+    let tx_script_source = format!(
+        "
+    begin
+        push.42 emit.{price} drop
+        push.43 emit.{unknown} drop
+    end
+",
+        price = price_event.as_u32(),
+        unknown = unknown_event.as_u32(),
+    );
+    let tx_script = executor
+        .compile_tx_script(ProgramAst::parse(&tx_script_source).unwrap(), vec![], vec![])
+        .unwrap();
+    executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    let events = trace_handler.0.lock().unwrap();
+    assert!(events.contains(&DecodedEvent::User {
+        event_id: price_event,
+        description: String::from("price set to 42"),
+    }));
+    assert!(events.contains(&DecodedEvent::Undecoded(unknown_event)));
+
+    // assets added to the account vault are decoded from the stack at the time of the event
+    let note_assets = data_store
+        .notes
+        .iter()
+        .flat_map(|note| note.note().assets().iter().copied())
+        .collect::<Vec<_>>();
+    let added_assets = events
+        .iter()
+        .filter_map(|event| match event {
+            DecodedEvent::Kernel {
+                event: TransactionEvent::AddAssetToAccountVault,
+                payload: TransactionEventPayload::Asset(asset),
+            } => Some(*asset),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(!added_assets.is_empty());
+    assert!(added_assets.iter().all(|asset| note_assets.contains(asset)));
+
+    // storage slot indexes must fit into a byte
+    assert_eq!(
+        TransactionEvent::SetAccountStorageItem.decode_payload(&[Felt::new(256); 5]),
+        Err(TransactionEventParsingError::MalformedPayload(
+            TransactionEvent::SetAccountStorageItem
+        ))
+    );
+}
+
+// FOREIGN ACCOUNTS
+// ================================================================================================
+
+#[test]
+fn test_read_foreign_account_storage() {
+    #[derive(Clone)]
+    struct ForeignAccountDataStore {
+        inner: MockDataStore,
+        foreign_account: ForeignAccountInputs,
+    }
+
+    impl DataStore for ForeignAccountDataStore {
+        fn get_transaction_inputs(
+            &self,
+            account_id: AccountId,
+            block_num: u32,
+            notes: &[NoteId],
+        ) -> Result<TransactionInputs, DataStoreError> {
+            self.inner.get_transaction_inputs(account_id, block_num, notes)
+        }
+
+        fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError> {
+            self.inner.get_account_code(account_id)
+        }
+
+        fn get_foreign_account_inputs(
+            &self,
+            account_id: AccountId,
+            block_ref: u32,
+        ) -> Result<ForeignAccountInputs, DataStoreError> {
+            assert_eq!(block_ref, self.inner.block_header.block_num());
+            if account_id != self.foreign_account.account_id() {
+                return Err(DataStoreError::AccountNotFound(account_id));
+            }
+            Ok(self.foreign_account.clone())
+        }
+    }
+
+    // build an oracle account which publishes a price in its storage
+    let mut data_store = MockDataStore::default();
+    let price = [Felt::new(12345), Felt::new(6), ZERO, ZERO];
+    let storage = AccountStorage::new(vec![(
+        STORAGE_INDEX_0,
+        (StorageSlotType::Value { value_arity: 0 }, price),
+    )])
+    .unwrap();
+    let oracle = Account::new(
+        AccountId::try_from(ACCOUNT_ID_SENDER).unwrap(),
+        AssetVault::default(),
+        storage,
+        mock_account_code(&TransactionKernel::assembler()),
+        ONE,
+    );
+
+    // record the oracle in the account database of the reference block
+    let account_db = SimpleSmt::with_leaves(
+        ACCOUNT_TREE_DEPTH,
+        [&data_store.account, &oracle]
+            .map(|account| (u64::from(account.id()), Word::from(account.hash()))),
+    )
+    .unwrap();
+    let header = data_store.block_header;
+    data_store.block_header = BlockHeader::new(
+        header.prev_hash(),
+        header.block_num(),
+        header.chain_root(),
+        account_db.root(),
+        header.nullifier_root(),
+        header.note_root(),
+        header.batch_root(),
+        header.proof_hash(),
+        header.version(),
+        header.timestamp(),
+    );
+    let index = NodeIndex::new(ACCOUNT_TREE_DEPTH, u64::from(oracle.id())).unwrap();
+    let witness =
+        AccountWitness::new(oracle.id(), oracle.hash(), account_db.get_path(index).unwrap());
+    let foreign_account = ForeignAccountInputs::from_account(&oracle, witness).unwrap();
+
+    let account_id = data_store.account.id();
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let data_store = ForeignAccountDataStore { inner: data_store, foreign_account };
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account_id).unwrap();
+
+    let tx_script_code = ProgramAst::parse(&format!(
+        "
+        use.miden::account
+
+        begin
+            push.{index} push.{oracle_id}
+            exec.account::get_foreign_item
+            push.{price} assert_eqw
+        end
+        ",
+        index = STORAGE_INDEX_0,
+        oracle_id = u64::from(oracle.id()),
+        price = prepare_word(&price),
+    ))
+    .unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+
+    // the transaction script reads the price from the storage of the oracle
+    let executed_transaction = executor
+        .execute_transaction_with_foreign_accounts(
+            account_id,
+            block_ref,
+            &note_ids,
+            &[oracle.id()],
+            Some(tx_script.clone()),
+            EMPTY_WORD,
+        )
+        .unwrap();
+
+    // the data read from the oracle is a part of the witness of the transaction
+    let executed_tx_bytes = executed_transaction.to_bytes();
+    let tx_witness: TransactionWitness = executed_transaction.into();
+    let (stack_inputs, advice_inputs) = tx_witness.get_kernel_inputs(&KernelVersion::current());
+    let mem_advice_provider: MemAdviceProvider = advice_inputs.into();
+    let mut host = TransactionHost::new(tx_witness.account().into(), mem_advice_provider);
+    vm_processor::execute(
+        &witness_program(&tx_witness),
+        stack_inputs,
+        &mut host,
+        Default::default(),
+    )
+    .unwrap();
+
+    // the data of the oracle is recorded together with the transaction, and thus replaying the
+    // transaction does not require access to the oracle
+    let replay_dir = unique_temp_path("miden-tx-foreign-replay-test");
+    let recording_executor = TransactionExecutor::builder()
+        .with_data_store(data_store)
+        .with_replay_recording(&replay_dir)
+        .build();
+    recording_executor.load_account(account_id).unwrap();
+    recording_executor
+        .execute_transaction_with_foreign_accounts(
+            account_id,
+            block_ref,
+            &note_ids,
+            &[oracle.id()],
+            Some(tx_script.clone()),
+            EMPTY_WORD,
+        )
+        .unwrap();
+    let path = std::fs::read_dir(&replay_dir).unwrap().next().unwrap().unwrap().path();
+    assert_eq!(replay(&path).unwrap().to_bytes(), executed_tx_bytes);
+    std::fs::remove_dir_all(&replay_dir).unwrap();
+
+    // reading a foreign account which was not provided to the transaction fails
+    let result = executor.execute_transaction(
+        account_id,
+        block_ref,
+        &note_ids,
+        Some(tx_script.clone()),
+        EMPTY_WORD,
+    );
+    assert!(matches!(
+        result,
+        Err(TransactionExecutorError::ExecuteTransactionProgramFailed {
+            phase: TransactionPhase::TransactionScript,
+            ..
+        })
+    ));
+
+    // foreign accounts unknown to the data store are reported as such
+    let result = executor.execute_transaction_with_foreign_accounts(
+        account_id,
+        block_ref,
+        &note_ids,
+        &[account_id],
+        Some(tx_script),
+        EMPTY_WORD,
+    );
+    assert!(matches!(
+        result,
+        Err(TransactionExecutorError::FetchForeignAccountInputsFailed {
+            source: DataStoreError::AccountNotFound(_),
+            ..
+        })
+    ));
+}
+
+// REFERENCE DATA STORES
+// ================================================================================================
+
+#[test]
+fn test_mem_data_store() {
+    let (account, blocks, notes) = mock_chain_records();
+    let block_ref = blocks.last().unwrap().block_num();
+    let note_ids = notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let mut data_store = MemDataStore::new();
+    for header in blocks.iter() {
+        data_store.add_block(*header).unwrap();
+    }
+    for note in notes {
+        data_store.add_note(note).unwrap();
+    }
+    data_store.add_account(account.clone(), None);
+
+    // blocks which do not extend the chain are rejected
+    assert!(matches!(
+        data_store.add_block(mock_block_header(block_ref, None, None, &[])),
+        Err(DataStoreError::UnexpectedBlockNum { .. })
+    ));
+    assert!(matches!(
+        data_store.add_block(mock_block_header(block_ref + 1, None, None, &[])),
+        Err(DataStoreError::ChainRootMismatch { .. })
+    ));
+
+    // the chain MMR commits to the chain preceding the referenced block
+    let tx_inputs = data_store.get_transaction_inputs(account.id(), block_ref, &note_ids).unwrap();
+    assert_eq!(tx_inputs.block_chain().chain_length(), block_ref as usize);
+    assert_eq!(
+        tx_inputs.block_chain().peaks().hash_peaks(),
+        blocks.last().unwrap().chain_root()
+    );
+
+    // notes can be consumed only against blocks created after the notes
+    let last_note_id = *note_ids.last().unwrap();
+    assert!(matches!(
+        data_store.get_transaction_inputs(account.id(), block_ref - 1, &[last_note_id]),
+        Err(DataStoreError::NoteNotInChain { .. })
+    ));
+
+    // fee parameters are included into the transaction inputs once they are set
+    let fee_faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fee_parameters =
+        FeeParameters::new(1, 10, 100).with_fee_faucet(fee_faucet_id, Digest::default());
+    let mut fee_data_store = data_store.clone();
+    fee_data_store.set_fee_parameters(Some(fee_parameters)).unwrap();
+    let tx_inputs = fee_data_store
+        .get_transaction_inputs(account.id(), block_ref, &note_ids)
+        .unwrap();
+    assert_eq!(tx_inputs.fee_parameters(), Some(&fee_parameters));
+    assert!(matches!(
+        fee_data_store.set_fee_parameters(Some(FeeParameters::new(1, 10, 100))),
+        Err(DataStoreError::InvalidTransactionInput(_))
+    ));
+    assert_eq!(fee_data_store.fee_parameters(), Some(&fee_parameters));
+
+    // the store can be shared by executors running on multiple threads
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    assert_send_sync(&data_store);
+
+    let executor = TransactionExecutor::new(data_store);
+    executor.load_account(account.id()).unwrap();
+    executor
+        .execute_transaction(account.id(), block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_data_store() {
+    let (account, blocks, notes) = mock_chain_records();
+    let block_ref = blocks.last().unwrap().block_num();
+    let note_ids = notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let fee_faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fee_parameters =
+        FeeParameters::new(1, 10, 100).with_fee_faucet(fee_faucet_id, Digest::default());
+
+    let path = unique_temp_path("miden-tx-store-test.sqlite");
+    {
+        let mut data_store = SqliteDataStore::open(&path).unwrap();
+        for header in blocks.iter() {
+            data_store.add_block(*header).unwrap();
+        }
+        for note in notes.iter() {
+            data_store.add_note(note).unwrap();
+        }
+        data_store.add_account(&account, None).unwrap();
+        data_store.set_fee_parameters(Some(fee_parameters)).unwrap();
+    }
+
+    // the chain and the fee parameters are restored when the database is re-opened
+    let mut data_store = SqliteDataStore::open(&path).unwrap();
+    assert_eq!(data_store.latest_block_header(), blocks.last());
+    assert_eq!(data_store.fee_parameters(), Some(&fee_parameters));
+
+    let tx_inputs = data_store.get_transaction_inputs(account.id(), block_ref, &note_ids).unwrap();
+    assert_eq!(tx_inputs.account(), &account);
+    assert_eq!(tx_inputs.input_notes().clone().into_vec(), notes);
+    assert_eq!(tx_inputs.fee_parameters(), Some(&fee_parameters));
+
+    // removed fee parameters are no longer included into the transaction inputs
+    data_store.set_fee_parameters(None).unwrap();
+    let tx_inputs = data_store.get_transaction_inputs(account.id(), block_ref, &note_ids).unwrap();
+    assert_eq!(tx_inputs.fee_parameters(), None);
+
+    let executor = TransactionExecutor::new(data_store);
+    executor.load_account(account.id()).unwrap();
+    executor
+        .execute_transaction(account.id(), block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+
+    std::fs::remove_file(path).unwrap();
+}
+
+// BLOCK BUILDING
+// ================================================================================================
+
+#[test]
+fn test_block_builder_applies_proven_transactions() {
+    let assembler = TransactionKernel::assembler();
+    let account = mock_account(None, ONE, None, &assembler);
+    let (notes, _) = mock_notes(&assembler, &AssetPreservationStatus::Preserved);
+
+    // record the account and the notes in the genesis block
+    let mut builder = BlockBuilder::new([(account.id(), account.hash())]);
+    let genesis = builder
+        .build_genesis_block(notes.iter().map(NoteEnvelope::from).collect(), ZERO)
+        .unwrap();
+    let block_1 = builder.build_block(builder.propose_block(vec![]).unwrap(), ONE).unwrap();
+
+    let mut data_store = MemDataStore::new();
+    data_store.add_block(*genesis.header()).unwrap();
+    data_store.add_block(*block_1.header()).unwrap();
+    data_store.add_account(account.clone(), None);
+    let mut note_ids = Vec::new();
+    for note in notes {
+        let proof = genesis.note_inclusion_proof(note.id()).unwrap();
+        note_ids.push(note.id());
+        data_store.add_note(InputNote::new(note, proof)).unwrap();
+    }
+
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account.id()).unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account.id(), 1, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+    let proven_transaction = TransactionProver::new(ProvingOptions::default())
+        .prove_transaction(executed_transaction)
+        .unwrap();
+
+    let batch = TransactionBatch::new(vec![proven_transaction.clone()], &[]).unwrap();
+    let proposed = builder.propose_block(vec![batch.clone()]).unwrap();
+
+    // a proposed block cannot be built once the chain moved past it
+    let mut stale_builder = builder.clone();
+    stale_builder
+        .build_block(stale_builder.propose_block(vec![]).unwrap(), Felt::new(2))
+        .unwrap();
+    assert_eq!(
+        stale_builder.build_block(proposed.clone(), Felt::new(3)).unwrap_err(),
+        BlockError::UnexpectedBlockNum { expected: 3, actual: 2 }
+    );
+
+    let block = builder.build_block(proposed, Felt::new(2)).unwrap();
+    assert_eq!(block.batches().len(), 1);
+    assert_ne!(block.header().batch_root(), block_1.header().batch_root());
+    assert_ne!(block.header().proof_hash(), block_1.header().proof_hash());
+    let update = &block.updated_accounts()[0];
+    assert_eq!(update.final_state_hash(), proven_transaction.final_account_hash());
+    assert_eq!(update.transactions(), [proven_transaction.id()]);
+    for nullifier in proven_transaction.input_notes().iter() {
+        assert!(block.produced_nullifiers().contains(nullifier));
+        assert_eq!(builder.nullifier_block_num(*nullifier), Some(2));
+    }
+    assert_eq!(
+        builder.account_state_hash(account.id()),
+        Some(proven_transaction.final_account_hash())
+    );
+
+    // the new block extends the chain known to the data store
+    data_store.add_block(*block.header()).unwrap();
+
+    // the same transaction cannot be applied twice
+    let proposed = builder.propose_block(vec![batch]).unwrap();
+    assert!(matches!(
+        builder.build_block(proposed, Felt::new(3)),
+        Err(BlockError::AccountStateMismatch { .. })
+    ));
+    assert_eq!(builder.latest_block_header(), Some(block.header()));
+}
+
+/// Returns an existing account, and a chain of blocks in which every mock note is recorded in a
+/// separate block; the last block commits to the state of the account.
+fn mock_chain_records() -> (Account, Vec<BlockHeader>, Vec<InputNote>) {
+    let assembler = TransactionKernel::assembler();
+    let account = mock_account(None, ONE, None, &assembler);
+    let (notes, _) = mock_notes(&assembler, &AssetPreservationStatus::Preserved);
+
+    let mut chain = Mmr::default();
+    let mut blocks = Vec::new();
+    let mut input_notes = Vec::new();
+    for (block_num, note) in notes.into_iter().enumerate() {
+        let block_num = block_num as u32;
+        let note_tree = SimpleSmt::with_leaves(
+            NOTE_LEAF_DEPTH,
+            [(0, note.id().into()), (1, note.metadata().into())],
+        )
+        .unwrap();
+        let chain_root = chain.peaks(chain.forest()).unwrap().hash_peaks();
+        let header = mock_block_header(block_num, Some(chain_root), Some(note_tree.root()), &[]);
+        chain.add(header.hash());
+        blocks.push(header);
+
+        let note_path = note_tree.get_path(NodeIndex::new(NOTE_TREE_DEPTH, 0).unwrap()).unwrap();
+        let proof =
+            NoteInclusionProof::new(block_num, header.sub_hash(), header.note_root(), 0, note_path)
+                .unwrap();
+        input_notes.push(InputNote::new(note, proof));
+    }
+
+    let chain_root = chain.peaks(chain.forest()).unwrap().hash_peaks();
+    let block_num = chain.forest() as u32;
+    blocks.push(mock_block_header(block_num, Some(chain_root), None, &[account.clone()]));
+
+    (account, blocks, input_notes)
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns a path in the temporary directory which is unique to the calling test run.
+pub(crate) fn unique_temp_path(name: &str) -> std::path::PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system time is after the unix epoch")
+        .as_nanos();
+    std::env::temp_dir().join(format!("{name}-{}-{nanos}", std::process::id()))
+}
+
+/// Compiles the program of the transaction described by the specified witness.
+fn witness_program(tx_witness: &TransactionWitness) -> Program {
+    TransactionCompiler::new()
+        .compile_transaction_from_inputs(tx_witness.tx_inputs(), tx_witness.tx_script())
+        .unwrap()
+}
+
+// MOCK DATA STORE
+// ================================================================================================
+
+#[derive(Clone)]
+pub(crate) struct MockDataStore {
+    pub account: Account,
+    pub block_header: BlockHeader,
+    pub block_chain: ChainMmr,
+    pub notes: Vec<InputNote>,
+    pub fee_parameters: Option<FeeParameters>,
+}
+
+impl MockDataStore {
+    pub fn new(asset_preservation: AssetPreservationStatus) -> Self {
+        let (account, _, block_header, block_chain, notes, fee_parameters) =
+            mock_inputs(MockAccountType::StandardExisting, asset_preservation).into_parts();
+
+        Self {
+            account,
+            block_header,
+            block_chain,
+            notes: notes.into_vec(),
+            fee_parameters,
+        }
+    }
+
+    /// Returns a data store for transactions against the specified account which do not consume
+    /// any notes.
+    #[cfg(feature = "testing")]
+    pub fn with_account(account: Account) -> Self {
+        let (account, block_header, block_chain, notes, _) = mock_inputs_with_existing(
+            MockAccountType::StandardExisting,
+            AssetPreservationStatus::Preserved,
+            Some(account),
+            Some(vec![]),
+        );
+
+        Self {
+            account,
+            block_header,
+            block_chain,
+            notes,
+            fee_parameters: None,
+        }
+    }
+
+    /// Returns a data store for transactions against the default mock account which consume the
+    /// specified notes.
+    pub fn with_notes(notes: Vec<Note>) -> Self {
+        let (account, block_header, block_chain, notes, _) = mock_inputs_with_existing(
+            MockAccountType::StandardExisting,
+            AssetPreservationStatus::Preserved,
+            None,
+            Some(notes),
+        );
+
+        Self {
+            account,
+            block_header,
+            block_chain,
+            notes,
+            fee_parameters: None,
+        }
+    }
+
+    pub fn with_fee_parameters(mut self, fee_parameters: FeeParameters) -> Self {
+        self.fee_parameters = Some(fee_parameters);
+        self
+    }
+}
+
+impl Default for MockDataStore {
+    fn default() -> Self {
+        Self::new(AssetPreservationStatus::Preserved)
+    }
+}
+
+impl DataStore for MockDataStore {
+    fn get_transaction_inputs(
+        &self,
         account_id: AccountId,
         block_num: u32,
         notes: &[NoteId],
@@ -382,14 +2590,19 @@ impl DataStore for MockDataStore {
             .cloned()
             .collect::<Vec<_>>();
 
-        Ok(TransactionInputs::new(
+        let tx_inputs = TransactionInputs::new(
             self.account.clone(),
             None,
             self.block_header,
             self.block_chain.clone(),
             InputNotes::new(notes).unwrap(),
         )
-        .unwrap())
+        .unwrap();
+
+        match self.fee_parameters {
+            Some(fee_parameters) => Ok(tx_inputs.with_fee_parameters(fee_parameters).unwrap()),
+            None => Ok(tx_inputs),
+        }
     }
 
     fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError> {
@@ -0,0 +1,262 @@
+use miden_objects::{
+    notes::Nullifier,
+    transaction::{
+        FeeParameters, ProvenTransaction, ProvenTransactionHeader, MAX_INPUT_NOTES_PER_TRANSACTION,
+        MAX_OUTPUT_NOTES_PER_TRANSACTION,
+    },
+    utils::{collections::BTreeSet, serde::Serializable},
+};
+
+use super::TransactionValidationError;
+
+#[cfg(all(test, feature = "testing"))]
+mod tests;
+
+// VALIDATION LIMITS
+// ================================================================================================
+
+/// Limits enforced by a [TransactionValidator] on incoming transactions.
+///
+/// The limits are policies of the operator receiving the transactions, and thus can be stricter
+/// than the limits enforced by the protocol (e.g., [MAX_INPUT_NOTES_PER_TRANSACTION]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationLimits {
+    /// Maximum number of notes consumed by a transaction.
+    pub max_input_notes: usize,
+    /// Maximum number of notes created by a transaction.
+    pub max_output_notes: usize,
+    /// Maximum size of a serialized transaction (including the proof) in bytes.
+    pub max_tx_size: usize,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self {
+            max_input_notes: MAX_INPUT_NOTES_PER_TRANSACTION,
+            max_output_notes: MAX_OUTPUT_NOTES_PER_TRANSACTION,
+            max_tx_size: 1 << 20,
+        }
+    }
+}
+
+// TRANSACTION VALIDATOR
+// ================================================================================================
+
+/// The [TransactionValidator] performs cheap admission checks on incoming [ProvenTransaction]s.
+///
+/// The checks do not involve verification of the transaction proof, and thus are intended to be
+/// performed before the proof is verified (e.g., by a mempool or an RPC endpoint) to reject
+/// invalid transactions early. The checks are:
+/// - The serialized transaction does not exceed the maximum size.
+/// - The numbers of consumed and created notes do not exceed the limits.
+/// - The transaction has an effect, i.e., it consumes or creates notes, or updates the account.
+/// - None of the consumed notes is being consumed by another pending transaction.
+/// - The transaction is not already expired with respect to the next block.
+/// - None of the created notes is already expired with respect to the next block.
+/// - The account details of the transaction, if any, are consistent with the updated account.
+/// - If the validator was configured with fee parameters, the fee charged by the transaction
+///   kernel covers the fee required by these parameters, and is paid into an output note of the
+///   transaction addressed to the fee recipient of these parameters.
+///
+/// Transactions which pass these checks must still be verified via a
+/// [TransactionVerifier](crate::TransactionVerifier).
+#[derive(Debug, Clone, Default)]
+pub struct TransactionValidator {
+    limits: ValidationLimits,
+    fee_parameters: Option<FeeParameters>,
+}
+
+impl TransactionValidator {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [TransactionValidator] enforcing the specified limits.
+    pub fn new(limits: ValidationLimits) -> Self {
+        Self { limits, fee_parameters: None }
+    }
+
+    /// Returns this validator configured to reject transactions which did not pay the fee required
+    /// by the specified fee parameters (see [FeeParameters::is_fee_sufficient()]).
+    pub fn with_fee_parameters(mut self, fee_parameters: FeeParameters) -> Self {
+        self.fee_parameters = Some(fee_parameters);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the limits enforced by this validator.
+    pub fn limits(&self) -> &ValidationLimits {
+        &self.limits
+    }
+
+    /// Returns the fee parameters enforced by this validator, if any.
+    pub fn fee_parameters(&self) -> Option<&FeeParameters> {
+        self.fee_parameters.as_ref()
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Validates the provided transaction against the current state of the mempool.
+    ///
+    /// `chain_height` is the number of the latest block, and `pending_nullifiers` contains the
+    /// nullifiers of the notes consumed by transactions which were accepted but not yet included
+    /// into a block.
+    ///
+    /// # Errors
+    /// Returns an error describing the reason for rejecting the transaction if any of the checks
+    /// fails.
+    pub fn validate(
+        &self,
+        transaction: &ProvenTransaction,
+        chain_height: u32,
+        pending_nullifiers: &BTreeSet<Nullifier>,
+    ) -> Result<(), TransactionValidationError> {
+        let tx_size = transaction.to_bytes().len();
+        self.validate_header(&transaction.header(), tx_size, chain_height, pending_nullifiers)
+    }
+
+    /// Validates the transaction serialized in the provided bytes against the current state of
+    /// the mempool.
+    ///
+    /// Only the header of the transaction is decoded; the proof bytes are not touched. Thus, this
+    /// is the cheapest way to validate transactions received over the network.
+    ///
+    /// # Errors
+    /// Returns an error describing the reason for rejecting the transaction if the bytes are not
+    /// a valid serialized transaction, or if any of the checks fails.
+    pub fn validate_bytes(
+        &self,
+        bytes: &[u8],
+        chain_height: u32,
+        pending_nullifiers: &BTreeSet<Nullifier>,
+    ) -> Result<ProvenTransactionHeader, TransactionValidationError> {
+        if bytes.len() > self.limits.max_tx_size {
+            return Err(TransactionValidationError::TransactionTooLarge {
+                actual: bytes.len(),
+                max: self.limits.max_tx_size,
+            });
+        }
+
+        let (header, _) = ProvenTransactionHeader::read_from_proven_tx_bytes(bytes)
+            .map_err(TransactionValidationError::MalformedTransaction)?;
+        self.validate_header(&header, bytes.len(), chain_height, pending_nullifiers)?;
+
+        Ok(header)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn validate_header(
+        &self,
+        tx: &ProvenTransactionHeader,
+        tx_size: usize,
+        chain_height: u32,
+        pending_nullifiers: &BTreeSet<Nullifier>,
+    ) -> Result<(), TransactionValidationError> {
+        if tx_size > self.limits.max_tx_size {
+            return Err(TransactionValidationError::TransactionTooLarge {
+                actual: tx_size,
+                max: self.limits.max_tx_size,
+            });
+        }
+
+        let num_input_notes = tx.input_notes().num_notes();
+        if num_input_notes > self.limits.max_input_notes {
+            return Err(TransactionValidationError::TooManyInputNotes {
+                actual: num_input_notes,
+                max: self.limits.max_input_notes,
+            });
+        }
+
+        let num_output_notes = tx.output_notes().num_notes();
+        if num_output_notes > self.limits.max_output_notes {
+            return Err(TransactionValidationError::TooManyOutputNotes {
+                actual: num_output_notes,
+                max: self.limits.max_output_notes,
+            });
+        }
+
+        if num_input_notes == 0
+            && num_output_notes == 0
+            && tx.initial_account_hash() == tx.final_account_hash()
+        {
+            return Err(TransactionValidationError::EmptyTransaction(tx.id()));
+        }
+
+        if let Some(nullifier) =
+            tx.input_notes().iter().find(|nullifier| pending_nullifiers.contains(nullifier))
+        {
+            return Err(TransactionValidationError::NullifierAlreadyPending(*nullifier));
+        }
+
+        // the transaction and the notes it creates are recorded in the next block at the earliest
+        let next_block_num = chain_height.saturating_add(1);
+        if let Some(expiration_block_num) = tx.expiration_block_num() {
+            if next_block_num > expiration_block_num {
+                return Err(TransactionValidationError::TransactionExpired {
+                    tx_id: tx.id(),
+                    expiration_block_num,
+                });
+            }
+        }
+
+        if let Some(note) =
+            tx.output_notes().iter().find(|note| note.metadata().is_expired(next_block_num))
+        {
+            return Err(TransactionValidationError::OutputNoteExpired {
+                note_id: note.note_id(),
+                expiration_block_num: note
+                    .metadata()
+                    .expiration_block_num()
+                    .expect("expired note has an expiration block number"),
+            });
+        }
+
+        if let Some(fee_parameters) = self.fee_parameters.as_ref() {
+            let fee = tx.fee().ok_or_else(|| TransactionValidationError::InsufficientFee {
+                tx_id: tx.id(),
+                fee: None,
+            })?;
+
+            // the fee note is committed to by the proof only as one of the output notes
+            let expected_note_id = fee_parameters.fee_note_id(fee.asset());
+            if fee.note_id() != expected_note_id {
+                return Err(TransactionValidationError::FeeNoteMismatch {
+                    tx_id: tx.id(),
+                    expected: expected_note_id,
+                    actual: fee.note_id(),
+                });
+            }
+            if !tx.output_notes().iter().any(|note| note.note_id() == fee.note_id()) {
+                return Err(TransactionValidationError::FeeNoteNotFound {
+                    tx_id: tx.id(),
+                    note_id: fee.note_id(),
+                });
+            }
+
+            let num_notes = num_input_notes + num_output_notes;
+            if !fee_parameters.is_fee_sufficient(&fee, num_notes) {
+                return Err(TransactionValidationError::InsufficientFee {
+                    tx_id: tx.id(),
+                    fee: Some(fee),
+                });
+            }
+        }
+
+        if let Some(account) = tx.account_details() {
+            if account.id() != tx.account_id() {
+                return Err(TransactionValidationError::AccountDetailsMismatch {
+                    expected: tx.account_id(),
+                    actual: account.id(),
+                });
+            }
+            if !account.id().is_public() {
+                return Err(TransactionValidationError::PrivateAccountDetails(account.id()));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,326 @@
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::{Account, AccountCode, AccountId, AccountStorage},
+    assembly::{ModuleAst, ProgramAst},
+    assets::{Asset, AssetVault, FungibleAsset},
+    transaction::{FeeParameters, ProvenTransaction, TransactionFee},
+    utils::{collections::BTreeSet, serde::Serializable},
+    Digest, Felt, EMPTY_WORD, ONE, ZERO,
+};
+use mock::{
+    constants::{
+        ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_SENDER,
+        ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX,
+    },
+    utils::prepare_word,
+};
+use vm_core::utils::to_hex;
+
+use super::{TransactionValidator, ValidationLimits};
+use crate::{tests::MockDataStore, TestProver, TransactionExecutor, TransactionValidationError};
+
+// HELPERS
+// ================================================================================================
+
+/// Executes a transaction against the account of the specified data store, consuming all notes of
+/// the data store, and proves it with the [TestProver].
+fn prove_transaction(data_store: MockDataStore, tx_script: Option<String>) -> ProvenTransaction {
+    let executor = TransactionExecutor::new(data_store.clone());
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let tx_script = tx_script.map(|source| {
+        let tx_script_code = ProgramAst::parse(&source).unwrap();
+        executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap()
+    });
+
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, tx_script, EMPTY_WORD)
+        .unwrap();
+    TestProver::new().prove_transaction(executed_transaction).unwrap()
+}
+
+/// Returns a transaction which consumes two notes and creates three notes.
+fn mock_transaction() -> (ProvenTransaction, u32) {
+    let data_store = MockDataStore::default();
+    let block_ref = data_store.block_header.block_num();
+    (prove_transaction(data_store, None), block_ref)
+}
+
+/// Returns a transaction which expires at the specified offset from its reference block, and
+/// creates a single note which expires at the specified offset from the reference block.
+fn expiring_transaction(tx_expiration: u32, note_expiration: u32) -> (ProvenTransaction, u32) {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+
+    let send_asset_with_expiration = "
+        use.miden::contracts::wallets::basic->wallet
+        use.miden::tx
+
+        export.send_asset_with_expiration
+            exec.wallet::send_asset
+            drop dropw dropw
+            exec.tx::set_note_expiration
+        end
+    ";
+    let account_code_src = format!(
+        "
+        use.miden::account
+        {send_asset_with_expiration}
+        export.incr_nonce
+            push.0 swap
+            exec.account::incr_nonce
+        end
+        "
+    );
+    let assembler = TransactionKernel::assembler();
+    let procedure_root =
+        AccountCode::new(ModuleAst::parse(send_asset_with_expiration).unwrap(), &assembler)
+            .unwrap()
+            .procedures()[0];
+    let account_code =
+        AccountCode::new(ModuleAst::parse(&account_code_src).unwrap(), &assembler).unwrap();
+    let account = Account::new(
+        AccountId::try_from(ACCOUNT_ID_SENDER).unwrap(),
+        AssetVault::new(&[asset]).unwrap(),
+        AccountStorage::new(vec![]).unwrap(),
+        account_code.clone(),
+        ONE,
+    );
+    let incr_nonce_root = account_code
+        .procedures()
+        .iter()
+        .find(|&&root| root != procedure_root)
+        .copied()
+        .unwrap();
+
+    let data_store = MockDataStore::with_account(account);
+    let block_ref = data_store.block_header.block_num();
+    let recipient = [ZERO, ONE, Felt::new(2), Felt::new(3)];
+    let tx_script = format!(
+        "
+        use.miden::tx
+
+        begin
+            push.{note_expiration}
+            push.{recipient}
+            push.4
+            push.{asset}
+            call.0x{procedure_root}
+            dropw dropw dropw dropw
+
+            push.1 call.0x{incr_nonce_root} drop
+
+            push.{tx_expiration} exec.tx::update_expiration_block_num
+        end
+        ",
+        note_expiration = block_ref + note_expiration,
+        recipient = prepare_word(&recipient),
+        asset = prepare_word(&asset.into()),
+        procedure_root = to_hex(&procedure_root.as_bytes()).unwrap(),
+        incr_nonce_root = to_hex(&incr_nonce_root.as_bytes()).unwrap(),
+        tx_expiration = block_ref + tx_expiration,
+    );
+
+    (prove_transaction(data_store, Some(tx_script)), block_ref)
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn valid_transaction_is_accepted() {
+    let validator = TransactionValidator::default();
+    let (transaction, block_ref) = mock_transaction();
+
+    assert_eq!(validator.validate(&transaction, block_ref, &BTreeSet::new()), Ok(()));
+    let header = validator
+        .validate_bytes(&transaction.to_bytes(), block_ref, &BTreeSet::new())
+        .unwrap();
+    assert_eq!(header.id(), transaction.id());
+    assert_eq!(header.account_id(), transaction.account_id());
+}
+
+#[test]
+fn invalid_transactions_are_rejected() {
+    let validator = TransactionValidator::default();
+    let (transaction, block_ref) = mock_transaction();
+
+    // malformed transaction
+    let result = validator.validate_bytes(&[1, 2, 3], block_ref, &BTreeSet::new());
+    assert!(matches!(result, Err(TransactionValidationError::MalformedTransaction(_))));
+
+    let bytes = transaction.to_bytes();
+    let result = validator.validate_bytes(&bytes[..bytes.len() / 4], block_ref, &BTreeSet::new());
+    assert!(matches!(result, Err(TransactionValidationError::MalformedTransaction(_))));
+
+    // the note is already consumed by a pending transaction
+    let nullifier = *transaction.input_notes().iter().next().unwrap();
+    let result = validator.validate(&transaction, block_ref, &BTreeSet::from([nullifier]));
+    assert_eq!(result, Err(TransactionValidationError::NullifierAlreadyPending(nullifier)));
+
+    // transaction without any effect
+    let data_store = MockDataStore::with_account(MockDataStore::default().account);
+    let block_ref = data_store.block_header.block_num();
+    let empty_transaction = prove_transaction(data_store, None);
+    let result = validator.validate(&empty_transaction, block_ref, &BTreeSet::new());
+    assert_eq!(
+        result,
+        Err(TransactionValidationError::EmptyTransaction(empty_transaction.id()))
+    );
+}
+
+#[test]
+fn expired_transactions_are_rejected() {
+    let validator = TransactionValidator::default();
+    let (transaction, block_ref) = expiring_transaction(10, 5);
+    let note_id = transaction.output_notes().iter().next().unwrap().note_id();
+
+    // the transaction and its note can still be included into the next block
+    assert_eq!(validator.validate(&transaction, block_ref + 4, &BTreeSet::new()), Ok(()));
+
+    // the output note expires before the next block
+    let result = validator.validate(&transaction, block_ref + 5, &BTreeSet::new());
+    assert_eq!(
+        result,
+        Err(TransactionValidationError::OutputNoteExpired {
+            note_id,
+            expiration_block_num: block_ref + 5
+        })
+    );
+
+    // the transaction expires before the next block
+    let result = validator.validate(&transaction, block_ref + 10, &BTreeSet::new());
+    assert_eq!(
+        result,
+        Err(TransactionValidationError::TransactionExpired {
+            tx_id: transaction.id(),
+            expiration_block_num: block_ref + 10
+        })
+    );
+}
+
+#[test]
+fn limits_are_enforced() {
+    let (transaction, block_ref) = mock_transaction();
+    let bytes = transaction.to_bytes();
+    assert_eq!(transaction.input_notes().num_notes(), 2);
+    assert_eq!(transaction.output_notes().num_notes(), 3);
+
+    let limits = ValidationLimits { max_input_notes: 1, ..Default::default() };
+    let result =
+        TransactionValidator::new(limits).validate(&transaction, block_ref, &BTreeSet::new());
+    assert_eq!(result, Err(TransactionValidationError::TooManyInputNotes { actual: 2, max: 1 }));
+
+    let limits = ValidationLimits {
+        max_output_notes: 1,
+        ..Default::default()
+    };
+    let result =
+        TransactionValidator::new(limits).validate(&transaction, block_ref, &BTreeSet::new());
+    assert_eq!(
+        result,
+        Err(TransactionValidationError::TooManyOutputNotes { actual: 3, max: 1 })
+    );
+
+    // the size limit is enforced before the transaction is decoded
+    let limits = ValidationLimits { max_tx_size: 1024, ..Default::default() };
+    let result =
+        TransactionValidator::new(limits).validate_bytes(&bytes, block_ref, &BTreeSet::new());
+    assert_eq!(
+        result,
+        Err(TransactionValidationError::TransactionTooLarge { actual: bytes.len(), max: 1024 })
+    );
+}
+
+#[test]
+fn fees_are_enforced() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fee_recipient = Digest::new([ONE, ZERO, ONE, ZERO]);
+    let fee_parameters = FeeParameters::new(1, 10, 100).with_fee_faucet(faucet_id, fee_recipient);
+
+    // paying the fee changes the account vault, and thus the nonce must be incremented
+    let data_store = MockDataStore::default().with_fee_parameters(fee_parameters);
+    let block_ref = data_store.block_header.block_num();
+    let incr_nonce_root = to_hex(
+        &data_store.account.code().procedures()[ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX].as_bytes(),
+    )
+    .unwrap();
+    let tx_script = format!("begin push.1 call.0x{incr_nonce_root} drop end");
+    let transaction = prove_transaction(data_store, Some(tx_script));
+    let fee = transaction.fee().expect("fee was not charged");
+
+    // the fee charged by the kernel covers the fee required by the same parameters
+    let validator = TransactionValidator::default().with_fee_parameters(fee_parameters);
+    assert_eq!(validator.validate(&transaction, block_ref, &BTreeSet::new()), Ok(()));
+
+    // but not the fee required by stricter parameters
+    let validator = TransactionValidator::default().with_fee_parameters(
+        FeeParameters::new(1, 10, fee.asset().amount() + 1)
+            .with_fee_faucet(faucet_id, fee_recipient),
+    );
+    let result = validator.validate(&transaction, block_ref, &BTreeSet::new());
+    assert_eq!(
+        result,
+        Err(TransactionValidationError::InsufficientFee {
+            tx_id: transaction.id(),
+            fee: Some(fee)
+        })
+    );
+
+    // the fee must be paid to the fee recipient of the operator
+    let other_parameters = fee_parameters.with_fee_faucet(faucet_id, Digest::default());
+    let result = TransactionValidator::default().with_fee_parameters(other_parameters).validate(
+        &transaction,
+        block_ref,
+        &BTreeSet::new(),
+    );
+    assert_eq!(
+        result,
+        Err(TransactionValidationError::FeeNoteMismatch {
+            tx_id: transaction.id(),
+            expected: other_parameters.fee_note_id(fee.asset()),
+            actual: fee.note_id(),
+        })
+    );
+
+    // and the fee note must be an output note of the transaction
+    let claimed_asset = FungibleAsset::new(faucet_id, fee.asset().amount() * 2).unwrap();
+    let claimed_fee = TransactionFee::new(
+        claimed_asset,
+        fee.num_cycles(),
+        fee_parameters.fee_note_id(claimed_asset),
+    );
+    let tampered_transaction = ProvenTransaction::new(
+        transaction.account_id(),
+        transaction.initial_account_hash(),
+        transaction.final_account_hash(),
+        transaction.input_notes().clone(),
+        transaction.output_notes().clone(),
+        transaction.tx_script_root(),
+        transaction.block_ref(),
+        transaction.kernel_hash(),
+        transaction.account_details().cloned(),
+        transaction.expiration_block_num(),
+        Some(claimed_fee),
+        transaction.proof().clone(),
+    );
+    let validator = TransactionValidator::default().with_fee_parameters(fee_parameters);
+    assert_eq!(
+        validator.validate(&tampered_transaction, block_ref, &BTreeSet::new()),
+        Err(TransactionValidationError::FeeNoteNotFound {
+            tx_id: tampered_transaction.id(),
+            note_id: claimed_fee.note_id(),
+        })
+    );
+
+    // transactions which did not pay any fee are rejected
+    let (transaction, block_ref) = mock_transaction();
+    let result = validator.validate(&transaction, block_ref, &BTreeSet::new());
+    assert_eq!(
+        result,
+        Err(TransactionValidationError::InsufficientFee { tx_id: transaction.id(), fee: None })
+    );
+}
@@ -1,6 +1,8 @@
-use miden_lib::transaction::TransactionKernel;
-use miden_objects::{transaction::ProvenTransaction, vm::ProgramInfo};
+use miden_lib::transaction::KernelVersion;
+use miden_objects::{transaction::ProvenTransaction, utils::collections::BTreeMap, Digest};
 use miden_verifier::verify;
+#[cfg(feature = "concurrent")]
+use rayon::prelude::*;
 
 use super::TransactionVerifierError;
 
@@ -9,56 +11,137 @@ use super::TransactionVerifierError;
 
 /// The [TransactionVerifier] is used to verify  [ProvenTransaction]s.
 ///
-/// The [TransactionVerifier] contains a [ProgramInfo] object which is associated with the
-/// transaction kernel program.  The `proof_security_level` specifies the minimum security
-/// level that the transaction proof must have in order to be considered valid.
+/// The [TransactionVerifier] contains the versions of the transaction kernel it accepts, keyed by
+/// their hashes. By default, only the current transaction kernel is accepted; during a rolling
+/// kernel upgrade, a verifier accepting several kernel versions can be instantiated via
+/// [TransactionVerifier::with_kernels()]. Each transaction is verified only against the kernel
+/// version it was executed with (see [ProvenTransaction::kernel_hash()]). The
+/// `proof_security_level` specifies the minimum security level that the transaction proof must
+/// have in order to be considered valid.
 pub struct TransactionVerifier {
-    tx_program_info: ProgramInfo,
+    kernels: BTreeMap<Digest, KernelVersion>,
     proof_security_level: u32,
 }
 
 impl TransactionVerifier {
-    /// Returns a new [TransactionVerifier] instantiated with the specified security level.
+    /// Returns a new [TransactionVerifier] instantiated with the specified security level, which
+    /// accepts transactions proven against the current transaction kernel.
     pub fn new(proof_security_level: u32) -> Self {
-        let tx_program_info = TransactionKernel::program_info();
-        Self { tx_program_info, proof_security_level }
+        Self::with_kernels(proof_security_level, [KernelVersion::current()])
     }
 
-    /// Verifies the provided [ProvenTransaction] against the transaction kernel.
+    /// Returns a new [TransactionVerifier] instantiated with the specified security level, which
+    /// accepts transactions proven against any of the specified transaction kernel versions.
+    pub fn with_kernels(
+        proof_security_level: u32,
+        kernels: impl IntoIterator<Item = KernelVersion>,
+    ) -> Self {
+        let kernels = kernels.into_iter().map(|kernel| (kernel.hash(), kernel)).collect();
+        Self { kernels, proof_security_level }
+    }
+
+    /// Returns a new [TransactionVerifier] which accepts proofs of any security level.
+    ///
+    /// This is intended to verify proofs generated by the [TestProver](crate::TestProver) and
+    /// must never be used outside of tests.
+    #[cfg(feature = "testing")]
+    pub fn new_insecure() -> Self {
+        Self::new(0)
+    }
+
+    /// Verifies the provided [ProvenTransaction] against the transaction kernel it was executed
+    /// with.
     ///
     /// # Errors
     /// Returns an error if:
-    /// - Transaction verification fails.
+    /// - The transaction was executed with a kernel version not accepted by this verifier.
+    /// - Transaction verification fails. This includes the case when
+    ///   the expiration block number of the transaction differs from the one committed to by the
+    ///   proof; as the transaction kernel only accepts expiration block numbers after the
+    ///   reference block, a transaction claiming to expire at or before its reference block is
+    ///   always rejected.
     /// - The security level of the verified proof is insufficient.
     pub fn verify(&self, transaction: ProvenTransaction) -> Result<(), TransactionVerifierError> {
+        self.verify_transaction(&transaction)
+    }
+
+    /// Verifies the provided [ProvenTransaction]s against the transaction kernels they were
+    /// executed with and returns the result of verification of each transaction, in the order in
+    /// which the transactions were provided.
+    ///
+    /// When the `concurrent` feature is enabled, proofs are verified in parallel on the global
+    /// [rayon](https://docs.rs/rayon) thread pool; otherwise, they are verified one after another.
+    /// In both cases, a failure to verify one transaction does not prevent the remaining
+    /// transactions from being verified.
+    pub fn verify_batch(
+        &self,
+        transactions: &[ProvenTransaction],
+    ) -> Vec<Result<(), TransactionVerifierError>> {
+        #[cfg(feature = "concurrent")]
+        let results = transactions.par_iter().map(|tx| self.verify_transaction(tx)).collect();
+
+        #[cfg(not(feature = "concurrent"))]
+        let results = transactions.iter().map(|tx| self.verify_transaction(tx)).collect();
+
+        results
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over the versions of the transaction kernel accepted by this verifier.
+    pub fn kernels(&self) -> impl Iterator<Item = &KernelVersion> {
+        self.kernels.values()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn verify_transaction(
+        &self,
+        transaction: &ProvenTransaction,
+    ) -> Result<(), TransactionVerifierError> {
+        let kernel = self.kernels.get(&transaction.kernel_hash()).ok_or(
+            TransactionVerifierError::UnknownKernel {
+                tx_id: transaction.id(),
+                kernel_hash: transaction.kernel_hash(),
+            },
+        )?;
+
         // build stack inputs and outputs
-        let stack_inputs = TransactionKernel::build_input_stack(
+        let stack_inputs = kernel.build_input_stack(
             transaction.account_id(),
             Some(transaction.initial_account_hash()),
             transaction.input_notes().commitment(),
             transaction.block_ref(),
         );
-        let stack_outputs = TransactionKernel::build_output_stack(
+        let stack_outputs = kernel.build_output_stack(
             transaction.final_account_hash(),
             transaction.output_notes().commitment(),
             transaction.tx_script_root(),
+            transaction.expiration_block_num(),
+            transaction.fee(),
         );
 
         // verify transaction proof
         let proof_security_level = verify(
-            self.tx_program_info.clone(),
+            kernel.program_info().clone(),
             stack_inputs,
             stack_outputs,
             transaction.proof().clone(),
         )
-        .map_err(TransactionVerifierError::TransactionVerificationFailed)?;
+        .map_err(|source| TransactionVerifierError::TransactionVerificationFailed {
+            tx_id: transaction.id(),
+            source,
+        })?;
 
         // check security level
         if proof_security_level < self.proof_security_level {
-            return Err(TransactionVerifierError::InsufficientProofSecurityLevel(
-                proof_security_level,
-                self.proof_security_level,
-            ));
+            return Err(TransactionVerifierError::InsufficientProofSecurityLevel {
+                tx_id: transaction.id(),
+                actual: proof_security_level,
+                required: self.proof_security_level,
+            });
         }
 
         Ok(())
@@ -0,0 +1,98 @@
+use miden_lib::notes::{create_bearer_note, standard_script_kind};
+use miden_objects::{
+    accounts::{
+        Account, AccountId, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+        ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+    },
+    assembly::ProgramAst,
+    assets::{Asset, AssetVault, FungibleAsset},
+    notes::NoteFile,
+    Felt, EMPTY_WORD,
+};
+use miden_tx::TransactionExecutor;
+use mock::constants::{ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN, ACCOUNT_ID_SENDER};
+
+mod common;
+use common::{
+    get_account_with_default_account_code, get_new_key_pair_with_advice_map, MockDataStore,
+};
+
+// BEARER SCRIPT TEST
+// ===============================================================================================
+// We test the bearer script. The note file is shared via its hex encoding, and the note can then
+// be consumed by any account which received the note file.
+#[test]
+fn test_bearer_script() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let note_file = create_bearer_note(
+        vec![fungible_asset],
+        sender_account_id,
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+    )
+    .unwrap();
+
+    // the note file survives encoding into a gift link
+    let note_file = NoteFile::from_hex(&note_file.to_hex()).unwrap();
+    let note = note_file.note().clone();
+    assert!(note_file.inclusion_proof().is_none());
+    assert_eq!(standard_script_kind(&note.script().hash()), Some("BEARER"));
+
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+    )
+    .unwrap();
+
+    // any account knowing the note details can consume the note
+    for account_id in [
+        ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
+        ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+    ] {
+        let target_account_id = AccountId::try_from(account_id).unwrap();
+        let (target_pub_key, target_keypair_felt) = get_new_key_pair_with_advice_map();
+        let target_account =
+            get_account_with_default_account_code(target_account_id, target_pub_key, None);
+
+        let data_store =
+            MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
+        let executor = TransactionExecutor::new(data_store.clone());
+        executor.load_account(target_account_id).unwrap();
+
+        let block_ref = data_store.block_header.block_num();
+        let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+        let tx_script = executor
+            .compile_tx_script(
+                tx_script_code.clone(),
+                vec![(target_pub_key, target_keypair_felt)],
+                vec![],
+            )
+            .unwrap();
+
+        let executed_transaction = executor
+            .execute_transaction(
+                target_account_id,
+                block_ref,
+                &note_ids,
+                Some(tx_script),
+                EMPTY_WORD,
+            )
+            .unwrap();
+
+        let target_account_after: Account = Account::new(
+            target_account_id,
+            AssetVault::new(&[fungible_asset]).unwrap(),
+            target_account.storage().clone(),
+            target_account.code().clone(),
+            Felt::new(2),
+        );
+        assert_eq!(executed_transaction.final_account().hash(), target_account_after.hash());
+    }
+}
@@ -0,0 +1,172 @@
+use miden_objects::{
+    accounts::AccountId,
+    assembly::ProgramAst,
+    assets::{Asset, FungibleAsset},
+    block::ProposedBlock,
+    notes::Note,
+    transaction::{ProvenTransaction, TransactionBatch},
+    BlockError, Felt, Word, EMPTY_WORD,
+};
+use miden_tx::{ProvingOptions, TransactionExecutor, TransactionProver};
+use mock::{
+    constants::{
+        ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
+        ACCOUNT_ID_SENDER,
+    },
+    utils::prepare_word,
+};
+
+mod common;
+use common::{
+    get_account_with_default_account_code, get_new_key_pair_with_advice_map,
+    get_note_with_fungible_asset_and_script, MockDataStore,
+};
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Executes and proves a transaction against the account of the specified data store, which
+/// consumes all notes of the data store and is authenticated via the provided key pair.
+fn prove_transaction(
+    data_store: MockDataStore,
+    tx_script_code: &str,
+    key_pair: (Word, Vec<Felt>),
+) -> ProvenTransaction {
+    let executor = TransactionExecutor::new(data_store.clone());
+    let account_id = data_store.account.id();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let tx_script_code = ProgramAst::parse(tx_script_code).unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![key_pair], vec![]).unwrap();
+
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+    TransactionProver::new(ProvingOptions::default())
+        .prove_transaction(executed_transaction)
+        .unwrap()
+}
+
+/// Returns a transaction in which the sender creates the specified note, and a transaction in
+/// which the target account consumes the same note.
+fn create_and_consume_note(asset: Asset) -> (Note, ProvenTransaction, ProvenTransaction) {
+    let note_script = ProgramAst::parse(
+        "
+        use.miden::note
+
+        begin
+            dropw
+            exec.note::add_assets_to_account
+        end
+        ",
+    )
+    .unwrap();
+    let fungible_asset = match asset {
+        Asset::Fungible(asset) => asset,
+        Asset::NonFungible(_) => unreachable!("the note carries a fungible asset"),
+    };
+    let note = get_note_with_fungible_asset_and_script(fungible_asset, note_script);
+
+    // the sender creates the note via its wallet
+    let sender_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let (sender_pub_key, sender_keypair_felt) = get_new_key_pair_with_advice_map();
+    let sender = get_account_with_default_account_code(sender_id, sender_pub_key, Some(asset));
+    let producer = prove_transaction(
+        MockDataStore::with_existing(Some(sender), Some(vec![])),
+        &format!(
+            "
+            use.miden::contracts::auth::basic->auth_tx
+            use.miden::contracts::wallets::basic->wallet
+
+            begin
+                push.{recipient}
+                push.{tag}
+                push.{asset}
+                call.wallet::send_asset drop
+                dropw dropw
+                call.auth_tx::auth_tx_rpo_falcon512
+            end
+            ",
+            recipient = prepare_word(&note.recipient().into()),
+            tag = Felt::from(note.metadata().tag()),
+            asset = prepare_word(&asset.into()),
+        ),
+        (sender_pub_key, sender_keypair_felt),
+    );
+    assert_eq!(producer.output_notes().iter().next().unwrap().note_id(), note.id());
+
+    // the target consumes the note
+    let target_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (target_pub_key, target_keypair_felt) = get_new_key_pair_with_advice_map();
+    let target = get_account_with_default_account_code(target_id, target_pub_key, None);
+    let consumer = prove_transaction(
+        MockDataStore::with_existing(Some(target), Some(vec![note.clone()])),
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+        (target_pub_key, target_keypair_felt),
+    );
+    assert_eq!(consumer.input_notes().iter().next(), Some(&note.nullifier()));
+
+    (note, producer, consumer)
+}
+
+// PROPOSED BLOCK TESTS
+// ================================================================================================
+
+#[test]
+fn test_proposed_block_with_unauthenticated_note() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+    let (note, producer, consumer) = create_and_consume_note(asset);
+
+    // the note is consumed without a proof of inclusion, and thus is left to the block to match
+    let consumer_batch = TransactionBatch::new(vec![consumer.clone()], &[note.clone()]).unwrap();
+    assert_eq!(consumer_batch.unauthenticated_notes().get(&note.nullifier()), Some(&note.id()));
+    let producer_batch = TransactionBatch::new(vec![producer.clone()], &[]).unwrap();
+
+    // the note must be created by another batch of the block
+    let result = ProposedBlock::new(10, vec![consumer_batch.clone()]);
+    assert_eq!(result.unwrap_err(), BlockError::UnauthenticatedNoteNotFound(note.id()));
+
+    // the batches are reordered such that the note is created before it is consumed, and the
+    // note is erased from the block
+    let block = ProposedBlock::new(10, vec![consumer_batch, producer_batch]).unwrap();
+    assert_eq!(block.block_num(), 10);
+    let tx_ids = block
+        .batches()
+        .iter()
+        .flat_map(|batch| batch.transaction_ids())
+        .collect::<Vec<_>>();
+    assert_eq!(tx_ids, vec![producer.id(), consumer.id()]);
+    assert_eq!(block.erased_notes(), &[note.id()]);
+    assert!(block.input_notes().is_empty());
+    assert!(block.output_notes().is_empty());
+    assert!(block.expired_notes().is_empty());
+
+    // notes which are not declared as unauthenticated are recorded by the block
+    let block = ProposedBlock::new(
+        10,
+        vec![
+            TransactionBatch::new(vec![consumer.clone()], &[]).unwrap(),
+            TransactionBatch::new(vec![producer.clone()], &[]).unwrap(),
+        ],
+    )
+    .unwrap();
+    assert_eq!(block.input_notes(), &[note.nullifier()]);
+    assert_eq!(block.output_notes().len(), 1);
+    assert_eq!(block.output_notes()[0].note_id(), note.id());
+    assert!(block.erased_notes().is_empty());
+
+    // the same note cannot be created twice
+    let producer_batch = TransactionBatch::new(vec![producer], &[]).unwrap();
+    let result = ProposedBlock::new(10, vec![producer_batch.clone(), producer_batch]);
+    assert_eq!(result.unwrap_err(), BlockError::DuplicateOutputNote(note.id()));
+}
@@ -0,0 +1,180 @@
+use std::sync::{Arc, Mutex};
+
+use miden_lib::{
+    notes::{create_bridge_note, standard_script_kind, BridgeEvent, BridgeMessage},
+    transaction::{EventId, EventNamespace},
+};
+use miden_objects::{
+    accounts::AccountId, assets::FungibleAsset, Felt, NoteError, Word, EMPTY_WORD,
+};
+use miden_tx::{
+    host::{EventHandler, EventHandlers},
+    TransactionExecutor,
+};
+use mock::constants::{
+    ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN_2,
+    ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
+    ACCOUNT_ID_SENDER,
+};
+use vm_processor::{ExecutionError, ProcessState};
+
+mod common;
+use common::{
+    get_account_with_default_account_code, get_faucet_account_with_max_supply_and_total_issuance,
+    get_new_key_pair_with_advice_map, MockDataStore,
+};
+
+// BRIDGE SCRIPT TEST
+// ================================================================================================
+// We test the bridge script. The note is consumed by the bridge faucet which burns the asset of
+// the note and emits the bridge event, which is picked up by the relayer via an event handler
+// attached to the transaction host of the faucet.
+#[test]
+fn test_bridge_script() {
+    let (faucet_pub_key, _faucet_keypair_felts) = get_new_key_pair_with_advice_map();
+    let faucet_account =
+        get_faucet_account_with_max_supply_and_total_issuance(faucet_pub_key, 200, Some(100));
+    let fungible_asset = FungibleAsset::new(faucet_account.id(), 60).unwrap();
+
+    let mut destination_address = [0_u8; 32];
+    destination_address[12..].copy_from_slice(&[0xab; 20]);
+    let message = BridgeMessage::new(faucet_account.id(), 1, destination_address);
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let note = create_bridge_note(
+        fungible_asset,
+        message,
+        sender_account_id,
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+    )
+    .unwrap();
+    assert_eq!(standard_script_kind(&note.script().hash()), Some("BRIDGE"));
+
+    // CONSTRUCT AND EXECUTE TX (Success)
+    // --------------------------------------------------------------------------------------------
+    let relayer = Arc::new(BridgeRelayer(Mutex::new(Vec::new())));
+    let mut event_handlers = EventHandlers::new();
+    event_handlers.register(EventNamespace::MidenLib, relayer.clone()).unwrap();
+
+    let data_store =
+        MockDataStore::with_existing(Some(faucet_account.clone()), Some(vec![note.clone()]));
+
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_event_handlers(event_handlers)
+        .build();
+    executor.load_account(faucet_account.id()).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let executed_transaction = executor
+        .execute_transaction(faucet_account.id(), block_ref, &note_ids, None, EMPTY_WORD)
+        .unwrap();
+
+    // check that the faucet burned the asset
+    assert_eq!(executed_transaction.account_delta().nonce(), Some(Felt::new(2)));
+    assert!(executed_transaction.output_notes().is_empty());
+
+    // the relayer picked up the burned asset together with its destination
+    let events = relayer.0.lock().unwrap().clone();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].asset(), fungible_asset);
+    assert_eq!(events[0].message(), &message);
+
+    // the bridge message can also be recovered from the input notes of the burning transaction
+    let burned_note = executed_transaction.input_notes().get_note(0).note();
+    assert_eq!(BridgeMessage::from_note(burned_note).unwrap(), message);
+
+    // CONSTRUCT AND EXECUTE TX (Failure)
+    // --------------------------------------------------------------------------------------------
+    // the note cannot be consumed by an account other than the bridge faucet
+    let (target_pub_key, _target_keypair_felts) = get_new_key_pair_with_advice_map();
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let target_account =
+        get_account_with_default_account_code(target_account_id, target_pub_key, None);
+
+    let data_store =
+        MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
+
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(target_account_id).unwrap();
+
+    let executed_transaction =
+        executor.execute_transaction(target_account_id, block_ref, &note_ids, None, EMPTY_WORD);
+
+    assert!(executed_transaction.is_err());
+}
+
+#[test]
+fn test_bridge_note_requires_asset_of_bridge_faucet() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let other_faucet_id = AccountId::try_from(ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let fungible_asset = FungibleAsset::new(faucet_id, 100).unwrap();
+
+    let message = BridgeMessage::new(other_faucet_id, 1, [0; 32]);
+    let result = create_bridge_note(fungible_asset, message, sender_account_id, Word::default());
+    assert_eq!(result, Err(NoteError::BridgeFaucetMismatch(other_faucet_id, faucet_id)));
+
+    // messages round-trip through note inputs, and malformed inputs are rejected
+    let message = BridgeMessage::new(faucet_id, u32::MAX, [0xff; 32]);
+    let mut inputs = message.to_inputs();
+    assert_eq!(BridgeMessage::from_inputs(&inputs).unwrap(), message);
+
+    inputs[4] = Felt::new(u32::MAX as u64 + 1);
+    assert!(BridgeMessage::from_inputs(&inputs).is_err());
+    assert!(BridgeMessage::from_inputs(&inputs[..8]).is_err());
+}
+
+#[test]
+fn test_bridge_event_decoding() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let other_faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN_2).unwrap();
+    let asset = FungibleAsset::new(faucet_id, 100).unwrap();
+    let message = BridgeMessage::new(faucet_id, 7, [0x11; 32]);
+
+    // the words of the event payload are laid out on the stack with their first element deepest
+    let event_stack = |asset: FungibleAsset, message: &BridgeMessage| {
+        let asset: Word = asset.into();
+        let mut stack = asset.into_iter().rev().collect::<Vec<_>>();
+        for word in message.to_inputs().chunks(4) {
+            stack.extend(word.iter().rev());
+        }
+        stack
+    };
+
+    let event = BridgeEvent::from_stack(&event_stack(asset, &message)).unwrap();
+    assert_eq!(event.asset(), asset);
+    assert_eq!(event.message(), &message);
+
+    // the burned asset must be issued by the bridge faucet, and the payload must be complete
+    let other_asset = FungibleAsset::new(other_faucet_id, 100).unwrap();
+    assert_eq!(
+        BridgeEvent::from_stack(&event_stack(other_asset, &message)),
+        Err(NoteError::BridgeFaucetMismatch(faucet_id, other_faucet_id))
+    );
+    assert!(BridgeEvent::from_stack(&event_stack(asset, &message)[..12]).is_err());
+}
+
+// BRIDGE RELAYER
+// ================================================================================================
+
+/// Event handler recording the bridge events emitted during the execution of a transaction.
+struct BridgeRelayer(Mutex<Vec<BridgeEvent>>);
+
+impl EventHandler for BridgeRelayer {
+    fn on_event(
+        &self,
+        event_id: EventId,
+        process: &dyn ProcessState,
+    ) -> Result<(), ExecutionError> {
+        if event_id == BridgeEvent::event_id() {
+            let event = BridgeEvent::from_stack(&process.get_stack_state())
+                .map_err(|err| ExecutionError::EventError(err.to_string()))?;
+            self.0.lock().unwrap().push(event);
+        }
+        Ok(())
+    }
+}
@@ -1,16 +1,16 @@
-use miden_lib::transaction::TransactionKernel;
+use miden_lib::transaction::{memory::FAUCET_STORAGE_DATA_SLOT, TransactionKernel};
 use miden_objects::{
     accounts::{Account, AccountCode, AccountId, AccountStorage, StorageSlotType},
     assembly::{ModuleAst, ProgramAst},
     assets::{Asset, AssetVault, FungibleAsset},
     crypto::{dsa::rpo_falcon512::KeyPair, utils::Serializable},
-    notes::{Note, NoteId, NoteScript},
+    notes::{Note, NoteId, NoteScript, NoteTag},
     transaction::{ChainMmr, InputNote, InputNotes, TransactionInputs},
     BlockHeader, Felt, Word,
 };
 use miden_tx::{DataStore, DataStoreError};
 use mock::{
-    constants::{ACCOUNT_ID_SENDER, DEFAULT_ACCOUNT_CODE},
+    constants::{ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_SENDER, DEFAULT_ACCOUNT_CODE},
     mock::{
         account::MockAccountType,
         notes::AssetPreservationStatus,
@@ -24,6 +24,7 @@ use mock::{
 #[derive(Clone)]
 pub struct MockDataStore {
     pub account: Account,
+    pub account_seed: Option<Word>,
     pub block_header: BlockHeader,
     pub block_chain: ChainMmr,
     pub notes: Vec<InputNote>,
@@ -31,11 +32,12 @@ pub struct MockDataStore {
 
 impl MockDataStore {
     pub fn new() -> Self {
-        let (account, _, block_header, block_chain, notes) =
+        let (account, _, block_header, block_chain, notes, _) =
             mock_inputs(MockAccountType::StandardExisting, AssetPreservationStatus::Preserved)
                 .into_parts();
         Self {
             account,
+            account_seed: None,
             block_header,
             block_chain,
             notes: notes.into_vec(),
@@ -52,11 +54,22 @@ impl MockDataStore {
             );
         Self {
             account,
+            account_seed: None,
             block_header,
             block_chain,
             notes: consumed_notes,
         }
     }
+
+    /// Returns a data store for a transaction creating the specified new account, which does not
+    /// consume any notes.
+    #[allow(dead_code)]
+    pub fn with_new_account(account: Account, account_seed: Word) -> Self {
+        assert!(account.is_new());
+        let mut data_store = Self::with_existing(Some(account), Some(vec![]));
+        data_store.account_seed = Some(account_seed);
+        data_store
+    }
 }
 
 impl Default for MockDataStore {
@@ -85,7 +98,7 @@ impl DataStore for MockDataStore {
 
         Ok(TransactionInputs::new(
             self.account.clone(),
-            None,
+            self.account_seed,
             self.block_header,
             self.block_chain.clone(),
             InputNotes::new(notes).unwrap(),
@@ -151,7 +164,46 @@ pub fn get_note_with_fungible_asset_and_script(
         &[fungible_asset.into()],
         SERIAL_NUM,
         sender_id,
-        Felt::new(1),
+        NoteTag::from(1),
     )
     .unwrap()
 }
+
+#[allow(dead_code)]
+pub fn get_faucet_account_with_max_supply_and_total_issuance(
+    public_key: Word,
+    max_supply: u64,
+    total_issuance: Option<u64>,
+) -> Account {
+    let faucet_account_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let faucet_account_code_src =
+        include_str!("../../miden-lib/asm/miden/contracts/faucets/basic_fungible.masm");
+    let faucet_account_code_ast = ModuleAst::parse(faucet_account_code_src).unwrap();
+    let account_assembler = TransactionKernel::assembler();
+
+    let faucet_account_code =
+        AccountCode::new(faucet_account_code_ast.clone(), &account_assembler).unwrap();
+
+    let faucet_storage_slot_1 = [Felt::new(max_supply), Felt::new(0), Felt::new(0), Felt::new(0)];
+    let mut faucet_account_storage = AccountStorage::new(vec![
+        (0, (StorageSlotType::Value { value_arity: 0 }, public_key)),
+        (1, (StorageSlotType::Value { value_arity: 0 }, faucet_storage_slot_1)),
+    ])
+    .unwrap();
+
+    if total_issuance.is_some() {
+        let faucet_storage_slot_254 =
+            [Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(total_issuance.unwrap())];
+        faucet_account_storage
+            .set_item(FAUCET_STORAGE_DATA_SLOT, faucet_storage_slot_254)
+            .unwrap();
+    };
+
+    Account::new(
+        faucet_account_id,
+        AssetVault::new(&[]).unwrap(),
+        faucet_account_storage.clone(),
+        faucet_account_code.clone(),
+        Felt::new(1),
+    )
+}
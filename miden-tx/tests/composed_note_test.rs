@@ -0,0 +1,202 @@
+use miden_lib::notes::{NoteScriptComponent, NoteScriptComposer};
+use miden_objects::{
+    accounts::{Account, AccountId},
+    assembly::ProgramAst,
+    assets::{Asset, AssetVault, FungibleAsset},
+    notes::{Note, NoteTag},
+    utils::collections::Vec,
+    Felt, StarkField, EMPTY_WORD,
+};
+use miden_tx::TransactionExecutor;
+use mock::constants::{
+    ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
+    ACCOUNT_ID_SENDER,
+};
+
+mod common;
+use common::{
+    get_account_with_default_account_code, get_new_key_pair_with_advice_map, MockDataStore,
+};
+
+// COMPOSED NOTE SCRIPT TESTS
+// ===============================================================================================
+// We test a time-locked pay-to-id note composed out of the standard components. The note can be
+// consumed only by the target account, and only once the unlock block height is reached.
+#[test]
+fn test_timelocked_p2id_composed_script() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (target_pub_key, target_keypair_felt) = get_new_key_pair_with_advice_map();
+    let target_account =
+        get_account_with_default_account_code(target_account_id, target_pub_key, None);
+
+    let composer = NoteScriptComposer::new()
+        .then(NoteScriptComponent::Timelock)
+        .then(NoteScriptComponent::TargetAccount)
+        .then(NoteScriptComponent::ReceiveAssets);
+    assert_eq!(composer.num_inputs(), 2);
+    let note_script = composer.compile().unwrap();
+
+    // the current block height is 4
+    let create_note = |unlock_height: u32| {
+        Note::new(
+            note_script.clone(),
+            &[Felt::from(unlock_height), target_account_id.into()],
+            &[fungible_asset],
+            [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+            sender_account_id,
+            NoteTag::default(),
+        )
+        .unwrap()
+    };
+    let note_unlocked = create_note(3);
+    let note_locked = create_note(5);
+
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+    )
+    .unwrap();
+
+    // CONSTRUCT AND EXECUTE TX (unlocked note - success)
+    // --------------------------------------------------------------------------------------------
+    let data_store =
+        MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note_unlocked]));
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(target_account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let tx_script = executor
+        .compile_tx_script(
+            tx_script_code.clone(),
+            vec![(target_pub_key, target_keypair_felt.clone())],
+            vec![],
+        )
+        .unwrap();
+
+    let executed_transaction = executor
+        .execute_transaction(target_account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    let target_account_after: Account = Account::new(
+        target_account_id,
+        AssetVault::new(&[fungible_asset]).unwrap(),
+        target_account.storage().clone(),
+        target_account.code().clone(),
+        Felt::new(2),
+    );
+    assert_eq!(executed_transaction.final_account().hash(), target_account_after.hash());
+
+    // CONSTRUCT AND EXECUTE TX (locked note - failure)
+    // --------------------------------------------------------------------------------------------
+    let data_store =
+        MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note_locked]));
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(target_account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+    let tx_script = executor
+        .compile_tx_script(tx_script_code, vec![(target_pub_key, target_keypair_felt)], vec![])
+        .unwrap();
+
+    let result = executor.execute_transaction(
+        target_account_id,
+        block_ref,
+        &note_ids,
+        Some(tx_script),
+        EMPTY_WORD,
+    );
+    assert!(result.is_err());
+}
+
+// We test a note script which reads the memo commitment of the note. The note can be consumed only
+// if the memo commits to the expected invoice, and the memo does not take up any note inputs.
+#[test]
+fn test_note_script_reads_memo() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (target_pub_key, target_keypair_felt) = get_new_key_pair_with_advice_map();
+    let target_account =
+        get_account_with_default_account_code(target_account_id, target_pub_key, None);
+
+    let invoice = b"invoice #42: 100 tokens";
+    let memo = Note::compute_memo(invoice);
+    let memo_word = memo.iter().map(|x| x.as_int().to_string()).collect::<Vec<_>>().join(".");
+
+    let composer = NoteScriptComposer::new()
+        .then(NoteScriptComponent::Custom {
+            imports: vec!["use.miden::note".to_string()],
+            body: format!("    exec.note::get_memo push.{memo_word} assert_eqw\n"),
+            num_inputs: 0,
+        })
+        .then(NoteScriptComponent::ReceiveAssets);
+    let note_script = composer.compile().unwrap();
+
+    let note = Note::new(
+        note_script,
+        &[],
+        &[fungible_asset],
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+        sender_account_id,
+        NoteTag::default(),
+    )
+    .unwrap();
+
+    // the memo is committed to by the note recipient
+    let note_with_memo = note.clone().with_memo_data(invoice);
+    assert!(note_with_memo.verify_memo(invoice));
+    assert_ne!(note_with_memo.id(), note.id());
+    assert_eq!(note_with_memo.nullifier(), note.nullifier());
+
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+    )
+    .unwrap();
+
+    for (note, should_succeed) in [(note_with_memo, true), (note.with_memo_data(b"other"), false)] {
+        let data_store =
+            MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note]));
+        let executor = TransactionExecutor::new(data_store.clone());
+        executor.load_account(target_account_id).unwrap();
+
+        let block_ref = data_store.block_header.block_num();
+        let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+        let tx_script = executor
+            .compile_tx_script(
+                tx_script_code.clone(),
+                vec![(target_pub_key, target_keypair_felt.clone())],
+                vec![],
+            )
+            .unwrap();
+
+        let result = executor.execute_transaction(
+            target_account_id,
+            block_ref,
+            &note_ids,
+            Some(tx_script),
+            EMPTY_WORD,
+        );
+        assert_eq!(result.is_ok(), should_succeed);
+    }
+}
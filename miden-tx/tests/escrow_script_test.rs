@@ -0,0 +1,125 @@
+use miden_lib::notes::{standard_script_kind, Escrow};
+use miden_objects::{
+    accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN},
+    assembly::ProgramAst,
+    assets::{Asset, FungibleAsset},
+    notes::Note,
+    vm::AdviceInputs,
+    Digest, Felt, Word, EMPTY_WORD,
+};
+use miden_tx::TransactionExecutor;
+use mock::constants::{
+    ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
+};
+use vm_processor::{AdviceProvider, MemAdviceProvider, SignatureKind};
+
+mod common;
+use common::{
+    get_account_with_default_account_code, get_new_key_pair_with_advice_map, MockDataStore,
+};
+
+// ESCROW SCRIPT TESTS
+// ================================================================================================
+// We test the escrow script. The beneficiary can consume the note only with a release signature of
+// the arbiter, and the sender can reclaim the note only once the deadline has been reached.
+#[test]
+fn test_escrow_script_release_by_arbiter() {
+    let beneficiary_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let sender_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+    let (arbiter_pub_key, arbiter_sk_felts) = get_new_key_pair_with_advice_map();
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let escrow = Escrow::new(beneficiary_id, arbiter_pub_key, 100);
+    let note = create_escrow_note(&escrow, sender_id, serial_num);
+    assert_eq!(standard_script_kind(&note.script().hash()), Some("ESCROW"));
+
+    // the arbiter recovers the terms from the note details and signs the release
+    let terms = Escrow::from_note(&note).unwrap();
+    assert_eq!(terms, escrow);
+    let signer: MemAdviceProvider = AdviceInputs::default()
+        .with_map([(Digest::from(arbiter_pub_key).as_bytes(), arbiter_sk_felts)])
+        .into();
+    let signature = signer
+        .get_signature(
+            SignatureKind::RpoFalcon512,
+            arbiter_pub_key,
+            terms.release_message(note.serial_num()),
+        )
+        .unwrap();
+
+    // the beneficiary can consume the note with the release signature
+    let release_inputs = escrow.release_inputs(note.serial_num(), signature);
+    assert!(consume_note(beneficiary_id, &note, vec![release_inputs]).is_ok());
+
+    // but not without it
+    assert!(consume_note(beneficiary_id, &note, vec![]).is_err());
+
+    // and the sender cannot reclaim the note before the deadline
+    assert!(!escrow.is_reclaimable(4));
+    assert!(consume_note(sender_id, &note, vec![]).is_err());
+}
+
+#[test]
+fn test_escrow_script_reclaim_by_sender() {
+    let beneficiary_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let sender_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+    let (arbiter_pub_key, _arbiter_sk_felts) = get_new_key_pair_with_advice_map();
+
+    // the reference block of the mock chain is block 4
+    let escrow = Escrow::new(beneficiary_id, arbiter_pub_key, 4);
+    let note = create_escrow_note(&escrow, sender_id, [Felt::new(5); 4]);
+
+    assert!(escrow.is_reclaimable(4));
+    assert!(consume_note(sender_id, &note, vec![]).is_ok());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn create_escrow_note(escrow: &Escrow, sender_id: AccountId, serial_num: Word) -> Note {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+    escrow.create_note(vec![fungible_asset], sender_id, serial_num).unwrap()
+}
+
+/// Executes a transaction consuming the specified note against a new account with the specified
+/// ID, passing the provided entries to the transaction script inputs.
+fn consume_note(
+    account_id: AccountId,
+    note: &Note,
+    script_inputs: Vec<(Word, Vec<Felt>)>,
+) -> Result<(), String> {
+    let (pub_key, sk_felts) = get_new_key_pair_with_advice_map();
+    let account = get_account_with_default_account_code(account_id, pub_key, None);
+
+    let data_store = MockDataStore::with_existing(Some(account), Some(vec![note.clone()]));
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+    )
+    .unwrap();
+
+    let mut inputs = vec![(pub_key, sk_felts)];
+    inputs.extend(script_inputs);
+    let tx_script = executor.compile_tx_script(tx_script_code, inputs, vec![]).unwrap();
+
+    executor
+        .execute_transaction(account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
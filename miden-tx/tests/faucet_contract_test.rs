@@ -1,23 +1,30 @@
 use miden_lib::{
-    accounts::faucets::create_basic_fungible_faucet,
+    accounts::faucets::{
+        create_basic_fungible_faucet, create_mint_note, create_mint_script, get_total_issuance,
+    },
+    notes::{create_note, Script},
     transaction::{memory::FAUCET_STORAGE_DATA_SLOT, TransactionKernel},
     AuthScheme,
 };
 use miden_objects::{
-    accounts::{Account, AccountCode, AccountId, AccountStorage, StorageSlotType},
+    accounts::{AccountCode, AccountId},
     assembly::{ModuleAst, ProgramAst},
-    assets::{Asset, AssetVault, FungibleAsset, TokenSymbol},
+    assets::{Asset, FungibleAsset, TokenSymbol},
     crypto::dsa::rpo_falcon512::{KeyPair, PublicKey},
     notes::{NoteAssets, NoteMetadata},
     transaction::OutputNote,
-    Felt, Word, ZERO,
+    Felt, NoteError, EMPTY_WORD, ZERO,
 };
 use miden_tx::TransactionExecutor;
-use mock::{constants::ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, utils::prepare_word};
+use mock::{
+    constants::{ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN, ACCOUNT_ID_SENDER},
+    utils::prepare_word,
+};
 
 mod common;
 use common::{
-    get_new_key_pair_with_advice_map, get_note_with_fungible_asset_and_script, MockDataStore,
+    get_faucet_account_with_max_supply_and_total_issuance, get_new_key_pair_with_advice_map,
+    get_note_with_fungible_asset_and_script, MockDataStore,
 };
 
 // TESTS MINT FUNGIBLE ASSET
@@ -33,7 +40,7 @@ fn test_faucet_contract_mint_fungible_asset_succeeds() {
     // --------------------------------------------------------------------------------------------
     let data_store = MockDataStore::with_existing(Some(faucet_account.clone()), Some(vec![]));
 
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     executor.load_account(faucet_account.id()).unwrap();
 
     let block_ref = data_store.block_header.block_num();
@@ -73,7 +80,7 @@ fn test_faucet_contract_mint_fungible_asset_succeeds() {
 
     // Execute the transaction and get the witness
     let transaction_result = executor
-        .execute_transaction(faucet_account.id(), block_ref, &note_ids, Some(tx_script))
+        .execute_transaction(faucet_account.id(), block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
         .unwrap();
 
     let fungible_asset: Asset =
@@ -82,7 +89,7 @@ fn test_faucet_contract_mint_fungible_asset_succeeds() {
     let expected_note = OutputNote::new(
         recipient.into(),
         NoteAssets::new(&[fungible_asset]).unwrap(),
-        NoteMetadata::new(faucet_account.id(), tag, Felt::new(1)),
+        NoteMetadata::new(faucet_account.id(), tag.try_into().unwrap(), Felt::new(1)),
     );
 
     let created_note = transaction_result.output_notes().get_note(0).clone();
@@ -101,7 +108,7 @@ fn test_faucet_contract_mint_fungible_asset_fails_exceeds_max_supply() {
     // --------------------------------------------------------------------------------------------
     let data_store = MockDataStore::with_existing(Some(faucet_account.clone()), Some(vec![]));
 
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     executor.load_account(faucet_account.id()).unwrap();
 
     let block_ref = data_store.block_header.block_num();
@@ -139,12 +146,88 @@ fn test_faucet_contract_mint_fungible_asset_fails_exceeds_max_supply() {
         .unwrap();
 
     // Execute the transaction and get the witness
-    let transaction_result =
-        executor.execute_transaction(faucet_account.id(), block_ref, &note_ids, Some(tx_script));
+    let transaction_result = executor.execute_transaction(
+        faucet_account.id(),
+        block_ref,
+        &note_ids,
+        Some(tx_script),
+        EMPTY_WORD,
+    );
 
     assert!(transaction_result.is_err());
 }
 
+#[test]
+fn test_faucet_contract_mint_script() {
+    let (faucet_pub_key, faucet_keypair_felts) = get_new_key_pair_with_advice_map();
+    let faucet_account =
+        get_faucet_account_with_max_supply_and_total_issuance(faucet_pub_key, 200, Some(50));
+    assert_eq!(get_total_issuance(&faucet_account), Some(50));
+
+    // CONSTRUCT AND EXECUTE TX (Success)
+    // --------------------------------------------------------------------------------------------
+    let data_store = MockDataStore::with_existing(Some(faucet_account.clone()), Some(vec![]));
+
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(faucet_account.id()).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+
+    let target_1 = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let target_2 = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let notes = vec![
+        create_mint_note(faucet_account.id(), 30, target_1, [Felt::new(1); 4]).unwrap(),
+        create_mint_note(faucet_account.id(), 20, target_2, [Felt::new(2); 4]).unwrap(),
+    ];
+
+    let tx_script = executor
+        .compile_tx_script(
+            create_mint_script(&notes).unwrap(),
+            vec![(faucet_pub_key, faucet_keypair_felts)],
+            vec![],
+        )
+        .unwrap();
+
+    let transaction_result = executor
+        .execute_transaction(faucet_account.id(), block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    let created_notes = transaction_result
+        .output_notes()
+        .iter()
+        .map(|note| note.id())
+        .collect::<Vec<_>>();
+    assert_eq!(created_notes, notes.iter().map(|note| note.id()).collect::<Vec<_>>());
+
+    // the total issuance can be read by anyone via the faucet interface
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::faucets::basic_fungible->faucet
+
+        begin
+            push.0 call.faucet::get_total_issuance
+            push.50 assert_eq
+        end
+        ",
+    )
+    .unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+    executor
+        .execute_transaction(faucet_account.id(), block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    // notes minting tokens of another faucet cannot be distributed
+    let note = create_note(
+        Script::P2ID { target: target_1 },
+        vec![FungibleAsset::new(faucet_account.id(), 10).unwrap().into()],
+        target_2,
+        None,
+        [Felt::new(3); 4],
+    )
+    .unwrap();
+    assert_eq!(create_mint_script(&[note.clone()]), Err(NoteError::InvalidMintNote(note.id())));
+}
+
 // TESTS BURN FUNGIBLE ASSET
 // ================================================================================================
 
@@ -190,7 +273,7 @@ fn test_faucet_contract_burn_fungible_asset_succeeds() {
     let data_store =
         MockDataStore::with_existing(Some(faucet_account.clone()), Some(vec![note.clone()]));
 
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     executor.load_account(faucet_account.id()).unwrap();
 
     let block_ref = data_store.block_header.block_num();
@@ -198,7 +281,7 @@ fn test_faucet_contract_burn_fungible_asset_succeeds() {
 
     // Execute the transaction and get the witness
     let transaction_result = executor
-        .execute_transaction(faucet_account.id(), block_ref, &note_ids, None)
+        .execute_transaction(faucet_account.id(), block_ref, &note_ids, None, EMPTY_WORD)
         .unwrap();
 
     // check that the account burned the asset
@@ -247,41 +330,8 @@ fn test_faucet_contract_creation() {
     let exp_faucet_account_code =
         AccountCode::new(exp_faucet_account_code_ast.clone(), &account_assembler).unwrap();
 
-    assert_eq!(faucet_account.code(), &exp_faucet_account_code);
-}
-
-fn get_faucet_account_with_max_supply_and_total_issuance(
-    public_key: Word,
-    max_supply: u64,
-    total_issuance: Option<u64>,
-) -> Account {
-    let faucet_account_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
-    let faucet_account_code_src =
-        include_str!("../../miden-lib/asm/miden/contracts/faucets/basic_fungible.masm");
-    let faucet_account_code_ast = ModuleAst::parse(faucet_account_code_src).unwrap();
-    let account_assembler = TransactionKernel::assembler();
-
-    let faucet_account_code =
-        AccountCode::new(faucet_account_code_ast.clone(), &account_assembler).unwrap();
-
-    let faucet_storage_slot_1 = [Felt::new(max_supply), Felt::new(0), Felt::new(0), Felt::new(0)];
-    let mut faucet_account_storage = AccountStorage::new(vec![
-        (0, (StorageSlotType::Value { value_arity: 0 }, public_key)),
-        (1, (StorageSlotType::Value { value_arity: 0 }, faucet_storage_slot_1)),
-    ])
-    .unwrap();
-
-    if total_issuance.is_some() {
-        let faucet_storage_slot_254 =
-            [Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(total_issuance.unwrap())];
-        faucet_account_storage.set_item(FAUCET_STORAGE_DATA_SLOT, faucet_storage_slot_254);
-    };
-
-    Account::new(
-        faucet_account_id,
-        AssetVault::new(&[]).unwrap(),
-        faucet_account_storage.clone(),
-        faucet_account_code.clone(),
-        Felt::new(1),
-    )
+    // the faucet is composed out of the components of the account builder, and thus exports the
+    // same procedures as the `basic_fungible` module
+    assert_eq!(faucet_account.code().procedures(), exp_faucet_account_code.procedures());
+    assert_eq!(faucet_account.code().root(), exp_faucet_account_code.root());
 }
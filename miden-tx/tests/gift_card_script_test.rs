@@ -0,0 +1,158 @@
+use miden_lib::notes::{standard_script_kind, GiftCard};
+use miden_objects::{
+    accounts::{Account, AccountId},
+    assembly::ProgramAst,
+    assets::{Asset, AssetVault, FungibleAsset},
+    transaction::OutputNote,
+    Felt, NoteError, EMPTY_WORD,
+};
+use miden_tx::TransactionExecutor;
+use mock::constants::{
+    ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
+    ACCOUNT_ID_SENDER,
+};
+
+mod common;
+use common::{
+    get_account_with_default_account_code, get_new_key_pair_with_advice_map, MockDataStore,
+};
+
+// GIFT CARD SCRIPT TEST
+// ================================================================================================
+// We test the gift card script. The holder withdraws part of the balance, and the gift card note
+// is re-issued with the remaining balance and an incremented usage counter.
+#[test]
+fn test_gift_card_script() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset = FungibleAsset::new(faucet_id, 100).unwrap();
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let card = GiftCard::new(
+        fungible_asset,
+        30,
+        sender_account_id,
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+    )
+    .unwrap();
+    let note = card.note().unwrap();
+    assert_eq!(standard_script_kind(&note.script().hash()), Some("GIFT_CARD"));
+
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (target_pub_key, target_keypair_felt) = get_new_key_pair_with_advice_map();
+    let target_account =
+        get_account_with_default_account_code(target_account_id, target_pub_key, None);
+
+    let data_store =
+        MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
+
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(target_account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+    )
+    .unwrap();
+
+    // CONSTRUCT AND EXECUTE TX (Success)
+    // --------------------------------------------------------------------------------------------
+    let tx_script = executor
+        .compile_tx_script(
+            tx_script_code.clone(),
+            vec![(target_pub_key, target_keypair_felt.clone()), card.withdrawal_inputs(25)],
+            vec![],
+        )
+        .unwrap();
+
+    let executed_transaction = executor
+        .execute_transaction(target_account_id, block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    // the holder received the withdrawn amount
+    let withdrawn_asset: Asset = FungibleAsset::new(faucet_id, 25).unwrap().into();
+    let target_account_after = Account::new(
+        target_account_id,
+        AssetVault::new(&[withdrawn_asset]).unwrap(),
+        target_account.storage().clone(),
+        target_account.code().clone(),
+        Felt::new(2),
+    );
+    assert_eq!(executed_transaction.final_account().hash(), target_account_after.hash());
+
+    // the gift card was re-issued with the remaining balance
+    let next_card = card.withdraw(25, target_account_id).unwrap().unwrap();
+    assert_eq!(next_card.asset().amount(), 75);
+    assert_eq!(next_card.usage_count(), 1);
+
+    let next_note = next_card.note().unwrap();
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+    assert_eq!(executed_transaction.output_notes().get_note(0), &OutputNote::from(&next_note));
+
+    // CONSTRUCT AND EXECUTE TX (Failure)
+    // --------------------------------------------------------------------------------------------
+    // withdrawing more than the maximum withdrawal fails
+    let tx_script = executor
+        .compile_tx_script(
+            tx_script_code,
+            vec![(target_pub_key, target_keypair_felt), card.withdrawal_inputs(31)],
+            vec![],
+        )
+        .unwrap();
+
+    let executed_transaction = executor.execute_transaction(
+        target_account_id,
+        block_ref,
+        &note_ids,
+        Some(tx_script),
+        EMPTY_WORD,
+    );
+
+    assert!(executed_transaction.is_err());
+}
+
+#[test]
+fn test_gift_card_serial_number_chaining() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let holder_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+
+    let card = GiftCard::new(
+        FungibleAsset::new(faucet_id, 25).unwrap(),
+        10,
+        sender_account_id,
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+    )
+    .unwrap();
+
+    // every re-issued note has a fresh serial number and is created by the holder
+    let second = card.withdraw(10, holder_account_id).unwrap().unwrap();
+    let third = second.withdraw(10, holder_account_id).unwrap().unwrap();
+    assert_ne!(card.serial_num(), second.serial_num());
+    assert_ne!(second.serial_num(), third.serial_num());
+    assert_eq!(third.sender(), holder_account_id);
+    assert_eq!(third.usage_count(), 2);
+    assert_eq!(third.asset().amount(), 5);
+
+    // withdrawing the whole balance does not re-issue the note
+    assert_eq!(third.withdraw(5, holder_account_id), Ok(None));
+
+    // withdrawals above the limit or the balance are rejected
+    assert_eq!(
+        third.withdraw(6, holder_account_id),
+        Err(NoteError::InvalidGiftCardWithdrawal(6))
+    );
+    assert_eq!(
+        card.withdraw(11, holder_account_id),
+        Err(NoteError::InvalidGiftCardWithdrawal(11))
+    );
+}
@@ -1,20 +1,25 @@
-use miden_lib::notes::{create_note, Script};
+use miden_lib::{
+    notes::{create_note, standard_script_kind, Script},
+    transaction::TransactionKernel,
+};
 use miden_objects::{
-    accounts::{Account, AccountId},
-    assembly::ProgramAst,
+    accounts::{Account, AccountCode, AccountId, AccountStorage, ProcedureAccess, StorageSlotType},
+    assembly::{ModuleAst, ProgramAst},
     assets::{Asset, AssetVault, FungibleAsset},
     utils::collections::Vec,
-    Felt,
+    Felt, EMPTY_WORD, ONE,
 };
-use miden_tx::TransactionExecutor;
+use miden_tx::{host::TransactionPhase, TransactionExecutor, TransactionExecutorError};
 use mock::constants::{
     ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN_2,
-    ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN, ACCOUNT_ID_SENDER,
+    ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN, ACCOUNT_ID_SENDER, DEFAULT_ACCOUNT_CODE,
 };
+use vm_core::utils::to_hex;
 
 mod common;
 use common::{
-    get_account_with_default_account_code, get_new_key_pair_with_advice_map, MockDataStore,
+    get_account_with_default_account_code, get_new_key_pair_with_advice_map,
+    get_note_with_fungible_asset_and_script, MockDataStore,
 };
 
 // P2ID TESTS
@@ -52,7 +57,7 @@ fn test_p2id_script() {
     let data_store =
         MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
 
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     executor.load_account(target_account_id).unwrap();
 
     let block_ref = data_store.block_header.block_num();
@@ -79,7 +84,13 @@ fn test_p2id_script() {
 
     // Execute the transaction and get the witness
     let executed_transaction = executor
-        .execute_transaction(target_account_id, block_ref, &note_ids, Some(tx_script_target))
+        .execute_transaction(
+            target_account_id,
+            block_ref,
+            &note_ids,
+            Some(tx_script_target),
+            EMPTY_WORD,
+        )
         .unwrap();
 
     // vault delta
@@ -92,6 +103,17 @@ fn test_p2id_script() {
     );
     assert_eq!(executed_transaction.final_account().hash(), target_account_after.hash());
 
+    // transaction description
+    let description = executed_transaction.description().with_script_kinds(standard_script_kind);
+    assert_eq!(description.nonce_change(), Some((Felt::new(1), Felt::new(2))));
+    assert_eq!(description.assets_received(), &[fungible_asset]);
+    assert!(description.assets_sent().is_empty());
+    assert_eq!(description.consumed_notes().len(), 1);
+    assert_eq!(description.consumed_notes()[0].id(), Some(note.id()));
+    assert_eq!(description.consumed_notes()[0].script_kind(), Some("P2ID"));
+    assert!(description.created_notes().is_empty());
+    assert!(description.to_string().contains("[P2ID]"));
+
     // CONSTRUCT AND EXECUTE TX (Failure)
     // --------------------------------------------------------------------------------------------
     // A "malicious" account tries to consume the note, we expect an error
@@ -104,7 +126,7 @@ fn test_p2id_script() {
 
     let data_store_malicious_account =
         MockDataStore::with_existing(Some(malicious_account), Some(vec![note]));
-    let mut executor_2 = TransactionExecutor::new(data_store_malicious_account.clone());
+    let executor_2 = TransactionExecutor::new(data_store_malicious_account.clone());
     executor_2.load_account(malicious_account_id).unwrap();
     let tx_script_malicious = executor
         .compile_tx_script(
@@ -127,10 +149,202 @@ fn test_p2id_script() {
         block_ref,
         &note_ids,
         Some(tx_script_malicious),
+        EMPTY_WORD,
     );
 
-    // Check that we got the expected result - TransactionExecutorError
-    assert!(transaction_result_2.is_err());
+    // Check that we got the expected result - the failure is attributed to the P2ID note
+    match transaction_result_2 {
+        Err(TransactionExecutorError::ExecuteTransactionProgramFailed {
+            phase,
+            failed_note,
+            ..
+        }) => {
+            assert_eq!(phase, TransactionPhase::NoteExecution { note_index: 0 });
+            assert_eq!(failed_note, Some(note_ids[0]));
+        },
+        result => panic!("unexpected result: {:?}", result.map(|tx| tx.id())),
+    }
+}
+
+/// We test that a P2ID note cannot be consumed by an account whose wallet procedures can be
+/// invoked only by transaction scripts.
+#[test]
+fn test_p2id_script_with_restricted_wallet() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+
+    // restrict all procedures of the target account to transaction scripts
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (target_pub_key, target_keypair_felt) = get_new_key_pair_with_advice_map();
+    let account = get_account_with_default_account_code(target_account_id, target_pub_key, None);
+    let mut code = account.code().clone();
+    for proc_root in account.code().procedures() {
+        code = code.with_procedure_access(*proc_root, ProcedureAccess::TxScriptOnly).unwrap();
+    }
+    let target_account = Account::new(
+        target_account_id,
+        account.vault().clone(),
+        account.storage().clone(),
+        code,
+        account.nonce(),
+    );
+
+    let note = create_note(
+        Script::P2ID { target: target_account_id },
+        vec![fungible_asset],
+        sender_account_id,
+        None,
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+    )
+    .unwrap();
+
+    let data_store =
+        MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(target_account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+    )
+    .unwrap();
+    let tx_script = executor
+        .compile_tx_script(tx_script_code, vec![(target_pub_key, target_keypair_felt)], vec![])
+        .unwrap();
+
+    // the note script cannot invoke the wallet procedures of the account
+    let result = executor.execute_transaction(
+        target_account_id,
+        block_ref,
+        &[note.id()],
+        Some(tx_script.clone()),
+        EMPTY_WORD,
+    );
+    assert!(matches!(
+        result,
+        Err(TransactionExecutorError::ExecuteTransactionProgramFailed {
+            phase: TransactionPhase::NoteExecution { note_index: 0 },
+            ..
+        })
+    ));
+
+    // the transaction script can still invoke the authentication procedure of the account
+    let executed_transaction = executor
+        .execute_transaction(target_account_id, block_ref, &[], Some(tx_script.clone()), EMPTY_WORD)
+        .unwrap();
+    assert_eq!(executed_transaction.final_account().nonce(), Felt::new(2));
+
+    // internal procedures cannot be invoked by transaction scripts either
+    let mut code = account.code().clone();
+    for proc_root in account.code().procedures() {
+        code = code.with_procedure_access(*proc_root, ProcedureAccess::Internal).unwrap();
+    }
+    let internal_account = Account::new(
+        target_account_id,
+        account.vault().clone(),
+        account.storage().clone(),
+        code,
+        account.nonce(),
+    );
+    let data_store = MockDataStore::with_existing(Some(internal_account), Some(vec![]));
+    let executor = TransactionExecutor::new(data_store);
+    executor.load_account(target_account_id).unwrap();
+    let result = executor.execute_transaction(
+        target_account_id,
+        block_ref,
+        &[],
+        Some(tx_script),
+        EMPTY_WORD,
+    );
+    assert!(matches!(
+        result,
+        Err(TransactionExecutorError::ExecuteTransactionProgramFailed {
+            phase: TransactionPhase::TransactionScript,
+            ..
+        })
+    ));
+}
+
+/// We test that access levels do not restrict account procedures which do not invoke kernel
+/// procedures: such procedures cannot access the account state, and thus can be invoked by note
+/// scripts and transaction scripts even if they are internal.
+#[test]
+fn test_internal_procedure_without_kernel_calls() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset = FungibleAsset::new(faucet_id, 100).unwrap();
+
+    // the account exposes an internal procedure which doubles the value on top of the stack
+    let double = "export.double mul.2 end";
+    let assembler = TransactionKernel::assembler();
+    let double_root = AccountCode::new(ModuleAst::parse(double).unwrap(), &assembler)
+        .unwrap()
+        .procedures()[0];
+    let account_code_src = format!("{DEFAULT_ACCOUNT_CODE}\n{double}");
+    let account_code = AccountCode::new(ModuleAst::parse(&account_code_src).unwrap(), &assembler)
+        .unwrap()
+        .with_procedure_access(double_root, ProcedureAccess::Internal)
+        .unwrap();
+
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (pub_key, keypair_felt) = get_new_key_pair_with_advice_map();
+    let account = Account::new(
+        account_id,
+        AssetVault::default(),
+        AccountStorage::new(vec![(0, (StorageSlotType::Value { value_arity: 0 }, pub_key))])
+            .unwrap(),
+        account_code,
+        ONE,
+    );
+
+    // both the note script and the transaction script invoke the internal procedure
+    let double_root = to_hex(&double_root.as_bytes()).unwrap();
+    let note_script = ProgramAst::parse(&format!(
+        "
+        use.miden::note
+
+        begin
+            dropw
+            push.21 call.0x{double_root} push.42 assert_eq
+            exec.note::add_assets_to_account
+        end
+        "
+    ))
+    .unwrap();
+    let note = get_note_with_fungible_asset_and_script(fungible_asset, note_script);
+
+    let data_store = MockDataStore::with_existing(Some(account), Some(vec![note.clone()]));
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account_id).unwrap();
+
+    let tx_script_code = ProgramAst::parse(&format!(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            push.21 call.0x{double_root} push.42 assert_eq
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        "
+    ))
+    .unwrap();
+    let tx_script = executor
+        .compile_tx_script(tx_script_code, vec![(pub_key, keypair_felt)], vec![])
+        .unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &[note.id()], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+    assert_eq!(executed_transaction.final_account().vault().get_balance(faucet_id), Ok(100));
 }
 
 /// We test the Pay to script with 2 assets to test the loop inside the script.
@@ -169,7 +383,7 @@ fn test_p2id_script_multiple_assets() {
     let data_store =
         MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
 
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     executor.load_account(target_account_id).unwrap();
 
     let block_ref = data_store.block_header.block_num();
@@ -195,7 +409,13 @@ fn test_p2id_script_multiple_assets() {
 
     // Execute the transaction and get the witness
     let transaction_result = executor
-        .execute_transaction(target_account_id, block_ref, &note_ids, Some(tx_script_target))
+        .execute_transaction(
+            target_account_id,
+            block_ref,
+            &note_ids,
+            Some(tx_script_target),
+            EMPTY_WORD,
+        )
         .unwrap();
 
     // vault delta
@@ -220,7 +440,7 @@ fn test_p2id_script_multiple_assets() {
 
     let data_store_malicious_account =
         MockDataStore::with_existing(Some(malicious_account), Some(vec![note]));
-    let mut executor_2 = TransactionExecutor::new(data_store_malicious_account.clone());
+    let executor_2 = TransactionExecutor::new(data_store_malicious_account.clone());
     executor_2.load_account(malicious_account_id).unwrap();
     let tx_script_malicious = executor
         .compile_tx_script(
@@ -243,8 +463,87 @@ fn test_p2id_script_multiple_assets() {
         block_ref,
         &note_origins,
         Some(tx_script_malicious),
+        EMPTY_WORD,
     );
 
     // Check that we got the expected result - TransactionExecutorError
     assert!(transaction_result_2.is_err());
 }
+
+// We test that the kernel rejects consumption of an expired note. The block height of the mock
+// chain is 4, so the transaction is included into block 5 or later: a note expiring at block 5 can
+// still be consumed (and the transaction expires together with the note), while notes expiring at
+// block 4 or earlier cannot.
+#[test]
+fn test_p2id_script_note_expiration() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (target_pub_key, target_keypair_felt) = get_new_key_pair_with_advice_map();
+    let target_account =
+        get_account_with_default_account_code(target_account_id, target_pub_key, None);
+
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+    )
+    .unwrap();
+
+    for (expiration_block_num, should_succeed) in [(6, true), (5, true), (4, false), (3, false)] {
+        let note = create_note(
+            Script::P2ID { target: target_account_id },
+            vec![fungible_asset],
+            sender_account_id,
+            None,
+            [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+        )
+        .unwrap()
+        .with_expiration(expiration_block_num)
+        .unwrap();
+
+        let data_store =
+            MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
+        let executor = TransactionExecutor::new(data_store.clone());
+        executor.load_account(target_account_id).unwrap();
+
+        let block_ref = data_store.block_header.block_num();
+        assert_eq!(note.metadata().is_expired(block_ref + 1), !should_succeed);
+
+        let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+        let tx_script = executor
+            .compile_tx_script(
+                tx_script_code.clone(),
+                vec![(target_pub_key, target_keypair_felt.clone())],
+                vec![],
+            )
+            .unwrap();
+
+        let result = executor.execute_transaction(
+            target_account_id,
+            block_ref,
+            &note_ids,
+            Some(tx_script),
+            EMPTY_WORD,
+        );
+        if should_succeed {
+            let executed_transaction = result.unwrap();
+            assert_eq!(executed_transaction.expiration_block_num(), Some(expiration_block_num));
+        } else {
+            assert!(matches!(
+                result,
+                Err(TransactionExecutorError::ExecuteTransactionProgramFailed {
+                    phase: TransactionPhase::Prologue,
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -1,10 +1,11 @@
-use miden_lib::notes::{create_note, Script};
+use miden_lib::notes::{create_note, NoteScriptTemplate, Script};
 use miden_objects::{
     accounts::{Account, AccountId},
     assembly::ProgramAst,
     assets::{Asset, AssetVault, FungibleAsset},
+    notes::{Note, NoteTag},
     utils::collections::Vec,
-    Felt,
+    Felt, NoteError, EMPTY_WORD,
 };
 use miden_tx::TransactionExecutor;
 use mock::constants::{
@@ -94,7 +95,7 @@ fn test_p2idr_script() {
         Some(target_account.clone()),
         Some(vec![note_in_time.clone()]),
     );
-    let mut executor_1 = TransactionExecutor::new(data_store_1.clone());
+    let executor_1 = TransactionExecutor::new(data_store_1.clone());
 
     executor_1.load_account(target_account_id).unwrap();
 
@@ -126,6 +127,7 @@ fn test_p2idr_script() {
             block_ref_1,
             &note_ids,
             Some(tx_script_target.clone()),
+            EMPTY_WORD,
         )
         .unwrap();
 
@@ -145,7 +147,7 @@ fn test_p2idr_script() {
         Some(sender_account.clone()),
         Some(vec![note_in_time.clone()]),
     );
-    let mut executor_2 = TransactionExecutor::new(data_store_2.clone());
+    let executor_2 = TransactionExecutor::new(data_store_2.clone());
     executor_2.load_account(sender_account_id).unwrap();
     let tx_script_sender = executor_2
         .compile_tx_script(
@@ -164,6 +166,7 @@ fn test_p2idr_script() {
         block_ref_2,
         &note_ids_2,
         Some(tx_script_sender.clone()),
+        EMPTY_WORD,
     );
 
     // Check that we got the expected result - TransactionExecutorError and not ExecutedTransaction
@@ -176,7 +179,7 @@ fn test_p2idr_script() {
         Some(malicious_account.clone()),
         Some(vec![note_in_time.clone()]),
     );
-    let mut executor_3 = TransactionExecutor::new(data_store_3.clone());
+    let executor_3 = TransactionExecutor::new(data_store_3.clone());
     executor_3.load_account(malicious_account_id).unwrap();
     let tx_script_malicious = executor_3
         .compile_tx_script(
@@ -195,6 +198,7 @@ fn test_p2idr_script() {
         block_ref_3,
         &note_ids_3,
         Some(tx_script_malicious.clone()),
+        EMPTY_WORD,
     );
 
     // Check that we got the expected result - TransactionExecutorError and not ExecutedTransaction
@@ -207,7 +211,7 @@ fn test_p2idr_script() {
         Some(target_account.clone()),
         Some(vec![note_reclaimable.clone()]),
     );
-    let mut executor_4 = TransactionExecutor::new(data_store_4.clone());
+    let executor_4 = TransactionExecutor::new(data_store_4.clone());
     executor_4.load_account(target_account_id).unwrap();
 
     let block_ref_4 = data_store_4.block_header.block_num();
@@ -215,7 +219,13 @@ fn test_p2idr_script() {
 
     // Execute the transaction and get the witness
     let transaction_result_4 = executor_4
-        .execute_transaction(target_account_id, block_ref_4, &note_ids_4, Some(tx_script_target))
+        .execute_transaction(
+            target_account_id,
+            block_ref_4,
+            &note_ids_4,
+            Some(tx_script_target),
+            EMPTY_WORD,
+        )
         .unwrap();
 
     // Check that we got the expected result - ExecutedTransaction
@@ -239,7 +249,7 @@ fn test_p2idr_script() {
         Some(sender_account.clone()),
         Some(vec![note_reclaimable.clone()]),
     );
-    let mut executor_5 = TransactionExecutor::new(data_store_5.clone());
+    let executor_5 = TransactionExecutor::new(data_store_5.clone());
 
     executor_5.load_account(sender_account_id).unwrap();
 
@@ -248,7 +258,13 @@ fn test_p2idr_script() {
 
     // Execute the transaction and get the witness
     let transaction_result_5 = executor_5
-        .execute_transaction(sender_account_id, block_ref_5, &note_ids_5, Some(tx_script_sender))
+        .execute_transaction(
+            sender_account_id,
+            block_ref_5,
+            &note_ids_5,
+            Some(tx_script_sender),
+            EMPTY_WORD,
+        )
         .unwrap();
 
     // Assert that the sender_account received the funds and the nonce increased by 1
@@ -271,7 +287,7 @@ fn test_p2idr_script() {
         Some(malicious_account.clone()),
         Some(vec![note_reclaimable.clone()]),
     );
-    let mut executor_6 = TransactionExecutor::new(data_store_6.clone());
+    let executor_6 = TransactionExecutor::new(data_store_6.clone());
 
     executor_6.load_account(malicious_account_id).unwrap();
 
@@ -284,9 +300,71 @@ fn test_p2idr_script() {
         block_ref_6,
         &note_ids_6,
         Some(tx_script_malicious),
+        EMPTY_WORD,
     );
 
     // Check that we got the expected result - TransactionExecutorError and not ExecutedTransaction
     // Sixth transaction should not work (malicious account can never consume), we expect an error
     assert!(transaction_result_6.is_err())
 }
+
+// P2IDR TEMPLATE TESTS
+// ===============================================================================================
+// The inputs built via the P2IDR template must match the inputs of the standard P2IDR note
+// regardless of the order in which the values are provided.
+#[test]
+fn test_p2idr_template_inputs() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let tag = NoteTag::for_account(target_account_id);
+
+    let template = NoteScriptTemplate::p2idr().unwrap();
+    let inputs = template
+        .inputs_builder()
+        .set("recall_height", 5_u32)
+        .unwrap()
+        .set("target", target_account_id)
+        .unwrap()
+        .build()
+        .unwrap();
+    let note = Note::new(
+        template.script().clone(),
+        &inputs,
+        &[fungible_asset],
+        serial_num,
+        sender_account_id,
+        tag,
+    )
+    .unwrap();
+
+    let expected_note = create_note(
+        Script::P2IDR {
+            target: target_account_id,
+            recall_height: 5,
+        },
+        vec![fungible_asset],
+        sender_account_id,
+        Some(tag),
+        serial_num,
+    )
+    .unwrap();
+    assert_eq!(note.id(), expected_note.id());
+
+    // values not matching the size of the input, unknown inputs and missing inputs are rejected
+    assert!(matches!(
+        template.inputs_builder().set("recall_height", 5_u64),
+        Err(NoteError::InvalidNoteInputValue(_))
+    ));
+    assert!(matches!(
+        template.inputs_builder().set("recipient", target_account_id),
+        Err(NoteError::UnknownNoteInput(_))
+    ));
+    assert!(matches!(
+        template.inputs_builder().set("target", target_account_id).unwrap().build(),
+        Err(NoteError::MissingNoteInput(name)) if name == "recall_height"
+    ));
+}
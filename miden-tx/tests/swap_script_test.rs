@@ -1,19 +1,28 @@
 use common::{
     get_account_with_default_account_code, get_new_key_pair_with_advice_map, MockDataStore,
 };
-use miden_lib::notes::{create_note, Script};
+use miden_lib::notes::{
+    create_note, create_p2id_note, create_swap_note, OracleConstraint, Script, SWAP_USE_CASE_ID,
+};
 use miden_objects::{
-    accounts::{Account, AccountId, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN},
+    accounts::{
+        Account, AccountId, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+        ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+    },
     assembly::ProgramAst,
     assets::{Asset, AssetVault, FungibleAsset, NonFungibleAsset, NonFungibleAssetDetails},
-    notes::{NoteAssets, NoteMetadata},
+    crypto::merkle::{NodeIndex, SimpleSmt},
+    notes::{NoteAssets, NoteMetadata, NoteTag},
     transaction::OutputNote,
-    Felt,
+    Felt, StarkField, EMPTY_WORD, ZERO,
 };
 use miden_tx::TransactionExecutor;
-use mock::constants::{
-    ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
-    ACCOUNT_ID_SENDER,
+use mock::{
+    constants::{
+        ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN,
+        ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN, ACCOUNT_ID_SENDER,
+    },
+    mock::block::mock_block_header,
 };
 use vm_processor::Digest;
 
@@ -48,6 +57,7 @@ fn test_swap_script() {
     let swap_script = Script::SWAP {
         asset: non_fungible_asset,
         serial_num: [Felt::new(6), Felt::new(7), Felt::new(8), Felt::new(9)],
+        oracle: None,
     };
 
     let note = create_note(
@@ -64,7 +74,7 @@ fn test_swap_script() {
     let data_store =
         MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
 
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     executor.load_account(target_account_id).unwrap();
 
     let block_ref = data_store.block_header.block_num();
@@ -86,7 +96,13 @@ fn test_swap_script() {
 
     // Execute the transaction
     let transaction_result = executor
-        .execute_transaction(target_account_id, block_ref, &note_ids, Some(tx_script_target))
+        .execute_transaction(
+            target_account_id,
+            block_ref,
+            &note_ids,
+            Some(tx_script_target),
+            EMPTY_WORD,
+        )
         .unwrap();
 
     // target account vault delta
@@ -113,7 +129,7 @@ fn test_swap_script() {
     ]);
 
     let note_metadata =
-        NoteMetadata::new(target_account_id, sender_account_id.into(), Felt::new(1));
+        NoteMetadata::new(target_account_id, NoteTag::for_account(sender_account_id), Felt::new(1));
 
     let note_assets = NoteAssets::new(&[non_fungible_asset]).unwrap();
 
@@ -123,3 +139,236 @@ fn test_swap_script() {
 
     assert_eq!(created_note, &requested_note);
 }
+
+#[test]
+fn test_swap_script_with_oracle() {
+    // Create assets
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let non_fungible_asset: Asset = NonFungibleAsset::new(
+        &NonFungibleAssetDetails::new(faucet_id_2, vec![1, 2, 3, 4]).unwrap(),
+    )
+    .unwrap()
+    .into();
+
+    // Create sender, target, and oracle accounts; the oracle publishes a price of 50 in slot 0
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (target_pub_key, target_sk_felt) = get_new_key_pair_with_advice_map();
+    let target_account = get_account_with_default_account_code(
+        target_account_id,
+        target_pub_key,
+        Some(non_fungible_asset),
+    );
+
+    let oracle_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+    let oracle_account = get_account_with_default_account_code(
+        oracle_account_id,
+        [Felt::new(50), ZERO, ZERO, ZERO],
+        None,
+    );
+
+    // Build the account database containing the oracle account
+    let account_db = SimpleSmt::with_leaves(
+        64,
+        [&target_account, &oracle_account]
+            .iter()
+            .map(|account| (Felt::from(account.id()).as_int(), *account.hash())),
+    )
+    .unwrap();
+    let oracle_path = account_db
+        .get_path(NodeIndex::new(64, Felt::from(oracle_account_id).as_int()).unwrap())
+        .unwrap();
+
+    for (min_price, should_succeed) in [(40, true), (50, true), (60, false)] {
+        let oracle = OracleConstraint::new(oracle_account_id, 0, Felt::new(min_price));
+
+        // Create the note
+        let swap_script = Script::SWAP {
+            asset: non_fungible_asset,
+            serial_num: [Felt::new(6), Felt::new(7), Felt::new(8), Felt::new(9)],
+            oracle: Some(oracle),
+        };
+
+        let note = create_note(
+            swap_script,
+            vec![fungible_asset],
+            sender_account_id,
+            None,
+            [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+        )
+        .unwrap();
+
+        // CONSTRUCT AND EXECUTE TX
+        // ----------------------------------------------------------------------------------------
+        let mut data_store =
+            MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
+        data_store.block_header = mock_block_header(
+            data_store.block_header.block_num(),
+            Some(data_store.block_chain.peaks().hash_peaks()),
+            None,
+            &[target_account.clone(), oracle_account.clone()],
+        );
+        assert_eq!(data_store.block_header.account_root(), account_db.root());
+
+        let executor = TransactionExecutor::new(data_store.clone());
+        executor.load_account(target_account_id).unwrap();
+
+        let block_ref = data_store.block_header.block_num();
+        let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+        // the oracle proof is passed to the note script via the transaction script inputs
+        let oracle_proof =
+            oracle.build_proof(sender_account_id, &oracle_account, &oracle_path).unwrap();
+
+        let tx_script_code = ProgramAst::parse(
+            "
+            use.miden::contracts::auth::basic->auth_tx
+
+            begin
+                call.auth_tx::auth_tx_rpo_falcon512
+            end
+            ",
+        )
+        .unwrap();
+        let tx_script_target = executor
+            .compile_tx_script(
+                tx_script_code,
+                vec![(target_pub_key, target_sk_felt.clone()), oracle_proof],
+                vec![],
+            )
+            .unwrap();
+
+        let transaction_result = executor.execute_transaction(
+            target_account_id,
+            block_ref,
+            &note_ids,
+            Some(tx_script_target),
+            EMPTY_WORD,
+        );
+
+        if should_succeed {
+            let transaction_result = transaction_result.unwrap();
+            assert_eq!(transaction_result.output_notes().num_notes(), 1);
+            assert_eq!(
+                transaction_result.output_notes().get_note(0).assets(),
+                &NoteAssets::new(&[non_fungible_asset]).unwrap()
+            );
+        } else {
+            assert!(transaction_result.is_err());
+        }
+    }
+}
+
+#[test]
+fn test_swap_note_builder() {
+    // Create assets
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let non_fungible_asset: Asset = NonFungibleAsset::new(
+        &NonFungibleAssetDetails::new(faucet_id_2, vec![1, 2, 3, 4]).unwrap(),
+    )
+    .unwrap()
+    .into();
+
+    // Create sender and target account
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let (target_pub_key, target_sk_felt) = get_new_key_pair_with_advice_map();
+    let target_account = get_account_with_default_account_code(
+        target_account_id,
+        target_pub_key,
+        Some(non_fungible_asset),
+    );
+
+    // Create the note; it is tagged with the faucets of the traded pair
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let payback_serial_num = [Felt::new(6), Felt::new(7), Felt::new(8), Felt::new(9)];
+    let note = create_swap_note(
+        sender_account_id,
+        fungible_asset,
+        non_fungible_asset,
+        serial_num,
+        payback_serial_num,
+    )
+    .unwrap();
+
+    let payload = ((u64::from(faucet_id) >> 56) << 8 | u64::from(faucet_id_2) >> 56) as u16;
+    let tag = NoteTag::for_local_execution(SWAP_USE_CASE_ID, payload).unwrap();
+    assert_eq!(note.metadata().tag(), tag);
+
+    let swap_script = Script::SWAP {
+        asset: non_fungible_asset,
+        serial_num: payback_serial_num,
+        oracle: None,
+    };
+    let expected =
+        create_note(swap_script, vec![fungible_asset], sender_account_id, None, serial_num)
+            .unwrap();
+    assert_eq!(note.id(), expected.id());
+
+    // CONSTRUCT AND EXECUTE TX
+    // --------------------------------------------------------------------------------------------
+    let data_store =
+        MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note.clone()]));
+
+    // the payback note is the P2ID note the sender expects to receive
+    let payback_note = create_p2id_note(
+        target_account_id,
+        sender_account_id,
+        vec![non_fungible_asset],
+        payback_serial_num,
+    )
+    .unwrap();
+
+    // the executor knows the P2ID script, and thus can recover the payback note
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_note_scripts([payback_note.script().clone()])
+        .build();
+    executor.load_account(target_account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let tx_script_code = ProgramAst::parse(
+        "
+            use.miden::contracts::auth::basic->auth_tx
+
+            begin
+                call.auth_tx::auth_tx_rpo_falcon512
+            end
+            ",
+    )
+    .unwrap();
+    let tx_script_target = executor
+        .compile_tx_script(tx_script_code, vec![(target_pub_key, target_sk_felt)], vec![])
+        .unwrap();
+
+    let transaction_result = executor
+        .execute_transaction(
+            target_account_id,
+            block_ref,
+            &note_ids,
+            Some(tx_script_target),
+            EMPTY_WORD,
+        )
+        .unwrap();
+
+    assert_eq!(transaction_result.output_notes().num_notes(), 1);
+    let created_note = transaction_result.output_notes().get_note(0);
+    assert_eq!(created_note.id(), payback_note.id());
+    assert_eq!(created_note.recipient(), &payback_note.recipient());
+
+    // the full payback note is recovered from the recipient preimage signaled by the kernel
+    assert_eq!(transaction_result.output_note_details(payback_note.id()), Some(&payback_note));
+}
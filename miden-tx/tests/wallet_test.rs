@@ -1,12 +1,30 @@
-use miden_lib::{accounts::wallets::create_basic_wallet, AuthScheme};
+#[cfg(feature = "ecdsa")]
+use miden_lib::auth::{ecdsa, tx_message};
+use miden_lib::{
+    accounts::{
+        faucets::TokenMetadata, wallets::create_basic_wallet, AccountBuilder, AccountComponent,
+    },
+    auth::{AuthSchemeError, MultisigConfig},
+    notes::create_p2id_note,
+    transaction::TransactionKernel,
+    AuthScheme,
+};
+#[cfg(feature = "ecdsa")]
+use miden_objects::transaction::{InputNote, InputNotes, OutputNotes};
 use miden_objects::{
-    accounts::{Account, AccountId, AccountStorage, StorageSlotType},
-    assembly::ProgramAst,
+    accounts::{Account, AccountCode, AccountId, AccountStorage, StorageSlotType},
+    assembly::{ModuleAst, ProgramAst},
     assets::{Asset, AssetVault, FungibleAsset},
+    block::ProposedBlock,
     crypto::dsa::rpo_falcon512::{KeyPair, PublicKey},
-    Felt, Word, ONE, ZERO,
+    transaction::{TransactionBatch, TransactionScriptInputs, TransactionWitness},
+    utils::sync::Arc,
+    Digest, Felt, Word, EMPTY_WORD, ONE, ZERO,
+};
+use miden_tx::{
+    BasicAuthenticator, ProvingOptions, TransactionEffectMismatch, TransactionExecutor,
+    TransactionExecutorError, TransactionProver, TransactionRequest,
 };
-use miden_tx::TransactionExecutor;
 use mock::{
     constants::{
         ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
@@ -14,6 +32,7 @@ use mock::{
     },
     utils::prepare_word,
 };
+use vm_core::utils::to_hex;
 
 mod common;
 use common::{
@@ -60,7 +79,7 @@ fn test_receive_asset_via_wallet() {
     // --------------------------------------------------------------------------------------------
     let data_store = MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note]));
 
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     executor.load_account(target_account.id()).unwrap();
 
     let block_ref = data_store.block_header.block_num();
@@ -84,7 +103,7 @@ fn test_receive_asset_via_wallet() {
 
     // Execute the transaction and get the witness
     let transaction_result = executor
-        .execute_transaction(target_account.id(), block_ref, &note_ids, Some(tx_script))
+        .execute_transaction(target_account.id(), block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
         .unwrap();
 
     // nonce delta
@@ -106,6 +125,88 @@ fn test_receive_asset_via_wallet() {
     assert_eq!(transaction_result.final_account().hash(), target_account_after.hash());
 }
 
+#[test]
+// Testing the basic Miden wallet - receiving an asset with the signature provided by an
+// authenticator rather than by the advice inputs of the transaction
+fn test_receive_asset_via_wallet_with_authenticator() {
+    let faucet_id_1 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset_1 = FungibleAsset::new(faucet_id_1, 100).unwrap();
+
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let target_keypair = KeyPair::new().unwrap();
+    let target_pub_key: Word = target_keypair.public_key().into();
+    let target_account =
+        get_account_with_default_account_code(target_account_id, target_pub_key, None);
+
+    let note_script_ast = ProgramAst::parse(
+        "
+    use.miden::note
+    use.miden::contracts::wallets::basic->wallet
+
+    begin
+        dropw
+        exec.note::get_assets drop
+        mem_loadw
+        call.wallet::receive_asset
+        dropw
+    end
+    ",
+    )
+    .unwrap();
+
+    let note = get_note_with_fungible_asset_and_script(fungible_asset_1, note_script_ast);
+
+    // CONSTRUCT AND EXECUTE TX (Success)
+    // --------------------------------------------------------------------------------------------
+    let data_store = MockDataStore::with_existing(Some(target_account.clone()), Some(vec![note]));
+    let authenticator = BasicAuthenticator::new(&[target_keypair]);
+
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_authenticator(Arc::new(authenticator))
+        .build();
+    executor.load_account(target_account.id()).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let note_ids = data_store.notes.iter().map(|note| note.id()).collect::<Vec<_>>();
+
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+    )
+    .unwrap();
+
+    // the secret key is not provided to the transaction script
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+
+    let transaction_result = executor
+        .execute_transaction(target_account.id(), block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    assert_eq!(transaction_result.account_delta().nonce(), Some(Felt::new(2)));
+
+    // the secret key must not be a part of the transaction witness
+    let secret_key_entry = transaction_result
+        .advice_witness()
+        .mapped_values(&Digest::from(target_pub_key).as_bytes());
+    assert!(secret_key_entry.is_none());
+
+    // the signature provided by the authenticator is recorded in the witness, thus an executor
+    // without a data store and without an authenticator re-executes the transaction
+    let tx_witness: TransactionWitness = transaction_result.clone().into();
+    let witness_executor = TransactionExecutor::builder().with_data_store(()).build();
+    let reexecuted_transaction = witness_executor.execute_from_witness(tx_witness).unwrap();
+    assert_eq!(reexecuted_transaction.id(), transaction_result.id());
+    assert_eq!(reexecuted_transaction.account_delta(), transaction_result.account_delta());
+    assert_eq!(reexecuted_transaction.tx_script_args(), transaction_result.tx_script_args());
+}
+
 #[test]
 // Testing the basic Miden wallet - sending an asset
 fn test_send_asset_via_wallet() {
@@ -127,7 +228,7 @@ fn test_send_asset_via_wallet() {
     // --------------------------------------------------------------------------------------------
     let data_store = MockDataStore::with_existing(Some(sender_account.clone()), Some(vec![]));
 
-    let mut executor = TransactionExecutor::new(data_store.clone());
+    let executor = TransactionExecutor::new(data_store.clone());
     executor.load_account(sender_account.id()).unwrap();
 
     let block_ref = data_store.block_header.block_num();
@@ -164,7 +265,7 @@ fn test_send_asset_via_wallet() {
 
     // Execute the transaction and get the witness
     let transaction_result = executor
-        .execute_transaction(sender_account.id(), block_ref, &note_ids, Some(tx_script))
+        .execute_transaction(sender_account.id(), block_ref, &note_ids, Some(tx_script), EMPTY_WORD)
         .unwrap();
 
     // clones account info
@@ -184,6 +285,284 @@ fn test_send_asset_via_wallet() {
     assert_eq!(transaction_result.final_account().hash(), sender_account_after.hash());
 }
 
+#[test]
+// Testing the basic Miden wallet - recovering the full details of a sent note
+fn test_send_asset_via_wallet_recovers_output_note() {
+    let faucet_id_1 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset_1: Asset = FungibleAsset::new(faucet_id_1, 100).unwrap().into();
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let (sender_pub_key, sender_keypair_felt) = get_new_key_pair_with_advice_map();
+    let sender_account = get_account_with_default_account_code(
+        sender_account_id,
+        sender_pub_key,
+        fungible_asset_1.into(),
+    );
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let note =
+        create_p2id_note(sender_account_id, target_account_id, vec![fungible_asset_1], serial_num)
+            .unwrap();
+
+    let data_store = MockDataStore::with_existing(Some(sender_account.clone()), Some(vec![]));
+    let block_ref = data_store.block_header.block_num();
+
+    // the recipient of the note is built by the kernel from the inputs stored in memory
+    let tx_script_code = ProgramAst::parse(
+        format!(
+            "
+        use.miden::contracts::auth::basic->auth_tx
+        use.miden::contracts::wallets::basic->wallet
+        use.miden::note
+        use.miden::tx
+
+        begin
+            push.{inputs} mem_storew.0 dropw
+            push.0 exec.note::compute_inputs_hash
+            push.{script_hash}
+            padw
+            push.{serial_num}
+            exec.tx::build_recipient_hash
+            push.{tag}
+            push.{asset}
+            call.wallet::send_asset drop
+            dropw dropw
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+            inputs = prepare_word(&[target_account_id.into(), ZERO, ZERO, ZERO]),
+            script_hash = prepare_word(&note.script().hash().into()),
+            serial_num = prepare_word(&serial_num),
+            tag = Felt::from(note.metadata().tag()),
+            asset = prepare_word(&fungible_asset_1.into())
+        )
+        .as_str(),
+    )
+    .unwrap();
+    let tx_script_inputs = vec![(sender_pub_key, sender_keypair_felt)];
+
+    // the full note is recovered if the executor knows the script of the note
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_note_scripts([note.script().clone()])
+        .build();
+    executor.load_account(sender_account.id()).unwrap();
+    let tx_script = executor
+        .compile_tx_script(tx_script_code.clone(), tx_script_inputs.clone(), vec![])
+        .unwrap();
+
+    let transaction_result = executor
+        .execute_transaction(sender_account.id(), block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+    assert_eq!(transaction_result.output_notes().num_notes(), 1);
+    assert_eq!(transaction_result.output_note_details(note.id()), Some(&note));
+    assert_eq!(transaction_result.full_output_notes().collect::<Vec<_>>(), vec![&note]);
+
+    // otherwise, only the commitments to the note are available
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(sender_account.id()).unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, tx_script_inputs, vec![]).unwrap();
+
+    let transaction_result = executor
+        .execute_transaction(sender_account.id(), block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+    assert_eq!(transaction_result.output_notes().get_note(0).id(), note.id());
+    assert!(transaction_result.output_note_details(note.id()).is_none());
+}
+
+#[test]
+// Testing the basic Miden wallet - sending an asset in a note which expires, and discarding the
+// note once it expired
+fn test_send_asset_via_wallet_with_note_expiration() {
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset: Asset = FungibleAsset::new(faucet_id, 100).unwrap().into();
+
+    // the account sends assets via a procedure which sets the expiration of the created note
+    let send_asset_with_expiration = "
+        use.miden::contracts::wallets::basic->wallet
+        use.miden::tx
+
+        #! Inputs: [ASSET, tag, RECIPIENT, expiration_block_num]
+        #! Outputs: []
+        export.send_asset_with_expiration
+            exec.wallet::send_asset
+            # => [note_ptr, 0, 0, 0, 0, 0, 0, 0, 0, expiration_block_num]
+
+            drop dropw dropw
+            # => [expiration_block_num]
+
+            exec.tx::set_note_expiration
+            # => []
+        end
+    ";
+    let assembler = TransactionKernel::assembler();
+    let procedure_root =
+        AccountCode::new(ModuleAst::parse(send_asset_with_expiration).unwrap(), &assembler)
+            .unwrap()
+            .procedures()[0];
+    let account_code_src = format!(
+        "
+        use.miden::contracts::auth::basic->basic_eoa
+        {send_asset_with_expiration}
+        export.basic_eoa::auth_tx_rpo_falcon512
+        "
+    );
+    let account_code =
+        AccountCode::new(ModuleAst::parse(&account_code_src).unwrap(), &assembler).unwrap();
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let (sender_pub_key, sender_keypair_felt) = get_new_key_pair_with_advice_map();
+    let sender_account = Account::new(
+        sender_account_id,
+        AssetVault::new(&[fungible_asset]).unwrap(),
+        AccountStorage::new(vec![(0, (StorageSlotType::Value { value_arity: 0 }, sender_pub_key))])
+            .unwrap(),
+        account_code,
+        ONE,
+    );
+
+    // CONSTRUCT AND EXECUTE TX (Success)
+    // --------------------------------------------------------------------------------------------
+    let data_store = MockDataStore::with_existing(Some(sender_account.clone()), Some(vec![]));
+
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(sender_account.id()).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let expiration_block_num = block_ref + 10;
+
+    let recipient = [ZERO, ONE, Felt::new(2), Felt::new(3)];
+    let tag = Felt::new(4);
+
+    let tx_script_code = ProgramAst::parse(
+        format!(
+            "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            push.{expiration_block_num}
+            push.{recipient}
+            push.{tag}
+            push.{asset}
+            call.0x{procedure_root}
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+            recipient = prepare_word(&recipient),
+            asset = prepare_word(&fungible_asset.into()),
+            procedure_root = to_hex(&procedure_root.as_bytes()).unwrap(),
+        )
+        .as_str(),
+    )
+    .unwrap();
+    let tx_script = executor
+        .compile_tx_script(tx_script_code, vec![(sender_pub_key, sender_keypair_felt)], vec![])
+        .unwrap();
+
+    let executed_transaction = executor
+        .execute_transaction(sender_account.id(), block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    // the expiration set by the account is recorded in the metadata of the created note
+    let output_note = executed_transaction.output_notes().get_note(0).clone();
+    assert_eq!(output_note.metadata().expiration_block_num(), Some(expiration_block_num));
+
+    // CONSTRUCT BLOCKS
+    // --------------------------------------------------------------------------------------------
+    let proven_transaction = TransactionProver::new(ProvingOptions::default())
+        .prove_transaction(executed_transaction)
+        .unwrap();
+    assert_eq!(
+        proven_transaction.output_notes().get_note(0).metadata().expiration_block_num(),
+        Some(expiration_block_num)
+    );
+    let batch = TransactionBatch::new(vec![proven_transaction], &[]).unwrap();
+
+    // the note is recorded by blocks up to its expiration block
+    let block = ProposedBlock::new(expiration_block_num, vec![batch.clone()]).unwrap();
+    assert_eq!(block.output_notes().len(), 1);
+    assert_eq!(block.output_notes()[0].note_id(), output_note.id());
+    assert!(block.expired_notes().is_empty());
+
+    // and discarded by later blocks
+    let block = ProposedBlock::new(expiration_block_num + 1, vec![batch]).unwrap();
+    assert!(block.output_notes().is_empty());
+    assert_eq!(block.expired_notes(), &[output_note.id()]);
+}
+
+#[test]
+// Testing the basic Miden wallet - sending an asset via a transaction request
+fn test_send_asset_via_transaction_request() {
+    let faucet_id_1 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let fungible_asset_1: Asset = FungibleAsset::new(faucet_id_1, 100).unwrap().into();
+
+    let sender_account_id = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let (sender_pub_key, sender_keypair_felt) = get_new_key_pair_with_advice_map();
+    let sender_account = get_account_with_default_account_code(
+        sender_account_id,
+        sender_pub_key,
+        fungible_asset_1.into(),
+    );
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+
+    let data_store = MockDataStore::with_existing(Some(sender_account.clone()), Some(vec![]));
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(sender_account.id()).unwrap();
+
+    let mut script_inputs = TransactionScriptInputs::new();
+    script_inputs.insert(sender_pub_key, &sender_keypair_felt);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let request = TransactionRequest::new(sender_account.id())
+        .pay_to_id(fungible_asset_1, target_account_id, serial_num)
+        .unwrap()
+        .expect_balance(faucet_id_1, 0)
+        .unwrap()
+        .with_script_inputs(script_inputs.clone());
+    let expected_note_ids =
+        request.expected_output_notes().map(|note| note.id()).collect::<Vec<_>>();
+
+    let block_ref = data_store.block_header.block_num();
+    let transaction_result = executor.execute_request(block_ref, request).unwrap();
+
+    let created_note_ids = transaction_result
+        .output_notes()
+        .iter()
+        .map(|note| note.id())
+        .collect::<Vec<_>>();
+    assert_eq!(created_note_ids, expected_note_ids);
+    assert_eq!(
+        transaction_result.account_delta().vault().removed_assets,
+        vec![fungible_asset_1]
+    );
+    assert_eq!(transaction_result.account_delta().nonce(), Some(Felt::new(2)));
+
+    // executing a request with a wrong balance expectation fails with a typed mismatch
+    let request = TransactionRequest::new(sender_account.id())
+        .pay_to_id(fungible_asset_1, target_account_id, serial_num)
+        .unwrap()
+        .expect_balance(faucet_id_1, 100)
+        .unwrap()
+        .with_script_inputs(script_inputs);
+
+    match executor.execute_request(block_ref, request) {
+        Err(TransactionExecutorError::UnexpectedTransactionEffects { mismatches, .. }) => {
+            assert_eq!(
+                mismatches,
+                vec![TransactionEffectMismatch::BalanceMismatch {
+                    faucet_id: faucet_id_1,
+                    expected: 100,
+                    actual: 0,
+                }]
+            );
+        },
+        result => panic!("expected effect mismatch, got {result:?}"),
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[test]
 fn test_wallet_creation() {
@@ -216,3 +595,355 @@ fn test_wallet_creation() {
     let pub_key_word: Word = pub_key.into();
     assert_eq!(wallet.storage().get_item(0).as_elements(), pub_key_word);
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_account_builder() {
+    use miden_objects::{
+        accounts::{AccountStorageMode, AccountType},
+        assets::TokenSymbol,
+        AccountError,
+    };
+
+    let key_pair: KeyPair = KeyPair::new().unwrap();
+    let pub_key: PublicKey = key_pair.public_key();
+    let init_seed = [7_u8; 32];
+
+    // a wallet built from standard components matches the one created by create_basic_wallet
+    let (wallet, _) = create_basic_wallet(
+        init_seed,
+        AuthScheme::RpoFalcon512 { pub_key },
+        AccountType::RegularAccountUpdatableCode,
+    )
+    .unwrap();
+    let (account, seed) = AccountBuilder::new(init_seed)
+        .with_component(AccountComponent::BasicWallet)
+        .with_component(AccountComponent::Auth(AuthScheme::RpoFalcon512 { pub_key }))
+        .build()
+        .unwrap();
+    assert_eq!(account.hash(), wallet.hash());
+    assert_eq!(
+        AccountId::new(seed, account.code().root(), account.storage().root()).unwrap(),
+        account.id()
+    );
+
+    // a custom component adds its procedures and storage slots to the account
+    let counter = [Felt::new(3), ZERO, ZERO, ZERO];
+    let custom = AccountComponent::Custom {
+        imports: vec!["use.miden::account".to_string()],
+        code: "export.get_counter push.2 exec.account::get_item end\n".to_string(),
+        storage: vec![(2, (StorageSlotType::Value { value_arity: 0 }, counter))],
+    };
+    let (account, _) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_component(AccountComponent::BasicWallet)
+        .with_component(AccountComponent::Auth(AuthScheme::RpoFalcon512 { pub_key }))
+        .with_component(custom)
+        .build()
+        .unwrap();
+    assert!(account.id().is_on_chain());
+    assert_eq!(account.code().procedures().len(), 4);
+    assert_eq!(account.storage().get_item(2), Digest::from(counter));
+    let pub_key_word: Word = pub_key.into();
+    assert_eq!(account.storage().get_item(0), Digest::from(pub_key_word));
+
+    // components cannot initialize the same storage slot
+    let conflicting = AccountComponent::Custom {
+        imports: vec![],
+        code: "export.foo push.1 drop end\n".to_string(),
+        storage: vec![(0, (StorageSlotType::Value { value_arity: 0 }, counter))],
+    };
+    let result = AccountBuilder::new(init_seed)
+        .with_component(AccountComponent::Auth(AuthScheme::RpoFalcon512 { pub_key }))
+        .with_component(conflicting)
+        .build();
+    assert_eq!(result.unwrap_err(), AccountError::StorageSlotConflict(0));
+
+    // faucet components cannot be added to regular accounts
+    let metadata =
+        TokenMetadata::new(TokenSymbol::try_from("POL").unwrap(), 2, Felt::new(100)).unwrap();
+    let result = AccountBuilder::new(init_seed)
+        .with_component(AccountComponent::BasicFungibleFaucet { metadata })
+        .build();
+    assert!(matches!(
+        result,
+        Err(AccountError::AccountComponentNotSupported {
+            account_type: AccountType::RegularAccountUpdatableCode,
+            ..
+        })
+    ));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_new_public_wallet_account_details() {
+    use miden_objects::accounts::{AccountStorageMode, AccountType};
+
+    let key_pair: KeyPair = KeyPair::new().unwrap();
+    let pub_key: PublicKey = key_pair.public_key();
+
+    // build a new public wallet with a procedure updating one of its storage slots
+    let counter = [Felt::new(5), ZERO, ZERO, ZERO];
+    let set_counter = format!(
+        "export.set_counter push.{} push.2 exec.account::set_item dropw dropw end\n",
+        prepare_word(&counter)
+    );
+    let set_counter_root = AccountCode::new(
+        ModuleAst::parse(&format!("use.miden::account\n{set_counter}")).unwrap(),
+        &TransactionKernel::assembler(),
+    )
+    .unwrap()
+    .procedures()[0];
+    let custom = AccountComponent::Custom {
+        imports: vec!["use.miden::account".to_string()],
+        code: set_counter,
+        storage: vec![(2, (StorageSlotType::Value { value_arity: 0 }, [ZERO; 4]))],
+    };
+    let (account, seed) = AccountBuilder::new([9_u8; 32])
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_component(AccountComponent::BasicWallet)
+        .with_component(AccountComponent::Auth(AuthScheme::RpoFalcon512 { pub_key }))
+        .with_component(custom)
+        .build()
+        .unwrap();
+    assert!(account.is_new());
+
+    // CONSTRUCT AND EXECUTE TX
+    // --------------------------------------------------------------------------------------------
+    let data_store = MockDataStore::with_new_account(account.clone(), seed);
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_authenticator(Arc::new(BasicAuthenticator::new(&[key_pair])))
+        .build();
+    executor.load_account(account.id()).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let tx_script_code = ProgramAst::parse(&format!(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            call.0x{set_counter_root}
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+        set_counter_root = to_hex(&set_counter_root.as_bytes()).unwrap()
+    ))
+    .unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account.id(), block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    // the full state of the new account is included in the executed transaction
+    let account_details = executed_transaction.account_details().cloned().unwrap();
+    assert_eq!(account_details.id(), account.id());
+    assert_eq!(account_details.nonce(), ONE);
+    assert_eq!(account_details.code().root(), account.code().root());
+    assert_eq!(account_details.storage().get_item(2), Digest::from(counter));
+    assert_eq!(account_details.hash(), executed_transaction.final_account().hash());
+
+    // and carried over to the proven transaction
+    let proven_transaction = TransactionProver::new(ProvingOptions::default())
+        .prove_transaction(executed_transaction)
+        .unwrap();
+    assert_eq!(proven_transaction.account_details(), Some(&account_details));
+}
+
+#[test]
+fn test_new_account_delta_without_state_sets_nonce() {
+    // build a new account with neither storage items nor assets, whose only procedure increments
+    // the nonce
+    let incr_nonce = "export.incr_nonce push.1 exec.account::incr_nonce end\n".to_string();
+    let incr_nonce_root = AccountCode::new(
+        ModuleAst::parse(&format!("use.miden::account\n{incr_nonce}")).unwrap(),
+        &TransactionKernel::assembler(),
+    )
+    .unwrap()
+    .procedures()[0];
+    let (account, seed) = AccountBuilder::new([7_u8; 32])
+        .with_component(AccountComponent::Custom {
+            imports: vec!["use.miden::account".to_string()],
+            code: incr_nonce,
+            storage: vec![],
+        })
+        .build()
+        .unwrap();
+    assert!(account.is_new());
+
+    let data_store = MockDataStore::with_new_account(account.clone(), seed);
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account.id()).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let tx_script_code = ProgramAst::parse(&format!(
+        "begin call.0x{} end",
+        to_hex(&incr_nonce_root.as_bytes()).unwrap()
+    ))
+    .unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![], vec![]).unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account.id(), block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+
+    // the delta does not change any state, but still sets the nonce of the new account
+    let account_delta = executed_transaction.account_delta();
+    assert!(account_delta.is_empty());
+    assert_eq!(account_delta.nonce(), Some(ONE));
+    assert_eq!(executed_transaction.final_account().nonce(), ONE);
+
+    // and thus applying the delta to the initial state yields the final state of the account
+    let mut final_account = account.clone();
+    final_account.apply_delta(account_delta).unwrap();
+    assert_eq!(final_account.hash(), executed_transaction.final_account().hash());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_multisig_wallet() {
+    let key_pair_0 = KeyPair::new().unwrap();
+    let key_pair_1 = KeyPair::new().unwrap();
+    let key_pair_2 = KeyPair::new().unwrap();
+    let pub_keys = vec![key_pair_0.public_key(), key_pair_1.public_key(), key_pair_2.public_key()];
+
+    // invalid configurations are rejected
+    assert!(matches!(
+        MultisigConfig::new(4, pub_keys.clone()),
+        Err(AuthSchemeError::InvalidThreshold { threshold: 4, num_keys: 3 })
+    ));
+    assert!(matches!(
+        MultisigConfig::new(1, vec![pub_keys[0], pub_keys[0]]),
+        Err(AuthSchemeError::DuplicatePublicKey(_))
+    ));
+
+    // build a 2-of-3 multisig wallet
+    let config = MultisigConfig::new(2, pub_keys.clone()).unwrap();
+    let (code, storage) = AccountBuilder::new([3_u8; 32])
+        .with_component(AccountComponent::BasicWallet)
+        .with_component(AccountComponent::Auth(AuthScheme::RpoFalcon512Multisig {
+            config: config.clone(),
+        }))
+        .build_code_and_storage()
+        .unwrap();
+    assert_eq!(storage.get_item(0), Digest::from(config.commitment()));
+
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let account = Account::new(account_id, AssetVault::default(), storage, code, ONE);
+
+    // only the first and the last signers hold their keys
+    let data_store = MockDataStore::with_existing(Some(account.clone()), Some(vec![]));
+    let authenticator = BasicAuthenticator::new(&[key_pair_0, key_pair_2]);
+    let executor = TransactionExecutor::builder()
+        .with_data_store(data_store.clone())
+        .with_authenticator(Arc::new(authenticator))
+        .build();
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::multisig->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_rpo_falcon512_multisig
+        end
+        ",
+    )
+    .unwrap();
+
+    // the transaction is authenticated by two of the three signers
+    let signers = config.advice_inputs(&[pub_keys[0], pub_keys[2]]).unwrap();
+    let tx_script = executor
+        .compile_tx_script(tx_script_code.clone(), vec![signers], vec![])
+        .unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+    assert_eq!(executed_transaction.account_delta().nonce(), Some(Felt::new(2)));
+
+    // a single signature does not meet the threshold
+    let signers = config.advice_inputs(&[pub_keys[0]]).unwrap();
+    let tx_script = executor.compile_tx_script(tx_script_code, vec![signers], vec![]).unwrap();
+    let result =
+        executor.execute_transaction(account_id, block_ref, &[], Some(tx_script), EMPTY_WORD);
+    assert!(matches!(
+        result,
+        Err(TransactionExecutorError::ExecuteTransactionProgramFailed { .. })
+    ));
+
+    // signers must be a part of the configuration
+    let outsider = KeyPair::new().unwrap().public_key();
+    assert!(matches!(
+        config.advice_inputs(&[pub_keys[0], outsider]),
+        Err(AuthSchemeError::UnknownSigner(_))
+    ));
+}
+
+#[cfg(all(feature = "ecdsa", not(target_arch = "wasm32")))]
+#[test]
+fn test_ecdsa_wallet() {
+    let signing_key = ecdsa::SigningKey::from_slice(&[7_u8; 32]).unwrap();
+    let pub_key = *signing_key.verifying_key();
+
+    let (code, storage) = AccountBuilder::new([5_u8; 32])
+        .with_component(AccountComponent::BasicWallet)
+        .with_component(AccountComponent::Auth(AuthScheme::EcdsaK256 { pub_key }))
+        .build_code_and_storage()
+        .unwrap();
+    assert_eq!(storage.get_item(0), Digest::from(ecdsa::pub_key_commitment(&pub_key)));
+
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN).unwrap();
+    let account = Account::new(account_id, AssetVault::default(), storage, code, ONE);
+
+    let data_store = MockDataStore::with_existing(Some(account.clone()), Some(vec![]));
+    let executor = TransactionExecutor::new(data_store.clone());
+    executor.load_account(account_id).unwrap();
+
+    let block_ref = data_store.block_header.block_num();
+    let tx_script_code = ProgramAst::parse(
+        "
+        use.miden::contracts::auth::ecdsa->auth_tx
+
+        begin
+            call.auth_tx::auth_tx_ecdsa_k256
+        end
+        ",
+    )
+    .unwrap();
+
+    // sign the message of a transaction which neither consumes nor creates notes
+    let message = tx_message(
+        account_id,
+        ONE,
+        InputNotes::<InputNote>::default().commitment(),
+        OutputNotes::default().commitment(),
+    );
+    let (key, signature) = ecdsa::sign(&signing_key, message).unwrap();
+    assert!(ecdsa::verify(&pub_key, message, &signature));
+
+    let tx_script = executor
+        .compile_tx_script(tx_script_code.clone(), vec![(key, signature)], vec![])
+        .unwrap();
+    let executed_transaction = executor
+        .execute_transaction(account_id, block_ref, &[], Some(tx_script), EMPTY_WORD)
+        .unwrap();
+    assert_eq!(executed_transaction.account_delta().nonce(), Some(Felt::new(2)));
+
+    // a signature over a different message is rejected
+    let (_, wrong_signature) = ecdsa::sign(&signing_key, [ONE; 4]).unwrap();
+    assert!(!ecdsa::verify(&pub_key, message, &wrong_signature));
+
+    let tx_script = executor
+        .compile_tx_script(tx_script_code, vec![(key, wrong_signature)], vec![])
+        .unwrap();
+    let result =
+        executor.execute_transaction(account_id, block_ref, &[], Some(tx_script), EMPTY_WORD);
+    assert!(matches!(
+        result,
+        Err(TransactionExecutorError::ExecuteTransactionProgramFailed { .. })
+    ));
+}
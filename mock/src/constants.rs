@@ -1,12 +1,13 @@
 use miden_objects::{
     accounts::{AccountId, AccountType, SlotItem, StorageSlotType},
     assets::{Asset, NonFungibleAsset, NonFungibleAssetDetails},
+    crypto::merkle::TieredSmt,
     Felt, FieldElement, Word, ZERO,
 };
 
 pub use super::mock::account::{
     ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX, ACCOUNT_PROCEDURE_SET_CODE_PROC_IDX,
-    ACCOUNT_PROCEDURE_SET_ITEM_PROC_IDX,
+    ACCOUNT_PROCEDURE_SET_ITEM_PROC_IDX, ACCOUNT_PROCEDURE_SET_MAP_ITEM_PROC_IDX,
 };
 use super::{
     mock::account::{mock_account, mock_fungible_faucet, mock_non_fungible_faucet},
@@ -62,11 +63,21 @@ pub fn storage_item_1() -> SlotItem {
     (STORAGE_INDEX_1, (StorageSlotType::Value { value_arity: 0 }, STORAGE_VALUE_1))
 }
 
-pub const CHILD_ROOT_PARENT_LEAF_INDEX: u8 = 10;
-pub const CHILD_SMT_DEPTH: u8 = 64;
-pub const CHILD_STORAGE_INDEX_0: u64 = 40;
-pub const CHILD_STORAGE_VALUE_0: [Felt; 4] =
-    [Felt::new(11), Felt::new(12), Felt::new(13), Felt::new(14)];
+pub const STORAGE_INDEX_2: u8 = 40;
+pub const STORAGE_MAP_KEY_0: Word =
+    [Felt::new(101), Felt::new(102), Felt::new(103), Felt::new(104)];
+pub const STORAGE_MAP_VALUE_0: Word = [Felt::new(11), Felt::new(12), Felt::new(13), Felt::new(14)];
+
+pub fn storage_map_2() -> TieredSmt {
+    TieredSmt::with_entries([(STORAGE_MAP_KEY_0.into(), STORAGE_MAP_VALUE_0)]).unwrap()
+}
+
+pub fn storage_item_2() -> SlotItem {
+    (
+        STORAGE_INDEX_2,
+        (StorageSlotType::Map { value_arity: 0 }, *storage_map_2().root()),
+    )
+}
 
 pub fn non_fungible_asset(account_id: u64) -> Asset {
     let non_fungible_asset_details = NonFungibleAssetDetails::new(
@@ -0,0 +1,126 @@
+use miden_lib::{
+    accounts::{faucets::create_basic_fungible_faucet, wallets::create_basic_wallet},
+    notes::{create_note, Script},
+    AuthScheme,
+};
+use miden_objects::{
+    accounts::{Account, AccountId, AccountType},
+    assets::{Asset, AssetVault, FungibleAsset, TokenSymbol},
+    crypto::dsa::rpo_falcon512::KeyPair,
+    notes::Note,
+    transaction::{InputNotes, TransactionInputs},
+    utils::collections::Vec,
+    Felt, FieldElement, Word,
+};
+
+use super::{
+    constants::{ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_SENDER, FUNGIBLE_ASSET_AMOUNT},
+    mock::{block::mock_block_header, chain::mock_chain_data},
+};
+
+// ACCOUNT FIXTURES
+// ================================================================================================
+
+/// Returns an existing basic wallet account holding the provided assets, together with the seed
+/// used to derive its ID and the key pair authenticating it.
+///
+/// The wallet is created via [create_basic_wallet] with a random init seed and an RPO Falcon512
+/// key pair, so its code and storage are identical to what a real wallet would have. The nonce
+/// of the returned account is set to ONE.
+///
+/// # Panics
+/// Panics if the provided assets cannot be put into a single asset vault.
+pub fn funded_wallet(assets: &[Asset]) -> (Account, Word, KeyPair) {
+    let key_pair = KeyPair::new().expect("failed to generate key pair");
+    let auth_scheme = AuthScheme::RpoFalcon512 { pub_key: key_pair.public_key() };
+
+    let (wallet, seed) =
+        create_basic_wallet(rand::random(), auth_scheme, AccountType::RegularAccountUpdatableCode)
+            .expect("failed to create basic wallet");
+
+    let vault = AssetVault::new(assets).expect("invalid wallet assets");
+    let wallet = Account::new(
+        wallet.id(),
+        vault,
+        wallet.storage().clone(),
+        wallet.code().clone(),
+        Felt::ONE,
+    );
+
+    (wallet, seed, key_pair)
+}
+
+/// Returns a new basic fungible faucet account for the specified token symbol and max supply,
+/// together with the seed used to derive its ID and the key pair authenticating it.
+///
+/// The faucet is created with 10 decimals and its nonce is set to ZERO, so the returned seed
+/// must be provided when the faucet executes its first transaction.
+///
+/// # Panics
+/// Panics if the symbol is not a valid token symbol or if the max supply is out of bounds.
+pub fn fungible_faucet(symbol: &str, max_supply: u64) -> (Account, Word, KeyPair) {
+    let key_pair = KeyPair::new().expect("failed to generate key pair");
+    let auth_scheme = AuthScheme::RpoFalcon512 { pub_key: key_pair.public_key() };
+
+    let symbol = TokenSymbol::new(symbol).expect("invalid token symbol");
+    let (faucet, seed) = create_basic_fungible_faucet(
+        rand::random(),
+        symbol,
+        10,
+        Felt::new(max_supply),
+        auth_scheme,
+    )
+    .expect("failed to create fungible faucet");
+
+    (faucet, seed, key_pair)
+}
+
+// NOTE FIXTURES
+// ================================================================================================
+
+/// Returns a P2ID note addressed to the provided account.
+///
+/// The note is sent by [ACCOUNT_ID_SENDER], carries [FUNGIBLE_ASSET_AMOUNT] units of the asset
+/// issued by [ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN], and uses a random serial number.
+pub fn p2id_note_to(account: &Account) -> Note {
+    let sender = AccountId::try_from(ACCOUNT_ID_SENDER).unwrap();
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let asset: Asset = FungibleAsset::new(faucet_id, FUNGIBLE_ASSET_AMOUNT).unwrap().into();
+
+    let serial_num = [
+        Felt::new(rand::random()),
+        Felt::new(rand::random()),
+        Felt::new(rand::random()),
+        Felt::new(rand::random()),
+    ];
+
+    create_note(Script::P2ID { target: account.id() }, vec![asset], sender, None, serial_num)
+        .expect("failed to create P2ID note")
+}
+
+// TRANSACTION INPUT FIXTURES
+// ================================================================================================
+
+/// Returns [TransactionInputs] for executing a transaction against the provided account which
+/// consumes the provided notes.
+///
+/// A mock chain is built such that every note is recorded in its own block and carries a valid
+/// inclusion proof against that chain. The reference block is the block following the last block
+/// of the chain.
+///
+/// # Panics
+/// Panics if more than 4 notes are provided, or if the account seed is inconsistent with the
+/// account.
+pub fn fixture_transaction_inputs(
+    account: Account,
+    account_seed: Option<Word>,
+    notes: Vec<Note>,
+) -> TransactionInputs {
+    let (chain_mmr, recorded_notes) = mock_chain_data(notes);
+    let block_header =
+        mock_block_header(4, Some(chain_mmr.peaks().hash_peaks()), None, &[account.clone()]);
+
+    let input_notes = InputNotes::new(recorded_notes).expect("invalid input notes");
+    TransactionInputs::new(account, account_seed, block_header, chain_mmr, input_notes)
+        .expect("invalid transaction inputs")
+}
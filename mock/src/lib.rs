@@ -1,9 +1,10 @@
 use std::{fs::File, io::Read, path::PathBuf};
 
-use miden_lib::transaction::{memory, ToTransactionKernelInputs, TransactionKernel};
+use miden_lib::transaction::{memory, KernelVersion, ToTransactionKernelInputs, TransactionKernel};
 use miden_objects::{
     notes::NoteAssets,
     transaction::{OutputNotes, PreparedTransaction, TransactionInputs, TransactionScript},
+    utils::sync::Arc,
     Felt, StarkField,
 };
 use mock::host::MockHost;
@@ -14,6 +15,7 @@ use vm_processor::{
 
 pub mod builders;
 pub mod constants;
+pub mod fixtures;
 pub mod mock;
 pub mod procedures;
 pub mod utils;
@@ -40,8 +42,8 @@ pub fn run_tx_with_inputs(
     tx: &PreparedTransaction,
     inputs: AdviceInputs,
 ) -> Result<Process<MockHost>, ExecutionError> {
-    let program = tx.program().clone();
-    let (stack_inputs, mut advice_inputs) = tx.get_kernel_inputs();
+    let program = tx.shared_program();
+    let (stack_inputs, mut advice_inputs) = tx.get_kernel_inputs(&KernelVersion::current());
     advice_inputs.extend(inputs);
     let host = MockHost::new(tx.account().into(), advice_inputs);
     let mut process =
@@ -121,5 +123,5 @@ pub fn prepare_transaction(
     };
 
     let program = assembler.compile(code).unwrap();
-    PreparedTransaction::new(program, tx_script, tx_inputs)
+    PreparedTransaction::new(Arc::new(program), tx_script, tx_inputs)
 }
@@ -9,10 +9,11 @@ use miden_objects::{
 
 use crate::constants::{
     generate_account_seed, non_fungible_asset, non_fungible_asset_2, storage_item_0,
-    storage_item_1, AccountSeedType, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
-    ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN_1, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN_2,
-    ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN,
-    FUNGIBLE_ASSET_AMOUNT, FUNGIBLE_FAUCET_INITIAL_BALANCE,
+    storage_item_1, storage_item_2, storage_map_2, AccountSeedType,
+    ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN_1,
+    ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN_2, ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN,
+    ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_ON_CHAIN, FUNGIBLE_ASSET_AMOUNT,
+    FUNGIBLE_FAUCET_INITIAL_BALANCE, STORAGE_INDEX_2,
 };
 
 fn mock_account_vault() -> AssetVault {
@@ -38,14 +39,19 @@ fn mock_account_vault() -> AssetVault {
 }
 
 pub fn mock_account_storage() -> AccountStorage {
-    // create account storage
-    AccountStorage::new(vec![storage_item_0(), storage_item_1()]).unwrap()
+    // create account storage; the contents of the map stored at slot 2 are tracked as well
+    AccountStorage::with_maps(
+        vec![storage_item_0(), storage_item_1(), storage_item_2()],
+        [(STORAGE_INDEX_2, storage_map_2())].into_iter().collect(),
+    )
+    .unwrap()
 }
 
 // Constants that define the indexes of the account procedures of interest
 pub const ACCOUNT_PROCEDURE_INCR_NONCE_PROC_IDX: usize = 2;
 pub const ACCOUNT_PROCEDURE_SET_ITEM_PROC_IDX: usize = 3;
 pub const ACCOUNT_PROCEDURE_SET_CODE_PROC_IDX: usize = 4;
+pub const ACCOUNT_PROCEDURE_SET_MAP_ITEM_PROC_IDX: usize = 8;
 
 pub fn mock_account_code(assembler: &Assembler) -> AccountCode {
     let account_code = "\
@@ -108,6 +114,12 @@ pub fn mock_account_code(assembler: &Assembler) -> AccountCode {
                 push.2.1
                 sub
             end
+
+            # acct proc 8
+            export.set_map_item
+                exec.account::set_map_item
+                # => [MAP_ROOT', V]
+            end
             ";
     let account_module_ast = ModuleAst::parse(account_code).unwrap();
     AccountCode::new(account_module_ast, assembler).unwrap()
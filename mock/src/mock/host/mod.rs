@@ -1,8 +1,8 @@
 use miden_lib::transaction::TransactionEvent;
 use miden_objects::{
-    accounts::{delta::AccountVaultDelta, AccountStub},
+    accounts::{delta::AccountVaultDelta, AccountHeader},
     utils::{collections::BTreeMap, string::ToString},
-    Digest,
+    Digest, Felt, ZERO,
 };
 use vm_processor::{
     crypto::NodeIndex, AdviceExtractor, AdviceInjector, AdviceInputs, AdviceProvider, AdviceSource,
@@ -26,9 +26,10 @@ pub struct MockHost {
 
 impl MockHost {
     /// Returns a new [MockHost] instance with the provided [AdviceInputs].
-    pub fn new(account: AccountStub, advice_inputs: AdviceInputs) -> Self {
+    pub fn new(account: AccountHeader, advice_inputs: AdviceInputs) -> Self {
         let adv_provider: MemAdviceProvider = advice_inputs.into();
-        let proc_index_map = AccountProcedureIndexMap::new(account.code_root(), &adv_provider);
+        let proc_index_map =
+            AccountProcedureIndexMap::new(account.code_commitment(), &adv_provider);
         Self {
             adv_provider,
             acct_procedure_index_map: proc_index_map,
@@ -47,10 +48,11 @@ impl MockHost {
         &mut self,
         process: &S,
     ) -> Result<(), ExecutionError> {
-        let proc_idx = self
+        let (proc_idx, proc_access) = self
             .acct_procedure_index_map
             .get_proc_index(process)
             .map_err(|err| ExecutionError::EventError(err.to_string()))?;
+        self.adv_provider.push_stack(AdviceSource::Value(proc_access))?;
         self.adv_provider.push_stack(AdviceSource::Value(proc_idx.into()))?;
         Ok(())
     }
@@ -92,6 +94,16 @@ impl Host for MockHost {
             AddAssetToAccountVault => Ok(()),
             RemoveAssetFromAccountVault => Ok(()),
             PushAccountProcedureIndex => self.on_push_account_procedure_index(process),
+            SetAccountStorageItem => Ok(()),
+            SetAccountStorageMapItem => Ok(()),
+            CreateNote => Ok(()),
+            BuildRecipient => Ok(()),
+            GetForeignAccountItem => Ok(()),
+            NotesProcessingStart
+            | NoteExecutionStart
+            | NoteExecutionEnd
+            | TxScriptProcessingStart
+            | EpilogueStart => Ok(()),
         }?;
 
         Ok(HostResponse::None)
@@ -2,9 +2,9 @@ use miden_objects::{
     accounts::AccountId,
     assembly::{Assembler, ProgramAst},
     assets::{Asset, FungibleAsset},
-    notes::{Note, NoteScript},
+    notes::{Note, NoteScript, NoteTag},
     utils::collections::Vec,
-    Felt, Word, ZERO,
+    Felt, Word,
 };
 
 use crate::{
@@ -57,7 +57,7 @@ pub fn mock_notes(
         &[fungible_asset_1],
         SERIAL_NUM_4,
         sender,
-        ZERO,
+        NoteTag::default(),
     )
     .unwrap();
 
@@ -68,14 +68,20 @@ pub fn mock_notes(
         &[fungible_asset_2],
         SERIAL_NUM_5,
         sender,
-        ZERO,
+        NoteTag::default(),
     )
     .unwrap();
 
     const SERIAL_NUM_6: Word = [Felt::new(21), Felt::new(22), Felt::new(23), Felt::new(24)];
-    let created_note_3 =
-        Note::new(note_script, &[Felt::new(2)], &[fungible_asset_3], SERIAL_NUM_6, sender, ZERO)
-            .unwrap();
+    let created_note_3 = Note::new(
+        note_script,
+        &[Felt::new(2)],
+        &[fungible_asset_3],
+        SERIAL_NUM_6,
+        sender,
+        NoteTag::default(),
+    )
+    .unwrap();
 
     let created_notes = vec![created_note_1, created_note_2, created_note_3];
 
@@ -135,9 +141,15 @@ pub fn mock_notes(
 
     // Consumed Notes
     const SERIAL_NUM_1: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
-    let consumed_note_1 =
-        Note::new(note_1_script, &[Felt::new(1)], &[fungible_asset_1], SERIAL_NUM_1, sender, ZERO)
-            .unwrap();
+    let consumed_note_1 = Note::new(
+        note_1_script,
+        &[Felt::new(1)],
+        &[fungible_asset_1],
+        SERIAL_NUM_1,
+        sender,
+        NoteTag::default(),
+    )
+    .unwrap();
 
     const SERIAL_NUM_2: Word = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
     let consumed_note_2 = Note::new(
@@ -146,7 +158,7 @@ pub fn mock_notes(
         &[fungible_asset_2, fungible_asset_3],
         SERIAL_NUM_2,
         sender,
-        ZERO,
+        NoteTag::default(),
     )
     .unwrap();
 
@@ -160,7 +172,7 @@ pub fn mock_notes(
         &[fungible_asset_2, fungible_asset_3],
         SERIAL_NUM_3,
         sender,
-        ZERO,
+        NoteTag::default(),
     )
     .unwrap();
 
@@ -174,7 +186,7 @@ pub fn mock_notes(
         &[non_fungible_asset_2(ACCOUNT_ID_NON_FUNGIBLE_FAUCET_ON_CHAIN)],
         SERIAL_NUM_7,
         sender,
-        ZERO,
+        NoteTag::default(),
     )
     .unwrap();
 
@@ -220,7 +232,7 @@ pub fn mock_notes(
         ],
         SERIAL_NUM_8,
         sender,
-        ZERO,
+        NoteTag::default(),
     )
     .unwrap();
 
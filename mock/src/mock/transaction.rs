@@ -3,7 +3,7 @@ use miden_objects::{
     notes::Note,
     transaction::{
         ChainMmr, ExecutedTransaction, InputNote, InputNotes, OutputNote, OutputNotes,
-        TransactionInputs, TransactionOutputs,
+        TransactionInputs, TransactionMeasurements, TransactionOutputs,
     },
     utils::collections::Vec,
     BlockHeader, Felt, FieldElement,
@@ -145,15 +145,27 @@ pub fn mock_executed_tx(asset_preservation: AssetPreservationStatus) -> Executed
     let tx_outputs = TransactionOutputs {
         account: final_account.into(),
         output_notes: OutputNotes::new(output_notes).unwrap(),
+        expiration_block_num: None,
+        fee: None,
     };
 
     // dummy components
-    let program = build_dummy_tx_program();
+    let kernel_hash = build_dummy_tx_program().hash();
     let account_delta = AccountDelta::default();
     let advice_witness = AdviceInputs::default();
 
     // Executed Transaction
-    ExecutedTransaction::new(program, tx_inputs, tx_outputs, account_delta, None, advice_witness)
+    ExecutedTransaction::new(
+        kernel_hash,
+        tx_inputs,
+        tx_outputs,
+        account_delta,
+        None,
+        None,
+        Word::default(),
+        advice_witness,
+        TransactionMeasurements::default(),
+    )
 }
 
 // HELPER FUNCTIONS
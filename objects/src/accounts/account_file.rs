@@ -0,0 +1,248 @@
+use super::{
+    validate_account_seed, Account, AccountError, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Digest, Felt, Serializable, ToString, Vec, Word,
+};
+use crate::utils::format;
+
+// AUTH DATA
+// ================================================================================================
+
+/// Secret key material used to authenticate transactions against an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthData {
+    /// An RPO Falcon512 key pair, stored as its public key and the serialized key pair (in the
+    /// format produced by `KeyPair::to_bytes()`).
+    RpoFalcon512 { pub_key: Word, key_pair: Vec<u8> },
+}
+
+impl AuthData {
+    /// Returns the public key associated with this auth data.
+    pub fn pub_key(&self) -> Word {
+        match self {
+            Self::RpoFalcon512 { pub_key, .. } => *pub_key,
+        }
+    }
+
+    /// Returns the advice map entry from which the transaction host can produce signatures for
+    /// the account, i.e., pub_key |-> key_pair.
+    pub fn to_advice_map_entry(&self) -> ([u8; 32], Vec<Felt>) {
+        match self {
+            Self::RpoFalcon512 { pub_key, key_pair } => (
+                Digest::from(*pub_key).as_bytes(),
+                key_pair.iter().map(|byte| Felt::from(*byte)).collect(),
+            ),
+        }
+    }
+}
+
+impl Serializable for AuthData {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::RpoFalcon512 { pub_key, key_pair } => {
+                target.write_u8(0);
+                pub_key.write_into(target);
+                target.write_u64(key_pair.len() as u64);
+                target.write_bytes(key_pair);
+            },
+        }
+    }
+}
+
+impl Deserializable for AuthData {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => {
+                let pub_key = Word::read_from(source)?;
+                let len = source.read_u64()? as usize;
+                let key_pair = source.read_vec(len)?;
+                Ok(Self::RpoFalcon512 { pub_key, key_pair })
+            },
+            tag => Err(DeserializationError::InvalidValue(format!("invalid auth data tag {tag}"))),
+        }
+    }
+}
+
+// ACCOUNT FILE
+// ================================================================================================
+
+/// A container of the full state of an account, which can be used to export an account from one
+/// client and import it into another (e.g., saved to a file), or to bootstrap test environments.
+///
+/// An account file contains:
+/// - The full state of the account.
+/// - The seed of the account, if the account is new (i.e., it was not yet recorded on chain). The
+///   seed is required to execute the first transaction against the account.
+/// - Optionally, the secret key material used to authenticate transactions against the account.
+///
+/// The serialized form of an account file is prefixed with its version, such that files written
+/// by older versions of the format can be recognized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountFile {
+    account: Account,
+    account_seed: Option<Word>,
+    auth: Option<AuthData>,
+}
+
+impl AccountFile {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The current version of the account file format.
+    pub const VERSION: u8 = 1;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [AccountFile] for the specified account.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The account is new and the seed is not provided, or the seed is not valid for the account.
+    /// - The account is not new and the seed is provided.
+    pub fn new(
+        account: Account,
+        account_seed: Option<Word>,
+        auth: Option<AuthData>,
+    ) -> Result<Self, AccountError> {
+        match (account.is_new(), account_seed) {
+            (true, Some(seed)) => validate_account_seed(&account, seed)?,
+            (true, None) => return Err(AccountError::AccountSeedMissing(account.id())),
+            (false, Some(_)) => return Err(AccountError::AccountSeedNotRequired(account.id())),
+            (false, None) => (),
+        }
+
+        Ok(Self { account, account_seed, auth })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the account contained in this file.
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    /// Returns the seed of the account, if the account is new.
+    pub fn account_seed(&self) -> Option<Word> {
+        self.account_seed
+    }
+
+    /// Returns the secret key material of the account, if this file contains one.
+    pub fn auth(&self) -> Option<&AuthData> {
+        self.auth.as_ref()
+    }
+
+    /// Consumes this file and returns its account, seed, and secret key material.
+    pub fn into_parts(self) -> (Account, Option<Word>, Option<AuthData>) {
+        (self.account, self.account_seed, self.auth)
+    }
+
+    // FILE I/O
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the serialized account file to the specified path.
+    #[cfg(feature = "std")]
+    pub fn write(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    /// Reads an account file from the specified path.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be read, or if its contents are not a valid
+    /// serialized account file.
+    #[cfg(feature = "std")]
+    pub fn read(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::read_from_bytes(&bytes)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for AccountFile {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(Self::VERSION);
+        self.account.write_into(target);
+        self.account_seed.write_into(target);
+        self.auth.write_into(target);
+    }
+}
+
+impl Deserializable for AccountFile {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        if version != Self::VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported account file version {version}"
+            )));
+        }
+
+        let account = Account::read_from(source)?;
+        let account_seed = <Option<Word>>::read_from(source)?;
+        let auth = <Option<AuthData>>::read_from(source)?;
+
+        Self::new(account, account_seed, auth)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountFile, AuthData, Deserializable, Serializable};
+    use crate::{
+        accounts::{
+            Account, AccountCode, AccountError, AccountId, AccountStorage,
+            ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        },
+        assembly::{Assembler, ModuleAst},
+        assets::AssetVault,
+        ONE, ZERO,
+    };
+
+    fn build_account(nonce: crate::Felt) -> Account {
+        let id = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let module = ModuleAst::parse("export.foo push.1 push.2 mul end").unwrap();
+        let code = AccountCode::new(module, &Assembler::default()).unwrap();
+        let storage = AccountStorage::new(Vec::new()).unwrap();
+
+        Account::new(id, AssetVault::default(), storage, code, nonce)
+    }
+
+    #[test]
+    fn account_file_serialization() {
+        let auth = AuthData::RpoFalcon512 {
+            pub_key: [ONE, ZERO, ONE, ZERO],
+            key_pair: vec![1, 2, 3, 4],
+        };
+        let file = AccountFile::new(build_account(ONE), None, Some(auth)).unwrap();
+
+        let bytes = file.to_bytes();
+        assert_eq!(bytes[0], AccountFile::VERSION);
+        assert_eq!(file, AccountFile::read_from_bytes(&bytes).unwrap());
+
+        // files of unknown versions are rejected
+        let mut bytes = bytes;
+        bytes[0] = AccountFile::VERSION + 1;
+        assert!(AccountFile::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn account_file_seed_requirements() {
+        let new_account = build_account(ZERO);
+        assert_eq!(
+            AccountFile::new(new_account.clone(), None, None),
+            Err(AccountError::AccountSeedMissing(new_account.id()))
+        );
+
+        let account = build_account(ONE);
+        assert_eq!(
+            AccountFile::new(account.clone(), Some([ONE; 4]), None),
+            Err(AccountError::AccountSeedNotRequired(account.id()))
+        );
+    }
+}
@@ -22,6 +22,27 @@ pub enum AccountType {
     RegularAccountUpdatableCode,
 }
 
+/// Specifies the broad kind of an account, i.e., whether the account can issue assets.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountIdKind {
+    /// A regular account, which can hold assets but cannot issue them.
+    Regular,
+    /// A faucet account, which can issue either fungible or non-fungible assets.
+    Faucet,
+}
+
+/// Specifies where the state of an account is stored.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountStorageMode {
+    /// The full state of the account is stored on-chain.
+    Public,
+    /// Only the hash of the account is stored on-chain, which serves as a commitment to the
+    /// account state.
+    Private,
+}
+
 /// Unique identifier of an account.
 ///
 /// Account ID consists of 1 field element (~64 bits). This field element uniquely identifies a
@@ -131,6 +152,24 @@ impl AccountId {
         }
     }
 
+    /// Returns the kind of this account ID.
+    pub fn kind(&self) -> AccountIdKind {
+        if self.is_faucet() {
+            AccountIdKind::Faucet
+        } else {
+            AccountIdKind::Regular
+        }
+    }
+
+    /// Returns the storage mode of this account ID.
+    pub fn storage_mode(&self) -> AccountStorageMode {
+        if self.0.as_int() >> 61 & Self::ON_CHAIN_ACCOUNT_SELECTOR == 1 {
+            AccountStorageMode::Public
+        } else {
+            AccountStorageMode::Private
+        }
+    }
+
     /// Returns true if an account with this ID is a faucet (can issue assets).
     pub fn is_faucet(&self) -> bool {
         matches!(
@@ -139,6 +178,16 @@ impl AccountId {
         )
     }
 
+    /// Returns true if an account with this ID is a fungible asset faucet.
+    pub fn is_fungible_faucet(&self) -> bool {
+        self.account_type() == AccountType::FungibleFaucet
+    }
+
+    /// Returns true if an account with this ID is a non-fungible asset faucet.
+    pub fn is_non_fungible_faucet(&self) -> bool {
+        self.account_type() == AccountType::NonFungibleFaucet
+    }
+
     /// Returns true if an account with this ID is a regular account.
     pub fn is_regular_account(&self) -> bool {
         matches!(
@@ -147,9 +196,22 @@ impl AccountId {
         )
     }
 
+    /// Returns true if the code of an account with this ID can be updated.
+    ///
+    /// Only regular accounts can have updatable code; the code of faucets is always immutable.
+    pub fn is_updatable_code(&self) -> bool {
+        self.account_type() == AccountType::RegularAccountUpdatableCode
+    }
+
     /// Returns true if an account with this ID is an on-chain account.
     pub fn is_on_chain(&self) -> bool {
-        self.0.as_int() >> 61 & Self::ON_CHAIN_ACCOUNT_SELECTOR == 1
+        self.storage_mode() == AccountStorageMode::Public
+    }
+
+    /// Returns true if the full state of an account with this ID is public (i.e., stored
+    /// on-chain).
+    pub fn is_public(&self) -> bool {
+        self.is_on_chain()
     }
 
     /// Finds and returns a seed suitable for creating an account ID for the specified account type
@@ -356,7 +418,7 @@ mod tests {
             ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
             ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
         },
-        AccountId, AccountType,
+        AccountId, AccountIdKind, AccountStorageMode, AccountType,
     };
 
     #[test]
@@ -393,4 +455,36 @@ mod tests {
         assert_eq!(account_id.account_type(), AccountType::NonFungibleFaucet);
         assert!(!account_id.is_on_chain());
     }
+
+    #[test]
+    fn test_account_kind_helpers() {
+        let account_id = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN)
+            .expect("Valid account ID");
+        assert_eq!(account_id.kind(), AccountIdKind::Regular);
+        assert_eq!(account_id.storage_mode(), AccountStorageMode::Public);
+        assert!(account_id.is_public());
+        assert!(!account_id.is_updatable_code());
+        assert!(!account_id.is_fungible_faucet());
+
+        let account_id = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN)
+            .expect("Valid account ID");
+        assert_eq!(account_id.kind(), AccountIdKind::Regular);
+        assert_eq!(account_id.storage_mode(), AccountStorageMode::Private);
+        assert!(!account_id.is_public());
+        assert!(account_id.is_updatable_code());
+
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).expect("Valid account ID");
+        assert_eq!(account_id.kind(), AccountIdKind::Faucet);
+        assert!(account_id.is_fungible_faucet());
+        assert!(!account_id.is_non_fungible_faucet());
+        assert!(!account_id.is_updatable_code());
+
+        let account_id = AccountId::try_from(ACCOUNT_ID_NON_FUNGIBLE_FAUCET_OFF_CHAIN)
+            .expect("Valid account ID");
+        assert_eq!(account_id.kind(), AccountIdKind::Faucet);
+        assert_eq!(account_id.storage_mode(), AccountStorageMode::Private);
+        assert!(account_id.is_non_fungible_faucet());
+        assert!(!account_id.is_fungible_faucet());
+    }
 }
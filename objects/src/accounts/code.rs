@@ -1,12 +1,17 @@
-use core::cell::OnceCell;
-
 use assembly::ast::AstSerdeOptions;
 
 use super::{
     AccountError, Assembler, AssemblyContext, ByteReader, ByteWriter, Deserializable,
-    DeserializationError, Digest, ModuleAst, Serializable, Vec,
+    DeserializationError, Digest, Felt, ModuleAst, Serializable, Vec, Word, ZERO,
+};
+use crate::{
+    crypto::merkle::SimpleSmt,
+    utils::{
+        string::ToString,
+        sync::{Arc, OnceLock},
+        vec,
+    },
 };
-use crate::crypto::merkle::SimpleSmt;
 
 // CONSTANTS
 // ================================================================================================
@@ -20,13 +25,24 @@ const MODULE_SERDE_OPTIONS: AstSerdeOptions = AstSerdeOptions::new(true);
 /// A public interface of an account.
 ///
 /// Account's public interface consists of a set of account procedures, each procedure being a Miden
-/// VM program. Thus, MAST root of each procedure commits to the underlying program. We commit to
-/// the entire account interface by building a simple Merkle tree out of all procedure MAST roots.
+/// VM program. Thus, MAST root of each procedure commits to the underlying program. Every
+/// procedure is also assigned a [ProcedureAccess] level restricting the scripts which may invoke
+/// it; by default, all procedures can be invoked by both note scripts and transaction scripts.
+///
+/// We commit to the entire account interface by building a simple Merkle tree out of all
+/// procedures, where the leaf of a procedure is hash(PROC_ROOT, [access, 0, 0, 0]). The procedure
+/// tree is stored as a tree of depth [AccountCode::PROCEDURE_LEAF_DEPTH] in which every procedure
+/// occupies two leaves (its MAST root and its access level), so that the procedures and their
+/// access levels can be read from the tree by the transaction host.
+///
+/// The module AST is shared via an [Arc], and thus cloning account code (e.g., as a part of
+/// cloning an [Account](super::Account)) does not copy the underlying module.
 #[derive(Debug, Clone)]
 pub struct AccountCode {
-    module: ModuleAst,
+    module: Arc<ModuleAst>,
     procedures: Vec<Digest>,
-    procedure_tree: OnceCell<SimpleSmt>,
+    procedure_access: Vec<ProcedureAccess>,
+    procedure_tree: OnceLock<SimpleSmt>,
 }
 
 impl AccountCode {
@@ -36,6 +52,13 @@ impl AccountCode {
     /// The depth of the Merkle tree that is used to commit to the account's public interface.
     pub const PROCEDURE_TREE_DEPTH: u8 = 8;
 
+    /// The depth of the leaves of the procedure tree holding the MAST roots and the access levels
+    /// of the procedures.
+    ///
+    /// This is equal to `PROCEDURE_TREE_DEPTH + 1`: the leaf of a procedure in the procedure tree
+    /// is the parent of its MAST root and its access level.
+    pub const PROCEDURE_LEAF_DEPTH: u8 = Self::PROCEDURE_TREE_DEPTH + 1;
+
     /// The maximum number of account interface procedures.
     pub const MAX_NUM_PROCEDURES: usize = 2_usize.pow(Self::PROCEDURE_TREE_DEPTH as u32);
 
@@ -43,6 +66,9 @@ impl AccountCode {
     // --------------------------------------------------------------------------------------------
     /// Returns a new definition of an account's interface compiled from the specified source code.
     ///
+    /// All procedures of the returned account code are [ProcedureAccess::Public]; access levels
+    /// can be restricted via [AccountCode::with_procedure_access()].
+    ///
     /// # Errors
     /// Returns an error if:
     /// - Compilation of the provided module fails.
@@ -65,30 +91,60 @@ impl AccountCode {
         }
 
         Ok(Self {
-            procedure_tree: OnceCell::new(),
-            module,
+            procedure_tree: OnceLock::new(),
+            module: Arc::new(module),
+            procedure_access: vec![ProcedureAccess::default(); procedures.len()],
             procedures,
         })
     }
 
     /// Returns a new definition of an account's interface instantiated from the provided
-    /// module and list of procedure digests.
+    /// module, list of procedure digests, and the access levels of these procedures.
     ///
     /// **Note**: this function assumes that the list of provided procedure digests results from
     /// the compilation of the provided module, but this is not checked.
     ///
     /// # Panics
-    /// Panics if the number of procedures is smaller than 1 or greater than 256.
-    pub fn from_parts(module: ModuleAst, procedures: Vec<Digest>) -> Self {
+    /// Panics if:
+    /// - The number of procedures is smaller than 1 or greater than 256.
+    /// - The number of access levels is not equal to the number of procedures.
+    pub fn from_parts(
+        module: ModuleAst,
+        procedures: Vec<Digest>,
+        procedure_access: Vec<ProcedureAccess>,
+    ) -> Self {
         assert!(!procedures.is_empty(), "no account procedures");
         assert!(procedures.len() <= Self::MAX_NUM_PROCEDURES, "too many account procedures");
+        assert_eq!(procedures.len(), procedure_access.len(), "invalid number of access levels");
         Self {
-            procedure_tree: OnceCell::new(),
-            module,
+            procedure_tree: OnceLock::new(),
+            module: Arc::new(module),
             procedures,
+            procedure_access,
         }
     }
 
+    /// Returns this account code with the access level of the procedure with the specified root
+    /// set to `access`.
+    ///
+    /// Since access levels are committed to by the procedure tree, this changes the root of the
+    /// account code unless the procedure already had the specified access level.
+    ///
+    /// # Errors
+    /// Returns an error if a procedure with the specified root is not defined for this account.
+    pub fn with_procedure_access(
+        mut self,
+        root: Digest,
+        access: ProcedureAccess,
+    ) -> Result<Self, AccountError> {
+        let index = self
+            .get_procedure_index_by_root(root)
+            .ok_or(AccountError::AccountCodeProcedureNotFound(root))?;
+        self.procedure_access[index] = access;
+        self.procedure_tree = OnceLock::new();
+        Ok(self)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -110,7 +166,8 @@ impl AccountCode {
     /// Returns a reference to the procedure tree.
     pub fn procedure_tree(&self) -> &SimpleSmt {
         // build procedure tree only when requested
-        self.procedure_tree.get_or_init(|| build_procedure_tree(&self.procedures))
+        self.procedure_tree
+            .get_or_init(|| build_procedure_tree(&self.procedures, &self.procedure_access))
     }
 
     /// Returns the number of public interface procedures defined for this account.
@@ -136,6 +193,18 @@ impl AccountCode {
     pub fn get_procedure_index_by_root(&self, root: Digest) -> Option<usize> {
         self.procedures.iter().position(|r| r == &root)
     }
+
+    /// Returns the access level of the procedure with the specified root or None if such
+    /// procedure is not defined for this account.
+    pub fn get_procedure_access(&self, root: Digest) -> Option<ProcedureAccess> {
+        self.get_procedure_index_by_root(root).map(|index| self.procedure_access[index])
+    }
+
+    /// Returns an iterator over the roots of the procedures defined for this account together
+    /// with their access levels.
+    pub fn procedure_access(&self) -> impl Iterator<Item = (Digest, ProcedureAccess)> + '_ {
+        self.procedures.iter().copied().zip(self.procedure_access.iter().copied())
+    }
 }
 
 // EQUALITY
@@ -144,7 +213,9 @@ impl AccountCode {
 impl PartialEq for AccountCode {
     fn eq(&self, other: &Self) -> bool {
         // TODO: consider checking equality based only on the set of procedures
-        self.module == other.module && self.procedures == other.procedures
+        self.module == other.module
+            && self.procedures == other.procedures
+            && self.procedure_access == other.procedure_access
     }
 }
 
@@ -160,7 +231,10 @@ impl Serializable for AccountCode {
         // since the number of procedures is guaranteed to be between 1 and 256, we can store the
         // number as a single byte - but we do have to subtract 1 to store 256 as 255.
         target.write_u8((self.procedures.len() - 1) as u8);
-        self.procedures.write_into(target);
+        for (root, access) in self.procedure_access() {
+            root.write_into(target);
+            access.write_into(target);
+        }
     }
 }
 
@@ -169,29 +243,100 @@ impl Deserializable for AccountCode {
         let mut module = ModuleAst::read_from(source, MODULE_SERDE_OPTIONS)?;
         module.load_source_locations(source)?;
         let num_procedures = (source.read_u8()? as usize) + 1;
-        let procedures = Digest::read_batch_from(source, num_procedures)?;
+        let mut procedures = Vec::with_capacity(num_procedures);
+        let mut procedure_access = Vec::with_capacity(num_procedures);
+        for _ in 0..num_procedures {
+            procedures.push(Digest::read_from(source)?);
+            procedure_access.push(ProcedureAccess::read_from(source)?);
+        }
+
+        Ok(Self::from_parts(module, procedures, procedure_access))
+    }
+}
+
+// PROCEDURE ACCESS
+// ================================================================================================
+
+/// The access level of an account procedure, restricting the scripts on behalf of which it may
+/// access the account state.
+///
+/// Access levels are committed to by the account code root, and are enforced by the transaction
+/// kernel when an account procedure authenticates itself to the kernel, i.e., when it invokes a
+/// kernel procedure which can be invoked only from the account context (e.g., to modify the
+/// account vault or storage, or to increment the account nonce). Procedures which do not invoke
+/// such kernel procedures cannot modify the state of the account, and are therefore never
+/// restricted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ProcedureAccess {
+    /// The procedure can access the account state when invoked by note scripts and by transaction
+    /// scripts.
+    #[default]
+    Public = 0,
+    /// The procedure can access the account state when invoked by transaction scripts only.
+    TxScriptOnly = 1,
+    /// The procedure cannot access the account state when invoked by note scripts or transaction
+    /// scripts; it can do so only when executed as a part of other account procedures.
+    Internal = 2,
+}
+
+impl From<ProcedureAccess> for Word {
+    fn from(access: ProcedureAccess) -> Self {
+        [Felt::from(access as u8), ZERO, ZERO, ZERO]
+    }
+}
+
+impl TryFrom<u8> for ProcedureAccess {
+    type Error = AccountError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Public),
+            1 => Ok(Self::TxScriptOnly),
+            2 => Ok(Self::Internal),
+            _ => Err(AccountError::InvalidProcedureAccess(value)),
+        }
+    }
+}
 
-        Ok(Self::from_parts(module, procedures))
+impl Serializable for ProcedureAccess {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for ProcedureAccess {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::try_from(source.read_u8()?)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
     }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
-fn build_procedure_tree(procedures: &[Digest]) -> SimpleSmt {
+fn build_procedure_tree(procedures: &[Digest], procedure_access: &[ProcedureAccess]) -> SimpleSmt {
     // order the procedure digests to achieve a reproducible tree
     let procedures = {
-        let mut procedures = procedures.to_vec();
-        procedures.sort_by_key(|a| a.as_bytes());
+        let mut procedures = procedures
+            .iter()
+            .copied()
+            .zip(procedure_access.iter().copied())
+            .collect::<Vec<_>>();
+        procedures.sort_by_key(|(root, _)| root.as_bytes());
         procedures
     };
 
+    // every procedure occupies two leaves: its MAST root, and its access level
     SimpleSmt::with_leaves(
-        AccountCode::PROCEDURE_TREE_DEPTH,
+        AccountCode::PROCEDURE_LEAF_DEPTH,
         procedures
             .iter()
             .enumerate()
-            .map(|(idx, p)| (idx as u64, p.into()))
+            .flat_map(|(idx, (root, access))| {
+                let index = 2 * idx as u64;
+                [(index, Word::from(*root)), (index + 1, Word::from(*access))]
+            })
             .collect::<Vec<_>>(),
     )
     .expect("failed to build procedure tree")
@@ -202,7 +347,10 @@ fn build_procedure_tree(procedures: &[Digest]) -> SimpleSmt {
 
 #[cfg(test)]
 mod tests {
-    use super::{AccountCode, Assembler, Deserializable, ModuleAst, Serializable};
+    use super::{
+        AccountCode, Assembler, Deserializable, ModuleAst, ProcedureAccess, Serializable, Word,
+    };
+    use crate::{crypto::merkle::NodeIndex, AccountError, Digest, Hasher};
 
     #[test]
     fn serialize_code() {
@@ -226,4 +374,58 @@ mod tests {
         let code2 = AccountCode::read_from_bytes(&bytes).unwrap();
         assert_eq!(code1, code2)
     }
+
+    #[test]
+    fn procedure_access() {
+        let source = "
+            export.foo
+                push.1 push.2 mul
+            end
+
+            export.bar
+                push.1 push.2 add
+            end
+        ";
+
+        let module = ModuleAst::parse(source).unwrap();
+        let code = AccountCode::new(module, &Assembler::default()).unwrap();
+        let foo = code.get_procedure_by_index(0);
+        let bar = code.get_procedure_by_index(1);
+        assert_eq!(code.get_procedure_access(foo), Some(ProcedureAccess::Public));
+
+        // access levels are committed to by the account code root
+        let restricted =
+            code.clone().with_procedure_access(bar, ProcedureAccess::Internal).unwrap();
+        assert_eq!(restricted.get_procedure_access(foo), Some(ProcedureAccess::Public));
+        assert_eq!(restricted.get_procedure_access(bar), Some(ProcedureAccess::Internal));
+        assert_ne!(restricted.root(), code.root());
+        assert_ne!(restricted, code);
+
+        // the leaf of a procedure is the hash of its root and its access level
+        let index = restricted.procedure_tree().leaves().find(|(_, leaf)| *leaf == *bar).unwrap().0;
+        let leaf = restricted
+            .procedure_tree()
+            .get_node(NodeIndex::new(AccountCode::PROCEDURE_TREE_DEPTH, index / 2).unwrap())
+            .unwrap();
+        assert_eq!(leaf, Hasher::merge(&[bar, Word::from(ProcedureAccess::Internal).into()]));
+
+        let bytes = restricted.to_bytes();
+        assert_eq!(restricted, AccountCode::read_from_bytes(&bytes).unwrap());
+        let from_parts = AccountCode::from_parts(
+            restricted.module().clone(),
+            restricted.procedures().to_vec(),
+            restricted.procedure_access().map(|(_, access)| access).collect(),
+        );
+        assert_eq!(from_parts.root(), restricted.root());
+
+        // restoring the access level restores the commitment
+        let public = restricted.with_procedure_access(bar, ProcedureAccess::Public).unwrap();
+        assert_eq!(public.root(), code.root());
+        assert_eq!(public, code);
+
+        assert_eq!(
+            code.with_procedure_access(Digest::default(), ProcedureAccess::TxScriptOnly),
+            Err(AccountError::AccountCodeProcedureNotFound(Digest::default()))
+        );
+    }
 }
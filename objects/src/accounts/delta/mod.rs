@@ -5,7 +5,7 @@ use super::{
 use crate::{assets::Asset, AccountDeltaError};
 
 mod storage;
-pub use storage::AccountStorageDelta;
+pub use storage::{AccountStorageDelta, StorageMapDelta};
 
 mod vault;
 pub use vault::AccountVaultDelta;
@@ -29,34 +29,80 @@ pub struct AccountDelta {
 }
 
 impl AccountDelta {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The maximum number of storage entries (i.e., cleared and updated items) in a single delta.
+    ///
+    /// This matches the number of mutable storage slots, as each slot can be referenced by a delta
+    /// at most once.
+    pub const MAX_STORAGE_ENTRIES: usize = 255;
+
+    /// The maximum number of vault entries (i.e., added and removed assets) in a single delta.
+    pub const MAX_VAULT_ENTRIES: usize = u16::MAX as usize;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns new [AccountDelta] instantiated from the provided components.
     ///
     /// # Errors
-    /// Returns an error if:
-    /// - Storage or vault deltas are invalid.
-    /// - Storage and vault deltas are empty, and the nonce was updated.
-    /// - Storage or vault deltas are not empty, but nonce was not updated.
+    /// Returns an error if the delta is invalid; see [AccountDelta::validate()].
     pub fn new(
         storage: AccountStorageDelta,
         vault: AccountVaultDelta,
         nonce: Option<Felt>,
     ) -> Result<Self, AccountDeltaError> {
+        let delta = Self { storage, vault, nonce };
+        delta.validate()?;
+
+        Ok(delta)
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether this account delta is valid, and can thus be accepted by batch and block
+    /// producers.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Storage or vault deltas are invalid.
+    /// - The storage delta contains more than [AccountDelta::MAX_STORAGE_ENTRIES] entries.
+    /// - The vault delta contains more than [AccountDelta::MAX_VAULT_ENTRIES] entries.
+    /// - Storage and vault deltas are empty, and the nonce was updated.
+    /// - Storage or vault deltas are not empty, but nonce was not updated.
+    pub fn validate(&self) -> Result<(), AccountDeltaError> {
         // make sure storage and vault deltas are valid
-        storage.validate()?;
-        vault.validate()?;
+        self.storage.validate()?;
+        self.vault.validate()?;
 
-        // nonce must be updated if and only if either account storage or vault were updated
-        validate_nonce(nonce, &storage, &vault)?;
+        // make sure the delta is not larger than what can be included into a block
+        let num_storage_entries = self.storage.num_entries();
+        if num_storage_entries > Self::MAX_STORAGE_ENTRIES {
+            return Err(AccountDeltaError::TooManyStorageEntries {
+                actual: num_storage_entries,
+                max: Self::MAX_STORAGE_ENTRIES,
+            });
+        }
 
-        Ok(Self { storage, vault, nonce })
+        let num_vault_entries = self.vault.num_entries();
+        if num_vault_entries > Self::MAX_VAULT_ENTRIES {
+            return Err(AccountDeltaError::TooManyVaultEntries {
+                actual: num_vault_entries,
+                max: Self::MAX_VAULT_ENTRIES,
+            });
+        }
+
+        // nonce must be updated if and only if either account storage or vault were updated
+        validate_nonce(self.nonce, &self.storage, &self.vault)
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns true if this account delta does not contain any updates.
+    /// Returns true if this account delta does not contain any storage or vault updates.
+    ///
+    /// Note that an empty delta may still update the nonce of the account.
     pub fn is_empty(&self) -> bool {
         self.storage.is_empty() && self.vault.is_empty()
     }
@@ -76,6 +122,31 @@ impl AccountDelta {
         self.nonce
     }
 
+    // MERGING
+    // --------------------------------------------------------------------------------------------
+
+    /// Merges this delta with the specified delta, which is assumed to describe changes applied
+    /// to the account after the changes described by this delta (e.g., by the next transaction
+    /// executed against the account).
+    ///
+    /// The nonce of the merged delta is the latest updated nonce. The nonce is kept even if the
+    /// storage and vault changes of both deltas cancel each other out, as the nonce of the account
+    /// was still incremented.
+    ///
+    /// # Errors
+    /// Returns an error if the storage or vault deltas cannot be merged, or if the merged delta is
+    /// invalid.
+    pub fn merge(self, other: Self) -> Result<Self, AccountDeltaError> {
+        let storage = self.storage.merge(other.storage)?;
+        let vault = self.vault.merge(other.vault)?;
+        let nonce = other.nonce.or(self.nonce);
+
+        Self::new(storage, vault, nonce)
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
     /// Converts this storage delta into individual delta components.
     pub fn into_parts(self) -> (AccountStorageDelta, AccountVaultDelta, Option<Felt>) {
         (self.storage, self.vault, self.nonce)
@@ -96,10 +167,8 @@ impl Deserializable for AccountDelta {
         let vault = AccountVaultDelta::read_from(source)?;
         let nonce = <Option<Felt>>::read_from(source)?;
 
-        validate_nonce(nonce, &storage, &vault)
-            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
-
-        Ok(Self { storage, vault, nonce })
+        Self::new(storage, vault, nonce)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
     }
 }
 
@@ -108,10 +177,12 @@ impl Deserializable for AccountDelta {
 
 /// Checks if the nonce was updated correctly given the provided storage and vault deltas.
 ///
+/// The nonce may be updated without any storage or vault updates (e.g., when a transaction only
+/// increments the nonce of the account).
+///
 /// # Errors
-/// Returns an error if:
-/// - Storage or vault were updated, but the nonce was either not updated or set to 0.
-/// - Storage and vault were not updated, but the nonce was updated.
+/// Returns an error if storage or vault were updated, but the nonce was either not updated or set
+/// to 0.
 fn validate_nonce(
     nonce: Option<Felt>,
     storage: &AccountStorageDelta,
@@ -132,10 +203,6 @@ fn validate_nonce(
                 ))
             },
         }
-    } else if nonce.is_some() {
-        return Err(AccountDeltaError::InconsistentNonceUpdate(
-            "nonce updated for empty delta".to_string(),
-        ));
     }
 
     Ok(())
@@ -146,8 +213,12 @@ fn validate_nonce(
 
 #[cfg(test)]
 mod tests {
-    use super::{AccountDelta, AccountStorageDelta, AccountVaultDelta};
-    use crate::{ONE, ZERO};
+    use super::{AccountDelta, AccountDeltaError, AccountStorageDelta, AccountVaultDelta};
+    use crate::{
+        accounts::{AccountId, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN},
+        assets::FungibleAsset,
+        Felt, ONE, ZERO,
+    };
 
     #[test]
     fn account_delta_nonce_validation() {
@@ -155,24 +226,88 @@ mod tests {
         let storage_delta = AccountStorageDelta {
             cleared_items: vec![],
             updated_items: vec![],
+            ..Default::default()
         };
 
-        let vault_delta = AccountVaultDelta {
-            added_assets: vec![],
-            removed_assets: vec![],
-        };
+        let vault_delta = AccountVaultDelta::default();
 
         assert!(AccountDelta::new(storage_delta.clone(), vault_delta.clone(), None).is_ok());
-        assert!(AccountDelta::new(storage_delta.clone(), vault_delta.clone(), Some(ONE)).is_err());
+        assert!(AccountDelta::new(storage_delta.clone(), vault_delta.clone(), Some(ONE)).is_ok());
 
         // non-empty delta
         let storage_delta = AccountStorageDelta {
             cleared_items: vec![1],
             updated_items: vec![],
+            ..Default::default()
         };
 
         assert!(AccountDelta::new(storage_delta.clone(), vault_delta.clone(), None).is_err());
         assert!(AccountDelta::new(storage_delta.clone(), vault_delta.clone(), Some(ZERO)).is_err());
         assert!(AccountDelta::new(storage_delta.clone(), vault_delta.clone(), Some(ONE)).is_ok());
     }
+
+    #[test]
+    fn account_delta_merge_keeps_nonce() {
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let asset = FungibleAsset::new(faucet_id, 10).unwrap();
+
+        // the second delta removes the asset added by the first delta
+        let first = AccountDelta::new(
+            AccountStorageDelta::default(),
+            AccountVaultDelta {
+                added_assets: vec![asset],
+                ..Default::default()
+            },
+            Some(Felt::new(2)),
+        )
+        .unwrap();
+        let second = AccountDelta::new(
+            AccountStorageDelta::default(),
+            AccountVaultDelta {
+                removed_assets: vec![asset],
+                ..Default::default()
+            },
+            Some(Felt::new(3)),
+        )
+        .unwrap();
+
+        // the changes cancel out, but the nonce was still incremented
+        let merged = first.clone().merge(second).unwrap();
+        assert!(merged.is_empty());
+        assert_eq!(merged.nonce(), Some(Felt::new(3)));
+
+        // a delta which does not update the nonce keeps the nonce of the previous delta
+        let merged = first.merge(AccountDelta::default()).unwrap();
+        assert_eq!(merged.vault().added_assets, vec![asset]);
+        assert_eq!(merged.nonce(), Some(Felt::new(2)));
+    }
+
+    #[test]
+    fn account_delta_size_validation() {
+        let vault_delta = AccountVaultDelta::default();
+
+        // the limit on storage entries applies to cleared and updated items combined
+        let storage_delta = AccountStorageDelta {
+            cleared_items: (0..200).collect(),
+            updated_items: (200..=254).map(|idx| (idx, [ONE; 4])).collect(),
+            ..Default::default()
+        };
+        assert_eq!(storage_delta.num_entries(), AccountDelta::MAX_STORAGE_ENTRIES);
+        assert!(AccountDelta::new(storage_delta, vault_delta.clone(), Some(ONE)).is_ok());
+
+        let storage_delta = AccountStorageDelta {
+            cleared_items: Vec::new(),
+            updated_items: (0..=AccountStorageDelta::MAX_UPDATED_ITEMS as u8)
+                .map(|idx| (idx, [ONE; 4]))
+                .collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            AccountDelta::new(storage_delta, vault_delta, Some(ONE)),
+            Err(AccountDeltaError::TooManyUpdatedStorageItems {
+                actual: AccountStorageDelta::MAX_UPDATED_ITEMS + 1,
+                max: AccountStorageDelta::MAX_UPDATED_ITEMS,
+            })
+        );
+    }
 }
@@ -2,6 +2,7 @@ use super::{
     AccountDeltaError, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
     ToString, Vec, Word,
 };
+use crate::Digest;
 
 // CONSTANTS
 // ================================================================================================
@@ -15,13 +16,29 @@ const MAX_MUTABLE_STORAGE_SLOT_IDX: u8 = 254;
 ///
 /// The differences are represented as follows:
 /// - item updates: represented by `cleared_items` and `updated_items` field.
+/// - map updates: represented by `updated_maps` field, which contains the changes to the entries
+///   of the maps stored in storage slots, keyed by slot index. The new roots of the updated maps
+///   must be recorded as item updates.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct AccountStorageDelta {
     pub cleared_items: Vec<u8>,
     pub updated_items: Vec<(u8, Word)>,
+    pub updated_maps: Vec<(u8, StorageMapDelta)>,
 }
 
 impl AccountStorageDelta {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The maximum number of storage items which can be cleared by a single delta.
+    pub const MAX_CLEARED_ITEMS: usize = u8::MAX as usize;
+
+    /// The maximum number of storage items which can be updated by a single delta.
+    pub const MAX_UPDATED_ITEMS: usize = u8::MAX as usize;
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
     /// Checks whether this storage delta is valid.
     ///
     /// # Errors
@@ -29,19 +46,22 @@ impl AccountStorageDelta {
     /// - The number of cleared or updated items is greater than 255.
     /// - Any of cleared or updated items are at slot 255 (i.e., immutable slot).
     /// - Any of the cleared or updated items is referenced more than once (e.g., updated twice).
+    /// - Any of the updated maps is at slot 255, or is referenced more than once.
+    /// - The new root of any of the updated maps is not recorded as an updated item.
+    /// - Any of the map deltas is invalid (see [StorageMapDelta::validate()]).
     pub fn validate(&self) -> Result<(), AccountDeltaError> {
         let num_cleared_items = self.cleared_items.len();
         let num_updated_items = self.updated_items.len();
 
-        if num_cleared_items > u8::MAX as usize {
+        if num_cleared_items > Self::MAX_CLEARED_ITEMS {
             return Err(AccountDeltaError::TooManyClearedStorageItems {
                 actual: num_cleared_items,
-                max: u8::MAX as usize,
+                max: Self::MAX_CLEARED_ITEMS,
             });
-        } else if num_updated_items > u8::MAX as usize {
-            return Err(AccountDeltaError::TooManyRemovedAssets {
+        } else if num_updated_items > Self::MAX_UPDATED_ITEMS {
+            return Err(AccountDeltaError::TooManyUpdatedStorageItems {
                 actual: num_updated_items,
-                max: u8::MAX as usize,
+                max: Self::MAX_UPDATED_ITEMS,
             });
         }
 
@@ -71,29 +91,123 @@ impl AccountStorageDelta {
             }
         }
 
+        // make sure updated maps vector does not contain errors
+        for (pos, (idx, map_delta)) in self.updated_maps.iter().enumerate() {
+            if *idx > MAX_MUTABLE_STORAGE_SLOT_IDX {
+                return Err(AccountDeltaError::ImmutableStorageSlot(*idx as usize));
+            }
+
+            if self.updated_maps[..pos].iter().any(|x| x.0 == *idx) {
+                return Err(AccountDeltaError::DuplicateStorageMapUpdate(*idx));
+            }
+
+            if !self.updated_items.iter().any(|x| x.0 == *idx) {
+                return Err(AccountDeltaError::StorageMapRootNotUpdated(*idx));
+            }
+
+            map_delta.validate(*idx)?;
+        }
+
         Ok(())
     }
 
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
     /// Returns true if storage delta contains no updates.
     pub fn is_empty(&self) -> bool {
-        self.cleared_items.is_empty() && self.updated_items.is_empty()
+        self.cleared_items.is_empty()
+            && self.updated_items.is_empty()
+            && self.updated_maps.is_empty()
+    }
+
+    /// Returns the total number of storage items and map entries cleared or updated by this
+    /// delta.
+    pub fn num_entries(&self) -> usize {
+        let num_map_entries: usize =
+            self.updated_maps.iter().map(|(_, map_delta)| map_delta.num_entries()).sum();
+
+        self.cleared_items.len() + self.updated_items.len() + num_map_entries
+    }
+
+    // MERGING
+    // --------------------------------------------------------------------------------------------
+
+    /// Merges this delta with the specified delta, which is assumed to describe changes applied
+    /// after the changes described by this delta.
+    ///
+    /// Items cleared or updated by the other delta override the changes made to the same items by
+    /// this delta. Map entries are merged in the same way for each updated map, and the changes to
+    /// the entries of a map are discarded if its slot is cleared by the other delta.
+    ///
+    /// # Errors
+    /// Returns an error if the merged delta is invalid.
+    pub fn merge(self, other: Self) -> Result<Self, AccountDeltaError> {
+        let Self {
+            mut cleared_items,
+            mut updated_items,
+            mut updated_maps,
+        } = self;
+
+        for idx in other.cleared_items {
+            updated_items.retain(|(updated_idx, _)| *updated_idx != idx);
+            updated_maps.retain(|(updated_idx, _)| *updated_idx != idx);
+            if !cleared_items.contains(&idx) {
+                cleared_items.push(idx);
+            }
+        }
+
+        for (idx, value) in other.updated_items {
+            cleared_items.retain(|cleared_idx| *cleared_idx != idx);
+            updated_items.retain(|(updated_idx, _)| *updated_idx != idx);
+            updated_items.push((idx, value));
+        }
+
+        for (idx, map_delta) in other.updated_maps {
+            match updated_maps.iter_mut().find(|(updated_idx, _)| *updated_idx == idx) {
+                Some((_, current)) => current.merge(map_delta),
+                None => updated_maps.push((idx, map_delta)),
+            }
+        }
+
+        let delta = Self {
+            cleared_items,
+            updated_items,
+            updated_maps,
+        };
+        delta.validate()?;
+
+        Ok(delta)
     }
 }
 
 impl Serializable for AccountStorageDelta {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        assert!(self.cleared_items.len() <= u8::MAX as usize, "too many cleared storage items");
+        assert!(
+            self.cleared_items.len() <= Self::MAX_CLEARED_ITEMS,
+            "too many cleared storage items"
+        );
         target.write_u8(self.cleared_items.len() as u8);
         for idx in self.cleared_items.iter() {
             idx.write_into(target);
         }
 
-        assert!(self.updated_items.len() <= u8::MAX as usize, "too many updated storage items");
+        assert!(
+            self.updated_items.len() <= Self::MAX_UPDATED_ITEMS,
+            "too many updated storage items"
+        );
         target.write_u8(self.updated_items.len() as u8);
         for (idx, value) in self.updated_items.iter() {
             idx.write_into(target);
             value.write_into(target);
         }
+
+        assert!(self.updated_maps.len() <= u8::MAX as usize, "too many updated storage maps");
+        target.write_u8(self.updated_maps.len() as u8);
+        for (idx, map_delta) in self.updated_maps.iter() {
+            idx.write_into(target);
+            map_delta.write_into(target);
+        }
     }
 }
 
@@ -153,7 +267,182 @@ impl Deserializable for AccountStorageDelta {
             updated_items.push((idx, value));
         }
 
-        Ok(Self { cleared_items, updated_items })
+        // deserialize and validate updated maps
+        let num_updated_maps = source.read_u8()? as usize;
+        let mut updated_maps: Vec<(u8, StorageMapDelta)> = Vec::with_capacity(num_updated_maps);
+        for _ in 0..num_updated_maps {
+            let idx = source.read_u8()?;
+            let map_delta = StorageMapDelta::read_from(source)?;
+
+            // make sure index is valid
+            if idx > MAX_MUTABLE_STORAGE_SLOT_IDX {
+                return Err(DeserializationError::InvalidValue(
+                    "immutable storage map updated".to_string(),
+                ));
+            }
+
+            // make sure the same map hasn't been updated before
+            if updated_maps.iter().any(|x| x.0 == idx) {
+                return Err(DeserializationError::InvalidValue(
+                    "storage map updated more than once".to_string(),
+                ));
+            }
+
+            // make sure the new root of the map is recorded in the same delta
+            if !updated_items.iter().any(|x| x.0 == idx) {
+                return Err(DeserializationError::InvalidValue(
+                    "storage map updated without updating its root".to_string(),
+                ));
+            }
+
+            map_delta
+                .validate(idx)
+                .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+
+            updated_maps.push((idx, map_delta));
+        }
+
+        Ok(Self {
+            cleared_items,
+            updated_items,
+            updated_maps,
+        })
+    }
+}
+
+// STORAGE MAP DELTA
+// ================================================================================================
+
+/// [StorageMapDelta] stores the differences between two states of a map stored in a storage slot.
+///
+/// The differences are represented as follows:
+/// - leaf updates: represented by `cleared_leaves` and `updated_leaves` field, where leaves are
+///   identified by the keys of the map. A cleared leaf is a key removed from the map (i.e., whose
+///   value was set to [ZERO; 4]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageMapDelta {
+    pub cleared_leaves: Vec<Digest>,
+    pub updated_leaves: Vec<(Digest, Word)>,
+}
+
+impl StorageMapDelta {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The maximum number of leaves which can be cleared or updated by a single map delta.
+    pub const MAX_LEAVES: usize = u16::MAX as usize;
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether this delta is valid for the map stored at the specified slot.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The number of cleared or updated leaves is greater than 65535.
+    /// - Any of the updated leaves is set to [ZERO; 4] (i.e., it should be a cleared leaf).
+    /// - Any of the cleared or updated leaves is referenced more than once.
+    pub fn validate(&self, slot_idx: u8) -> Result<(), AccountDeltaError> {
+        let num_leaves = self.cleared_leaves.len().max(self.updated_leaves.len());
+        if num_leaves > Self::MAX_LEAVES {
+            return Err(AccountDeltaError::TooManyStorageMapLeaves {
+                actual: num_leaves,
+                max: Self::MAX_LEAVES,
+            });
+        }
+
+        for (pos, key) in self.cleared_leaves.iter().enumerate() {
+            if self.cleared_leaves[..pos].contains(key) {
+                return Err(AccountDeltaError::DuplicateStorageMapLeafUpdate(slot_idx, *key));
+            }
+        }
+
+        for (pos, (key, value)) in self.updated_leaves.iter().enumerate() {
+            if *value == Word::default() {
+                return Err(AccountDeltaError::EmptyStorageMapLeafUpdate(slot_idx, *key));
+            }
+
+            if self.cleared_leaves.contains(key)
+                || self.updated_leaves[..pos].iter().any(|x| x.0 == *key)
+            {
+                return Err(AccountDeltaError::DuplicateStorageMapLeafUpdate(slot_idx, *key));
+            }
+        }
+
+        Ok(())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this map delta contains no updates.
+    pub fn is_empty(&self) -> bool {
+        self.cleared_leaves.is_empty() && self.updated_leaves.is_empty()
+    }
+
+    /// Returns the total number of leaves cleared or updated by this map delta.
+    pub fn num_entries(&self) -> usize {
+        self.cleared_leaves.len() + self.updated_leaves.len()
+    }
+
+    // MERGING
+    // --------------------------------------------------------------------------------------------
+
+    /// Merges the specified delta, which is assumed to describe changes applied after the changes
+    /// described by this delta, into this delta.
+    ///
+    /// Leaves cleared or updated by the other delta override the changes made to the same leaves
+    /// by this delta.
+    pub fn merge(&mut self, other: Self) {
+        for key in other.cleared_leaves {
+            self.updated_leaves.retain(|(updated_key, _)| *updated_key != key);
+            if !self.cleared_leaves.contains(&key) {
+                self.cleared_leaves.push(key);
+            }
+        }
+
+        for (key, value) in other.updated_leaves {
+            self.cleared_leaves.retain(|cleared_key| *cleared_key != key);
+            self.updated_leaves.retain(|(updated_key, _)| *updated_key != key);
+            self.updated_leaves.push((key, value));
+        }
+    }
+}
+
+impl Serializable for StorageMapDelta {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        assert!(self.cleared_leaves.len() <= Self::MAX_LEAVES, "too many cleared map leaves");
+        target.write_u16(self.cleared_leaves.len() as u16);
+        for key in self.cleared_leaves.iter() {
+            key.write_into(target);
+        }
+
+        assert!(self.updated_leaves.len() <= Self::MAX_LEAVES, "too many updated map leaves");
+        target.write_u16(self.updated_leaves.len() as u16);
+        for (key, value) in self.updated_leaves.iter() {
+            key.write_into(target);
+            value.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for StorageMapDelta {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_cleared_leaves = source.read_u16()? as usize;
+        let mut cleared_leaves = Vec::with_capacity(num_cleared_leaves);
+        for _ in 0..num_cleared_leaves {
+            cleared_leaves.push(Digest::read_from(source)?);
+        }
+
+        let num_updated_leaves = source.read_u16()? as usize;
+        let mut updated_leaves = Vec::with_capacity(num_updated_leaves);
+        for _ in 0..num_updated_leaves {
+            let key = Digest::read_from(source)?;
+            let value = Word::read_from(source)?;
+            updated_leaves.push((key, value));
+        }
+
+        Ok(Self { cleared_leaves, updated_leaves })
     }
 }
 
@@ -162,14 +451,17 @@ impl Deserializable for AccountStorageDelta {
 
 #[cfg(test)]
 mod tests {
-    use super::{AccountStorageDelta, Deserializable, Serializable};
-    use crate::{ONE, ZERO};
+    use super::{
+        AccountDeltaError, AccountStorageDelta, Deserializable, Serializable, StorageMapDelta,
+    };
+    use crate::{Digest, ONE, ZERO};
 
     #[test]
     fn account_storage_delta_validation() {
         let delta = AccountStorageDelta {
             cleared_items: vec![1, 2, 3],
             updated_items: vec![(4, [ONE, ONE, ONE, ONE]), (5, [ONE, ONE, ONE, ZERO])],
+            ..Default::default()
         };
         assert!(delta.validate().is_ok());
 
@@ -180,6 +472,7 @@ mod tests {
         let delta = AccountStorageDelta {
             cleared_items: vec![1, 2, 255],
             updated_items: vec![],
+            ..Default::default()
         };
         assert!(delta.validate().is_err());
 
@@ -190,6 +483,7 @@ mod tests {
         let delta = AccountStorageDelta {
             cleared_items: vec![1, 2, 1],
             updated_items: vec![],
+            ..Default::default()
         };
         assert!(delta.validate().is_err());
 
@@ -200,6 +494,7 @@ mod tests {
         let delta = AccountStorageDelta {
             cleared_items: vec![],
             updated_items: vec![(4, [ONE, ONE, ONE, ONE]), (255, [ONE, ONE, ONE, ZERO])],
+            ..Default::default()
         };
         assert!(delta.validate().is_err());
 
@@ -214,6 +509,7 @@ mod tests {
                 (5, [ONE, ONE, ONE, ZERO]),
                 (4, [ONE, ONE, ZERO, ZERO]),
             ],
+            ..Default::default()
         };
         assert!(delta.validate().is_err());
 
@@ -224,6 +520,138 @@ mod tests {
         let delta = AccountStorageDelta {
             cleared_items: vec![1, 2, 3],
             updated_items: vec![(2, [ONE, ONE, ONE, ONE]), (5, [ONE, ONE, ONE, ZERO])],
+            ..Default::default()
+        };
+        assert!(delta.validate().is_err());
+
+        let bytes = delta.to_bytes();
+        assert!(AccountStorageDelta::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn account_storage_delta_map_updates() {
+        let key_1 = Digest::new([ONE, ZERO, ZERO, ZERO]);
+        let key_2 = Digest::new([ONE, ONE, ZERO, ZERO]);
+
+        let delta = AccountStorageDelta {
+            cleared_items: vec![],
+            updated_items: vec![(4, [ONE, ONE, ONE, ONE])],
+            updated_maps: vec![(
+                4,
+                StorageMapDelta {
+                    cleared_leaves: vec![key_1],
+                    updated_leaves: vec![(key_2, [ONE, ZERO, ZERO, ZERO])],
+                },
+            )],
+        };
+        assert!(delta.validate().is_ok());
+
+        // the root of the map and each of the changed leaves count as separate entries
+        assert_eq!(delta.num_entries(), 3);
+
+        let bytes = delta.to_bytes();
+        assert_eq!(AccountStorageDelta::read_from_bytes(&bytes), Ok(delta.clone()));
+
+        // later updates to the same leaves override earlier ones
+        let other = AccountStorageDelta {
+            updated_items: vec![(4, [ONE, ONE, ONE, ZERO])],
+            updated_maps: vec![(
+                4,
+                StorageMapDelta {
+                    cleared_leaves: vec![key_2],
+                    updated_leaves: vec![(key_1, [ONE, ONE, ZERO, ZERO])],
+                },
+            )],
+            ..Default::default()
+        };
+        let merged = delta.clone().merge(other).unwrap();
+        assert_eq!(
+            merged.updated_maps,
+            vec![(
+                4,
+                StorageMapDelta {
+                    cleared_leaves: vec![key_2],
+                    updated_leaves: vec![(key_1, [ONE, ONE, ZERO, ZERO])],
+                },
+            )]
+        );
+
+        // clearing the slot of a map discards the changes to its entries
+        let other = AccountStorageDelta {
+            cleared_items: vec![4],
+            ..Default::default()
+        };
+        let merged = delta.merge(other).unwrap();
+        assert_eq!(merged.cleared_items, vec![4]);
+        assert!(merged.updated_items.is_empty());
+        assert!(merged.updated_maps.is_empty());
+
+        // map updated without updating its root
+        let delta = AccountStorageDelta {
+            updated_items: vec![(5, [ONE, ONE, ONE, ONE])],
+            updated_maps: vec![(
+                4,
+                StorageMapDelta {
+                    cleared_leaves: vec![key_1],
+                    updated_leaves: vec![],
+                },
+            )],
+            ..Default::default()
+        };
+        assert_eq!(delta.validate(), Err(AccountDeltaError::StorageMapRootNotUpdated(4)));
+
+        let bytes = delta.to_bytes();
+        assert!(AccountStorageDelta::read_from_bytes(&bytes).is_err());
+
+        // invalid index in updated maps
+        let delta = AccountStorageDelta {
+            updated_maps: vec![(255, StorageMapDelta::default())],
+            ..Default::default()
+        };
+        assert!(delta.validate().is_err());
+
+        let bytes = delta.to_bytes();
+        assert!(AccountStorageDelta::read_from_bytes(&bytes).is_err());
+
+        // duplicate in updated maps
+        let delta = AccountStorageDelta {
+            updated_items: vec![(4, [ONE, ONE, ONE, ONE])],
+            updated_maps: vec![(4, StorageMapDelta::default()), (4, StorageMapDelta::default())],
+            ..Default::default()
+        };
+        assert!(delta.validate().is_err());
+
+        let bytes = delta.to_bytes();
+        assert!(AccountStorageDelta::read_from_bytes(&bytes).is_err());
+
+        // duplicate across cleared and updated leaves
+        let delta = AccountStorageDelta {
+            updated_items: vec![(4, [ONE, ONE, ONE, ONE])],
+            updated_maps: vec![(
+                4,
+                StorageMapDelta {
+                    cleared_leaves: vec![key_1],
+                    updated_leaves: vec![(key_1, [ONE, ZERO, ZERO, ZERO])],
+                },
+            )],
+            ..Default::default()
+        };
+        assert!(delta.validate().is_err());
+
+        let bytes = delta.to_bytes();
+        assert!(AccountStorageDelta::read_from_bytes(&bytes).is_err());
+
+        // leaf updated to an empty value
+        let delta = AccountStorageDelta {
+            updated_items: vec![(4, [ONE, ONE, ONE, ONE])],
+            updated_maps: vec![(
+                4,
+                StorageMapDelta {
+                    cleared_leaves: vec![],
+                    updated_leaves: vec![(key_1, [ZERO; 4])],
+                },
+            )],
+            ..Default::default()
         };
         assert!(delta.validate().is_err());
 
@@ -2,6 +2,11 @@ use super::{
     AccountDeltaError, Asset, ByteReader, ByteWriter, Deserializable, DeserializationError,
     Serializable, ToString, Vec,
 };
+use crate::{
+    accounts::AccountId,
+    assets::{FungibleAsset, NonFungibleAsset},
+    utils::collections::BTreeMap,
+};
 
 // ACCOUNT VAULT DELTA
 // ================================================================================================
@@ -9,50 +14,76 @@ use super::{
 /// [AccountVaultDelta] stores the difference between the initial and final account vault states.
 ///
 /// The difference is represented as follows:
-/// - added_assets: a vector of assets that were added to the account vault.
-/// - removed_assets: a vector of assets that were removed from the account vault.
+/// - added_assets: a vector of fungible assets that were added to the account vault.
+/// - removed_assets: a vector of fungible assets that were removed from the account vault.
+/// - added_nfts: a vector of non-fungible assets that were added to the account vault.
+/// - removed_nfts: a vector of non-fungible assets that were removed from the account vault.
+///
+/// Non-fungible assets are tracked separately from fungible assets and are identified by their
+/// full asset word, such that the final state of the vault can be reconstructed from the initial
+/// state and the delta.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct AccountVaultDelta {
-    pub added_assets: Vec<Asset>,
-    pub removed_assets: Vec<Asset>,
+    pub added_assets: Vec<FungibleAsset>,
+    pub removed_assets: Vec<FungibleAsset>,
+    pub added_nfts: Vec<NonFungibleAsset>,
+    pub removed_nfts: Vec<NonFungibleAsset>,
 }
 
 impl AccountVaultDelta {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The maximum number of assets which can be added to a vault by a single delta.
+    pub const MAX_ADDED_ASSETS: usize = u16::MAX as usize;
+
+    /// The maximum number of assets which can be removed from a vault by a single delta.
+    pub const MAX_REMOVED_ASSETS: usize = u16::MAX as usize;
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
     /// Checks whether this vault delta is valid.
     ///
     /// # Errors
     /// Returns an error if:
-    /// - The number added assets is greater than [u16::MAX].
-    /// - The number of removed assets is greater than [u16::MAX].
+    /// - The number of added assets (fungible and non-fungible) is greater than
+    ///   [AccountVaultDelta::MAX_ADDED_ASSETS].
+    /// - The number of removed assets (fungible and non-fungible) is greater than
+    ///   [AccountVaultDelta::MAX_REMOVED_ASSETS].
     /// - The same asset was added more than once, removed more than once, or both added and
     ///   removed.
     pub fn validate(&self) -> Result<(), AccountDeltaError> {
-        if self.added_assets.len() > u16::MAX as usize {
+        let num_added = self.added_assets.len() + self.added_nfts.len();
+        let num_removed = self.removed_assets.len() + self.removed_nfts.len();
+        if num_added > Self::MAX_ADDED_ASSETS {
             return Err(AccountDeltaError::TooManyAddedAsset {
-                actual: self.added_assets.len(),
-                max: u16::MAX as usize,
+                actual: num_added,
+                max: Self::MAX_ADDED_ASSETS,
             });
-        } else if self.removed_assets.len() > u16::MAX as usize {
+        } else if num_removed > Self::MAX_REMOVED_ASSETS {
             return Err(AccountDeltaError::TooManyRemovedAssets {
-                actual: self.removed_assets.len(),
-                max: u16::MAX as usize,
+                actual: num_removed,
+                max: Self::MAX_REMOVED_ASSETS,
             });
         }
 
         // make sure all added assets are unique
-        for (pos, asset) in self.added_assets.iter().enumerate() {
-            if self.added_assets[..pos].iter().any(|a| a.is_same(asset)) {
+        let added = self.added().collect::<Vec<_>>();
+        for (pos, asset) in added.iter().enumerate() {
+            if added[..pos].iter().any(|a| a.is_same(asset)) {
                 return Err(AccountDeltaError::DuplicateVaultUpdate(*asset));
             }
         }
 
-        // make sure all removed assets are the same
-        for (pos, asset) in self.removed_assets.iter().enumerate() {
-            if self.removed_assets[..pos].iter().any(|a| a.is_same(asset)) {
+        // make sure all removed assets are unique and were not added
+        let removed = self.removed().collect::<Vec<_>>();
+        for (pos, asset) in removed.iter().enumerate() {
+            if removed[..pos].iter().any(|a| a.is_same(asset)) {
                 return Err(AccountDeltaError::DuplicateVaultUpdate(*asset));
             }
 
-            if self.added_assets.iter().any(|a| a.is_same(asset)) {
+            if added.iter().any(|a| a.is_same(asset)) {
                 return Err(AccountDeltaError::DuplicateVaultUpdate(*asset));
             }
         }
@@ -60,66 +91,193 @@ impl AccountVaultDelta {
         Ok(())
     }
 
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
     /// Returns true if this vault delta contains no updates.
     pub fn is_empty(&self) -> bool {
-        self.added_assets.is_empty() && self.removed_assets.is_empty()
+        self.added_assets.is_empty()
+            && self.removed_assets.is_empty()
+            && self.added_nfts.is_empty()
+            && self.removed_nfts.is_empty()
+    }
+
+    /// Returns the total number of assets added or removed by this delta.
+    pub fn num_entries(&self) -> usize {
+        self.added_assets.len()
+            + self.removed_assets.len()
+            + self.added_nfts.len()
+            + self.removed_nfts.len()
+    }
+
+    /// Returns an iterator over all assets added by this delta; fungible assets are followed by
+    /// non-fungible assets.
+    pub fn added(&self) -> impl Iterator<Item = Asset> + '_ {
+        self.added_assets
+            .iter()
+            .copied()
+            .map(Asset::Fungible)
+            .chain(self.added_nfts.iter().copied().map(Asset::NonFungible))
+    }
+
+    /// Returns an iterator over all assets removed by this delta; fungible assets are followed by
+    /// non-fungible assets.
+    pub fn removed(&self) -> impl Iterator<Item = Asset> + '_ {
+        self.removed_assets
+            .iter()
+            .copied()
+            .map(Asset::Fungible)
+            .chain(self.removed_nfts.iter().copied().map(Asset::NonFungible))
+    }
+
+    // MERGING
+    // --------------------------------------------------------------------------------------------
+
+    /// Merges this delta with the specified delta, which is assumed to describe changes applied
+    /// after the changes described by this delta.
+    ///
+    /// Amounts of fungible assets issued by the same faucet are netted out, and a non-fungible
+    /// asset which is added by one delta and removed by the other does not appear in the merged
+    /// delta. The assets of the merged delta are ordered by faucet ID (fungible assets) and by
+    /// their serialized form (non-fungible assets).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The net amount added for a fungible asset exceeds the maximum asset amount.
+    /// - The same non-fungible asset is added or removed by both deltas.
+    /// - The merged delta is invalid.
+    pub fn merge(self, other: Self) -> Result<Self, AccountDeltaError> {
+        let mut fungible: BTreeMap<AccountId, i128> = BTreeMap::new();
+        let mut non_fungible: BTreeMap<[u8; 32], (NonFungibleAsset, i8)> = BTreeMap::new();
+
+        let added = self.added().chain(other.added()).map(|a| (a, 1));
+        let removed = self.removed().chain(other.removed()).map(|a| (a, -1));
+        for (asset, sign) in added.chain(removed) {
+            match asset {
+                Asset::Fungible(asset) => {
+                    *fungible.entry(asset.faucet_id()).or_default() +=
+                        sign as i128 * asset.amount() as i128;
+                },
+                Asset::NonFungible(nft) => {
+                    non_fungible.entry(asset.into()).or_insert((nft, 0)).1 += sign;
+                },
+            }
+        }
+
+        let mut delta = Self::default();
+        for (faucet_id, amount) in fungible {
+            if amount == 0 {
+                continue;
+            }
+            let asset = u64::try_from(amount.unsigned_abs())
+                .ok()
+                .and_then(|amount| FungibleAsset::new(faucet_id, amount).ok())
+                .ok_or(AccountDeltaError::FungibleAssetAmountTooBig(faucet_id))?;
+            if amount > 0 {
+                delta.added_assets.push(asset);
+            } else {
+                delta.removed_assets.push(asset);
+            }
+        }
+
+        for (asset, count) in non_fungible.into_values() {
+            match count {
+                0 => (),
+                1 => delta.added_nfts.push(asset),
+                -1 => delta.removed_nfts.push(asset),
+                _ => return Err(AccountDeltaError::DuplicateVaultUpdate(asset.into())),
+            }
+        }
+
+        delta.validate()?;
+
+        Ok(delta)
     }
 }
 
 impl Serializable for AccountVaultDelta {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        assert!(self.added_assets.len() <= u16::MAX as usize, "too many added assets");
-        target.write_u16(self.added_assets.len() as u16);
-        for asset in self.added_assets.iter() {
-            asset.write_into(target);
+        assert!(
+            self.added_assets.len() + self.added_nfts.len() <= Self::MAX_ADDED_ASSETS,
+            "too many added assets"
+        );
+        assert!(
+            self.removed_assets.len() + self.removed_nfts.len() <= Self::MAX_REMOVED_ASSETS,
+            "too many removed assets"
+        );
+
+        for assets in [&self.added_assets, &self.removed_assets] {
+            target.write_u16(assets.len() as u16);
+            for asset in assets.iter() {
+                Asset::Fungible(*asset).write_into(target);
+            }
         }
 
-        assert!(self.removed_assets.len() <= u16::MAX as usize, "too many removed assets");
-        target.write_u16(self.removed_assets.len() as u16);
-        for asset in self.removed_assets.iter() {
-            asset.write_into(target);
+        for nfts in [&self.added_nfts, &self.removed_nfts] {
+            target.write_u16(nfts.len() as u16);
+            for nft in nfts.iter() {
+                Asset::NonFungible(*nft).write_into(target);
+            }
         }
     }
 }
 
 impl Deserializable for AccountVaultDelta {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        // deserialize and validate added assets
-        let num_added_assets = source.read_u16()? as usize;
-        let mut added_assets: Vec<Asset> = Vec::with_capacity(num_added_assets);
-        for _ in 0..num_added_assets {
-            let asset = Asset::read_from(source)?;
-            if added_assets.iter().any(|a| a.is_same(&asset)) {
-                return Err(DeserializationError::InvalidValue(
-                    "asset added more than once".to_string(),
-                ));
-            }
+        let added_assets = read_fungible_assets(source)?;
+        let removed_assets = read_fungible_assets(source)?;
+        let added_nfts = read_nfts(source)?;
+        let removed_nfts = read_nfts(source)?;
 
-            added_assets.push(asset);
-        }
+        let delta = Self {
+            added_assets,
+            removed_assets,
+            added_nfts,
+            removed_nfts,
+        };
+        delta
+            .validate()
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+
+        Ok(delta)
+    }
+}
 
-        // deserialize and validate removed assets
-        let num_removed_assets = source.read_u16()? as usize;
-        let mut removed_assets: Vec<Asset> = Vec::with_capacity(num_removed_assets);
-        for _ in 0..num_removed_assets {
-            let asset = Asset::read_from(source)?;
+// HELPER FUNCTIONS
+// ================================================================================================
 
-            if removed_assets.iter().any(|a| a.is_same(&asset)) {
-                return Err(DeserializationError::InvalidValue(
-                    "asset added more than once".to_string(),
-                ));
-            }
+/// Reads a list of assets prefixed by its length.
+fn read_assets<R: ByteReader>(source: &mut R) -> Result<Vec<Asset>, DeserializationError> {
+    let num_assets = source.read_u16()? as usize;
+    (0..num_assets).map(|_| Asset::read_from(source)).collect()
+}
 
-            if added_assets.iter().any(|a| a.is_same(&asset)) {
-                return Err(DeserializationError::InvalidValue(
-                    "asset both added and removed".to_string(),
-                ));
-            }
-            removed_assets.push(asset);
-        }
+/// Reads a list of fungible assets prefixed by its length.
+fn read_fungible_assets<R: ByteReader>(
+    source: &mut R,
+) -> Result<Vec<FungibleAsset>, DeserializationError> {
+    read_assets(source)?
+        .into_iter()
+        .map(|asset| match asset {
+            Asset::Fungible(asset) => Ok(asset),
+            Asset::NonFungible(_) => Err(DeserializationError::InvalidValue(
+                "non-fungible asset in the list of fungible assets".to_string(),
+            )),
+        })
+        .collect()
+}
 
-        Ok(Self { added_assets, removed_assets })
-    }
+/// Reads a list of non-fungible assets prefixed by its length.
+fn read_nfts<R: ByteReader>(source: &mut R) -> Result<Vec<NonFungibleAsset>, DeserializationError> {
+    read_assets(source)?
+        .into_iter()
+        .map(|asset| match asset {
+            Asset::NonFungible(nft) => Ok(nft),
+            Asset::Fungible(_) => Err(DeserializationError::InvalidValue(
+                "fungible asset in the list of non-fungible assets".to_string(),
+            )),
+        })
+        .collect()
 }
 
 // TESTS
@@ -127,7 +285,7 @@ impl Deserializable for AccountVaultDelta {
 
 #[cfg(test)]
 mod tests {
-    use super::{AccountVaultDelta, Asset, Deserializable, Serializable};
+    use super::{AccountVaultDelta, Deserializable, Serializable};
     use crate::{
         accounts::{AccountId, AccountType},
         assets::{FungibleAsset, NonFungibleAsset, NonFungibleAssetDetails},
@@ -141,27 +299,26 @@ mod tests {
         let nffid1 = AccountId::new_dummy([0; 32], AccountType::NonFungibleFaucet);
         let nffid2 = AccountId::new_dummy([1; 32], AccountType::NonFungibleFaucet);
 
-        let asset1: Asset = FungibleAsset::new(ffid1, 10).unwrap().into();
-        let asset2: Asset = FungibleAsset::new(ffid1, 30).unwrap().into();
-        let asset3: Asset = FungibleAsset::new(ffid2, 20).unwrap().into();
+        let asset1 = FungibleAsset::new(ffid1, 10).unwrap();
+        let asset2 = FungibleAsset::new(ffid1, 30).unwrap();
+        let asset3 = FungibleAsset::new(ffid2, 20).unwrap();
 
-        let asset4: Asset =
+        let asset4 =
             NonFungibleAsset::new(&NonFungibleAssetDetails::new(nffid1, vec![1, 2, 3]).unwrap())
-                .unwrap()
-                .into();
-        let asset5: Asset =
+                .unwrap();
+        let asset5 =
             NonFungibleAsset::new(&NonFungibleAssetDetails::new(nffid1, vec![4, 5, 6]).unwrap())
-                .unwrap()
-                .into();
-        let asset6: Asset =
+                .unwrap();
+        let asset6 =
             NonFungibleAsset::new(&NonFungibleAssetDetails::new(nffid2, vec![7, 8, 9]).unwrap())
-                .unwrap()
-                .into();
+                .unwrap();
 
         // control case
         let delta = AccountVaultDelta {
-            added_assets: vec![asset1, asset4, asset5],
-            removed_assets: vec![asset3, asset6],
+            added_assets: vec![asset1],
+            removed_assets: vec![asset3],
+            added_nfts: vec![asset4, asset5],
+            removed_nfts: vec![asset6],
         };
         assert!(delta.validate().is_ok());
 
@@ -170,8 +327,9 @@ mod tests {
 
         // duplicate asset in added assets
         let delta = AccountVaultDelta {
-            added_assets: vec![asset1, asset4, asset5, asset2],
-            removed_assets: vec![],
+            added_assets: vec![asset1, asset2],
+            added_nfts: vec![asset4, asset5],
+            ..Default::default()
         };
         assert!(delta.validate().is_err());
 
@@ -180,8 +338,9 @@ mod tests {
 
         // duplicate asset in removed assets
         let delta = AccountVaultDelta {
-            added_assets: vec![],
-            removed_assets: vec![asset1, asset4, asset5, asset2],
+            removed_assets: vec![asset1, asset2],
+            removed_nfts: vec![asset4, asset5],
+            ..Default::default()
         };
         assert!(delta.validate().is_err());
 
@@ -191,11 +350,88 @@ mod tests {
         // duplicate asset across added and removed assets
         let delta = AccountVaultDelta {
             added_assets: vec![asset1, asset3],
-            removed_assets: vec![asset4, asset5, asset2],
+            removed_assets: vec![asset2],
+            removed_nfts: vec![asset4, asset5],
+            ..Default::default()
+        };
+        assert!(delta.validate().is_err());
+
+        let bytes = delta.to_bytes();
+        assert!(AccountVaultDelta::read_from_bytes(&bytes).is_err());
+
+        // duplicate non-fungible asset in added assets
+        let delta = AccountVaultDelta {
+            added_nfts: vec![asset4, asset5, asset4],
+            ..Default::default()
         };
         assert!(delta.validate().is_err());
 
         let bytes = delta.to_bytes();
         assert!(AccountVaultDelta::read_from_bytes(&bytes).is_err());
+
+        // duplicate non-fungible asset across added and removed assets
+        let delta = AccountVaultDelta {
+            added_nfts: vec![asset4],
+            removed_nfts: vec![asset4],
+            ..Default::default()
+        };
+        assert!(delta.validate().is_err());
+
+        let bytes = delta.to_bytes();
+        assert!(AccountVaultDelta::read_from_bytes(&bytes).is_err());
+
+        // a non-fungible asset cannot be deserialized as a fungible asset; swapping the length
+        // prefixes of the added fungible and non-fungible assets moves the asset across the lists
+        let delta = AccountVaultDelta {
+            added_nfts: vec![asset6],
+            ..Default::default()
+        };
+        let mut bytes = delta.to_bytes();
+        bytes.swap(0, 4);
+        bytes.swap(1, 5);
+        assert!(AccountVaultDelta::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn account_vault_delta_merge() {
+        let ffid = AccountId::new_dummy([0; 32], AccountType::FungibleFaucet);
+        let nffid = AccountId::new_dummy([0; 32], AccountType::NonFungibleFaucet);
+        let fungible = |amount| FungibleAsset::new(ffid, amount).unwrap();
+        let non_fungible =
+            NonFungibleAsset::new(&NonFungibleAssetDetails::new(nffid, vec![1, 2, 3]).unwrap())
+                .unwrap();
+
+        // fungible amounts are netted out, and a non-fungible asset added and then removed
+        // disappears from the merged delta
+        let first = AccountVaultDelta {
+            added_assets: vec![fungible(10)],
+            added_nfts: vec![non_fungible],
+            ..Default::default()
+        };
+        let second = AccountVaultDelta {
+            removed_assets: vec![fungible(30)],
+            removed_nfts: vec![non_fungible],
+            ..Default::default()
+        };
+        let merged = first.merge(second).unwrap();
+        assert_eq!(merged.added_assets, vec![]);
+        assert_eq!(merged.removed_assets, vec![fungible(20)]);
+        assert!(merged.added_nfts.is_empty() && merged.removed_nfts.is_empty());
+
+        // non-fungible assets of the merged delta are kept in the dedicated sets
+        let delta = AccountVaultDelta {
+            added_nfts: vec![non_fungible],
+            ..Default::default()
+        };
+        let merged = AccountVaultDelta::default().merge(delta).unwrap();
+        assert_eq!(merged.added_nfts, vec![non_fungible]);
+        assert!(merged.added_assets.is_empty());
+
+        // the same non-fungible asset cannot be added twice
+        let delta = AccountVaultDelta {
+            added_nfts: vec![non_fungible],
+            ..Default::default()
+        };
+        assert!(delta.clone().merge(delta).is_err());
     }
 }
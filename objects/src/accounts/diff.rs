@@ -0,0 +1,697 @@
+use core::fmt;
+
+use super::{
+    Account, AccountDelta, AccountError, AccountId, BTreeMap, Digest, Felt, StarkField, Vec, Word,
+};
+use crate::{
+    assets::{Asset, NonFungibleAsset},
+    EMPTY_WORD,
+};
+
+// ACCOUNT DIFF
+// ================================================================================================
+
+/// A single difference between two states of the same account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountChange {
+    /// The account nonce changed.
+    Nonce { before: Felt, after: Felt },
+    /// The account code changed.
+    Code { before: Digest, after: Digest },
+    /// The balance of the fungible asset issued by the specified faucet changed.
+    FungibleAsset {
+        faucet_id: AccountId,
+        before: u64,
+        after: u64,
+    },
+    /// The non-fungible asset was added to the account vault.
+    NonFungibleAssetAdded(NonFungibleAsset),
+    /// The non-fungible asset was removed from the account vault.
+    NonFungibleAssetRemoved(NonFungibleAsset),
+    /// The value of the storage slot at the specified index changed.
+    StorageSlot { index: u8, before: Word, after: Word },
+}
+
+/// Describes the differences between two states of the same account.
+///
+/// The changes are ordered as follows: nonce, code, vault changes (ordered by faucet ID for
+/// fungible assets, and by vault key for non-fungible assets), and storage changes (ordered by
+/// slot index).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDiff {
+    account_id: AccountId,
+    changes: Vec<AccountChange>,
+}
+
+impl AccountDiff {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns the differences between the `before` and `after` states of an account.
+    ///
+    /// # Errors
+    /// Returns an error if the two accounts have different IDs.
+    pub fn new(before: &Account, after: &Account) -> Result<Self, AccountError> {
+        if before.id() != after.id() {
+            return Err(AccountError::AccountIdMismatch(before.id(), after.id()));
+        }
+
+        let mut changes = Vec::new();
+
+        // --- nonce and code -----------------------------------------------------------------
+        if before.nonce() != after.nonce() {
+            changes.push(AccountChange::Nonce {
+                before: before.nonce(),
+                after: after.nonce(),
+            });
+        }
+        if before.code().root() != after.code().root() {
+            changes.push(AccountChange::Code {
+                before: before.code().root(),
+                after: after.code().root(),
+            });
+        }
+
+        // --- vault --------------------------------------------------------------------------
+        let (fungible_before, non_fungible_before) = split_vault_assets(before);
+        let (fungible_after, non_fungible_after) = split_vault_assets(after);
+
+        for faucet_id in union_keys(&fungible_before, &fungible_after) {
+            let before = fungible_before.get(&faucet_id).copied().unwrap_or_default();
+            let after = fungible_after.get(&faucet_id).copied().unwrap_or_default();
+            if before != after {
+                changes.push(AccountChange::FungibleAsset { faucet_id, before, after });
+            }
+        }
+
+        for key in union_keys(&non_fungible_before, &non_fungible_after) {
+            match (non_fungible_before.get(&key), non_fungible_after.get(&key)) {
+                (None, Some(asset)) => changes.push(AccountChange::NonFungibleAssetAdded(*asset)),
+                (Some(asset), None) => changes.push(AccountChange::NonFungibleAssetRemoved(*asset)),
+                _ => (),
+            }
+        }
+
+        // --- storage ------------------------------------------------------------------------
+        let slots_before = storage_slots(before);
+        let slots_after = storage_slots(after);
+
+        for index in union_keys(&slots_before, &slots_after) {
+            let before = slots_before.get(&index).copied().unwrap_or(EMPTY_WORD);
+            let after = slots_after.get(&index).copied().unwrap_or(EMPTY_WORD);
+            if before != after {
+                changes.push(AccountChange::StorageSlot { index, before, after });
+            }
+        }
+
+        Ok(Self { account_id: before.id(), changes })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the account this diff was computed for.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the list of changes between the two account states.
+    pub fn changes(&self) -> &[AccountChange] {
+        &self.changes
+    }
+
+    /// Returns true if the two account states are identical.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for AccountDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "account {}:", self.account_id)?;
+        if self.changes.is_empty() {
+            return writeln!(f, "  no changes");
+        }
+
+        for change in self.changes.iter() {
+            match change {
+                AccountChange::Nonce { before, after } => {
+                    writeln!(f, "  nonce: {} -> {}", before.as_int(), after.as_int())?
+                },
+                AccountChange::Code { before, after } => {
+                    writeln!(f, "  code: {before} -> {after}")?
+                },
+                AccountChange::FungibleAsset { faucet_id, before, after } => writeln!(
+                    f,
+                    "  asset {faucet_id}: {before} -> {after} ({})",
+                    format_amount_change(*after as i128 - *before as i128)
+                )?,
+                AccountChange::NonFungibleAssetAdded(asset) => {
+                    writeln!(f, "  asset {}: added", format_non_fungible(asset))?
+                },
+                AccountChange::NonFungibleAssetRemoved(asset) => {
+                    writeln!(f, "  asset {}: removed", format_non_fungible(asset))?
+                },
+                AccountChange::StorageSlot { index, before, after } => writeln!(
+                    f,
+                    "  storage[{index}]: {} -> {}",
+                    FormattedWord(before),
+                    FormattedWord(after)
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ACCOUNT DELTA DIFF
+// ================================================================================================
+
+/// A single mismatch between an expected and an actual [AccountDelta].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountDeltaMismatch {
+    /// The nonce updates differ.
+    Nonce {
+        expected: Option<Felt>,
+        actual: Option<Felt>,
+    },
+    /// The updates to the storage slot at the specified index differ. `None` means that the slot
+    /// was not updated; cleared slots are represented as [EMPTY_WORD].
+    StorageSlot {
+        index: u8,
+        expected: Option<Word>,
+        actual: Option<Word>,
+    },
+    /// The updates to the entry with the specified key of the storage map at the specified index
+    /// differ. `None` means that the entry was not updated; cleared entries are represented as
+    /// [EMPTY_WORD].
+    StorageMapItem {
+        index: u8,
+        key: Digest,
+        expected: Option<Word>,
+        actual: Option<Word>,
+    },
+    /// The net change in the balance of the fungible asset issued by the specified faucet
+    /// differs.
+    FungibleAsset {
+        faucet_id: AccountId,
+        expected: i128,
+        actual: i128,
+    },
+    /// The net change of the non-fungible asset differs. 1 means that the asset was added, -1
+    /// that it was removed and 0 that it was not touched.
+    NonFungibleAsset {
+        asset: NonFungibleAsset,
+        expected: i8,
+        actual: i8,
+    },
+}
+
+/// Describes the mismatches between an expected and an actual [AccountDelta].
+///
+/// Deltas are compared by their effects: clearing a storage slot or a storage map entry is
+/// equivalent to setting it to [EMPTY_WORD], and fungible asset updates are compared by their net
+/// amounts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountDeltaDiff {
+    mismatches: Vec<AccountDeltaMismatch>,
+}
+
+impl AccountDeltaDiff {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns the mismatches between the `expected` and `actual` account deltas.
+    pub fn new(expected: &AccountDelta, actual: &AccountDelta) -> Self {
+        let mut mismatches = Vec::new();
+
+        // --- nonce --------------------------------------------------------------------------
+        if expected.nonce() != actual.nonce() {
+            mismatches.push(AccountDeltaMismatch::Nonce {
+                expected: expected.nonce(),
+                actual: actual.nonce(),
+            });
+        }
+
+        // --- storage ------------------------------------------------------------------------
+        let expected_slots = storage_updates(expected);
+        let actual_slots = storage_updates(actual);
+
+        for index in union_keys(&expected_slots, &actual_slots) {
+            let expected = expected_slots.get(&index).copied();
+            let actual = actual_slots.get(&index).copied();
+            if expected != actual {
+                mismatches.push(AccountDeltaMismatch::StorageSlot { index, expected, actual });
+            }
+        }
+
+        let expected_map_items = storage_map_updates(expected);
+        let actual_map_items = storage_map_updates(actual);
+
+        for (index, key) in union_keys(&expected_map_items, &actual_map_items) {
+            let expected = expected_map_items.get(&(index, key)).copied();
+            let actual = actual_map_items.get(&(index, key)).copied();
+            if expected != actual {
+                mismatches.push(AccountDeltaMismatch::StorageMapItem {
+                    index,
+                    key,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        // --- vault --------------------------------------------------------------------------
+        let (expected_fungible, expected_non_fungible) = vault_updates(expected);
+        let (actual_fungible, actual_non_fungible) = vault_updates(actual);
+
+        for faucet_id in union_keys(&expected_fungible, &actual_fungible) {
+            let expected = expected_fungible.get(&faucet_id).copied().unwrap_or_default();
+            let actual = actual_fungible.get(&faucet_id).copied().unwrap_or_default();
+            if expected != actual {
+                mismatches.push(AccountDeltaMismatch::FungibleAsset {
+                    faucet_id,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        for key in union_keys(&expected_non_fungible, &actual_non_fungible) {
+            let (asset, expected) = match expected_non_fungible.get(&key) {
+                Some((asset, change)) => (*asset, *change),
+                None => (actual_non_fungible[&key].0, 0),
+            };
+            let actual = actual_non_fungible.get(&key).map(|(_, change)| *change).unwrap_or(0);
+            if expected != actual {
+                mismatches.push(AccountDeltaMismatch::NonFungibleAsset { asset, expected, actual });
+            }
+        }
+
+        Self { mismatches }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the list of mismatches between the two deltas.
+    pub fn mismatches(&self) -> &[AccountDeltaMismatch] {
+        &self.mismatches
+    }
+
+    /// Returns true if the two deltas have the same effects.
+    pub fn is_empty(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for AccountDeltaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.mismatches.is_empty() {
+            return writeln!(f, "account deltas match");
+        }
+
+        writeln!(f, "account deltas differ (expected / actual):")?;
+        for mismatch in self.mismatches.iter() {
+            match mismatch {
+                AccountDeltaMismatch::Nonce { expected, actual } => writeln!(
+                    f,
+                    "  nonce: {} / {}",
+                    FormattedOption(expected.map(|nonce| nonce.as_int())),
+                    FormattedOption(actual.map(|nonce| nonce.as_int()))
+                )?,
+                AccountDeltaMismatch::StorageSlot { index, expected, actual } => writeln!(
+                    f,
+                    "  storage[{index}]: {} / {}",
+                    FormattedOption(expected.as_ref().map(FormattedWord)),
+                    FormattedOption(actual.as_ref().map(FormattedWord))
+                )?,
+                AccountDeltaMismatch::StorageMapItem { index, key, expected, actual } => writeln!(
+                    f,
+                    "  storage[{index}][{key}]: {} / {}",
+                    FormattedOption(expected.as_ref().map(FormattedWord)),
+                    FormattedOption(actual.as_ref().map(FormattedWord))
+                )?,
+                AccountDeltaMismatch::FungibleAsset { faucet_id, expected, actual } => writeln!(
+                    f,
+                    "  asset {faucet_id}: {} / {}",
+                    format_amount_change(*expected),
+                    format_amount_change(*actual)
+                )?,
+                AccountDeltaMismatch::NonFungibleAsset { asset, expected, actual } => writeln!(
+                    f,
+                    "  asset {}: {} / {}",
+                    format_non_fungible(asset),
+                    format_amount_change(*expected as i128),
+                    format_amount_change(*actual as i128)
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the sorted union of the keys of the two provided maps.
+fn union_keys<K: Ord + Copy, V>(a: &BTreeMap<K, V>, b: &BTreeMap<K, V>) -> Vec<K> {
+    let mut keys = a.keys().chain(b.keys()).copied().collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Splits the assets in the account vault into fungible balances (keyed by faucet ID) and
+/// non-fungible assets (keyed by vault key).
+fn split_vault_assets(
+    account: &Account,
+) -> (BTreeMap<AccountId, u64>, BTreeMap<Digest, NonFungibleAsset>) {
+    let mut fungible = BTreeMap::new();
+    let mut non_fungible = BTreeMap::new();
+    for asset in account.vault().assets() {
+        match asset {
+            Asset::Fungible(asset) => {
+                fungible.insert(asset.faucet_id(), asset.amount());
+            },
+            Asset::NonFungible(asset) => {
+                non_fungible.insert(asset.vault_key().into(), asset);
+            },
+        }
+    }
+    (fungible, non_fungible)
+}
+
+/// Returns the values of all non-empty storage slots of the account.
+fn storage_slots(account: &Account) -> BTreeMap<u8, Word> {
+    account
+        .storage()
+        .slots()
+        .leaves()
+        .map(|(index, value)| (index as u8, *value))
+        .collect()
+}
+
+/// Returns the storage updates described by the delta, with cleared slots set to [EMPTY_WORD].
+fn storage_updates(delta: &AccountDelta) -> BTreeMap<u8, Word> {
+    let storage = delta.storage();
+    storage
+        .cleared_items
+        .iter()
+        .map(|index| (*index, EMPTY_WORD))
+        .chain(storage.updated_items.iter().copied())
+        .collect()
+}
+
+/// Returns the updates to storage map entries described by the delta, keyed by the index of the
+/// map and the key of the entry, with cleared entries set to [EMPTY_WORD].
+fn storage_map_updates(delta: &AccountDelta) -> BTreeMap<(u8, Digest), Word> {
+    let mut updates = BTreeMap::new();
+    for (index, map_delta) in delta.storage().updated_maps.iter() {
+        let cleared = map_delta.cleared_leaves.iter().map(|key| (*key, EMPTY_WORD));
+        for (key, value) in cleared.chain(map_delta.updated_leaves.iter().copied()) {
+            updates.insert((*index, key), value);
+        }
+    }
+    updates
+}
+
+/// Returns the net fungible asset changes (keyed by faucet ID) and the non-fungible asset changes
+/// (keyed by vault key) described by the delta.
+fn vault_updates(
+    delta: &AccountDelta,
+) -> (BTreeMap<AccountId, i128>, BTreeMap<Digest, (NonFungibleAsset, i8)>) {
+    let mut fungible = BTreeMap::new();
+    let mut non_fungible = BTreeMap::new();
+
+    let vault = delta.vault();
+    let added = vault.added().map(|asset| (asset, 1));
+    let removed = vault.removed().map(|asset| (asset, -1));
+    for (asset, sign) in added.chain(removed) {
+        match asset {
+            Asset::Fungible(asset) => {
+                *fungible.entry(asset.faucet_id()).or_insert(0) += sign * asset.amount() as i128;
+            },
+            Asset::NonFungible(asset) => {
+                non_fungible.entry(asset.vault_key().into()).or_insert((asset, 0)).1 += sign as i8;
+            },
+        }
+    }
+
+    (fungible, non_fungible)
+}
+
+/// Formats a signed amount change with an explicit sign.
+fn format_amount_change(change: i128) -> impl fmt::Display {
+    struct AmountChange(i128);
+
+    impl fmt::Display for AmountChange {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if self.0 > 0 {
+                write!(f, "+{}", self.0)
+            } else {
+                write!(f, "{}", self.0)
+            }
+        }
+    }
+
+    AmountChange(change)
+}
+
+/// Formats a non-fungible asset as the digest of its word representation.
+fn format_non_fungible(asset: &NonFungibleAsset) -> Digest {
+    Word::from(*asset).into()
+}
+
+struct FormattedWord<'a>(&'a Word);
+
+impl fmt::Display for FormattedWord<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.0;
+        write!(f, "[{}, {}, {}, {}]", a.as_int(), b.as_int(), c.as_int(), d.as_int())
+    }
+}
+
+struct FormattedOption<T>(Option<T>);
+
+impl<T: fmt::Display> fmt::Display for FormattedOption<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(value) => write!(f, "{value}"),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountChange, AccountDeltaDiff, AccountDeltaMismatch, AccountDiff};
+    use crate::{
+        accounts::{
+            Account, AccountCode, AccountDelta, AccountId, AccountStorage, AccountStorageDelta,
+            AccountVaultDelta, StorageMapDelta, StorageSlotType,
+            ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_NON_FUNGIBLE_FAUCET_OFF_CHAIN,
+            ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        },
+        assembly::{Assembler, ModuleAst},
+        assets::{Asset, AssetVault, FungibleAsset, NonFungibleAsset, NonFungibleAssetDetails},
+        utils::string::ToString,
+        AccountError, Digest, Felt, Word, EMPTY_WORD, ONE, ZERO,
+    };
+
+    #[test]
+    fn account_diff() {
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let nft_faucet_id = AccountId::try_from(ACCOUNT_ID_NON_FUNGIBLE_FAUCET_OFF_CHAIN).unwrap();
+        let fungible = |amount| Asset::from(FungibleAsset::new(faucet_id, amount).unwrap());
+        let nft = NonFungibleAsset::new(
+            &NonFungibleAssetDetails::new(nft_faucet_id, vec![1, 2, 3]).unwrap(),
+        )
+        .unwrap();
+
+        let module = ModuleAst::parse("export.foo push.1 push.2 mul end").unwrap();
+        let code = AccountCode::new(module, &Assembler::default()).unwrap();
+        let account = |assets: &[Asset], slots: &[(u8, Word)], nonce| {
+            let slots = slots
+                .iter()
+                .map(|(index, value)| (*index, (StorageSlotType::default(), *value)));
+            Account::new(
+                account_id,
+                AssetVault::new(assets).unwrap(),
+                AccountStorage::new(slots.collect()).unwrap(),
+                code.clone(),
+                nonce,
+            )
+        };
+
+        let a = [ONE, ZERO, ZERO, ZERO];
+        let b = [ZERO, ONE, ZERO, ZERO];
+        let c = [ZERO, ZERO, ONE, ZERO];
+        let d = [ZERO, ZERO, ZERO, ONE];
+        let before = account(&[fungible(100), Asset::from(nft)], &[(0, a), (1, b)], ONE);
+        let after = account(&[fungible(150)], &[(0, a), (1, c), (2, d)], Felt::new(2));
+
+        // identical accounts have no changes
+        let diff = AccountDiff::new(&before, &before).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), format!("account {account_id}:\n  no changes\n"));
+
+        let diff = AccountDiff::new(&before, &after).unwrap();
+        assert_eq!(diff.account_id(), account_id);
+        assert_eq!(
+            diff.changes(),
+            &[
+                AccountChange::Nonce { before: ONE, after: Felt::new(2) },
+                AccountChange::FungibleAsset { faucet_id, before: 100, after: 150 },
+                AccountChange::NonFungibleAssetRemoved(nft),
+                AccountChange::StorageSlot { index: 1, before: b, after: c },
+                AccountChange::StorageSlot { index: 2, before: EMPTY_WORD, after: d },
+            ]
+        );
+        assert_eq!(
+            diff.to_string(),
+            format!(
+                "account {account_id}:\n  \
+                nonce: 1 -> 2\n  \
+                asset {faucet_id}: 100 -> 150 (+50)\n  \
+                asset {}: removed\n  \
+                storage[1]: [0, 1, 0, 0] -> [0, 0, 1, 0]\n  \
+                storage[2]: [0, 0, 0, 0] -> [0, 0, 0, 1]\n",
+                Digest::from(Word::from(nft))
+            )
+        );
+
+        // the reverse diff reports the opposite changes
+        let diff = AccountDiff::new(&after, &before).unwrap();
+        assert!(diff.changes().contains(&AccountChange::NonFungibleAssetAdded(nft)));
+        assert!(diff.to_string().contains(&format!("asset {faucet_id}: 150 -> 100 (-50)")));
+
+        // accounts with different IDs cannot be diffed
+        let other_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let other = Account::new(
+            other_id,
+            AssetVault::default(),
+            AccountStorage::new(vec![]).unwrap(),
+            code,
+            ONE,
+        );
+        assert_eq!(
+            AccountDiff::new(&before, &other),
+            Err(AccountError::AccountIdMismatch(account_id, other_id))
+        );
+    }
+
+    #[test]
+    fn account_delta_diff() {
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let asset = |amount| FungibleAsset::new(faucet_id, amount).unwrap();
+        let value = [ONE, ONE, ONE, ONE];
+
+        let expected = AccountDelta::new(
+            AccountStorageDelta {
+                cleared_items: vec![1],
+                updated_items: vec![(2, value)],
+                ..Default::default()
+            },
+            AccountVaultDelta {
+                added_assets: vec![asset(100)],
+                ..Default::default()
+            },
+            Some(Felt::new(2)),
+        )
+        .unwrap();
+
+        // a delta with the same effects matches
+        let same = AccountDelta::new(
+            AccountStorageDelta {
+                cleared_items: vec![],
+                updated_items: vec![(1, [ZERO; 4]), (2, value)],
+                ..Default::default()
+            },
+            AccountVaultDelta {
+                added_assets: vec![asset(100)],
+                ..Default::default()
+            },
+            Some(Felt::new(2)),
+        )
+        .unwrap();
+        assert!(AccountDeltaDiff::new(&expected, &same).is_empty());
+
+        // a delta with different effects does not
+        let actual = AccountDelta::new(
+            AccountStorageDelta {
+                cleared_items: vec![],
+                updated_items: vec![(2, value)],
+                ..Default::default()
+            },
+            AccountVaultDelta {
+                added_assets: vec![asset(90)],
+                ..Default::default()
+            },
+            Some(Felt::new(2)),
+        )
+        .unwrap();
+        let diff = AccountDeltaDiff::new(&expected, &actual);
+        assert_eq!(
+            diff.mismatches(),
+            &[
+                AccountDeltaMismatch::StorageSlot {
+                    index: 1,
+                    expected: Some([ZERO; 4]),
+                    actual: None
+                },
+                AccountDeltaMismatch::FungibleAsset { faucet_id, expected: 100, actual: 90 },
+            ]
+        );
+    }
+
+    #[test]
+    fn account_delta_diff_storage_maps() {
+        let key_0 = Digest::new([ONE, ZERO, ZERO, ZERO]);
+        let key_1 = Digest::new([ZERO, ONE, ZERO, ZERO]);
+        let value = [ONE, ONE, ONE, ONE];
+        let root = [ONE, ZERO, ONE, ZERO];
+        let delta = |cleared_leaves, updated_leaves| {
+            AccountDelta::new(
+                AccountStorageDelta {
+                    updated_items: vec![(3, root)],
+                    updated_maps: vec![(3, StorageMapDelta { cleared_leaves, updated_leaves })],
+                    ..Default::default()
+                },
+                AccountVaultDelta::default(),
+                Some(Felt::new(2)),
+            )
+            .unwrap()
+        };
+
+        // clearing a map entry is equivalent to setting it to an empty word
+        let expected = delta(vec![key_0], vec![(key_1, value)]);
+        let same = delta(vec![], vec![(key_0, [ZERO; 4]), (key_1, value)]);
+        assert!(AccountDeltaDiff::new(&expected, &same).is_empty());
+
+        // map entries are compared per key
+        let actual = delta(vec![key_0], vec![]);
+        let diff = AccountDeltaDiff::new(&expected, &actual);
+        assert_eq!(
+            diff.mismatches(),
+            &[AccountDeltaMismatch::StorageMapItem {
+                index: 3,
+                key: key_1,
+                expected: Some(value),
+                actual: None
+            }]
+        );
+        assert_eq!(
+            diff.to_string(),
+            format!(
+                "account deltas differ (expected / actual):\n  \
+                storage[3][{key_1}]: [1, 1, 1, 1] / -\n"
+            )
+        );
+    }
+}
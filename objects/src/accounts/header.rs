@@ -0,0 +1,141 @@
+use super::{
+    Account, AccountId, ByteReader, ByteWriter, Deserializable, DeserializationError, Digest, Felt,
+    Hasher, Serializable, ZERO,
+};
+
+// ACCOUNT HEADER
+// ================================================================================================
+
+/// A header of an account which contains information that succinctly describes the state of the
+/// components of the account.
+///
+/// The [AccountHeader] is composed of:
+/// - id: the account id ([AccountId]) of the account.
+/// - nonce: the nonce of the account.
+/// - vault_root: a commitment to the account's vault ([AssetVault](crate::assets::AssetVault)).
+/// - storage_commitment: a commitment to the account's storage
+///   ([AccountStorage](super::AccountStorage)).
+/// - code_commitment: a commitment to the account's code ([AccountCode](super::AccountCode)).
+///
+/// The header is the canonical representation of the account state which is committed to by the
+/// account hash, and which the transaction kernel outputs for the final state of the account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AccountHeader {
+    id: AccountId,
+    nonce: Felt,
+    vault_root: Digest,
+    storage_commitment: Digest,
+    code_commitment: Digest,
+}
+
+impl AccountHeader {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new [AccountHeader].
+    pub fn new(
+        id: AccountId,
+        nonce: Felt,
+        vault_root: Digest,
+        storage_commitment: Digest,
+        code_commitment: Digest,
+    ) -> Self {
+        Self {
+            id,
+            nonce,
+            vault_root,
+            storage_commitment,
+            code_commitment,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns hash of this account.
+    ///
+    /// Hash of an account is computed as hash(id, nonce, vault_root, storage_commitment,
+    /// code_commitment). Computing the account hash requires 2 permutations of the hash function.
+    pub fn hash(&self) -> Digest {
+        Hasher::hash_elements(&self.to_elements())
+    }
+
+    /// Returns the id of this account.
+    pub fn id(&self) -> AccountId {
+        self.id
+    }
+
+    /// Returns the nonce of this account.
+    pub fn nonce(&self) -> Felt {
+        self.nonce
+    }
+
+    /// Returns the vault root of this account.
+    pub fn vault_root(&self) -> Digest {
+        self.vault_root
+    }
+
+    /// Returns the storage commitment of this account.
+    pub fn storage_commitment(&self) -> Digest {
+        self.storage_commitment
+    }
+
+    /// Returns the code commitment of this account.
+    pub fn code_commitment(&self) -> Digest {
+        self.code_commitment
+    }
+
+    /// Returns the elements of this header in the order in which they are hashed to compute the
+    /// account hash, i.e., `[id, 0, 0, nonce, VAULT_ROOT, STORAGE_COMMITMENT, CODE_COMMITMENT]`.
+    pub fn to_elements(&self) -> [Felt; 16] {
+        let mut elements = [ZERO; 16];
+        elements[0] = self.id.into();
+        elements[3] = self.nonce;
+        elements[4..8].copy_from_slice(self.vault_root.as_elements());
+        elements[8..12].copy_from_slice(self.storage_commitment.as_elements());
+        elements[12..].copy_from_slice(self.code_commitment.as_elements());
+        elements
+    }
+}
+
+impl From<Account> for AccountHeader {
+    fn from(account: Account) -> Self {
+        (&account).into()
+    }
+}
+
+impl From<&Account> for AccountHeader {
+    fn from(account: &Account) -> Self {
+        Self {
+            id: account.id(),
+            nonce: account.nonce(),
+            vault_root: account.vault().commitment(),
+            storage_commitment: account.storage().root(),
+            code_commitment: account.code().root(),
+        }
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for AccountHeader {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.id.write_into(target);
+        self.nonce.write_into(target);
+        self.vault_root.write_into(target);
+        self.storage_commitment.write_into(target);
+        self.code_commitment.write_into(target);
+    }
+}
+
+impl Deserializable for AccountHeader {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let id = AccountId::read_from(source)?;
+        let nonce = Felt::read_from(source)?;
+        let vault_root = Digest::read_from(source)?;
+        let storage_commitment = Digest::read_from(source)?;
+        let code_commitment = Digest::read_from(source)?;
+
+        Ok(Self::new(id, nonce, vault_root, storage_commitment, code_commitment))
+    }
+}
@@ -5,27 +5,39 @@ use super::{
         collections::{BTreeMap, Vec},
         serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
         string::{String, ToString},
+        sync::OnceLock,
     },
     AccountError, Digest, Felt, FieldElement, Hasher, StarkField, Word, ZERO,
 };
 
+mod account_file;
+pub use account_file::{AccountFile, AuthData};
+
 mod account_id;
-pub use account_id::{compute_digest, digest_pow, validate_account_seed, AccountId, AccountType};
+pub use account_id::{
+    compute_digest, digest_pow, validate_account_seed, AccountId, AccountIdKind,
+    AccountStorageMode, AccountType,
+};
 
 mod code;
-pub use code::AccountCode;
+pub use code::{AccountCode, ProcedureAccess};
 
 pub mod delta;
-pub use delta::{AccountDelta, AccountStorageDelta, AccountVaultDelta};
+pub use delta::{AccountDelta, AccountStorageDelta, AccountVaultDelta, StorageMapDelta};
+
+mod diff;
+pub use diff::{AccountChange, AccountDeltaDiff, AccountDeltaMismatch, AccountDiff};
 
 mod seed;
 pub use seed::get_account_seed;
 
 mod storage;
-pub use storage::{AccountStorage, SlotItem, StorageSlotType};
+pub use storage::{
+    AccountStorage, SlotItem, StorageMapProof, StorageSlot, StorageSlotType, StorageValue,
+};
 
-mod stub;
-pub use stub::AccountStub;
+mod header;
+pub use header::AccountHeader;
 
 // TESTING CONSTANTS
 // ================================================================================================
@@ -63,13 +75,18 @@ pub const ACCOUNT_ID_INSUFFICIENT_ONES: u64 = 0b1100000110 << 54;
 /// Out of the the above components account ID is always immutable (once defined it can never be
 /// changed). Other components may be mutated throughout the lifetime of the account. However,
 /// account state can be changed only by invoking one of account interface methods.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// The account hash is computed lazily and cached; the cache is cleared whenever the account is
+/// mutated.
+#[derive(Debug, Clone)]
 pub struct Account {
     id: AccountId,
     vault: AssetVault,
     storage: AccountStorage,
     code: AccountCode,
     nonce: Felt,
+
+    hash: OnceLock<Digest>,
 }
 
 impl Account {
@@ -84,7 +101,14 @@ impl Account {
         code: AccountCode,
         nonce: Felt,
     ) -> Self {
-        Self { id, vault, storage, code, nonce }
+        Self {
+            id,
+            vault,
+            storage,
+            code,
+            nonce,
+            hash: OnceLock::new(),
+        }
     }
 
     // PUBLIC ACCESSORS
@@ -93,15 +117,18 @@ impl Account {
     /// Returns hash of this account.
     ///
     /// Hash of an account is computed as hash(id, nonce, vault_root, storage_root, code_root).
-    /// Computing the account hash requires 2 permutations of the hash function.
+    /// Computing the account hash requires 2 permutations of the hash function; the result is
+    /// cached so that subsequent calls do not recompute it.
     pub fn hash(&self) -> Digest {
-        hash_account(
-            self.id,
-            self.nonce,
-            self.vault.commitment(),
-            self.storage.root(),
-            self.code.root(),
-        )
+        *self.hash.get_or_init(|| {
+            hash_account(
+                self.id,
+                self.nonce,
+                self.vault.commitment(),
+                self.storage.root(),
+                self.code.root(),
+            )
+        })
     }
 
     /// Returns unique identifier of this account.
@@ -122,6 +149,7 @@ impl Account {
     #[cfg(test)]
     /// Returns a mutable reference to the vault of this account.
     pub fn vault_mut(&mut self) -> &mut AssetVault {
+        self.hash.take();
         &mut self.vault
     }
 
@@ -142,6 +170,7 @@ impl Account {
 
     /// Returns nonce for this account.
     pub fn set_nonce(&mut self, nonce: Felt) {
+        self.hash.take();
         self.nonce = nonce;
     }
 
@@ -164,14 +193,142 @@ impl Account {
     pub fn is_new(&self) -> bool {
         self.nonce == ZERO
     }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies the provided delta to this account.
+    ///
+    /// Assets added by the delta are added to the vault (fungible assets are aggregated with the
+    /// assets of the same faucet), assets removed by the delta are removed from it, storage slots
+    /// and tracked maps are updated as described by [AccountStorage::apply_delta()], and the
+    /// nonce is set to the nonce of the delta.
+    ///
+    /// The delta is applied atomically: if an error is returned, this account is not modified.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The delta is invalid (see [AccountDelta::validate()]).
+    /// - The new nonce is not greater than the current nonce of the account.
+    /// - The storage delta cannot be applied to the account storage.
+    /// - The vault delta cannot be applied to the account vault (e.g., a removed asset is not in
+    ///   the vault, or an added non-fungible asset is already in it).
+    pub fn apply_delta(&mut self, delta: &AccountDelta) -> Result<(), AccountError> {
+        delta.validate().map_err(AccountError::AccountDeltaInvalid)?;
+
+        let nonce = match delta.nonce() {
+            Some(nonce) if nonce.as_int() <= self.nonce.as_int() => {
+                return Err(AccountError::NonceMustBeMonotonicallyIncreasing(
+                    self.nonce.as_int(),
+                    nonce.as_int(),
+                ));
+            },
+            Some(nonce) => nonce,
+            None => self.nonce,
+        };
+
+        let mut storage = self.storage.clone();
+        storage.apply_delta(delta.storage())?;
+
+        let mut vault = self.vault.clone();
+        vault.apply_delta(delta.vault()).map_err(AccountError::AssetVaultError)?;
+
+        self.storage = storage;
+        self.vault = vault;
+        self.nonce = nonce;
+        self.hash.take();
+
+        Ok(())
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies that this account is described by the specified header.
+    ///
+    /// The commitments to the storage and code of this account are recomputed from their contents
+    /// rather than read from the account: the storage commitment is computed from the storage
+    /// items and maps, and the code commitment is computed from the procedures obtained by
+    /// compiling the account module with the specified assembler. Thus, this can be used to
+    /// validate account data received from an untrusted source against a trusted header.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The account module cannot be compiled with the specified assembler.
+    /// - The storage items of this account do not form a valid account storage.
+    /// - The ID, nonce, or any of the commitments of this account do not match the header.
+    pub fn verify_against(
+        &self,
+        header: &AccountHeader,
+        assembler: &Assembler,
+    ) -> Result<(), AccountError> {
+        // recompute the code commitment from the account module; the procedures of the received
+        // code are not used as they may not result from the compilation of the module, but their
+        // access levels are committed to by the code commitment and are thus carried over
+        let mut code = AccountCode::new(self.code.module().clone(), assembler)?;
+        for (root, access) in self.code.procedure_access() {
+            code = code.with_procedure_access(root, access)?;
+        }
+
+        // recompute the storage commitment from the storage items and maps
+        let items = self
+            .storage
+            .iter()
+            .map(|(index, value, slot_type)| (index, (slot_type, value)))
+            .collect();
+        let storage = AccountStorage::with_maps(items, self.storage.maps().clone())?;
+
+        let account_header = AccountHeader::new(
+            self.id,
+            self.nonce,
+            self.vault.commitment(),
+            storage.root(),
+            code.root(),
+        );
+        if &account_header != header {
+            return Err(AccountError::AccountHeaderMismatch(header.clone(), account_header));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that the hash of this account is equal to the specified hash.
+    ///
+    /// This is similar to [Account::verify_against()], but can be used when only the hash of the
+    /// account is trusted (e.g., when it is read from the account database of a block).
+    ///
+    /// # Errors
+    /// Returns an error if the hash of this account does not match the specified hash.
+    pub fn verify_against_hash(&self, hash: Digest) -> Result<(), AccountError> {
+        let account_hash = self.hash();
+        if account_hash != hash {
+            return Err(AccountError::AccountHashMismatch(hash, account_hash));
+        }
+
+        Ok(())
+    }
+}
+
+impl PartialEq for Account {
+    fn eq(&self, other: &Self) -> bool {
+        let Account { id, vault, storage, code, nonce, hash: _ } = self;
+
+        id == &other.id
+            && vault == &other.vault
+            && storage == &other.storage
+            && code == &other.code
+            && nonce == &other.nonce
+    }
 }
 
+impl Eq for Account {}
+
 // SERIALIZATION
 // ================================================================================================
 
 impl Serializable for Account {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        let Account { id, vault, storage, code, nonce } = self;
+        let Account { id, vault, storage, code, nonce, hash: _ } = self;
 
         id.write_into(target);
         vault.write_into(target);
@@ -223,11 +380,205 @@ pub fn hash_account(
     storage_root: Digest,
     code_root: Digest,
 ) -> Digest {
-    let mut elements = [ZERO; 16];
-    elements[0] = id.into();
-    elements[3] = nonce;
-    elements[4..8].copy_from_slice(&*vault_root);
-    elements[8..12].copy_from_slice(&*storage_root);
-    elements[12..].copy_from_slice(&*code_root);
-    Hasher::hash_elements(&elements)
+    AccountHeader::new(id, nonce, vault_root, storage_root, code_root).hash()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Account, AccountCode, AccountDelta, AccountError, AccountHeader, AccountId, AccountStorage,
+        AccountStorageDelta, AccountVaultDelta, Assembler, AssetVault, Deserializable, Felt,
+        ModuleAst, ProcedureAccess, Serializable, StorageSlotType,
+        ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        ZERO,
+    };
+    use crate::{assets::FungibleAsset, AssetVaultError, ONE};
+
+    fn build_account() -> Account {
+        let id = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let module = ModuleAst::parse("export.foo push.1 push.2 mul end").unwrap();
+        let code = AccountCode::new(module, &Assembler::default()).unwrap();
+        let storage =
+            AccountStorage::new(vec![(0, (StorageSlotType::default(), [ONE, ONE, ONE, ONE]))])
+                .unwrap();
+
+        Account::new(id, AssetVault::default(), storage, code, ONE)
+    }
+
+    #[test]
+    fn verify_account_against_header() {
+        let account = build_account();
+        let header = AccountHeader::from(&account);
+
+        // data received from an untrusted source
+        let received = Account::read_from_bytes(&account.to_bytes()).unwrap();
+        assert!(received.verify_against(&header, &Assembler::default()).is_ok());
+        assert!(received.verify_against_hash(header.hash()).is_ok());
+
+        // the access levels of the procedures are committed to by the code commitment
+        let foo = account.code().procedures()[0];
+        let restricted = Account::new(
+            account.id(),
+            AssetVault::default(),
+            account.storage().clone(),
+            account
+                .code()
+                .clone()
+                .with_procedure_access(foo, ProcedureAccess::Internal)
+                .unwrap(),
+            ONE,
+        );
+        let restricted_header = AccountHeader::from(&restricted);
+        assert!(restricted.verify_against(&restricted_header, &Assembler::default()).is_ok());
+        assert!(restricted.verify_against(&header, &Assembler::default()).is_err());
+
+        // a stale header does not describe the account
+        let stale = AccountHeader::new(
+            header.id(),
+            ZERO,
+            header.vault_root(),
+            header.storage_commitment(),
+            header.code_commitment(),
+        );
+        assert_eq!(
+            received.verify_against(&stale, &Assembler::default()),
+            Err(AccountError::AccountHeaderMismatch(stale.clone(), header.clone()))
+        );
+        assert_eq!(
+            received.verify_against_hash(stale.hash()),
+            Err(AccountError::AccountHashMismatch(stale.hash(), header.hash()))
+        );
+
+        // tampered storage does not match the header
+        let mut storage = received.storage().clone();
+        storage.set_item(0, [Felt::new(2), ONE, ONE, ONE]).unwrap();
+        let tampered = Account::new(
+            received.id(),
+            AssetVault::default(),
+            storage,
+            received.code().clone(),
+            ONE,
+        );
+        assert!(tampered.verify_against(&header, &Assembler::default()).is_err());
+        assert!(tampered.verify_against_hash(header.hash()).is_err());
+
+        // procedures which do not result from the compilation of the module are not trusted
+        let module = ModuleAst::parse("export.foo push.1 push.2 add end").unwrap();
+        let other_code = AccountCode::new(module, &Assembler::default()).unwrap();
+        let forged_code = AccountCode::from_parts(
+            other_code.module().clone(),
+            received.code().procedures().to_vec(),
+            received.code().procedure_access().map(|(_, access)| access).collect(),
+        );
+        let forged = Account::new(
+            received.id(),
+            AssetVault::default(),
+            received.storage().clone(),
+            forged_code,
+            ONE,
+        );
+        assert_eq!(forged.code().root(), header.code_commitment());
+        assert!(forged.verify_against(&header, &Assembler::default()).is_err());
+        let forged = Account::read_from_bytes(&forged.to_bytes()).unwrap();
+        assert!(forged.verify_against(&header, &Assembler::default()).is_err());
+    }
+
+    #[test]
+    fn account_hash_cache_is_sync() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let account = build_account();
+        let hash = account.hash();
+        assert_send_sync(&account);
+
+        // the cached hash is shared with the threads reading the account
+        std::thread::scope(|scope| {
+            scope.spawn(|| assert_eq!(account.hash(), hash));
+            scope.spawn(|| assert_eq!(account.hash(), hash));
+        });
+    }
+
+    #[test]
+    fn apply_delta_to_account() {
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let asset = |amount| FungibleAsset::new(faucet_id, amount).unwrap();
+        let mut account = build_account();
+
+        // added fungible assets are aggregated, and storage and nonce are updated
+        let delta = AccountDelta::new(
+            AccountStorageDelta {
+                cleared_items: vec![0],
+                updated_items: vec![(1, [ONE, ZERO, ZERO, ZERO])],
+                ..Default::default()
+            },
+            AccountVaultDelta {
+                added_assets: vec![asset(100)],
+                ..Default::default()
+            },
+            Some(Felt::new(2)),
+        )
+        .unwrap();
+        account.apply_delta(&delta).unwrap();
+        let delta = AccountDelta::new(
+            AccountStorageDelta::default(),
+            AccountVaultDelta {
+                added_assets: vec![asset(50)],
+                ..Default::default()
+            },
+            Some(Felt::new(3)),
+        )
+        .unwrap();
+        account.apply_delta(&delta).unwrap();
+
+        let mut expected_storage = build_account().storage().clone();
+        expected_storage.set_item(0, [ZERO; 4]).unwrap();
+        expected_storage.set_item(1, [ONE, ZERO, ZERO, ZERO]).unwrap();
+        let expected = Account::new(
+            account.id(),
+            AssetVault::new(&[asset(150).into()]).unwrap(),
+            expected_storage,
+            account.code().clone(),
+            Felt::new(3),
+        );
+        assert_eq!(account.hash(), expected.hash());
+
+        // a delta with a stale nonce is rejected
+        let delta = AccountDelta::new(
+            AccountStorageDelta::default(),
+            AccountVaultDelta {
+                removed_assets: vec![asset(10)],
+                ..Default::default()
+            },
+            Some(Felt::new(3)),
+        )
+        .unwrap();
+        assert_eq!(
+            account.apply_delta(&delta),
+            Err(AccountError::NonceMustBeMonotonicallyIncreasing(3, 3))
+        );
+
+        // removing more than the vault holds fails, and the account is left untouched
+        let delta = AccountDelta::new(
+            AccountStorageDelta {
+                updated_items: vec![(2, [ONE; 4])],
+                ..Default::default()
+            },
+            AccountVaultDelta {
+                removed_assets: vec![asset(200)],
+                ..Default::default()
+            },
+            Some(Felt::new(4)),
+        )
+        .unwrap();
+        assert!(matches!(
+            account.apply_delta(&delta),
+            Err(AccountError::AssetVaultError(
+                AssetVaultError::SubtractFungibleAssetBalanceError(_)
+            ))
+        ));
+        assert_eq!(account.hash(), expected.hash());
+    }
 }
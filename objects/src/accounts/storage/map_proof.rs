@@ -0,0 +1,86 @@
+use super::{AccountStorage, Digest, Word};
+use crate::crypto::merkle::{MerklePath, TieredSmtProof};
+
+// STORAGE MAP PROOF
+// ================================================================================================
+
+/// A proof of a key in a map stored in one of the slots of an account storage.
+///
+/// The proof consists of two parts:
+/// - A proof of the key in the map (i.e., an opening of the map tree), which commits to the root
+///   of the map.
+/// - A Merkle path from the map root (i.e., the value of the slot) to the storage commitment.
+///
+/// Thus, given the storage commitment of an account (e.g., from its [AccountHeader]), the proof
+/// can be used to verify the value stored under a key in a map of the account without access to
+/// the rest of the account storage.
+///
+/// [AccountHeader]: super::super::AccountHeader
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageMapProof {
+    slot_index: u8,
+    slot_path: MerklePath,
+    map_proof: TieredSmtProof,
+}
+
+impl StorageMapProof {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [StorageMapProof] from a proof of a key in the map stored in the specified
+    /// slot and the path from the slot to the storage commitment.
+    pub fn new(slot_index: u8, slot_path: MerklePath, map_proof: TieredSmtProof) -> Self {
+        Self { slot_index, slot_path, map_proof }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the slot storing the map.
+    pub fn slot_index(&self) -> u8 {
+        self.slot_index
+    }
+
+    /// Returns the Merkle path from the map root to the storage commitment.
+    pub fn slot_path(&self) -> &MerklePath {
+        &self.slot_path
+    }
+
+    /// Returns the proof of the key in the map.
+    pub fn map_proof(&self) -> &TieredSmtProof {
+        &self.map_proof
+    }
+
+    /// Returns the root of the map, i.e., the value of the storage slot.
+    pub fn map_root(&self) -> Digest {
+        self.map_proof.compute_root()
+    }
+
+    /// Returns the value stored under the specified key if the key is covered by this proof, or
+    /// None otherwise.
+    ///
+    /// Keys which are covered by the proof but are not present in the map are returned with an
+    /// empty value (i.e., [ZERO; 4]).
+    pub fn get(&self, key: &Digest) -> Option<Word> {
+        self.map_proof.get(key)
+    }
+
+    /// Returns the storage commitment implied by this proof.
+    pub fn compute_storage_commitment(&self) -> Digest {
+        self.slot_path
+            .compute_root(self.slot_index as u64, self.map_root())
+            .expect("slot index is within the storage tree")
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this proof attests that the specified value is stored under the specified
+    /// key in the map of an account storage with the specified commitment.
+    ///
+    /// An empty value (i.e., [ZERO; 4]) attests that the key is not present in the map.
+    pub fn verify(&self, key: &Digest, value: &Word, storage_commitment: &Digest) -> bool {
+        self.slot_path.depth() == AccountStorage::STORAGE_TREE_DEPTH
+            && self.get(key).as_ref() == Some(value)
+            && self.compute_storage_commitment() == *storage_commitment
+    }
+}
@@ -2,22 +2,31 @@ use super::{
     AccountError, BTreeMap, ByteReader, ByteWriter, Deserializable, DeserializationError, Digest,
     Felt, Hasher, Serializable, String, ToString, Vec, Word,
 };
-use crate::crypto::merkle::{NodeIndex, SimpleSmt};
+use crate::{
+    accounts::AccountStorageDelta,
+    crypto::merkle::{NodeIndex, SimpleSmt, TieredSmt},
+};
+
+mod map_proof;
+pub use map_proof::StorageMapProof;
 
 mod slot;
 pub use slot::StorageSlotType;
 
+mod typed_slot;
+pub use typed_slot::{StorageSlot, StorageValue};
+
 // TYPE ALIASES
 // ================================================================================================
 
 /// A type that represents a single storage slot item. The tuple contains the slot index of the item
 /// and the entry of the item.
-pub type SlotItem = (u8, StorageSlot);
+pub type SlotItem = (u8, StorageEntry);
 
 /// A type that represents a single storage slot entry. The tuple contains the type of the slot and
 /// the value of the slot - the value can be a raw value or a commitment to the underlying data
 /// structure.
-pub type StorageSlot = (StorageSlotType, Word);
+pub type StorageEntry = (StorageSlotType, Word);
 
 // ACCOUNT STORAGE
 // ================================================================================================
@@ -33,10 +42,15 @@ pub type StorageSlot = (StorageSlotType, Word);
 ///
 /// Storage slots are stored in a simple Sparse Merkle tree of depth 8. Slot 255 is always reserved
 /// and contains information about slot types of all other slots.
+///
+/// The contents of map slots can be tracked by the storage as well. In this case, the value of the
+/// slot is the root of a tiered sparse Merkle tree holding the map, and proofs of individual map
+/// entries against the storage commitment can be built via [AccountStorage::open()].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AccountStorage {
     slots: SimpleSmt,
     types: Vec<StorageSlotType>,
+    maps: BTreeMap<u8, TieredSmt>,
 }
 
 impl AccountStorage {
@@ -53,6 +67,24 @@ impl AccountStorage {
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of account storage initialized with the provided items.
     pub fn new(items: Vec<SlotItem>) -> Result<AccountStorage, AccountError> {
+        Self::with_maps(items, BTreeMap::new())
+    }
+
+    /// Returns a new instance of account storage initialized with the provided items and the
+    /// contents of the provided maps.
+    ///
+    /// Each map must be stored in a slot of a map type whose value is the root of the map.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Any of the items is stored in the reserved slot, or the items contain duplicate slots.
+    /// - Any of the items has an invalid slot type (see [StorageSlotType::is_valid()]).
+    /// - Any of the maps is stored in a slot which is not of a map type.
+    /// - The value of the slot of any of the maps is not the root of the map.
+    pub fn with_maps(
+        items: Vec<SlotItem>,
+        maps: BTreeMap<u8, TieredSmt>,
+    ) -> Result<AccountStorage, AccountError> {
         // initialize slot types vector
         let mut types = vec![StorageSlotType::default(); 256];
 
@@ -69,6 +101,9 @@ impl AccountStorage {
                 }
 
                 let (slot_type, slot_value) = x.1;
+                if !slot_type.is_valid() {
+                    return Err(AccountError::StorageSlotTypeInvalid(x.0, slot_type));
+                }
                 types[x.0 as usize] = slot_type;
                 Ok((x.0 as u64, slot_value))
             })
@@ -84,7 +119,22 @@ impl AccountStorage {
         let slots = SimpleSmt::with_leaves(Self::STORAGE_TREE_DEPTH, entires)
             .map_err(AccountError::DuplicateStorageItems)?;
 
-        Ok(Self { slots, types })
+        let storage = Self { slots, types, maps };
+
+        // make sure the maps are committed to by their slots
+        for (&index, map) in storage.maps.iter() {
+            if !matches!(storage.types[index as usize], StorageSlotType::Map { .. }) {
+                return Err(AccountError::StorageSlotNotMap(index));
+            }
+            if storage.get_item(index) != map.root() {
+                return Err(AccountError::StorageSlotValueInvalid(
+                    index,
+                    "slot value is not the root of the map".to_string(),
+                ));
+            }
+        }
+
+        Ok(storage)
     }
 
     // PUBLIC ACCESSORS
@@ -124,13 +174,239 @@ impl AccountStorage {
         Hasher::hash_elements(&self.types.iter().map(Felt::from).collect::<Vec<_>>())
     }
 
+    /// Returns an iterator over the slots of this storage which are in use, i.e., which are not
+    /// empty or are not of the default type.
+    ///
+    /// Each item is a tuple of the slot index, the value of the slot, and the type of the slot.
+    /// The slot reserved for the slot types commitment is not included.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, Word, StorageSlotType)> + '_ {
+        (0..Self::SLOT_TYPES_COMMITMENT_INDEX).filter_map(|index| {
+            let value = Word::from(self.get_item(index));
+            let slot_type = self.types[index as usize];
+            if value == SimpleSmt::EMPTY_VALUE && slot_type.is_default() {
+                None
+            } else {
+                Some((index, value, slot_type))
+            }
+        })
+    }
+
+    /// Returns the number of slots of this storage which are in use, i.e., the number of items
+    /// returned by [AccountStorage::iter()].
+    pub fn len_used(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns a reference to the maps tracked by this storage, keyed by their slot indexes.
+    pub fn maps(&self) -> &BTreeMap<u8, TieredSmt> {
+        &self.maps
+    }
+
+    /// Returns the value stored under the specified key in the map at the specified slot.
+    ///
+    /// If the key is not present in the map, [ZERO; 4] is returned.
+    ///
+    /// # Errors
+    /// Returns an error if the contents of the map at the specified slot are not tracked by this
+    /// storage.
+    pub fn get_map_item(&self, index: u8, key: Digest) -> Result<Word, AccountError> {
+        let map = self.maps.get(&index).ok_or(AccountError::StorageMapNotFound(index))?;
+        Ok(map.get_value(key))
+    }
+
+    /// Returns a proof of the specified key in the map at the specified slot against the
+    /// commitment to this storage.
+    ///
+    /// The proof can be used to verify the value stored under the key (or its absence) given only
+    /// the storage commitment, e.g., by light clients or when reading storage of foreign accounts.
+    ///
+    /// # Errors
+    /// Returns an error if the contents of the map at the specified slot are not tracked by this
+    /// storage.
+    pub fn open(&self, index: u8, key: Digest) -> Result<StorageMapProof, AccountError> {
+        let map = self.maps.get(&index).ok_or(AccountError::StorageMapNotFound(index))?;
+        let slot_index = NodeIndex::new(Self::STORAGE_TREE_DEPTH, index as u64)
+            .expect("index is u8 - index within range");
+        let slot_path = self.slots.get_path(slot_index).expect("index is u8 - index within range");
+
+        Ok(StorageMapProof::new(index, slot_path, map.prove(key)))
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that this storage conforms to the specified layout, i.e., to the slot types declared
+    /// by the code of the account (e.g., via [StorageSlot] handles).
+    ///
+    /// Slots which are not declared by the layout must be empty and of the default type, as the
+    /// account code would otherwise have no way of interpreting their contents.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The layout declares the slot reserved for the slot types commitment.
+    /// - The layout declares an invalid slot type.
+    /// - The type of any declared slot differs from the type of the slot in this storage.
+    /// - Any of the slots not declared by the layout is not empty or is not of the default type.
+    pub fn validate_layout(&self, layout: &[(u8, StorageSlotType)]) -> Result<(), AccountError> {
+        let mut declared = [false; 256];
+        for &(index, expected) in layout {
+            if index == Self::SLOT_TYPES_COMMITMENT_INDEX {
+                return Err(AccountError::StorageSlotIsReserved(index));
+            }
+            if !expected.is_valid() {
+                return Err(AccountError::StorageSlotTypeInvalid(index, expected));
+            }
+
+            let actual = self.types[index as usize];
+            if actual != expected {
+                return Err(AccountError::StorageSlotTypeMismatch(index, expected, actual));
+            }
+            declared[index as usize] = true;
+        }
+
+        for index in 0..Self::SLOT_TYPES_COMMITMENT_INDEX {
+            if declared[index as usize] {
+                continue;
+            }
+            if !self.types[index as usize].is_default()
+                || Word::from(self.get_item(index)) != SimpleSmt::EMPTY_VALUE
+            {
+                return Err(AccountError::StorageSlotNotDeclared(index));
+            }
+        }
+
+        Ok(())
+    }
+
     // PUBLIC MODIFIERS
     // --------------------------------------------------------------------------------------------
-    /// Sets an item from the storage at the specified index.
-    pub fn set_item(&mut self, index: u8, value: Word) -> Word {
-        self.slots
+    /// Sets an item from the storage at the specified index and returns the previous value of the
+    /// item.
+    ///
+    /// If the contents of a map are tracked at the specified index, the slot must keep committing
+    /// to them, i.e., the entries of such a map must be updated via
+    /// [AccountStorage::set_map_item()] instead.
+    ///
+    /// # Errors
+    /// Returns an error if the contents of a map are tracked at the specified index and the value
+    /// is not the root of the map.
+    pub fn set_item(&mut self, index: u8, value: Word) -> Result<Word, AccountError> {
+        if let Some(map) = self.maps.get(&index) {
+            if Word::from(map.root()) != value {
+                return Err(AccountError::StorageSlotValueInvalid(
+                    index,
+                    "slot value is not the root of the map tracked at the slot".to_string(),
+                ));
+            }
+        }
+
+        Ok(self
+            .slots
             .update_leaf(index as u64, value)
-            .expect("index is u8 - index within range")
+            .expect("index is u8 - index within range"))
+    }
+
+    /// Sets the value stored under the specified key in the map at the specified slot, and
+    /// updates the value of the slot to the new root of the map. Returns the previous value
+    /// stored under the key.
+    ///
+    /// Setting the value to [ZERO; 4] removes the key from the map.
+    ///
+    /// # Errors
+    /// Returns an error if the contents of the map at the specified slot are not tracked by this
+    /// storage.
+    pub fn set_map_item(
+        &mut self,
+        index: u8,
+        key: Digest,
+        value: Word,
+    ) -> Result<Word, AccountError> {
+        let map = self.maps.get_mut(&index).ok_or(AccountError::StorageMapNotFound(index))?;
+        let old_value = map.insert(key, value);
+        let map_root = map.root();
+
+        self.slots
+            .update_leaf(index as u64, map_root.into())
+            .expect("index is u8 - index within range");
+
+        Ok(old_value)
+    }
+
+    /// Applies the provided storage delta to this storage.
+    ///
+    /// Entry updates of the maps tracked by this storage are applied to the maps, so that the
+    /// storage keeps tracking them; if the delta also records the new value of the slot of such a
+    /// map (i.e., the slot is updated or cleared), the value must match the root of the updated
+    /// map. The maps which are not tracked are updated by setting their slots to the new roots
+    /// recorded by the delta, and their entry updates are ignored.
+    ///
+    /// The delta is applied atomically: if an error is returned, this storage is not modified.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Entry updates are recorded for a slot which is not of a map type.
+    /// - The new root of a tracked map recorded by the delta differs from the root of the map
+    ///   after its entries are updated.
+    pub fn apply_delta(&mut self, delta: &AccountStorageDelta) -> Result<(), AccountError> {
+        let mut storage = self.clone();
+        let is_tracked_map = |index: u8| {
+            self.maps.contains_key(&index)
+                && delta.updated_maps.iter().any(|(map_index, _)| *map_index == index)
+        };
+
+        // the slots of tracked maps are updated by applying the map entry updates below, as
+        // setting them directly would fail unless they already commit to the new roots
+        let slot_updates = delta
+            .cleared_items
+            .iter()
+            .map(|&index| (index, Word::default()))
+            .chain(delta.updated_items.iter().copied());
+        for (index, value) in slot_updates {
+            if !is_tracked_map(index) {
+                storage.set_item(index, value)?;
+            }
+        }
+
+        for (index, map_delta) in delta.updated_maps.iter() {
+            if !matches!(storage.types[*index as usize], StorageSlotType::Map { .. }) {
+                return Err(AccountError::StorageSlotNotMap(*index));
+            }
+
+            // the slot of an untracked map was already set to the new root of the map (if any)
+            if !storage.maps.contains_key(index) {
+                continue;
+            }
+
+            let leaves = map_delta
+                .cleared_leaves
+                .iter()
+                .map(|key| (*key, Word::default()))
+                .chain(map_delta.updated_leaves.iter().copied());
+            for (key, value) in leaves {
+                storage.set_map_item(*index, key, value)?;
+            }
+
+            let new_root = if delta.cleared_items.contains(index) {
+                Some(Word::default())
+            } else {
+                delta
+                    .updated_items
+                    .iter()
+                    .find(|(item_index, _)| item_index == index)
+                    .map(|(_, value)| *value)
+            };
+            if let Some(root) = new_root {
+                if Word::from(storage.get_item(*index)) != root {
+                    return Err(AccountError::StorageSlotValueInvalid(
+                        *index,
+                        "slot value is not the root of the updated map".to_string(),
+                    ));
+                }
+            }
+        }
+
+        *self = storage;
+        Ok(())
     }
 }
 
@@ -171,6 +447,18 @@ impl Serializable for AccountStorage {
             target.write_u8(idx as u8);
             target.write(value);
         }
+
+        // serialize the contents of the tracked maps
+        target.write_u8(self.maps.len() as u8);
+        for (&idx, map) in self.maps.iter() {
+            let entries = map.iter().collect::<Vec<_>>();
+            target.write_u8(idx);
+            target.write_u64(entries.len() as u64);
+            for (key, value) in entries {
+                target.write(key);
+                target.write(value);
+            }
+        }
     }
 }
 
@@ -196,7 +484,22 @@ impl Deserializable for AccountStorage {
             items.push((idx, (slot_type, slot_value)));
         }
 
-        Self::new(items).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+        // read the contents of the tracked maps
+        let mut maps = BTreeMap::new();
+        let num_maps = source.read_u8()?;
+        for _ in 0..num_maps {
+            let idx = source.read_u8()?;
+            let num_entries = source.read_u64()?;
+            let entries = (0..num_entries)
+                .map(|_| Ok((source.read::<Digest>()?, source.read::<Word>()?)))
+                .collect::<Result<Vec<_>, DeserializationError>>()?;
+            let map = TieredSmt::with_entries(entries)
+                .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+            maps.insert(idx, map);
+        }
+
+        Self::with_maps(items, maps)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
     }
 }
 
@@ -205,8 +508,29 @@ impl Deserializable for AccountStorage {
 
 #[cfg(test)]
 mod tests {
-    use super::{AccountStorage, Deserializable, Serializable, StorageSlotType};
-    use crate::{ONE, ZERO};
+    use super::{
+        AccountError, AccountStorage, AccountStorageDelta, BTreeMap, Deserializable, Digest,
+        Serializable, StorageSlotType, TieredSmt,
+    };
+    use crate::{accounts::StorageMapDelta, Felt, ONE, ZERO};
+
+    fn storage_with_map() -> AccountStorage {
+        let map = TieredSmt::with_entries([
+            (Digest::new([ONE, ZERO, ZERO, ZERO]), [Felt::new(10), ZERO, ZERO, ZERO]),
+            (Digest::new([ONE, ONE, ZERO, ZERO]), [Felt::new(20), ZERO, ZERO, ZERO]),
+        ])
+        .unwrap();
+        let map_root = map.root();
+
+        AccountStorage::with_maps(
+            vec![
+                (0, (StorageSlotType::default(), [ONE, ONE, ONE, ONE])),
+                (3, (StorageSlotType::Map { value_arity: 0 }, *map_root)),
+            ],
+            BTreeMap::from([(3, map)]),
+        )
+        .unwrap()
+    }
 
     #[test]
     fn account_storage_serialization() {
@@ -237,5 +561,207 @@ mod tests {
         .unwrap();
         let bytes = storage.to_bytes();
         assert_eq!(storage, AccountStorage::read_from_bytes(&bytes).unwrap());
+
+        // storage with a tracked map
+        let storage = storage_with_map();
+        let bytes = storage.to_bytes();
+        assert_eq!(storage, AccountStorage::read_from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn account_storage_map_proofs() {
+        let mut storage = storage_with_map();
+        let key = Digest::new([ONE, ONE, ZERO, ZERO]);
+        let value = [Felt::new(20), ZERO, ZERO, ZERO];
+
+        // proof of a present key
+        let proof = storage.open(3, key).unwrap();
+        assert_eq!(proof.map_root(), storage.get_item(3));
+        assert!(proof.verify(&key, &value, &storage.root()));
+        assert!(!proof.verify(&key, &[ONE, ZERO, ZERO, ZERO], &storage.root()));
+
+        // proof of an absent key
+        let absent_key = Digest::new([ZERO, ZERO, ONE, ZERO]);
+        let proof = storage.open(3, absent_key).unwrap();
+        assert!(proof.verify(&absent_key, &[ZERO; 4], &storage.root()));
+
+        // updating the map updates the storage commitment
+        let old_root = storage.root();
+        let new_value = [Felt::new(5), ZERO, ZERO, ZERO];
+        assert_eq!(storage.set_map_item(3, key, new_value).unwrap(), value);
+        assert_eq!(storage.get_map_item(3, key).unwrap(), new_value);
+        assert_ne!(storage.root(), old_root);
+
+        let proof = storage.open(3, key).unwrap();
+        assert!(proof.verify(&key, &new_value, &storage.root()));
+        assert!(!proof.verify(&key, &new_value, &old_root));
+
+        // maps are not tracked for other slots
+        assert_eq!(storage.open(0, key), Err(AccountError::StorageMapNotFound(0)));
+    }
+
+    #[test]
+    fn account_storage_set_item_keeps_tracked_maps() {
+        let mut storage = storage_with_map();
+        let map_root = storage.get_item(3);
+        let key = Digest::new([ONE, ZERO, ZERO, ZERO]);
+
+        // the slot of a tracked map cannot be set to a value other than the root of the map
+        assert!(matches!(
+            storage.set_item(3, [ONE, ONE, ONE, ONE]),
+            Err(AccountError::StorageSlotValueInvalid(3, _))
+        ));
+        assert_eq!(storage.get_item(3), map_root);
+        assert_eq!(storage.get_map_item(3, key).unwrap(), [Felt::new(10), ZERO, ZERO, ZERO]);
+
+        // setting the slot to the root of the map keeps the map
+        assert_eq!(storage.set_item(3, *map_root).unwrap(), *map_root);
+        assert_eq!(storage.get_map_item(3, key).unwrap(), [Felt::new(10), ZERO, ZERO, ZERO]);
+
+        // other slots can be set freely
+        assert_eq!(storage.set_item(0, [ZERO; 4]).unwrap(), [ONE, ONE, ONE, ONE]);
+    }
+
+    #[test]
+    fn account_storage_apply_delta_keeps_tracked_maps() {
+        let mut storage = storage_with_map();
+        let key = Digest::new([ONE, ZERO, ZERO, ZERO]);
+        let new_key = Digest::new([ZERO, ONE, ZERO, ZERO]);
+        let value = [Felt::new(30), ZERO, ZERO, ZERO];
+
+        let mut expected = storage.clone();
+        expected.set_map_item(3, key, [ZERO; 4]).unwrap();
+        expected.set_map_item(3, new_key, value).unwrap();
+        let new_root = expected.get_item(3);
+
+        // the delta updates both the slot of the map and the entries of the map
+        let map_delta = StorageMapDelta {
+            cleared_leaves: vec![key],
+            updated_leaves: vec![(new_key, value)],
+        };
+        let delta = AccountStorageDelta {
+            cleared_items: vec![],
+            updated_items: vec![(0, [ZERO; 4]), (3, *new_root)],
+            updated_maps: vec![(3, map_delta)],
+        };
+        storage.apply_delta(&delta).unwrap();
+        expected.set_item(0, [ZERO; 4]).unwrap();
+        assert_eq!(storage, expected);
+        assert_eq!(storage.get_map_item(3, new_key).unwrap(), value);
+
+        // the map is still tracked, so later entry updates are applied as well
+        let map_delta = StorageMapDelta {
+            cleared_leaves: vec![],
+            updated_leaves: vec![(key, value)],
+        };
+        let delta = AccountStorageDelta {
+            cleared_items: vec![],
+            updated_items: vec![],
+            updated_maps: vec![(3, map_delta.clone())],
+        };
+        storage.apply_delta(&delta).unwrap();
+        assert_eq!(storage.get_map_item(3, key).unwrap(), value);
+        assert_ne!(storage.get_item(3), new_root);
+
+        // a slot value which is not the root of the updated map is rejected atomically
+        let delta = AccountStorageDelta {
+            cleared_items: vec![],
+            updated_items: vec![(0, [ONE; 4]), (3, *new_root)],
+            updated_maps: vec![(3, map_delta)],
+        };
+        let expected = storage.clone();
+        assert!(matches!(
+            storage.apply_delta(&delta),
+            Err(AccountError::StorageSlotValueInvalid(3, _))
+        ));
+        assert_eq!(storage, expected);
+    }
+
+    #[test]
+    fn account_storage_maps_must_match_slots() {
+        let map = TieredSmt::with_entries([(
+            Digest::new([ONE, ZERO, ZERO, ZERO]),
+            [ONE, ZERO, ZERO, ZERO],
+        )])
+        .unwrap();
+
+        // the map is stored in a value slot
+        let result = AccountStorage::with_maps(
+            vec![(1, (StorageSlotType::default(), *map.root()))],
+            BTreeMap::from([(1, map.clone())]),
+        );
+        assert_eq!(result, Err(AccountError::StorageSlotNotMap(1)));
+
+        // the slot does not commit to the map
+        let result = AccountStorage::with_maps(
+            vec![(1, (StorageSlotType::Map { value_arity: 0 }, [ONE, ONE, ZERO, ZERO]))],
+            BTreeMap::from([(1, map)]),
+        );
+        assert!(matches!(result, Err(AccountError::StorageSlotValueInvalid(1, _))));
+    }
+
+    #[test]
+    fn account_storage_slot_types_are_validated() {
+        // invalid slot types are rejected at construction
+        let slot_type = StorageSlotType::Array { depth: 1, value_arity: 0 };
+        let result = AccountStorage::new(vec![(0, (slot_type, [ONE, ZERO, ZERO, ZERO]))]);
+        assert_eq!(result, Err(AccountError::StorageSlotTypeInvalid(0, slot_type)));
+
+        let storage = AccountStorage::new(vec![
+            (0, (StorageSlotType::Value { value_arity: 0 }, [ONE, ONE, ZERO, ZERO])),
+            (1, (StorageSlotType::Map { value_arity: 0 }, [ONE, ZERO, ZERO, ZERO])),
+        ])
+        .unwrap();
+
+        // the storage conforms to the declared layout
+        let layout = [
+            (0, StorageSlotType::Value { value_arity: 0 }),
+            (1, StorageSlotType::Map { value_arity: 0 }),
+            (2, StorageSlotType::Value { value_arity: 0 }),
+        ];
+        assert_eq!(storage.validate_layout(&layout), Ok(()));
+
+        // a map root is provided for a value slot
+        let layout = [
+            (0, StorageSlotType::Value { value_arity: 0 }),
+            (1, StorageSlotType::Value { value_arity: 0 }),
+        ];
+        assert_eq!(
+            storage.validate_layout(&layout),
+            Err(AccountError::StorageSlotTypeMismatch(
+                1,
+                StorageSlotType::Value { value_arity: 0 },
+                StorageSlotType::Map { value_arity: 0 }
+            ))
+        );
+
+        // a value is provided for a slot which is not declared
+        let layout = [(1, StorageSlotType::Map { value_arity: 0 })];
+        assert_eq!(storage.validate_layout(&layout), Err(AccountError::StorageSlotNotDeclared(0)));
+
+        // the reserved slot cannot be declared
+        let layout = [(255, StorageSlotType::Value { value_arity: 64 })];
+        assert_eq!(storage.validate_layout(&layout), Err(AccountError::StorageSlotIsReserved(255)));
+    }
+
+    #[test]
+    fn account_storage_iter() {
+        let storage = AccountStorage::new(vec![
+            (0, (StorageSlotType::Value { value_arity: 0 }, [ONE, ONE, ZERO, ZERO])),
+            (2, (StorageSlotType::Map { value_arity: 0 }, [ZERO; 4])),
+            (3, (StorageSlotType::Value { value_arity: 0 }, [ZERO; 4])),
+        ])
+        .unwrap();
+
+        let items = storage.iter().collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                (0, [ONE, ONE, ZERO, ZERO], StorageSlotType::Value { value_arity: 0 }),
+                (2, [ZERO; 4], StorageSlotType::Map { value_arity: 0 }),
+            ]
+        );
+        assert_eq!(storage.len_used(), 2);
+        assert_eq!(AccountStorage::new(vec![]).unwrap().len_used(), 0);
     }
 }
@@ -0,0 +1,245 @@
+use core::{fmt, marker::PhantomData};
+
+use super::{AccountError, AccountStorage, Digest, Felt, SlotItem, StorageSlotType, Word};
+use crate::{utils::string::ToString, StarkField, ZERO};
+
+// STORAGE VALUE
+// ================================================================================================
+
+/// Defines how a Rust value is encoded into a single storage slot.
+///
+/// The encoding must be a bijection between the values of the type and a subset of words, such
+/// that a value written into a slot can be read back from it.
+pub trait StorageValue: Sized {
+    /// The type of the slots holding values of this type.
+    const SLOT_TYPE: StorageSlotType;
+
+    /// Returns the word encoding this value.
+    fn to_slot_word(&self) -> Word;
+
+    /// Decodes a value from the provided word.
+    ///
+    /// # Errors
+    /// Returns an error message if the word is not a valid encoding of a value of this type.
+    fn from_slot_word(word: Word) -> Result<Self, &'static str>;
+}
+
+impl StorageValue for Word {
+    const SLOT_TYPE: StorageSlotType = StorageSlotType::Value { value_arity: 0 };
+
+    fn to_slot_word(&self) -> Word {
+        *self
+    }
+
+    fn from_slot_word(word: Word) -> Result<Self, &'static str> {
+        Ok(word)
+    }
+}
+
+impl StorageValue for Digest {
+    const SLOT_TYPE: StorageSlotType = StorageSlotType::Value { value_arity: 0 };
+
+    fn to_slot_word(&self) -> Word {
+        (*self).into()
+    }
+
+    fn from_slot_word(word: Word) -> Result<Self, &'static str> {
+        Ok(word.into())
+    }
+}
+
+/// A single element is stored as `[value, 0, 0, 0]`.
+impl StorageValue for Felt {
+    const SLOT_TYPE: StorageSlotType = StorageSlotType::Value { value_arity: 0 };
+
+    fn to_slot_word(&self) -> Word {
+        [*self, ZERO, ZERO, ZERO]
+    }
+
+    fn from_slot_word(word: Word) -> Result<Self, &'static str> {
+        if word[1..] != [ZERO; 3] {
+            return Err("a single element slot must have its last three elements set to zero");
+        }
+        Ok(word[0])
+    }
+}
+
+/// A [u64] is stored as `[lo, hi, 0, 0]` where `lo` and `hi` are its 32-bit limbs.
+impl StorageValue for u64 {
+    const SLOT_TYPE: StorageSlotType = StorageSlotType::Value { value_arity: 0 };
+
+    fn to_slot_word(&self) -> Word {
+        [Felt::from(*self as u32), Felt::from((*self >> 32) as u32), ZERO, ZERO]
+    }
+
+    fn from_slot_word(word: Word) -> Result<Self, &'static str> {
+        let lo = u32::try_from(word[0].as_int()).map_err(|_| "low limb is not a u32 value")?;
+        let hi = u32::try_from(word[1].as_int()).map_err(|_| "high limb is not a u32 value")?;
+        if word[2..] != [ZERO; 2] {
+            return Err("a u64 slot must have its last two elements set to zero");
+        }
+        Ok(((hi as u64) << 32) | lo as u64)
+    }
+}
+
+// STORAGE SLOT
+// ================================================================================================
+
+/// A handle to a storage slot holding values of type `T`.
+///
+/// Storage slot handles are intended to be defined as constants next to the code which defines
+/// the storage layout of an account (e.g., by the account component builders of miden-lib), such
+/// that Rust code reading or writing account storage refers to slots via the handle rather than
+/// via a raw index. This way, a value of a wrong type cannot be written to a slot, and the
+/// encoding of the value is defined in a single place by the [StorageValue] implementation.
+pub struct StorageSlot<T> {
+    index: u8,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T: StorageValue> StorageSlot<T> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a handle to the storage slot at the specified index.
+    ///
+    /// # Panics
+    /// Panics if the index refers to the slot reserved for the slot types commitment.
+    pub const fn new(index: u8) -> Self {
+        assert!(
+            index != AccountStorage::SLOT_TYPES_COMMITMENT_INDEX,
+            "slot types commitment slot cannot be used as a typed slot"
+        );
+        Self { index, _value: PhantomData }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of this slot.
+    pub const fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the type of this slot.
+    pub const fn slot_type(&self) -> StorageSlotType {
+        T::SLOT_TYPE
+    }
+
+    /// Returns the storage item which initializes this slot with the specified value.
+    ///
+    /// The returned item can be passed to [AccountStorage::new()].
+    pub fn item(&self, value: &T) -> SlotItem {
+        (self.index, (T::SLOT_TYPE, value.to_slot_word()))
+    }
+
+    /// Reads the value of this slot from the provided storage.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The type of the slot in the provided storage differs from the type of this slot.
+    /// - The content of the slot is not a valid encoding of a value of type `T`.
+    pub fn read(&self, storage: &AccountStorage) -> Result<T, AccountError> {
+        self.check_slot_type(storage)?;
+        T::from_slot_word(storage.get_item(self.index).into())
+            .map_err(|msg| AccountError::StorageSlotValueInvalid(self.index, msg.to_string()))
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the specified value into this slot of the provided storage.
+    ///
+    /// # Errors
+    /// Returns an error if the type of the slot in the provided storage differs from the type of
+    /// this slot, or if the slot commits to a map tracked by the storage.
+    pub fn write(&self, storage: &mut AccountStorage, value: &T) -> Result<(), AccountError> {
+        self.check_slot_type(storage)?;
+        storage.set_item(self.index, value.to_slot_word())?;
+        Ok(())
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that the slot of the provided storage has the type of this slot.
+    fn check_slot_type(&self, storage: &AccountStorage) -> Result<(), AccountError> {
+        let actual = storage.slot_types()[self.index as usize];
+        if actual != T::SLOT_TYPE {
+            return Err(AccountError::StorageSlotTypeMismatch(self.index, T::SLOT_TYPE, actual));
+        }
+        Ok(())
+    }
+}
+
+// Implemented manually to avoid requiring `T` to implement the traits.
+
+impl<T> Clone for StorageSlot<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StorageSlot<T> {}
+
+impl<T> PartialEq for StorageSlot<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for StorageSlot<T> {}
+
+impl<T> fmt::Debug for StorageSlot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageSlot")
+            .field("index", &self.index)
+            .field("value", &core::any::type_name::<T>())
+            .finish()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountStorage, StorageSlot, StorageSlotType, Word};
+    use crate::{AccountError, Felt, ONE, ZERO};
+
+    const COUNTER: StorageSlot<u64> = StorageSlot::new(1);
+    const OWNER_KEY: StorageSlot<Word> = StorageSlot::new(2);
+
+    #[test]
+    fn typed_slots_round_trip() {
+        let counter = (7 << 32) | 5;
+        let mut storage =
+            AccountStorage::new(vec![COUNTER.item(&counter), OWNER_KEY.item(&[ONE; 4])]).unwrap();
+
+        assert_eq!(storage.get_item(1), [Felt::new(5), Felt::new(7), ZERO, ZERO].into());
+        assert_eq!(COUNTER.read(&storage), Ok(counter));
+        assert_eq!(OWNER_KEY.read(&storage), Ok([ONE; 4]));
+
+        COUNTER.write(&mut storage, &(counter + 1)).unwrap();
+        assert_eq!(COUNTER.read(&storage), Ok(counter + 1));
+    }
+
+    #[test]
+    fn typed_slot_rejects_invalid_content() {
+        let mut storage = AccountStorage::new(vec![
+            OWNER_KEY.item(&[ONE; 4]),
+            (3, (StorageSlotType::Map { value_arity: 0 }, [ONE; 4])),
+        ])
+        .unwrap();
+
+        // slot 2 holds a word which is not a valid u64 encoding
+        let slot = StorageSlot::<u64>::new(2);
+        assert!(matches!(slot.read(&storage), Err(AccountError::StorageSlotValueInvalid(2, _))));
+
+        // slot 3 holds a map commitment
+        let slot = StorageSlot::<Word>::new(3);
+        assert!(matches!(
+            slot.write(&mut storage, &[ZERO; 4]),
+            Err(AccountError::StorageSlotTypeMismatch(3, ..))
+        ));
+    }
+}
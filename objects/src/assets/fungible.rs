@@ -1,8 +1,6 @@
 use core::fmt;
 
-use super::{
-    parse_word, AccountId, AccountType, Asset, AssetError, Felt, StarkField, ToString, Word, ZERO,
-};
+use super::{parse_word, AccountId, Asset, AssetError, Felt, StarkField, ToString, Word, ZERO};
 
 // FUNGIBLE ASSET
 // ================================================================================================
@@ -119,7 +117,7 @@ impl FungibleAsset {
     /// - The faucet_id is not a valid fungible faucet ID.
     /// - The provided amount is greater than 2^63 - 1.
     fn validate(&self) -> Result<(), AssetError> {
-        if !matches!(self.faucet_id.account_type(), AccountType::FungibleFaucet) {
+        if !self.faucet_id.is_fungible_faucet() {
             return Err(AssetError::not_a_fungible_faucet_id(self.faucet_id));
         }
 
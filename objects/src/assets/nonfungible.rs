@@ -1,8 +1,7 @@
 use core::fmt;
 
 use super::{
-    parse_word, AccountId, AccountType, Asset, AssetError, Felt, Hasher, StarkField, ToString, Vec,
-    Word,
+    parse_word, AccountId, Asset, AssetError, Felt, Hasher, StarkField, ToString, Vec, Word,
 };
 
 /// Position of the faucet_id inside the [NonFungibleAsset] word.
@@ -46,7 +45,7 @@ impl NonFungibleAsset {
     /// # Errors
     /// Returns an error if the provided faucet ID is not for a non-fungible asset faucet.
     pub fn from_parts(faucet_id: AccountId, mut data_hash: Word) -> Result<Self, AssetError> {
-        if !matches!(faucet_id.account_type(), AccountType::NonFungibleFaucet) {
+        if !faucet_id.is_non_fungible_faucet() {
             return Err(AssetError::not_a_non_fungible_faucet_id(faucet_id));
         }
         // set the element 1 to the faucet_id
@@ -94,7 +93,7 @@ impl NonFungibleAsset {
         let faucet_id = AccountId::try_from(self.0[FAUCET_ID_POS])
             .map_err(|e| AssetError::InvalidAccountId(e.to_string()))?;
 
-        if !matches!(faucet_id.account_type(), AccountType::NonFungibleFaucet) {
+        if !faucet_id.is_non_fungible_faucet() {
             return Err(AssetError::not_a_fungible_faucet_id(faucet_id));
         }
 
@@ -173,7 +172,7 @@ impl NonFungibleAssetDetails {
     /// # Errors
     /// Returns an error if the provided faucet ID is not for a non-fungible asset faucet.
     pub fn new(faucet_id: AccountId, asset_data: Vec<u8>) -> Result<Self, AssetError> {
-        if !matches!(faucet_id.account_type(), AccountType::NonFungibleFaucet) {
+        if !faucet_id.is_non_fungible_faucet() {
             return Err(AssetError::not_a_non_fungible_faucet_id(faucet_id));
         }
 
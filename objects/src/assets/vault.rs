@@ -1,8 +1,11 @@
 use super::{
-    AccountId, AccountType, Asset, ByteReader, ByteWriter, Deserializable, DeserializationError,
-    FungibleAsset, NonFungibleAsset, Serializable, ToString, Vec, ZERO,
+    AccountId, Asset, ByteReader, ByteWriter, Deserializable, DeserializationError, FungibleAsset,
+    NonFungibleAsset, Serializable, ToString, Vec, ZERO,
+};
+use crate::{
+    accounts::AccountVaultDelta, crypto::merkle::TieredSmt, utils::collections::BTreeMap,
+    AssetVaultError, Digest, Word,
 };
-use crate::{crypto::merkle::TieredSmt, AssetVaultError, Digest};
 
 // ASSET VAULT
 // ================================================================================================
@@ -63,7 +66,7 @@ impl AssetVault {
     /// # Errors
     /// Returns an error if the specified ID is not an ID of a fungible asset faucet.
     pub fn get_balance(&self, faucet_id: AccountId) -> Result<u64, AssetVaultError> {
-        if !matches!(faucet_id.account_type(), AccountType::FungibleFaucet) {
+        if !faucet_id.is_fungible_faucet() {
             return Err(AssetVaultError::NotAFungibleFaucetId(faucet_id));
         }
 
@@ -87,6 +90,95 @@ impl AssetVault {
     // PUBLIC MODIFIERS
     // --------------------------------------------------------------------------------------------
 
+    /// Applies the provided vault delta to this vault.
+    ///
+    /// The delta is applied atomically: the final value of every leaf affected by the delta is
+    /// computed first, and the vault is modified only if the entire delta can be applied. Each
+    /// affected leaf of the underlying tree is then updated exactly once, regardless of how many
+    /// assets in the delta map to it.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The total value of fungible assets issued by the same faucet would be greater than or
+    ///   equal to 2^63.
+    /// - A removed fungible asset is not found in the vault, or the vault holds a smaller amount
+    ///   of it than is being removed.
+    /// - An added non-fungible asset is already in the vault, or a removed non-fungible asset is
+    ///   not in the vault.
+    pub fn apply_delta(&mut self, delta: &AccountVaultDelta) -> Result<(), AssetVaultError> {
+        // compute the final values of all affected leaves without modifying the tree
+        let mut updates = BTreeMap::<Digest, Word>::new();
+
+        for asset in delta.added() {
+            let key: Digest = asset.vault_key().into();
+            let current = self.pending_value(&updates, key);
+            let new = match asset {
+                Asset::Fungible(asset) => {
+                    if current == TieredSmt::EMPTY_VALUE {
+                        asset.into()
+                    } else {
+                        FungibleAsset::new_unchecked(current)
+                            .add(asset)
+                            .map_err(AssetVaultError::AddFungibleAssetBalanceError)?
+                            .into()
+                    }
+                },
+                Asset::NonFungible(asset) => {
+                    if current != TieredSmt::EMPTY_VALUE {
+                        return Err(AssetVaultError::DuplicateNonFungibleAsset(asset));
+                    }
+                    asset.into()
+                },
+            };
+            updates.insert(key, new);
+        }
+
+        for asset in delta.removed() {
+            let key: Digest = asset.vault_key().into();
+            let current = self.pending_value(&updates, key);
+            let new = match asset {
+                Asset::Fungible(asset) => {
+                    if current == TieredSmt::EMPTY_VALUE {
+                        return Err(AssetVaultError::FungibleAssetNotFound(asset));
+                    }
+                    let mut current = FungibleAsset::new_unchecked(current);
+                    current
+                        .sub(asset.amount())
+                        .map_err(AssetVaultError::SubtractFungibleAssetBalanceError)?;
+                    match current.amount() {
+                        0 => TieredSmt::EMPTY_VALUE,
+                        _ => current.into(),
+                    }
+                },
+                Asset::NonFungible(asset) => {
+                    if current == TieredSmt::EMPTY_VALUE {
+                        return Err(AssetVaultError::NonFungibleAssetNotFound(asset));
+                    }
+                    TieredSmt::EMPTY_VALUE
+                },
+            };
+            updates.insert(key, new);
+        }
+
+        // apply all updates to the tree, skipping the leaves which end up unchanged
+        for (key, value) in updates {
+            if self.asset_tree.get_value(key) != value {
+                self.asset_tree.insert(key, value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of the leaf with the specified key, taking into account the pending
+    /// updates which have not yet been applied to the tree.
+    fn pending_value(&self, updates: &BTreeMap<Digest, Word>, key: Digest) -> Word {
+        match updates.get(&key) {
+            Some(value) => *value,
+            None => self.asset_tree.get_value(key),
+        }
+    }
+
     // ADD ASSET
     // --------------------------------------------------------------------------------------------
     /// Add the specified asset to the vault.
@@ -238,3 +330,37 @@ impl Deserializable for AssetVault {
         Self::new(&assets).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountVaultDelta, Asset, AssetVault, FungibleAsset};
+    use crate::accounts::{AccountId, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN};
+
+    #[test]
+    fn apply_delta_is_atomic() {
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let asset = |amount| Asset::Fungible(FungibleAsset::new(faucet_id, amount).unwrap());
+
+        let mut vault = AssetVault::new(&[asset(100)]).unwrap();
+
+        // removing more than the vault holds fails, and the vault is left untouched
+        let delta = AccountVaultDelta {
+            removed_assets: vec![FungibleAsset::new(faucet_id, 150).unwrap()],
+            ..Default::default()
+        };
+        let initial_commitment = vault.commitment();
+        assert!(vault.apply_delta(&delta).is_err());
+        assert_eq!(vault.commitment(), initial_commitment);
+
+        // a valid delta updates the balance
+        let delta = AccountVaultDelta {
+            removed_assets: vec![FungibleAsset::new(faucet_id, 40).unwrap()],
+            ..Default::default()
+        };
+        vault.apply_delta(&delta).unwrap();
+        assert_eq!(vault, AssetVault::new(&[asset(60)]).unwrap());
+    }
+}
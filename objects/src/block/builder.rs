@@ -0,0 +1,470 @@
+use super::{
+    outputs::build_note_tree, BTreeMap, BlockAccountUpdate, BlockHeader, BlockOutputs, Digest,
+    Felt, Hasher, NullifierTree, ProposedBlock, Vec, ACCOUNT_TREE_DEPTH, ZERO,
+};
+use crate::{
+    accounts::AccountId,
+    crypto::merkle::{Mmr, MmrPeaks, SimpleSmt},
+    notes::{NoteEnvelope, NoteId, NoteInclusionProof, Nullifier, NOTE_TREE_DEPTH},
+    transaction::{TransactionBatch, TransactionId, TransactionWeight},
+    utils::{collections::BTreeSet, serde::Serializable},
+    BlockError,
+};
+
+// BLOCK BUILDER
+// ================================================================================================
+
+/// Builds blocks out of proposed blocks on top of the full state of the chain.
+///
+/// The builder maintains the state of the chain required to build blocks: the MMR of all block
+/// hashes, the account database, and the nullifier database. It is intended to be used by local
+/// devnets and tests, where the full state of the chain fits into memory; the transactions of the
+/// proposed blocks are expected to have been verified already.
+///
+/// The consistency of a proposed block on its own (e.g., the matching of unauthenticated notes,
+/// duplicate notes, and expired batches) is checked by [ProposedBlock::new()]. For every block,
+/// the builder additionally checks the proposed block against the state of the chain, and:
+/// - Applies the account updates of the transactions (in the order in which the batches and the
+///   transactions are applied) and computes the new account database root.
+/// - Records the nullifiers of the consumed notes and computes the new nullifier database root.
+/// - Builds the note tree from the created notes and computes the note root.
+/// - Adds the hash of the previous block to the chain MMR and computes the new chain root.
+/// - Computes the batch root and the proof hash of the block (see [Block]).
+///
+/// The first block built by the builder is the genesis block, which can contain notes but no
+/// transactions.
+#[derive(Debug, Clone)]
+pub struct BlockBuilder {
+    latest: Option<BlockHeader>,
+    chain: Mmr,
+    block_hashes: BTreeSet<Digest>,
+    account_tree: SimpleSmt,
+    accounts: BTreeMap<AccountId, Digest>,
+    nullifier_tree: NullifierTree,
+}
+
+impl BlockBuilder {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [BlockBuilder] for a chain without any blocks, in which the specified
+    /// accounts are recorded with the specified state hashes.
+    pub fn new<I>(genesis_accounts: I) -> Self
+    where
+        I: IntoIterator<Item = (AccountId, Digest)>,
+    {
+        let accounts = genesis_accounts.into_iter().collect::<BTreeMap<_, _>>();
+        let account_tree = SimpleSmt::with_leaves(
+            ACCOUNT_TREE_DEPTH,
+            accounts.iter().map(|(id, hash)| (u64::from(*id), (*hash).into())),
+        )
+        .expect("account IDs are unique");
+
+        Self {
+            latest: None,
+            chain: Mmr::default(),
+            block_hashes: BTreeSet::new(),
+            account_tree,
+            accounts,
+            nullifier_tree: NullifierTree::new(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the header of the latest block built by this builder, if any.
+    pub fn latest_block_header(&self) -> Option<&BlockHeader> {
+        self.latest.as_ref()
+    }
+
+    /// Returns the number of the next block built by this builder.
+    pub fn next_block_num(&self) -> u32 {
+        self.latest.map_or(0, |header| header.block_num() + 1)
+    }
+
+    /// Returns the peaks of the MMR of all blocks built by this builder, i.e., the peaks the
+    /// chain root of the next block commits to.
+    pub fn chain_peaks(&self) -> MmrPeaks {
+        self.chain.peaks(self.chain.forest()).expect("forest is the forest of the MMR")
+    }
+
+    /// Returns the state hash of the specified account recorded in the account database, or
+    /// None if the account was not recorded yet.
+    pub fn account_state_hash(&self, account_id: AccountId) -> Option<Digest> {
+        self.accounts.get(&account_id).copied()
+    }
+
+    /// Returns the number of the block in which the note with the specified nullifier was
+    /// consumed, or None if the note was not consumed yet.
+    pub fn nullifier_block_num(&self, nullifier: Nullifier) -> Option<u32> {
+        self.nullifier_tree.get_block_num(&nullifier)
+    }
+
+    // BLOCK BUILDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the [ProposedBlock] with the number of the next block built from the provided
+    /// batches (see [ProposedBlock::new()]).
+    pub fn propose_block(
+        &self,
+        batches: Vec<TransactionBatch>,
+    ) -> Result<ProposedBlock, BlockError> {
+        ProposedBlock::new(self.next_block_num(), batches)
+    }
+
+    /// Builds the genesis block containing the specified notes.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The genesis block was already built.
+    /// - The same note is provided more than once.
+    /// - The number of notes exceeds the capacity of the note tree.
+    pub fn build_genesis_block(
+        &mut self,
+        notes: Vec<NoteEnvelope>,
+        timestamp: Felt,
+    ) -> Result<Block, BlockError> {
+        if self.latest.is_some() {
+            return Err(BlockError::GenesisBlockAlreadyBuilt);
+        }
+
+        let mut created = BTreeSet::new();
+        for note in notes.iter() {
+            if !created.insert(note.note_id()) {
+                return Err(BlockError::DuplicateOutputNote(note.note_id()));
+            }
+        }
+
+        let block = ProposedBlock::new(0, Vec::new())?;
+        self.apply_block(block, Vec::new(), notes, timestamp)
+    }
+
+    /// Builds the next block out of the provided proposed block, and updates the state of the
+    /// chain accordingly. If no blocks were built yet, the genesis block without notes is built.
+    ///
+    /// If the proposed block cannot be built, the state of the chain is left unchanged.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The number of the proposed block is not the number of the next block.
+    /// - Any of the transactions references a block unknown to the builder.
+    /// - The initial account state of any of the transactions does not match the state of the
+    ///   account at the time the transaction is applied.
+    /// - Any of the consumed notes was already consumed in a previous block.
+    /// - The combined weight of the transactions exceeds the block limit.
+    pub fn build_block(
+        &mut self,
+        block: ProposedBlock,
+        timestamp: Felt,
+    ) -> Result<Block, BlockError> {
+        let block_num = self.next_block_num();
+        if block.block_num() != block_num {
+            return Err(BlockError::UnexpectedBlockNum {
+                expected: block_num,
+                actual: block.block_num(),
+            });
+        }
+
+        let mut weight = TransactionWeight::default();
+        let mut updates = BTreeMap::<AccountId, (Digest, Vec<TransactionId>)>::new();
+        for tx in block.batches().iter().flat_map(|batch| batch.transactions()) {
+            if !self.block_hashes.contains(&tx.block_ref()) {
+                return Err(BlockError::UnknownBlockRef(tx.block_ref()));
+            }
+
+            // the initial state of a transaction against an account which is not recorded yet
+            // is the state of a new account, whose validity is enforced by the transaction kernel
+            let account_id = tx.account_id();
+            let current_hash = updates
+                .get(&account_id)
+                .map(|(hash, _)| *hash)
+                .or_else(|| self.account_state_hash(account_id));
+            if let Some(current_hash) = current_hash {
+                if current_hash != tx.initial_account_hash() {
+                    return Err(BlockError::AccountStateMismatch {
+                        account_id,
+                        expected: current_hash,
+                        actual: tx.initial_account_hash(),
+                    });
+                }
+            }
+            let (final_hash, tx_ids) = updates.entry(account_id).or_default();
+            *final_hash = tx.final_account_hash();
+            tx_ids.push(tx.id());
+
+            weight = weight.combine(&tx.weight());
+        }
+        if !weight.fits_in_block() {
+            return Err(BlockError::BlockLimitExceeded);
+        }
+
+        for nullifier in block.input_notes() {
+            if let Some(block_num) = self.nullifier_block_num(*nullifier) {
+                return Err(BlockError::NullifierAlreadySpent(nullifier.inner(), block_num));
+            }
+        }
+
+        let updates = updates
+            .into_iter()
+            .map(|(account_id, (final_hash, tx_ids))| {
+                BlockAccountUpdate::new(account_id, final_hash, tx_ids)
+            })
+            .collect();
+        let notes = block.output_notes().to_vec();
+
+        self.apply_block(block, updates, notes, timestamp)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies the provided (validated) changes to the state of the chain, and returns the new
+    /// block.
+    ///
+    /// # Errors
+    /// Returns an error if the number of created notes exceeds the capacity of the note tree; in
+    /// this case, the state of the chain is left unchanged.
+    fn apply_block(
+        &mut self,
+        block: ProposedBlock,
+        updated_accounts: Vec<BlockAccountUpdate>,
+        created_notes: Vec<NoteEnvelope>,
+        timestamp: Felt,
+    ) -> Result<Block, BlockError> {
+        if created_notes.len() > 1 << NOTE_TREE_DEPTH {
+            return Err(BlockError::TooManyOutputNotes(created_notes.len()));
+        }
+        let note_root = build_note_tree(&created_notes).root();
+
+        let block_num = block.block_num();
+        let produced_nullifiers = block.input_notes().to_vec();
+        for update in updated_accounts.iter() {
+            self.account_tree
+                .update_leaf(u64::from(update.account_id()), update.final_state_hash().into())
+                .expect("account ID is a valid leaf index");
+            self.accounts.insert(update.account_id(), update.final_state_hash());
+        }
+        for nullifier in produced_nullifiers.iter() {
+            self.nullifier_tree
+                .insert(*nullifier, block_num)
+                .expect("nullifiers were validated and the block is not the genesis block");
+        }
+
+        let header = BlockHeader::new(
+            self.latest.map_or(Digest::default(), |header| header.hash()),
+            block_num,
+            self.chain_peaks().hash_peaks(),
+            self.account_tree.root(),
+            self.nullifier_tree.root(),
+            note_root,
+            compute_batch_root(block.batches()),
+            compute_proof_hash(block.batches()),
+            ZERO,
+            timestamp,
+        );
+
+        self.chain.add(header.hash());
+        self.block_hashes.insert(header.hash());
+        self.latest = Some(header);
+
+        let outputs =
+            BlockOutputs::new(header, updated_accounts, created_notes, produced_nullifiers);
+        Ok(Block { outputs, block })
+    }
+}
+
+// BLOCK
+// ================================================================================================
+
+/// A block built by a [BlockBuilder], i.e., a [ProposedBlock] together with the header of the
+/// block and the changes the block makes to the state of the chain (see [BlockOutputs]).
+///
+/// The header of a block commits to its batches in two ways:
+/// - batch_root: the sequential hash of the commitments to the batches of the block (in the order
+///   in which they are applied), where the commitment to a batch is the sequential hash of the IDs
+///   of its transactions.
+/// - proof_hash: the sequential hash of the hashes of the serialized proofs of all transactions of
+///   the block. Blocks are not proven; instead, the proofs of their transactions are verified
+///   individually, and the block commits to the proofs which were verified.
+#[derive(Debug, Clone)]
+pub struct Block {
+    outputs: BlockOutputs,
+    block: ProposedBlock,
+}
+
+impl Block {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the header of this block.
+    pub fn header(&self) -> &BlockHeader {
+        self.outputs.block_header()
+    }
+
+    /// Returns the batches of this block, in the order in which they were applied.
+    pub fn batches(&self) -> &[TransactionBatch] {
+        self.block.batches()
+    }
+
+    /// Returns the updates of the accounts updated in this block.
+    pub fn updated_accounts(&self) -> &[BlockAccountUpdate] {
+        self.outputs.updated_accounts()
+    }
+
+    /// Returns the envelopes of the notes created in this block, excluding the erased and expired
+    /// notes.
+    pub fn created_notes(&self) -> &[NoteEnvelope] {
+        self.outputs.created_notes()
+    }
+
+    /// Returns the nullifiers of the notes consumed in this block, excluding the erased notes.
+    pub fn produced_nullifiers(&self) -> &[Nullifier] {
+        self.outputs.produced_nullifiers()
+    }
+
+    /// Returns the IDs of the notes which were created and consumed within this block.
+    pub fn erased_notes(&self) -> &[NoteId] {
+        self.block.erased_notes()
+    }
+
+    /// Returns the IDs of the notes which were created by this block but expired before it.
+    pub fn expired_notes(&self) -> &[NoteId] {
+        self.block.expired_notes()
+    }
+
+    /// Returns the note tree of this block, i.e., the tree whose root is the note root of the
+    /// block header.
+    pub fn note_tree(&self) -> SimpleSmt {
+        self.outputs.note_tree()
+    }
+
+    /// Returns the proof of inclusion of the specified note in this block, or None if the note
+    /// was not created in this block.
+    pub fn note_inclusion_proof(&self, note_id: NoteId) -> Option<NoteInclusionProof> {
+        self.outputs.note_inclusion_proof(note_id)
+    }
+
+    /// Returns the outputs of this block, i.e., the data with which the store of a node is
+    /// updated.
+    pub fn outputs(&self) -> &BlockOutputs {
+        &self.outputs
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Consumes this block and returns its outputs.
+    pub fn into_outputs(self) -> BlockOutputs {
+        self.outputs
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the batch root of a block with the provided batches (see [Block]).
+fn compute_batch_root(batches: &[TransactionBatch]) -> Digest {
+    let elements = batches
+        .iter()
+        .flat_map(|batch| {
+            let tx_ids = batch.transaction_ids().flat_map(|id| *id.inner()).collect::<Vec<_>>();
+            *Hasher::hash_elements(&tx_ids)
+        })
+        .collect::<Vec<_>>();
+    Hasher::hash_elements(&elements)
+}
+
+/// Returns the proof hash of a block with the provided batches (see [Block]).
+fn compute_proof_hash(batches: &[TransactionBatch]) -> Digest {
+    let elements = batches
+        .iter()
+        .flat_map(|batch| batch.transactions())
+        .flat_map(|tx| *Hasher::hash(&tx.proof().to_bytes()))
+        .collect::<Vec<_>>();
+    Hasher::hash_elements(&elements)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockBuilder, Digest, Felt, Hasher, ProposedBlock, ACCOUNT_TREE_DEPTH, ZERO};
+    use crate::{
+        accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN},
+        crypto::merkle::{Mmr, SimpleSmt},
+        notes::{NoteEnvelope, NoteId, NoteMetadata, NoteTag},
+        BlockError, ONE,
+    };
+
+    #[test]
+    fn blocks_extend_the_chain() {
+        let digest = |x: u64| Digest::new([Felt::new(x), ZERO, ZERO, ZERO]);
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let metadata = NoteMetadata::new(account_id, NoteTag::default(), ONE);
+        let notes = vec![
+            NoteEnvelope::new(NoteId::new(digest(1), digest(2)), metadata),
+            NoteEnvelope::new(NoteId::new(digest(3), digest(4)), metadata),
+        ];
+
+        let mut builder = BlockBuilder::new([(account_id, digest(5))]);
+        assert_eq!(
+            builder
+                .build_genesis_block(vec![notes[0], notes[1], notes[0]], Felt::new(100))
+                .unwrap_err(),
+            BlockError::DuplicateOutputNote(notes[0].note_id())
+        );
+        assert!(builder.latest_block_header().is_none());
+
+        let genesis = builder.build_genesis_block(notes.clone(), Felt::new(100)).unwrap();
+        let header = *genesis.header();
+        assert_eq!(header.block_num(), 0);
+        assert_eq!(header.prev_hash(), Digest::default());
+        assert_eq!(header.note_root(), genesis.note_tree().root());
+
+        let account_tree =
+            SimpleSmt::with_leaves(ACCOUNT_TREE_DEPTH, [(u64::from(account_id), digest(5).into())])
+                .unwrap();
+        assert_eq!(header.account_root(), account_tree.root());
+
+        // the inclusion proofs of the notes open the note root of the block
+        let proof = genesis.note_inclusion_proof(notes[1].note_id()).unwrap();
+        assert_eq!(proof.origin().block_num, 0);
+        assert_eq!(proof.note_root(), header.note_root());
+        assert_eq!(proof.sub_hash(), header.sub_hash());
+        assert!(genesis.note_inclusion_proof(NoteId::new(digest(5), digest(6))).is_none());
+        assert!(genesis.batches().is_empty());
+        assert_eq!(header.batch_root(), Hasher::hash_elements(&[]));
+        assert_eq!(header.proof_hash(), Hasher::hash_elements(&[]));
+
+        assert_eq!(
+            builder.build_genesis_block(vec![], Felt::new(100)).unwrap_err(),
+            BlockError::GenesisBlockAlreadyBuilt
+        );
+
+        // the chain root of every block commits to all previous blocks
+        let mut mmr = Mmr::default();
+        mmr.add(header.hash());
+        for block_num in 1..5 {
+            let block = builder.propose_block(vec![]).unwrap();
+            let block = builder.build_block(block, Felt::new(100 + block_num as u64)).unwrap();
+            let header = block.header();
+            assert_eq!(header.block_num(), block_num);
+            assert_eq!(header.prev_hash(), mmr.get(mmr.forest() - 1).unwrap());
+            assert_eq!(header.chain_root(), mmr.peaks(mmr.forest()).unwrap().hash_peaks());
+            assert_eq!(header.account_root(), account_tree.root());
+            mmr.add(header.hash());
+        }
+        assert_eq!(builder.chain_peaks(), mmr.peaks(5).unwrap());
+        assert_eq!(builder.latest_block_header().unwrap().block_num(), 4);
+
+        // proposed blocks can only be built as the next block of the chain
+        let stale = ProposedBlock::new(4, vec![]).unwrap();
+        assert_eq!(
+            builder.build_block(stale, Felt::new(200)).unwrap_err(),
+            BlockError::UnexpectedBlockNum { expected: 5, actual: 4 }
+        );
+        assert_eq!(builder.next_block_num(), 5);
+    }
+}
@@ -1,4 +1,7 @@
-use super::{Digest, Felt, Hasher, Vec, ZERO};
+use super::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Digest, Felt, Hasher,
+    Serializable, Vec, ZERO,
+};
 
 /// The header of a block. It contains metadata about the block, commitments to the current
 /// state of the chain and the hash of the proof that attests to the integrity of the chain.
@@ -181,3 +184,49 @@ impl BlockHeader {
         Hasher::hash_elements(&elements)
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for BlockHeader {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.prev_hash.write_into(target);
+        target.write_u32(self.block_num);
+        self.chain_root.write_into(target);
+        self.account_root.write_into(target);
+        self.nullifier_root.write_into(target);
+        self.note_root.write_into(target);
+        self.batch_root.write_into(target);
+        self.proof_hash.write_into(target);
+        self.version.write_into(target);
+        self.timestamp.write_into(target);
+    }
+}
+
+impl Deserializable for BlockHeader {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let prev_hash = Digest::read_from(source)?;
+        let block_num = source.read_u32()?;
+        let chain_root = Digest::read_from(source)?;
+        let account_root = Digest::read_from(source)?;
+        let nullifier_root = Digest::read_from(source)?;
+        let note_root = Digest::read_from(source)?;
+        let batch_root = Digest::read_from(source)?;
+        let proof_hash = Digest::read_from(source)?;
+        let version = Felt::read_from(source)?;
+        let timestamp = Felt::read_from(source)?;
+
+        Ok(Self::new(
+            prev_hash,
+            block_num,
+            chain_root,
+            account_root,
+            nullifier_root,
+            note_root,
+            batch_root,
+            proof_hash,
+            version,
+            timestamp,
+        ))
+    }
+}
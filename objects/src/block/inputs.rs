@@ -0,0 +1,180 @@
+use super::{
+    AccountWitness, BTreeMap, BlockHeader, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Digest, NullifierWitness, Serializable, Vec,
+};
+use crate::{
+    accounts::AccountId, crypto::merkle::MmrPeaks, notes::Nullifier, utils::format, BlockError,
+};
+
+// BLOCK INPUTS
+// ================================================================================================
+
+/// The data from the state of the chain required to build the next block.
+///
+/// Block inputs are produced by the store of a node and consumed by the block prover. They
+/// consist of:
+/// - block_header: the header of the latest block, on top of which the next block is built.
+/// - chain_peaks: the peaks of the chain MMR committed to by the latest block header, into which
+///   the latest block is added when building the next block.
+/// - accounts: the witnesses of the current state of all accounts updated in the next block.
+/// - nullifiers: the witnesses of the nullifiers of all notes consumed in the next block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockInputs {
+    block_header: BlockHeader,
+    chain_peaks: MmrPeaks,
+    accounts: BTreeMap<AccountId, AccountWitness>,
+    nullifiers: BTreeMap<Nullifier, NullifierWitness>,
+}
+
+impl BlockInputs {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns new [BlockInputs] instantiated from the provided parameters.
+    pub fn new(
+        block_header: BlockHeader,
+        chain_peaks: MmrPeaks,
+        accounts: Vec<AccountWitness>,
+        nullifiers: Vec<NullifierWitness>,
+    ) -> Self {
+        Self {
+            block_header,
+            chain_peaks,
+            accounts: accounts.into_iter().map(|witness| (witness.account_id(), witness)).collect(),
+            nullifiers: nullifiers
+                .into_iter()
+                .map(|witness| (witness.nullifier(), witness))
+                .collect(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the header of the block on top of which the next block is built.
+    pub fn block_header(&self) -> &BlockHeader {
+        &self.block_header
+    }
+
+    /// Returns the peaks of the chain MMR committed to by the latest block header.
+    pub fn chain_peaks(&self) -> &MmrPeaks {
+        &self.chain_peaks
+    }
+
+    /// Returns the witnesses of the accounts updated in the next block, keyed by account ID.
+    pub fn accounts(&self) -> &BTreeMap<AccountId, AccountWitness> {
+        &self.accounts
+    }
+
+    /// Returns the witness of the specified account, if present.
+    pub fn get_account(&self, account_id: &AccountId) -> Option<&AccountWitness> {
+        self.accounts.get(account_id)
+    }
+
+    /// Returns the witnesses of the nullifiers consumed in the next block, keyed by nullifier.
+    pub fn nullifiers(&self) -> &BTreeMap<Nullifier, NullifierWitness> {
+        &self.nullifiers
+    }
+
+    /// Returns the witness of the specified nullifier, if present.
+    pub fn get_nullifier(&self, nullifier: &Nullifier) -> Option<&NullifierWitness> {
+        self.nullifiers.get(nullifier)
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that all witnesses of these inputs are consistent with the latest block header,
+    /// and that none of the nullifiers was already spent.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Any of the account witnesses does not open the account root of the block header.
+    /// - Any of the nullifier witnesses does not open the nullifier root of the block header.
+    /// - Any of the nullifiers was already recorded in the nullifier database.
+    pub fn validate(&self) -> Result<(), BlockError> {
+        let account_root = self.block_header.account_root();
+        for (account_id, witness) in self.accounts.iter() {
+            if !witness.verify(account_root) {
+                return Err(BlockError::InvalidAccountWitness(*account_id));
+            }
+        }
+
+        let nullifier_root = self.block_header.nullifier_root();
+        for (nullifier, witness) in self.nullifiers.iter() {
+            if !witness.verify(nullifier_root) {
+                return Err(BlockError::InvalidNullifierWitness(nullifier.inner()));
+            }
+            if let Some(block_num) = witness.block_num()? {
+                return Err(BlockError::NullifierAlreadySpent(nullifier.inner(), block_num));
+            }
+        }
+
+        Ok(())
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Consumes these block inputs and returns their underlying parts.
+    pub fn into_parts(
+        self,
+    ) -> (
+        BlockHeader,
+        MmrPeaks,
+        BTreeMap<AccountId, AccountWitness>,
+        BTreeMap<Nullifier, NullifierWitness>,
+    ) {
+        (self.block_header, self.chain_peaks, self.accounts, self.nullifiers)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for BlockInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.block_header.write_into(target);
+
+        target.write_u64(self.chain_peaks.num_leaves() as u64);
+        target.write_u64(self.chain_peaks.peaks().len() as u64);
+        for peak in self.chain_peaks.peaks() {
+            peak.write_into(target);
+        }
+
+        target.write_u64(self.accounts.len() as u64);
+        for witness in self.accounts.values() {
+            witness.write_into(target);
+        }
+
+        target.write_u64(self.nullifiers.len() as u64);
+        for witness in self.nullifiers.values() {
+            witness.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for BlockInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let block_header = BlockHeader::read_from(source)?;
+
+        let num_leaves = source.read_u64()? as usize;
+        let num_peaks = source.read_u64()?;
+        let peaks = (0..num_peaks)
+            .map(|_| Digest::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+        let chain_peaks = MmrPeaks::new(num_leaves, peaks)
+            .map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))?;
+
+        let num_accounts = source.read_u64()?;
+        let accounts = (0..num_accounts)
+            .map(|_| AccountWitness::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let num_nullifiers = source.read_u64()?;
+        let nullifiers = (0..num_nullifiers)
+            .map(|_| NullifierWitness::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(block_header, chain_peaks, accounts, nullifiers))
+    }
+}
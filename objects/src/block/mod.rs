@@ -1,4 +1,49 @@
-use super::{utils::collections::Vec, Digest, Felt, Hasher, ZERO};
+use super::{
+    utils::{
+        collections::{BTreeMap, Vec},
+        serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    },
+    Digest, Felt, Hasher, Word, ZERO,
+};
+
+mod builder;
+pub use builder::{Block, BlockBuilder};
 
 mod header;
 pub use header::BlockHeader;
+
+mod inputs;
+pub use inputs::BlockInputs;
+
+mod nullifier_tree;
+pub use nullifier_tree::NullifierTree;
+
+mod outputs;
+pub use outputs::{BlockAccountUpdate, BlockOutputs};
+
+mod proposed;
+pub use proposed::ProposedBlock;
+
+mod witness;
+pub use witness::{AccountWitness, NullifierWitness};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Depth of the account database tree, which is a sparse Merkle tree indexed by account IDs.
+pub const ACCOUNT_TREE_DEPTH: u8 = 64;
+
+/// Maximum size of all transactions included in a single block, in bytes.
+pub const MAX_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Maximum number of notes consumed by all transactions included in a single block.
+pub const MAX_INPUT_NOTES_PER_BLOCK: usize = 8192;
+
+/// Maximum number of notes created by all transactions included in a single block.
+pub const MAX_OUTPUT_NOTES_PER_BLOCK: usize = 8192;
+
+/// Maximum size of all account updates included in a single block, in bytes.
+///
+/// The update of a private account consists of its new state hash only, while the update of a
+/// new on-chain account contains the full state of the account.
+pub const MAX_ACCOUNT_UPDATES_SIZE_PER_BLOCK: usize = 1024 * 1024;
@@ -0,0 +1,216 @@
+use super::{
+    BTreeMap, ByteReader, ByteWriter, Deserializable, DeserializationError, Digest,
+    NullifierWitness, Serializable,
+};
+use crate::{crypto::merkle::TieredSmt, notes::Nullifier, utils::format, NullifierTreeError};
+
+// NULLIFIER TREE
+// ================================================================================================
+
+/// The nullifier database of the chain.
+///
+/// The nullifier database is a tiered sparse Merkle tree which maps the nullifiers of consumed
+/// notes to `[block_num, 0, 0, 0]`, where `block_num` is the number of the block in which the
+/// note was consumed. Since the value of a nullifier which was not yet recorded is [ZERO; 4],
+/// nullifiers cannot be recorded in the genesis block.
+///
+/// Openings of the tree are returned as [NullifierWitness]es, which can be verified against the
+/// root of the tree without access to the tree itself.
+///
+/// [ZERO; 4]: crate::ZERO
+#[derive(Debug, Clone, Default)]
+pub struct NullifierTree {
+    smt: TieredSmt,
+    nullifiers: BTreeMap<Nullifier, u32>,
+}
+
+impl NullifierTree {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty [NullifierTree].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new [NullifierTree] in which the specified nullifiers are recorded as consumed in
+    /// the specified blocks.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The same nullifier is provided more than once.
+    /// - Any of the block numbers is the number of the genesis block.
+    pub fn with_entries<I>(entries: I) -> Result<Self, NullifierTreeError>
+    where
+        I: IntoIterator<Item = (Nullifier, u32)>,
+    {
+        let mut tree = Self::new();
+        for (nullifier, block_num) in entries {
+            tree.insert(nullifier, block_num)?;
+        }
+        Ok(tree)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> Digest {
+        self.smt.root()
+    }
+
+    /// Returns the number of nullifiers recorded in the tree.
+    pub fn num_nullifiers(&self) -> usize {
+        self.nullifiers.len()
+    }
+
+    /// Returns the number of the block in which the note with the specified nullifier was
+    /// consumed, or None if the note was not consumed yet.
+    pub fn get_block_num(&self, nullifier: &Nullifier) -> Option<u32> {
+        self.nullifiers.get(nullifier).copied()
+    }
+
+    /// Returns an iterator over the recorded nullifiers and the numbers of the blocks in which
+    /// they were recorded, ordered by nullifier.
+    pub fn entries(&self) -> impl Iterator<Item = (Nullifier, u32)> + '_ {
+        self.nullifiers.iter().map(|(nullifier, block_num)| (*nullifier, *block_num))
+    }
+
+    /// Returns an opening of the specified nullifier against the current root of the tree.
+    ///
+    /// For nullifiers which were not recorded yet, the returned witness is a proof of
+    /// non-membership.
+    pub fn open(&self, nullifier: &Nullifier) -> NullifierWitness {
+        NullifierWitness::new(*nullifier, self.smt.prove(nullifier.inner()))
+    }
+
+    /// Returns the root of the tree after the specified nullifiers are recorded as consumed in
+    /// the specified block, without modifying the tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Any of the nullifiers is already recorded in the tree, or is provided more than once.
+    /// - The block number is the number of the genesis block.
+    pub fn compute_updated_root<'a, I>(
+        &self,
+        nullifiers: I,
+        block_num: u32,
+    ) -> Result<Digest, NullifierTreeError>
+    where
+        I: IntoIterator<Item = &'a Nullifier>,
+    {
+        let mut tree = self.clone();
+        for nullifier in nullifiers {
+            tree.insert(*nullifier, block_num)?;
+        }
+        Ok(tree.root())
+    }
+
+    // MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the specified nullifier as consumed in the specified block.
+    ///
+    /// Returns the opening of the nullifier against the root of the tree before the insertion,
+    /// i.e., the proof that the nullifier was not yet recorded.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The nullifier is already recorded in the tree.
+    /// - The block number is the number of the genesis block.
+    pub fn insert(
+        &mut self,
+        nullifier: Nullifier,
+        block_num: u32,
+    ) -> Result<NullifierWitness, NullifierTreeError> {
+        if block_num == 0 {
+            return Err(NullifierTreeError::InvalidBlockNum(block_num));
+        }
+        if let Some(block_num) = self.get_block_num(&nullifier) {
+            return Err(NullifierTreeError::NullifierAlreadySpent {
+                nullifier: nullifier.inner(),
+                block_num,
+            });
+        }
+
+        let witness = self.open(&nullifier);
+        self.smt.insert(nullifier.inner(), NullifierWitness::spent_value(block_num));
+        self.nullifiers.insert(nullifier, block_num);
+        Ok(witness)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for NullifierTree {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.nullifiers.len() as u64);
+        for (nullifier, block_num) in self.nullifiers.iter() {
+            nullifier.write_into(target);
+            target.write_u32(*block_num);
+        }
+    }
+}
+
+impl Deserializable for NullifierTree {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_entries = source.read_u64()?;
+        let mut tree = Self::new();
+        for _ in 0..num_entries {
+            let nullifier = Nullifier::read_from(source)?;
+            let block_num = source.read_u32()?;
+            tree.insert(nullifier, block_num)
+                .map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))?;
+        }
+        Ok(tree)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{NullifierTree, NullifierTreeError};
+    use crate::{
+        notes::Nullifier,
+        utils::serde::{Deserializable, Serializable},
+        Digest, ONE, ZERO,
+    };
+
+    #[test]
+    fn nullifier_tree_insertion() {
+        let first = Nullifier::from(Digest::new([ONE, ZERO, ZERO, ZERO]));
+        let second = Nullifier::from(Digest::new([ZERO, ONE, ZERO, ZERO]));
+        let mut tree = NullifierTree::new();
+
+        // the returned witness proves that the nullifier was not recorded in the previous tree
+        let initial_root = tree.root();
+        let updated_root = tree.compute_updated_root([&first], 3).unwrap();
+        let witness = tree.insert(first, 3).unwrap();
+        assert!(witness.verify_unspent(initial_root));
+        assert_eq!(tree.root(), updated_root);
+
+        // the opening of a recorded nullifier attests to the block in which it was recorded
+        let witness = tree.open(&first);
+        assert!(witness.verify(tree.root()));
+        assert_eq!(witness.block_num(), Ok(Some(3)));
+        assert!(tree.open(&second).verify_unspent(tree.root()));
+
+        assert_eq!(
+            tree.insert(first, 4),
+            Err(NullifierTreeError::NullifierAlreadySpent {
+                nullifier: first.inner(),
+                block_num: 3
+            })
+        );
+        assert_eq!(tree.insert(second, 0), Err(NullifierTreeError::InvalidBlockNum(0)));
+
+        tree.insert(second, 4).unwrap();
+        assert_eq!(tree.num_nullifiers(), 2);
+        let bytes = tree.to_bytes();
+        let deserialized = NullifierTree::read_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized.root(), tree.root());
+        assert_eq!(deserialized.get_block_num(&second), Some(4));
+    }
+}
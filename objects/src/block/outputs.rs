@@ -0,0 +1,288 @@
+use super::{
+    BlockHeader, ByteReader, ByteWriter, Deserializable, DeserializationError, Digest,
+    Serializable, Vec,
+};
+use crate::{
+    accounts::AccountId,
+    crypto::merkle::{NodeIndex, SimpleSmt},
+    notes::{
+        NoteEnvelope, NoteId, NoteInclusionProof, Nullifier, NOTE_LEAF_DEPTH, NOTE_TREE_DEPTH,
+    },
+    transaction::TransactionId,
+};
+
+// BLOCK ACCOUNT UPDATE
+// ================================================================================================
+
+/// The update of a single account in a block.
+///
+/// The update describes the state of the account after all transactions of the block executed
+/// against the account were applied, together with the IDs of these transactions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockAccountUpdate {
+    account_id: AccountId,
+    final_state_hash: Digest,
+    transactions: Vec<TransactionId>,
+}
+
+impl BlockAccountUpdate {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [BlockAccountUpdate] instantiated from the provided parameters.
+    pub fn new(
+        account_id: AccountId,
+        final_state_hash: Digest,
+        transactions: Vec<TransactionId>,
+    ) -> Self {
+        Self {
+            account_id,
+            final_state_hash,
+            transactions,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the updated account.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the state hash of the account after the block was applied.
+    pub fn final_state_hash(&self) -> Digest {
+        self.final_state_hash
+    }
+
+    /// Returns the IDs of the transactions of the block which updated the account.
+    pub fn transactions(&self) -> &[TransactionId] {
+        &self.transactions
+    }
+}
+
+impl Serializable for BlockAccountUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account_id.write_into(target);
+        self.final_state_hash.write_into(target);
+        target.write_u64(self.transactions.len() as u64);
+        for tx_id in self.transactions.iter() {
+            tx_id.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for BlockAccountUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account_id = AccountId::read_from(source)?;
+        let final_state_hash = Digest::read_from(source)?;
+        let num_transactions = source.read_u64()?;
+        let transactions = (0..num_transactions)
+            .map(|_| TransactionId::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(account_id, final_state_hash, transactions))
+    }
+}
+
+// BLOCK OUTPUTS
+// ================================================================================================
+
+/// The result of building a block, i.e., the data with which the store of a node is updated.
+///
+/// Block outputs consist of:
+/// - block_header: the header of the new block.
+/// - updated_accounts: the final states of all accounts updated in the block.
+/// - created_notes: the envelopes of all notes created in the block.
+/// - produced_nullifiers: the nullifiers of all notes consumed in the block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockOutputs {
+    block_header: BlockHeader,
+    updated_accounts: Vec<BlockAccountUpdate>,
+    created_notes: Vec<NoteEnvelope>,
+    produced_nullifiers: Vec<Nullifier>,
+}
+
+impl BlockOutputs {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns new [BlockOutputs] instantiated from the provided parameters.
+    pub fn new(
+        block_header: BlockHeader,
+        updated_accounts: Vec<BlockAccountUpdate>,
+        created_notes: Vec<NoteEnvelope>,
+        produced_nullifiers: Vec<Nullifier>,
+    ) -> Self {
+        Self {
+            block_header,
+            updated_accounts,
+            created_notes,
+            produced_nullifiers,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the header of the new block.
+    pub fn block_header(&self) -> &BlockHeader {
+        &self.block_header
+    }
+
+    /// Returns the updates of the accounts updated in the block.
+    pub fn updated_accounts(&self) -> &[BlockAccountUpdate] {
+        &self.updated_accounts
+    }
+
+    /// Returns the envelopes of the notes created in the block.
+    pub fn created_notes(&self) -> &[NoteEnvelope] {
+        &self.created_notes
+    }
+
+    /// Returns the nullifiers of the notes consumed in the block.
+    pub fn produced_nullifiers(&self) -> &[Nullifier] {
+        &self.produced_nullifiers
+    }
+
+    // NOTE TREE
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the note tree of the block, i.e., the tree whose root is the note root of the block
+    /// header.
+    ///
+    /// The tree is rebuilt from the created notes on every call.
+    pub fn note_tree(&self) -> SimpleSmt {
+        build_note_tree(&self.created_notes)
+    }
+
+    /// Returns the proof of inclusion of the specified note in the block, or None if the note was
+    /// not created in the block.
+    pub fn note_inclusion_proof(&self, note_id: NoteId) -> Option<NoteInclusionProof> {
+        let index = self.created_notes.iter().position(|note| note.note_id() == note_id)? as u64;
+        let note_path = self
+            .note_tree()
+            .get_path(NodeIndex::new(NOTE_TREE_DEPTH, index).expect("note index is valid"))
+            .expect("note index is within the tree");
+
+        let proof = NoteInclusionProof::new(
+            self.block_header.block_num(),
+            self.block_header.sub_hash(),
+            self.block_header.note_root(),
+            index,
+            note_path,
+        )
+        .expect("note index is valid");
+
+        Some(proof)
+    }
+}
+
+impl Serializable for BlockOutputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.block_header.write_into(target);
+
+        target.write_u64(self.updated_accounts.len() as u64);
+        for update in self.updated_accounts.iter() {
+            update.write_into(target);
+        }
+
+        target.write_u64(self.created_notes.len() as u64);
+        for note in self.created_notes.iter() {
+            note.write_into(target);
+        }
+
+        target.write_u64(self.produced_nullifiers.len() as u64);
+        for nullifier in self.produced_nullifiers.iter() {
+            nullifier.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for BlockOutputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let block_header = BlockHeader::read_from(source)?;
+
+        let num_updates = source.read_u64()?;
+        let updated_accounts = (0..num_updates)
+            .map(|_| BlockAccountUpdate::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let num_notes = source.read_u64()?;
+        let created_notes = (0..num_notes)
+            .map(|_| NoteEnvelope::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let num_nullifiers = source.read_u64()?;
+        let produced_nullifiers = (0..num_nullifiers)
+            .map(|_| Nullifier::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(block_header, updated_accounts, created_notes, produced_nullifiers))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the note tree of a block in which the provided notes were created.
+///
+/// The note with index `i` is stored in the tree as two leaves: the ID of the note at index `2i`
+/// and the metadata of the note at index `2i + 1`; thus, the node at index `i` and depth
+/// [NOTE_TREE_DEPTH] commits to both the ID and the metadata of the note.
+///
+/// # Panics
+/// Panics if the number of notes exceeds the capacity of the note tree.
+pub(super) fn build_note_tree(notes: &[NoteEnvelope]) -> SimpleSmt {
+    let leaves = notes.iter().enumerate().flat_map(|(index, note)| {
+        let index = 2 * index as u64;
+        [(index, note.note_id().into()), (index + 1, note.metadata().into())]
+    });
+    SimpleSmt::with_leaves(NOTE_LEAF_DEPTH, leaves).expect("notes fit into the note tree")
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockAccountUpdate, BlockHeader, BlockOutputs, Deserializable, Serializable};
+    use crate::{
+        accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN},
+        notes::{NoteEnvelope, NoteId, NoteMetadata, NoteTag, Nullifier},
+        transaction::TransactionId,
+        Digest, Felt, ONE, ZERO,
+    };
+
+    #[test]
+    fn block_outputs_serialization() {
+        let digest = |x: u64| Digest::new([Felt::new(x), ZERO, ZERO, ZERO]);
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+
+        let header = BlockHeader::new(
+            digest(1),
+            7,
+            digest(2),
+            digest(3),
+            digest(4),
+            digest(5),
+            digest(6),
+            digest(7),
+            ONE,
+            Felt::new(1000),
+        );
+        let update = BlockAccountUpdate::new(
+            account_id,
+            digest(8),
+            vec![TransactionId::new(digest(9), digest(10), digest(11), digest(12))],
+        );
+        let note = NoteEnvelope::new(
+            NoteId::new(digest(13), digest(14)),
+            NoteMetadata::new(account_id, NoteTag::default(), ONE),
+        );
+        let outputs =
+            BlockOutputs::new(header, vec![update], vec![note], vec![Nullifier::from(digest(15))]);
+
+        let bytes = outputs.to_bytes();
+        assert_eq!(outputs, BlockOutputs::read_from_bytes(&bytes).unwrap());
+    }
+}
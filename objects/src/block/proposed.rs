@@ -0,0 +1,167 @@
+use super::{BTreeMap, Vec};
+use crate::{
+    notes::{NoteEnvelope, NoteId, Nullifier},
+    transaction::{sort_by_dependencies, TransactionBatch},
+    utils::collections::BTreeSet,
+    BlockError,
+};
+
+// PROPOSED BLOCK
+// ================================================================================================
+
+/// A set of transaction batches selected for inclusion into the next block.
+///
+/// Unauthenticated notes which were not matched within the batches consuming them must be created
+/// by other batches of the block. Such notes are erased from the block, in the same way notes
+/// created and consumed within a single batch are erased from the batch (see [TransactionBatch]).
+/// Thus, the input and output notes of a proposed block are:
+/// - input_notes: the nullifiers of all notes consumed by the batches, except for the erased notes.
+/// - output_notes: the envelopes of all notes created by the batches, except for the erased and
+///   expired notes.
+///
+/// Notes which expire before the block (i.e., notes with an expiration block number smaller than
+/// the number of the block) can never be consumed, and thus are garbage-collected: they are not
+/// added to the output notes of the block, and their IDs are recorded as expired notes instead.
+/// Expired notes created and consumed within the block are erased as usual, since the transactions
+/// consuming them checked their expiration against the blocks referenced by the transactions.
+///
+/// The batches are ordered such that every batch comes after the batches creating the
+/// unauthenticated notes it consumes. None of the batches of a proposed block is expired with
+/// respect to the number of the block.
+#[derive(Debug, Clone)]
+pub struct ProposedBlock {
+    block_num: u32,
+    batches: Vec<TransactionBatch>,
+    input_notes: Vec<Nullifier>,
+    output_notes: Vec<NoteEnvelope>,
+    erased_notes: Vec<NoteId>,
+    expired_notes: Vec<NoteId>,
+}
+
+impl ProposedBlock {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [ProposedBlock] with the specified number built from the provided batches.
+    ///
+    /// The batches are reordered (preserving the provided order where possible) such that every
+    /// unauthenticated note is created before it is consumed. Output notes which expire before the
+    /// block are discarded.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Any of the batches expired before the block with the specified number.
+    /// - Any of the unauthenticated notes consumed by a batch is not created by another batch.
+    /// - The batches cannot be ordered, i.e., there is a cycle of batches consuming
+    ///   unauthenticated notes created by each other. The error contains the index of one of the
+    ///   batches of the cycle in the provided batches.
+    /// - The same note is consumed or created by more than one batch.
+    pub fn new(block_num: u32, batches: Vec<TransactionBatch>) -> Result<Self, BlockError> {
+        if let Some(expiration_block_num) = batches
+            .iter()
+            .find(|batch| batch.is_expired(block_num))
+            .and_then(|batch| batch.expiration_block_num())
+        {
+            return Err(BlockError::ExpiredBatch { expiration_block_num, block_num });
+        }
+
+        let producers = batches
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, batch)| batch.output_notes().iter().map(move |n| (n.note_id(), idx)))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut dependencies = Vec::new();
+        let mut unauthenticated_notes = BTreeMap::new();
+        for (idx, batch) in batches.iter().enumerate() {
+            for (nullifier, note_id) in batch.unauthenticated_notes() {
+                let producer = producers
+                    .get(note_id)
+                    .ok_or(BlockError::UnauthenticatedNoteNotFound(*note_id))?;
+                dependencies.push((*producer, idx));
+                unauthenticated_notes.insert(*nullifier, *note_id);
+            }
+        }
+
+        let order = sort_by_dependencies(batches.len(), &dependencies)
+            .map_err(BlockError::CyclicBatchDependency)?;
+        let mut batches = batches.into_iter().map(Some).collect::<Vec<_>>();
+        let batches = order
+            .into_iter()
+            .map(|idx| batches[idx].take().expect("batch ordered more than once"))
+            .collect::<Vec<_>>();
+
+        let mut input_notes = Vec::new();
+        let mut consumed = BTreeSet::new();
+        for nullifier in batches.iter().flat_map(|batch| batch.input_notes()) {
+            if !consumed.insert(*nullifier) {
+                return Err(BlockError::DuplicateInputNote(nullifier.inner()));
+            }
+            if !unauthenticated_notes.contains_key(nullifier) {
+                input_notes.push(*nullifier);
+            }
+        }
+
+        let erased_notes = unauthenticated_notes.values().copied().collect::<BTreeSet<_>>();
+        let mut output_notes = Vec::new();
+        let mut expired_notes = Vec::new();
+        let mut created = BTreeSet::new();
+        for note in batches.iter().flat_map(|batch| batch.output_notes()) {
+            if !created.insert(note.note_id()) {
+                return Err(BlockError::DuplicateOutputNote(note.note_id()));
+            }
+            if erased_notes.contains(&note.note_id()) {
+                continue;
+            }
+            if note.metadata().is_expired(block_num) {
+                expired_notes.push(note.note_id());
+            } else {
+                output_notes.push(*note);
+            }
+        }
+
+        Ok(Self {
+            block_num,
+            batches,
+            input_notes,
+            output_notes,
+            erased_notes: erased_notes.into_iter().collect(),
+            expired_notes,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of this block.
+    pub fn block_num(&self) -> u32 {
+        self.block_num
+    }
+
+    /// Returns the batches of this block, in the order in which they are applied.
+    pub fn batches(&self) -> &[TransactionBatch] {
+        &self.batches
+    }
+
+    /// Returns the nullifiers of the notes consumed by this block, excluding the erased notes.
+    pub fn input_notes(&self) -> &[Nullifier] {
+        &self.input_notes
+    }
+
+    /// Returns the envelopes of the notes created by this block, excluding the erased and expired
+    /// notes.
+    pub fn output_notes(&self) -> &[NoteEnvelope] {
+        &self.output_notes
+    }
+
+    /// Returns the IDs of the notes which were created by one batch of this block and consumed by
+    /// another batch of this block.
+    pub fn erased_notes(&self) -> &[NoteId] {
+        &self.erased_notes
+    }
+
+    /// Returns the IDs of the notes which were created by this block but expired before it, and
+    /// thus were discarded from its output notes.
+    pub fn expired_notes(&self) -> &[NoteId] {
+        &self.expired_notes
+    }
+}
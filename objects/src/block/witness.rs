@@ -0,0 +1,296 @@
+use super::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Digest, Felt, Serializable, Vec,
+    Word, ACCOUNT_TREE_DEPTH, ZERO,
+};
+use crate::{
+    accounts::AccountId,
+    crypto::merkle::{MerklePath, TieredSmtProof},
+    notes::Nullifier,
+    utils::format,
+    BlockError, StarkField,
+};
+
+// ACCOUNT WITNESS
+// ================================================================================================
+
+/// An opening of an account in the account database, attesting to the current state hash of the
+/// account.
+///
+/// The account database is a sparse Merkle tree of depth [ACCOUNT_TREE_DEPTH] in which the leaf
+/// at the index defined by the account ID holds the hash of the account state. The leaf of an
+/// account which was not yet recorded in the database is [ZERO; 4].
+///
+/// [ACCOUNT_TREE_DEPTH]: super::ACCOUNT_TREE_DEPTH
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountWitness {
+    account_id: AccountId,
+    initial_state_hash: Digest,
+    proof: MerklePath,
+}
+
+impl AccountWitness {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [AccountWitness] for the specified account.
+    pub fn new(account_id: AccountId, initial_state_hash: Digest, proof: MerklePath) -> Self {
+        Self { account_id, initial_state_hash, proof }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the account.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the state hash of the account recorded in the account database.
+    pub fn initial_state_hash(&self) -> Digest {
+        self.initial_state_hash
+    }
+
+    /// Returns the Merkle path from the leaf of the account to the account database root.
+    pub fn proof(&self) -> &MerklePath {
+        &self.proof
+    }
+
+    /// Returns true if the account was not yet recorded in the account database.
+    pub fn is_new_account(&self) -> bool {
+        self.initial_state_hash == Digest::default()
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the account database implied by this witness, or None if the path of
+    /// the witness is not a path of the account database.
+    pub fn compute_root(&self) -> Option<Digest> {
+        self.compute_root_with(self.initial_state_hash)
+    }
+
+    /// Returns the root of the account database after the state hash of the account is set to
+    /// the specified value, or None if the path of the witness is not a path of the account
+    /// database.
+    pub fn compute_updated_root(&self, final_state_hash: Digest) -> Option<Digest> {
+        self.compute_root_with(final_state_hash)
+    }
+
+    /// Returns true if this witness attests that the state hash of the account is recorded in the
+    /// account database with the specified root.
+    pub fn verify(&self, account_root: Digest) -> bool {
+        self.compute_root() == Some(account_root)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    fn compute_root_with(&self, state_hash: Digest) -> Option<Digest> {
+        if self.proof.depth() != ACCOUNT_TREE_DEPTH {
+            return None;
+        }
+        self.proof.compute_root(u64::from(self.account_id), state_hash).ok()
+    }
+}
+
+impl Serializable for AccountWitness {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account_id.write_into(target);
+        self.initial_state_hash.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl Deserializable for AccountWitness {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account_id = AccountId::read_from(source)?;
+        let initial_state_hash = Digest::read_from(source)?;
+        let proof = MerklePath::read_from(source)?;
+
+        Ok(Self::new(account_id, initial_state_hash, proof))
+    }
+}
+
+// NULLIFIER WITNESS
+// ================================================================================================
+
+/// An opening of a nullifier in the nullifier database.
+///
+/// The nullifier database is a tiered sparse Merkle tree which maps the nullifiers of consumed
+/// notes to `[block_num, 0, 0, 0]`, where `block_num` is the number of the block in which the
+/// note was consumed. The value of a nullifier which was not yet recorded is [ZERO; 4].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NullifierWitness {
+    nullifier: Nullifier,
+    proof: TieredSmtProof,
+}
+
+impl NullifierWitness {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [NullifierWitness] for the specified nullifier.
+    pub fn new(nullifier: Nullifier, proof: TieredSmtProof) -> Self {
+        Self { nullifier, proof }
+    }
+
+    /// Returns the value stored in the nullifier database for a note consumed in the specified
+    /// block.
+    pub fn spent_value(block_num: u32) -> Word {
+        [Felt::from(block_num), ZERO, ZERO, ZERO]
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the nullifier.
+    pub fn nullifier(&self) -> Nullifier {
+        self.nullifier
+    }
+
+    /// Returns the opening of the nullifier in the nullifier database.
+    pub fn proof(&self) -> &TieredSmtProof {
+        &self.proof
+    }
+
+    /// Returns the number of the block in which the note was consumed, or None if the note was
+    /// not yet consumed.
+    ///
+    /// # Errors
+    /// Returns an error if the proof of this witness does not cover the nullifier, or if the value
+    /// of the nullifier is not of the form `[block_num, 0, 0, 0]`.
+    pub fn block_num(&self) -> Result<Option<u32>, BlockError> {
+        let invalid_witness = || BlockError::InvalidNullifierWitness(self.nullifier.inner());
+
+        let value = self.proof.get(&self.nullifier.inner()).ok_or_else(invalid_witness)?;
+        if value == [ZERO; 4] {
+            return Ok(None);
+        }
+        if value[1..] != [ZERO; 3] {
+            return Err(invalid_witness());
+        }
+        u32::try_from(value[0].as_int()).map(Some).map_err(|_| invalid_witness())
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this witness is an opening of the nullifier in the nullifier database
+    /// with the specified root.
+    pub fn verify(&self, nullifier_root: Digest) -> bool {
+        self.proof.get(&self.nullifier.inner()).is_some()
+            && self.proof.compute_root() == nullifier_root
+    }
+
+    /// Returns true if this witness attests that the note was not yet consumed, according to the
+    /// nullifier database with the specified root.
+    pub fn verify_unspent(&self, nullifier_root: Digest) -> bool {
+        self.verify(nullifier_root) && matches!(self.block_num(), Ok(None))
+    }
+}
+
+impl Serializable for NullifierWitness {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.nullifier.write_into(target);
+
+        let (path, entries) = self.proof.clone().into_parts();
+        path.write_into(target);
+        target.write_u64(entries.len() as u64);
+        for (key, value) in entries {
+            key.write_into(target);
+            value.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for NullifierWitness {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let nullifier = Nullifier::read_from(source)?;
+
+        let path = MerklePath::read_from(source)?;
+        let num_entries = source.read_u64()?;
+        let entries = (0..num_entries)
+            .map(|_| Ok((Digest::read_from(source)?, Word::read_from(source)?)))
+            .collect::<Result<Vec<_>, DeserializationError>>()?;
+        let proof = TieredSmtProof::new(path, entries)
+            .map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))?;
+
+        Ok(Self::new(nullifier, proof))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountWitness, NullifierWitness, ACCOUNT_TREE_DEPTH};
+    use crate::{
+        accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN},
+        crypto::merkle::{NodeIndex, SimpleSmt, TieredSmt},
+        notes::Nullifier,
+        utils::serde::{Deserializable, Serializable},
+        BlockError, Digest, Felt, ONE, ZERO,
+    };
+
+    #[test]
+    fn account_witness_verification() {
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let state_hash = Digest::new([ONE, ONE, ZERO, ZERO]);
+        let mut tree = SimpleSmt::with_leaves(
+            ACCOUNT_TREE_DEPTH,
+            [(u64::from(account_id), state_hash.into())],
+        )
+        .unwrap();
+
+        let index = NodeIndex::new(ACCOUNT_TREE_DEPTH, u64::from(account_id)).unwrap();
+        let witness = AccountWitness::new(account_id, state_hash, tree.get_path(index).unwrap());
+        assert!(!witness.is_new_account());
+        assert!(witness.verify(tree.root()));
+        assert!(!witness.verify(Digest::default()));
+
+        // a witness for a different state hash does not verify
+        let final_state_hash = Digest::new([ONE, ONE, ONE, ZERO]);
+        let invalid = AccountWitness::new(account_id, final_state_hash, witness.proof().clone());
+        assert!(!invalid.verify(tree.root()));
+
+        // the updated root matches the root of the updated tree
+        let updated_root = witness.compute_updated_root(final_state_hash);
+        tree.update_leaf(u64::from(account_id), final_state_hash.into()).unwrap();
+        assert_eq!(updated_root, Some(tree.root()));
+
+        let bytes = witness.to_bytes();
+        assert_eq!(witness, AccountWitness::read_from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn nullifier_witness_verification() {
+        let spent = Nullifier::from(Digest::new([ONE, ZERO, ZERO, ZERO]));
+        let unspent = Nullifier::from(Digest::new([ZERO, ONE, ZERO, ZERO]));
+        let tree =
+            TieredSmt::with_entries([(spent.inner(), NullifierWitness::spent_value(3))]).unwrap();
+
+        let witness = NullifierWitness::new(spent, tree.prove(spent.inner()));
+        assert!(witness.verify(tree.root()));
+        assert!(!witness.verify_unspent(tree.root()));
+        assert_eq!(witness.block_num(), Ok(Some(3)));
+
+        // the opening of a different leaf does not attest to the state of the nullifier
+        let other = Nullifier::from(Digest::new([ZERO, ZERO, ZERO, Felt::new(1 << 62)]));
+        let invalid = NullifierWitness::new(other, witness.proof().clone());
+        assert_eq!(invalid.block_num(), Err(BlockError::InvalidNullifierWitness(other.inner())));
+        assert!(!invalid.verify_unspent(tree.root()));
+
+        // the value of a nullifier must encode a block number
+        let tree = TieredSmt::with_entries([(spent.inner(), [ONE, ONE, ZERO, ZERO])]).unwrap();
+        let malformed = NullifierWitness::new(spent, tree.prove(spent.inner()));
+        assert_eq!(malformed.block_num(), Err(BlockError::InvalidNullifierWitness(spent.inner())));
+
+        let witness = NullifierWitness::new(unspent, tree.prove(unspent.inner()));
+        assert!(witness.verify_unspent(tree.root()));
+        assert!(!witness.verify_unspent(Digest::new([Felt::new(7), ZERO, ZERO, ZERO])));
+        assert_eq!(witness.block_num(), Ok(None));
+
+        let bytes = witness.to_bytes();
+        assert_eq!(witness, NullifierWitness::read_from_bytes(&bytes).unwrap());
+    }
+}
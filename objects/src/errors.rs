@@ -4,10 +4,11 @@ use assembly::AssemblyError;
 use vm_processor::DeserializationError;
 
 use super::{
-    accounts::AccountId,
+    accounts::{AccountHeader, AccountId, AccountType, StorageSlotType},
     assets::{Asset, FungibleAsset, NonFungibleAsset},
     crypto::merkle::MerkleError,
-    notes::NoteId,
+    notes::{NoteId, NoteTag},
+    transaction::{FeeParameters, TransactionId},
     utils::string::String,
     Digest, Word,
 };
@@ -19,25 +20,60 @@ use super::{
 pub enum AccountError {
     AccountCodeAssemblerError(AssemblyError),
     AccountCodeNoProcedures,
-    AccountCodeTooManyProcedures { max: usize, actual: usize },
+    AccountCodeProcedureNotFound(Digest),
+    AccountCodeTooManyProcedures {
+        max: usize,
+        actual: usize,
+    },
+    AccountComponentNotSupported {
+        component: String,
+        account_type: AccountType,
+    },
+    AccountDeltaInvalid(AccountDeltaError),
+    AccountHashMismatch(Digest, Digest),
+    AccountHeaderMismatch(AccountHeader, AccountHeader),
     AccountIdInvalidFieldElement(String),
+    AccountIdMismatch(AccountId, AccountId),
     AccountIdTooFewOnes,
+    AccountSeedMissing(AccountId),
+    AccountSeedNotRequired(AccountId),
     ApplyStorageSlotsDiffFailed(MerkleError),
     ApplyStorageStoreDiffFailed(MerkleError),
     AssetVaultError(AssetVaultError),
+    AuthSchemeNotSupported(String),
     DuplicateStorageItems(MerkleError),
     FungibleFaucetIdInvalidFirstBit,
     FungibleFaucetInvalidMetadata(String),
     HexParseError(String),
-    InconsistentAccountIdSeed { expected: AccountId, actual: AccountId },
+    InconsistentAccountIdSeed {
+        expected: AccountId,
+        actual: AccountId,
+    },
+    InvalidProcedureAccess(u8),
     NonceMustBeMonotonicallyIncreasing(u64, u64),
-    SeedDigestTooFewTrailingZeros { expected: u32, actual: u32 },
+    SeedDigestTooFewTrailingZeros {
+        expected: u32,
+        actual: u32,
+    },
     SetStoreNodeFailed(MerkleError),
     StorageArrayRequiresMoreThanOneElement,
-    StorageArrayTooLong { actual: usize, max: usize },
-    StorageSlotArrayTooSmall { actual: u8, min: u8 },
+    StorageArrayTooLong {
+        actual: usize,
+        max: usize,
+    },
+    StorageMapNotFound(u8),
+    StorageSlotArrayTooSmall {
+        actual: u8,
+        min: u8,
+    },
+    StorageSlotConflict(u8),
     StorageSlotIsReserved(u8),
-    StubDataIncorrectLength(usize, usize),
+    StorageSlotNotDeclared(u8),
+    StorageSlotNotMap(u8),
+    StorageSlotTypeInvalid(u8, StorageSlotType),
+    StorageSlotTypeMismatch(u8, StorageSlotType, StorageSlotType),
+    StorageSlotValueInvalid(u8, String),
+    HeaderDataIncorrectLength(usize, usize),
 }
 
 impl AccountError {
@@ -73,13 +109,21 @@ impl std::error::Error for AccountError {}
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum AccountDeltaError {
     DuplicateStorageItemUpdate(usize),
+    DuplicateStorageMapLeafUpdate(u8, Digest),
+    DuplicateStorageMapUpdate(u8),
     DuplicateVaultUpdate(Asset),
+    EmptyStorageMapLeafUpdate(u8, Digest),
+    FungibleAssetAmountTooBig(AccountId),
     InconsistentNonceUpdate(String),
     ImmutableStorageSlot(usize),
+    StorageMapRootNotUpdated(u8),
     TooManyAddedAsset { actual: usize, max: usize },
     TooManyClearedStorageItems { actual: usize, max: usize },
     TooManyRemovedAssets { actual: usize, max: usize },
+    TooManyStorageEntries { actual: usize, max: usize },
+    TooManyStorageMapLeaves { actual: usize, max: usize },
     TooManyUpdatedStorageItems { actual: usize, max: usize },
+    TooManyVaultEntries { actual: usize, max: usize },
 }
 
 #[cfg(feature = "std")]
@@ -91,6 +135,26 @@ impl fmt::Display for AccountDeltaError {
     }
 }
 
+// ACCOUNT UPDATE ERROR
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountUpdateError {
+    AccountIdMismatch { expected: AccountId, actual: AccountId },
+    DeltaMergeFailed(AccountDeltaError),
+    InconsistentStateTransition { expected: Digest, actual: Digest },
+    NoTransactions,
+}
+
+impl fmt::Display for AccountUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AccountUpdateError {}
+
 // ASSET ERROR
 // ================================================================================================
 
@@ -199,24 +263,101 @@ impl fmt::Display for AssetVaultError {
 #[cfg(feature = "std")]
 impl std::error::Error for AssetVaultError {}
 
+// BLOCK ERROR
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockError {
+    AccountStateMismatch {
+        account_id: AccountId,
+        expected: Digest,
+        actual: Digest,
+    },
+    BlockLimitExceeded,
+    CyclicBatchDependency(usize),
+    DuplicateInputNote(Digest),
+    DuplicateOutputNote(NoteId),
+    ExpiredBatch {
+        expiration_block_num: u32,
+        block_num: u32,
+    },
+    GenesisBlockAlreadyBuilt,
+    InvalidAccountWitness(AccountId),
+    InvalidNullifierWitness(Digest),
+    NullifierAlreadySpent(Digest, u32),
+    TooManyOutputNotes(usize),
+    UnauthenticatedNoteNotFound(NoteId),
+    UnexpectedBlockNum {
+        expected: u32,
+        actual: u32,
+    },
+    UnknownBlockRef(Digest),
+}
+
+impl fmt::Display for BlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlockError {}
+
+// NULLIFIER TREE ERROR
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NullifierTreeError {
+    InvalidBlockNum(u32),
+    NullifierAlreadySpent { nullifier: Digest, block_num: u32 },
+}
+
+impl fmt::Display for NullifierTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NullifierTreeError {}
+
 // NOTE ERROR
 // ================================================================================================
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum NoteError {
+    BridgeFaucetMismatch(AccountId, AccountId),
     DuplicateFungibleAsset(AccountId),
     DuplicateNonFungibleAsset(NonFungibleAsset),
+    DuplicateNoteInput(String),
     EmptyAssetList,
     InconsistentStubId(NoteId, NoteId),
     InconsistentStubAssetHash(Digest, Digest),
     InvalidStubDataLen(usize),
     InvalidOriginIndex(String),
     InvalidAssetData(AssetError),
+    InvalidBridgeMessage(String),
+    InvalidEscrowTerms(String),
+    InvalidExpirationBlockNum(u64),
+    InvalidGiftCardLimit(u64),
+    InvalidGiftCardWithdrawal(u64),
+    InvalidMintNote(NoteId),
+    InvalidNoteInputLayout(String),
+    InvalidNoteInputValue(String),
+    InvalidNoteTag(u64),
+    InvalidNoteTagUseCase(u16),
+    InvalidOracleAccountPathDepth(u8),
+    MissingNoteInput(String),
+    NetworkNoteTagForOffChainAccount(AccountId),
+    NotANetworkNote(NoteId),
     NoteDeserializationError(DeserializationError),
     NoteMetadataSenderInvalid(AccountError),
+    NoteTagRequiresPublicNote(NoteTag),
+    OracleAccountMismatch(AccountId, AccountId),
     ScriptCompilationError(AssemblyError),
     TooManyAssets(usize),
     TooManyInputs(usize),
+    UnknownNoteInput(String),
 }
 
 impl NoteError {
@@ -259,7 +400,21 @@ impl std::error::Error for NoteError {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ChainMmrError {
-    BlockNumTooBig { chain_length: usize, block_num: usize },
+    BlockNotTracked(u32),
+    BlockNumTooBig {
+        chain_length: usize,
+        block_num: usize,
+    },
+    ChainRootMismatch {
+        block_num: u32,
+        expected: Digest,
+        actual: Digest,
+    },
+    InvalidBlockPath(u32),
+    UnexpectedBlockNum {
+        expected: u32,
+        actual: u32,
+    },
 }
 
 impl ChainMmrError {
@@ -277,6 +432,26 @@ impl fmt::Display for ChainMmrError {
 #[cfg(feature = "std")]
 impl std::error::Error for ChainMmrError {}
 
+// TRANSACTION BATCH ERROR
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionBatchError {
+    CyclicNoteDependency(TransactionId),
+    DuplicateInputNote(Digest),
+    DuplicateOutputNote(NoteId),
+    UnconsumedUnauthenticatedNote(NoteId),
+}
+
+impl fmt::Display for TransactionBatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransactionBatchError {}
+
 // TRANSACTION SCRIPT ERROR
 // ================================================================================================
 
@@ -302,7 +477,9 @@ pub enum TransactionInputError {
     AccountSeedNotProvidedForNewAccount,
     AccountSeedProvidedForExistingAccount,
     DuplicateInputNote(Digest),
+    InconsistentForeignAccountInputs(AccountId),
     InvalidAccountSeed(AccountError),
+    InvalidFeeParameters(FeeParameters),
     TooManyInputNotes { max: usize, actual: usize },
 }
 
@@ -323,7 +500,14 @@ pub enum TransactionOutputError {
     DuplicateOutputNote(NoteId),
     ExtractAccountStorageSlotsDeltaFailed(MerkleError),
     FinalAccountDataNotFound,
-    FinalAccountStubDataInvalid(AccountError),
+    FinalAccountHeaderDataInvalid(AccountError),
+    FinalAccountStateInconsistent(Digest, Digest),
+    FinalAccountStorageDeltaInvalid(AccountError),
+    FinalAccountVaultDeltaInvalid(AssetVaultError),
+    FeeNoteNotFound,
+    InvalidExpirationBlockNum(u64),
+    InvalidFee(AssetError),
+    InvalidFeeNumCycles(u64),
     OutputNoteDataNotFound,
     OutputNoteDataInvalid(NoteError),
     OutputNotesCommitmentInconsistent(Digest, Digest),
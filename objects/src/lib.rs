@@ -16,8 +16,9 @@ pub mod transaction;
 
 mod errors;
 pub use errors::{
-    AccountDeltaError, AccountError, AssetError, AssetVaultError, ChainMmrError, NoteError,
-    TransactionInputError, TransactionOutputError, TransactionScriptError,
+    AccountDeltaError, AccountError, AccountUpdateError, AssetError, AssetVaultError, BlockError,
+    ChainMmrError, NoteError, NullifierTreeError, TransactionBatchError, TransactionInputError,
+    TransactionOutputError, TransactionScriptError,
 };
 // RE-EXPORTS
 // ================================================================================================
@@ -39,15 +40,53 @@ pub mod utils {
     pub use miden_crypto::utils::{format, vec};
     pub use vm_core::utils::{collections, group_slice_elements, string, IntoBytes};
 
+    pub mod sync {
+        #[cfg(not(feature = "std"))]
+        pub use alloc::sync::Arc;
+        #[cfg(feature = "std")]
+        pub use std::sync::Arc;
+
+        // Lazily computed values (e.g., object hashes) are cached in a `OnceLock` so that the
+        // objects caching them can be shared between threads; without `std` there are no threads,
+        // and a `OnceCell` is used instead.
+        #[cfg(not(feature = "std"))]
+        pub use core::cell::OnceCell as OnceLock;
+        #[cfg(feature = "std")]
+        pub use std::sync::OnceLock;
+    }
+
     pub mod serde {
         pub use miden_crypto::utils::{
-            ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+            ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
         };
     }
 }
 
+/// Re-exports of the types most commonly used when working with accounts, assets, notes, and
+/// transactions.
+pub mod prelude {
+    pub use crate::{
+        accounts::{
+            Account, AccountCode, AccountDelta, AccountFile, AccountHeader, AccountId,
+            AccountStorage, AccountType, StorageSlotType,
+        },
+        assembly::{ModuleAst, ProgramAst},
+        assets::{Asset, AssetVault, FungibleAsset, NonFungibleAsset, TokenSymbol},
+        notes::{
+            NetworkNote, Note, NoteAssets, NoteExecutionHint, NoteFile, NoteId, NoteInputs,
+            NoteMetadata, NoteScript, NoteTag, NoteType, Nullifier,
+        },
+        transaction::{
+            ChainMmr, ExecutedTransaction, InputNote, InputNotes, OutputNote, OutputNotes,
+            ProvenTransaction, TransactionId, TransactionInputs, TransactionScript,
+            TransactionScriptInputs, TransactionWitness,
+        },
+        BlockHeader, Digest, Felt, Hasher, Word, ONE, ZERO,
+    };
+}
+
 pub mod vm {
     pub use miden_verifier::ExecutionProof;
-    pub use vm_core::{code_blocks::CodeBlock, Program, ProgramInfo};
+    pub use vm_core::{code_blocks::CodeBlock, Kernel, Program, ProgramInfo};
     pub use vm_processor::{AdviceInputs, StackInputs, StackOutputs};
 }
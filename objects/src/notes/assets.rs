@@ -1,9 +1,8 @@
-use core::cell::OnceCell;
-
 use miden_crypto::utils::{ByteReader, ByteWriter, Deserializable, Serializable};
 use vm_processor::DeserializationError;
 
 use super::{Asset, Digest, Felt, Hasher, NoteError, Vec, Word, WORD_SIZE, ZERO};
+use crate::utils::sync::OnceLock;
 
 // NOTE ASSETS
 // ================================================================================================
@@ -15,10 +14,13 @@ use super::{Asset, Digest, Felt, Hasher, NoteError, Vec, Word, WORD_SIZE, ZERO};
 /// All the assets in a note can be reduced to a single commitment which is computed by
 /// sequentially hashing the assets. Note that the same list of assets can result in two different
 /// commitments if the asset ordering is different.
+///
+/// Since notes usually carry only a few assets, up to [NoteAssets::INLINE_CAPACITY] assets are
+/// stored inline (without a heap allocation).
 #[derive(Debug, Clone)]
 pub struct NoteAssets {
-    assets: Vec<Asset>,
-    hash: OnceCell<Digest>,
+    assets: AssetList,
+    hash: OnceLock<Digest>,
 }
 
 impl NoteAssets {
@@ -27,6 +29,9 @@ impl NoteAssets {
     /// The maximum number of assets which can be carried by a single note.
     pub const MAX_NUM_ASSETS: usize = 255;
 
+    /// The maximum number of assets which are stored without a heap allocation.
+    pub const INLINE_CAPACITY: usize = 4;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns new [NoteAssets] constructed from the provided list of assets.
@@ -56,8 +61,8 @@ impl NoteAssets {
         }
 
         Ok(Self {
-            assets: assets.to_vec(),
-            hash: OnceCell::new(),
+            assets: AssetList::from_slice(assets),
+            hash: OnceLock::new(),
         })
     }
 
@@ -66,17 +71,17 @@ impl NoteAssets {
 
     /// Returns a commitment to the note's assets.
     pub fn commitment(&self) -> Digest {
-        *self.hash.get_or_init(|| compute_asset_commitment(&self.assets))
+        *self.hash.get_or_init(|| compute_asset_commitment(self.assets.as_slice()))
     }
 
     /// Returns the number of assets.
     pub fn num_assets(&self) -> usize {
-        self.assets.len()
+        self.assets.as_slice().len()
     }
 
     /// Returns an iterator over all assets.
     pub fn iter(&self) -> core::slice::Iter<Asset> {
-        self.assets.iter()
+        self.assets.as_slice().iter()
     }
 
     /// Returns all assets represented as a vector of field elements.
@@ -85,17 +90,18 @@ impl NoteAssets {
     /// because hashing the returned elements results in the note asset commitment.
     pub fn to_padded_assets(&self) -> Vec<Felt> {
         // if we have an odd number of assets with pad with a single word.
-        let padded_len = if self.assets.len() % 2 == 0 {
-            self.assets.len() * WORD_SIZE
+        let num_assets = self.num_assets();
+        let padded_len = if num_assets % 2 == 0 {
+            num_assets * WORD_SIZE
         } else {
-            (self.assets.len() + 1) * WORD_SIZE
+            (num_assets + 1) * WORD_SIZE
         };
 
         // allocate a vector to hold the padded assets
-        let mut padded_assets = Vec::with_capacity(padded_len * WORD_SIZE);
+        let mut padded_assets = Vec::with_capacity(padded_len);
 
         // populate the vector with the assets
-        padded_assets.extend(self.assets.iter().flat_map(|asset| <[Felt; 4]>::from(*asset)));
+        padded_assets.extend(self.iter().flat_map(|asset| <[Felt; 4]>::from(*asset)));
 
         // pad with an empty word if we have an odd number of assets
         padded_assets.resize(padded_len, ZERO);
@@ -106,12 +112,56 @@ impl NoteAssets {
 
 impl PartialEq for NoteAssets {
     fn eq(&self, other: &Self) -> bool {
-        self.assets == other.assets
+        self.assets.as_slice() == other.assets.as_slice()
     }
 }
 
 impl Eq for NoteAssets {}
 
+// ASSET LIST
+// ================================================================================================
+
+/// A list of assets which keeps up to [NoteAssets::INLINE_CAPACITY] assets inline, and moves
+/// larger lists to the heap.
+#[derive(Clone)]
+enum AssetList {
+    Inline {
+        assets: [Asset; NoteAssets::INLINE_CAPACITY],
+        len: u8,
+    },
+    Heap(Vec<Asset>),
+}
+
+impl AssetList {
+    /// Returns a new [AssetList] containing the provided assets.
+    ///
+    /// The provided list is assumed to be non-empty.
+    fn from_slice(assets: &[Asset]) -> Self {
+        if assets.len() > NoteAssets::INLINE_CAPACITY {
+            return Self::Heap(assets.to_vec());
+        }
+
+        // unused slots are filled with copies of the first asset; they are never exposed
+        let mut inline = [assets[0]; NoteAssets::INLINE_CAPACITY];
+        inline[..assets.len()].copy_from_slice(assets);
+        Self::Inline { assets: inline, len: assets.len() as u8 }
+    }
+
+    /// Returns the assets in this list as a slice.
+    fn as_slice(&self) -> &[Asset] {
+        match self {
+            Self::Inline { assets, len } => &assets[..*len as usize],
+            Self::Heap(assets) => assets,
+        }
+    }
+}
+
+impl core::fmt::Debug for AssetList {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -150,9 +200,11 @@ fn compute_asset_commitment(assets: &[Asset]) -> Digest {
 
 impl Serializable for NoteAssets {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        debug_assert!(self.assets.len() <= NoteAssets::MAX_NUM_ASSETS);
-        target.write_u8((self.assets.len() - 1) as u8);
-        self.assets.write_into(target);
+        debug_assert!(self.num_assets() <= NoteAssets::MAX_NUM_ASSETS);
+        target.write_u8((self.num_assets() - 1) as u8);
+        for asset in self.iter() {
+            asset.write_into(target);
+        }
     }
 }
 
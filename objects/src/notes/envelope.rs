@@ -11,12 +11,12 @@ use super::{Felt, Note, NoteId, NoteMetadata, Vec, Word};
 ///
 /// Contains:
 /// - note_id: ID of the note that was created
-/// - note_metadata: metadata of the note that was created. Metadata is padded with ZERO such that
-///   it is four elements in size (a word). The metadata includes the following elements:
-///     - sender
-///     - tag
+/// - note_metadata: metadata of the note that was created. Metadata is four elements in size (a
+///   word). The metadata includes the following elements:
 ///     - num assets
-///     - ZERO
+///     - tag and expiration block number (the expiration is ZERO if the note never expires)
+///     - sender
+///     - aux
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct NoteEnvelope {
     note_id: NoteId,
@@ -1,9 +1,11 @@
-use core::cell::OnceCell;
-
 use miden_crypto::utils::{ByteReader, ByteWriter, Deserializable, Serializable};
 use vm_processor::DeserializationError;
 
-use super::{Digest, Felt, Hasher, NoteError, Vec, ZERO};
+use super::{Digest, Felt, Hasher, NoteError, ZERO};
+use crate::{
+    transaction::ToInputElements,
+    utils::{collections::Vec, sync::OnceLock},
+};
 
 // NOTE INPUTS
 // ================================================================================================
@@ -15,12 +17,12 @@ use super::{Digest, Felt, Hasher, NoteError, Vec, ZERO};
 #[derive(Clone, Debug)]
 pub struct NoteInputs {
     inputs: [Felt; 16],
-    hash: OnceCell<Digest>,
+    hash: OnceLock<Digest>,
 }
 
 impl NoteInputs {
     /// Number of note inputs.
-    const NOTE_NUM_INPUTS: usize = 16;
+    pub const NOTE_NUM_INPUTS: usize = 16;
 
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
@@ -35,22 +37,53 @@ impl NoteInputs {
             return Err(NoteError::too_many_inputs(inputs.len()));
         }
 
-        // pad inputs with ZERO to be constant size (16 elements)
-        let padded_inputs: [Felt; Self::NOTE_NUM_INPUTS] = inputs
-            .iter()
-            .cloned()
-            .chain(core::iter::repeat(ZERO))
-            .take(Self::NOTE_NUM_INPUTS)
-            .collect::<Vec<_>>()
-            .try_into()
-            .expect("padded are of the correct length");
+        // pad inputs with ZERO to be constant size (16 elements); this is done in place to avoid
+        // allocating an intermediate vector
+        let mut padded_inputs = [ZERO; Self::NOTE_NUM_INPUTS];
+        padded_inputs[..inputs.len()].copy_from_slice(inputs);
 
         Ok(Self {
             inputs: padded_inputs,
-            hash: OnceCell::new(),
+            hash: OnceLock::new(),
         })
     }
 
+    /// Returns NoteInputs created from the encodings of the provided values.
+    ///
+    /// The values are encoded via [ToInputElements] and concatenated in the order in which they
+    /// are provided, e.g.:
+    ///
+    /// ```ignore
+    /// let inputs = NoteInputs::from_values(&[&target_account_id, &amount, &recall_enabled])?;
+    /// ```
+    ///
+    /// results in inputs `[target_account_id, amount_lo, amount_hi, recall_enabled]` for a [u64]
+    /// amount and a [bool] flag.
+    ///
+    /// When the inputs are loaded into memory via `push.{ptr} exec.note::get_inputs`, input `i` is
+    /// located at position `i % 4` of the word at address `ptr + i / 4`. As `mem_loadw` puts the
+    /// last element of a word on top of the stack, the first word of the example above is read
+    /// as follows:
+    ///
+    /// ```masm
+    /// padw push.0 exec.note::get_inputs mem_loadw
+    /// # => [recall_enabled, amount_hi, amount_lo, target_account_id]
+    /// ```
+    ///
+    /// The encodings of values spanning multiple elements (e.g., [Digest]) are not aligned to
+    /// words; values which should be read via a single `mem_loadw` must be placed at an offset
+    /// which is a multiple of 4.
+    ///
+    /// # Errors
+    /// Returns an error if the encoded values span more than 16 elements.
+    pub fn from_values(values: &[&dyn ToInputElements]) -> Result<Self, NoteError> {
+        let mut inputs = Vec::new();
+        for value in values {
+            value.write_into_elements(&mut inputs);
+        }
+        Self::new(&inputs)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -99,10 +132,11 @@ impl Deserializable for NoteInputs {
 #[cfg(test)]
 mod tests {
     use super::{Felt, NoteInputs, ZERO};
+    use crate::{accounts::AccountId, Digest, ONE};
 
     #[test]
     fn test_input_ordering() {
-        use super::Vec;
+        use crate::utils::collections::Vec;
 
         // inputs are provided in reverse stack order
         let inputs = Vec::from([Felt::new(1), Felt::new(2), Felt::new(3)]);
@@ -129,4 +163,30 @@ mod tests {
         let note_inputs = NoteInputs::new(&inputs).expect("note created should succeed");
         assert_eq!(&expected_ordering, note_inputs.inputs());
     }
+
+    #[test]
+    fn test_inputs_from_values() {
+        let account_id = AccountId::new_unchecked(Felt::new(7));
+        let amount: u64 = (3 << 32) | 5;
+        let digest = Digest::new([Felt::new(10), Felt::new(11), Felt::new(12), Felt::new(13)]);
+
+        let note_inputs = NoteInputs::from_values(&[&account_id, &amount, &true, &digest])
+            .expect("inputs should fit");
+        assert_eq!(
+            &note_inputs.inputs()[..8],
+            &[
+                Felt::new(7),
+                Felt::new(5),
+                Felt::new(3),
+                ONE,
+                Felt::new(10),
+                Felt::new(11),
+                Felt::new(12),
+                Felt::new(13)
+            ]
+        );
+
+        let too_many = [digest; 5];
+        assert!(NoteInputs::from_values(&[&too_many]).is_err());
+    }
 }
@@ -1,25 +1,67 @@
 use miden_crypto::utils::{ByteReader, ByteWriter, Deserializable, Serializable};
+use vm_core::StarkField;
 use vm_processor::DeserializationError;
 
-use super::{AccountId, Felt, NoteError, Word};
+use super::{AccountId, Felt, NoteAssets, NoteError, NoteTag, ToString, Word, ZERO};
 
-/// Represents metadata associated with a note. This includes the sender, tag, and number of assets.
+/// Represents metadata associated with a note. This includes the sender, tag, number of assets,
+/// expiration block number, and auxiliary data.
 /// - sender is the account which created the note.
-/// - tag is a tag which can be used to identify the target account for the note.
+/// - tag is a [NoteTag] which can be used to identify the target account for the note, and which
+///   hints at who is expected to execute the note.
 /// - num_assets is the number of assets in the note.
+/// - expiration_block_num is the number of the last block in which the note can be consumed. The
+///   transaction kernel accepts only transactions consuming the note against an earlier reference
+///   block, and lowers their expiration block number to that of the note. Notes without an
+///   expiration block number never expire.
+/// - aux is an arbitrary field element which can be set by the sender of the note (e.g., to
+///   carry application specific data alongside the tag).
+///
+/// The metadata is encoded into a word as `[num_assets, tag | expiration_block_num << 32, sender,
+/// aux]`, i.e., the tag and the expiration block number share the second element of the word.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct NoteMetadata {
     sender: AccountId,
-    tag: Felt,
+    tag: NoteTag,
     num_assets: Felt,
+    expiration_block_num: Option<u32>,
+    aux: Felt,
 }
 
 impl NoteMetadata {
     /// Returns a new note metadata object created with the specified parameters.
-    pub fn new(sender: AccountId, tag: Felt, num_assets: Felt) -> Self {
-        // TODO: Assert num assets is valid
-        Self { sender, tag, num_assets }
+    ///
+    /// The returned metadata does not have an expiration block number, and its auxiliary field is
+    /// set to ZERO.
+    pub fn new(sender: AccountId, tag: NoteTag, num_assets: Felt) -> Self {
+        Self {
+            sender,
+            tag,
+            num_assets,
+            expiration_block_num: None,
+            aux: ZERO,
+        }
+    }
+
+    /// Returns this metadata with the expiration block number set to the specified value.
+    ///
+    /// # Errors
+    /// Returns an error if the expiration block number is 0, as 0 is used to encode notes which
+    /// never expire, or if it is [u32::MAX], as such block number cannot be encoded together with
+    /// the tag in a single field element.
+    pub fn with_expiration(mut self, expiration_block_num: u32) -> Result<Self, NoteError> {
+        if expiration_block_num == 0 || expiration_block_num == u32::MAX {
+            return Err(NoteError::InvalidExpirationBlockNum(expiration_block_num as u64));
+        }
+        self.expiration_block_num = Some(expiration_block_num);
+        Ok(self)
+    }
+
+    /// Returns this metadata with the auxiliary field set to the specified value.
+    pub fn with_aux(mut self, aux: Felt) -> Self {
+        self.aux = aux;
+        self
     }
 
     /// Returns the account which created the note.
@@ -28,7 +70,7 @@ impl NoteMetadata {
     }
 
     /// Returns the tag associated with the note.
-    pub fn tag(&self) -> Felt {
+    pub fn tag(&self) -> NoteTag {
         self.tag
     }
 
@@ -36,6 +78,47 @@ impl NoteMetadata {
     pub fn num_assets(&self) -> Felt {
         self.num_assets
     }
+
+    /// Returns the number of the last block in which the note can be consumed, or None if the
+    /// note never expires.
+    pub fn expiration_block_num(&self) -> Option<u32> {
+        self.expiration_block_num
+    }
+
+    /// Returns the auxiliary field of the note.
+    pub fn aux(&self) -> Felt {
+        self.aux
+    }
+
+    /// Returns true if the note cannot be consumed in the block with the specified number.
+    ///
+    /// Transactions are included into a block following their reference block, thus a transaction
+    /// consuming the note must be executed against a reference block `n` such that
+    /// `!is_expired(n + 1)`.
+    ///
+    /// Expired notes can never be consumed again, and thus can be discarded by the operator.
+    pub fn is_expired(&self, block_num: u32) -> bool {
+        self.expiration_block_num.map_or(false, |expiration| block_num > expiration)
+    }
+
+    /// Checks that this metadata is well formed.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The number of assets is greater than [NoteAssets::MAX_NUM_ASSETS].
+    /// - The expiration block number is 0 or [u32::MAX].
+    pub fn validate(&self) -> Result<(), NoteError> {
+        let num_assets = self.num_assets.as_int();
+        if num_assets > NoteAssets::MAX_NUM_ASSETS as u64 {
+            return Err(NoteError::TooManyAssets(num_assets as usize));
+        }
+        if let Some(expiration_block_num) = self.expiration_block_num {
+            if expiration_block_num == 0 || expiration_block_num == u32::MAX {
+                return Err(NoteError::InvalidExpirationBlockNum(expiration_block_num as u64));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl From<NoteMetadata> for Word {
@@ -46,10 +129,14 @@ impl From<NoteMetadata> for Word {
 
 impl From<&NoteMetadata> for Word {
     fn from(metadata: &NoteMetadata) -> Self {
+        let expiration_block_num = metadata.expiration_block_num.unwrap_or(0) as u64;
+        let tag = metadata.tag.inner() as u64;
+
         let mut elements = Word::default();
         elements[0] = metadata.num_assets;
-        elements[1] = metadata.tag;
+        elements[1] = Felt::new(tag | (expiration_block_num << 32));
         elements[2] = metadata.sender.into();
+        elements[3] = metadata.aux;
         elements
     }
 }
@@ -58,12 +145,22 @@ impl TryFrom<Word> for NoteMetadata {
     type Error = NoteError;
 
     fn try_from(elements: Word) -> Result<Self, Self::Error> {
-        // TODO: Assert num assets is valid
-        Ok(Self {
+        let tag_and_expiration = elements[1].as_int();
+        let expiration_block_num = match (tag_and_expiration >> 32) as u32 {
+            0 => None,
+            value => Some(value),
+        };
+
+        let metadata = Self {
             sender: elements[2].try_into().map_err(NoteError::NoteMetadataSenderInvalid)?,
-            tag: elements[1],
+            tag: NoteTag::from(tag_and_expiration as u32),
             num_assets: elements[0],
-        })
+            expiration_block_num,
+            aux: elements[3],
+        };
+        metadata.validate()?;
+
+        Ok(metadata)
     }
 }
 
@@ -75,15 +172,91 @@ impl Serializable for NoteMetadata {
         self.sender.write_into(target);
         self.tag.write_into(target);
         self.num_assets.write_into(target);
+        target.write_u32(self.expiration_block_num.unwrap_or(0));
+        self.aux.write_into(target);
     }
 }
 
 impl Deserializable for NoteMetadata {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let sender = AccountId::read_from(source)?;
-        let tag = Felt::read_from(source)?;
+        let tag = NoteTag::read_from(source)?;
         let num_assets = Felt::read_from(source)?;
+        let expiration_block_num = match source.read_u32()? {
+            0 => None,
+            value => Some(value),
+        };
+        let aux = Felt::read_from(source)?;
+
+        let metadata = Self {
+            sender,
+            tag,
+            num_assets,
+            expiration_block_num,
+            aux,
+        };
+        metadata
+            .validate()
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+
+        Ok(metadata)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_crypto::utils::{Deserializable, Serializable};
+
+    use super::{Felt, NoteMetadata, NoteTag, Word, ZERO};
+    use crate::{
+        accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN},
+        notes::NoteExecutionHint,
+        NoteError, ONE,
+    };
+
+    fn sender() -> AccountId {
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap()
+    }
+
+    #[test]
+    fn metadata_word_encoding() {
+        let tag = NoteTag::for_public_use_case(5, 7, NoteExecutionHint::Local).unwrap();
+        let metadata = NoteMetadata::new(sender(), tag, ONE)
+            .with_expiration(1000)
+            .unwrap()
+            .with_aux(Felt::new(42));
+
+        let word = Word::from(metadata);
+        assert_eq!(word[0], ONE);
+        assert_eq!(word[1], Felt::new(tag.inner() as u64 | 1000 << 32));
+        assert_eq!(word[2], Felt::from(sender()));
+        assert_eq!(word[3], Felt::new(42));
+        assert_eq!(NoteMetadata::try_from(word).unwrap(), metadata);
+
+        let bytes = metadata.to_bytes();
+        assert_eq!(NoteMetadata::read_from_bytes(&bytes).unwrap(), metadata);
+
+        // notes without an expiration block number encode the tag only
+        let metadata = NoteMetadata::new(sender(), tag, ONE);
+        let word = Word::from(metadata);
+        assert_eq!(word[1], Felt::from(tag));
+        assert_eq!(word[3], ZERO);
+        assert_eq!(NoteMetadata::try_from(word).unwrap().expiration_block_num(), None);
+    }
+
+    #[test]
+    fn metadata_validation() {
+        let metadata = NoteMetadata::new(sender(), NoteTag::default(), ONE);
+        assert_eq!(metadata.with_expiration(0), Err(NoteError::InvalidExpirationBlockNum(0)));
+        assert_eq!(
+            metadata.with_expiration(u32::MAX),
+            Err(NoteError::InvalidExpirationBlockNum(u32::MAX as u64))
+        );
 
-        Ok(Self { sender, tag, num_assets })
+        let word = [Felt::new(256), ZERO, Felt::from(sender()), ZERO];
+        assert_eq!(NoteMetadata::try_from(word), Err(NoteError::TooManyAssets(256)));
     }
 }
@@ -1,14 +1,12 @@
-use core::cell::OnceCell;
-
 use vm_processor::DeserializationError;
 
 use super::{
     accounts::AccountId,
     assembly::{Assembler, AssemblyContext, ProgramAst},
     assets::Asset,
-    utils::{collections::Vec, string::ToString},
+    utils::{collections::Vec, string::ToString, sync::OnceLock},
     vm::CodeBlock,
-    Digest, Felt, Hasher, NoteError, Word, WORD_SIZE, ZERO,
+    Digest, Felt, Hasher, NoteError, Word, ZERO,
 };
 
 mod envelope;
@@ -20,9 +18,21 @@ pub use inputs::NoteInputs;
 mod metadata;
 pub use metadata::NoteMetadata;
 
+mod network;
+pub use network::NetworkNote;
+
+mod note_file;
+pub use note_file::NoteFile;
+
 mod note_id;
 pub use note_id::NoteId;
 
+mod note_tag;
+pub use note_tag::{NoteExecutionHint, NoteTag};
+
+mod note_type;
+pub use note_type::NoteType;
+
 mod nullifier;
 pub use nullifier::Nullifier;
 
@@ -65,16 +75,25 @@ pub const NOTE_LEAF_DEPTH: u8 = NOTE_TREE_DEPTH + 1;
 /// Auxiliary data which is used to verify authenticity and signal additional information:
 /// - A metadata object which contains information about the sender, the tag and the number of
 ///   assets in the note.
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// - A memo commitment, which is an opaque word attached to the note by its creator (e.g., the
+///   hash of an invoice). The memo is committed to by the note recipient, and can be read by the
+///   note script via `note::get_memo`. Notes without a memo have the memo set to
+///   [Digest::default()].
+///
+/// Commitments to the note (recipient, ID and nullifier) are computed lazily and cached, so that
+/// repeated access to them is cheap.
+#[derive(Clone, Debug)]
 pub struct Note {
     script: NoteScript,
     inputs: NoteInputs,
     assets: NoteAssets,
     serial_num: Word,
     metadata: NoteMetadata,
+    memo: Digest,
 
-    id: OnceCell<NoteId>,
-    nullifier: OnceCell<Nullifier>,
+    recipient: OnceLock<Digest>,
+    id: OnceLock<NoteId>,
+    nullifier: OnceLock<Nullifier>,
 }
 
 impl Note {
@@ -93,7 +112,7 @@ impl Note {
         assets: &[Asset],
         serial_num: Word,
         sender: AccountId,
-        tag: Felt,
+        tag: NoteTag,
     ) -> Result<Self, NoteError> {
         let assets = NoteAssets::new(assets)?;
         let num_assets = assets.num_assets();
@@ -103,8 +122,10 @@ impl Note {
             assets,
             serial_num,
             metadata: NoteMetadata::new(sender, tag, Felt::new(num_assets as u64)),
-            id: OnceCell::new(),
-            nullifier: OnceCell::new(),
+            memo: Digest::default(),
+            recipient: OnceLock::new(),
+            id: OnceLock::new(),
+            nullifier: OnceLock::new(),
         })
     }
 
@@ -122,11 +143,48 @@ impl Note {
             assets,
             serial_num,
             metadata,
-            id: OnceCell::new(),
-            nullifier: OnceCell::new(),
+            memo: Digest::default(),
+            recipient: OnceLock::new(),
+            id: OnceLock::new(),
+            nullifier: OnceLock::new(),
         }
     }
 
+    /// Returns this note with the expiration block number set to the specified value.
+    ///
+    /// The note cannot be consumed in transactions executed against blocks after the block with
+    /// the specified number.
+    ///
+    /// # Errors
+    /// Returns an error if the expiration block number is 0.
+    pub fn with_expiration(mut self, expiration_block_num: u32) -> Result<Self, NoteError> {
+        self.metadata = self.metadata.with_expiration(expiration_block_num)?;
+        Ok(self)
+    }
+
+    /// Returns this note with the memo commitment set to the specified value.
+    ///
+    /// As the memo is committed to by the note recipient, the recipient and the ID of the returned
+    /// note differ from the ones of this note.
+    pub fn with_memo(mut self, memo: Digest) -> Self {
+        self.memo = memo;
+        self.recipient = OnceLock::new();
+        self.id = OnceLock::new();
+        self
+    }
+
+    /// Returns this note with the memo commitment set to the commitment to the specified data.
+    ///
+    /// The commitment is computed via [Note::compute_memo()].
+    pub fn with_memo_data(self, data: &[u8]) -> Self {
+        self.with_memo(Self::compute_memo(data))
+    }
+
+    /// Returns the memo commitment to the specified data (e.g., a serialized invoice).
+    pub fn compute_memo(data: &[u8]) -> Digest {
+        Hasher::hash(data)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -155,13 +213,43 @@ impl Note {
         &self.metadata
     }
 
+    /// Returns the memo commitment of this note; [Digest::default()] if the note has no memo.
+    pub fn memo(&self) -> Digest {
+        self.memo
+    }
+
+    /// Returns true if the memo of this note is a commitment to the specified data.
+    pub fn verify_memo(&self, data: &[u8]) -> bool {
+        self.memo == Self::compute_memo(data)
+    }
+
     /// Returns the recipient of this note.
     /// Recipient is defined and calculated as:
-    ///  hash(hash(hash(serial_num, [0; 4]), script_hash), input_hash)
+    ///  hash(hash(hash(serial_num, memo), script_hash), input_hash)
+    ///
+    /// For notes without a memo, the memo is [0; 4].
     pub fn recipient(&self) -> Digest {
-        let serial_num_hash = Hasher::merge(&[self.serial_num.into(), Digest::default()]);
-        let merge_script = Hasher::merge(&[serial_num_hash, self.script.hash()]);
-        Hasher::merge(&[merge_script, self.inputs.hash()])
+        *self.recipient.get_or_init(|| {
+            Self::compute_recipient(
+                self.serial_num,
+                self.memo,
+                self.script.hash(),
+                self.inputs.hash(),
+            )
+        })
+    }
+
+    /// Returns the recipient computed from the specified serial number, memo commitment, script
+    /// hash and inputs commitment (see [Note::recipient()]).
+    pub fn compute_recipient(
+        serial_num: Word,
+        memo: Digest,
+        script_hash: Digest,
+        inputs_hash: Digest,
+    ) -> Digest {
+        let serial_num_hash = Hasher::merge(&[serial_num.into(), memo]);
+        let merge_script = Hasher::merge(&[serial_num_hash, script_hash]);
+        Hasher::merge(&[merge_script, inputs_hash])
     }
 
     /// Returns a unique identifier of this note, which is simultaneously a commitment to the note.
@@ -181,6 +269,20 @@ impl Note {
     }
 }
 
+impl PartialEq for Note {
+    fn eq(&self, other: &Self) -> bool {
+        // cached commitments are derived from the other fields and are not compared
+        self.script == other.script
+            && self.inputs == other.inputs
+            && self.assets == other.assets
+            && self.serial_num == other.serial_num
+            && self.metadata == other.metadata
+            && self.memo == other.memo
+    }
+}
+
+impl Eq for Note {}
+
 // SERIALIZATION
 // ================================================================================================
 
@@ -192,7 +294,9 @@ impl Serializable for Note {
             assets,
             serial_num,
             metadata,
+            memo,
 
+            recipient: _,
             id: _,
             nullifier: _,
         } = self;
@@ -202,6 +306,7 @@ impl Serializable for Note {
         assets.write_into(target);
         serial_num.write_into(target);
         metadata.write_into(target);
+        memo.write_into(target);
     }
 }
 
@@ -212,6 +317,7 @@ impl Deserializable for Note {
         let assets = NoteAssets::read_from(source)?;
         let serial_num = Word::read_from(source)?;
         let metadata = NoteMetadata::read_from(source)?;
+        let memo = Digest::read_from(source)?;
 
         Ok(Self {
             script,
@@ -219,8 +325,10 @@ impl Deserializable for Note {
             assets,
             serial_num,
             metadata,
-            id: OnceCell::new(),
-            nullifier: OnceCell::new(),
+            memo,
+            recipient: OnceLock::new(),
+            id: OnceLock::new(),
+            nullifier: OnceLock::new(),
         })
     }
 }
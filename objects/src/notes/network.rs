@@ -0,0 +1,191 @@
+use core::cmp::Ordering;
+
+use miden_crypto::utils::{ByteReader, ByteWriter, Deserializable, Serializable};
+use vm_processor::DeserializationError;
+
+use super::{
+    AccountId, Note, NoteError, NoteExecutionHint, NoteId, NoteTag, NoteType, Nullifier, ToString,
+};
+
+// NETWORK NOTE
+// ================================================================================================
+
+/// A note which is expected to be consumed by the network operator.
+///
+/// Network notes carry a tag with the [NoteExecutionHint::Network] execution hint. Such notes are
+/// public, and thus the operator can consume them against on-chain accounts without involving
+/// their owners: a note with a network account tag (see [NoteTag::for_network_account()]) is
+/// consumed against the account it targets, while a note with a network use case tag is consumed
+/// against an account chosen by the operator.
+///
+/// Network notes are ordered by their tags first, and by their IDs second. Thus, a sorted
+/// collection of network notes (e.g., the queue of notes awaiting execution by the operator) keeps
+/// the notes targeting the same account next to each other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkNote {
+    note: Note,
+    tag: NoteTag,
+}
+
+impl NetworkNote {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [NetworkNote] wrapping the provided note, which was created with the
+    /// specified note type.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The note is not expected to be consumed by the network.
+    /// - The note is not public, and thus cannot be consumed by the network operator.
+    pub fn new(note: Note, note_type: NoteType) -> Result<Self, NoteError> {
+        let tag = note.metadata().tag();
+        if tag.execution_hint() != NoteExecutionHint::Network {
+            return Err(NoteError::NotANetworkNote(note.id()));
+        }
+        tag.validate(note_type)?;
+
+        Ok(Self { note, tag })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the underlying note.
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    /// Returns the ID of this note.
+    pub fn id(&self) -> NoteId {
+        self.note.id()
+    }
+
+    /// Returns the nullifier of this note.
+    pub fn nullifier(&self) -> Nullifier {
+        self.note.nullifier()
+    }
+
+    /// Returns the tag of this note.
+    pub fn tag(&self) -> NoteTag {
+        self.tag
+    }
+
+    /// Returns true if this note can be consumed by the network against the specified account.
+    ///
+    /// Notes with a network account tag can be consumed only against the account they target,
+    /// while notes with a network use case tag can be consumed against any on-chain account.
+    pub fn can_be_consumed_by(&self, account_id: AccountId) -> bool {
+        if !account_id.is_on_chain() {
+            return false;
+        }
+        !self.tag.is_network_account() || self.tag.targets_network_account(account_id)
+    }
+
+    /// Consumes this network note and returns the underlying note.
+    pub fn into_note(self) -> Note {
+        self.note
+    }
+}
+
+impl PartialOrd for NetworkNote {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NetworkNote {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tag.cmp(&other.tag).then_with(|| self.id().cmp(&other.id()))
+    }
+}
+
+impl From<NetworkNote> for Note {
+    fn from(note: NetworkNote) -> Self {
+        note.note
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for NetworkNote {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.note.write_into(target);
+    }
+}
+
+impl Deserializable for NetworkNote {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // only public notes can be wrapped into network notes
+        let note = Note::read_from(source)?;
+        Self::new(note, NoteType::Public)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{NetworkNote, Note, NoteExecutionHint, NoteTag, NoteType};
+    use crate::{
+        accounts::{
+            AccountId, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+            ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+            ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
+        },
+        assembly::ProgramAst,
+        assets::FungibleAsset,
+        notes::NoteScript,
+        utils::serde::{Deserializable, Serializable},
+        Digest, Felt, NoteError,
+    };
+
+    fn note_with_tag(tag: NoteTag) -> Note {
+        let code = ProgramAst::parse("begin push.1 drop end").unwrap();
+        let script = NoteScript::from_parts(code, Digest::default());
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let asset = FungibleAsset::new(faucet_id, 100).unwrap();
+        let sender =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN).unwrap();
+        Note::new(script, &[], &[asset.into()], [Felt::new(1); 4], sender, tag).unwrap()
+    }
+
+    #[test]
+    fn public_network_notes_are_accepted() {
+        let target =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let note = note_with_tag(NoteTag::for_network_account(target).unwrap());
+
+        let network_note = NetworkNote::new(note.clone(), NoteType::Public).unwrap();
+        assert_eq!(network_note.id(), note.id());
+        assert!(network_note.can_be_consumed_by(target));
+
+        let bytes = network_note.to_bytes();
+        assert_eq!(NetworkNote::read_from_bytes(&bytes).unwrap(), network_note);
+    }
+
+    #[test]
+    fn off_chain_and_local_notes_are_rejected() {
+        let target =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let tag = NoteTag::for_network_account(target).unwrap();
+        assert_eq!(
+            NetworkNote::new(note_with_tag(tag), NoteType::OffChain),
+            Err(NoteError::NoteTagRequiresPublicNote(tag))
+        );
+
+        let tag = NoteTag::for_public_use_case(5, 7, NoteExecutionHint::Network).unwrap();
+        assert_eq!(
+            NetworkNote::new(note_with_tag(tag), NoteType::OffChain),
+            Err(NoteError::NoteTagRequiresPublicNote(tag))
+        );
+
+        let note = note_with_tag(NoteTag::for_account(target));
+        assert_eq!(
+            NetworkNote::new(note.clone(), NoteType::Public),
+            Err(NoteError::NotANetworkNote(note.id()))
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use miden_crypto::utils::{ByteReader, ByteWriter, Deserializable, Serializable};
+use vm_processor::DeserializationError;
+
+use super::{Note, NoteInclusionProof};
+use crate::utils::{
+    collections::Vec,
+    format,
+    string::{String, ToString},
+};
+
+// NOTE FILE
+// ================================================================================================
+
+/// A container of the data required to consume a note, which can be shared with the consumer of
+/// the note off-chain (e.g., saved to a file, or encoded into a link).
+///
+/// The details of a note (i.e., its script, inputs, assets, and serial number) are sufficient to
+/// consume the note for anyone who can satisfy the note script. Thus, for notes which can be
+/// consumed by any account (e.g., bearer notes), a note file acts as the secret granting the
+/// right to claim the assets of the note and must be shared only with the intended consumer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NoteFile {
+    /// The details of a note; the consumer must obtain the inclusion proof of the note (e.g., from
+    /// the node) once the note is included in a block.
+    NoteDetails(Note),
+    /// The details of a note together with the proof of its inclusion in a block.
+    NoteWithProof(Note, NoteInclusionProof),
+}
+
+impl NoteFile {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the note contained in this file.
+    pub fn note(&self) -> &Note {
+        match self {
+            Self::NoteDetails(note) | Self::NoteWithProof(note, _) => note,
+        }
+    }
+
+    /// Returns the inclusion proof of the note, if this file contains one.
+    pub fn inclusion_proof(&self) -> Option<&NoteInclusionProof> {
+        match self {
+            Self::NoteDetails(_) => None,
+            Self::NoteWithProof(_, proof) => Some(proof),
+        }
+    }
+
+    // ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the serialized note file encoded as a lowercase hex string.
+    pub fn to_hex(&self) -> String {
+        let mut hex = String::new();
+        for byte in self.to_bytes() {
+            hex.push(char::from_digit((byte >> 4) as u32, 16).expect("nibble is a hex digit"));
+            hex.push(char::from_digit((byte & 0xf) as u32, 16).expect("nibble is a hex digit"));
+        }
+        hex
+    }
+
+    /// Returns a note file decoded from a hex string produced by [NoteFile::to_hex()].
+    ///
+    /// # Errors
+    /// Returns an error if the string is not a valid hex encoding of a note file.
+    pub fn from_hex(hex: &str) -> Result<Self, DeserializationError> {
+        let invalid_hex = || DeserializationError::InvalidValue("invalid hex string".to_string());
+
+        let digits = hex.as_bytes();
+        if digits.len() % 2 != 0 {
+            return Err(invalid_hex());
+        }
+
+        let bytes = digits
+            .chunks(2)
+            .map(|pair| {
+                let hi = (pair[0] as char).to_digit(16).ok_or_else(invalid_hex)?;
+                let lo = (pair[1] as char).to_digit(16).ok_or_else(invalid_hex)?;
+                Ok(((hi << 4) | lo) as u8)
+            })
+            .collect::<Result<Vec<u8>, DeserializationError>>()?;
+
+        Self::read_from_bytes(&bytes)
+    }
+}
+
+impl From<Note> for NoteFile {
+    fn from(note: Note) -> Self {
+        Self::NoteDetails(note)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for NoteFile {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::NoteDetails(note) => {
+                target.write_u8(0);
+                note.write_into(target);
+            },
+            Self::NoteWithProof(note, proof) => {
+                target.write_u8(1);
+                note.write_into(target);
+                proof.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for NoteFile {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::NoteDetails(Note::read_from(source)?)),
+            1 => {
+                let note = Note::read_from(source)?;
+                let proof = NoteInclusionProof::read_from(source)?;
+                Ok(Self::NoteWithProof(note, proof))
+            },
+            tag => Err(DeserializationError::InvalidValue(format!("invalid note file tag {tag}"))),
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use core::fmt;
+
+use miden_crypto::utils::{ByteReader, ByteWriter, Deserializable, Serializable};
+use vm_core::StarkField;
+use vm_processor::DeserializationError;
+
+use super::{AccountId, Felt, NoteError, NoteType};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Prefix of tags targeting a single on-chain account whose notes are executed by the network.
+const NETWORK_ACCOUNT: u32 = 0;
+
+/// Prefix of tags of public notes with a use case which are executed by the network.
+const NETWORK_PUBLIC_USE_CASE: u32 = 0x4000_0000;
+
+/// Prefix of tags of public notes with a use case which are executed locally.
+const LOCAL_PUBLIC_USE_CASE: u32 = 0x8000_0000;
+
+/// Prefix of tags of notes which are executed locally (i.e., tags targeting an account, and tags
+/// with a use case which are not restricted to public notes).
+const LOCAL_ANY: u32 = 0xc000_0000;
+
+/// Mask of the two most significant bits of a tag which encode the kind of the tag.
+const PREFIX_MASK: u32 = 0xc000_0000;
+
+// NOTE EXECUTION HINT
+// ================================================================================================
+
+/// Specifies who is expected to consume a note.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoteExecutionHint {
+    /// The note is expected to be consumed by the network operator against an on-chain account.
+    Network,
+    /// The note is expected to be consumed by a user in a locally executed transaction.
+    Local,
+}
+
+// NOTE TAG
+// ================================================================================================
+
+/// A tag which helps recipients discover the notes addressed to them.
+///
+/// The tag is a 32-bit value whose two most significant bits encode the kind of the tag:
+///
+/// | Prefix | Execution | Payload                                   | Allowed note types |
+/// | ------ | --------- | ----------------------------------------- | ------------------ |
+/// | `0b00` | network   | 30 most significant bits of an account ID | public             |
+/// | `0b01` | network   | 14-bit use case ID, 16-bit payload        | public             |
+/// | `0b10` | local     | 14-bit use case ID, 16-bit payload        | public             |
+/// | `0b11` | local     | 14 most significant bits of an account ID | any                |
+/// |        |           | or 14-bit use case ID, 16-bit payload     |                    |
+///
+/// Tags targeting an account carry only the most significant bits of the account ID, such that
+/// the recipient can fetch the notes matching the tag without revealing its full account ID.
+///
+/// Tags should be built via the provided constructors rather than by packing the bits manually.
+/// The constructors check that the requested combination of parameters is valid, and
+/// [NoteTag::validate()] checks that the tag can be used with a given [NoteType].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NoteTag(u32);
+
+impl NoteTag {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The maximum value of a use case ID; use case IDs are encoded using 14 bits.
+    pub const MAX_USE_CASE_ID: u16 = (1 << 14) - 1;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a tag for a locally executed note targeting the specified account.
+    ///
+    /// The tag contains the 14 most significant bits of the account ID and can be used with notes
+    /// of any type.
+    pub fn for_account(account_id: AccountId) -> Self {
+        let id_bits = (u64::from(account_id) >> 50) as u32;
+        Self(LOCAL_ANY | (id_bits << 16))
+    }
+
+    /// Returns a tag for a note targeting the specified on-chain account which is executed by the
+    /// network.
+    ///
+    /// The tag contains the 30 most significant bits of the account ID. Notes with such a tag
+    /// must be public.
+    ///
+    /// # Errors
+    /// Returns an error if the specified account is not an on-chain account.
+    pub fn for_network_account(account_id: AccountId) -> Result<Self, NoteError> {
+        if !account_id.is_on_chain() {
+            return Err(NoteError::NetworkNoteTagForOffChainAccount(account_id));
+        }
+        let id_bits = (u64::from(account_id) >> 34) as u32;
+        Ok(Self(NETWORK_ACCOUNT | id_bits))
+    }
+
+    /// Returns a tag for a public note with the specified use case and payload.
+    ///
+    /// Depending on the execution hint, the note is expected to be consumed either by the network
+    /// or locally. Notes with such a tag must be public.
+    ///
+    /// # Errors
+    /// Returns an error if the use case ID is greater than [NoteTag::MAX_USE_CASE_ID].
+    pub fn for_public_use_case(
+        use_case_id: u16,
+        payload: u16,
+        execution: NoteExecutionHint,
+    ) -> Result<Self, NoteError> {
+        let prefix = match execution {
+            NoteExecutionHint::Network => NETWORK_PUBLIC_USE_CASE,
+            NoteExecutionHint::Local => LOCAL_PUBLIC_USE_CASE,
+        };
+        Self::with_use_case(prefix, use_case_id, payload)
+    }
+
+    /// Returns a tag for a locally executed note with the specified use case and payload.
+    ///
+    /// The tag can be used with notes of any type.
+    ///
+    /// # Errors
+    /// Returns an error if the use case ID is greater than [NoteTag::MAX_USE_CASE_ID].
+    pub fn for_local_execution(use_case_id: u16, payload: u16) -> Result<Self, NoteError> {
+        Self::with_use_case(LOCAL_ANY, use_case_id, payload)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the execution hint encoded in this tag.
+    pub fn execution_hint(&self) -> NoteExecutionHint {
+        match self.0 & PREFIX_MASK {
+            NETWORK_ACCOUNT | NETWORK_PUBLIC_USE_CASE => NoteExecutionHint::Network,
+            _ => NoteExecutionHint::Local,
+        }
+    }
+
+    /// Returns true if this tag targets a single on-chain account executed by the network.
+    pub fn is_network_account(&self) -> bool {
+        self.0 & PREFIX_MASK == NETWORK_ACCOUNT
+    }
+
+    /// Returns true if this tag targets the specified account executed by the network, i.e., if
+    /// this is a network account tag built from the ID of the specified account.
+    ///
+    /// Since the tag contains only the 30 most significant bits of the account ID, the tag may
+    /// target more than one account.
+    pub fn targets_network_account(&self, account_id: AccountId) -> bool {
+        self.is_network_account() && self.0 == (u64::from(account_id) >> 34) as u32
+    }
+
+    /// Returns true if notes with this tag must be public.
+    pub fn requires_public_note(&self) -> bool {
+        self.0 & PREFIX_MASK != LOCAL_ANY
+    }
+
+    /// Returns the value of this tag.
+    pub fn inner(&self) -> u32 {
+        self.0
+    }
+
+    /// Checks that this tag can be used by a note of the specified type.
+    ///
+    /// # Errors
+    /// Returns an error if this tag requires a public note, and the note type is not public.
+    pub fn validate(&self, note_type: NoteType) -> Result<Self, NoteError> {
+        if self.requires_public_note() && !note_type.is_public() {
+            return Err(NoteError::NoteTagRequiresPublicNote(*self));
+        }
+        Ok(*self)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a tag with the specified prefix, use case ID and payload.
+    fn with_use_case(prefix: u32, use_case_id: u16, payload: u16) -> Result<Self, NoteError> {
+        if use_case_id > Self::MAX_USE_CASE_ID {
+            return Err(NoteError::InvalidNoteTagUseCase(use_case_id));
+        }
+        Ok(Self(prefix | ((use_case_id as u32) << 16) | payload as u32))
+    }
+}
+
+impl fmt::Display for NoteTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// CONVERSIONS
+// ================================================================================================
+
+impl From<u32> for NoteTag {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<NoteTag> for u32 {
+    fn from(tag: NoteTag) -> Self {
+        tag.0
+    }
+}
+
+impl From<NoteTag> for Felt {
+    fn from(tag: NoteTag) -> Self {
+        Felt::from(tag.0)
+    }
+}
+
+impl TryFrom<Felt> for NoteTag {
+    type Error = NoteError;
+
+    fn try_from(value: Felt) -> Result<Self, Self::Error> {
+        let value = value.as_int();
+        u32::try_from(value).map(Self).map_err(|_| NoteError::InvalidNoteTag(value))
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for NoteTag {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.0);
+    }
+}
+
+impl Deserializable for NoteTag {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u32().map(Self)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{NoteExecutionHint, NoteTag, NoteType};
+    use crate::{
+        accounts::{
+            AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+            ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
+        },
+        NoteError,
+    };
+
+    #[test]
+    fn account_tags() {
+        let on_chain = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN)
+            .expect("valid account ID");
+        let off_chain = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN)
+            .expect("valid account ID");
+
+        let tag = NoteTag::for_account(off_chain);
+        assert_eq!(tag.execution_hint(), NoteExecutionHint::Local);
+        assert_eq!(tag.inner() >> 16 & 0x3fff, (u64::from(off_chain) >> 50) as u32);
+        assert!(tag.validate(NoteType::OffChain).is_ok());
+
+        let tag = NoteTag::for_network_account(on_chain).unwrap();
+        assert!(tag.is_network_account());
+        assert!(tag.targets_network_account(on_chain));
+        assert!(!tag.targets_network_account(off_chain));
+        assert_eq!(tag.execution_hint(), NoteExecutionHint::Network);
+        assert_eq!(
+            tag.validate(NoteType::OffChain),
+            Err(NoteError::NoteTagRequiresPublicNote(tag))
+        );
+        assert!(tag.validate(NoteType::Public).is_ok());
+
+        assert_eq!(
+            NoteTag::for_network_account(off_chain),
+            Err(NoteError::NetworkNoteTagForOffChainAccount(off_chain))
+        );
+    }
+
+    #[test]
+    fn use_case_tags() {
+        let tag = NoteTag::for_public_use_case(5, 7, NoteExecutionHint::Network).unwrap();
+        assert_eq!(tag.inner(), 0x4005_0007);
+        assert_eq!(tag.execution_hint(), NoteExecutionHint::Network);
+        assert!(!tag.is_network_account());
+
+        let tag = NoteTag::for_public_use_case(5, 7, NoteExecutionHint::Local).unwrap();
+        assert_eq!(tag.inner(), 0x8005_0007);
+        assert!(tag.validate(NoteType::OffChain).is_err());
+
+        let tag = NoteTag::for_local_execution(NoteTag::MAX_USE_CASE_ID, 7).unwrap();
+        assert_eq!(tag.inner(), 0xffff_0007);
+        assert!(tag.validate(NoteType::OffChain).is_ok());
+
+        assert_eq!(
+            NoteTag::for_local_execution(NoteTag::MAX_USE_CASE_ID + 1, 0),
+            Err(NoteError::InvalidNoteTagUseCase(NoteTag::MAX_USE_CASE_ID + 1))
+        );
+    }
+}
@@ -0,0 +1,22 @@
+// NOTE TYPE
+// ================================================================================================
+
+/// Specifies how the details of a note are made available once the note is created.
+///
+/// - [NoteType::OffChain] notes are stored on chain only as their ID and metadata; the details of
+///   the note must be communicated to the recipient off-chain.
+/// - [NoteType::Public] notes are stored on chain together with all of their details, such that
+///   anyone (including the network operator) can consume them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum NoteType {
+    OffChain,
+    Public,
+}
+
+impl NoteType {
+    /// Returns true if the details of notes of this type are stored on chain.
+    pub fn is_public(&self) -> bool {
+        matches!(self, Self::Public)
+    }
+}
@@ -1,8 +1,9 @@
 use assembly::ast::AstSerdeOptions;
 
 use super::{Assembler, AssemblyContext, CodeBlock, Digest, NoteError, ProgramAst};
-use crate::utils::serde::{
-    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+use crate::utils::{
+    serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    sync::Arc,
 };
 
 // CONSTANTS
@@ -18,10 +19,13 @@ const CODE_SERDE_OPTIONS: AstSerdeOptions = AstSerdeOptions::new(true);
 ///
 /// A note's script represents a program which must be executed for a note to be consumed. As such
 /// it defines the rules and side effects of consuming a given note.
+///
+/// The program AST is shared via an [Arc], and thus cloning a note script does not copy the
+/// underlying program.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NoteScript {
     hash: Digest,
-    code: ProgramAst,
+    code: Arc<ProgramAst>,
 }
 
 impl NoteScript {
@@ -37,7 +41,13 @@ impl NoteScript {
         let code_block = assembler
             .compile_in_context(&code, &mut AssemblyContext::for_program(Some(&code)))
             .map_err(NoteError::ScriptCompilationError)?;
-        Ok((Self { hash: code_block.hash(), code }, code_block))
+        Ok((
+            Self {
+                hash: code_block.hash(),
+                code: Arc::new(code),
+            },
+            code_block,
+        ))
     }
 
     /// Returns a new [NoteScript] instantiated from the provided components.
@@ -45,7 +55,7 @@ impl NoteScript {
     /// **Note**: this function assumes that the specified hash results from the compilation of the
     /// provided program, but this is not checked.
     pub fn from_parts(code: ProgramAst, hash: Digest) -> Self {
-        Self { code, hash }
+        Self { code: Arc::new(code), hash }
     }
 
     // PUBLIC ACCESSORS
@@ -0,0 +1,212 @@
+use super::{AccountDelta, AccountId, Digest, ExecutedTransaction, TransactionId};
+use crate::{utils::collections::Vec, AccountUpdateError};
+
+// ACCOUNT UPDATE
+// ================================================================================================
+
+/// The aggregated update of a single account by one or more transactions.
+///
+/// When several transactions executed against the same account are included into a batch, their
+/// effects on the account are combined into a single update which takes the account from the
+/// initial state of the first transaction to the final state of the last transaction. An account
+/// update consists of:
+/// - account_id: ID of the updated account.
+/// - initial_state_hash: the hash of the account before the first transaction was executed.
+/// - final_state_hash: the hash of the account after the last transaction was executed.
+/// - delta: the combined changes made to the account by all transactions.
+/// - transactions: the IDs of the transactions which updated the account, in execution order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountUpdate {
+    account_id: AccountId,
+    initial_state_hash: Digest,
+    final_state_hash: Digest,
+    delta: AccountDelta,
+    transactions: Vec<TransactionId>,
+}
+
+impl AccountUpdate {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [AccountUpdate] describing the effects of a single transaction.
+    pub fn new(
+        account_id: AccountId,
+        initial_state_hash: Digest,
+        final_state_hash: Digest,
+        delta: AccountDelta,
+        tx_id: TransactionId,
+    ) -> Self {
+        Self {
+            account_id,
+            initial_state_hash,
+            final_state_hash,
+            delta,
+            transactions: vec![tx_id],
+        }
+    }
+
+    /// Returns a new [AccountUpdate] aggregating the effects of the provided transactions on the
+    /// account they were executed against.
+    ///
+    /// The transactions must be provided in the order in which they were executed.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No transactions were provided.
+    /// - The transactions were not all executed against the same account.
+    /// - The transactions do not form a chain, i.e., the initial account state of any of the
+    ///   transactions is not the final account state of the previous transaction.
+    /// - The account deltas of the transactions cannot be merged.
+    pub fn from_transactions<'a, I>(transactions: I) -> Result<Self, AccountUpdateError>
+    where
+        I: IntoIterator<Item = &'a ExecutedTransaction>,
+    {
+        let mut transactions = transactions.into_iter().map(Self::from);
+        let mut update = transactions.next().ok_or(AccountUpdateError::NoTransactions)?;
+        for tx_update in transactions {
+            update.merge(tx_update)?;
+        }
+
+        Ok(update)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the updated account.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the hash of the account state before the update.
+    pub fn initial_state_hash(&self) -> Digest {
+        self.initial_state_hash
+    }
+
+    /// Returns the hash of the account state after the update.
+    pub fn final_state_hash(&self) -> Digest {
+        self.final_state_hash
+    }
+
+    /// Returns the combined changes made to the account by this update.
+    pub fn delta(&self) -> &AccountDelta {
+        &self.delta
+    }
+
+    /// Returns the IDs of the transactions aggregated in this update, in execution order.
+    pub fn transactions(&self) -> &[TransactionId] {
+        &self.transactions
+    }
+
+    // MERGING
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified update, which must directly follow this update, to this update.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The updates are for different accounts.
+    /// - The initial state of the other update is not the final state of this update.
+    /// - The account deltas of the updates cannot be merged.
+    pub fn merge(&mut self, other: Self) -> Result<(), AccountUpdateError> {
+        if self.account_id != other.account_id {
+            return Err(AccountUpdateError::AccountIdMismatch {
+                expected: self.account_id,
+                actual: other.account_id,
+            });
+        }
+
+        if self.final_state_hash != other.initial_state_hash {
+            return Err(AccountUpdateError::InconsistentStateTransition {
+                expected: self.final_state_hash,
+                actual: other.initial_state_hash,
+            });
+        }
+
+        self.delta = self
+            .delta
+            .clone()
+            .merge(other.delta)
+            .map_err(AccountUpdateError::DeltaMergeFailed)?;
+        self.final_state_hash = other.final_state_hash;
+        self.transactions.extend(other.transactions);
+
+        Ok(())
+    }
+}
+
+impl From<&ExecutedTransaction> for AccountUpdate {
+    fn from(tx: &ExecutedTransaction) -> Self {
+        Self::new(
+            tx.account_id(),
+            tx.initial_account().hash(),
+            tx.final_account().hash(),
+            tx.account_delta().clone(),
+            tx.id(),
+        )
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountUpdate, AccountUpdateError};
+    use crate::{
+        accounts::{
+            AccountDelta, AccountId, AccountStorageDelta, AccountVaultDelta,
+            ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        },
+        transaction::TransactionId,
+        Digest, Felt, ONE, ZERO,
+    };
+
+    fn digest(value: u64) -> Digest {
+        Digest::new([Felt::new(value), ZERO, ZERO, ZERO])
+    }
+
+    fn update(initial: u64, final_: u64, slot: u8, nonce: u64) -> AccountUpdate {
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let storage = AccountStorageDelta {
+            cleared_items: vec![],
+            updated_items: vec![(slot, [Felt::new(nonce), ONE, ONE, ONE])],
+            ..Default::default()
+        };
+        let delta =
+            AccountDelta::new(storage, AccountVaultDelta::default(), Some(Felt::new(nonce)))
+                .unwrap();
+        let tx_id = TransactionId::new(digest(initial), digest(final_), digest(0), digest(0));
+
+        AccountUpdate::new(account_id, digest(initial), digest(final_), delta, tx_id)
+    }
+
+    #[test]
+    fn chained_updates_are_merged() {
+        let mut merged = update(1, 2, 0, 1);
+        merged.merge(update(2, 3, 0, 2)).unwrap();
+        merged.merge(update(3, 4, 1, 3)).unwrap();
+
+        assert_eq!(merged.initial_state_hash(), digest(1));
+        assert_eq!(merged.final_state_hash(), digest(4));
+        assert_eq!(merged.transactions().len(), 3);
+        assert_eq!(merged.delta().nonce(), Some(Felt::new(3)));
+        assert_eq!(
+            merged.delta().storage().updated_items,
+            vec![(0, [Felt::new(2), ONE, ONE, ONE]), (1, [Felt::new(3), ONE, ONE, ONE])]
+        );
+    }
+
+    #[test]
+    fn broken_chain_is_rejected() {
+        let mut merged = update(1, 2, 0, 1);
+        assert_eq!(
+            merged.merge(update(3, 4, 0, 2)),
+            Err(AccountUpdateError::InconsistentStateTransition {
+                expected: digest(2),
+                actual: digest(3)
+            })
+        );
+    }
+}
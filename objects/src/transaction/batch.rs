@@ -0,0 +1,361 @@
+use super::{
+    inputs::build_input_notes_commitment, outputs::build_output_notes_commitment, Digest,
+    ProvenTransaction, TransactionId,
+};
+use crate::{
+    notes::{Note, NoteEnvelope, NoteId, Nullifier},
+    utils::collections::{BTreeMap, BTreeSet, Vec},
+    TransactionBatchError,
+};
+
+// TRANSACTION BATCH
+// ================================================================================================
+
+/// A set of proven transactions which are included into a block together.
+///
+/// Notes which are created by one transaction of the batch and consumed by another transaction of
+/// the same batch never need to be recorded on chain. Such notes are erased from the batch, i.e.,
+/// their nullifiers are removed from the input notes of the batch and their envelopes are removed
+/// from the output notes of the batch. Thus, the input and output notes of a batch are:
+/// - input_notes: the nullifiers of all notes consumed by the transactions of the batch, except
+///   for the erased notes.
+/// - output_notes: the envelopes of all notes created by the transactions of the batch, except for
+///   the erased notes.
+///
+/// A transaction may consume a note without providing a proof of its inclusion in the chain;
+/// such notes are called unauthenticated notes. An unauthenticated note must be created by another
+/// transaction which is executed before the consuming transaction, either in the same batch or in
+/// another batch of the same block. Thus, the transactions of a batch are ordered such that every
+/// transaction comes after the transactions creating the unauthenticated notes it consumes.
+/// Unauthenticated notes which are not created within the batch are left to be matched at the
+/// block level (see [ProposedBlock](crate::block::ProposedBlock)).
+///
+/// A batch expires together with the first of its transactions, i.e., the expiration block number
+/// of a batch is the minimum of the expiration block numbers of its transactions.
+///
+/// The number of the block into which a batch is included is not known when the batch is built.
+/// Thus, output notes of the batch which expire before that block are garbage-collected by the
+/// block (see [ProposedBlock::expired_notes()](crate::block::ProposedBlock::expired_notes)).
+///
+/// A proven transaction commits only to the nullifiers of the notes it consumes, which cannot be
+/// linked to the IDs of the notes created within the batch. Thus, the batch producer must provide
+/// the details of the unauthenticated notes (e.g., as received together with the transactions
+/// consuming them). Notes for which no details are provided are considered authenticated, and are
+/// never erased.
+#[derive(Debug, Clone)]
+pub struct TransactionBatch {
+    transactions: Vec<ProvenTransaction>,
+    input_notes: Vec<Nullifier>,
+    output_notes: Vec<NoteEnvelope>,
+    erased_notes: Vec<NoteId>,
+    unauthenticated_notes: BTreeMap<Nullifier, NoteId>,
+    expiration_block_num: Option<u32>,
+}
+
+impl TransactionBatch {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [TransactionBatch] built from the provided transactions.
+    ///
+    /// `unauthenticated_notes` contains the details of the notes consumed by the transactions
+    /// without a proof of inclusion in the chain. Such notes which are created within the batch
+    /// are erased from the batch-level input and output notes. The transactions are reordered
+    /// (preserving the provided order where possible) such that every unauthenticated note is
+    /// created before it is consumed.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The same note is consumed by more than one transaction of the batch.
+    /// - The same note is created by more than one transaction of the batch.
+    /// - Any of the unauthenticated notes is not consumed by the transactions of the batch.
+    /// - The transactions cannot be ordered, i.e., there is a cycle of transactions consuming
+    ///   unauthenticated notes created by each other.
+    pub fn new(
+        transactions: Vec<ProvenTransaction>,
+        unauthenticated_notes: &[Note],
+    ) -> Result<Self, TransactionBatchError> {
+        let consumers = transactions
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, tx)| tx.input_notes().iter().map(move |nullifier| (*nullifier, idx)))
+            .collect::<BTreeMap<_, _>>();
+        let producers = transactions
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, tx)| tx.output_notes().iter().map(move |note| (note.note_id(), idx)))
+            .collect::<BTreeMap<_, _>>();
+
+        // every unauthenticated note created within the batch makes its consumer depend on its
+        // producer
+        let mut dependencies = Vec::new();
+        for note in unauthenticated_notes {
+            let consumer = *consumers
+                .get(&note.nullifier())
+                .ok_or(TransactionBatchError::UnconsumedUnauthenticatedNote(note.id()))?;
+            if let Some(producer) = producers.get(&note.id()) {
+                dependencies.push((*producer, consumer));
+            }
+        }
+
+        let order = sort_by_dependencies(transactions.len(), &dependencies)
+            .map_err(|idx| TransactionBatchError::CyclicNoteDependency(transactions[idx].id()))?;
+        let mut transactions = transactions.into_iter().map(Some).collect::<Vec<_>>();
+        let transactions = order
+            .into_iter()
+            .map(|idx| transactions[idx].take().expect("transaction ordered more than once"))
+            .collect::<Vec<_>>();
+
+        let nullifiers = transactions
+            .iter()
+            .flat_map(|tx| tx.input_notes().iter().copied())
+            .collect::<Vec<_>>();
+        let envelopes = transactions
+            .iter()
+            .flat_map(|tx| tx.output_notes().iter().copied())
+            .collect::<Vec<_>>();
+        let note_links = unauthenticated_notes
+            .iter()
+            .map(|note| (note.nullifier(), note.id()))
+            .collect::<BTreeMap<_, _>>();
+
+        let (input_notes, output_notes, erased_notes) =
+            erase_notes(nullifiers, envelopes, &note_links)?;
+        let unauthenticated_notes = note_links
+            .into_iter()
+            .filter(|(_, note_id)| !producers.contains_key(note_id))
+            .collect();
+        let expiration_block_num =
+            transactions.iter().filter_map(|tx| tx.expiration_block_num()).min();
+
+        Ok(Self {
+            transactions,
+            input_notes,
+            output_notes,
+            erased_notes,
+            unauthenticated_notes,
+            expiration_block_num,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the transactions of this batch.
+    pub fn transactions(&self) -> &[ProvenTransaction] {
+        &self.transactions
+    }
+
+    /// Returns an iterator over the IDs of the transactions of this batch.
+    pub fn transaction_ids(&self) -> impl Iterator<Item = TransactionId> + '_ {
+        self.transactions.iter().map(|tx| tx.id())
+    }
+
+    /// Returns the nullifiers of the notes consumed by this batch, excluding the erased notes.
+    pub fn input_notes(&self) -> &[Nullifier] {
+        &self.input_notes
+    }
+
+    /// Returns the envelopes of the notes created by this batch, excluding the erased notes.
+    pub fn output_notes(&self) -> &[NoteEnvelope] {
+        &self.output_notes
+    }
+
+    /// Returns the IDs of the notes which were both created and consumed within this batch.
+    pub fn erased_notes(&self) -> &[NoteId] {
+        &self.erased_notes
+    }
+
+    /// Returns the unauthenticated notes consumed by this batch which are not created within this
+    /// batch, keyed by their nullifiers.
+    ///
+    /// These notes must be created by another batch of the block into which this batch is
+    /// included.
+    pub fn unauthenticated_notes(&self) -> &BTreeMap<Nullifier, NoteId> {
+        &self.unauthenticated_notes
+    }
+
+    /// Returns the number of the last block into which this batch can be included, or None if
+    /// none of the transactions of this batch expires.
+    pub fn expiration_block_num(&self) -> Option<u32> {
+        self.expiration_block_num
+    }
+
+    /// Returns true if this batch cannot be included into the block with the specified number.
+    pub fn is_expired(&self, block_num: u32) -> bool {
+        self.expiration_block_num.map_or(false, |expiration| block_num > expiration)
+    }
+
+    /// Returns a commitment to the nullifiers of the notes consumed by this batch.
+    ///
+    /// The commitment is computed in the same way as the input notes commitment of a
+    /// transaction.
+    pub fn nullifiers_commitment(&self) -> Digest {
+        build_input_notes_commitment(&self.input_notes)
+    }
+
+    /// Returns a commitment to the notes created by this batch.
+    ///
+    /// The commitment is computed in the same way as the output notes commitment of a
+    /// transaction.
+    pub fn output_notes_commitment(&self) -> Digest {
+        build_output_notes_commitment(&self.output_notes)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Removes the notes which are both consumed and created from the provided nullifiers and
+/// envelopes, and returns the remaining nullifiers, the remaining envelopes, and the IDs of the
+/// removed notes.
+///
+/// `note_links` maps the nullifiers of notes to their IDs; notes which are not in the map are
+/// never removed.
+fn erase_notes(
+    nullifiers: Vec<Nullifier>,
+    envelopes: Vec<NoteEnvelope>,
+    note_links: &BTreeMap<Nullifier, NoteId>,
+) -> Result<(Vec<Nullifier>, Vec<NoteEnvelope>, Vec<NoteId>), TransactionBatchError> {
+    let mut consumed = BTreeSet::new();
+    for nullifier in nullifiers.iter() {
+        if !consumed.insert(*nullifier) {
+            return Err(TransactionBatchError::DuplicateInputNote(nullifier.inner()));
+        }
+    }
+
+    let mut created = BTreeSet::new();
+    for envelope in envelopes.iter() {
+        if !created.insert(envelope.note_id()) {
+            return Err(TransactionBatchError::DuplicateOutputNote(envelope.note_id()));
+        }
+    }
+
+    let erased = nullifiers
+        .iter()
+        .filter_map(|nullifier| note_links.get(nullifier).map(|note_id| (*nullifier, *note_id)))
+        .filter(|(_, note_id)| created.contains(note_id))
+        .collect::<BTreeMap<_, _>>();
+    let erased_ids = erased.values().copied().collect::<BTreeSet<_>>();
+
+    let input_notes = nullifiers
+        .into_iter()
+        .filter(|nullifier| !erased.contains_key(nullifier))
+        .collect();
+    let output_notes = envelopes
+        .into_iter()
+        .filter(|envelope| !erased_ids.contains(&envelope.note_id()))
+        .collect();
+
+    Ok((input_notes, output_notes, erased_ids.into_iter().collect()))
+}
+
+/// Returns the order in which the specified number of items must be processed such that every
+/// item is processed after all items it depends on.
+///
+/// `dependencies` contains `(dependency, dependent)` pairs of item indexes. Among the items whose
+/// dependencies were already processed, the item with the lowest index is processed first; thus,
+/// the original order of the items is preserved where possible.
+///
+/// # Errors
+/// Returns the index of an item which is part of a dependency cycle, if there is such a cycle.
+pub(crate) fn sort_by_dependencies(
+    num_items: usize,
+    dependencies: &[(usize, usize)],
+) -> Result<Vec<usize>, usize> {
+    let mut num_dependencies = vec![0_usize; num_items];
+    let mut dependents = vec![Vec::new(); num_items];
+    for &(dependency, dependent) in dependencies {
+        num_dependencies[dependent] += 1;
+        dependents[dependency].push(dependent);
+    }
+
+    let mut ready = (0..num_items)
+        .filter(|idx| num_dependencies[*idx] == 0)
+        .collect::<BTreeSet<_>>();
+    let mut order = Vec::with_capacity(num_items);
+    while let Some(idx) = ready.pop_first() {
+        order.push(idx);
+        for &dependent in dependents[idx].iter() {
+            num_dependencies[dependent] -= 1;
+            if num_dependencies[dependent] == 0 {
+                ready.insert(dependent);
+            }
+        }
+    }
+
+    match (0..num_items).find(|idx| num_dependencies[*idx] > 0) {
+        Some(idx) => Err(idx),
+        None => Ok(order),
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{erase_notes, sort_by_dependencies, BTreeMap, NoteEnvelope, NoteId, Nullifier};
+    use crate::{
+        accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN},
+        notes::{NoteMetadata, NoteTag},
+        Digest, Felt, TransactionBatchError, ONE, ZERO,
+    };
+
+    fn note(seed: u64) -> (Nullifier, NoteEnvelope) {
+        let sender =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let metadata = NoteMetadata::new(sender, NoteTag::default(), ONE);
+        let digest = |x: u64| Digest::new([Felt::new(x), ZERO, ZERO, ZERO]);
+
+        let nullifier = Nullifier::from(digest(seed));
+        let note_id = NoteId::new(digest(seed), digest(seed + 1));
+        (nullifier, NoteEnvelope::new(note_id, metadata))
+    }
+
+    #[test]
+    fn notes_created_and_consumed_within_batch_are_erased() {
+        let (nullifier_1, envelope_1) = note(1);
+        let (nullifier_2, envelope_2) = note(10);
+        let (nullifier_3, envelope_3) = note(20);
+
+        // note 1 is created and consumed within the batch, note 2 is only consumed and note 3 is
+        // created and consumed but its details are not known to the batch producer
+        let note_links = BTreeMap::from([
+            (nullifier_1, envelope_1.note_id()),
+            (nullifier_2, envelope_2.note_id()),
+        ]);
+        let (input_notes, output_notes, erased) = erase_notes(
+            vec![nullifier_1, nullifier_2, nullifier_3],
+            vec![envelope_1, envelope_3],
+            &note_links,
+        )
+        .unwrap();
+
+        assert_eq!(input_notes, vec![nullifier_2, nullifier_3]);
+        assert_eq!(output_notes, vec![envelope_3]);
+        assert_eq!(erased, vec![envelope_1.note_id()]);
+    }
+
+    #[test]
+    fn duplicate_notes_are_rejected() {
+        let (nullifier, envelope) = note(1);
+
+        let result = erase_notes(vec![nullifier, nullifier], vec![], &BTreeMap::new());
+        assert_eq!(result, Err(TransactionBatchError::DuplicateInputNote(nullifier.inner())));
+
+        let result = erase_notes(vec![], vec![envelope, envelope], &BTreeMap::new());
+        assert_eq!(result, Err(TransactionBatchError::DuplicateOutputNote(envelope.note_id())));
+    }
+
+    #[test]
+    fn items_are_sorted_by_dependencies() {
+        // no dependencies preserve the original order
+        assert_eq!(sort_by_dependencies(3, &[]), Ok(vec![0, 1, 2]));
+
+        // item 0 depends on item 2, which depends on item 1
+        assert_eq!(sort_by_dependencies(3, &[(2, 0), (1, 2)]), Ok(vec![1, 2, 0]));
+
+        // items 1 and 2 depend on each other
+        assert_eq!(sort_by_dependencies(3, &[(1, 2), (2, 1)]), Err(1));
+    }
+}
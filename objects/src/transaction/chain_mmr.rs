@@ -1,8 +1,12 @@
 use super::Digest;
 use crate::{
-    crypto::merkle::{InnerNodeInfo, MmrPeaks, PartialMmr},
-    utils::collections::{BTreeMap, Vec},
-    ChainMmrError,
+    crypto::merkle::{InnerNodeInfo, MerklePath, MmrPeaks, PartialMmr},
+    utils::{
+        collections::{BTreeMap, Vec},
+        format,
+        serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    },
+    BlockHeader, ChainMmrError, Hasher,
 };
 
 // CHAIN MMR
@@ -69,3 +73,492 @@ impl ChainMmr {
         self.mmr.inner_nodes(self.blocks.iter())
     }
 }
+
+// CHAIN MMR BUILDER
+// ================================================================================================
+
+/// Maintains a partial view of the chain MMR which can be incrementally updated with new blocks.
+///
+/// The builder keeps the peaks of the chain MMR and the authentication paths of a set of tracked
+/// blocks (e.g., the blocks in which the notes of interest to a client were created). Whenever a
+/// new block is added, the peaks and the authentication paths of all tracked blocks are updated;
+/// thus, the builder can be kept up to date without access to the full MMR.
+///
+/// The chain root committed to by the header of block `N` is the root of the MMR of blocks
+/// `0..N`. Thus, the MMR maintained by the builder contains all known blocks except for the latest
+/// one, and the [ChainMmr] produced by the builder is the one to be used together with the header
+/// of the latest block (see [ChainMmrBuilder::latest_block_header()]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainMmrBuilder {
+    /// Number of blocks in the MMR.
+    forest: usize,
+    /// Peaks of the MMR ordered from the peak of the largest tree to the peak of the smallest one.
+    peaks: Vec<Digest>,
+    /// A map of `block_num |-> (block_hash, path)` for all tracked blocks in the MMR, where the
+    /// path authenticates the block against the peak of its tree.
+    tracked: BTreeMap<u32, (Digest, Vec<Digest>)>,
+    /// The latest known block and a flag indicating whether the block should be tracked.
+    latest: Option<(BlockHeader, bool)>,
+}
+
+impl ChainMmrBuilder {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [ChainMmrBuilder] for a chain without any blocks; the first block added to
+    /// the builder must be the genesis block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new [ChainMmrBuilder] for the chain ending with the specified block, where
+    /// `peaks` are the peaks of the MMR of all blocks preceding the latest block.
+    ///
+    /// This allows a client to start following the chain from a recent block without processing
+    /// all previous blocks. Neither the latest block nor any of the previous blocks are tracked;
+    /// previous blocks can be tracked via [ChainMmrBuilder::track_block()].
+    ///
+    /// # Errors
+    /// Returns an error if the chain root of the latest block is not the root of the MMR described
+    /// by the provided peaks.
+    pub fn from_peaks(peaks: MmrPeaks, latest_block: BlockHeader) -> Result<Self, ChainMmrError> {
+        let mut builder = Self {
+            forest: peaks.num_leaves(),
+            peaks: peaks.peaks().to_vec(),
+            tracked: BTreeMap::new(),
+            latest: None,
+        };
+        builder.check_next_block(&latest_block)?;
+        builder.latest = Some((latest_block, false));
+
+        Ok(builder)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of blocks in the MMR maintained by this builder, i.e., the number of
+    /// the latest known block.
+    pub fn chain_length(&self) -> usize {
+        self.forest
+    }
+
+    /// Returns the peaks of the MMR maintained by this builder.
+    pub fn peaks(&self) -> MmrPeaks {
+        MmrPeaks::new(self.forest, self.peaks.clone()).expect("peaks are consistent with forest")
+    }
+
+    /// Returns the header of the latest known block, if any.
+    pub fn latest_block_header(&self) -> Option<&BlockHeader> {
+        self.latest.as_ref().map(|(header, _)| header)
+    }
+
+    /// Returns an iterator over the numbers of the blocks tracked by this builder.
+    ///
+    /// The latest known block is not included, as it is not a part of the MMR yet.
+    pub fn tracked_blocks(&self) -> impl Iterator<Item = u32> + '_ {
+        self.tracked.keys().copied()
+    }
+
+    /// Returns the hash of the specified block and the path authenticating the block against the
+    /// peak of its tree in the MMR, or None if the block is not tracked by this builder.
+    pub fn open(&self, block_num: u32) -> Option<(Digest, MerklePath)> {
+        self.tracked
+            .get(&block_num)
+            .map(|(block_hash, path)| (*block_hash, MerklePath::new(path.clone())))
+    }
+
+    // MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the specified block to the chain.
+    ///
+    /// If `track` is true, the authentication path of the block will be maintained once the block
+    /// becomes a part of the MMR (i.e., once the next block is added).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The block does not directly follow the latest known block.
+    /// - The chain root of the block is not the root of the MMR of all previous blocks.
+    pub fn add_block(&mut self, header: BlockHeader, track: bool) -> Result<(), ChainMmrError> {
+        let mut builder = self.clone();
+        if let Some((latest, track_latest)) = builder.latest.take() {
+            builder.append(latest.hash(), track_latest);
+        }
+        builder.check_next_block(&header)?;
+        builder.latest = Some((header, track));
+
+        *self = builder;
+        Ok(())
+    }
+
+    /// Starts tracking the specified block, authenticated by the provided path against the peak
+    /// of its tree in the MMR (e.g., a path obtained from a node).
+    ///
+    /// # Errors
+    /// Returns an error if the block is not a part of the MMR, or if the provided path does not
+    /// authenticate the block.
+    pub fn track_block(
+        &mut self,
+        block_num: u32,
+        block_hash: Digest,
+        path: MerklePath,
+    ) -> Result<(), ChainMmrError> {
+        let (peak_idx, mut index, height) = self
+            .locate(block_num as usize)
+            .ok_or(ChainMmrError::block_num_too_big(self.forest, block_num as usize))?;
+        if path.len() != height {
+            return Err(ChainMmrError::InvalidBlockPath(block_num));
+        }
+
+        let mut node = block_hash;
+        for sibling in path.iter() {
+            node = if index & 1 == 0 {
+                Hasher::merge(&[node, *sibling])
+            } else {
+                Hasher::merge(&[*sibling, node])
+            };
+            index >>= 1;
+        }
+        if node != self.peaks[peak_idx] {
+            return Err(ChainMmrError::InvalidBlockPath(block_num));
+        }
+
+        self.tracked.insert(block_num, (block_hash, path.to_vec()));
+        Ok(())
+    }
+
+    /// Stops tracking the specified block; returns true if the block was tracked.
+    pub fn untrack_block(&mut self, block_num: u32) -> bool {
+        self.tracked.remove(&block_num).is_some()
+    }
+
+    // BUILDERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a [ChainMmr] containing authentication paths for all blocks tracked by this
+    /// builder.
+    pub fn build(&self) -> ChainMmr {
+        self.build_for_blocks(self.tracked_blocks())
+            .expect("all tracked blocks are a part of the MMR")
+    }
+
+    /// Returns a [ChainMmr] containing authentication paths only for the specified blocks (e.g.,
+    /// the blocks in which the input notes of a transaction were created).
+    ///
+    /// # Errors
+    /// Returns an error if any of the specified blocks is not tracked by this builder.
+    pub fn build_for_blocks<I>(&self, blocks: I) -> Result<ChainMmr, ChainMmrError>
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        let mut mmr = PartialMmr::from_peaks(self.peaks());
+        let mut tracked = BTreeMap::new();
+        for block_num in blocks {
+            let (block_hash, path) =
+                self.open(block_num).ok_or(ChainMmrError::BlockNotTracked(block_num))?;
+            mmr.add(block_num as usize, block_hash, &path)
+                .expect("path authenticates the block against the MMR peaks");
+            tracked.insert(block_num, block_hash);
+        }
+
+        ChainMmr::new(mmr, tracked)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an error if the specified block cannot be appended to the chain described by this
+    /// builder.
+    fn check_next_block(&self, header: &BlockHeader) -> Result<(), ChainMmrError> {
+        if header.block_num() as usize != self.forest {
+            return Err(ChainMmrError::UnexpectedBlockNum {
+                expected: self.forest as u32,
+                actual: header.block_num(),
+            });
+        }
+
+        let chain_root = self.peaks().hash_peaks();
+        if header.chain_root() != chain_root {
+            return Err(ChainMmrError::ChainRootMismatch {
+                block_num: header.block_num(),
+                expected: chain_root,
+                actual: header.chain_root(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Appends the specified block hash to the MMR, updating the peaks and the authentication
+    /// paths of the tracked blocks.
+    fn append(&mut self, block_hash: Digest, track: bool) {
+        let pos = self.forest;
+        if track {
+            self.tracked.insert(pos as u32, (block_hash, Vec::new()));
+        }
+
+        // merge the new leaf with the peaks of the trees of the same height, as long as the
+        // forest contains such trees; the leaves of the merged trees get the peak of the other
+        // tree appended to their paths
+        let mut node = block_hash;
+        let mut height = 0;
+        while self.forest & (1 << height) != 0 {
+            let left = self.peaks.pop().expect("forest contains a tree of this height");
+            let right_start = pos + 1 - (1 << height);
+            let left_start = right_start - (1 << height);
+            for (block_num, (_, path)) in self.tracked.range_mut(left_start as u32..=pos as u32) {
+                if (*block_num as usize) < right_start {
+                    path.push(node);
+                } else {
+                    path.push(left);
+                }
+            }
+
+            node = Hasher::merge(&[left, node]);
+            height += 1;
+        }
+
+        self.peaks.push(node);
+        self.forest += 1;
+    }
+
+    /// Returns the index of the peak of the tree containing the specified leaf, the position of
+    /// the leaf within the tree, and the height of the tree.
+    fn locate(&self, pos: usize) -> Option<(usize, usize, usize)> {
+        let mut tree_start = 0;
+        let mut peak_idx = 0;
+        for height in (0..usize::BITS as usize).rev() {
+            let tree_size = 1 << height;
+            if self.forest & tree_size != 0 {
+                if pos < tree_start + tree_size {
+                    return Some((peak_idx, pos - tree_start, height));
+                }
+                tree_start += tree_size;
+                peak_idx += 1;
+            }
+        }
+        None
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for ChainMmr {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        let peaks = self.mmr.peaks();
+        target.write_u64(peaks.num_leaves() as u64);
+        target.write_u64(peaks.peaks().len() as u64);
+        for peak in peaks.peaks() {
+            peak.write_into(target);
+        }
+
+        // the partial MMR is serialized as the set of authentication paths for the tracked blocks
+        target.write_u64(self.blocks.len() as u64);
+        for (block_num, block_hash) in self.blocks.iter() {
+            let proof = self
+                .mmr
+                .open(*block_num)
+                .expect("block position is within the chain")
+                .expect("block is tracked by the partial MMR");
+            target.write_u32(*block_num as u32);
+            block_hash.write_into(target);
+            proof.merkle_path.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for ChainMmr {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_leaves = source.read_u64()? as usize;
+        let num_peaks = source.read_u64()?;
+        let peaks = (0..num_peaks)
+            .map(|_| Digest::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+        let peaks = MmrPeaks::new(num_leaves, peaks)
+            .map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))?;
+
+        let mut mmr = PartialMmr::from_peaks(peaks);
+        let mut blocks = BTreeMap::new();
+        let num_blocks = source.read_u64()?;
+        for _ in 0..num_blocks {
+            let block_num = source.read_u32()?;
+            let block_hash = Digest::read_from(source)?;
+            let path = MerklePath::read_from(source)?;
+            mmr.add(block_num as usize, block_hash, &path)
+                .map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))?;
+            blocks.insert(block_num, block_hash);
+        }
+
+        Self::new(mmr, blocks).map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))
+    }
+}
+
+impl Serializable for ChainMmrBuilder {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.forest as u64);
+        target.write_u64(self.peaks.len() as u64);
+        for peak in self.peaks.iter() {
+            peak.write_into(target);
+        }
+
+        target.write_u64(self.tracked.len() as u64);
+        for (block_num, (block_hash, path)) in self.tracked.iter() {
+            target.write_u32(*block_num);
+            block_hash.write_into(target);
+            MerklePath::new(path.clone()).write_into(target);
+        }
+
+        match &self.latest {
+            Some((header, track)) => {
+                target.write_bool(true);
+                header.write_into(target);
+                target.write_bool(*track);
+            },
+            None => target.write_bool(false),
+        }
+    }
+}
+
+impl Deserializable for ChainMmrBuilder {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let forest = source.read_u64()? as usize;
+        let num_peaks = source.read_u64()?;
+        let peaks = (0..num_peaks)
+            .map(|_| Digest::read_from(source))
+            .collect::<Result<Vec<_>, _>>()?;
+        let peaks = MmrPeaks::new(forest, peaks)
+            .map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))?;
+
+        let mut builder = Self {
+            forest,
+            peaks: peaks.peaks().to_vec(),
+            tracked: BTreeMap::new(),
+            latest: None,
+        };
+
+        let num_tracked = source.read_u64()?;
+        for _ in 0..num_tracked {
+            let block_num = source.read_u32()?;
+            let block_hash = Digest::read_from(source)?;
+            let path = MerklePath::read_from(source)?;
+            builder
+                .track_block(block_num, block_hash, path)
+                .map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))?;
+        }
+
+        if source.read_bool()? {
+            let header = BlockHeader::read_from(source)?;
+            let track = source.read_bool()?;
+            builder
+                .check_next_block(&header)
+                .map_err(|err| DeserializationError::InvalidValue(format!("{err:?}")))?;
+            builder.latest = Some((header, track));
+        }
+
+        Ok(builder)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ChainMmrBuilder, Deserializable, Serializable};
+    use crate::{
+        crypto::merkle::{MerklePath, Mmr},
+        BlockHeader, ChainMmrError, Digest, Felt, ONE, ZERO,
+    };
+
+    fn block_header(block_num: u32, chain_root: Digest) -> BlockHeader {
+        let digest = |x: u64| Digest::new([Felt::new(x), ZERO, ZERO, ZERO]);
+        let x = block_num as u64;
+        BlockHeader::new(
+            digest(x),
+            block_num,
+            chain_root,
+            digest(x + 1),
+            digest(x + 2),
+            digest(x + 3),
+            digest(x + 4),
+            digest(x + 5),
+            ONE,
+            Felt::new(x),
+        )
+    }
+
+    #[test]
+    fn chain_mmr_builder_tracks_blocks() {
+        let tracked = [0, 3, 6, 7, 12];
+        let mut builder = ChainMmrBuilder::new();
+        let mut mmr = Mmr::default();
+        for block_num in 0..20 {
+            let header = block_header(block_num, mmr.peaks(mmr.forest()).unwrap().hash_peaks());
+            builder.add_block(header, tracked.contains(&block_num)).unwrap();
+            mmr.add(header.hash());
+        }
+
+        // the latest block is not a part of the MMR
+        assert_eq!(builder.chain_length(), 19);
+        assert_eq!(builder.peaks(), mmr.peaks(19).unwrap());
+        assert_eq!(builder.tracked_blocks().collect::<Vec<_>>(), tracked);
+
+        for block_num in tracked {
+            let proof = mmr.open(block_num as usize, 19).unwrap();
+            let (block_hash, path) = builder.open(block_num).unwrap();
+            assert_eq!(block_hash, mmr.get(block_num as usize).unwrap());
+            assert_eq!(path, proof.merkle_path);
+        }
+        assert!(builder.open(1).is_none());
+
+        let chain_mmr = builder.build_for_blocks([3, 7]).unwrap();
+        assert_eq!(chain_mmr.chain_length(), 19);
+        assert_eq!(chain_mmr.peaks(), mmr.peaks(19).unwrap());
+        assert_eq!(builder.build_for_blocks([1]), Err(ChainMmrError::BlockNotTracked(1)));
+
+        // blocks can be tracked after they were added using a path from the full MMR
+        let proof = mmr.open(1, 19).unwrap();
+        let block_hash = mmr.get(1).unwrap();
+        assert_eq!(
+            builder.track_block(1, block_hash, MerklePath::new(vec![block_hash; 4])),
+            Err(ChainMmrError::InvalidBlockPath(1))
+        );
+        builder.track_block(1, block_hash, proof.merkle_path).unwrap();
+        assert_eq!(builder.build().chain_length(), 19);
+
+        // the partial state survives serialization
+        let bytes = builder.to_bytes();
+        assert_eq!(ChainMmrBuilder::read_from_bytes(&bytes).unwrap(), builder);
+    }
+
+    #[test]
+    fn chain_mmr_builder_rejects_inconsistent_blocks() {
+        let mut builder = ChainMmrBuilder::new();
+        let genesis = block_header(0, builder.peaks().hash_peaks());
+        builder.add_block(genesis, false).unwrap();
+
+        assert_eq!(
+            builder.add_block(block_header(2, Digest::default()), false),
+            Err(ChainMmrError::UnexpectedBlockNum { expected: 1, actual: 2 })
+        );
+        assert!(matches!(
+            builder.add_block(block_header(1, Digest::default()), false),
+            Err(ChainMmrError::ChainRootMismatch { block_num: 1, .. })
+        ));
+        assert_eq!(builder.latest_block_header(), Some(&genesis));
+
+        // a builder started from the peaks of the chain follows the chain from there on
+        let mut mmr = Mmr::default();
+        mmr.add(genesis.hash());
+        let header = block_header(1, mmr.peaks(1).unwrap().hash_peaks());
+        let mut from_peaks = ChainMmrBuilder::from_peaks(mmr.peaks(1).unwrap(), header).unwrap();
+        builder.add_block(header, false).unwrap();
+
+        mmr.add(header.hash());
+        let next = block_header(2, mmr.peaks(2).unwrap().hash_peaks());
+        from_peaks.add_block(next, false).unwrap();
+        builder.add_block(next, false).unwrap();
+        assert_eq!(from_peaks, builder);
+    }
+}
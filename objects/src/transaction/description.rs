@@ -0,0 +1,351 @@
+use core::fmt;
+
+use super::{
+    AccountId, Digest, ExecutedTransaction, Felt, NoteEnvelope, Nullifier, OutputNote,
+    ProvenTransaction, TransactionId,
+};
+use crate::{
+    assets::Asset,
+    notes::NoteId,
+    utils::{
+        collections::Vec,
+        string::{String, ToString},
+    },
+    StarkField,
+};
+
+// TRANSACTION DESCRIPTION
+// ================================================================================================
+
+/// A human-readable description of the effects of a transaction.
+///
+/// The description contains the information a wallet would show to a user before confirming a
+/// transaction: the account the transaction was executed against, the change of the account
+/// nonce, the assets which entered and left the account vault, and the notes consumed and created
+/// by the transaction.
+///
+/// A description built from an [ExecutedTransaction] contains all of the above. A description
+/// built from a [ProvenTransaction] contains only the data available in the proven transaction:
+/// the consumed notes are identified by their nullifiers, and nonce and vault changes are unknown.
+///
+/// Consumed notes can be labeled with the kind of their script (e.g., "P2ID") via
+/// [TransactionDescription::with_script_kinds()].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionDescription {
+    tx_id: TransactionId,
+    account_id: AccountId,
+    initial_account_hash: Digest,
+    final_account_hash: Digest,
+    nonce_change: Option<(Felt, Felt)>,
+    assets_received: Vec<Asset>,
+    assets_sent: Vec<Asset>,
+    consumed_notes: Vec<NoteDescription>,
+    created_notes: Vec<NoteDescription>,
+}
+
+impl TransactionDescription {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the described transaction.
+    pub fn tx_id(&self) -> TransactionId {
+        self.tx_id
+    }
+
+    /// Returns the ID of the account the transaction was executed against.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the initial and the final nonce of the account if the nonce was changed by the
+    /// transaction and the change is known.
+    pub fn nonce_change(&self) -> Option<(Felt, Felt)> {
+        self.nonce_change
+    }
+
+    /// Returns the assets added to the account vault by the transaction.
+    pub fn assets_received(&self) -> &[Asset] {
+        &self.assets_received
+    }
+
+    /// Returns the assets removed from the account vault by the transaction.
+    pub fn assets_sent(&self) -> &[Asset] {
+        &self.assets_sent
+    }
+
+    /// Returns descriptions of the notes consumed by the transaction.
+    pub fn consumed_notes(&self) -> &[NoteDescription] {
+        &self.consumed_notes
+    }
+
+    /// Returns descriptions of the notes created by the transaction.
+    pub fn created_notes(&self) -> &[NoteDescription] {
+        &self.created_notes
+    }
+
+    // MODIFIERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Labels consumed notes with the kinds of their scripts.
+    ///
+    /// The provided function is invoked with the root of every known note script, and should
+    /// return a name of the script kind if the script is recognized.
+    pub fn with_script_kinds<F, S>(mut self, script_kind: F) -> Self
+    where
+        F: Fn(&Digest) -> Option<S>,
+        S: AsRef<str>,
+    {
+        for note in self.consumed_notes.iter_mut() {
+            if let Some(root) = &note.script_root {
+                note.script_kind = script_kind(root).map(|kind| kind.as_ref().to_string());
+            }
+        }
+        self
+    }
+}
+
+impl From<&ExecutedTransaction> for TransactionDescription {
+    fn from(tx: &ExecutedTransaction) -> Self {
+        let initial_nonce = tx.initial_account().nonce();
+        let nonce_change = tx.account_delta().nonce().map(|nonce| (initial_nonce, nonce));
+        let vault_delta = tx.account_delta().vault();
+
+        let consumed_notes = tx
+            .input_notes()
+            .iter()
+            .map(|input_note| {
+                let note = input_note.note();
+                NoteDescription {
+                    id: Some(note.id()),
+                    nullifier: Some(note.nullifier()),
+                    script_root: Some(note.script().hash()),
+                    script_kind: None,
+                    assets: note.assets().iter().copied().collect(),
+                }
+            })
+            .collect();
+
+        let created_notes = tx.output_notes().iter().map(NoteDescription::from).collect();
+
+        Self {
+            tx_id: tx.id(),
+            account_id: tx.account_id(),
+            initial_account_hash: tx.initial_account().hash(),
+            final_account_hash: tx.final_account().hash(),
+            nonce_change,
+            assets_received: vault_delta.added().collect(),
+            assets_sent: vault_delta.removed().collect(),
+            consumed_notes,
+            created_notes,
+        }
+    }
+}
+
+impl From<&ProvenTransaction> for TransactionDescription {
+    fn from(tx: &ProvenTransaction) -> Self {
+        let consumed_notes = tx.input_notes().iter().map(NoteDescription::from).collect();
+        let created_notes = tx.output_notes().iter().map(NoteDescription::from).collect();
+
+        Self {
+            tx_id: tx.id(),
+            account_id: tx.account_id(),
+            initial_account_hash: tx.initial_account_hash(),
+            final_account_hash: tx.final_account_hash(),
+            nonce_change: None,
+            assets_received: Vec::new(),
+            assets_sent: Vec::new(),
+            consumed_notes,
+            created_notes,
+        }
+    }
+}
+
+impl fmt::Display for TransactionDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Transaction ")?;
+        write_digest(f, &self.tx_id.inner())?;
+        writeln!(f, " against account {}", self.account_id)?;
+
+        write!(f, "  account hash: ")?;
+        write_digest(f, &self.initial_account_hash)?;
+        write!(f, " -> ")?;
+        write_digest(f, &self.final_account_hash)?;
+        writeln!(f)?;
+
+        if let Some((initial_nonce, final_nonce)) = self.nonce_change {
+            writeln!(f, "  nonce: {} -> {}", initial_nonce.as_int(), final_nonce.as_int())?;
+        }
+
+        if !self.assets_received.is_empty() {
+            writeln!(f, "  assets received:")?;
+            for asset in self.assets_received.iter() {
+                write!(f, "    - ")?;
+                write_asset(f, asset)?;
+                writeln!(f)?;
+            }
+        }
+
+        if !self.assets_sent.is_empty() {
+            writeln!(f, "  assets sent:")?;
+            for asset in self.assets_sent.iter() {
+                write!(f, "    - ")?;
+                write_asset(f, asset)?;
+                writeln!(f)?;
+            }
+        }
+
+        writeln!(f, "  consumed notes ({}):", self.consumed_notes.len())?;
+        for note in self.consumed_notes.iter() {
+            writeln!(f, "    - {note}")?;
+        }
+
+        writeln!(f, "  created notes ({}):", self.created_notes.len())?;
+        for note in self.created_notes.iter() {
+            writeln!(f, "    - {note}")?;
+        }
+
+        Ok(())
+    }
+}
+
+// NOTE DESCRIPTION
+// ================================================================================================
+
+/// A human-readable description of a note consumed or created by a transaction.
+///
+/// Depending on the source of the description, a note is identified either by its ID, by its
+/// nullifier, or by both. The script root and assets are present only if the note details are
+/// known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoteDescription {
+    id: Option<NoteId>,
+    nullifier: Option<Nullifier>,
+    script_root: Option<Digest>,
+    script_kind: Option<String>,
+    assets: Vec<Asset>,
+}
+
+impl NoteDescription {
+    /// Returns the ID of the note, if known.
+    pub fn id(&self) -> Option<NoteId> {
+        self.id
+    }
+
+    /// Returns the nullifier of the note, if known.
+    pub fn nullifier(&self) -> Option<Nullifier> {
+        self.nullifier
+    }
+
+    /// Returns the root of the note script, if known.
+    pub fn script_root(&self) -> Option<Digest> {
+        self.script_root
+    }
+
+    /// Returns the kind of the note script, if the script was recognized.
+    pub fn script_kind(&self) -> Option<&str> {
+        self.script_kind.as_deref()
+    }
+
+    /// Returns the assets carried by the note; empty if the assets are not known.
+    pub fn assets(&self) -> &[Asset] {
+        &self.assets
+    }
+}
+
+impl From<&OutputNote> for NoteDescription {
+    fn from(note: &OutputNote) -> Self {
+        Self {
+            id: Some(note.id()),
+            nullifier: None,
+            script_root: None,
+            script_kind: None,
+            assets: note.assets().iter().copied().collect(),
+        }
+    }
+}
+
+impl From<&NoteEnvelope> for NoteDescription {
+    fn from(note: &NoteEnvelope) -> Self {
+        Self {
+            id: Some(note.note_id()),
+            nullifier: None,
+            script_root: None,
+            script_kind: None,
+            assets: Vec::new(),
+        }
+    }
+}
+
+impl From<&Nullifier> for NoteDescription {
+    fn from(nullifier: &Nullifier) -> Self {
+        Self {
+            id: None,
+            nullifier: Some(*nullifier),
+            script_root: None,
+            script_kind: None,
+            assets: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for NoteDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.id, self.nullifier) {
+            (Some(id), _) => {
+                write!(f, "note ")?;
+                write_digest(f, &id.inner())?;
+            },
+            (None, Some(nullifier)) => {
+                write!(f, "note with nullifier ")?;
+                write_digest(f, &nullifier.inner())?;
+            },
+            (None, None) => write!(f, "unknown note")?,
+        }
+
+        match (&self.script_kind, &self.script_root) {
+            (Some(kind), _) => write!(f, " [{kind}]")?,
+            (None, Some(root)) => {
+                write!(f, " [script ")?;
+                write_digest(f, root)?;
+                write!(f, "]")?;
+            },
+            (None, None) => (),
+        }
+
+        if !self.assets.is_empty() {
+            write!(f, " carrying ")?;
+            for (i, asset) in self.assets.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write_asset(f, asset)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the provided digest as a hex string.
+fn write_digest(f: &mut fmt::Formatter<'_>, digest: &Digest) -> fmt::Result {
+    write!(f, "0x")?;
+    for byte in digest.as_bytes() {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+/// Writes a short description of the provided asset.
+fn write_asset(f: &mut fmt::Formatter<'_>, asset: &Asset) -> fmt::Result {
+    match asset {
+        Asset::Fungible(asset) => {
+            write!(f, "{} of fungible asset from faucet {}", asset.amount(), asset.faucet_id())
+        },
+        Asset::NonFungible(asset) => {
+            write!(f, "non-fungible asset from faucet {}", asset.faucet_id())
+        },
+    }
+}
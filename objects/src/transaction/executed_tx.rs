@@ -1,9 +1,21 @@
-use core::cell::OnceCell;
+use core::fmt;
 
 use super::{
-    Account, AccountDelta, AccountId, AccountStub, AdviceInputs, BlockHeader, InputNotes,
-    OutputNotes, Program, TransactionId, TransactionInputs, TransactionOutputs, TransactionScript,
-    TransactionWitness,
+    tx_witness::{read_advice_inputs, write_advice_inputs},
+    Account, AccountDelta, AccountHeader, AccountId, AdviceInputs, BlockHeader, Digest, InputNotes,
+    OutputNotes, TransactionDescription, TransactionFee, TransactionId, TransactionInputs,
+    TransactionMeasurements, TransactionOutputs, TransactionScript, TransactionSummary,
+    TransactionWitness, Word,
+};
+use crate::{
+    notes::{Note, NoteId},
+    utils::{
+        collections::{BTreeMap, Vec},
+        format,
+        serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+        string::ToString,
+        sync::OnceLock,
+    },
 };
 
 // EXECUTED TRANSACTION
@@ -19,15 +31,26 @@ use super::{
 ///   stateless manner. This includes all public transaction inputs, but also all nondeterministic
 ///   inputs that the host provided to Miden VM while executing the transaction (i.e., advice
 ///   witness).
+///
+/// In addition, an executed transaction may contain the full details (i.e., script, inputs, and
+/// serial number) of the output notes whose recipients were known to the transaction host. Senders
+/// need these details to deliver the notes to their recipients off-chain.
+///
+/// Like [TransactionWitness], an executed transaction does not contain the transaction program,
+/// but only the hash of the transaction kernel it was executed with.
 #[derive(Debug, Clone)]
 pub struct ExecutedTransaction {
-    id: OnceCell<TransactionId>,
-    program: Program,
+    id: OnceLock<TransactionId>,
+    kernel_hash: Digest,
     tx_inputs: TransactionInputs,
     tx_outputs: TransactionOutputs,
     account_delta: AccountDelta,
+    account_details: Option<Account>,
     tx_script: Option<TransactionScript>,
+    tx_script_args: Word,
     advice_witness: AdviceInputs,
+    measurements: TransactionMeasurements,
+    output_note_details: BTreeMap<NoteId, Note>,
 }
 
 impl ExecutedTransaction {
@@ -36,30 +59,68 @@ impl ExecutedTransaction {
 
     /// Returns a new [ExecutedTransaction] instantiated from the provided data.
     ///
+    /// `kernel_hash` is the hash of the main code block of the transaction kernel the transaction
+    /// was executed with, i.e., the hash of the transaction program. `account_details` is expected
+    /// to contain the full final state of the account if the transaction created a new on-chain
+    /// account, and None otherwise. `tx_script_args` are the arguments the transaction script was
+    /// executed with, and `measurements` describe the resources consumed by executing the
+    /// transaction program.
+    ///
     /// # Panics
-    /// Panics if input and output account IDs are not the same.
+    /// Panics if:
+    /// - Input and output account IDs are not the same.
+    /// - Account details are provided, but their hash is not the final account hash.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        program: Program,
+        kernel_hash: Digest,
         tx_inputs: TransactionInputs,
         tx_outputs: TransactionOutputs,
         account_delta: AccountDelta,
+        account_details: Option<Account>,
         tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
         advice_witness: AdviceInputs,
+        measurements: TransactionMeasurements,
     ) -> Self {
         // make sure account IDs are consistent across transaction inputs and outputs
         assert_eq!(tx_inputs.account().id(), tx_outputs.account.id());
 
+        // make sure account details describe the final state of the account
+        if let Some(account) = account_details.as_ref() {
+            assert_eq!(account.hash(), tx_outputs.account.hash());
+        }
+
         Self {
-            id: OnceCell::new(),
-            program,
+            id: OnceLock::new(),
+            kernel_hash,
             tx_inputs,
             tx_outputs,
             account_delta,
+            account_details,
             tx_script,
+            tx_script_args,
             advice_witness,
+            measurements,
+            output_note_details: BTreeMap::new(),
         }
     }
 
+    /// Returns this transaction with the specified full output notes attached to it.
+    ///
+    /// # Panics
+    /// Panics if any of the provided notes is not an output note of this transaction.
+    pub fn with_output_note_details<I: IntoIterator<Item = Note>>(mut self, notes: I) -> Self {
+        for note in notes {
+            assert!(
+                self.is_output_note(note.id()),
+                "note {} is not an output note",
+                note.id().inner()
+            );
+            self.output_note_details.insert(note.id(), note);
+        }
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -68,9 +129,10 @@ impl ExecutedTransaction {
         *self.id.get_or_init(|| self.into())
     }
 
-    /// Returns a reference the program defining this transaction.
-    pub fn program(&self) -> &Program {
-        &self.program
+    /// Returns the hash of the main code block of the transaction kernel this transaction was
+    /// executed with, i.e., the hash of the transaction program.
+    pub fn kernel_hash(&self) -> Digest {
+        self.kernel_hash
     }
 
     /// Returns the ID of the account against which this transaction was executed.
@@ -84,7 +146,7 @@ impl ExecutedTransaction {
     }
 
     /// Returns description of the account after the transaction was executed.
-    pub fn final_account(&self) -> &AccountStub {
+    pub fn final_account(&self) -> &AccountHeader {
         &self.tx_outputs.account
     }
 
@@ -98,11 +160,46 @@ impl ExecutedTransaction {
         &self.tx_outputs.output_notes
     }
 
+    /// Returns the full details of the output note with the specified ID, or None if the note was
+    /// not created by this transaction or its details are not known.
+    ///
+    /// The details of an output note are known if its recipient was built via the
+    /// `build_recipient_hash` kernel procedure, and the script and the inputs of the note were
+    /// available to the transaction host at that time.
+    pub fn output_note_details(&self, note_id: NoteId) -> Option<&Note> {
+        self.output_note_details.get(&note_id)
+    }
+
+    /// Returns an iterator over the output notes of this transaction whose full details are known,
+    /// in the order in which the notes were created.
+    pub fn full_output_notes(&self) -> impl Iterator<Item = &Note> {
+        self.output_notes()
+            .iter()
+            .filter_map(|note| self.output_note_details.get(&note.id()))
+    }
+
+    /// Returns the number of the last block into which this transaction can be included, or None
+    /// if the transaction does not expire.
+    pub fn expiration_block_num(&self) -> Option<u32> {
+        self.tx_outputs.expiration_block_num
+    }
+
+    /// Returns the fee charged by the transaction kernel, or None if the transaction was executed
+    /// without fee parameters.
+    pub fn fee(&self) -> Option<TransactionFee> {
+        self.tx_outputs.fee
+    }
+
     /// Returns a reference to the transaction script.
     pub fn tx_script(&self) -> Option<&TransactionScript> {
         self.tx_script.as_ref()
     }
 
+    /// Returns the arguments the transaction script was executed with.
+    pub fn tx_script_args(&self) -> Word {
+        self.tx_script_args
+    }
+
     /// Returns the block header for the block against which the transaction was executed.
     pub fn block_header(&self) -> &BlockHeader {
         self.tx_inputs.block_header()
@@ -113,6 +210,15 @@ impl ExecutedTransaction {
         &self.account_delta
     }
 
+    /// Returns the full final state of the account if this transaction created a new on-chain
+    /// account, or None otherwise.
+    ///
+    /// Account details allow the operator to register new on-chain accounts without receiving
+    /// any account data out-of-band.
+    pub fn account_details(&self) -> Option<&Account> {
+        self.account_details.as_ref()
+    }
+
     /// Returns a reference to the inputs for this transaction.
     pub fn tx_inputs(&self) -> &TransactionInputs {
         &self.tx_inputs
@@ -124,15 +230,39 @@ impl ExecutedTransaction {
         &self.advice_witness
     }
 
+    /// Returns the measurements of the resources consumed by executing this transaction.
+    pub fn measurements(&self) -> &TransactionMeasurements {
+        &self.measurements
+    }
+
+    /// Returns a human-readable description of the effects of this transaction.
+    pub fn description(&self) -> TransactionDescription {
+        self.into()
+    }
+
+    /// Returns the [TransactionSummary] committing to the effects of this transaction.
+    ///
+    /// The summary matches the summary of the corresponding
+    /// [ProvenTransaction](super::ProvenTransaction).
+    pub fn summary(&self) -> TransactionSummary {
+        self.into()
+    }
+
+    /// Returns true if a note with the specified ID was created by this transaction.
+    fn is_output_note(&self, note_id: NoteId) -> bool {
+        self.output_notes().iter().any(|note| note.id() == note_id)
+    }
+
     // CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
     /// Returns individual components of this transaction.
     pub fn into_parts(self) -> (AccountDelta, TransactionOutputs, TransactionWitness) {
         let tx_witness = TransactionWitness::new(
-            self.program,
+            self.kernel_hash,
             self.tx_inputs,
             self.tx_script,
+            self.tx_script_args,
             self.advice_witness,
         );
 
@@ -140,9 +270,92 @@ impl ExecutedTransaction {
     }
 }
 
+impl fmt::Display for ExecutedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
 impl From<ExecutedTransaction> for TransactionWitness {
     fn from(tx: ExecutedTransaction) -> Self {
         let (_, _, tx_witness) = tx.into_parts();
         tx_witness
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for ExecutedTransaction {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.kernel_hash.write_into(target);
+        self.tx_inputs.write_into(target);
+        self.tx_outputs.write_into(target);
+        self.account_delta.write_into(target);
+        self.account_details.write_into(target);
+        self.tx_script.write_into(target);
+        self.tx_script_args.write_into(target);
+        write_advice_inputs(&self.advice_witness, target);
+        target.write_u64(self.measurements.num_cycles as u64);
+
+        // the number of output notes is bounded by MAX_OUTPUT_NOTES_PER_TRANSACTION
+        target.write_u16(self.output_note_details.len() as u16);
+        for note in self.output_note_details.values() {
+            note.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for ExecutedTransaction {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let kernel_hash = Digest::read_from(source)?;
+        let tx_inputs = TransactionInputs::read_from(source)?;
+        let tx_outputs = TransactionOutputs::read_from(source)?;
+        let account_delta = AccountDelta::read_from(source)?;
+        let account_details = Option::<Account>::read_from(source)?;
+        let tx_script = Option::<TransactionScript>::read_from(source)?;
+        let tx_script_args = Word::read_from(source)?;
+        let advice_witness = read_advice_inputs(source)?;
+        let measurements = TransactionMeasurements { num_cycles: source.read_u64()? as usize };
+        let num_note_details = source.read_u16()?;
+        let mut output_note_details = Vec::with_capacity(num_note_details as usize);
+        for _ in 0..num_note_details {
+            output_note_details.push(Note::read_from(source)?);
+        }
+
+        if tx_inputs.account().id() != tx_outputs.account.id() {
+            return Err(DeserializationError::InvalidValue(
+                "initial and final account IDs differ".to_string(),
+            ));
+        }
+        if let Some(account) = account_details.as_ref() {
+            if account.hash() != tx_outputs.account.hash() {
+                return Err(DeserializationError::InvalidValue(
+                    "account details do not match the final account state".to_string(),
+                ));
+            }
+        }
+
+        for note in output_note_details.iter() {
+            if !tx_outputs.output_notes.iter().any(|output| output.id() == note.id()) {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "note {} is not an output note of the transaction",
+                    note.id().inner()
+                )));
+            }
+        }
+
+        Ok(Self::new(
+            kernel_hash,
+            tx_inputs,
+            tx_outputs,
+            account_delta,
+            account_details,
+            tx_script,
+            tx_script_args,
+            advice_witness,
+            measurements,
+        )
+        .with_output_note_details(output_note_details))
+    }
+}
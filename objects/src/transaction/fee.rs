@@ -0,0 +1,308 @@
+use super::{AccountId, ExecutedTransaction};
+use crate::{
+    assets::{Asset, FungibleAsset},
+    notes::{NoteAssets, NoteId},
+    utils::serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Digest,
+};
+
+// TRANSACTION MEASUREMENTS
+// ================================================================================================
+
+/// Measurements of the resources consumed by executing a transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionMeasurements {
+    /// The number of VM cycles consumed by the transaction program.
+    pub num_cycles: usize,
+}
+
+// FEE PARAMETERS
+// ================================================================================================
+
+/// Parameters from which the fee required for including a transaction into a block is computed.
+///
+/// The required fee of a transaction is computed as:
+///
+/// `verification_cost + num_cycles * base_fee_per_cycle + num_notes * base_fee_per_note`
+///
+/// where `num_notes` is the total number of notes consumed and created by the transaction. The
+/// verification cost is charged for every transaction, as every transaction proof must be
+/// verified regardless of its complexity.
+///
+/// Fees are denominated in abstract fee units, unless a fee faucet is specified, in which case
+/// fees are denominated in the fungible asset issued by the fee faucet. An operator may charge
+/// additional priority fees (see
+/// [ProvenTransaction::priority_fee()](super::ProvenTransaction::priority_fee)) to order
+/// transactions competing for the same block.
+///
+/// When fee parameters with a fee faucet are provided as a part of the
+/// [TransactionInputs](super::TransactionInputs), the transaction kernel charges the fee during the
+/// epilogue of the transaction by moving it from the account vault into a fee note. The fee note
+/// is an output note of the transaction which contains only the fee asset, has the tag 0, and is
+/// addressed to the fee recipient. In this case, `num_cycles` is the number of cycles executed
+/// before the fee is charged, the fee note is counted towards `num_notes`, and the paid fee is
+/// reported as a [TransactionFee]. If the computed fee is zero, no fee is charged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeParameters {
+    /// Fee charged per VM cycle consumed by the transaction.
+    pub base_fee_per_cycle: u64,
+    /// Fee charged per note consumed or created by the transaction.
+    pub base_fee_per_note: u64,
+    /// Fixed fee charged per transaction for the verification of its proof.
+    pub verification_cost: u64,
+    /// The fungible faucet issuing the asset in which fees are paid, if fees are charged by the
+    /// transaction kernel.
+    pub fee_faucet_id: Option<AccountId>,
+    /// The recipient of the fee note into which fees charged by the transaction kernel are paid.
+    pub fee_recipient: Digest,
+}
+
+impl FeeParameters {
+    /// The maximum base fee per cycle which can be charged by the transaction kernel.
+    ///
+    /// Together with the bounds on the remaining parameters, this guarantees that the fee computed
+    /// by the kernel never exceeds [FungibleAsset::MAX_AMOUNT].
+    pub const MAX_KERNEL_FEE_PER_CYCLE: u64 = (1 << 30) - 1;
+
+    /// The maximum base fee per note and verification cost which can be charged by the transaction
+    /// kernel.
+    pub const MAX_KERNEL_FEE: u64 = u32::MAX as u64;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns new [FeeParameters] instantiated from the provided values.
+    ///
+    /// The returned parameters do not specify a fee faucet, and thus fees are not charged by the
+    /// transaction kernel.
+    pub fn new(base_fee_per_cycle: u64, base_fee_per_note: u64, verification_cost: u64) -> Self {
+        Self {
+            base_fee_per_cycle,
+            base_fee_per_note,
+            verification_cost,
+            fee_faucet_id: None,
+            fee_recipient: Digest::default(),
+        }
+    }
+
+    /// Returns these parameters with the fee faucet set to the specified faucet, and the fee note
+    /// addressed to the specified recipient.
+    pub fn with_fee_faucet(mut self, fee_faucet_id: AccountId, fee_recipient: Digest) -> Self {
+        self.fee_faucet_id = Some(fee_faucet_id);
+        self.fee_recipient = fee_recipient;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if fees defined by these parameters can be charged by the transaction kernel.
+    ///
+    /// This is the case if the fee faucet is a fungible faucet, the base fee per cycle does not
+    /// exceed [Self::MAX_KERNEL_FEE_PER_CYCLE], and the base fee per note and the verification
+    /// cost do not exceed [Self::MAX_KERNEL_FEE].
+    pub fn is_kernel_chargeable(&self) -> bool {
+        self.fee_faucet_id.map_or(false, |faucet_id| faucet_id.is_fungible_faucet())
+            && self.base_fee_per_cycle <= Self::MAX_KERNEL_FEE_PER_CYCLE
+            && self.base_fee_per_note <= Self::MAX_KERNEL_FEE
+            && self.verification_cost <= Self::MAX_KERNEL_FEE
+    }
+
+    // FEE ESTIMATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the fee required for a transaction which consumed the specified number of cycles
+    /// and consumed and created the specified total number of notes.
+    ///
+    /// The computation saturates at [u64::MAX].
+    pub fn required_fee(&self, num_cycles: usize, num_notes: usize) -> u64 {
+        let cycles_fee = self.base_fee_per_cycle.saturating_mul(num_cycles as u64);
+        let notes_fee = self.base_fee_per_note.saturating_mul(num_notes as u64);
+        self.verification_cost.saturating_add(cycles_fee).saturating_add(notes_fee)
+    }
+
+    /// Returns the fee required for including the specified executed transaction into a block.
+    pub fn estimate_fee(&self, transaction: &ExecutedTransaction) -> u64 {
+        let num_notes =
+            transaction.input_notes().num_notes() + transaction.output_notes().num_notes();
+        self.required_fee(transaction.measurements().num_cycles, num_notes)
+    }
+
+    /// Returns true if the specified fee paid by a transaction which consumed and created the
+    /// specified total number of notes covers the fee required by these parameters.
+    ///
+    /// The fee must be paid in the asset of the fee faucet of these parameters, into the fee note
+    /// expected by these parameters (see [Self::fee_note_id()]). This does not check that the fee
+    /// note is an output note of the transaction.
+    pub fn is_fee_sufficient(&self, fee: &TransactionFee, num_notes: usize) -> bool {
+        self.fee_faucet_id == Some(fee.asset().faucet_id())
+            && fee.asset().amount() >= self.required_fee(fee.num_cycles() as usize, num_notes)
+            && fee.note_id() == self.fee_note_id(fee.asset())
+    }
+
+    /// Returns the ID of the fee note into which the specified fee asset is paid when charged
+    /// according to these parameters.
+    ///
+    /// The fee note contains only the fee asset and is addressed to the fee recipient of these
+    /// parameters, thus its ID is fully determined by the two.
+    pub fn fee_note_id(&self, asset: FungibleAsset) -> NoteId {
+        let assets = NoteAssets::new(&[Asset::Fungible(asset)])
+            .expect("a single fungible asset is valid note assets");
+        NoteId::new(self.fee_recipient, assets.commitment())
+    }
+}
+
+impl Serializable for FeeParameters {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.base_fee_per_cycle);
+        target.write_u64(self.base_fee_per_note);
+        target.write_u64(self.verification_cost);
+        self.fee_faucet_id.write_into(target);
+        self.fee_recipient.write_into(target);
+    }
+}
+
+impl Deserializable for FeeParameters {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let base_fee_per_cycle = source.read_u64()?;
+        let base_fee_per_note = source.read_u64()?;
+        let verification_cost = source.read_u64()?;
+        let fee_faucet_id = <Option<AccountId>>::read_from(source)?;
+        let fee_recipient = Digest::read_from(source)?;
+        Ok(Self {
+            base_fee_per_cycle,
+            base_fee_per_note,
+            verification_cost,
+            fee_faucet_id,
+            fee_recipient,
+        })
+    }
+}
+
+// TRANSACTION FEE
+// ================================================================================================
+
+/// The fee charged by the transaction kernel for executing a transaction.
+///
+/// The fee is removed from the account vault during the epilogue of the transaction, and is
+/// committed to by the proof of the transaction together with the number of cycles it was
+/// computed for. This allows an operator to check that the paid fee covers the fee required by its
+/// own [FeeParameters] via [FeeParameters::is_fee_sufficient()].
+///
+/// The fee is paid into the fee note, which is the last output note of the transaction. The ID of
+/// the fee note is not committed to by the proof directly, but the output notes of the transaction
+/// are; thus the fee note must be checked to be among the output notes of the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionFee {
+    asset: FungibleAsset,
+    num_cycles: u32,
+    note_id: NoteId,
+}
+
+impl TransactionFee {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [TransactionFee] instantiated from the paid asset, the number of cycles for
+    /// which the fee was charged, and the ID of the note into which the fee was paid.
+    pub fn new(asset: FungibleAsset, num_cycles: u32, note_id: NoteId) -> Self {
+        Self { asset, num_cycles, note_id }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the asset removed from the account vault to pay the fee.
+    pub fn asset(&self) -> FungibleAsset {
+        self.asset
+    }
+
+    /// Returns the number of cycles executed by the transaction before the fee was charged.
+    pub fn num_cycles(&self) -> u32 {
+        self.num_cycles
+    }
+
+    /// Returns the ID of the note into which the fee was paid.
+    pub fn note_id(&self) -> NoteId {
+        self.note_id
+    }
+}
+
+impl Serializable for TransactionFee {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.asset.write_into(target);
+        target.write_u32(self.num_cycles);
+        self.note_id.write_into(target);
+    }
+}
+
+impl Deserializable for TransactionFee {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let asset = FungibleAsset::read_from(source)?;
+        let num_cycles = source.read_u32()?;
+        let note_id = NoteId::read_from(source)?;
+        Ok(Self { asset, num_cycles, note_id })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{FeeParameters, TransactionFee};
+    use crate::{
+        accounts::{
+            AccountId, ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+            ACCOUNT_ID_NON_FUNGIBLE_FAUCET_OFF_CHAIN,
+        },
+        assets::FungibleAsset,
+        Digest, ONE, ZERO,
+    };
+
+    #[test]
+    fn required_fee() {
+        let params = FeeParameters::new(2, 100, 1000);
+        assert_eq!(params.required_fee(0, 0), 1000);
+        assert_eq!(params.required_fee(500, 3), 1000 + 2 * 500 + 100 * 3);
+
+        // the fee saturates instead of overflowing
+        let params = FeeParameters::new(u64::MAX, 1, 1);
+        assert_eq!(params.required_fee(2, 1), u64::MAX);
+    }
+
+    #[test]
+    fn fee_sufficiency() {
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let recipient = Digest::new([ONE, ZERO, ONE, ZERO]);
+        let params = FeeParameters::new(2, 100, 1000).with_fee_faucet(faucet_id, recipient);
+        assert!(params.is_kernel_chargeable());
+
+        // 1000 + 2 * 500 + 100 * 2 = 2200
+        let asset = FungibleAsset::new(faucet_id, 2200).unwrap();
+        let fee = TransactionFee::new(asset, 500, params.fee_note_id(asset));
+        assert!(params.is_fee_sufficient(&fee, 2));
+        assert!(!params.is_fee_sufficient(&fee, 3));
+
+        // the fee note is addressed to the fee recipient and contains only the fee asset
+        let other_params = params.with_fee_faucet(faucet_id, Digest::default());
+        assert!(!other_params.is_fee_sufficient(&fee, 2));
+        let fee = TransactionFee::new(asset, 500, other_params.fee_note_id(asset));
+        assert!(other_params.is_fee_sufficient(&fee, 2));
+        assert!(!params.is_fee_sufficient(&fee, 2));
+
+        let more = FungibleAsset::new(faucet_id, 2201).unwrap();
+        let fee = TransactionFee::new(asset, 500, params.fee_note_id(more));
+        assert!(!params.is_fee_sufficient(&fee, 2));
+
+        // fees paid in other assets, or without a fee faucet, are never sufficient
+        assert!(!FeeParameters::new(0, 0, 0).is_fee_sufficient(&fee, 0));
+
+        let non_fungible_faucet_id =
+            AccountId::try_from(ACCOUNT_ID_NON_FUNGIBLE_FAUCET_OFF_CHAIN).unwrap();
+        assert!(!params
+            .with_fee_faucet(non_fungible_faucet_id, Digest::default())
+            .is_kernel_chargeable());
+        assert!(!FeeParameters::new(1 << 30, 0, 0)
+            .with_fee_faucet(faucet_id, Digest::default())
+            .is_kernel_chargeable());
+    }
+}
@@ -0,0 +1,178 @@
+use super::{AccountHeader, AccountId, BlockHeader, Digest};
+use crate::{
+    accounts::{Account, AccountStorage},
+    block::AccountWitness,
+    utils::{
+        format,
+        serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    },
+    TransactionInputError,
+};
+
+// FOREIGN ACCOUNT INPUTS
+// ================================================================================================
+
+/// Contains the data required to read the state of a foreign account (i.e., an account other than
+/// the one the transaction is executed against) from within a transaction.
+///
+/// The foreign account inputs consist of:
+/// - header: the header of the foreign account as of the reference block of the transaction.
+/// - storage: the storage of the foreign account.
+/// - witness: an opening of the foreign account in the account database of the reference block,
+///   attesting to the hash of the foreign account state.
+///
+/// Foreign accounts are read-only: a transaction can read items from the storage of a foreign
+/// account, but it cannot modify its state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignAccountInputs {
+    header: AccountHeader,
+    storage: AccountStorage,
+    witness: AccountWitness,
+}
+
+impl ForeignAccountInputs {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns new [ForeignAccountInputs] instantiated from the provided parameters.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The storage commitment of the header is not the root of the provided storage.
+    /// - The witness is not an opening of the account described by the header.
+    pub fn new(
+        header: AccountHeader,
+        storage: AccountStorage,
+        witness: AccountWitness,
+    ) -> Result<Self, TransactionInputError> {
+        if header.storage_commitment() != storage.root()
+            || witness.account_id() != header.id()
+            || witness.initial_state_hash() != header.hash()
+        {
+            return Err(TransactionInputError::InconsistentForeignAccountInputs(header.id()));
+        }
+
+        Ok(Self { header, storage, witness })
+    }
+
+    /// Returns new [ForeignAccountInputs] for the provided account opened by the specified
+    /// witness.
+    ///
+    /// # Errors
+    /// Returns an error if the witness is not an opening of the provided account.
+    pub fn from_account(
+        account: &Account,
+        witness: AccountWitness,
+    ) -> Result<Self, TransactionInputError> {
+        Self::new(account.into(), account.storage().clone(), witness)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the ID of the foreign account.
+    pub fn account_id(&self) -> AccountId {
+        self.header.id()
+    }
+
+    /// Returns the hash of the foreign account state.
+    pub fn account_hash(&self) -> Digest {
+        self.header.hash()
+    }
+
+    /// Returns the header of the foreign account.
+    pub fn header(&self) -> &AccountHeader {
+        &self.header
+    }
+
+    /// Returns the storage of the foreign account.
+    pub fn storage(&self) -> &AccountStorage {
+        &self.storage
+    }
+
+    /// Returns the opening of the foreign account in the account database.
+    pub fn witness(&self) -> &AccountWitness {
+        &self.witness
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the state of the foreign account described by these inputs is the state
+    /// recorded in the account database of the specified block.
+    pub fn verify(&self, block_header: &BlockHeader) -> bool {
+        self.witness.verify(block_header.account_root())
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for ForeignAccountInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.header.write_into(target);
+        self.storage.write_into(target);
+        self.witness.write_into(target);
+    }
+}
+
+impl Deserializable for ForeignAccountInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let header = AccountHeader::read_from(source)?;
+        let storage = AccountStorage::read_from(source)?;
+        let witness = AccountWitness::read_from(source)?;
+
+        Self::new(header, storage, witness)
+            .map_err(|err| DeserializationError::InvalidValue(format!("{err}")))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ForeignAccountInputs;
+    use crate::{
+        accounts::{
+            Account, AccountCode, AccountId, AccountStorage, StorageSlotType,
+            ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        },
+        assembly::{Assembler, ModuleAst},
+        assets::AssetVault,
+        block::{AccountWitness, ACCOUNT_TREE_DEPTH},
+        crypto::merkle::{NodeIndex, SimpleSmt},
+        utils::serde::{Deserializable, Serializable},
+        Digest, Felt, TransactionInputError, ONE,
+    };
+
+    #[test]
+    fn foreign_account_inputs_validation() {
+        let id = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let module = ModuleAst::parse("export.foo push.1 push.2 mul end").unwrap();
+        let code = AccountCode::new(module, &Assembler::default()).unwrap();
+        let price = [Felt::new(42), ONE, ONE, ONE];
+        let storage = AccountStorage::new(vec![(0, (StorageSlotType::default(), price))]).unwrap();
+        let account = Account::new(id, AssetVault::default(), storage, code, ONE);
+
+        let tree =
+            SimpleSmt::with_leaves(ACCOUNT_TREE_DEPTH, [(u64::from(id), account.hash().into())])
+                .unwrap();
+        let index = NodeIndex::new(ACCOUNT_TREE_DEPTH, u64::from(id)).unwrap();
+        let proof = tree.get_path(index).unwrap();
+
+        let witness = AccountWitness::new(id, account.hash(), proof.clone());
+        let inputs = ForeignAccountInputs::from_account(&account, witness).unwrap();
+        assert!(inputs.witness().verify(tree.root()));
+        assert_eq!(inputs.storage().get_item(0), Digest::from(price));
+
+        let bytes = inputs.to_bytes();
+        assert_eq!(inputs, ForeignAccountInputs::read_from_bytes(&bytes).unwrap());
+
+        // a witness attesting to a different state of the account is rejected
+        let stale_witness = AccountWitness::new(id, Digest::default(), proof);
+        assert_eq!(
+            ForeignAccountInputs::from_account(&account, stale_witness),
+            Err(TransactionInputError::InconsistentForeignAccountInputs(id))
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use core::fmt::Debug;
 
-use super::{BlockHeader, ChainMmr, Digest, Felt, Hasher, Word, MAX_INPUT_NOTES_PER_TRANSACTION};
+use super::{
+    BlockHeader, ChainMmr, Digest, FeeParameters, Felt, Hasher, Word,
+    MAX_INPUT_NOTES_PER_TRANSACTION,
+};
 use crate::{
     accounts::{validate_account_seed, Account},
     notes::{Note, NoteId, NoteInclusionProof, NoteOrigin, Nullifier},
@@ -23,6 +26,7 @@ pub struct TransactionInputs {
     block_header: BlockHeader,
     block_chain: ChainMmr,
     input_notes: InputNotes,
+    fee_parameters: Option<FeeParameters>,
 }
 
 impl TransactionInputs {
@@ -57,9 +61,29 @@ impl TransactionInputs {
             block_header,
             block_chain,
             input_notes,
+            fee_parameters: None,
         })
     }
 
+    /// Returns these transaction inputs with the fee parameters set to the specified parameters.
+    ///
+    /// When executed with fee parameters, the transaction kernel charges the fee computed from
+    /// these parameters by removing it from the account vault during the transaction epilogue.
+    ///
+    /// # Errors
+    /// Returns an error if the fee defined by the parameters cannot be charged by the transaction
+    /// kernel (see [FeeParameters::is_kernel_chargeable()]).
+    pub fn with_fee_parameters(
+        mut self,
+        fee_parameters: FeeParameters,
+    ) -> Result<Self, TransactionInputError> {
+        if !fee_parameters.is_kernel_chargeable() {
+            return Err(TransactionInputError::InvalidFeeParameters(fee_parameters));
+        }
+        self.fee_parameters = Some(fee_parameters);
+        Ok(self)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -89,21 +113,64 @@ impl TransactionInputs {
         &self.input_notes
     }
 
+    /// Returns the parameters from which the fee charged by the transaction kernel is computed, or
+    /// None if no fee is charged.
+    pub fn fee_parameters(&self) -> Option<&FeeParameters> {
+        self.fee_parameters.as_ref()
+    }
+
     // CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
     /// Consumes these transaction inputs and returns their underlying components.
-    pub fn into_parts(self) -> (Account, Option<Word>, BlockHeader, ChainMmr, InputNotes) {
+    pub fn into_parts(
+        self,
+    ) -> (Account, Option<Word>, BlockHeader, ChainMmr, InputNotes, Option<FeeParameters>) {
         (
             self.account,
             self.account_seed,
             self.block_header,
             self.block_chain,
             self.input_notes,
+            self.fee_parameters,
         )
     }
 }
 
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for TransactionInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account.write_into(target);
+        self.account_seed.write_into(target);
+        self.block_header.write_into(target);
+        self.block_chain.write_into(target);
+        self.input_notes.write_into(target);
+        self.fee_parameters.write_into(target);
+    }
+}
+
+impl Deserializable for TransactionInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account = Account::read_from(source)?;
+        let account_seed = Option::<Word>::read_from(source)?;
+        let block_header = BlockHeader::read_from(source)?;
+        let block_chain = ChainMmr::read_from(source)?;
+        let input_notes = InputNotes::read_from(source)?;
+        let fee_parameters = Option::<FeeParameters>::read_from(source)?;
+
+        let tx_inputs = Self::new(account, account_seed, block_header, block_chain, input_notes)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+        match fee_parameters {
+            Some(fee_parameters) => tx_inputs
+                .with_fee_parameters(fee_parameters)
+                .map_err(|err| DeserializationError::InvalidValue(err.to_string())),
+            None => Ok(tx_inputs),
+        }
+    }
+}
+
 // TO NULLIFIER TRAIT
 // ================================================================================================
 
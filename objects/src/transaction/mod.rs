@@ -1,35 +1,53 @@
 use super::{
-    accounts::{Account, AccountDelta, AccountId, AccountStub},
+    accounts::{Account, AccountDelta, AccountHeader, AccountId},
     notes::{NoteEnvelope, Nullifier},
+    utils::sync::Arc,
     vm::{AdviceInputs, Program},
-    BlockHeader, Digest, Felt, Hasher, Word, WORD_SIZE, ZERO,
+    BlockHeader, Digest, Felt, Hasher, Word, EMPTY_WORD, WORD_SIZE, ZERO,
 };
 
+mod account_update;
+mod batch;
 mod chain_mmr;
+mod description;
 mod executed_tx;
+mod fee;
+mod foreign_account;
 mod inputs;
 mod outputs;
 mod prepared_tx;
 mod proven_tx;
+mod script_inputs;
+mod summary;
 mod transaction_id;
 mod tx_script;
 mod tx_witness;
+mod weight;
 
-pub use chain_mmr::ChainMmr;
+pub use account_update::AccountUpdate;
+pub(crate) use batch::sort_by_dependencies;
+pub use batch::TransactionBatch;
+pub use chain_mmr::{ChainMmr, ChainMmrBuilder};
+pub use description::{NoteDescription, TransactionDescription};
 pub use executed_tx::ExecutedTransaction;
+pub use fee::{FeeParameters, TransactionFee, TransactionMeasurements};
+pub use foreign_account::ForeignAccountInputs;
 pub use inputs::{InputNote, InputNotes, TransactionInputs};
 pub use outputs::{OutputNote, OutputNotes, TransactionOutputs};
 pub use prepared_tx::PreparedTransaction;
-pub use proven_tx::ProvenTransaction;
+pub use proven_tx::{ProvenTransaction, ProvenTransactionHeader};
+pub use script_inputs::{ToInputElements, ToTransactionScriptInputs, TransactionScriptInputs};
+pub use summary::TransactionSummary;
 pub use transaction_id::TransactionId;
 pub use tx_script::TransactionScript;
-pub use tx_witness::TransactionWitness;
+pub use tx_witness::{read_advice_inputs, write_advice_inputs, TransactionWitness};
+pub use weight::TransactionWeight;
 
 // CONSTANTS
 // ================================================================================================
 
 /// Maximum number of notes consumed in a single transaction.
-const MAX_INPUT_NOTES_PER_TRANSACTION: usize = 1024;
+pub const MAX_INPUT_NOTES_PER_TRANSACTION: usize = 1024;
 
 /// Maximum number of notes created in a single transaction.
-const MAX_OUTPUT_NOTES_PER_TRANSACTION: usize = 1024;
+pub const MAX_OUTPUT_NOTES_PER_TRANSACTION: usize = 1024;
@@ -1,8 +1,8 @@
 use core::fmt::Debug;
 
-use super::MAX_OUTPUT_NOTES_PER_TRANSACTION;
+use super::{TransactionFee, MAX_OUTPUT_NOTES_PER_TRANSACTION};
 use crate::{
-    accounts::AccountStub,
+    accounts::AccountHeader,
     notes::{Note, NoteAssets, NoteEnvelope, NoteId, NoteMetadata},
     utils::{
         collections::{self, BTreeSet, Vec},
@@ -16,10 +16,40 @@ use crate::{
 // ================================================================================================
 
 /// Describes the result of executing a transaction.
+///
+/// `expiration_block_num` is the number of the last block into which the transaction can be
+/// included, if the expiration of the transaction was set during its execution. `fee` is the fee
+/// charged by the transaction kernel, if the transaction was executed with fee parameters.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TransactionOutputs {
-    pub account: AccountStub,
+    pub account: AccountHeader,
     pub output_notes: OutputNotes,
+    pub expiration_block_num: Option<u32>,
+    pub fee: Option<TransactionFee>,
+}
+
+impl Serializable for TransactionOutputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account.write_into(target);
+        self.output_notes.write_into(target);
+        self.expiration_block_num.write_into(target);
+        self.fee.write_into(target);
+    }
+}
+
+impl Deserializable for TransactionOutputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account = AccountHeader::read_from(source)?;
+        let output_notes = OutputNotes::read_from(source)?;
+        let expiration_block_num = <Option<u32>>::read_from(source)?;
+        let fee = <Option<TransactionFee>>::read_from(source)?;
+        Ok(Self {
+            account,
+            output_notes,
+            expiration_block_num,
+            fee,
+        })
+    }
 }
 
 // TO ENVELOPE TRAIT
@@ -193,7 +223,7 @@ impl<T: ToEnvelope> Deserializable for OutputNotes<T> {
 ///
 /// For a non-empty list of notes, this is a sequential hash of (note_id, metadata) tuples for the
 /// notes created in a transaction. For an empty list, [ZERO; 4] is returned.
-fn build_output_notes_commitment<T: ToEnvelope>(notes: &[T]) -> Digest {
+pub(super) fn build_output_notes_commitment<T: ToEnvelope>(notes: &[T]) -> Digest {
     if notes.is_empty() {
         return Digest::default();
     }
@@ -1,4 +1,7 @@
-use super::{Account, BlockHeader, InputNotes, Program, TransactionInputs, TransactionScript};
+use super::{
+    Account, Arc, BlockHeader, InputNotes, Program, TransactionInputs, TransactionScript, Word,
+    EMPTY_WORD,
+};
 
 // PREPARED TRANSACTION
 // ================================================================================================
@@ -7,12 +10,16 @@ use super::{Account, BlockHeader, InputNotes, Program, TransactionInputs, Transa
 ///
 /// This includes:
 /// - A an executable program which defines the transaction.
-/// - An optional transaction script.
+/// - An optional transaction script and the arguments it is executed with.
 /// - A set of inputs against which the transaction program should be executed.
+///
+/// The transaction program is shared via an [Arc] so that the same program can be passed through
+/// the execution and proving pipeline without copying its MAST.
 #[derive(Debug)]
 pub struct PreparedTransaction {
-    program: Program,
+    program: Arc<Program>,
     tx_script: Option<TransactionScript>,
+    tx_script_args: Word,
     tx_inputs: TransactionInputs,
 }
 
@@ -21,12 +28,31 @@ impl PreparedTransaction {
     // --------------------------------------------------------------------------------------------
     /// Returns a new [PreparedTransaction] instantiated from the provided executable transaction
     /// program and inputs required to execute this program.
+    ///
+    /// The transaction script is executed with empty arguments unless different arguments are set
+    /// via [PreparedTransaction::with_tx_script_args()].
     pub fn new(
-        program: Program,
+        program: Arc<Program>,
         tx_script: Option<TransactionScript>,
         tx_inputs: TransactionInputs,
     ) -> Self {
-        Self { program, tx_script, tx_inputs }
+        Self {
+            program,
+            tx_script,
+            tx_script_args: EMPTY_WORD,
+            tx_inputs,
+        }
+    }
+
+    /// Returns this transaction with the arguments of its transaction script set to the provided
+    /// word.
+    ///
+    /// The arguments are passed to the transaction kernel together with the other transaction
+    /// inputs rather than compiled into the transaction program, thus the same program can be
+    /// executed with different arguments.
+    pub fn with_tx_script_args(mut self, tx_script_args: Word) -> Self {
+        self.tx_script_args = tx_script_args;
+        self
     }
 
     // ACCESSORS
@@ -37,6 +63,11 @@ impl PreparedTransaction {
         &self.program
     }
 
+    /// Returns a shared reference to the transaction program.
+    pub fn shared_program(&self) -> Arc<Program> {
+        self.program.clone()
+    }
+
     /// Returns the account for this transaction.
     pub fn account(&self) -> &Account {
         self.tx_inputs.account()
@@ -57,6 +88,11 @@ impl PreparedTransaction {
         self.tx_script.as_ref()
     }
 
+    /// Returns the arguments the transaction script is executed with.
+    pub fn tx_script_args(&self) -> Word {
+        self.tx_script_args
+    }
+
     /// Returns a reference to the inputs for this transaction.
     pub fn tx_inputs(&self) -> &TransactionInputs {
         &self.tx_inputs
@@ -66,7 +102,7 @@ impl PreparedTransaction {
     // --------------------------------------------------------------------------------------------
 
     /// Consumes the prepared transaction and returns its parts.
-    pub fn into_parts(self) -> (Program, Option<TransactionScript>, TransactionInputs) {
-        (self.program, self.tx_script, self.tx_inputs)
+    pub fn into_parts(self) -> (Arc<Program>, Option<TransactionScript>, Word, TransactionInputs) {
+        (self.program, self.tx_script, self.tx_script_args, self.tx_inputs)
     }
 }
@@ -1,8 +1,14 @@
-use miden_crypto::utils::{ByteReader, ByteWriter, Deserializable, Serializable};
+use core::fmt;
+
+use miden_crypto::utils::{ByteReader, ByteWriter, Deserializable, Serializable, SliceReader};
 use miden_verifier::ExecutionProof;
 use vm_processor::DeserializationError;
 
-use super::{AccountId, Digest, InputNotes, NoteEnvelope, Nullifier, OutputNotes, TransactionId};
+use super::{
+    Account, AccountId, Digest, InputNotes, NoteEnvelope, Nullifier, OutputNotes,
+    TransactionDescription, TransactionFee, TransactionId, TransactionSummary, TransactionWeight,
+};
+use crate::utils::string::ToString;
 
 // PROVEN TRANSACTION
 // ================================================================================================
@@ -19,6 +25,17 @@ use super::{AccountId, Digest, InputNotes, NoteEnvelope, Nullifier, OutputNotes,
 ///   transaction.
 /// - tx_script_root: the script root of the transaction, if one was used.
 /// - block_ref: the block hash of the last known block at the time the transaction was executed.
+/// - kernel_hash: the hash of the executable program of the transaction kernel the transaction
+///   was executed with; the proof is verified against the [ProgramInfo](crate::vm::ProgramInfo)
+///   of this kernel.
+/// - account_details: the full final state of the account, if the transaction created a new
+///   on-chain account.
+/// - priority_fee: an optional fee offered on top of the required fee, which operators may use to
+///   order transactions competing for inclusion into a block.
+/// - expiration_block_num: the number of the last block into which the transaction can be
+///   included, if the transaction expires.
+/// - fee: the fee charged by the transaction kernel, if the transaction was executed with fee
+///   parameters.
 /// - proof: a STARK proof that attests to the correct execution of the transaction.
 #[derive(Clone, Debug)]
 pub struct ProvenTransaction {
@@ -30,6 +47,11 @@ pub struct ProvenTransaction {
     output_notes: OutputNotes<NoteEnvelope>,
     tx_script_root: Option<Digest>,
     block_ref: Digest,
+    kernel_hash: Digest,
+    account_details: Option<Account>,
+    priority_fee: Option<u64>,
+    expiration_block_num: Option<u32>,
+    fee: Option<TransactionFee>,
     proof: ExecutionProof,
 }
 
@@ -38,6 +60,14 @@ impl ProvenTransaction {
     // --------------------------------------------------------------------------------------------
 
     /// Returns a new [ProvenTransaction] instantiated from the provided parameters.
+    ///
+    /// The expiration block number is committed to by the transaction ID. It is also an output of
+    /// the transaction kernel, thus it must match the expiration set during the execution of the
+    /// transaction for the proof of the transaction to be valid. The same applies to the fee
+    /// charged by the transaction kernel.
+    ///
+    /// # Panics
+    /// Panics if account details are provided, but their hash is not the final account hash.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         account_id: AccountId,
@@ -47,14 +77,26 @@ impl ProvenTransaction {
         output_notes: OutputNotes<NoteEnvelope>,
         tx_script_root: Option<Digest>,
         block_ref: Digest,
+        kernel_hash: Digest,
+        account_details: Option<Account>,
+        expiration_block_num: Option<u32>,
+        fee: Option<TransactionFee>,
         proof: ExecutionProof,
     ) -> Self {
+        if let Some(account) = account_details.as_ref() {
+            assert_eq!(account.hash(), final_account_hash, "inconsistent account details");
+        }
+
         let id = TransactionId::new(
             initial_account_hash,
             final_account_hash,
             input_notes.commitment(),
             output_notes.commitment(),
         );
+        let id = match expiration_block_num {
+            Some(expiration_block_num) => id.with_expiration_block_num(expiration_block_num),
+            None => id,
+        };
 
         Self {
             id,
@@ -65,10 +107,24 @@ impl ProvenTransaction {
             output_notes,
             tx_script_root,
             block_ref,
+            kernel_hash,
+            account_details,
+            priority_fee: None,
+            expiration_block_num,
+            fee,
             proof,
         }
     }
 
+    /// Returns this transaction with the priority fee set to the specified value.
+    ///
+    /// The priority fee is not committed to by the transaction ID or the proof; it is a bid made
+    /// by the submitter of the transaction to the operator.
+    pub fn with_priority_fee(mut self, priority_fee: u64) -> Self {
+        self.priority_fee = Some(priority_fee);
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -116,6 +172,81 @@ impl ProvenTransaction {
     pub fn block_ref(&self) -> Digest {
         self.block_ref
     }
+
+    /// Returns the hash of the transaction kernel the transaction was executed with.
+    pub fn kernel_hash(&self) -> Digest {
+        self.kernel_hash
+    }
+
+    /// Returns the full final state of the account if this transaction created a new on-chain
+    /// account, or None otherwise.
+    pub fn account_details(&self) -> Option<&Account> {
+        self.account_details.as_ref()
+    }
+
+    /// Returns the priority fee offered for the inclusion of this transaction, if any.
+    pub fn priority_fee(&self) -> Option<u64> {
+        self.priority_fee
+    }
+
+    /// Returns the number of the last block into which this transaction can be included, or None
+    /// if the transaction never expires.
+    pub fn expiration_block_num(&self) -> Option<u32> {
+        self.expiration_block_num
+    }
+
+    /// Returns the fee charged by the transaction kernel, or None if the transaction was executed
+    /// without fee parameters.
+    pub fn fee(&self) -> Option<TransactionFee> {
+        self.fee
+    }
+
+    /// Returns true if this transaction cannot be included into the block with the specified
+    /// number.
+    pub fn is_expired(&self, block_num: u32) -> bool {
+        self.expiration_block_num.map_or(false, |expiration| block_num > expiration)
+    }
+
+    /// Returns the amount of block resources consumed by this transaction.
+    ///
+    /// The account update size accounts for the final account hash, and for the serialized
+    /// account details if this transaction created a new on-chain account.
+    pub fn weight(&self) -> TransactionWeight {
+        let account_update_size = self.final_account_hash.as_bytes().len()
+            + self.account_details.as_ref().map_or(0, |account| account.to_bytes().len());
+
+        TransactionWeight::new(
+            self.to_bytes().len(),
+            self.input_notes.num_notes(),
+            self.output_notes.num_notes(),
+            account_update_size,
+        )
+    }
+
+    /// Returns the header of this transaction, i.e., all transaction data except for the proof.
+    pub fn header(&self) -> ProvenTransactionHeader {
+        self.into()
+    }
+
+    /// Returns a human-readable description of the effects of this transaction.
+    ///
+    /// Since a proven transaction does not contain note details or the account delta, the
+    /// description identifies consumed notes by their nullifiers only, and does not contain
+    /// changes of the account nonce and vault.
+    pub fn description(&self) -> TransactionDescription {
+        self.into()
+    }
+
+    /// Returns the [TransactionSummary] committing to the effects of this transaction.
+    pub fn summary(&self) -> TransactionSummary {
+        self.into()
+    }
+}
+
+impl fmt::Display for ProvenTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.description().fmt(f)
+    }
 }
 
 // SERIALIZATION
@@ -130,11 +261,200 @@ impl Serializable for ProvenTransaction {
         self.output_notes.write_into(target);
         self.tx_script_root.write_into(target);
         self.block_ref.write_into(target);
+        self.kernel_hash.write_into(target);
+        self.account_details.write_into(target);
+        self.priority_fee.write_into(target);
+        self.expiration_block_num.write_into(target);
+        self.fee.write_into(target);
         self.proof.write_into(target);
     }
 }
 
 impl Deserializable for ProvenTransaction {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let header = ProvenTransactionHeader::read_from(source)?;
+        let proof = ExecutionProof::read_from(source)?;
+
+        Ok(Self {
+            id: header.id,
+            account_id: header.account_id,
+            initial_account_hash: header.initial_account_hash,
+            final_account_hash: header.final_account_hash,
+            input_notes: header.input_notes,
+            output_notes: header.output_notes,
+            tx_script_root: header.tx_script_root,
+            block_ref: header.block_ref,
+            kernel_hash: header.kernel_hash,
+            account_details: header.account_details,
+            priority_fee: header.priority_fee,
+            expiration_block_num: header.expiration_block_num,
+            fee: header.fee,
+            proof,
+        })
+    }
+}
+
+// PROVEN TRANSACTION HEADER
+// ================================================================================================
+
+/// All data of a [ProvenTransaction] except for the proof.
+///
+/// A header can be read directly from the serialized bytes of a [ProvenTransaction] via
+/// [ProvenTransactionHeader::read_from_proven_tx_bytes()]. Since the proof is placed at the end of
+/// the serialized transaction, this does not require decoding (or copying) the proof bytes. This
+/// is useful for checks which need to be performed before the proof is verified (e.g., checking
+/// the transaction ID, the account ID, or the consumed nullifiers), especially if the same
+/// transaction is received multiple times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvenTransactionHeader {
+    id: TransactionId,
+    account_id: AccountId,
+    initial_account_hash: Digest,
+    final_account_hash: Digest,
+    input_notes: InputNotes<Nullifier>,
+    output_notes: OutputNotes<NoteEnvelope>,
+    tx_script_root: Option<Digest>,
+    block_ref: Digest,
+    kernel_hash: Digest,
+    account_details: Option<Account>,
+    priority_fee: Option<u64>,
+    expiration_block_num: Option<u32>,
+    fee: Option<TransactionFee>,
+}
+
+impl ProvenTransactionHeader {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads the header of a [ProvenTransaction] from the provided serialized transaction bytes.
+    ///
+    /// Returns the header together with the slice of bytes holding the serialized proof. The proof
+    /// bytes are neither decoded nor copied; the slice can be later decoded via
+    /// [ExecutionProof::read_from_bytes()] if the proof needs to be verified.
+    ///
+    /// # Errors
+    /// Returns an error if the header portion of the provided bytes is not a valid serialized
+    /// [ProvenTransaction] header.
+    pub fn read_from_proven_tx_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), DeserializationError> {
+        let mut source = SliceReader::new(bytes);
+        let header = Self::read_from(&mut source)?;
+
+        // the header is re-serialized to determine where the proof bytes start; this is cheap as
+        // the header does not contain any large data
+        let header_len = header.to_bytes().len();
+        Ok((header, &bytes[header_len..]))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns unique identifier of the transaction.
+    pub fn id(&self) -> TransactionId {
+        self.id
+    }
+
+    /// Returns ID of the account against which the transaction was executed.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the initial account state hash.
+    pub fn initial_account_hash(&self) -> Digest {
+        self.initial_account_hash
+    }
+
+    /// Returns the final account state hash.
+    pub fn final_account_hash(&self) -> Digest {
+        self.final_account_hash
+    }
+
+    /// Returns a reference to the notes consumed by the transaction.
+    pub fn input_notes(&self) -> &InputNotes<Nullifier> {
+        &self.input_notes
+    }
+
+    /// Returns a reference to the notes produced by the transaction.
+    pub fn output_notes(&self) -> &OutputNotes<NoteEnvelope> {
+        &self.output_notes
+    }
+
+    /// Returns the script root of the transaction.
+    pub fn tx_script_root(&self) -> Option<Digest> {
+        self.tx_script_root
+    }
+
+    /// Returns the block reference the transaction was executed against.
+    pub fn block_ref(&self) -> Digest {
+        self.block_ref
+    }
+
+    /// Returns the hash of the transaction kernel the transaction was executed with.
+    pub fn kernel_hash(&self) -> Digest {
+        self.kernel_hash
+    }
+
+    /// Returns the full final state of the account if the transaction created a new on-chain
+    /// account, or None otherwise.
+    pub fn account_details(&self) -> Option<&Account> {
+        self.account_details.as_ref()
+    }
+
+    /// Returns the priority fee offered for the inclusion of the transaction, if any.
+    pub fn priority_fee(&self) -> Option<u64> {
+        self.priority_fee
+    }
+
+    /// Returns the number of the last block into which the transaction can be included, or None
+    /// if the transaction never expires.
+    pub fn expiration_block_num(&self) -> Option<u32> {
+        self.expiration_block_num
+    }
+
+    /// Returns the fee charged by the transaction kernel, or None if the transaction was executed
+    /// without fee parameters.
+    pub fn fee(&self) -> Option<TransactionFee> {
+        self.fee
+    }
+}
+
+impl From<&ProvenTransaction> for ProvenTransactionHeader {
+    fn from(tx: &ProvenTransaction) -> Self {
+        Self {
+            id: tx.id,
+            account_id: tx.account_id,
+            initial_account_hash: tx.initial_account_hash,
+            final_account_hash: tx.final_account_hash,
+            input_notes: tx.input_notes.clone(),
+            output_notes: tx.output_notes.clone(),
+            tx_script_root: tx.tx_script_root,
+            block_ref: tx.block_ref,
+            kernel_hash: tx.kernel_hash,
+            account_details: tx.account_details.clone(),
+            priority_fee: tx.priority_fee,
+            expiration_block_num: tx.expiration_block_num,
+            fee: tx.fee,
+        }
+    }
+}
+
+impl Serializable for ProvenTransactionHeader {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account_id.write_into(target);
+        self.initial_account_hash.write_into(target);
+        self.final_account_hash.write_into(target);
+        self.input_notes.write_into(target);
+        self.output_notes.write_into(target);
+        self.tx_script_root.write_into(target);
+        self.block_ref.write_into(target);
+        self.kernel_hash.write_into(target);
+        self.account_details.write_into(target);
+        self.priority_fee.write_into(target);
+        self.expiration_block_num.write_into(target);
+        self.fee.write_into(target);
+    }
+}
+
+impl Deserializable for ProvenTransactionHeader {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let account_id = AccountId::read_from(source)?;
         let initial_account_hash = Digest::read_from(source)?;
@@ -146,14 +466,30 @@ impl Deserializable for ProvenTransaction {
         let tx_script_root = Deserializable::read_from(source)?;
 
         let block_ref = Digest::read_from(source)?;
-        let proof = ExecutionProof::read_from(source)?;
+        let kernel_hash = Digest::read_from(source)?;
 
-        let id = TransactionId::new(
+        let account_details = <Option<Account>>::read_from(source)?;
+        if let Some(account) = account_details.as_ref() {
+            if account.hash() != final_account_hash {
+                return Err(DeserializationError::InvalidValue(
+                    "account details are inconsistent with the final account hash".to_string(),
+                ));
+            }
+        }
+
+        let priority_fee = <Option<u64>>::read_from(source)?;
+        let expiration_block_num = <Option<u32>>::read_from(source)?;
+        let fee = <Option<TransactionFee>>::read_from(source)?;
+
+        let mut id = TransactionId::new(
             initial_account_hash,
             final_account_hash,
             input_notes.commitment(),
             output_notes.commitment(),
         );
+        if let Some(expiration_block_num) = expiration_block_num {
+            id = id.with_expiration_block_num(expiration_block_num);
+        }
 
         Ok(Self {
             id,
@@ -164,7 +500,110 @@ impl Deserializable for ProvenTransaction {
             output_notes,
             tx_script_root,
             block_ref,
-            proof,
+            kernel_hash,
+            account_details,
+            priority_fee,
+            expiration_block_num,
+            fee,
         })
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ProvenTransactionHeader;
+    use crate::{
+        accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN},
+        transaction::{InputNotes, OutputNotes, TransactionId},
+        utils::serde::{Deserializable, Serializable},
+        Digest,
+    };
+
+    #[test]
+    fn header_serialization_matches_proven_tx_prefix() {
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let header = ProvenTransactionHeader {
+            id: TransactionId::new(
+                Digest::default(),
+                Digest::default(),
+                Digest::default(),
+                Digest::default(),
+            ),
+            account_id,
+            initial_account_hash: Digest::default(),
+            final_account_hash: Digest::default(),
+            input_notes: InputNotes::new(vec![]).unwrap(),
+            output_notes: OutputNotes::new(vec![]).unwrap(),
+            tx_script_root: None,
+            block_ref: Digest::default(),
+            kernel_hash: Digest::default(),
+            account_details: None,
+            priority_fee: Some(10),
+            expiration_block_num: None,
+            fee: None,
+        };
+
+        // append arbitrary "proof" bytes and make sure they are returned untouched
+        let mut bytes = header.to_bytes();
+        bytes.extend_from_slice(&[1, 2, 3, 4]);
+
+        let (parsed, proof_bytes) =
+            ProvenTransactionHeader::read_from_proven_tx_bytes(&bytes).unwrap();
+        assert_eq!(parsed.account_id(), account_id);
+        assert_eq!(parsed.block_ref(), Digest::default());
+        assert_eq!(parsed.priority_fee(), Some(10));
+        assert_eq!(proof_bytes, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn header_serialization_round_trip_commits_to_expiration() {
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let base_id = TransactionId::new(
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+        );
+        let header = ProvenTransactionHeader {
+            id: base_id.with_expiration_block_num(42),
+            account_id,
+            initial_account_hash: Digest::default(),
+            final_account_hash: Digest::default(),
+            input_notes: InputNotes::new(vec![]).unwrap(),
+            output_notes: OutputNotes::new(vec![]).unwrap(),
+            tx_script_root: None,
+            block_ref: Digest::default(),
+            kernel_hash: Digest::default(),
+            account_details: None,
+            priority_fee: None,
+            expiration_block_num: Some(42),
+            fee: None,
+        };
+
+        let bytes = header.to_bytes();
+        let parsed = ProvenTransactionHeader::read_from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, header);
+        assert_eq!(parsed.expiration_block_num(), Some(42));
+
+        // altering the expiration block number in the serialized bytes changes the transaction ID;
+        // the expiration block number is followed by the (absent) fee
+        let mut tampered = bytes.clone();
+        let exp_offset = tampered.len() - 5;
+        tampered[exp_offset..exp_offset + 4].copy_from_slice(&43_u32.to_le_bytes());
+        let parsed = ProvenTransactionHeader::read_from_bytes(&tampered).unwrap();
+        assert_eq!(parsed.expiration_block_num(), Some(43));
+        assert_ne!(parsed.id(), header.id);
+
+        // stripping the expiration block number changes the transaction ID as well
+        let mut stripped = bytes[..exp_offset - 1].to_vec();
+        stripped.extend([0, 0]);
+        let parsed = ProvenTransactionHeader::read_from_bytes(&stripped).unwrap();
+        assert_eq!(parsed.expiration_block_num(), None);
+        assert_eq!(parsed.id(), base_id);
+    }
+}
@@ -0,0 +1,209 @@
+use super::{AccountId, Digest, Felt, Hasher, Word};
+use crate::{assets::Asset, utils::collections::Vec};
+
+// INPUT ELEMENTS ENCODING
+// ================================================================================================
+
+/// Defines how a Rust value is encoded into field elements when passed to a transaction script
+/// via the advice map.
+///
+/// The encodings of the provided implementations follow the conventions of the Miden stdlib, such
+/// that after the elements of an advice map entry are moved onto the advice stack (e.g., via
+/// `adv.push_mapval`), the values can be read with the following instructions:
+///
+/// | Rust type                 | Elements                    | Reading in MASM                  |
+/// | ------------------------- | --------------------------- | -------------------------------- |
+/// | [Felt], [u32], [bool]     | `[value]`                   | `adv_push.1`                     |
+/// | [AccountId]               | `[id]`                      | `adv_push.1`                     |
+/// | [u64]                     | `[lo, hi]`                  | `adv_push.2` (`hi` on top)       |
+/// | [Word], [Digest]          | `[a, b, c, d]`              | `adv_loadw` (`d` on top)         |
+/// | [Asset]                   | asset word                  | `adv_loadw`                      |
+/// | `[T]`, `[T; N]`, `Vec<T>` | elements of all items       | each item in order               |
+///
+/// Thus, [u64] values are read in the layout expected by `std::math::u64`.
+///
+/// Note that [Word] is encoded via the implementation for arrays of [Felt].
+///
+/// Values of a struct are expected to be encoded in the order of its fields, in which case the
+/// fields are read in MASM in the same order.
+///
+/// The same encodings are used to build note inputs via
+/// [NoteInputs::from_values()](crate::notes::NoteInputs::from_values).
+pub trait ToInputElements {
+    /// Appends the field elements encoding this value to the provided vector.
+    fn write_into_elements(&self, target: &mut Vec<Felt>);
+
+    /// Returns the field elements encoding this value.
+    fn to_input_elements(&self) -> Vec<Felt> {
+        let mut elements = Vec::new();
+        self.write_into_elements(&mut elements);
+        elements
+    }
+}
+
+impl ToInputElements for Felt {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        target.push(*self);
+    }
+}
+
+impl ToInputElements for bool {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        target.push(Felt::from(*self as u32));
+    }
+}
+
+impl ToInputElements for u32 {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        target.push(Felt::from(*self));
+    }
+}
+
+impl ToInputElements for u64 {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        target.push(Felt::from(*self as u32));
+        target.push(Felt::from((*self >> 32) as u32));
+    }
+}
+
+impl ToInputElements for AccountId {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        target.push((*self).into());
+    }
+}
+
+impl ToInputElements for Digest {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        target.extend_from_slice(self.as_elements());
+    }
+}
+
+impl ToInputElements for Asset {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        target.extend_from_slice(&Word::from(self));
+    }
+}
+
+impl<T: ToInputElements> ToInputElements for [T] {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        self.iter().for_each(|item| item.write_into_elements(target));
+    }
+}
+
+impl<T: ToInputElements> ToInputElements for Vec<T> {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        self.as_slice().write_into_elements(target);
+    }
+}
+
+impl<T: ToInputElements, const N: usize> ToInputElements for [T; N] {
+    fn write_into_elements(&self, target: &mut Vec<Felt>) {
+        self.as_slice().write_into_elements(target);
+    }
+}
+
+// TRANSACTION SCRIPT INPUTS
+// ================================================================================================
+
+/// A set of advice map entries passed to a transaction script.
+///
+/// [TransactionScriptInputs] can be passed anywhere transaction script inputs are expected (e.g.,
+/// to `TransactionScript::new()`) as it iterates over `(Word, Vec<Felt>)` pairs.
+///
+/// Values can be inserted either under an explicit key, or under a key computed as the hash of
+/// the value elements. The latter allows passing the key to the script as its commitment, e.g.,
+/// by hardcoding it into the script source.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionScriptInputs {
+    entries: Vec<(Word, Vec<Felt>)>,
+}
+
+impl TransactionScriptInputs {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty set of transaction script inputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the advice map entries of these inputs in insertion order.
+    pub fn entries(&self) -> &[(Word, Vec<Felt>)] {
+        &self.entries
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Encodes the provided value and inserts it under the specified key.
+    pub fn insert<T: ToInputElements + ?Sized>(&mut self, key: Word, value: &T) -> &mut Self {
+        self.entries.push((key, value.to_input_elements()));
+        self
+    }
+
+    /// Encodes the provided value and inserts it under the hash of its elements. The key of the
+    /// inserted entry is returned.
+    pub fn insert_committed<T: ToInputElements + ?Sized>(&mut self, value: &T) -> Word {
+        let elements = value.to_input_elements();
+        let key: Word = Hasher::hash_elements(&elements).into();
+        self.entries.push((key, elements));
+        key
+    }
+}
+
+impl IntoIterator for TransactionScriptInputs {
+    type Item = (Word, Vec<Felt>);
+    type IntoIter = <Vec<(Word, Vec<Felt>)> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+// TO TRANSACTION SCRIPT INPUTS
+// ================================================================================================
+
+/// Defines how a Rust value is converted into the inputs of a transaction script.
+///
+/// This is expected to be implemented for structs describing the arguments of a specific
+/// transaction script, such that the script inputs are built in a single place rather than
+/// hand-packed at every call site.
+pub trait ToTransactionScriptInputs {
+    /// Returns the transaction script inputs encoding this value.
+    fn to_script_inputs(&self) -> TransactionScriptInputs;
+}
+
+impl ToTransactionScriptInputs for TransactionScriptInputs {
+    fn to_script_inputs(&self) -> TransactionScriptInputs {
+        self.clone()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ToInputElements, TransactionScriptInputs};
+    use crate::{utils::collections::Vec, Felt, Hasher, Word, ONE, ZERO};
+
+    #[test]
+    fn u64_is_encoded_as_u32_limbs() {
+        let value: u64 = (7 << 32) | 5;
+        assert_eq!(value.to_input_elements(), [Felt::new(5), Felt::new(7)]);
+    }
+
+    #[test]
+    fn committed_inputs_are_keyed_by_hash() {
+        let word: Word = [ONE, ZERO, ONE, ZERO];
+        let mut inputs = TransactionScriptInputs::new();
+        let key = inputs.insert_committed(&[word, word]);
+
+        let expected_elements = [word, word].to_input_elements();
+        assert_eq!(expected_elements.len(), 8);
+        assert_eq!(key, Word::from(Hasher::hash_elements(&expected_elements)));
+        assert_eq!(inputs.into_iter().collect::<Vec<_>>(), vec![(key, expected_elements)]);
+    }
+}
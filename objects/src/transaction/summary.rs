@@ -0,0 +1,264 @@
+use super::{
+    AccountId, Digest, ExecutedTransaction, Felt, Hasher, ProvenTransaction, Word, WORD_SIZE, ZERO,
+};
+use crate::utils::serde::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// TRANSACTION SUMMARY
+// ================================================================================================
+
+/// A compact commitment to the effects of a transaction.
+///
+/// The summary consists of:
+/// - account_id: ID of the account the transaction was executed against.
+/// - account_update_commitment: a commitment to the transition of the account state, computed as
+///   hash(initial_account_hash, final_account_hash). Note that this is not a commitment to the
+///   account delta.
+/// - input_notes_commitment: a commitment to the nullifiers of the notes consumed by the
+///   transaction.
+/// - output_notes_commitment: a commitment to the notes created by the transaction.
+/// - expiration_block_num: the number of the last block into which the transaction can be
+///   included, if the transaction expires.
+/// - block_ref: the hash of the block the transaction was executed against.
+///
+/// All of the above can be computed both from an [ExecutedTransaction] and from the
+/// corresponding [ProvenTransaction], thus the hash of the summary can be used as the single
+/// message identifying a transaction when it is signed, displayed, or referenced by other
+/// systems. In addition to the data the [TransactionId](super::TransactionId) commits to, the
+/// summary hash also commits to the account and the reference block of the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionSummary {
+    account_id: AccountId,
+    account_update_commitment: Digest,
+    input_notes_commitment: Digest,
+    output_notes_commitment: Digest,
+    expiration_block_num: Option<u32>,
+    block_ref: Digest,
+    hash: Digest,
+}
+
+impl TransactionSummary {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [TransactionSummary] instantiated from the provided parameters.
+    pub fn new(
+        account_id: AccountId,
+        initial_account_hash: Digest,
+        final_account_hash: Digest,
+        input_notes_commitment: Digest,
+        output_notes_commitment: Digest,
+        expiration_block_num: Option<u32>,
+        block_ref: Digest,
+    ) -> Self {
+        let account_update_commitment = Hasher::merge(&[initial_account_hash, final_account_hash]);
+        let hash = compute_summary_hash(
+            account_id,
+            account_update_commitment,
+            input_notes_commitment,
+            output_notes_commitment,
+            expiration_block_num,
+            block_ref,
+        );
+
+        Self {
+            account_id,
+            account_update_commitment,
+            input_notes_commitment,
+            output_notes_commitment,
+            expiration_block_num,
+            block_ref,
+            hash,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the canonical hash of this summary.
+    pub fn hash(&self) -> Digest {
+        self.hash
+    }
+
+    /// Returns the ID of the account the transaction was executed against.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the commitment to the transition of the account state.
+    pub fn account_update_commitment(&self) -> Digest {
+        self.account_update_commitment
+    }
+
+    /// Returns the commitment to the nullifiers of the notes consumed by the transaction.
+    pub fn input_notes_commitment(&self) -> Digest {
+        self.input_notes_commitment
+    }
+
+    /// Returns the commitment to the notes created by the transaction.
+    pub fn output_notes_commitment(&self) -> Digest {
+        self.output_notes_commitment
+    }
+
+    /// Returns the number of the last block into which the transaction can be included, or None
+    /// if the transaction never expires.
+    pub fn expiration_block_num(&self) -> Option<u32> {
+        self.expiration_block_num
+    }
+
+    /// Returns the hash of the block the transaction was executed against.
+    pub fn block_ref(&self) -> Digest {
+        self.block_ref
+    }
+}
+
+// CONVERSIONS INTO TRANSACTION SUMMARY
+// ================================================================================================
+
+impl From<&ExecutedTransaction> for TransactionSummary {
+    fn from(tx: &ExecutedTransaction) -> Self {
+        Self::new(
+            tx.account_id(),
+            tx.initial_account().hash(),
+            tx.final_account().hash(),
+            tx.input_notes().commitment(),
+            tx.output_notes().commitment(),
+            tx.expiration_block_num(),
+            tx.block_header().hash(),
+        )
+    }
+}
+
+impl From<&ProvenTransaction> for TransactionSummary {
+    fn from(tx: &ProvenTransaction) -> Self {
+        Self::new(
+            tx.account_id(),
+            tx.initial_account_hash(),
+            tx.final_account_hash(),
+            tx.input_notes().commitment(),
+            tx.output_notes().commitment(),
+            tx.expiration_block_num(),
+            tx.block_ref(),
+        )
+    }
+}
+
+impl From<TransactionSummary> for Digest {
+    fn from(summary: TransactionSummary) -> Self {
+        summary.hash
+    }
+}
+
+impl From<TransactionSummary> for Word {
+    fn from(summary: TransactionSummary) -> Self {
+        summary.hash.into()
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for TransactionSummary {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account_id.write_into(target);
+        self.account_update_commitment.write_into(target);
+        self.input_notes_commitment.write_into(target);
+        self.output_notes_commitment.write_into(target);
+        self.expiration_block_num.write_into(target);
+        self.block_ref.write_into(target);
+    }
+}
+
+impl Deserializable for TransactionSummary {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account_id = AccountId::read_from(source)?;
+        let account_update_commitment = Digest::read_from(source)?;
+        let input_notes_commitment = Digest::read_from(source)?;
+        let output_notes_commitment = Digest::read_from(source)?;
+        let expiration_block_num = <Option<u32>>::read_from(source)?;
+        let block_ref = Digest::read_from(source)?;
+
+        let hash = compute_summary_hash(
+            account_id,
+            account_update_commitment,
+            input_notes_commitment,
+            output_notes_commitment,
+            expiration_block_num,
+            block_ref,
+        );
+
+        Ok(Self {
+            account_id,
+            account_update_commitment,
+            input_notes_commitment,
+            output_notes_commitment,
+            expiration_block_num,
+            block_ref,
+            hash,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes the hash of a transaction summary as:
+///
+/// hash(account_update_commitment, input_notes_commitment, output_notes_commitment, block_ref,
+///      [account_id, has_expiration, expiration_block_num, 0])
+fn compute_summary_hash(
+    account_id: AccountId,
+    account_update_commitment: Digest,
+    input_notes_commitment: Digest,
+    output_notes_commitment: Digest,
+    expiration_block_num: Option<u32>,
+    block_ref: Digest,
+) -> Digest {
+    let mut elements = [ZERO; 5 * WORD_SIZE];
+    elements[..4].copy_from_slice(account_update_commitment.as_elements());
+    elements[4..8].copy_from_slice(input_notes_commitment.as_elements());
+    elements[8..12].copy_from_slice(output_notes_commitment.as_elements());
+    elements[12..16].copy_from_slice(block_ref.as_elements());
+    elements[16] = account_id.into();
+    elements[17] = Felt::from(expiration_block_num.is_some() as u32);
+    elements[18] = Felt::from(expiration_block_num.unwrap_or_default());
+    Hasher::hash_elements(&elements)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::TransactionSummary;
+    use crate::{
+        accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN},
+        utils::serde::{Deserializable, Serializable},
+        Digest, Felt,
+    };
+
+    #[test]
+    fn summary_hash_commits_to_expiration() {
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let digest = |value: u64| Digest::from([Felt::new(value); 4]);
+        let summary = |expiration_block_num| {
+            TransactionSummary::new(
+                account_id,
+                digest(1),
+                digest(2),
+                digest(3),
+                digest(4),
+                expiration_block_num,
+                digest(5),
+            )
+        };
+
+        assert_ne!(summary(None).hash(), summary(Some(0)).hash());
+        assert_ne!(summary(Some(0)).hash(), summary(Some(1)).hash());
+
+        let deserialized = TransactionSummary::read_from_bytes(&summary(Some(7)).to_bytes());
+        assert_eq!(deserialized.unwrap(), summary(Some(7)));
+    }
+}
@@ -12,6 +12,10 @@ use crate::utils::serde::{
 ///
 /// hash(init_account_hash, final_account_hash, input_notes_hash, output_notes_hash)
 ///
+/// For transactions which expire, the above digest is further merged with
+/// `[expiration_block_num, 0, 0, 0]`, so that the expiration of a transaction cannot be altered
+/// without changing its ID.
+///
 /// This achieves the following properties:
 /// - Transactions are identical if and only if they have the same ID.
 /// - Computing transaction ID can be done solely from public transaction data.
@@ -34,6 +38,13 @@ impl TransactionId {
         Self(Hasher::hash_elements(&elements))
     }
 
+    /// Returns the ID of the transaction with the components of this ID which expires after the
+    /// block with the specified number.
+    pub fn with_expiration_block_num(self, expiration_block_num: u32) -> Self {
+        let expiration: Word = [Felt::from(expiration_block_num), ZERO, ZERO, ZERO];
+        Self(Hasher::merge(&[self.0, expiration.into()]))
+    }
+
     /// Returns the elements representation of this transaction ID.
     pub fn as_elements(&self) -> &[Felt] {
         self.0.as_elements()
@@ -55,12 +66,16 @@ impl TransactionId {
 
 impl From<&ProvenTransaction> for TransactionId {
     fn from(tx: &ProvenTransaction) -> Self {
-        Self::new(
+        let id = Self::new(
             tx.initial_account_hash(),
             tx.final_account_hash(),
             tx.input_notes().commitment(),
             tx.output_notes().commitment(),
-        )
+        );
+        match tx.expiration_block_num() {
+            Some(expiration_block_num) => id.with_expiration_block_num(expiration_block_num),
+            None => id,
+        }
     }
 }
 
@@ -68,12 +83,16 @@ impl From<&ExecutedTransaction> for TransactionId {
     fn from(tx: &ExecutedTransaction) -> Self {
         let input_notes_hash = tx.input_notes().commitment();
         let output_notes_hash = tx.output_notes().commitment();
-        Self::new(
+        let id = Self::new(
             tx.initial_account().hash(),
             tx.final_account().hash(),
             input_notes_hash,
             output_notes_hash,
-        )
+        );
+        match tx.expiration_block_num() {
+            Some(expiration_block_num) => id.with_expiration_block_num(expiration_block_num),
+            None => id,
+        }
     }
 }
 
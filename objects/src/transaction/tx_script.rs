@@ -1,11 +1,22 @@
+use assembly::ast::AstSerdeOptions;
+
 use super::{Digest, Felt, Word};
 use crate::{
     assembly::{Assembler, AssemblyContext, ProgramAst},
-    utils::collections::{BTreeMap, Vec},
+    utils::{
+        collections::{BTreeMap, Vec},
+        serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    },
     vm::CodeBlock,
     TransactionScriptError,
 };
 
+// CONSTANTS
+// ================================================================================================
+
+/// Default serialization options for script code AST.
+const CODE_SERDE_OPTIONS: AstSerdeOptions = AstSerdeOptions::new(true);
+
 // TRANSACTION SCRIPT
 // ================================================================================================
 
@@ -87,3 +98,36 @@ impl TransactionScript {
         &self.inputs
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for TransactionScript {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.hash.write_into(target);
+        self.code.write_into(target, CODE_SERDE_OPTIONS);
+        target.write_u64(self.inputs.len() as u64);
+        for (key, values) in self.inputs.iter() {
+            key.write_into(target);
+            target.write_u64(values.len() as u64);
+            values.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for TransactionScript {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let hash = Digest::read_from(source)?;
+        let code = ProgramAst::read_from(source)?;
+        let num_inputs = source.read_u64()?;
+        let mut inputs = BTreeMap::new();
+        for _ in 0..num_inputs {
+            let key = Digest::read_from(source)?;
+            let num_values = source.read_u64()? as usize;
+            let values = Felt::read_batch_from(source, num_values)?;
+            inputs.insert(key, values);
+        }
+
+        Ok(Self { code, hash, inputs })
+    }
+}
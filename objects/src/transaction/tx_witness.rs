@@ -1,5 +1,14 @@
 use super::{
-    Account, AdviceInputs, BlockHeader, InputNotes, Program, TransactionInputs, TransactionScript,
+    Account, AdviceInputs, BlockHeader, Digest, Felt, InputNotes, TransactionInputs,
+    TransactionScript, Word,
+};
+use crate::{
+    crypto::merkle::InnerNodeInfo,
+    utils::{
+        collections::Vec,
+        serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+        string::ToString,
+    },
 };
 
 // TRANSACTION WITNESS
@@ -12,20 +21,25 @@ use super::{
 /// of transactions.
 ///
 /// A transaction witness consists of:
-/// - The executable transaction [Program].
+/// - The hash of the main code block of the transaction kernel the transaction was executed with.
 /// - Transaction inputs which contain information about the initial state of the account, input
 ///   notes, block header etc.
-/// - An optional transaction script.
+/// - An optional transaction script and the arguments it was executed with.
 /// - Advice witness which contains all data requested by the VM from the advice provider while
 ///   executing the transaction program.
 ///
+/// The executable transaction program is not a part of the witness, as it is fully determined by
+/// the transaction kernel, the input notes and the transaction script; the program is compiled
+/// from the witness by whoever re-executes or proves the transaction.
+///
 /// TODO: currently, the advice witness contains redundant and irrelevant data (e.g., tx inputs
 /// and tx outputs). we should optimize it to contain only the minimum data required for
 /// executing/proving the transaction.
 pub struct TransactionWitness {
-    program: Program,
+    kernel_hash: Digest,
     tx_inputs: TransactionInputs,
     tx_script: Option<TransactionScript>,
+    tx_script_args: Word,
     advice_witness: AdviceInputs,
 }
 
@@ -34,15 +48,17 @@ impl TransactionWitness {
     // --------------------------------------------------------------------------------------------
     /// Creates a new [TransactionWitness] from the provided data.
     pub fn new(
-        program: Program,
+        kernel_hash: Digest,
         tx_inputs: TransactionInputs,
         tx_script: Option<TransactionScript>,
+        tx_script_args: Word,
         advice_witness: AdviceInputs,
     ) -> Self {
         Self {
-            program,
+            kernel_hash,
             tx_inputs,
             tx_script,
+            tx_script_args,
             advice_witness,
         }
     }
@@ -50,9 +66,10 @@ impl TransactionWitness {
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a reference the program defining this transaction.
-    pub fn program(&self) -> &Program {
-        &self.program
+    /// Returns the hash of the main code block of the transaction kernel the transaction was
+    /// executed with, i.e., the hash of the transaction program.
+    pub fn kernel_hash(&self) -> Digest {
+        self.kernel_hash
     }
 
     /// Returns the account state before the transaction was executed.
@@ -75,6 +92,11 @@ impl TransactionWitness {
         self.tx_script.as_ref()
     }
 
+    /// Returns the arguments the transaction script was executed with.
+    pub fn tx_script_args(&self) -> Word {
+        self.tx_script_args
+    }
+
     /// Returns a reference to the inputs for this transaction.
     pub fn tx_inputs(&self) -> &TransactionInputs {
         &self.tx_inputs
@@ -86,3 +108,81 @@ impl TransactionWitness {
         &self.advice_witness
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for TransactionWitness {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.kernel_hash.write_into(target);
+        self.tx_inputs.write_into(target);
+        self.tx_script.write_into(target);
+        self.tx_script_args.write_into(target);
+        write_advice_inputs(&self.advice_witness, target);
+    }
+}
+
+impl Deserializable for TransactionWitness {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let kernel_hash = Digest::read_from(source)?;
+        let tx_inputs = TransactionInputs::read_from(source)?;
+        let tx_script = Option::<TransactionScript>::read_from(source)?;
+        let tx_script_args = Word::read_from(source)?;
+        let advice_witness = read_advice_inputs(source)?;
+
+        Ok(Self::new(kernel_hash, tx_inputs, tx_script, tx_script_args, advice_witness))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the canonical encoding of the specified advice inputs into `target`.
+///
+/// The advice stack, the advice map, and the inner nodes of the Merkle store are written in this
+/// order, each prefixed by its length.
+pub fn write_advice_inputs<W: ByteWriter>(advice_inputs: &AdviceInputs, target: &mut W) {
+    let (stack, map, store) = advice_inputs.clone().into_parts();
+    target.write_u64(stack.len() as u64);
+    stack.write_into(target);
+    target.write_u64(map.len() as u64);
+    for (key, values) in map.iter() {
+        target.write_bytes(key);
+        target.write_u64(values.len() as u64);
+        values.write_into(target);
+    }
+    let nodes = store.inner_nodes().collect::<Vec<_>>();
+    target.write_u64(nodes.len() as u64);
+    for node in nodes {
+        node.value.write_into(target);
+        node.left.write_into(target);
+        node.right.write_into(target);
+    }
+}
+
+/// Reads advice inputs written via [write_advice_inputs()] from `source`.
+pub fn read_advice_inputs<R: ByteReader>(
+    source: &mut R,
+) -> Result<AdviceInputs, DeserializationError> {
+    let stack_len = source.read_u64()? as usize;
+    let stack = Felt::read_batch_from(source, stack_len)?;
+    let num_entries = source.read_u64()? as usize;
+    let mut map_entries = Vec::with_capacity(num_entries);
+    for _ in 0..num_entries {
+        let key: [u8; 32] = source.read_array()?;
+        let num_values = source.read_u64()? as usize;
+        map_entries.push((key, Felt::read_batch_from(source, num_values)?));
+    }
+    let num_nodes = source.read_u64()? as usize;
+    let mut nodes = Vec::with_capacity(num_nodes);
+    for _ in 0..num_nodes {
+        let value = Digest::read_from(source)?;
+        let left = Digest::read_from(source)?;
+        let right = Digest::read_from(source)?;
+        nodes.push(InnerNodeInfo { value, left, right });
+    }
+    let mut advice_inputs = AdviceInputs::default().with_stack(stack).with_map(map_entries);
+    advice_inputs.extend_merkle_store(nodes);
+
+    Ok(advice_inputs)
+}
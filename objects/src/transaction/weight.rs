@@ -0,0 +1,134 @@
+use crate::block::{
+    MAX_ACCOUNT_UPDATES_SIZE_PER_BLOCK, MAX_BLOCK_SIZE, MAX_INPUT_NOTES_PER_BLOCK,
+    MAX_OUTPUT_NOTES_PER_BLOCK,
+};
+
+// TRANSACTION WEIGHT
+// ================================================================================================
+
+/// The amount of block resources consumed by a transaction.
+///
+/// A block can include a set of transactions only if their combined weight does not exceed the
+/// weight returned by [TransactionWeight::block_limit()] in any of the dimensions. Since the
+/// weight of a transaction is fully determined by its contents, mempools and batch producers can
+/// use it to pack blocks deterministically.
+///
+/// The weight consists of:
+/// - size: the size of the serialized transaction (including the proof) in bytes.
+/// - num_input_notes: the number of notes consumed by the transaction.
+/// - num_output_notes: the number of notes created by the transaction.
+/// - account_update_size: the size of the account update recorded in the block for the
+///   transaction in bytes, i.e., the size of the final account hash and of the account details
+///   (if any).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionWeight {
+    size: usize,
+    num_input_notes: usize,
+    num_output_notes: usize,
+    account_update_size: usize,
+}
+
+impl TransactionWeight {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [TransactionWeight] instantiated from the provided values.
+    pub fn new(
+        size: usize,
+        num_input_notes: usize,
+        num_output_notes: usize,
+        account_update_size: usize,
+    ) -> Self {
+        Self {
+            size,
+            num_input_notes,
+            num_output_notes,
+            account_update_size,
+        }
+    }
+
+    /// Returns the maximum weight of all transactions included in a single block.
+    pub fn block_limit() -> Self {
+        Self::new(
+            MAX_BLOCK_SIZE,
+            MAX_INPUT_NOTES_PER_BLOCK,
+            MAX_OUTPUT_NOTES_PER_BLOCK,
+            MAX_ACCOUNT_UPDATES_SIZE_PER_BLOCK,
+        )
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the size of the serialized transaction in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of notes consumed by the transaction.
+    pub fn num_input_notes(&self) -> usize {
+        self.num_input_notes
+    }
+
+    /// Returns the number of notes created by the transaction.
+    pub fn num_output_notes(&self) -> usize {
+        self.num_output_notes
+    }
+
+    /// Returns the size of the account update of the transaction in bytes.
+    pub fn account_update_size(&self) -> usize {
+        self.account_update_size
+    }
+
+    /// Returns true if this weight does not exceed the specified limit in any of the dimensions.
+    pub fn fits_within(&self, limit: &Self) -> bool {
+        self.size <= limit.size
+            && self.num_input_notes <= limit.num_input_notes
+            && self.num_output_notes <= limit.num_output_notes
+            && self.account_update_size <= limit.account_update_size
+    }
+
+    /// Returns true if this weight does not exceed the block limit.
+    pub fn fits_in_block(&self) -> bool {
+        self.fits_within(&Self::block_limit())
+    }
+
+    // ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the combined weight of this and the other weight.
+    ///
+    /// The computation saturates at [usize::MAX] in each of the dimensions.
+    pub fn combine(&self, other: &Self) -> Self {
+        Self {
+            size: self.size.saturating_add(other.size),
+            num_input_notes: self.num_input_notes.saturating_add(other.num_input_notes),
+            num_output_notes: self.num_output_notes.saturating_add(other.num_output_notes),
+            account_update_size: self.account_update_size.saturating_add(other.account_update_size),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::TransactionWeight;
+    use crate::block::MAX_INPUT_NOTES_PER_BLOCK;
+
+    #[test]
+    fn combined_weight_fits_within_limit() {
+        let weight = TransactionWeight::new(1000, 2, 3, 32);
+        let combined = weight.combine(&weight);
+        assert_eq!(combined, TransactionWeight::new(2000, 4, 6, 64));
+
+        assert!(combined.fits_within(&TransactionWeight::new(2000, 4, 6, 64)));
+        assert!(!combined.fits_within(&TransactionWeight::new(2000, 3, 6, 64)));
+        assert!(combined.fits_in_block());
+
+        let heavy = TransactionWeight::new(0, MAX_INPUT_NOTES_PER_BLOCK, 0, 0);
+        assert!(heavy.fits_in_block());
+        assert!(!heavy.combine(&weight).fits_in_block());
+    }
+}